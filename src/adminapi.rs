@@ -0,0 +1,198 @@
+//! サービスの状態の確認と操作を、ローカルのJSON APIとして提供するモジュール。
+//!
+//! `--admin-api-port <PORT>` で有効にすると、サービスは `http://127.0.0.1:<PORT>/api/` で待ち受けます。
+//! ダッシュボードやスクリプトから、CLIを実行したりレジストリを読んだりせずに連携できます。
+//! すべてのリクエストには、設定したときに表示されるトークンを `Authorization: Bearer <トークン>` で付けます。
+//!
+//! * `GET /api/accounts` - アカウントの一覧と、直近の通知の結果（パスワードは含まない）。
+//! * `GET /api/status` - サービスの開始時刻、一時停止中かどうか、次の定期通知の予定時刻など。
+//! * `GET /api/history?account=<ID>&count=<N>` - 通知の試行の履歴（既定は新しい50件）。
+//! * `POST /api/notify` - 定期通知を待たずに、すぐに通知するよう要求する。
+//!
+//! 状態の問い合わせと通知の要求は、名前付きパイプと同じ `Request` としてサービスのメインループに渡します。
+
+use crate::history::load_recent;
+use crate::ipc::Request;
+use crate::localhttp::{HttpRequest, HttpResponse, LocalHttpServer};
+use crate::state::load_state;
+use crate::store::load_all_configs;
+use crate::transfer::quote;
+use std::io;
+use windows::Win32::Security::Cryptography::{
+    BCRYPT_ALG_HANDLE, BCRYPT_USE_SYSTEM_PREFERRED_RNG, BCryptGenRandom,
+};
+
+/// APIのパスの接頭辞。
+const API_PREFIX: &str = "/api/";
+/// 履歴の件数が指定されなかった場合に返す件数。
+const DEFAULT_HISTORY_COUNT: usize = 50;
+/// トークンの長さ（バイト）。16進数の文字列にすると2倍の長さになる。
+const TOKEN_BYTES: usize = 32;
+
+/// 新しいトークンを、OSの暗号論的な乱数から作成します。
+pub fn generate_token() -> windows::core::Result<String> {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // 書き込み先のバッファは、呼び出しの間有効なスライスとして渡すため安全です。
+    unsafe {
+        BCryptGenRandom(
+            BCRYPT_ALG_HANDLE::default(),
+            &mut bytes,
+            BCRYPT_USE_SYSTEM_PREFERRED_RNG,
+        )
+        .ok()?;
+    }
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// ループバックアドレスの指定されたポートで、管理APIの待ち受けを開始します。
+///
+/// `token` と一致するトークンを付けたリクエストだけを受け付けます。
+/// `send_request` は、サービスのメインループに要求を渡して結果を待つ関数です。
+/// 返されたサーバーを破棄すると、待ち受けを終了します。
+pub fn start_admin_api<F>(port: u16, token: String, send_request: F) -> io::Result<LocalHttpServer>
+where
+    F: Fn(Request) -> Result<String, String> + Send + 'static,
+{
+    LocalHttpServer::start(port, move |request| {
+        if !is_authorized(request, &token) {
+            return json_response("401 Unauthorized", error_body("unauthorized"));
+        }
+        let Some(endpoint) = request.path.strip_prefix(API_PREFIX) else {
+            return json_response("404 Not Found", error_body("not found"));
+        };
+        match (request.method.as_str(), endpoint) {
+            ("GET", "accounts") => accounts(),
+            ("GET", "status") => match send_request(Request::QueryStatus) {
+                Ok(text) => json_response("200 OK", status_body(&text)),
+                Err(e) => json_response("503 Service Unavailable", error_body(&e)),
+            },
+            ("GET", "history") => history(request),
+            ("POST", "notify") => match send_request(Request::NotifyNow) {
+                Ok(_) => json_response("202 Accepted", "{\"accepted\": true}".to_string()),
+                Err(e) => json_response("503 Service Unavailable", error_body(&e)),
+            },
+            (_, "accounts" | "status" | "history" | "notify") => {
+                json_response("405 Method Not Allowed", error_body("method not allowed"))
+            }
+            _ => json_response("404 Not Found", error_body("not found")),
+        }
+    })
+}
+
+/// リクエストに正しいトークンが付いているかどうかを判定します。
+///
+/// 一致しない位置によって比較の時間が変わらないよう、すべてのバイトを比較します。
+fn is_authorized(request: &HttpRequest, token: &str) -> bool {
+    let Some(given) = request
+        .header("authorization")
+        .and_then(|v| v.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    let (given, token) = (given.trim().as_bytes(), token.as_bytes());
+    !token.is_empty()
+        && given.len() == token.len()
+        && given.iter().zip(token).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// `GET /api/accounts` の応答を作ります。
+fn accounts() -> HttpResponse {
+    let configs = match load_all_configs() {
+        Ok(configs) => configs,
+        Err(e) => {
+            return json_response("500 Internal Server Error", error_body(&e.to_string()));
+        }
+    };
+    let state = load_state().unwrap_or_default();
+    let optional = |value: Option<String>| value.as_deref().map_or("null".to_string(), quote);
+    let items: Vec<String> = configs
+        .iter()
+        .map(|config| {
+            let account = state.accounts.get(&config.master_id);
+            let last_ip: Vec<String> = account
+                .map(|a| a.last_ip.iter().map(|ip| quote(&ip.to_string())).collect())
+                .unwrap_or_default();
+            format!(
+                "{{\"master_id\": {}, \"provider\": {}, \"ipv4_notify\": {}, \"ipv6_notify\": {}, \"hostname\": {}, \"last_success\": {}, \"last_ip\": [{}], \"last_result\": {}}}",
+                quote(&config.master_id),
+                quote(config.provider.code()),
+                config.ipv4_notify,
+                config.ipv6_notify,
+                quote(&config.hostname),
+                optional(account.and_then(|a| a.last_success).map(|t| t.to_rfc3339())),
+                last_ip.join(", "),
+                optional(account.and_then(|a| a.last_result.clone()))
+            )
+        })
+        .collect();
+    json_response(
+        "200 OK",
+        format!("{{\"accounts\": [{}]}}", items.join(", ")),
+    )
+}
+
+/// `GET /api/history` の応答を作ります。
+fn history(request: &HttpRequest) -> HttpResponse {
+    let account = request.query_param("account");
+    let count = match request.query_param("count") {
+        None => DEFAULT_HISTORY_COUNT,
+        Some(count) => match count.parse() {
+            Ok(count) => count,
+            Err(_) => return json_response("400 Bad Request", error_body("invalid count")),
+        },
+    };
+    let entries = match load_recent(account.as_deref(), count) {
+        Ok(entries) => entries,
+        Err(e) => {
+            return json_response("500 Internal Server Error", error_body(&e.to_string()));
+        }
+    };
+    let items: Vec<String> = entries
+        .iter()
+        .map(|entry| {
+            format!(
+                "{{\"time\": {}, \"master_id\": {}, \"protocol\": {}, \"result\": {}, \"latency_ms\": {}}}",
+                quote(&entry.time.to_rfc3339()),
+                quote(&entry.master_id),
+                quote(&entry.protocol),
+                quote(&entry.result),
+                entry.latency.as_millis()
+            )
+        })
+        .collect();
+    json_response("200 OK", format!("{{\"history\": [{}]}}", items.join(", ")))
+}
+
+/// `service query-status` と同じ `key=value` 形式の応答を、JSONのオブジェクトに変換します。
+///
+/// 真偽値と数値はそのまま、それ以外は文字列として出力します。
+fn status_body(text: &str) -> String {
+    let fields: Vec<String> = text
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = if value == "true" || value == "false" || value.parse::<u64>().is_ok() {
+                value.to_string()
+            } else {
+                quote(value)
+            };
+            format!("{}: {}", quote(key), value)
+        })
+        .collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+/// エラーの内容を表すJSONを作ります。
+fn error_body(message: &str) -> String {
+    format!("{{\"error\": {}}}", quote(message))
+}
+
+/// JSONの応答を作ります。
+fn json_response(status: &'static str, body: String) -> HttpResponse {
+    HttpResponse {
+        status,
+        content_type: "application/json; charset=utf-8",
+        body,
+    }
+}
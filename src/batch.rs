@@ -0,0 +1,194 @@
+//! CSVファイルから、複数のアカウントをまとめて追加するためのモジュール。
+//!
+//! `account add --batch <FILE>` で、1行に1アカウントを `MasterID,パスワード,IPv4,IPv6` の形式で
+//! 記述したCSVファイルを読み込みます。`<FILE>` に `-` を指定すると、標準入力から読み込みます。
+//! IPv4とIPv6の列は省略でき、省略した場合は通知します。値には `yes` / `no`、`true` / `false`、
+//! `1` / `0` などを使えます。空行と `#` で始まる行は無視し、1行目が見出し（`master_id,...` や `id,...`）であれば読み飛ばします。
+//!
+//! すべての行を検証し、問題のある行があれば行番号とともにすべて報告して、どのアカウントも追加しません。
+//! 問題がなければ、すべてのアカウントを追加します。
+//!
+//! 例:
+//! ```text
+//! master_id,password,ipv4,ipv6
+//! mydns123456,secret,yes,no
+//! mydns654321,"pass,word",yes,yes
+//! ```
+
+use crate::error::AdapterError;
+use crate::i18n::{MessageKey, get_msg};
+use crate::logging::{log_error, log_info};
+use crate::registry::{Config, DEFAULT_KEEP_ALIVE_HOURS};
+use crate::store::{load_all_configs, save_config};
+use crate::{format_msg, outln};
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// CSVファイルを読み込んで検証し、すべての行のアカウントを追加します。
+///
+/// 検証に失敗した行があれば、行ごとのエラーを標準エラー出力に表示し、レジストリを変更せずにエラーを返します。
+pub fn add_batch_mode(path: &Path) -> Result<(), AdapterError> {
+    outln!("{}", get_msg(MessageKey::AddTitle));
+
+    let text = if path == Path::new("-") {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        text
+    } else {
+        fs::read_to_string(path)?
+    };
+    let existing = load_all_configs().unwrap_or_default();
+
+    let (configs, errors) = parse_batch(&text, &existing);
+    if !errors.is_empty() {
+        for (line, error) in &errors {
+            eprintln!(
+                "{}",
+                format_msg!(MessageKey::BatchRowErrorFmt, line = line, error = error)
+            );
+        }
+        return Err(format_msg!(MessageKey::BatchInvalidFmt, count = errors.len()).into());
+    }
+    if configs.is_empty() {
+        outln!("{}", get_msg(MessageKey::BatchEmpty));
+        return Ok(());
+    }
+
+    // 検証済みのすべてのアカウントを、まとめて保存する。
+    for config in &configs {
+        if let Err(e) = save_config(config) {
+            log_error(&format!(
+                "Failed to add account {}: {}",
+                config.master_id, e
+            ));
+            return Err(format_msg!(MessageKey::RegistrySaveFailFmt, error = e).into());
+        }
+        log_info(&format!("Account added: {}", config.master_id));
+    }
+    outln!(
+        "{}",
+        format_msg!(MessageKey::BatchSuccessFmt, count = configs.len())
+    );
+    Ok(())
+}
+
+/// CSVの内容を検証し、追加するアカウント設定と、行番号ごとのエラーを返します。
+///
+/// `existing` は現在の設定です。既に存在するアカウントは、誤って上書きしないようエラーとします。
+fn parse_batch(text: &str, existing: &[Config]) -> (Vec<Config>, Vec<(usize, String)>) {
+    let mut configs: Vec<Config> = Vec::new();
+    let mut errors = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        // 先頭の行だけは、BOMが付いていることがある。
+        let line = line.trim_start_matches('\u{FEFF}').trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields = match split_csv_line(line) {
+            Ok(fields) => fields,
+            Err(e) => {
+                errors.push((line_number, e));
+                continue;
+            }
+        };
+        let first = fields[0].trim();
+        if configs.is_empty()
+            && errors.is_empty()
+            && (first.eq_ignore_ascii_case("master_id") || first.eq_ignore_ascii_case("id"))
+        {
+            continue;
+        }
+        match config_from_row(&fields, existing) {
+            Ok(config) if configs.iter().any(|c| c.master_id == config.master_id) => errors.push((
+                line_number,
+                format_msg!(MessageKey::ImportDuplicateFmt, id = config.master_id),
+            )),
+            Ok(config) => configs.push(config),
+            Err(e) => errors.push((line_number, e)),
+        }
+    }
+    (configs, errors)
+}
+
+/// CSVの1行の列から、アカウント設定を組み立てて検証します。
+fn config_from_row(fields: &[String], existing: &[Config]) -> Result<Config, String> {
+    if !(2..=4).contains(&fields.len()) {
+        return Err(format_msg!(
+            MessageKey::BatchColumnCountFmt,
+            count = fields.len()
+        ));
+    }
+    let master_id = fields[0].trim().to_string();
+    if !master_id.starts_with("mydns") {
+        return Err(format!(
+            "{}: {}",
+            master_id,
+            get_msg(MessageKey::InvalidMasterIdPrefix)
+        ));
+    }
+    if existing.iter().any(|c| c.master_id == master_id) {
+        return Err(format_msg!(MessageKey::AccountExistsFmt, id = master_id));
+    }
+    let password = fields[1].clone();
+    if password.is_empty() {
+        return Err(format_msg!(
+            MessageKey::ImportMissingPasswordFmt,
+            id = master_id
+        ));
+    }
+    let flag = |index: usize, name: &str| match fields.get(index).map(|s| s.trim()) {
+        None | Some("") => Ok(true),
+        Some(value) => parse_flag(value).ok_or_else(|| {
+            format_msg!(MessageKey::BatchInvalidFlagFmt, name = name, value = value)
+        }),
+    };
+    Ok(Config {
+        ipv4_notify: flag(2, "IPv4")?,
+        ipv6_notify: flag(3, "IPv6")?,
+        keep_alive_hours: DEFAULT_KEEP_ALIVE_HOURS,
+        master_id,
+        password,
+        ..Config::default()
+    })
+}
+
+/// 通知の有無を表す値を解釈します。解釈できない場合は `None` を返します。
+fn parse_flag(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "yes" | "y" | "true" | "1" | "on" => Some(true),
+        "no" | "n" | "false" | "0" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// CSVの1行を列に分割します。
+///
+/// 列は `"` で囲むことができ、囲んだ列には `,` を含められます。`"` 自体は `""` と書きます。
+fn split_csv_line(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(get_msg(MessageKey::BatchUnterminatedQuote).to_string());
+    }
+    fields.push(field);
+    Ok(fields)
+}
@@ -0,0 +1,134 @@
+//! ログ記録とレジストリ操作の性能を計測するベンチマークモードを提供するモジュール。
+//!
+//! 隠しオプション `--bench` で実行され、現在のマシン上での次の値を計測して表示します。
+//! - ログの書き込みスループット（ローテーションが発生しない状態）
+//! - ローテーションが発生する状態での、1行あたりの書き込みコスト
+//! - 設定の読み込み・保存にかかる時間
+//!
+//! ログの計測は一時ディレクトリのファイルに対して行い、実際のログファイルには影響しません。
+//! 設定の保存の計測も、現在の保存先と同じ種類の一時的な保存先（`HKEY_CURRENT_USER` の一時的なキーか、
+//! 一時ディレクトリの設定ファイル）に対して行い、実際のアカウントや稼働中のサービスには影響しません。
+
+use crate::filestore::FileStore;
+use crate::format_msg;
+use crate::i18n::{MessageKey, get_msg};
+use crate::logging::{MAX_LOG_SIZE, log_files_for, log_lock_path, write_log_line};
+use crate::registry::{Config, ScratchRegistryKey};
+use crate::store::{ConfigStore, config_file, load_all_configs};
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::time::{Duration, Instant};
+
+/// ログ書き込みの計測で書き込む行数。
+const LOG_WRITE_ITERATIONS: u32 = 200;
+/// ローテーションの計測で書き込む行数。
+const ROTATION_ITERATIONS: u32 = 50;
+/// 設定の読み込み・保存の計測回数。
+const CONFIG_ITERATIONS: u32 = 20;
+/// 設定の保存の計測に使う一時的なMasterID。
+const BENCH_MASTER_ID: &str = "mydnsbench";
+
+/// ベンチマークモードを実行し、結果を表示します。
+pub fn bench_mode() -> io::Result<()> {
+    println!("{}", get_msg(MessageKey::BenchTitle));
+
+    let log_path = env::temp_dir().join("mydns-bench.log");
+    let sample = "[mydns000000] Notified https://ipv4.mydns.jp/login.html: Status 200 OK";
+
+    // 1. 空のファイルから書き込み、ローテーションが発生しない状態のスループットを計測する。
+    let _ = fs::remove_file(&log_path);
+    let elapsed = measure(LOG_WRITE_ITERATIONS, || {
+        write_log_line(&log_path, "INFO", sample)
+    })?;
+    print_result(MessageKey::BenchLogWrite, LOG_WRITE_ITERATIONS, elapsed);
+
+    // 2. 上限サイズまで埋めたファイルに書き込み、毎回ローテーションが発生する状態のコストを計測する。
+    //    ファイルを埋め直す時間は計測に含めない。
+    let mut elapsed = Duration::ZERO;
+    for _ in 0..ROTATION_ITERATIONS {
+        let mut writer = BufWriter::new(File::create(&log_path)?);
+        let line = format!("[2000-01-01 00:00:00] [INFO] {}\n", sample);
+        for _ in 0..MAX_LOG_SIZE / line.len() as u64 {
+            writer.write_all(line.as_bytes())?;
+        }
+        writer.flush()?;
+        drop(writer);
+        elapsed += measure(1, || write_log_line(&log_path, "INFO", sample))?;
+    }
+    print_result(MessageKey::BenchLogRotate, ROTATION_ITERATIONS, elapsed);
+    for path in log_files_for(&log_path) {
+        let _ = fs::remove_file(path);
+    }
+    let _ = fs::remove_file(log_lock_path(&log_path));
+
+    // 3. 設定の読み込みにかかる時間を計測する。
+    let elapsed = measure(CONFIG_ITERATIONS, || {
+        load_all_configs().map(|_| ()).map_err(io::Error::other)
+    })?;
+    print_result(MessageKey::BenchConfigLoad, CONFIG_ITERATIONS, elapsed);
+
+    // 4. 設定の保存にかかる時間を、現在の保存先と同じ種類の一時的な保存先で計測する。
+    //    実際の保存先に書き込むと、同じMasterIDのアカウントを上書きしたり、稼働中のサービスに
+    //    設定を読み込み直させたりするため。一時的な保存先は、計測の後にまとめて削除する。
+    let config = Config {
+        master_id: BENCH_MASTER_ID.to_string(),
+        password: "bench-password".to_string(),
+        ..Config::default()
+    };
+    let result = if config_file().is_some() {
+        let store = FileStore::new(env::temp_dir().join("mydns-bench.toml"));
+        let result = measure_save(&store, &config);
+        let _ = store.delete_files();
+        result
+    } else {
+        ScratchRegistryKey::create()
+            .map_err(io::Error::from)
+            .and_then(|key| measure_save(&key.store(), &config))
+    };
+    match result {
+        Ok(elapsed) => print_result(MessageKey::BenchConfigSave, CONFIG_ITERATIONS, elapsed),
+        Err(e) => println!(
+            "{}",
+            format_msg!(
+                MessageKey::BenchSkippedFmt,
+                label = get_msg(MessageKey::BenchConfigSave),
+                error = e
+            )
+        ),
+    }
+
+    Ok(())
+}
+
+/// 設定の保存を指定回数繰り返し、合計の所要時間を返します。
+fn measure_save(store: &dyn ConfigStore, config: &Config) -> io::Result<Duration> {
+    measure(CONFIG_ITERATIONS, || {
+        store.save(config).map_err(io::Error::other)
+    })
+}
+
+/// 処理を指定回数繰り返し、合計の所要時間を返します。
+fn measure<F: FnMut() -> io::Result<()>>(iterations: u32, mut f: F) -> io::Result<Duration> {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f()?;
+    }
+    Ok(start.elapsed())
+}
+
+/// 計測結果を1行で表示します。
+fn print_result(label_key: MessageKey, iterations: u32, elapsed: Duration) {
+    let avg_ms = elapsed.as_secs_f64() * 1000.0 / f64::from(iterations);
+    let per_sec = f64::from(iterations) / elapsed.as_secs_f64().max(f64::EPSILON);
+    println!(
+        "{}",
+        format_msg!(
+            MessageKey::BenchResultFmt,
+            label = get_msg(label_key),
+            n = iterations,
+            avg = format!("{:.3}", avg_ms),
+            rate = format!("{:.1}", per_sec)
+        )
+    );
+}
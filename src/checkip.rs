@@ -0,0 +1,209 @@
+//! 現在のグローバルアドレスを、外部の確認サービスに問い合わせるモジュール。
+//!
+//! 汎用のサービスの更新URLに `{ip}` が含まれる場合に、通知するアドレスを求めるために使います。
+//! 確認サービスは、問い合わせに使った接続の接続元のアドレスを返すため、NATの内側でも外から見えるアドレスがわかります。
+//!
+//! 確認サービスはIPv4とIPv6のそれぞれについて、順に問い合わせるURLの並び（チェーン）で指定します。
+//! 既定のチェーンは、IPv4専用とIPv6専用のホスト名を持つ複数のサービスです。`--check-ipv4-urls` と
+//! `--check-ipv6-urls` で変更できます。チェーンの過半数のサービスが同じアドレスを返した時点で、そのアドレスに決めます。
+//! 応答しないサービスがあれば次のサービスに問い合わせ、過半数に届かなかった場合は、
+//! 最も多くのサービスが返したアドレスを使います。1つのサービスが誤ったアドレスを返しても、多数決で正しいアドレスが選ばれます。
+//!
+//! `--check-ip-poll` を設定すると、サービスは一定の間隔でグローバルアドレスを確認し、変わっていれば通知します。
+//! ルーターのWAN側のアドレスが変わっても、このマシンのインターフェイスのアドレスは変わらないため、
+//! NATの内側ではWindowsからの変更の通知だけでは気付けない変化を検出できます。
+
+use crate::format_msg_log;
+use crate::i18n::MessageKey;
+use crate::logging::{log_detail, log_info};
+use crate::notify::build_client;
+use crate::registry::{Settings, load_settings};
+use reqwest::Client;
+use std::cmp::Reverse;
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::Duration;
+use tokio::time;
+
+/// IPv4アドレスの確認サービスの既定のチェーン。IPv4でしか接続できないホスト名を使う。
+pub const DEFAULT_CHECK_IPV4_URLS: &[&str] = &[
+    "https://api.ipify.org",
+    "https://ipv4.icanhazip.com",
+    "https://v4.ident.me",
+];
+/// IPv6アドレスの確認サービスの既定のチェーン。IPv6でしか接続できないホスト名を使う。
+pub const DEFAULT_CHECK_IPV6_URLS: &[&str] = &[
+    "https://api6.ipify.org",
+    "https://ipv6.icanhazip.com",
+    "https://v6.ident.me",
+];
+/// インターネットから直接届かないIPv4アドレスの範囲。CGNATの共有アドレス空間とRFC1918のプライベートアドレス。
+const NON_PUBLIC_IPV4_RANGES: &[(Ipv4Addr, u32)] = &[
+    (Ipv4Addr::new(100, 64, 0, 0), 10),
+    (Ipv4Addr::new(10, 0, 0, 0), 8),
+    (Ipv4Addr::new(172, 16, 0, 0), 12),
+    (Ipv4Addr::new(192, 168, 0, 0), 16),
+];
+/// `--check-ip-poll` が設定されていない間に、設定を確認し直す間隔。
+const POLL_DISABLED_RECHECK: Duration = Duration::from_secs(60);
+
+/// 問い合わせる確認サービスのURLの並びを返します。設定されていない場合は、既定のチェーンを返します。
+pub fn check_urls(settings: &Settings, ipv6: bool) -> Vec<String> {
+    let (custom, default) = if ipv6 {
+        (&settings.check_ipv6_urls, DEFAULT_CHECK_IPV6_URLS)
+    } else {
+        (&settings.check_ipv4_urls, DEFAULT_CHECK_IPV4_URLS)
+    };
+    if custom.is_empty() {
+        default.iter().map(|url| url.to_string()).collect()
+    } else {
+        custom.clone()
+    }
+}
+
+/// 現在のグローバルアドレスを、確認サービスのチェーンに問い合わせて多数決で決めます。
+///
+/// `ipv6` が `true` の場合はIPv6アドレスを、`false` の場合はIPv4アドレスを求めます。
+/// チェーンの過半数が同じアドレスを返した時点で、残りのサービスには問い合わせません。
+///
+/// # 戻り値
+/// 確認できたアドレス。どのサービスからも確認できなかった場合や、
+/// 最も多くのサービスが返したアドレスが1つに決まらなかった場合は、その内容を表す文字列。
+pub async fn lookup_global_address(client: &Client, ipv6: bool) -> Result<IpAddr, String> {
+    let urls = check_urls(&load_settings().unwrap_or_default(), ipv6);
+    let majority = urls.len() / 2 + 1;
+    // 返されたアドレスと、そのアドレスを返したサービスの数。
+    let mut votes: Vec<(IpAddr, usize)> = Vec::new();
+    let mut errors = Vec::new();
+    for url in &urls {
+        match query_service(client, url, ipv6).await {
+            Ok(addr) => {
+                let count = match votes.iter_mut().find(|(a, _)| *a == addr) {
+                    Some((_, count)) => {
+                        *count += 1;
+                        *count
+                    }
+                    None => {
+                        votes.push((addr, 1));
+                        1
+                    }
+                };
+                if count >= majority {
+                    break;
+                }
+            }
+            Err(e) => errors.push(format!("{}: {}", url, e)),
+        }
+    }
+
+    // 過半数に届かなかった場合も、最も多くのサービスが返したアドレスが1つに決まれば、それを使う。
+    votes.sort_by_key(|(_, count)| Reverse(*count));
+    if votes.len() > 1 {
+        log_detail(&format_msg_log!(
+            MessageKey::LogCheckIpDisagreeFmt,
+            answers = describe_votes(&votes)
+        ));
+    }
+    match votes.as_slice() {
+        [] => Err(errors.join("; ")),
+        [(addr, _)] => Ok(*addr),
+        [(addr, first), (_, second), ..] if first > second => Ok(*addr),
+        _ => Err(format_msg_log!(
+            MessageKey::LogCheckIpUndecidedFmt,
+            answers = describe_votes(&votes)
+        )),
+    }
+}
+
+/// IPv4アドレスが、インターネットから直接届かない範囲（CGNATまたはプライベートアドレス）にあるかを調べます。
+///
+/// # 戻り値
+/// 範囲にある場合は、その範囲を `100.64.0.0/10` の形式で表した文字列。IPv6アドレスや公開アドレスの場合は `None`。
+pub fn non_public_ipv4_range(addr: &IpAddr) -> Option<String> {
+    let IpAddr::V4(v4) = addr else {
+        return None;
+    };
+    NON_PUBLIC_IPV4_RANGES
+        .iter()
+        .find(|(network, prefix)| {
+            let mask = u32::MAX << (32 - prefix);
+            u32::from(*v4) & mask == u32::from(*network)
+        })
+        .map(|(network, prefix)| format!("{}/{}", network, prefix))
+}
+
+/// 1つの確認サービスに、現在のグローバルアドレスを問い合わせます。
+///
+/// 応答が求めたプロトコルのアドレスでない場合はエラーとします。
+async fn query_service(client: &Client, url: &str, ipv6: bool) -> Result<IpAddr, String> {
+    let res = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+        .map_err(|e| e.to_string())?;
+    let body = res.text().await.map_err(|e| e.to_string())?;
+    let body = body.trim();
+    match body.parse::<IpAddr>() {
+        Ok(addr) if addr.is_ipv6() == ipv6 => Ok(addr),
+        _ => Err(format!(
+            "unexpected response: {}",
+            body.chars().take(64).collect::<String>()
+        )),
+    }
+}
+
+/// 返されたアドレスとその数を、ログに記録するための文字列にします。
+fn describe_votes(votes: &[(IpAddr, usize)]) -> String {
+    votes
+        .iter()
+        .map(|(addr, count)| format!("{} x{}", addr, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `--check-ip-poll` で設定された間隔で、グローバルアドレスを確認し続けます。
+///
+/// 前回確認したアドレスから変わっていれば、`on_change` を呼び出します。
+/// 確認できなかったプロトコルは、変わらなかったものとして扱います。
+/// 間隔は確認のたびに設定から読み直し、変更を再起動なしで反映します。このタスクが中断されるまで戻りません。
+pub async fn watch_global_address<F: Fn()>(on_change: F) {
+    // 前回確認したIPv4アドレスとIPv6アドレス。
+    let mut last: [Option<IpAddr>; 2] = [None, None];
+    loop {
+        let settings = load_settings().unwrap_or_default();
+        if settings.check_ip_poll_minutes == 0 {
+            // 無効にしている間に変わったアドレスは、再び有効にしたときの確認で変化とみなさない。
+            last = [None, None];
+            time::sleep(POLL_DISABLED_RECHECK).await;
+            continue;
+        }
+        if let Ok(client) = build_client(&settings.proxy_url) {
+            let mut changed = false;
+            for (previous, ipv6) in last.iter_mut().zip([false, true]) {
+                match lookup_global_address(&client, ipv6).await {
+                    Ok(addr) => {
+                        if let Some(old) = previous.filter(|old| *old != addr) {
+                            log_info(&format_msg_log!(
+                                MessageKey::LogGlobalAddressChangedFmt,
+                                old = old,
+                                new = addr
+                            ));
+                            changed = true;
+                        }
+                        *previous = Some(addr);
+                    }
+                    Err(e) => {
+                        log_detail(&format_msg_log!(MessageKey::LogCheckIpFailedFmt, error = e))
+                    }
+                }
+            }
+            if changed {
+                on_change();
+            }
+        }
+        time::sleep(Duration::from_secs(
+            u64::from(settings.check_ip_poll_minutes) * 60,
+        ))
+        .await;
+    }
+}
@@ -0,0 +1,113 @@
+//! 通知ラウンドの前に、ネットワークに接続されているかを確かめるモジュール。
+//!
+//! 回線が切れている間に通知ラウンドを実行すると、すべてのアカウントが接続エラーになり、
+//! アカウントの数だけエラーがログに並んだうえ、再試行の間隔（バックオフ）も無駄に広がります。
+//! そこで通知ラウンドの前に接続の状態を確かめ、接続されていなければラウンドごと見送ります。
+//!
+//! まずWindowsのネットワーク一覧マネージャー（NLM）に、インターネットへの接続があるかを問い合わせます。
+//! NLMの判定は、ファイアウォールやプロキシの都合で実際には通信できるのに「インターネットなし」となることがあるため、
+//! インターネットへの接続が確認できなかった場合は、MyDNS.JPの通知先にHEADリクエストを送って確かめます。
+//! 何らかのHTTPの応答が返れば（認証エラーなどでも）、接続されているものとして扱います。
+//!
+//! また、現在の接続が従量制課金（モバイル回線やテザリングなど）かどうかも、Windowsの接続のコストの情報から求めます。
+//! サービスは `--metered` の設定に従い、従量制課金の接続では通知を減らしたり見送ったりします。
+
+use crate::format_msg_log;
+use crate::i18n::MessageKey;
+use crate::logging::log_detail;
+use crate::notify::{IPV4_NOTIFY_URL, IPV6_NOTIFY_URL};
+use reqwest::Client;
+use std::time::Duration;
+use tokio::task;
+use windows::Win32::Foundation::ERROR_SUCCESS;
+use windows::Win32::NetworkManagement::IpHelper::GetNetworkConnectivityHint;
+use windows::Win32::Networking::NetworkListManager::{
+    INetworkListManager, NLM_CONNECTIVITY_IPV4_INTERNET, NLM_CONNECTIVITY_IPV6_INTERNET,
+    NetworkListManager,
+};
+use windows::Win32::Networking::WinSock::{
+    NL_NETWORK_CONNECTIVITY_HINT, NetworkConnectivityCostHintFixed,
+    NetworkConnectivityCostHintVariable,
+};
+use windows::Win32::System::Com::{
+    CLSCTX_ALL, COINIT_MULTITHREADED, CoCreateInstance, CoInitializeEx, CoUninitialize,
+};
+
+/// 接続の確認のためのHEADリクエストの応答を待つ最大時間。
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// ネットワークに接続されていて、通知を送れる見込みがあるかどうかを返します。
+///
+/// NLMがインターネットへの接続を報告していれば、すぐに `true` を返します。
+/// そうでなければ、IPv4とIPv6の通知先にHEADリクエストを送り、どちらかが応答すれば `true` を返します。
+/// リクエストは `client` を使うため、設定されたプロキシを経由します。
+pub async fn is_network_available(client: &Client) -> bool {
+    match task::spawn_blocking(has_internet_connectivity).await {
+        Ok(Ok(true)) => return true,
+        Ok(Ok(false)) => {}
+        Ok(Err(e)) => log_detail(&format_msg_log!(
+            MessageKey::LogConnectivityNlmFailedFmt,
+            error = e
+        )),
+        Err(e) => log_detail(&format_msg_log!(
+            MessageKey::LogConnectivityNlmFailedFmt,
+            error = e
+        )),
+    }
+    for url in [IPV4_NOTIFY_URL, IPV6_NOTIFY_URL] {
+        match client.head(url).timeout(PROBE_TIMEOUT).send().await {
+            Ok(_) => return true,
+            Err(e) => log_detail(&format_msg_log!(
+                MessageKey::LogConnectivityProbeFailedFmt,
+                url = url,
+                error = e
+            )),
+        }
+    }
+    false
+}
+
+/// NLMに、IPv4またはIPv6でインターネットに接続されているかを問い合わせます。
+///
+/// COMの初期化と終了は、この関数の中で行います。呼び出したスレッドをブロックするため、
+/// 非同期のタスクからは `spawn_blocking` で呼び出します。
+fn has_internet_connectivity() -> windows::core::Result<bool> {
+    // COMのAPIを呼び出すため、unsafeブロックを使用する。
+    // 取得したインターフェイスは、COMを終了する前に解放する。
+    unsafe {
+        CoInitializeEx(None, COINIT_MULTITHREADED).ok()?;
+        let result = (|| {
+            let manager: INetworkListManager =
+                CoCreateInstance(&NetworkListManager, None, CLSCTX_ALL)?;
+            let connectivity = manager.GetConnectivity()?;
+            Ok(connectivity.0
+                & (NLM_CONNECTIVITY_IPV4_INTERNET.0 | NLM_CONNECTIVITY_IPV6_INTERNET.0)
+                != 0)
+        })();
+        CoUninitialize();
+        result
+    }
+}
+
+/// 現在の接続が、従量制課金の接続として扱うべきものかどうかを返します。
+///
+/// Windowsが接続のコストを「固定（データの上限あり）」か「従量制」と報告している場合、
+/// またはローミング中かデータの上限に達している場合に `true` を返します。
+/// コストの情報を取得できない場合は、従量制課金ではないものとして扱います。
+pub fn is_metered_connection() -> bool {
+    let mut hint = NL_NETWORK_CONNECTIVITY_HINT::default();
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // `hint` は呼び出しの間だけ有効な、この関数のローカル変数を指している。
+    let result = unsafe { GetNetworkConnectivityHint(&mut hint) };
+    if result != ERROR_SUCCESS {
+        log_detail(&format_msg_log!(
+            MessageKey::LogConnectivityCostFailedFmt,
+            error = windows::core::Error::from(result.to_hresult())
+        ));
+        return false;
+    }
+    hint.ConnectivityCost == NetworkConnectivityCostHintFixed
+        || hint.ConnectivityCost == NetworkConnectivityCostHintVariable
+        || hint.Roaming.as_bool()
+        || hint.OverDataLimit.as_bool()
+}
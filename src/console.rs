@@ -0,0 +1,128 @@
+//! コンソールへの出力の量（`--quiet` / `--verbose`）を管理するモジュール。
+//!
+//! - `--quiet`: 処理の経過や結果のメッセージを表示しません。エラーと、`--view` や `--log` などで
+//!   表示を求められた内容、対話的な入力のためのプロンプトは表示します。
+//! - `--verbose`: ログファイルに記録する行とHTTP通信の詳細を、標準エラー出力にも表示します。
+//!
+//! メッセージの表示には `outln!` マクロを使い、`--quiet` の指定に従わせます。
+//!
+//! `--view` や `--status` では、成功・失敗や有効・無効、サービスの状態を色分けして表示します。
+//! 色はエスケープシーケンスで付けるため、コンソールの仮想ターミナル処理を有効にできた場合だけ使います。
+//! 環境変数 `NO_COLOR` が設定されている場合、`--no-color` が指定された場合、
+//! 出力がリダイレクトされている場合は色を付けません。
+
+use std::env;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use windows::Win32::System::Console::{
+    CONSOLE_MODE, ENABLE_VIRTUAL_TERMINAL_PROCESSING, GetConsoleMode, GetStdHandle,
+    STD_OUTPUT_HANDLE, SetConsoleMode,
+};
+
+/// コンソールへの出力の量。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// エラーと、表示を求められた内容だけを出力する。
+    Quiet,
+    /// 通常の出力。
+    Normal,
+    /// ログの行とHTTP通信の詳細も出力する。
+    Verbose,
+}
+
+/// 現在の出力の量。`Verbosity` を数値として保持する。
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// コンソールへの出力の量を設定します。
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// 現在のコンソールへの出力の量を返します。
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        2 => Verbosity::Verbose,
+        _ => Verbosity::Normal,
+    }
+}
+
+/// `--quiet` が指定されていなければ、`println!` と同じようにメッセージを表示します。
+#[macro_export]
+macro_rules! outln {
+    ($($arg:tt)*) => {
+        if $crate::console::verbosity() != $crate::console::Verbosity::Quiet {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// 表示に付ける色。
+#[derive(Clone, Copy, Debug)]
+pub enum Color {
+    /// 成功、有効、実行中。
+    Green,
+    /// 失敗、停止。
+    Red,
+    /// 注意、処理中。
+    Yellow,
+    /// 無効、未設定。
+    Gray,
+}
+
+impl Color {
+    /// 色を付けるエスケープシーケンス（SGR）のパラメーター。
+    fn sgr(self) -> &'static str {
+        match self {
+            Color::Green => "32",
+            Color::Red => "31",
+            Color::Yellow => "33",
+            Color::Gray => "90",
+        }
+    }
+}
+
+/// 色を付けて表示するかどうか。`init_color` で決まる。
+static COLOR_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 色を付けて表示するかどうかを決め、必要ならコンソールの仮想ターミナル処理を有効にします。
+///
+/// `no_color` は `--no-color` が指定されたかどうかです。
+pub fn init_color(no_color: bool) {
+    // NO_COLOR の仕様（https://no-color.org/）に従い、空でない値が設定されていれば色を付けない。
+    let no_color_env = env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let enabled =
+        !no_color && !no_color_env && io::stdout().is_terminal() && enable_virtual_terminal();
+    COLOR_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// 標準出力のコンソールで、エスケープシーケンスを解釈する仮想ターミナル処理を有効にします。
+///
+/// 古いコンソールなど、有効にできなかった場合は `false` を返します。
+fn enable_virtual_terminal() -> bool {
+    // Win32 APIを呼び出すため、unsafeブロックを使用する。
+    // 標準出力のハンドルはプロセスが所有しているため、クローズしない。
+    unsafe {
+        let Ok(handle) = GetStdHandle(STD_OUTPUT_HANDLE) else {
+            return false;
+        };
+        let mut mode = CONSOLE_MODE::default();
+        if GetConsoleMode(handle, &mut mode).is_err() {
+            return false;
+        }
+        mode.0 & ENABLE_VIRTUAL_TERMINAL_PROCESSING.0 != 0
+            || SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING).is_ok()
+    }
+}
+
+/// 色を付けて表示する場合は、`text` を指定された色で囲んで返します。
+///
+/// 桁をそろえて表示する値は、そろえた後の文字列を渡してください。
+pub fn paint(text: &str, color: Color) -> String {
+    if COLOR_ENABLED.load(Ordering::Relaxed) {
+        format!("\x1b[{}m{}\x1b[0m", color.sgr(), text)
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,52 @@
+//! パニック（予期しない内部エラー）の発生を記録するモジュール。
+//!
+//! サービスがパニックで停止すると、何も記録されないまま消えたように見えてしまいます。
+//! `install_panic_hook` で、パニックの内容、発生箇所、バックトレース、バージョンを
+//! ログファイルとWindowsのイベントログ（アプリケーション）に書き込むフックを設定します。
+//! CLIでは、標準エラー出力にも短いメッセージを表示します。
+
+use crate::eventlog::report_event;
+use crate::i18n::MessageKey;
+use crate::logging::log_error;
+use crate::{format_msg, format_msg_log};
+use std::backtrace::Backtrace;
+use std::panic::{self, PanicHookInfo};
+use std::thread;
+use windows::Win32::System::EventLog::EVENTLOG_ERROR_TYPE;
+
+/// パニックを記録するフックを設定します。プロセスの開始直後に1回だけ呼び出します。
+pub fn install_panic_hook() {
+    panic::set_hook(Box::new(|info| {
+        let summary = panic_summary(info);
+        let backtrace = Backtrace::force_capture().to_string();
+
+        // ログは1行ずつ記録し、レベルによる絞り込みでバックトレースが欠けないようにする。
+        log_error(&summary);
+        for line in backtrace.lines() {
+            log_error(&format!("  {}", line));
+        }
+        report_event(
+            EVENTLOG_ERROR_TYPE,
+            &format!("{}\n\n{}", summary, backtrace),
+        );
+        eprintln!(
+            "{}",
+            format_msg!(MessageKey::PanicOccurredFmt, summary = summary)
+        );
+    }));
+}
+
+/// パニックの内容と発生箇所、スレッド、バージョンを1行にまとめます。
+fn panic_summary(info: &PanicHookInfo) -> String {
+    let message = info.payload_as_str().unwrap_or("<non-string payload>");
+    let location = info
+        .location()
+        .map_or_else(|| "<unknown>".to_string(), |l| l.to_string());
+    format_msg_log!(
+        MessageKey::LogPanicFmt,
+        version = env!("CARGO_PKG_VERSION"),
+        thread = thread::current().name().unwrap_or("<unnamed>"),
+        location = location,
+        message = message
+    )
+}
@@ -0,0 +1,279 @@
+//! アカウントのホスト名のDNSレコードを、公開リゾルバーで定期的に確認するモジュール（DNSの自己確認）。
+//!
+//! 通知の直後の確認（`dnsverify` モジュール）とは別に、`--dns-check <MINUTES>` で設定した間隔で、
+//! ホスト名が設定されたアカウントのレコードを複数の公開リゾルバーに直接問い合わせます。
+//! 最後に通知したアドレスと食い違うリゾルバーが多数を占める場合は警告を記録し、そのアカウントを通知し直します。
+//! MyDNS.JPの側でレコードが消えたり古いアドレスに戻ったりした場合に、次の変化を待たずに気付けます。
+//!
+//! OSのリゾルバーはキャッシュやhostsファイルの影響を受けるため、問い合わせはUDPで直接送ります。
+
+use crate::format_msg_log;
+use crate::i18n::MessageKey;
+use crate::logging::{log_detail, log_warn};
+use crate::registry::{Config, load_settings};
+use crate::state::load_state;
+use crate::store::load_all_configs;
+use chrono::{Local, TimeDelta};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::time::Duration;
+use tokio::{task, time};
+
+/// 問い合わせる公開リゾルバー（Cloudflare、Google、Quad9）。
+const PUBLIC_RESOLVERS: &[IpAddr] = &[
+    IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1)),
+    IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8)),
+    IpAddr::V4(Ipv4Addr::new(9, 9, 9, 9)),
+];
+/// 1回の問い合わせの応答を待つ最大時間。
+const QUERY_TIMEOUT: Duration = Duration::from_secs(3);
+/// 通知に成功してから、レコードを確認するまでに空ける時間。
+/// 成功の直後は、リゾルバーのキャッシュに古いアドレスが残っていることが多いため。
+const CHECK_GRACE: TimeDelta = TimeDelta::minutes(15);
+/// `--dns-check` が設定されていない間に、設定を確認し直す間隔。
+const CHECK_DISABLED_RECHECK: Duration = Duration::from_secs(60);
+/// DNSのレコードの種類（A、AAAA）。
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+/// 存在しないドメインを表す応答コード（NXDOMAIN）。
+const RCODE_NXDOMAIN: u8 = 3;
+
+/// `--dns-check` で設定された間隔で、DNSの自己確認を続けます。
+///
+/// 食い違いが見つかったアカウントがあれば、そのMasterIDの一覧を渡して `on_diverged` を呼び出します。
+/// 間隔は確認のたびに設定から読み直し、変更を再起動なしで反映します。このタスクが中断されるまで戻りません。
+pub async fn watch_dns_records<F: Fn(Vec<String>)>(on_diverged: F) {
+    loop {
+        let minutes = load_settings().unwrap_or_default().dns_check_minutes;
+        if minutes == 0 {
+            time::sleep(CHECK_DISABLED_RECHECK).await;
+            continue;
+        }
+        // サービスの開始時には通知したばかりのため、最初の確認も1回の間隔を空けてから行う。
+        time::sleep(Duration::from_secs(u64::from(minutes) * 60)).await;
+        let configs = load_all_configs().unwrap_or_default();
+        let diverged = check_dns_records(&configs).await;
+        if !diverged.is_empty() {
+            on_diverged(diverged);
+        }
+    }
+}
+
+/// ホスト名が設定されたアカウントのレコードを公開リゾルバーに問い合わせ、最後に通知したアドレスと比べます。
+///
+/// 通知に成功したことがないアカウントや、成功の直後のアカウントは確認しません。
+///
+/// # 戻り値
+/// レコードが通知したアドレスと食い違っていたアカウントのMasterID。
+pub async fn check_dns_records(configs: &[Config]) -> Vec<String> {
+    let state = load_state().unwrap_or_default();
+    let now = Local::now();
+    let mut diverged = Vec::new();
+    for config in configs {
+        let Some(account) = state.accounts.get(&config.master_id) else {
+            continue;
+        };
+        if config.hostname.is_empty()
+            || account.last_ip.is_empty()
+            || account
+                .last_success
+                .is_none_or(|success| now - success < CHECK_GRACE)
+        {
+            continue;
+        }
+        for ipv6 in [false, true] {
+            let expected: Vec<IpAddr> = account
+                .last_ip
+                .iter()
+                .filter(|addr| addr.is_ipv6() == ipv6)
+                .copied()
+                .collect();
+            if !expected.is_empty()
+                && !check_record(&config.master_id, &config.hostname, ipv6, &expected).await
+            {
+                diverged.push(config.master_id.clone());
+                break;
+            }
+        }
+    }
+    diverged
+}
+
+/// 1つのホスト名の1種類のレコードを、すべての公開リゾルバーに問い合わせて確認します。
+///
+/// 応答したリゾルバーの過半数が通知したアドレスを返さなかった場合に、食い違いとして警告を記録します。
+/// どのリゾルバーも応答しなかった場合は、判断できないため一致したものとして扱います。
+///
+/// # 戻り値
+/// レコードが通知したアドレスと一致した（または判断できなかった）場合に `true`。
+async fn check_record(master_id: &str, hostname: &str, ipv6: bool, expected: &[IpAddr]) -> bool {
+    let mut agreeing = 0;
+    // 食い違ったリゾルバーと、その応答。
+    let mut disagreeing = Vec::new();
+    for &resolver in PUBLIC_RESOLVERS {
+        match query_resolver(resolver, hostname, ipv6).await {
+            Ok(resolved) if expected.iter().all(|a| resolved.contains(a)) => agreeing += 1,
+            Ok(resolved) => disagreeing.push((resolver, resolved)),
+            Err(e) => log_detail(&format!(
+                "[{}] {}",
+                master_id,
+                format_msg_log!(
+                    MessageKey::LogDnsCheckQueryFailedFmt,
+                    host = hostname,
+                    resolver = resolver,
+                    error = e
+                )
+            )),
+        }
+    }
+    if disagreeing.len() <= agreeing {
+        return true;
+    }
+    let (resolver, resolved) = &disagreeing[0];
+    log_warn(&format!(
+        "[{}] {}",
+        master_id,
+        format_msg_log!(
+            MessageKey::LogDnsCheckDivergedFmt,
+            host = hostname,
+            resolver = resolver,
+            resolved = if resolved.is_empty() {
+                "-".to_string()
+            } else {
+                join_addresses(resolved)
+            },
+            addr = join_addresses(expected),
+            count = disagreeing.len(),
+            total = disagreeing.len() + agreeing
+        )
+    ));
+    false
+}
+
+/// リゾルバーにAレコードまたはAAAAレコードを問い合わせ、得られたアドレスの一覧を返します。
+///
+/// レコードがない場合や、ドメインが存在しない（NXDOMAIN）場合は空の一覧を返します。
+/// 問い合わせはブロッキング処理のため、専用のスレッドで実行します。
+async fn query_resolver(resolver: IpAddr, hostname: &str, ipv6: bool) -> io::Result<Vec<IpAddr>> {
+    let hostname = hostname.to_string();
+    task::spawn_blocking(move || query_blocking(resolver, &hostname, ipv6))
+        .await
+        .map_err(io::Error::other)?
+}
+
+/// `query_resolver` の本体。UDPで問い合わせを1回送り、応答を解析します。
+fn query_blocking(resolver: IpAddr, hostname: &str, ipv6: bool) -> io::Result<Vec<IpAddr>> {
+    let qtype = if ipv6 { TYPE_AAAA } else { TYPE_A };
+    let id = RandomState::new().build_hasher().finish() as u16;
+    let request = build_query(id, hostname, qtype)?;
+
+    let local: SocketAddr = match resolver {
+        IpAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+        IpAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+    };
+    let socket = UdpSocket::bind(local)?;
+    socket.set_read_timeout(Some(QUERY_TIMEOUT))?;
+    socket.connect((resolver, 53))?;
+    socket.send(&request)?;
+    let mut buffer = [0u8; 1500];
+    loop {
+        let len = socket.recv(&mut buffer)?;
+        // 別の問い合わせへの応答（IDが違うもの）は読み捨てる。
+        if len >= 2 && u16::from_be_bytes([buffer[0], buffer[1]]) == id {
+            return parse_response(&buffer[..len], qtype);
+        }
+    }
+}
+
+/// 再帰的な問い合わせを要求する、1つの質問を持つDNSのメッセージを組み立てます。
+fn build_query(id: u16, hostname: &str, qtype: u16) -> io::Result<Vec<u8>> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidInput, hostname.to_string());
+    let mut message = Vec::with_capacity(hostname.len() + 18);
+    message.extend_from_slice(&id.to_be_bytes());
+    // フラグ（RD: 再帰を要求）、質問1件、回答・権威・追加は0件。
+    message.extend_from_slice(&[0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0]);
+    for label in hostname.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(invalid());
+        }
+        message.push(label.len() as u8);
+        message.extend_from_slice(label.as_bytes());
+    }
+    message.push(0);
+    message.extend_from_slice(&qtype.to_be_bytes());
+    // クラスはIN。
+    message.extend_from_slice(&1u16.to_be_bytes());
+    Ok(message)
+}
+
+/// DNSの応答を解析し、回答のうち `qtype` の種類のレコードのアドレスを返します。
+fn parse_response(message: &[u8], qtype: u16) -> io::Result<Vec<IpAddr>> {
+    let malformed = || io::Error::new(io::ErrorKind::InvalidData, "malformed DNS response");
+    let read_u16 = |pos: usize| -> io::Result<u16> {
+        message
+            .get(pos..pos + 2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+            .ok_or_else(malformed)
+    };
+    if message.len() < 12 || message[2] & 0x80 == 0 {
+        return Err(malformed());
+    }
+    match message[3] & 0x0F {
+        0 => {}
+        RCODE_NXDOMAIN => return Ok(Vec::new()),
+        rcode => return Err(io::Error::other(format!("DNS error (RCODE {})", rcode))),
+    }
+    let questions = read_u16(4)?;
+    let answers = read_u16(6)?;
+
+    let mut pos = 12;
+    for _ in 0..questions {
+        // 名前、種類、クラス。
+        pos = skip_name(message, pos).ok_or_else(malformed)? + 4;
+    }
+    let mut addresses = Vec::new();
+    for _ in 0..answers {
+        pos = skip_name(message, pos).ok_or_else(malformed)?;
+        let rtype = read_u16(pos)?;
+        // 種類、クラス、TTL（4バイト）、データの長さ。
+        let rdlength = read_u16(pos + 8)? as usize;
+        let data = message
+            .get(pos + 10..pos + 10 + rdlength)
+            .ok_or_else(malformed)?;
+        pos += 10 + rdlength;
+        // CNAMEなどの、求めた種類以外のレコードは読み飛ばす。
+        if rtype != qtype {
+            continue;
+        }
+        match (rtype, data.len()) {
+            (TYPE_A, 4) => addresses.push(IpAddr::from(<[u8; 4]>::try_from(data).unwrap())),
+            (TYPE_AAAA, 16) => addresses.push(IpAddr::from(<[u8; 16]>::try_from(data).unwrap())),
+            _ => return Err(malformed()),
+        }
+    }
+    Ok(addresses)
+}
+
+/// メッセージ中の `pos` から始まる名前を読み飛ばし、その次の位置を返します。
+/// 名前が圧縮（ポインター）されている場合は、ポインターの2バイトだけを読み飛ばします。
+fn skip_name(message: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        let len = *message.get(pos)?;
+        match len {
+            0 => return Some(pos + 1),
+            l if l & 0xC0 == 0xC0 => return Some(pos + 2),
+            l => pos += 1 + l as usize,
+        }
+    }
+}
+
+/// アドレスの一覧を、ログに記録するためのカンマ区切りの文字列にします。
+fn join_addresses(addrs: &[IpAddr]) -> String {
+    addrs
+        .iter()
+        .map(IpAddr::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
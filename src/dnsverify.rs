@@ -0,0 +1,104 @@
+//! 通知の後に、アカウントのホスト名のDNSレコードが更新されたことを確認するモジュール。
+//!
+//! MyDNS.JPが通知を受け付けても、DNSに反映されるまでには時間がかかることがあります。
+//! アカウントにホスト名が設定されている場合は、通知の成功後にそのホスト名を名前解決し、
+//! MyDNS.JPが受け付けたアドレスと一致するかを確認します。
+//! 一致しない場合は、少し待ってから名前解決をやり直し、最後まで一致しなければ警告を記録します。
+
+use crate::format_msg_log;
+use crate::i18n::MessageKey;
+use crate::logging::{log_info, log_warn};
+use std::io;
+use std::net::{IpAddr, ToSocketAddrs};
+use std::time::Duration;
+use tokio::task;
+use tokio::time;
+
+/// 名前解決を試みる最大回数。
+const VERIFY_ATTEMPTS: u32 = 3;
+/// 名前解決をやり直すまでの待ち時間。
+const VERIFY_RETRY_DELAY: Duration = Duration::from_secs(20);
+
+/// `hostname` を名前解決し、`expected` のアドレスがすべて含まれるかを確認します。
+///
+/// 結果はログに記録するだけで、通知の成否には影響しません。
+///
+/// # 戻り値
+/// DNSの内容が通知したアドレスと一致した場合に `true`。
+pub async fn verify_dns(master_id: &str, hostname: &str, expected: &[IpAddr]) -> bool {
+    let expected_text = join_addresses(expected);
+    for attempt in 1..=VERIFY_ATTEMPTS {
+        match resolve(hostname).await {
+            Ok(resolved) if expected.iter().all(|a| resolved.contains(a)) => {
+                log_info(&format!(
+                    "[{}] {}",
+                    master_id,
+                    format_msg_log!(
+                        MessageKey::LogDnsVerifiedFmt,
+                        host = hostname,
+                        addr = expected_text
+                    )
+                ));
+                return true;
+            }
+            Ok(resolved) => log_warn(&format!(
+                "[{}] {}",
+                master_id,
+                format_msg_log!(
+                    MessageKey::LogDnsMismatchFmt,
+                    host = hostname,
+                    resolved = join_addresses(&resolved),
+                    addr = expected_text,
+                    attempt = attempt,
+                    max = VERIFY_ATTEMPTS
+                )
+            )),
+            Err(e) => log_warn(&format!(
+                "[{}] {}",
+                master_id,
+                format_msg_log!(
+                    MessageKey::LogDnsResolveFailedFmt,
+                    host = hostname,
+                    error = e,
+                    attempt = attempt,
+                    max = VERIFY_ATTEMPTS
+                )
+            )),
+        }
+        if attempt < VERIFY_ATTEMPTS {
+            time::sleep(VERIFY_RETRY_DELAY).await;
+        }
+    }
+    log_warn(&format!(
+        "[{}] {}",
+        master_id,
+        format_msg_log!(
+            MessageKey::LogDnsNotConvergedFmt,
+            host = hostname,
+            addr = expected_text
+        )
+    ));
+    false
+}
+
+/// ホスト名を名前解決し、得られたアドレスの一覧を返します。
+///
+/// 名前解決はOSのリゾルバーを使うブロッキング処理のため、専用のスレッドで実行します。
+async fn resolve(hostname: &str) -> io::Result<Vec<IpAddr>> {
+    let hostname = hostname.to_string();
+    task::spawn_blocking(move || {
+        let addrs = (hostname.as_str(), 0).to_socket_addrs()?;
+        Ok(addrs.map(|addr| addr.ip()).collect())
+    })
+    .await
+    .map_err(io::Error::other)?
+}
+
+/// アドレスの一覧を、ログに記録するためのカンマ区切りの文字列にします。
+fn join_addresses(addrs: &[IpAddr]) -> String {
+    addrs
+        .iter()
+        .map(IpAddr::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,275 @@
+//! 動作環境を診断する `doctor` サブコマンドを実装するモジュール。
+//!
+//! 管理者権限、レジストリの設定の読み書き、ログファイルへの書き込み、MyDNS.JPへの接続、
+//! サービスのインストール状態、時計のずれを順に確認し、項目ごとに `[PASS]` / `[WARN]` / `[FAIL]` で表示します。
+//! 表示内容はそのまま不具合の報告に貼り付けられるよう、バージョンと実行日時も含めます。
+//! パスワードなどの秘密は表示しません。
+
+use crate::console::{Color, paint};
+use crate::elevation::is_elevated;
+use crate::error::AdapterError;
+use crate::exitcode::ExitReason;
+use crate::format_msg;
+use crate::i18n::{MessageKey, get_msg};
+use crate::logging::get_log_path;
+use crate::notify::{IPV4_NOTIFY_URL, IPV6_NOTIFY_URL, build_client};
+use crate::registry::{
+    ConfigScope, NotifyMethod, Provider, config_scope, load_settings, machine_scope_writable,
+};
+use crate::store::{active_store, load_all_configs};
+use crate::winservice::{query_service_state, state_text};
+use chrono::{DateTime, Local};
+use reqwest::Client;
+use reqwest::header::DATE;
+use std::fs::OpenOptions;
+use std::time::Instant;
+use tokio::runtime::Runtime;
+use windows::Win32::System::Services::SERVICE_RUNNING;
+
+/// 許容する時計のずれ（秒）。これを超えると、ログの時刻や証明書の検証に支障が出るおそれがある。
+const MAX_CLOCK_SKEW_SECS: i64 = 300;
+
+/// 診断項目の結果。
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    /// 問題なし。
+    Pass,
+    /// 動作はするが、注意が必要。
+    Warn,
+    /// 問題あり。
+    Fail,
+}
+
+impl Outcome {
+    /// 結果を、翻訳しない色付きの表示（`[PASS]` など）に変換します。
+    fn label(self) -> String {
+        match self {
+            Outcome::Pass => paint("[PASS]", Color::Green),
+            Outcome::Warn => paint("[WARN]", Color::Yellow),
+            Outcome::Fail => paint("[FAIL]", Color::Red),
+        }
+    }
+}
+
+/// 動作環境を診断し、結果を表示します。
+///
+/// # 戻り値
+/// `[FAIL]` の項目があれば `ExitReason::Error`、なければ `ExitReason::Success`。
+pub fn doctor_mode() -> Result<ExitReason, AdapterError> {
+    println!("{}", get_msg(MessageKey::DoctorTitle));
+    println!(
+        "{}",
+        format_msg!(
+            MessageKey::DoctorHeaderFmt,
+            version = env!("CARGO_PKG_VERSION"),
+            time = Local::now().to_rfc3339(),
+            scope = active_store().location()
+        )
+    );
+
+    let mut outcomes = Vec::new();
+    let mut report = |outcome: Outcome, item: MessageKey, detail: &str| {
+        println!("{} {}: {}", outcome.label(), get_msg(item), detail);
+        outcomes.push(outcome);
+    };
+
+    // 管理者権限。設定の変更とサービスの操作に必要だが、状態の確認には不要なため警告にとどめる。
+    if is_elevated() {
+        report(
+            Outcome::Pass,
+            MessageKey::DoctorItemElevation,
+            get_msg(MessageKey::DoctorElevated),
+        );
+    } else {
+        report(
+            Outcome::Warn,
+            MessageKey::DoctorItemElevation,
+            get_msg(MessageKey::DoctorNotElevated),
+        );
+    }
+
+    // レジストリの設定。
+    let configs = match load_all_configs() {
+        Ok(configs) => {
+            let detail = format_msg!(MessageKey::DoctorAccountsFmt, count = configs.len());
+            if configs.is_empty() {
+                report(Outcome::Warn, MessageKey::DoctorItemRegistry, &detail);
+            } else {
+                report(Outcome::Pass, MessageKey::DoctorItemRegistry, &detail);
+            }
+            configs
+        }
+        Err(e) => {
+            report(
+                Outcome::Fail,
+                MessageKey::DoctorItemRegistry,
+                &e.to_string(),
+            );
+            Vec::new()
+        }
+    };
+    let settings = match load_settings() {
+        Ok(settings) => {
+            report(
+                Outcome::Pass,
+                MessageKey::DoctorItemSettings,
+                get_msg(MessageKey::DoctorReadable),
+            );
+            settings
+        }
+        Err(e) => {
+            report(
+                Outcome::Fail,
+                MessageKey::DoctorItemSettings,
+                &e.to_string(),
+            );
+            Default::default()
+        }
+    };
+    if config_scope() == ConfigScope::Machine {
+        if machine_scope_writable() {
+            report(
+                Outcome::Pass,
+                MessageKey::DoctorItemRegistryWrite,
+                get_msg(MessageKey::DoctorWritable),
+            );
+        } else {
+            report(
+                Outcome::Warn,
+                MessageKey::DoctorItemRegistryWrite,
+                get_msg(MessageKey::DoctorRegistryReadOnly),
+            );
+        }
+    }
+
+    // ログファイル。追記モードで開けるかだけを確認し、内容は書き込まない。
+    match get_log_path() {
+        Ok(path) => {
+            let detail = path.display().to_string();
+            match OpenOptions::new().append(true).create(true).open(&path) {
+                Ok(_) => report(Outcome::Pass, MessageKey::DoctorItemLog, &detail),
+                Err(e) => report(
+                    Outcome::Fail,
+                    MessageKey::DoctorItemLog,
+                    &format!("{} ({})", detail, e),
+                ),
+            }
+        }
+        Err(e) => report(Outcome::Fail, MessageKey::DoctorItemLog, &e.to_string()),
+    }
+
+    // MyDNS.JPへの接続。認証情報は送らず、応答が返ることだけを確認する。
+    // 汎用のサービスやログインで通知するアカウントは、MyDNS.JPのHTTPSに接続できなくても通知できる。
+    let mydns = configs
+        .iter()
+        .filter(|c| c.provider == Provider::MyDns && c.method == NotifyMethod::Http);
+    let mut server_time = None;
+    match build_client(&settings.proxy_url) {
+        Ok(client) => {
+            let runtime = Runtime::new()?;
+            for (item, url, needed) in [
+                (
+                    MessageKey::DoctorItemIpv4,
+                    IPV4_NOTIFY_URL,
+                    mydns.clone().any(|c| c.ipv4_notify),
+                ),
+                (
+                    MessageKey::DoctorItemIpv6,
+                    IPV6_NOTIFY_URL,
+                    mydns.clone().any(|c| c.ipv6_notify),
+                ),
+            ] {
+                match runtime.block_on(probe(&client, url)) {
+                    Ok((detail, date)) => {
+                        report(Outcome::Pass, item, &detail);
+                        server_time = server_time.or(date);
+                    }
+                    // 通知に使っていないプロトコルに接続できないのは、問題ではない。
+                    Err(e) if !needed => report(Outcome::Warn, item, &e),
+                    Err(e) => report(Outcome::Fail, item, &e),
+                }
+            }
+        }
+        Err(e) => report(Outcome::Fail, MessageKey::DoctorItemIpv4, &e.to_string()),
+    }
+
+    // サービスのインストール状態。
+    match query_service_state() {
+        Ok(Some(state)) if state == SERVICE_RUNNING => {
+            report(
+                Outcome::Pass,
+                MessageKey::DoctorItemService,
+                &state_text(state),
+            );
+        }
+        Ok(Some(state)) => report(
+            Outcome::Warn,
+            MessageKey::DoctorItemService,
+            &state_text(state),
+        ),
+        Ok(None) => report(
+            Outcome::Warn,
+            MessageKey::DoctorItemService,
+            get_msg(MessageKey::DoctorServiceNotInstalled),
+        ),
+        Err(e) => report(Outcome::Fail, MessageKey::DoctorItemService, &e.to_string()),
+    }
+
+    // 時計のずれ。MyDNS.JPの応答の Date ヘッダーと比べる。
+    match server_time {
+        Some(server) => {
+            let skew = (Local::now() - server).num_seconds();
+            let detail = format_msg!(MessageKey::DoctorClockSkewFmt, seconds = skew);
+            if skew.abs() > MAX_CLOCK_SKEW_SECS {
+                report(Outcome::Fail, MessageKey::DoctorItemClock, &detail);
+            } else {
+                report(Outcome::Pass, MessageKey::DoctorItemClock, &detail);
+            }
+        }
+        None => report(
+            Outcome::Warn,
+            MessageKey::DoctorItemClock,
+            get_msg(MessageKey::DoctorClockUnknown),
+        ),
+    }
+
+    let failures = outcomes.iter().filter(|o| **o == Outcome::Fail).count();
+    let warnings = outcomes.iter().filter(|o| **o == Outcome::Warn).count();
+    println!(
+        "{}",
+        format_msg!(
+            MessageKey::DoctorSummaryFmt,
+            fail = failures,
+            warn = warnings
+        )
+    );
+    Ok(if failures > 0 {
+        ExitReason::Error
+    } else {
+        ExitReason::Success
+    })
+}
+
+/// 通知先のURLに認証情報なしでリクエストを送り、応答が返るかを確認します。
+///
+/// 認証を求める応答（401）も、サーバーに到達できたものとして扱います。
+///
+/// # 戻り値
+/// 成功時は、ステータスと所要時間の表示と、応答の `Date` ヘッダーの時刻（あれば）。
+/// 失敗時は、エラーの内容。
+async fn probe(client: &Client, url: &str) -> Result<(String, Option<DateTime<Local>>), String> {
+    let started = Instant::now();
+    let res = client.get(url).send().await.map_err(|e| e.to_string())?;
+    let date = res
+        .headers()
+        .get(DATE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+        .map(|t| t.with_timezone(&Local));
+    let detail = format_msg!(
+        MessageKey::DoctorReachableFmt,
+        status = res.status(),
+        ms = started.elapsed().as_millis()
+    );
+    Ok((detail, date))
+}
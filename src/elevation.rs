@@ -0,0 +1,137 @@
+//! 管理者権限の有無を判定し、UACで昇格して同じコマンドを実行し直すためのモジュール。
+//!
+//! サービスの操作やマシン全体の設定（HKLM）への書き込みには管理者権限が必要です。
+//! 権限のないコンソールから実行された場合は、確認のうえ `ShellExecuteExW` の `runas` で
+//! 同じ引数のまま実行し直し、昇格したプロセスの終了を待ってその終了コードを引き継ぎます。
+//!
+//! 昇格したプロセスは新しいコンソールウィンドウで動くため、`--elevated` を付けて起動し、
+//! 終了前にEnterキーの入力を待って結果を読めるようにします。
+
+use crate::error::AdapterError;
+use crate::exitcode::{ExitError, ExitReason};
+use crate::i18n::{MessageKey, get_msg};
+use std::env;
+use windows::Win32::Foundation::{CloseHandle, ERROR_CANCELLED, HANDLE};
+use windows::Win32::Security::{GetTokenInformation, TOKEN_ELEVATION, TOKEN_QUERY, TokenElevation};
+use windows::Win32::System::Threading::{
+    GetCurrentProcess, GetExitCodeProcess, INFINITE, OpenProcessToken, WaitForSingleObject,
+};
+use windows::Win32::UI::Shell::{SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW, ShellExecuteExW};
+use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+use windows::core::{HRESULT, HSTRING, PCWSTR, w};
+
+/// 昇格して実行し直したプロセスであることを示す引数。
+pub const ELEVATED_FLAG: &str = "--elevated";
+
+/// 現在のプロセスが管理者権限で実行されているかどうかを判定します。
+///
+/// プロセスのアクセストークンの昇格状態（`TokenElevation`）を問い合わせます。
+/// SCMに接続できない環境や、SCMのアクセス許可が変更されている環境でも正しく判定できます。
+/// 問い合わせに失敗した場合は、権限がないものとみなします。
+pub fn is_elevated() -> bool {
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // 開いたトークンのハンドルは、問い合わせの成否にかかわらずクローズします。
+    unsafe {
+        let mut token = HANDLE::default();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token).is_err() {
+            return false;
+        }
+        let mut elevation = TOKEN_ELEVATION::default();
+        let mut returned = 0u32;
+        let result = GetTokenInformation(
+            token,
+            TokenElevation,
+            Some(&mut elevation as *mut TOKEN_ELEVATION as *mut _),
+            size_of::<TOKEN_ELEVATION>() as u32,
+            &mut returned,
+        );
+        let _ = CloseHandle(token);
+        result.is_ok() && elevation.TokenIsElevated != 0
+    }
+}
+
+/// 現在のプロセスが、昇格して実行し直されたものかどうかを返します。
+pub fn is_relaunched() -> bool {
+    env::args().any(|arg| arg == ELEVATED_FLAG)
+}
+
+/// 同じ引数で、管理者として実行し直します。
+///
+/// UACの確認ダイアログが表示され、許可されると昇格したプロセスが起動します。
+/// 作業ディレクトリも引き継ぐため、相対パスで指定したファイルもそのまま使えます。
+///
+/// # 戻り値
+/// 昇格したプロセスの終了コードに対応する終了の理由。
+/// UACの確認が拒否された場合は、`ExitReason::AdminRequired` のエラー。
+pub fn relaunch_elevated() -> Result<ExitReason, AdapterError> {
+    let exe = HSTRING::from(env::current_exe()?.as_os_str());
+    let dir = HSTRING::from(env::current_dir()?.as_os_str());
+    let mut command_line: Vec<String> = env::args().skip(1).map(|a| quote_arg(&a)).collect();
+    command_line.push(ELEVATED_FLAG.to_string());
+    let params = HSTRING::from(command_line.join(" "));
+
+    let mut info = SHELLEXECUTEINFOW {
+        cbSize: size_of::<SHELLEXECUTEINFOW>() as u32,
+        fMask: SEE_MASK_NOCLOSEPROCESS,
+        lpVerb: w!("runas"),
+        lpFile: PCWSTR(exe.as_ptr()),
+        lpParameters: PCWSTR(params.as_ptr()),
+        lpDirectory: PCWSTR(dir.as_ptr()),
+        nShow: SW_SHOWNORMAL.0,
+        ..Default::default()
+    };
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // 受け取ったプロセスのハンドルは、終了コードを取得した後にクローズします。
+    unsafe {
+        if let Err(e) = ShellExecuteExW(&mut info) {
+            if e.code() == HRESULT::from(ERROR_CANCELLED) {
+                return Err(ExitError::new(
+                    ExitReason::AdminRequired,
+                    get_msg(MessageKey::ElevationCancelled),
+                )
+                .into());
+            }
+            return Err(e.into());
+        }
+        if info.hProcess.is_invalid() {
+            return Ok(ExitReason::Success);
+        }
+        WaitForSingleObject(info.hProcess, INFINITE);
+        let mut code = 0u32;
+        let result = GetExitCodeProcess(info.hProcess, &mut code);
+        let _ = CloseHandle(info.hProcess);
+        result?;
+        Ok(ExitReason::from_code(code))
+    }
+}
+
+/// コマンドラインの1つの引数を、`CommandLineToArgvW` の規則に従って引用符で囲みます。
+///
+/// 空白や `"` を含まない引数は、そのまま返します。
+fn quote_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+        return arg.to_string();
+    }
+    let mut quoted = String::from('"');
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                // `"` の直前の `\` は2倍にし、`"` 自体も `\` でエスケープする。
+                quoted.push_str(&"\\".repeat(backslashes * 2 + 1));
+                quoted.push('"');
+                backslashes = 0;
+            }
+            _ => {
+                quoted.push_str(&"\\".repeat(backslashes));
+                quoted.push(c);
+                backslashes = 0;
+            }
+        }
+    }
+    // 閉じる `"` の直前の `\` も2倍にする。
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,134 @@
+//! アプリケーション全体で使うエラー型を定義するモジュール。
+//!
+//! コマンドの処理では、ファイルやコンソールの入出力（`std::io`）、HTTPの通信（`reqwest`）、
+//! Windows API（`windows`）、サービス制御マネージャー（`windows-service`）のエラーが混在します。
+//! これらを `AdapterError` にまとめることで、呼び出し元がエラーの種類で処理を分けられるようにし、
+//! `main` ではエラーの種類からプロセスの終了コード（`exitcode` モジュール）を決めます。
+
+use crate::exitcode::{ExitError, ExitReason};
+use std::error::Error;
+use std::fmt;
+use std::io;
+use windows::Win32::Foundation::{ERROR_ACCESS_DENIED, WIN32_ERROR};
+
+/// コマンドの処理で発生したエラー。
+#[derive(Debug)]
+pub enum AdapterError {
+    /// ファイルやコンソール、名前付きパイプなどの入出力のエラー。
+    Io(io::Error),
+    /// HTTPクライアントの作成や、通信のエラー。
+    Http(reqwest::Error),
+    /// Windows API（レジストリ、COMなど）のエラー。
+    Windows(windows::core::Error),
+    /// サービス制御マネージャーの操作のエラー。
+    Service(windows_service::Error),
+    /// 履歴のデータベース（SQLite）の操作のエラー。
+    #[cfg(feature = "sqlite")]
+    Database(rusqlite::Error),
+    /// 特定の終了コードで終了すべきエラー（管理者権限が必要な場合など）。
+    Exit(ExitError),
+    /// 上記以外の、メッセージだけを持つエラー。
+    Message(String),
+}
+
+impl AdapterError {
+    /// このエラーで終了するときの、プロセスの終了の理由を返します。
+    ///
+    /// アクセスが拒否されたWindows APIやサービス制御のエラーは、管理者権限が必要なものとして扱います。
+    /// HTTPのエラーは通信エラーとして扱います。
+    pub fn exit_reason(&self) -> ExitReason {
+        let access_denied = |code: Option<i32>| code == Some(ERROR_ACCESS_DENIED.0 as i32);
+        match self {
+            AdapterError::Exit(e) => e.reason,
+            AdapterError::Http(_) => ExitReason::NetworkFailure,
+            AdapterError::Windows(e) if e.code() == WIN32_ERROR(ERROR_ACCESS_DENIED.0).into() => {
+                ExitReason::AdminRequired
+            }
+            AdapterError::Service(windows_service::Error::Winapi(e))
+                if access_denied(e.raw_os_error()) =>
+            {
+                ExitReason::AdminRequired
+            }
+            _ => ExitReason::Error,
+        }
+    }
+}
+
+impl fmt::Display for AdapterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdapterError::Io(e) => write!(f, "{}", e),
+            AdapterError::Http(e) => write!(f, "{}", e),
+            AdapterError::Windows(e) => write!(f, "{}", e),
+            AdapterError::Service(e) => write!(f, "{}", e),
+            #[cfg(feature = "sqlite")]
+            AdapterError::Database(e) => write!(f, "{}", e),
+            AdapterError::Exit(e) => write!(f, "{}", e),
+            AdapterError::Message(message) => f.write_str(message),
+        }
+    }
+}
+
+impl Error for AdapterError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AdapterError::Io(e) => Some(e),
+            AdapterError::Http(e) => Some(e),
+            AdapterError::Windows(e) => Some(e),
+            AdapterError::Service(e) => Some(e),
+            #[cfg(feature = "sqlite")]
+            AdapterError::Database(e) => Some(e),
+            AdapterError::Exit(e) => Some(e),
+            AdapterError::Message(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for AdapterError {
+    fn from(e: io::Error) -> Self {
+        AdapterError::Io(e)
+    }
+}
+
+impl From<reqwest::Error> for AdapterError {
+    fn from(e: reqwest::Error) -> Self {
+        AdapterError::Http(e)
+    }
+}
+
+impl From<windows::core::Error> for AdapterError {
+    fn from(e: windows::core::Error) -> Self {
+        AdapterError::Windows(e)
+    }
+}
+
+impl From<windows_service::Error> for AdapterError {
+    fn from(e: windows_service::Error) -> Self {
+        AdapterError::Service(e)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for AdapterError {
+    fn from(e: rusqlite::Error) -> Self {
+        AdapterError::Database(e)
+    }
+}
+
+impl From<ExitError> for AdapterError {
+    fn from(e: ExitError) -> Self {
+        AdapterError::Exit(e)
+    }
+}
+
+impl From<String> for AdapterError {
+    fn from(message: String) -> Self {
+        AdapterError::Message(message)
+    }
+}
+
+impl From<&str> for AdapterError {
+    fn from(message: &str) -> Self {
+        AdapterError::Message(message.to_string())
+    }
+}
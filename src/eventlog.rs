@@ -0,0 +1,35 @@
+//! Windowsのイベントログ（アプリケーション）に記録するモジュール。
+//!
+//! パニックや、アカウントの通知の自動停止など、ログファイルを見ていなくても気付いてほしい出来事を記録します。
+//! イベントソースは登録していないため、イベントビューアーには説明が見つからない旨とともに本文が表示されます。
+
+use windows::Win32::Security::PSID;
+use windows::Win32::System::EventLog::{
+    DeregisterEventSource, REPORT_EVENT_TYPE, RegisterEventSourceW, ReportEventW,
+};
+use windows::core::{HSTRING, PCWSTR, w};
+
+/// イベントログ（アプリケーション）に、指定した種類（エラー、警告など）で本文を記録します。
+///
+/// 記録に失敗しても、呼び出し元の処理を妨げないよう無視します。
+pub fn report_event(event_type: REPORT_EVENT_TYPE, text: &str) {
+    let text = HSTRING::from(text);
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // 開いたイベントソースのハンドルは、記録の成否にかかわらずクローズします。
+    unsafe {
+        let Ok(source) = RegisterEventSourceW(None, w!("MyDNSAdapter")) else {
+            return;
+        };
+        let _ = ReportEventW(
+            source,
+            event_type,
+            0,
+            0,
+            PSID::default(),
+            0,
+            Some(&[PCWSTR(text.as_ptr())]),
+            None,
+        );
+        let _ = DeregisterEventSource(source);
+    }
+}
@@ -0,0 +1,100 @@
+//! プロセスの終了コードを定義するモジュール。
+//!
+//! タスクスケジューラーやスクリプトから実行したときに、失敗の種類に応じて対処を分けられるよう、
+//! 結果ごとに異なる終了コードを返します。
+//!
+//! | コード | 意味 |
+//! | :----- | :--- |
+//! | 0 | 成功 |
+//! | 1 | その他のエラー |
+//! | 2 | アカウントが設定されていない、または指定されたアカウントが見つからない |
+//! | 3 | 認証エラーで通知に失敗した |
+//! | 4 | 通信エラーやサーバーエラーで、すべての通知に失敗した |
+//! | 5 | 一部の通知だけが失敗した |
+//! | 6 | 管理者権限が必要 |
+
+use std::error::Error;
+use std::fmt;
+use std::process::ExitCode;
+
+/// プロセスの終了の理由。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitReason {
+    /// 正常に終了した。
+    Success,
+    /// 以下のいずれにも当てはまらないエラーで終了した。
+    Error,
+    /// 通知するアカウントが設定されていない、または指定されたアカウントが見つからない。
+    ConfigMissing,
+    /// すべての通知に失敗し、その中に認証エラーが含まれていた。
+    AuthFailure,
+    /// 通信エラーやサーバーエラーで、すべての通知に失敗した。
+    NetworkFailure,
+    /// 一部のアカウントまたはプロトコルの通知だけが失敗した。
+    PartialFailure,
+    /// 管理者権限が必要な操作を、管理者権限なしで実行しようとした。
+    AdminRequired,
+}
+
+impl ExitReason {
+    /// プロセスの終了コードの値を返します。
+    pub fn code(self) -> u8 {
+        match self {
+            ExitReason::Success => 0,
+            ExitReason::Error => 1,
+            ExitReason::ConfigMissing => 2,
+            ExitReason::AuthFailure => 3,
+            ExitReason::NetworkFailure => 4,
+            ExitReason::PartialFailure => 5,
+            ExitReason::AdminRequired => 6,
+        }
+    }
+
+    /// 終了コードの値から、終了の理由を返します。未知の値は `ExitReason::Error` とみなします。
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            0 => ExitReason::Success,
+            2 => ExitReason::ConfigMissing,
+            3 => ExitReason::AuthFailure,
+            4 => ExitReason::NetworkFailure,
+            5 => ExitReason::PartialFailure,
+            6 => ExitReason::AdminRequired,
+            _ => ExitReason::Error,
+        }
+    }
+}
+
+impl From<ExitReason> for ExitCode {
+    fn from(reason: ExitReason) -> Self {
+        ExitCode::from(reason.code())
+    }
+}
+
+/// 特定の終了コードで終了すべきエラー。
+///
+/// `main` は、`AdapterError::Exit` で返されたこの型のエラーであれば、その終了コードで終了します。
+/// それ以外のエラーの終了コードは、`AdapterError::exit_reason` がエラーの種類から決めます。
+#[derive(Debug)]
+pub struct ExitError {
+    /// 終了の理由。
+    pub reason: ExitReason,
+    /// 表示するエラーメッセージ。
+    message: String,
+}
+
+impl ExitError {
+    pub fn new(reason: ExitReason, message: impl Into<String>) -> Self {
+        ExitError {
+            reason,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ExitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ExitError {}
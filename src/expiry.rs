@@ -0,0 +1,125 @@
+//! MyDNS.JPのレコード失効が近づいていることを警告するモジュール。
+//!
+//! MyDNS.JPは、一定期間IPアドレスの通知がないレコードを失効させます。
+//! 通知が黙って失敗し続けていると、ある日突然ドメインが引けなくなるため、
+//! 各アカウントの最後の通知成功からの経過時間を監視し、失効期限に近づくにつれて
+//! 段階的に強い手段で警告します。
+//! - 失効期限の50%: ログに警告を記録する。
+//! - 失効期限の75%と90%: ログにエラーを記録し、Webhook（`--webhook-url`）でも知らせる。
+//!   `notify` で通知した場合と `--toast-monitor` の実行中は、トースト通知でも知らせる（`toast` モジュール）。
+
+use crate::format_msg_log;
+use crate::i18n::MessageKey;
+use crate::logging::{log_error, log_warn};
+use crate::registry::Config;
+use crate::state::{load_state, update_state};
+use crate::webhook::send_expiry_webhook;
+use chrono::{Local, TimeDelta};
+use reqwest::Client;
+
+/// MyDNS.JPがレコードを失効させるまでの期間（時間）。
+pub const MYDNS_EXPIRY_HOURS: i64 = 7 * 24;
+
+/// 失効期限に対する警告の段階。数値が大きいほど深刻です。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ExpiryLevel {
+    /// 失効期限の50%が経過。
+    Notice = 1,
+    /// 失効期限の75%が経過。
+    Warning = 2,
+    /// 失効期限の90%が経過。
+    Critical = 3,
+}
+
+impl ExpiryLevel {
+    /// 最後の通知成功からの経過時間に対応する警告の段階を返します。
+    /// 警告が不要な場合は `None` を返します。
+    fn from_elapsed(elapsed: TimeDelta) -> Option<Self> {
+        let expiry = TimeDelta::hours(MYDNS_EXPIRY_HOURS);
+        if elapsed >= expiry * 9 / 10 {
+            Some(ExpiryLevel::Critical)
+        } else if elapsed >= expiry * 3 / 4 {
+            Some(ExpiryLevel::Warning)
+        } else if elapsed >= expiry / 2 {
+            Some(ExpiryLevel::Notice)
+        } else {
+            None
+        }
+    }
+}
+
+/// `Warning` 以上の段階に上がった警告。CLIでは、トースト通知でも知らせる。
+#[derive(Clone, Debug)]
+pub struct ExpiryAlert {
+    /// 警告の対象のアカウントのMasterID。
+    pub master_id: String,
+    /// 最後の通知成功からの経過時間（時間）。
+    pub hours: i64,
+    /// 失効までの残り時間の見積もり（時間）。
+    pub remaining: i64,
+}
+
+/// 失効期限までの残り時間の見積もり（時間）を返します。
+pub fn remaining_hours(elapsed: TimeDelta) -> i64 {
+    (MYDNS_EXPIRY_HOURS - elapsed.num_hours()).max(0)
+}
+
+/// すべてのアカウントについて失効期限までの余裕を確認し、必要に応じて警告します。
+///
+/// 同じ段階の警告を毎回記録しないよう、記録済みの段階を共有状態に保持し、
+/// 段階が上がったときだけ記録します。通知が成功すると、段階は `state` 側でリセットされます。
+/// `Warning` 以上の段階に上がったアカウントは、Webhookでも知らせ、その警告を返します。
+pub async fn check_expiry_warnings(client: &Client, configs: &[Config]) -> Vec<ExpiryAlert> {
+    let state = load_state().unwrap_or_default();
+    let now = Local::now();
+    let mut alerts = Vec::new();
+
+    for config in configs {
+        let Some(account) = state.accounts.get(&config.master_id) else {
+            continue;
+        };
+        // 一度も成功していないアカウントは、失効期限の起点がわからないため対象外とする。
+        let Some(last_success) = account.last_success else {
+            continue;
+        };
+        let elapsed = now - last_success;
+        let Some(level) = ExpiryLevel::from_elapsed(elapsed) else {
+            continue;
+        };
+        if account.expiry_warn_level >= level as u8 {
+            continue;
+        }
+
+        let remaining = remaining_hours(elapsed);
+        let msg = format!(
+            "[{}] {}",
+            config.master_id,
+            format_msg_log!(
+                MessageKey::LogExpiryWarningFmt,
+                hours = elapsed.num_hours(),
+                remaining = remaining
+            )
+        );
+        // 段階を先に記録し、Webhookの送信中に次のラウンドが始まっても同じ警告を送らないようにする。
+        let master_id = config.master_id.clone();
+        let _ = update_state(|s| {
+            s.accounts.entry(master_id).or_default().expiry_warn_level = level as u8;
+        });
+
+        // 段階が上がるほど、より目立つ手段で警告する。
+        match level {
+            ExpiryLevel::Notice => log_warn(&msg),
+            ExpiryLevel::Warning | ExpiryLevel::Critical => {
+                log_error(&msg);
+                send_expiry_webhook(client, &config.master_id, elapsed.num_hours(), remaining)
+                    .await;
+                alerts.push(ExpiryAlert {
+                    master_id: config.master_id.clone(),
+                    hours: elapsed.num_hours(),
+                    remaining,
+                });
+            }
+        }
+    }
+    alerts
+}
@@ -0,0 +1,502 @@
+//! アカウントの設定をTOMLのファイルに保存する `ConfigStore` の実装。
+//!
+//! 構成管理ツールで設定を配布する場合など、レジストリを編集せずにアカウントを管理できるよう、
+//! `%ProgramData%\MyDNSAdapter\config.toml` にアカウントの設定を保存します。
+//! このファイルがあれば、マシン全体の保存先としてレジストリの代わりに自動的に使います（サービスも同様）。
+//! `--config-file <PATH>` で、CLIから別の場所のファイルを指定することもできます。
+//!
+//! ファイルの形式は `--export` で書き出すTOML形式と同じで、解釈と検証は `transfer` モジュールで行います。
+//! CLIからの変更では、一時ファイルに書き出してから置き換えるため、途中で失敗してもファイルは壊れません。
+//! ただし、ファイル全体を書き直すため、手で書いたコメントや並び順は残りません。
+//!
+//! 全体の設定（`--interval` など）は、引き続きレジストリに保存します。
+//! 通知の結果の記録（最終成功時刻、最後のエラー）と自動停止の記録は、設定ファイルを書き換えないよう、
+//! 同じディレクトリの別のファイル（`config.records`）に保存します。
+//! サービスとCLIが同時に書き込んでも変更が失われないよう、書き込みはロックファイル（`config.toml.lock`）で
+//! プロセス間でも直列化します。
+
+use crate::error::AdapterError;
+use crate::format_msg;
+use crate::i18n::MessageKey;
+use crate::registry::{Config, LastError, LastSuccess};
+use crate::store::{ConfigStore, StoreWatcher};
+use crate::transfer::{format_config_file, parse_config_file};
+use chrono::{DateTime, Local};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::SystemTime;
+
+use windows::Win32::Foundation::{CloseHandle, HANDLE, WAIT_OBJECT_0};
+use windows::Win32::Storage::FileSystem::{
+    FILE_NOTIFY_CHANGE_FILE_NAME, FILE_NOTIFY_CHANGE_LAST_WRITE, FindCloseChangeNotification,
+    FindFirstChangeNotificationW, FindNextChangeNotification,
+};
+use windows::Win32::System::Threading::{CreateEventW, INFINITE, SetEvent, WaitForMultipleObjects};
+use windows::core::HSTRING;
+
+/// 既定の設定ファイルを置く、`%ProgramData%` の下のディレクトリ名。
+const CONFIG_DIR_NAME: &str = "MyDNSAdapter";
+/// 既定の設定ファイルの名前。
+const CONFIG_FILE_NAME: &str = "config.toml";
+/// 通知の結果の記録を保存するファイルの拡張子（`config.records`）。
+const RECORDS_EXTENSION: &str = "records";
+/// 設定ファイルと記録のファイルの読み込みから書き戻しまでを、プロセス内で直列化するためのロック。
+/// 他のプロセスとの直列化には、`FileStore::lock` によるファイルロックを併用する。
+static CONFIG_FILE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 設定ファイルのアカウントの、通知の結果と自動停止の記録。
+#[derive(Clone, Debug, Default)]
+struct AccountRecord {
+    /// 最後に通知に成功した時刻とアドレス。
+    last_success: LastSuccess,
+    /// 最後に通知に失敗した時刻と内容。
+    last_error: Option<LastError>,
+    /// 通知を自動で停止した時刻。
+    suspended: Option<DateTime<Local>>,
+}
+
+/// 既定の設定ファイルのパス（`%ProgramData%\MyDNSAdapter\config.toml`）を返します。
+pub fn default_config_path() -> PathBuf {
+    let program_data =
+        env::var_os("ProgramData").map_or_else(|| PathBuf::from(r"C:\ProgramData"), PathBuf::from);
+    program_data.join(CONFIG_DIR_NAME).join(CONFIG_FILE_NAME)
+}
+
+/// アカウントの設定をTOMLのファイルに保存する `ConfigStore` の実装。
+#[derive(Clone, Debug)]
+pub struct FileStore {
+    /// 設定ファイルのパス。
+    path: PathBuf,
+}
+
+impl FileStore {
+    /// 指定されたパスの設定ファイルを読み書きするストアを作成します。
+    pub fn new(path: PathBuf) -> Self {
+        FileStore { path }
+    }
+
+    /// 設定ファイルを読み込みます。ファイルがない場合は、アカウントがないものとして扱います。
+    fn read(&self) -> Result<Vec<Config>, AdapterError> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        parse_config_file(&text).map_err(|e| {
+            format_msg!(
+                MessageKey::ConfigFileInvalidFmt,
+                path = self.path.display(),
+                error = e
+            )
+            .into()
+        })
+    }
+
+    /// 設定ファイルを読み込み、`update` で変更してから書き戻します。
+    ///
+    /// 一時ファイルに書き出してから置き換えるため、途中で失敗しても元のファイルはそのまま残ります。
+    /// 記録のファイルは `records` で変更し、設定ファイルを書き戻した後に書き戻します。
+    fn update<F>(&self, update: F) -> Result<(), AdapterError>
+    where
+        F: FnOnce(
+            &mut Vec<Config>,
+            &mut BTreeMap<String, AccountRecord>,
+        ) -> Result<(), AdapterError>,
+    {
+        let _guard = CONFIG_FILE_LOCK
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let _file_lock = self.lock()?;
+        let mut configs = self.read()?;
+        let mut records = self.read_records()?;
+        update(&mut configs, &mut records)?;
+        write_replacing(&self.path, &format_config_file(&configs))?;
+        write_replacing(&self.records_path(), &format_records(&records))?;
+        Ok(())
+    }
+
+    /// 設定ファイルと、隣に置いた記録のファイルとロックファイルを削除します。
+    pub fn delete_files(&self) -> io::Result<()> {
+        for path in [self.records_path(), self.lock_path(), self.path.clone()] {
+            match fs::remove_file(&path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// 記録のファイルのパス（`config.records`）を返します。
+    fn records_path(&self) -> PathBuf {
+        self.path.with_extension(RECORDS_EXTENSION)
+    }
+
+    /// 記録のファイルを読み込みます。ファイルがない場合は、記録がないものとして扱います。
+    fn read_records(&self) -> io::Result<BTreeMap<String, AccountRecord>> {
+        match fs::read_to_string(self.records_path()) {
+            Ok(text) => Ok(parse_records(&text)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 記録のファイルからアカウントの記録を読み込みます。読み込めない場合は、記録がないものとして扱います。
+    fn read_record(&self, master_id: &str) -> AccountRecord {
+        self.read_records()
+            .ok()
+            .and_then(|mut records| records.remove(master_id))
+            .unwrap_or_default()
+    }
+
+    /// アカウントの記録を `update` で変更し、記録のファイルに書き戻します。
+    ///
+    /// 通知中にアカウントが削除された場合に記録だけが残らないよう、設定ファイルにあるアカウントにだけ書き込みます。
+    fn update_record<F: FnOnce(&mut AccountRecord)>(
+        &self,
+        master_id: &str,
+        update: F,
+    ) -> Result<(), AdapterError> {
+        let _guard = CONFIG_FILE_LOCK
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let _file_lock = self.lock()?;
+        if !self.read()?.iter().any(|c| c.master_id == master_id) {
+            return Err(format_msg!(MessageKey::AccountNotFoundFmt, id = master_id).into());
+        }
+        let mut records = self.read_records()?;
+        update(records.entry(master_id.to_string()).or_default());
+        write_replacing(&self.records_path(), &format_records(&records))?;
+        Ok(())
+    }
+
+    /// ロックファイルのパス（`config.toml.lock`）を返します。
+    fn lock_path(&self) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(".lock");
+        PathBuf::from(name)
+    }
+
+    /// ロックファイル（`config.toml.lock`）を排他的にロックし、他のプロセスの書き込みが終わるのを待ちます。
+    ///
+    /// ロックは、返された `File` が破棄されたときに解除されます。
+    fn lock(&self) -> io::Result<File> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(self.lock_path())?;
+        file.lock()?;
+        Ok(file)
+    }
+}
+
+/// 一時ファイルに書き出してから置き換え、途中で失敗しても元のファイルが壊れないようにします。
+fn write_replacing(path: &Path, contents: &str) -> io::Result<()> {
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, path)
+}
+
+/// 記録のファイルの内容を、MasterIDをキーとした記録に変換します。
+///
+/// 実行時状態のファイルと同じ、INIファイルに似た単純な形式です。解釈できない行は無視します。
+/// ```text
+/// [account mydns123456]
+/// last_success=2026-01-01T00:00:00+09:00
+/// last_ipv4=203.0.113.1
+/// last_ipv6=2001:db8::1
+/// last_error_time=2026-01-01T00:00:00+09:00
+/// last_error_kind=auth
+/// last_error=401 Unauthorized
+/// suspended=
+/// ```
+fn parse_records(text: &str) -> BTreeMap<String, AccountRecord> {
+    let mut records: BTreeMap<String, AccountRecord> = BTreeMap::new();
+    let mut current: Option<String> = None;
+    let parse_time = |value: &str| {
+        DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|t| t.with_timezone(&Local))
+    };
+
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(id) = line
+            .strip_prefix("[account ")
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            current = Some(id.to_string());
+            continue;
+        }
+        let (Some(id), Some((key, value))) = (&current, line.split_once('=')) else {
+            continue;
+        };
+        let record = records.entry(id.clone()).or_default();
+        match key {
+            "last_success" => record.last_success.time = parse_time(value),
+            "last_ipv4" => record.last_success.ipv4 = value.parse().ok(),
+            "last_ipv6" => record.last_success.ipv6 = value.parse().ok(),
+            "last_error_time" => {
+                record.last_error = parse_time(value).map(|time| LastError {
+                    time,
+                    kind: String::new(),
+                    detail: String::new(),
+                })
+            }
+            "last_error_kind" => {
+                if let Some(error) = &mut record.last_error {
+                    error.kind = value.to_string();
+                }
+            }
+            "last_error" => {
+                if let Some(error) = &mut record.last_error {
+                    error.detail = value.to_string();
+                }
+            }
+            "suspended" => record.suspended = parse_time(value),
+            _ => {}
+        }
+    }
+    records
+}
+
+/// MasterIDをキーとした記録を、記録のファイルの内容に変換します。
+fn format_records(records: &BTreeMap<String, AccountRecord>) -> String {
+    let format_time =
+        |time: Option<DateTime<Local>>| time.map(|t| t.to_rfc3339()).unwrap_or_default();
+    let mut text = String::new();
+    for (id, record) in records {
+        text.push_str(&format!("[account {}]\n", id));
+        text.push_str(&format!(
+            "last_success={}\n",
+            format_time(record.last_success.time)
+        ));
+        if let Some(ipv4) = record.last_success.ipv4 {
+            text.push_str(&format!("last_ipv4={}\n", ipv4));
+        }
+        if let Some(ipv6) = record.last_success.ipv6 {
+            text.push_str(&format!("last_ipv6={}\n", ipv6));
+        }
+        if let Some(error) = &record.last_error {
+            // 1行に1項目の形式のため、内容に含まれる改行は空白に置き換える。
+            text.push_str(&format!(
+                "last_error_time={}\nlast_error_kind={}\nlast_error={}\n",
+                error.time.to_rfc3339(),
+                error.kind,
+                error.detail.replace(['\r', '\n'], " ")
+            ));
+        }
+        text.push_str(&format!("suspended={}\n", format_time(record.suspended)));
+    }
+    text
+}
+
+impl ConfigStore for FileStore {
+    fn load_all(&self) -> Result<Vec<Config>, AdapterError> {
+        self.read()
+    }
+
+    fn save(&self, config: &Config) -> Result<(), AdapterError> {
+        self.update(|configs, _| {
+            match configs.iter_mut().find(|c| c.master_id == config.master_id) {
+                Some(existing) => *existing = config.clone(),
+                None => configs.push(config.clone()),
+            }
+            Ok(())
+        })
+    }
+
+    fn delete(&self, master_id: &str) -> Result<(), AdapterError> {
+        self.update(|configs, records| {
+            let count = configs.len();
+            configs.retain(|c| c.master_id != master_id);
+            if configs.len() == count {
+                return Err(format_msg!(MessageKey::AccountNotFoundFmt, id = master_id).into());
+            }
+            records.remove(master_id);
+            Ok(())
+        })
+    }
+
+    fn rename(&self, old_id: &str, new_id: &str) -> Result<(), AdapterError> {
+        self.update(|configs, records| {
+            // 既存のアカウントを上書きしないよう、新しいMasterIDが使われていないことを先に確かめる。
+            if configs.iter().any(|c| c.master_id == new_id) {
+                return Err(format_msg!(MessageKey::AccountExistsFmt, id = new_id).into());
+            }
+            let config = configs
+                .iter_mut()
+                .find(|c| c.master_id == old_id)
+                .ok_or_else(|| format_msg!(MessageKey::AccountNotFoundFmt, id = old_id))?;
+            config.master_id = new_id.to_string();
+            if let Some(record) = records.remove(old_id) {
+                records.insert(new_id.to_string(), record);
+            }
+            Ok(())
+        })
+    }
+
+    fn watch(&self, on_change: Box<dyn Fn() + Send>) -> Result<StoreWatcher, AdapterError> {
+        Ok(Box::new(FileChangeWatcher::start(&self.path, on_change)?))
+    }
+
+    fn save_last_success(
+        &self,
+        master_id: &str,
+        time: DateTime<Local>,
+        addresses: &[IpAddr],
+    ) -> Result<(), AdapterError> {
+        self.update_record(master_id, |record| {
+            record.last_success.time = Some(time);
+            for address in addresses {
+                match address {
+                    IpAddr::V4(v4) => record.last_success.ipv4 = Some(*v4),
+                    IpAddr::V6(v6) => record.last_success.ipv6 = Some(*v6),
+                }
+            }
+        })
+    }
+
+    fn load_last_success(&self, master_id: &str) -> LastSuccess {
+        self.read_record(master_id).last_success
+    }
+
+    fn save_last_error(
+        &self,
+        master_id: &str,
+        time: DateTime<Local>,
+        kind: &str,
+        detail: &str,
+    ) -> Result<(), AdapterError> {
+        self.update_record(master_id, |record| {
+            record.last_error = Some(LastError {
+                time,
+                kind: kind.to_string(),
+                detail: detail.to_string(),
+            });
+        })
+    }
+
+    fn load_last_error(&self, master_id: &str) -> Option<LastError> {
+        self.read_record(master_id).last_error
+    }
+
+    fn save_suspended(
+        &self,
+        master_id: &str,
+        time: Option<DateTime<Local>>,
+    ) -> Result<(), AdapterError> {
+        self.update_record(master_id, |record| record.suspended = time)
+    }
+
+    fn load_suspended(&self, master_id: &str) -> Option<DateTime<Local>> {
+        self.read_record(master_id).suspended
+    }
+
+    fn location(&self) -> String {
+        self.path.display().to_string()
+    }
+}
+
+/// 設定ファイルの変更の監視を表すハンドル。
+///
+/// 破棄（`drop`）されると監視を終了し、監視用のスレッドの終了を待ちます。
+struct FileChangeWatcher {
+    /// 監視用のスレッドに終了を伝えるイベント。
+    stop_event: HANDLE,
+    /// 変更を待ち受けるスレッド。
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FileChangeWatcher {
+    /// 設定ファイルの変更の監視を開始します。
+    ///
+    /// 変更の通知はディレクトリ単位でしか受け取れないため、設定ファイルのあるディレクトリを監視し、
+    /// 設定ファイルの更新時刻が変わった場合だけ `on_change` を呼び出します。
+    /// 同じディレクトリにあるログファイルへの書き込みでは呼び出しません。
+    fn start<F: Fn() + Send + 'static>(path: &Path, on_change: F) -> io::Result<Self> {
+        let dir = path.parent().unwrap_or(Path::new("."));
+        fs::create_dir_all(dir)?;
+        let path = path.to_path_buf();
+        // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+        // 作成したハンドルは、失敗時はここで、成功時は監視用のスレッドと `drop` でクローズします。
+        unsafe {
+            let change = FindFirstChangeNotificationW(
+                &HSTRING::from(dir.as_os_str()),
+                false,
+                FILE_NOTIFY_CHANGE_FILE_NAME | FILE_NOTIFY_CHANGE_LAST_WRITE,
+            )?;
+            let stop_event = match CreateEventW(None, true, false, None) {
+                Ok(event) => event,
+                Err(e) => {
+                    let _ = FindCloseChangeNotification(change);
+                    return Err(e.into());
+                }
+            };
+
+            // ハンドルはスレッド間で送れない型のため、値として渡してスレッド側で復元する。
+            let (raw_change, raw_stop) = (change.0 as usize, stop_event.0 as usize);
+            let thread = thread::spawn(move || {
+                let change = HANDLE(raw_change as _);
+                let stop_event = HANDLE(raw_stop as _);
+                watch_loop(&path, change, stop_event, on_change);
+                let _ = FindCloseChangeNotification(change);
+            });
+            Ok(FileChangeWatcher {
+                stop_event,
+                thread: Some(thread),
+            })
+        }
+    }
+}
+
+impl Drop for FileChangeWatcher {
+    fn drop(&mut self) {
+        // 監視用のスレッドに終了を伝え、スレッドが終了してからイベントをクローズする。
+        unsafe {
+            let _ = SetEvent(self.stop_event);
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        unsafe {
+            let _ = CloseHandle(self.stop_event);
+        }
+    }
+}
+
+/// 終了を伝えられるまで、ディレクトリの変更を待ち受け、設定ファイルが変わっていればクロージャを呼び出します。
+///
+/// 変更の通知を受け取るたびに、`FindNextChangeNotification` で次の通知を要求します。
+/// 要求に失敗した場合は、監視を終了します。
+unsafe fn watch_loop<F: Fn()>(path: &Path, change: HANDLE, stop_event: HANDLE, on_change: F) {
+    let modified = |path: &Path| -> Option<SystemTime> { fs::metadata(path).ok()?.modified().ok() };
+    let mut last = modified(path);
+    loop {
+        // 終了の要求を先に並べ、変更と同時に届いた場合も終了を優先する。
+        let signaled = unsafe { WaitForMultipleObjects(&[stop_event, change], false, INFINITE) };
+        if signaled.0 != WAIT_OBJECT_0.0 + 1 {
+            return;
+        }
+        let current = modified(path);
+        if current != last {
+            last = current;
+            on_change();
+        }
+        if unsafe { FindNextChangeNotification(change) }.is_err() {
+            return;
+        }
+    }
+}
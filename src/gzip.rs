@@ -0,0 +1,418 @@
+//! ログのアーカイブを圧縮するための、最小限のgzip（RFC 1952）エンコーダー。
+//!
+//! 固定ハフマン符号のDEFLATE（RFC 1951）ブロックと、ハッシュチェーンによるLZ77で圧縮します。
+//! 動的ハフマン符号を使う一般的な実装ほどは縮みませんが、繰り返しの多いログでは十分に効果があり、
+//! 出力は標準的な `gzip` コマンドや各種ツールでそのまま展開できます。
+
+/// LZ77で参照できる過去のデータの範囲（DEFLATEの上限）。
+const WINDOW_SIZE: usize = 32 * 1024;
+/// 一致とみなす最短の長さ。
+const MIN_MATCH: usize = 3;
+/// 一致の最長の長さ（DEFLATEの上限）。
+const MAX_MATCH: usize = 258;
+/// 一致を探すときにたどるハッシュチェーンの最大の長さ。大きいほど縮むが遅くなる。
+const MAX_CHAIN: usize = 64;
+/// ハッシュテーブルのビット数。
+const HASH_BITS: u32 = 15;
+
+/// 長さ符号（257〜285）の基準の長さ。
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+/// 長さ符号の追加ビット数。
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+/// 距離符号（0〜29）の基準の距離。
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+/// 距離符号の追加ビット数。
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// `data` をgzip形式に圧縮したバイト列を返します。
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    // ヘッダー: マジックナンバー、圧縮方式（DEFLATE）、フラグなし、更新時刻なし、OS不明。
+    let mut out = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff];
+    let mut writer = BitWriter {
+        out,
+        acc: 0,
+        bits: 0,
+    };
+    deflate_fixed(data, &mut writer);
+    out = writer.finish();
+    out.extend_from_slice(&crc32(data).to_le_bytes());
+    // 元のサイズは、4GiBを法とした値を記録する決まり。
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out
+}
+
+/// 下位ビットから順にビット列を書き込む、DEFLATE用のライター。
+struct BitWriter {
+    out: Vec<u8>,
+    /// まだバイトとして書き出していないビット。
+    acc: u32,
+    /// `acc` に溜まっているビット数。
+    bits: u32,
+}
+
+impl BitWriter {
+    /// `value` の下位 `count` ビットを書き込みます。
+    fn write_bits(&mut self, value: u32, count: u32) {
+        self.acc |= value << self.bits;
+        self.bits += count;
+        while self.bits >= 8 {
+            self.out.push(self.acc as u8);
+            self.acc >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    /// ハフマン符号を書き込みます。符号は上位ビットから書く決まりのため、反転してから書き込みます。
+    fn write_code(&mut self, code: u32, len: u32) {
+        self.write_bits(code.reverse_bits() >> (32 - len), len);
+    }
+
+    /// 残りのビットをバイト境界まで埋めて書き出し、出力を返します。
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.out.push(self.acc as u8);
+        }
+        self.out
+    }
+}
+
+/// データ全体を、固定ハフマン符号の1つのDEFLATEブロックとして書き込みます。
+fn deflate_fixed(data: &[u8], w: &mut BitWriter) {
+    // BFINAL=1（最後のブロック）、BTYPE=01（固定ハフマン符号）。
+    w.write_bits(1, 1);
+    w.write_bits(1, 2);
+
+    let hash_size = 1usize << HASH_BITS;
+    // ハッシュ値ごとの最新の出現位置と、同じハッシュ値を持つ1つ前の出現位置（+1、0は「なし」）。
+    let mut head = vec![0usize; hash_size];
+    let mut prev = vec![0usize; WINDOW_SIZE];
+    let hash = |pos: usize| -> usize {
+        let v = (u32::from(data[pos]) << 16)
+            | (u32::from(data[pos + 1]) << 8)
+            | u32::from(data[pos + 2]);
+        (v.wrapping_mul(2_654_435_761) >> (32 - HASH_BITS)) as usize
+    };
+    let insert = |pos: usize, head: &mut [usize], prev: &mut [usize]| {
+        if pos + MIN_MATCH <= data.len() {
+            let h = hash(pos);
+            prev[pos % WINDOW_SIZE] = head[h];
+            head[h] = pos + 1;
+        }
+    };
+
+    let mut pos = 0;
+    while pos < data.len() {
+        let (mut best_len, mut best_dist) = (0, 0);
+        if pos + MIN_MATCH <= data.len() {
+            let max_len = MAX_MATCH.min(data.len() - pos);
+            let mut candidate = head[hash(pos)];
+            let mut chain = 0;
+            while candidate > 0 && chain < MAX_CHAIN {
+                let start = candidate - 1;
+                if pos - start > WINDOW_SIZE - 1 {
+                    break;
+                }
+                let len = data[start..]
+                    .iter()
+                    .zip(&data[pos..pos + max_len])
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                if len > best_len {
+                    best_len = len;
+                    best_dist = pos - start;
+                    if len == max_len {
+                        break;
+                    }
+                }
+                candidate = prev[start % WINDOW_SIZE];
+                chain += 1;
+            }
+        }
+
+        if best_len >= MIN_MATCH {
+            write_length(w, best_len);
+            write_distance(w, best_dist);
+            for p in pos..pos + best_len {
+                insert(p, &mut head, &mut prev);
+            }
+            pos += best_len;
+        } else {
+            write_literal(w, data[pos]);
+            insert(pos, &mut head, &mut prev);
+            pos += 1;
+        }
+    }
+    // ブロックの終わり（256）。
+    write_symbol(w, 256);
+}
+
+/// リテラル／長さの記号（0〜287）を、固定ハフマン符号で書き込みます。
+fn write_symbol(w: &mut BitWriter, symbol: u32) {
+    match symbol {
+        0..=143 => w.write_code(0x30 + symbol, 8),
+        144..=255 => w.write_code(0x190 + symbol - 144, 9),
+        256..=279 => w.write_code(symbol - 256, 7),
+        _ => w.write_code(0xc0 + symbol - 280, 8),
+    }
+}
+
+/// 1バイトのリテラルを書き込みます。
+fn write_literal(w: &mut BitWriter, byte: u8) {
+    write_symbol(w, u32::from(byte));
+}
+
+/// 一致の長さ（3〜258）を、長さ符号と追加ビットで書き込みます。
+fn write_length(w: &mut BitWriter, len: usize) {
+    let index = LENGTH_BASE
+        .iter()
+        .rposition(|&base| usize::from(base) <= len)
+        .unwrap_or(0);
+    write_symbol(w, 257 + index as u32);
+    let extra = u32::from(LENGTH_EXTRA[index]);
+    if extra > 0 {
+        w.write_bits((len - usize::from(LENGTH_BASE[index])) as u32, extra);
+    }
+}
+
+/// 一致の距離（1〜32768）を、固定の5ビットの距離符号と追加ビットで書き込みます。
+fn write_distance(w: &mut BitWriter, dist: usize) {
+    let index = DIST_BASE
+        .iter()
+        .rposition(|&base| usize::from(base) <= dist)
+        .unwrap_or(0);
+    w.write_code(index as u32, 5);
+    let extra = u32::from(DIST_EXTRA[index]);
+    if extra > 0 {
+        w.write_bits((dist - usize::from(DIST_BASE[index])) as u32, extra);
+    }
+}
+
+/// gzipのトレーラーに記録するCRC-32（IEEE 802.3）を計算します。
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 下位ビットから順にビット列を読み出す、テスト用のリーダー。
+    struct BitReader<'a> {
+        data: &'a [u8],
+        /// 次に読むビットの位置。
+        pos: usize,
+    }
+
+    impl BitReader<'_> {
+        /// `count` ビットを読み出し、最初に読んだビットを最下位とした値を返します。
+        fn bits(&mut self, count: u32) -> u32 {
+            let mut value = 0;
+            for i in 0..count {
+                let byte = self.data[self.pos / 8];
+                value |= u32::from((byte >> (self.pos % 8)) & 1) << i;
+                self.pos += 1;
+            }
+            value
+        }
+
+        /// 上位ビットから書かれたハフマン符号を、`count` ビット読み出します。
+        fn code(&mut self, count: u32) -> u32 {
+            (0..count).fold(0, |code, _| (code << 1) | self.bits(1))
+        }
+
+        /// 固定ハフマン符号のリテラル／長さの記号を1つ読み出します。
+        fn symbol(&mut self) -> u32 {
+            let mut code = self.code(7);
+            if code <= 0x17 {
+                return 256 + code;
+            }
+            code = (code << 1) | self.bits(1);
+            match code {
+                0x30..=0xbf => return code - 0x30,
+                0xc0..=0xc7 => return 280 + code - 0xc0,
+                _ => {}
+            }
+            code = (code << 1) | self.bits(1);
+            assert!((0x190..=0x1ff).contains(&code), "invalid code {code:#x}");
+            144 + code - 0x190
+        }
+    }
+
+    /// 圧縮の結果を検証するための、テスト用のgzipのデコーダー。
+    ///
+    /// `compress` が書き出す固定ハフマン符号のブロックと、非圧縮のブロックだけを展開できます。
+    /// ヘッダーとトレーラー（CRC-32と元のサイズ）も検証します。
+    fn decompress(gz: &[u8]) -> Vec<u8> {
+        assert!(gz.len() >= 18, "too short");
+        assert_eq!(gz[..4], [0x1f, 0x8b, 8, 0], "unexpected header");
+        let mut reader = BitReader {
+            data: &gz[10..gz.len() - 8],
+            pos: 0,
+        };
+        let mut out: Vec<u8> = Vec::new();
+        loop {
+            let last = reader.bits(1) == 1;
+            match reader.bits(2) {
+                0 => {
+                    reader.pos = reader.pos.div_ceil(8) * 8;
+                    let len = reader.bits(16);
+                    assert_eq!(reader.bits(16), !len & 0xffff);
+                    for _ in 0..len {
+                        out.push(reader.bits(8) as u8);
+                    }
+                }
+                1 => loop {
+                    let symbol = reader.symbol();
+                    match symbol {
+                        0..=255 => out.push(symbol as u8),
+                        256 => break,
+                        _ => {
+                            let index = (symbol - 257) as usize;
+                            let len = usize::from(LENGTH_BASE[index])
+                                + reader.bits(u32::from(LENGTH_EXTRA[index])) as usize;
+                            let index = reader.code(5) as usize;
+                            let dist = usize::from(DIST_BASE[index])
+                                + reader.bits(u32::from(DIST_EXTRA[index])) as usize;
+                            assert!(dist <= out.len(), "distance beyond the output");
+                            assert!(dist <= WINDOW_SIZE, "distance beyond the window");
+                            let start = out.len() - dist;
+                            for i in 0..len {
+                                out.push(out[start + i]);
+                            }
+                        }
+                    }
+                },
+                btype => panic!("unsupported block type {btype}"),
+            }
+            if last {
+                break;
+            }
+        }
+        // 最後のブロックの後は、バイト境界までの埋め草だけが残っているはず。
+        assert_eq!(reader.pos.div_ceil(8), reader.data.len(), "trailing data");
+        let trailer = &gz[gz.len() - 8..];
+        assert_eq!(trailer[..4], crc32(&out).to_le_bytes(), "CRC mismatch");
+        assert_eq!(
+            trailer[4..],
+            (out.len() as u32).to_le_bytes(),
+            "size mismatch"
+        );
+        out
+    }
+
+    /// 圧縮してから展開し、元のデータに戻ることを確かめます。圧縮後のサイズを返します。
+    fn round_trip(data: &[u8]) -> usize {
+        let gz = compress(data);
+        assert_eq!(decompress(&gz), data);
+        gz.len()
+    }
+
+    /// 再現できる疑似乱数のバイト列を作ります（xorshift64）。
+    fn random_bytes(len: usize, mut seed: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                (seed >> 32) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn empty_input_matches_standard_encoding() {
+        let gz = compress(b"");
+        assert_eq!(
+            gz,
+            [
+                0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff, 0x03, 0x00, 0, 0, 0, 0, 0, 0, 0, 0
+            ]
+        );
+        round_trip(b"");
+    }
+
+    #[test]
+    fn crc32_matches_known_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn short_inputs_round_trip() {
+        for data in [
+            &b"a"[..],
+            b"ab",
+            b"abc",
+            b"abcabc",
+            b"[2026-01-01 00:00:00] [INFO] Notified",
+            &[0x00, 0x8f, 0x90, 0xff],
+        ] {
+            round_trip(data);
+        }
+    }
+
+    #[test]
+    fn repetitive_input_round_trips_and_shrinks() {
+        let data = vec![b'x'; 100_000];
+        assert!(round_trip(&data) < 1_000);
+
+        let line = "[2026-01-01 00:00:00] [INFO] [mydns123456] Notified https://ipv4.mydns.jp/login.html: Status 200 OK\n";
+        let data = line.repeat(2_000);
+        assert!(round_trip(data.as_bytes()) < data.len() / 20);
+    }
+
+    #[test]
+    fn random_input_round_trips() {
+        for (len, seed) in [(1, 1), (100, 2), (4_096, 3), (70_000, 4)] {
+            let data = random_bytes(len, seed);
+            // 縮まないデータでも、固定ハフマン符号の増加分（1バイトあたり最大9ビット）に収まる。
+            assert!(round_trip(&data) <= len * 9 / 8 + 32);
+        }
+    }
+
+    #[test]
+    fn input_larger_than_window_round_trips() {
+        // 窓の大きさ付近の距離で繰り返すデータ。窓に収まる距離だけが参照される。
+        for block in [WINDOW_SIZE - 1, WINDOW_SIZE, WINDOW_SIZE + 1] {
+            let chunk = random_bytes(block, block as u64);
+            let data = [&chunk[..], &chunk[..], &chunk[..100]].concat();
+            let size = round_trip(&data);
+            if block < WINDOW_SIZE {
+                assert!(size < block * 9 / 8 + 1_000);
+            }
+        }
+
+        let data: String = (0..5_000)
+            .map(|i| {
+                format!(
+                    "[2026-01-01 00:00:00] [INFO] round {} finished: ok={} failed=0\n",
+                    i,
+                    i % 7
+                )
+            })
+            .collect();
+        assert!(data.len() > 3 * WINDOW_SIZE);
+        round_trip(data.as_bytes());
+    }
+}
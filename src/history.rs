@@ -0,0 +1,194 @@
+//! 通知の試行の履歴を記録・表示するモジュール。
+//!
+//! 通知リクエストを送るたびに、時刻、アカウント、プロトコル、結果、所要時間を
+//! 実行時データのディレクトリ（`%ProgramData%\MyDNSAdapter`）の `mydns.history` に1行ずつ記録します。
+//! 履歴は最新の `MAX_HISTORY_ENTRIES` 件だけを残し、それより古いものから削除します（リングバッファ）。
+//! `history` サブコマンドで表示し、いつ実際に更新されたのかを確認できます。
+//!
+//! サービスとCLIが同時に書き込んでも履歴が失われないよう、読み込みから書き戻しまでを
+//! プロセス内ではミューテックスで、プロセス間ではファイルのロックで直列化します。
+//!
+//! ファイルには1行に1件、RFC 3339形式の時刻、MasterID、プロトコル、結果、所要時間（ミリ秒）を
+//! タブ区切りで記録します。
+//!
+//! `sqlite` フィーチャーを有効にしてビルドし、`--history-db true` を設定した場合は、
+//! すべての試行を `historydb` モジュールのデータベースにも記録し、表示にはそちらを使います。
+
+use crate::console::{Color, paint};
+use crate::format_msg;
+use crate::i18n::{MessageKey, get_msg};
+use crate::state::data_dir;
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+/// 履歴ファイルの名前。
+const HISTORY_FILE_NAME: &str = "mydns.history";
+/// 履歴に残す最大件数。
+const MAX_HISTORY_ENTRIES: usize = 1000;
+/// 履歴ファイルの読み込みから書き戻しまでを、プロセス内で直列化するためのロック。
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+/// 1回の通知の試行の記録。
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// 通知リクエストを送った時刻。
+    pub time: DateTime<Local>,
+    /// アカウントのMasterID。
+    pub master_id: String,
+    /// 通知したプロトコル（`IPv4` / `IPv6`）。
+    pub protocol: String,
+    /// 結果。成功した場合は `OK`、失敗した場合はその内容。
+    pub result: String,
+    /// リクエストの送信から結果が得られるまでの時間。
+    pub latency: Duration,
+}
+
+/// 履歴ファイルのフルパスを取得します。
+fn get_history_path() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join(HISTORY_FILE_NAME))
+}
+
+/// 通知の試行を履歴に記録します。
+///
+/// 履歴は補助的な情報であるため、読み書きの失敗は無視します。
+pub fn record_attempt(entry: HistoryEntry) {
+    #[cfg(feature = "sqlite")]
+    if crate::historydb::is_enabled() {
+        let _ = crate::historydb::insert_attempt(&entry);
+    }
+    let _ = update_history(|entries| entries.push_back(entry));
+}
+
+/// アカウントの履歴を、新しいMasterIDのものに書き換えます。
+pub fn rename_history(old_id: &str, new_id: &str) -> io::Result<()> {
+    #[cfg(feature = "sqlite")]
+    if crate::historydb::is_enabled() {
+        crate::historydb::rename_account(old_id, new_id).map_err(io::Error::other)?;
+    }
+    update_history(|entries| {
+        for entry in entries.iter_mut().filter(|e| e.master_id == old_id) {
+            entry.master_id = new_id.to_string();
+        }
+    })
+}
+
+/// 履歴をクロージャで更新し、最大件数を超えた古いものを削除して保存します。
+fn update_history<F: FnOnce(&mut VecDeque<HistoryEntry>)>(f: F) -> io::Result<()> {
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(get_history_path()?)?;
+    // ロックは、ファイルが閉じられたときに解除される。
+    file.lock()?;
+    let mut entries: VecDeque<HistoryEntry> = read_entries(&mut file)?.into();
+    f(&mut entries);
+    while entries.len() > MAX_HISTORY_ENTRIES {
+        entries.pop_front();
+    }
+
+    let mut text = String::new();
+    for entry in &entries {
+        text.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            entry.time.to_rfc3339(),
+            entry.master_id,
+            entry.protocol,
+            entry.result.replace(['\t', '\r', '\n'], " "),
+            entry.latency.as_millis()
+        ));
+    }
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(text.as_bytes())
+}
+
+/// 履歴ファイルを読み込みます。解釈できない行は無視します。
+fn read_entries(file: &mut File) -> io::Result<Vec<HistoryEntry>> {
+    let mut text = String::new();
+    file.read_to_string(&mut text)?;
+    let entries = text
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let time = DateTime::parse_from_rfc3339(fields.next()?).ok()?;
+            let master_id = fields.next()?.to_string();
+            let protocol = fields.next()?.to_string();
+            let result = fields.next()?.to_string();
+            let latency = Duration::from_millis(fields.next()?.parse().ok()?);
+            Some(HistoryEntry {
+                time: time.with_timezone(&Local),
+                master_id,
+                protocol,
+                result,
+                latency,
+            })
+        })
+        .collect();
+    Ok(entries)
+}
+
+/// 保存されている通知の履歴を、古いものから順に読み込みます。履歴ファイルがない場合は空です。
+fn load_history() -> io::Result<Vec<HistoryEntry>> {
+    let path = get_history_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    read_entries(&mut File::open(&path)?)
+}
+
+/// 新しいものから `count` 件の通知の履歴を、古いものから順に読み込みます。
+///
+/// 履歴のデータベースが有効な場合は、件数の上限がないデータベースから読み込みます。
+/// `account` が指定された場合は、そのアカウントの履歴だけを読み込みます。
+pub fn load_recent(account: Option<&str>, count: usize) -> io::Result<Vec<HistoryEntry>> {
+    #[cfg(feature = "sqlite")]
+    if crate::historydb::is_enabled() {
+        return crate::historydb::load_recent(account, count).map_err(io::Error::other);
+    }
+    let mut entries = load_history()?;
+    entries.retain(|e| account.is_none_or(|id| e.master_id == id));
+    entries.drain(..entries.len().saturating_sub(count));
+    Ok(entries)
+}
+
+/// 通知の履歴を、古いものから順に表示します。
+///
+/// # 引数
+/// * `account` - 指定された場合は、そのアカウントの履歴だけを表示する。
+/// * `count` - 表示する最大件数。新しいものから数える。
+pub fn history_mode(account: Option<&str>, count: usize) -> io::Result<()> {
+    let entries = load_recent(account, count)?;
+
+    println!("{}", get_msg(MessageKey::HistoryTitle));
+    if entries.is_empty() {
+        println!("{}", get_msg(MessageKey::HistoryEmpty));
+        return Ok(());
+    }
+    for entry in &entries {
+        let result = if entry.result == "OK" {
+            paint(&entry.result, Color::Green)
+        } else {
+            paint(&entry.result, Color::Red)
+        };
+        println!(
+            "{}",
+            format_msg!(
+                MessageKey::HistoryRowFmt,
+                time = entry.time.format("%Y-%m-%d %H:%M:%S"),
+                id = entry.master_id,
+                proto = entry.protocol,
+                ms = entry.latency.as_millis(),
+                result = result
+            )
+        );
+    }
+    Ok(())
+}
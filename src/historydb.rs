@@ -0,0 +1,176 @@
+//! 通知の試行の履歴を、SQLiteのデータベースに長期間記録するモジュール。
+//!
+//! `--history-db true` で有効にすると、`history` モジュールのテキストの履歴（最新の1000件）に加えて、
+//! すべての試行を実行時データのディレクトリ（`%ProgramData%\MyDNSAdapter`）の `mydns-history.db` に記録します。
+//! 件数に上限はなく、アカウントと時刻の索引を持つため、記録が増えても検索の速さは変わりません。
+//! 有効にした場合は、`history` の表示、`--status` の直近24時間の集計、メトリクスにこのデータベースを使います。
+//!
+//! サービスとCLIが同時に書き込めるよう、WALモードで開き、ロックが解除されるまで待つ時間を設定します。
+//! このモジュールは、`sqlite` フィーチャーを有効にしてビルドした場合だけ含まれます。
+
+use crate::error::AdapterError;
+use crate::history::HistoryEntry;
+use crate::registry::load_settings;
+use crate::state::data_dir;
+use chrono::{DateTime, Local};
+use rusqlite::{Connection, params};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// データベースファイルの名前。
+const DATABASE_FILE_NAME: &str = "mydns-history.db";
+/// 他のプロセスが書き込み中の場合に、ロックが解除されるまで待つ最大の時間。
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+/// テーブルと索引を作成するSQL。時刻はUNIX時間のミリ秒で記録する。
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS attempts (
+    id INTEGER PRIMARY KEY,
+    time INTEGER NOT NULL,
+    master_id TEXT NOT NULL,
+    protocol TEXT NOT NULL,
+    ok INTEGER NOT NULL,
+    result TEXT NOT NULL,
+    latency_ms INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS attempts_account_time ON attempts (master_id, time);
+CREATE INDEX IF NOT EXISTS attempts_time ON attempts (time);
+";
+
+/// 一定期間の、アカウントごとの試行の集計。
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AttemptSummary {
+    /// 試行した回数（再試行を含む）。
+    pub attempts: u64,
+    /// 成功した回数。
+    pub successes: u64,
+    /// 1回の試行にかかった時間の平均（ミリ秒）。
+    pub average_latency_ms: u64,
+}
+
+/// 履歴をデータベースにも記録する設定になっているかどうかを返します。
+pub fn is_enabled() -> bool {
+    load_settings().is_ok_and(|s| s.history_database)
+}
+
+/// データベースファイルのフルパスを取得します。
+pub fn database_path() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join(DATABASE_FILE_NAME))
+}
+
+/// データベースを開いて、ファイルやテーブルがなければ作成します。
+pub fn initialize() -> Result<(), AdapterError> {
+    open().map(|_| ())
+}
+
+/// データベースを開きます。ファイルやテーブルがなければ作成します。
+fn open() -> Result<Connection, AdapterError> {
+    let conn = Connection::open(database_path()?)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    // `journal_mode` は設定後のモードを行として返すため、`query_row` で実行する。
+    conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))?;
+    conn.execute_batch(SCHEMA)?;
+    Ok(conn)
+}
+
+/// 通知の試行を1件記録します。
+pub fn insert_attempt(entry: &HistoryEntry) -> Result<(), AdapterError> {
+    open()?.execute(
+        "INSERT INTO attempts (time, master_id, protocol, ok, result, latency_ms)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            entry.time.timestamp_millis(),
+            entry.master_id,
+            entry.protocol,
+            entry.result == "OK",
+            entry.result,
+            i64::try_from(entry.latency.as_millis()).unwrap_or(i64::MAX)
+        ],
+    )?;
+    Ok(())
+}
+
+/// 新しいものから `count` 件の試行を、古いものから順に読み込みます。
+///
+/// `account` が指定された場合は、そのアカウントの試行だけを読み込みます。
+pub fn load_recent(account: Option<&str>, count: usize) -> Result<Vec<HistoryEntry>, AdapterError> {
+    const COLUMNS: &str = "SELECT time, master_id, protocol, result, latency_ms FROM attempts";
+    let conn = open()?;
+    let limit = i64::try_from(count).unwrap_or(i64::MAX);
+    // アカウントの索引を使えるよう、アカウントを指定した場合と全体とでSQLを分ける。
+    let rows = match account {
+        Some(id) => conn
+            .prepare(&format!(
+                "{} WHERE master_id = ?1 ORDER BY time DESC, id DESC LIMIT ?2",
+                COLUMNS
+            ))?
+            .query_map(params![id, limit], read_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?,
+        None => conn
+            .prepare(&format!("{} ORDER BY time DESC, id DESC LIMIT ?1", COLUMNS))?
+            .query_map(params![limit], read_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?,
+    };
+    let mut entries = Vec::new();
+    for (time, master_id, protocol, result, latency_ms) in rows {
+        // 範囲外の時刻は、書き換えられた行とみなして読み飛ばす。
+        let Some(time) = DateTime::from_timestamp_millis(time) else {
+            continue;
+        };
+        entries.push(HistoryEntry {
+            time: time.with_timezone(&Local),
+            master_id,
+            protocol,
+            result,
+            latency: Duration::from_millis(u64::try_from(latency_ms).unwrap_or(0)),
+        });
+    }
+    entries.reverse();
+    Ok(entries)
+}
+
+/// `load_recent` で読み込んだ1行の、時刻、MasterID、プロトコル、結果、所要時間。
+type AttemptRow = (i64, String, String, String, i64);
+
+/// `load_recent` の結果の1行を読み取ります。
+fn read_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<AttemptRow> {
+    Ok((
+        row.get(0)?,
+        row.get(1)?,
+        row.get(2)?,
+        row.get(3)?,
+        row.get(4)?,
+    ))
+}
+
+/// `since` 以降の試行を、アカウントごとに集計します。
+pub fn summarize_since(
+    since: DateTime<Local>,
+) -> Result<BTreeMap<String, AttemptSummary>, AdapterError> {
+    let conn = open()?;
+    let mut stmt = conn.prepare(
+        "SELECT master_id, COUNT(*), SUM(ok), AVG(latency_ms) FROM attempts
+         WHERE time >= ?1 GROUP BY master_id",
+    )?;
+    let rows = stmt.query_map(params![since.timestamp_millis()], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            AttemptSummary {
+                attempts: u64::try_from(row.get::<_, i64>(1)?).unwrap_or(0),
+                successes: u64::try_from(row.get::<_, i64>(2)?).unwrap_or(0),
+                average_latency_ms: row.get::<_, f64>(3)?.round() as u64,
+            },
+        ))
+    })?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+/// アカウントの試行を、新しいMasterIDのものに書き換えます。
+pub fn rename_account(old_id: &str, new_id: &str) -> Result<(), AdapterError> {
+    open()?.execute(
+        "UPDATE attempts SET master_id = ?2 WHERE master_id = ?1",
+        params![old_id, new_id],
+    )?;
+    Ok(())
+}
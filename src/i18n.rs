@@ -1,110 +1,868 @@
 //! 国際化（i18n）メッセージを管理するモジュール。
 //!
-//! ユーザーのUI言語設定（日本語かそれ以外か）に応じて、
-//! 対応するメッセージ文字列を返します。
+//! ユーザーのUI言語設定（日本語、英語、中国語、韓国語、ドイツ語、フランス語、スペイン語）に応じて、
+//! 対応するメッセージ文字列を返します。その他の言語では英語を使います。
 //! サービスログなど、ロケールに依存すべきでない場面では、
-//! 英語メッセージを直接取得する関数も提供します。
+//! ログの言語（既定は英語）のメッセージを取得する関数も提供します。
+//!
+//! 表示言語は、`--lang` オプション、環境変数 `MYDNS_LANG`、UI言語設定の順に決まります。
+//! `--lang` または `MYDNS_LANG` で明示的に指定した場合は、ログもその言語で記録します。
+//!
+//! メッセージは `MessageKey` で指定し、実行ファイルに埋め込んだ既定の表から取得します。
+//! 日本語と英語は `messages!` の表に並べて定義し、その他の言語は言語ごとのサブモジュール（`zh` など）に定義します。
+//! 実行ファイルと同じディレクトリの `lang` フォルダーに言語ごとのカタログ（`ja.json`、`de.json` など）を置くと、
+//! 再コンパイルせずに翻訳を追加・修正できます。カタログはキーとメッセージの組のJSONオブジェクトで、
+//! 記述したキーだけが既定のメッセージを置き換えます。
+//!
+//! ```text
+//! {
+//!   "view_title": "--- 現在の設定 ---",
+//!   "not_set": "(なし)"
+//! }
+//! ```
+//!
+//! 値を埋め込むメッセージは、`{id}` や `{error}` のような名前付きのプレースホルダーを使い、
+//! `format_msg!` と `format_msg_log!` で値を指定します。翻訳では、プレースホルダーの順序を自由に変えられます。
+//!
+//! カタログは最初のメッセージの取得時に1回だけ読み込みます。
+//! 表示言語にメッセージがない場合は、キーごとに英語のメッセージを使います。
 
+mod de;
+mod es;
+mod fr;
+mod ko;
+mod zh;
+
+use crate::json::{self, Json};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::env;
+use std::fmt::{self, Write};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::OnceLock;
 use windows::Win32::Globalization::GetUserDefaultUILanguage;
 
-/// ユーザーのUI言語設定に応じて、ローカライズされたメッセージを取得します。
-#[rustfmt::skip]
-#[allow(clippy::if_same_then_else)]
-pub fn get_msg(key: &str) -> &str {
-    // GetUserDefaultUILanguage() はユーザーのデフォルトUI言語のIDを返します。
-    // 1041 (0x0411) は日本語の言語IDです。
-    let is_jp = unsafe { GetUserDefaultUILanguage() == 1041 };
-    get_msg_lang(key, is_jp)
+/// メッセージカタログのフォルダー名。実行ファイルと同じディレクトリに置く。
+const CATALOG_DIR: &str = "lang";
+/// 表示言語を指定する環境変数の名前。
+const LANG_ENV_VAR: &str = "MYDNS_LANG";
+
+/// `--lang` オプションで指定された表示言語。
+static LANG_OVERRIDE: OnceLock<Lang> = OnceLock::new();
+
+/// メッセージの言語。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lang {
+    /// 日本語。
+    Ja,
+    /// 英語。メッセージがない場合のフォールバックにも使う。
+    En,
+    /// 中国語（簡体字）。
+    Zh,
+    /// 韓国語。
+    Ko,
+    /// ドイツ語。
+    De,
+    /// フランス語。
+    Fr,
+    /// スペイン語。
+    Es,
+}
+
+impl Lang {
+    /// サポートするすべての言語。
+    const ALL: [Lang; 7] = [
+        Lang::Ja,
+        Lang::En,
+        Lang::Zh,
+        Lang::Ko,
+        Lang::De,
+        Lang::Fr,
+        Lang::Es,
+    ];
+
+    /// カタログのファイル名に使う言語コード。
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::Ja => "ja",
+            Lang::En => "en",
+            Lang::Zh => "zh",
+            Lang::Ko => "ko",
+            Lang::De => "de",
+            Lang::Fr => "fr",
+            Lang::Es => "es",
+        }
+    }
+
+    /// 言語コード（`ja`、`en-US` など）から言語を決定します。
+    ///
+    /// 大文字と小文字は区別せず、地域の部分（`-` または `_` 以降）は無視します。
+    /// サポートしていない言語の場合は `None` を返します。
+    pub fn from_code(code: &str) -> Option<Self> {
+        let language = code.trim().split(['-', '_']).next().unwrap_or_default();
+        Lang::ALL
+            .into_iter()
+            .find(|lang| lang.code().eq_ignore_ascii_case(language))
+    }
+
+    /// ユーザーのUI言語設定から、表示に使う言語を決定します。
+    ///
+    /// 言語IDの下位10ビット（主言語）で判定するため、地域の違い（zh-CN と zh-TW など）は区別しません。
+    fn from_ui_language() -> Self {
+        // GetUserDefaultUILanguage() はユーザーのデフォルトUI言語のIDを返します。
+        // 主言語のIDは、0x11 が日本語、0x04 が中国語、0x12 が韓国語、
+        // 0x07 がドイツ語、0x0C がフランス語、0x0A がスペイン語です。
+        match unsafe { GetUserDefaultUILanguage() } & 0x3FF {
+            0x11 => Lang::Ja,
+            0x04 => Lang::Zh,
+            0x12 => Lang::Ko,
+            0x07 => Lang::De,
+            0x0C => Lang::Fr,
+            0x0A => Lang::Es,
+            _ => Lang::En,
+        }
+    }
+}
+
+/// メッセージキーと、日本語と英語の埋め込みのメッセージを定義します。
+///
+/// キーごとに `MessageKey` の列挙子を生成するため、存在しないキーや誤ったキーはコンパイルエラーになります。
+/// 日本語と英語の両方を書かなければマクロを展開できないため、どのキーにも両方の言語が必ずあります。
+macro_rules! messages {
+    ($($variant:ident = $name:literal => ($ja:literal, $en:literal),)*) => {
+        /// メッセージのキー。
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum MessageKey {
+            $($variant,)*
+        }
+
+        impl MessageKey {
+            /// すべてのメッセージキー。
+            const ALL: &[MessageKey] = &[$(MessageKey::$variant,)*];
+
+            /// 外部のカタログで使う、キーの名前（`view_title` など）。
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(MessageKey::$variant => $name,)*
+                }
+            }
+
+            /// 埋め込みの日本語と英語のメッセージ。
+            fn embedded(self) -> (&'static str, &'static str) {
+                match self {
+                    $(MessageKey::$variant => ($ja, $en),)*
+                }
+            }
+        }
+    };
+}
+
+impl MessageKey {
+    /// キーの名前からメッセージキーを求めます。存在しない名前の場合は `None` を返します。
+    fn from_name(name: &str) -> Option<Self> {
+        MessageKey::ALL
+            .iter()
+            .copied()
+            .find(|key| key.name() == name)
+    }
+}
+
+/// ある言語のメッセージの表。キーからメッセージを引く。
+type Catalog = HashMap<MessageKey, Cow<'static, str>>;
+
+/// `--lang` オプションで指定された表示言語を設定します。引数の解析後に1回だけ呼び出します。
+pub fn set_lang_override(lang: Lang) {
+    let _ = LANG_OVERRIDE.set(lang);
+}
+
+/// `--lang` オプションまたは環境変数 `MYDNS_LANG` で、明示的に指定された表示言語を返します。
+///
+/// 環境変数の値がサポートしていない言語の場合は、指定されていないものとみなします。
+pub fn explicit_lang() -> Option<Lang> {
+    static ENV_LANG: OnceLock<Option<Lang>> = OnceLock::new();
+    LANG_OVERRIDE.get().copied().or_else(|| {
+        *ENV_LANG.get_or_init(|| {
+            env::var(LANG_ENV_VAR)
+                .ok()
+                .and_then(|v| Lang::from_code(&v))
+        })
+    })
 }
 
-/// 常に英語のメッセージを取得します。
+/// 表示言語に応じて、ローカライズされたメッセージを取得します。
+///
+/// 明示的に指定された言語がなければ、ユーザーのUI言語設定に従います。
+pub fn get_msg(key: MessageKey) -> &'static str {
+    get_msg_lang(key, explicit_lang().unwrap_or_else(Lang::from_ui_language))
+}
+
+/// ログの言語のメッセージを取得します。
 ///
 /// サービスログなど、表示環境の言語設定に依存すべきでない場合に使用します。
-#[rustfmt::skip]
-#[allow(clippy::if_same_then_else)]
-pub fn get_msg_en(key: &str) -> &str {
-    get_msg_lang(key, false)
+/// 表示言語が明示的に指定されていればその言語、そうでなければ英語です。
+pub fn get_msg_log(key: MessageKey) -> &'static str {
+    get_msg_lang(key, explicit_lang().unwrap_or(Lang::En))
+}
+
+/// ローカライズされたメッセージを取得して、名前付きのプレースホルダーを値に置き換えます。
+///
+/// `format_msg!(MessageKey::AccountExistsFmt, id = master_id)` のように、`format!` と同じ形式で値を指定します。
+/// 値には `Display` を実装した任意の型を指定できます。
+#[macro_export]
+macro_rules! format_msg {
+    ($key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::format_template(
+            $crate::i18n::get_msg($key),
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),*],
+        )
+    };
+}
+
+/// ログの言語のメッセージを取得して、名前付きのプレースホルダーを値に置き換えます。
+///
+/// 値の指定方法は `format_msg!` と同じです。
+#[macro_export]
+macro_rules! format_msg_log {
+    ($key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::format_template(
+            $crate::i18n::get_msg_log($key),
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),*],
+        )
+    };
 }
 
-/// メッセージキーとロケール（日本語か否か）に基づいて、具体的なメッセージ文字列を返します。
+/// メッセージの `{id}` のような名前付きのプレースホルダーを、名前が一致する値に置き換えます。
 ///
-/// この関数は、アプリケーション内で使用されるすべての静的文字列を集中管理します。
-/// `#[rustfmt::skip]` と `#[allow(clippy::if_same_then_else)]` は、
-/// この巨大なmatch文の可読性を保つために意図的に使用されています。
-#[rustfmt::skip]
-#[allow(clippy::if_same_then_else)]
-fn get_msg_lang(key: &str, is_jp: bool) -> &str {
-    match key {
-        // main.rs
-        "config_title" => if is_jp { "--- MyDNS Adapter 設定 ---" } else { "--- MyDNS Adapter Configuration ---" },
-        "config_loaded" => if is_jp { "\n現在の設定を読み込みました。変更しない項目はEnterキーを押してください。" } else { "\nCurrent configuration loaded. Press Enter to keep current values." },
-        "master_id_prompt" => if is_jp { "MasterID" } else { "MasterID" },
-        "password_prompt" => if is_jp { "パスワード" } else { "Password" },
-        "ipv4_notify_prompt" => if is_jp { "IPv4通知を有効にしますか？" } else { "Enable IPv4 notification?" },
-        "ipv6_notify_prompt" => if is_jp { "IPv6通知を有効にしますか？" } else { "Enable IPv6 notification?" },
-        "registry_save_success" => if is_jp { "\n[成功] 設定をレジストリに保存しました。" } else { "\n[Success] Configuration saved to registry." },
-        "registry_save_fail_fmt" => if is_jp { "\n[失敗] レジストリ保存エラー: {}" } else { "\n[Failed] Registry save error: {}" },
-        "input_prompt_pw_fmt" => if is_jp { "{}を入力してください (現在値: {}, 変更しない場合はEnter): " } else { "Enter {} (Current: {}, Enter to keep): " },
-        "input_prompt_fmt" => if is_jp { "{}を入力してください (現在値: {}): " } else { "Enter {} (Current: {}): " },
-        "input_prompt_new_fmt" => if is_jp { "{}を入力してください: " } else { "Enter {}: " },
-        "not_set" => if is_jp { "(未設定)" } else { "(Not set)" },
-        "yes_no_prompt_fmt" => if is_jp { "{} (現在値: {}) {}: " } else { "{} (Current: {}) {}: " },
-        "yes_no_hint_true" => if is_jp { "(Y/n)" } else { "(Y/n)" },
-        "yes_no_hint_false" => if is_jp { "(y/N)" } else { "(y/N)" },
-        "yes_no_invalid" => if is_jp { "'y' または 'n' を入力するか、Enterキーを押してください。" } else { "Please enter 'y' or 'n', or press Enter." },
-        "view_title" => if is_jp { "--- 現在のMyDNS Adapter設定 ---" } else { "--- Current MyDNS Settings ---" },
-        "view_master_id_fmt" => if is_jp { "MasterID: {}" } else { "MasterID: {}" },
-        "view_password_fmt" => if is_jp { "パスワード: {}" } else { "Password: {}" },
-        "view_ipv4_fmt" => if is_jp { "IPv4 Notify: {}" } else { "IPv4 Notify: {}" },
-        "view_ipv6_fmt" => if is_jp { "IPv6 Notify: {}" } else { "IPv6 Notify: {}" },
-        "yes" => if is_jp { "Yes" } else { "Yes" },
-        "no" => if is_jp { "No" } else { "No" },
-        "view_no_accounts" => if is_jp { "アカウントが設定されていません。" } else { "No accounts are configured." },
-        "view_list_fmt" => if is_jp { "MasterID: {id},  パスワード: {pw},  IPv4 Notify: {v4},  IPv6 Notify: {v6}" } else { "MasterID: {id},  Password: {pw},  IPv4 Notify: {v4},  IPv6 Notify: {v6}" },
-        "add_title" => if is_jp { "--- 新規アカウント追加 ---" } else { "--- Add New Account ---" },
-        "edit_title" => if is_jp { "--- アカウント編集 ---" } else { "--- Edit Account ---" },
-        "remove_title" => if is_jp { "--- アカウント削除 ---" } else { "--- Remove Account ---" },
-        "account_exists_fmt" => if is_jp { "アカウント '{}' は既に存在します。" } else { "Account '{}' already exists." },
-        "account_not_found_fmt" => if is_jp { "アカウント '{}' は見つかりませんでした。" } else { "Account '{}' not found." },
-        "select_account_prompt" => if is_jp { "編集するアカウントを選択してください:" } else { "Select an account to edit:" },
-        "select_account_index_prompt" => if is_jp { "番号またはMasterIDを入力してください: " } else { "Enter number or MasterID: " },
-        "invalid_selection" => if is_jp { "無効な選択です。" } else { "Invalid selection." },
-        "confirm_remove_fmt" => if is_jp { "本当にアカウント '{}' を削除しますか？" } else { "Are you sure you want to remove account '{}'?" },
-        "confirm_prompt_fmt" => if is_jp { "{} {}: " } else { "{} {}: " },
-        "remove_success" => if is_jp { "[成功] アカウントを削除しました。" } else { "[Success] Account removed successfully." },
-        "remove_fail_fmt" => if is_jp { "[失敗] アカウント削除エラー: {}" } else { "[Failed] Failed to remove account: {}" },
-        "add_success" => if is_jp { "[成功] アカウントを追加しました。" } else { "[Success] Account added successfully." },
-        "no_accounts_add_prompt" => if is_jp { "アカウントが見つかりません。新規作成しますか？" } else { "No accounts found. Create new?" },
-        "operation_cancelled" => if is_jp { "操作をキャンセルしました。" } else { "Operation cancelled." },
-        "edit_target_fmt" => if is_jp { "対象アカウント: {}" } else { "Target Account: {}" },
-        "invalid_master_id_prefix" => if is_jp { "MasterIDは 'mydns' で始まる必要があります。" } else { "MasterID must start with 'mydns'." },
-
-        // winservice.rs
-        "admin_required_install" => if is_jp { "サービスのインストールには管理者権限が必要です。管理者として実行してください。" } else { "Administrator privileges are required to install the service. Please run as administrator." },
-        "service_installing_fmt" => if is_jp { "サービス '{}' をインストールしています..." } else { "Service '{}' installing..." },
-        "service_installed_fmt" => if is_jp { "サービス '{}' が正常にインストールされ、開始されました。" } else { "Service '{}' installed and started successfully." },
-        "admin_required_uninstall" => if is_jp { "サービスのアンインストールには管理者権限が必要です。管理者として実行してください。" } else { "Administrator privileges are required to uninstall the service. Please run as administrator." },
-        "service_not_installed_fmt" => if is_jp { "サービス '{}' はインストールされていません。" } else { "Service '{}' is not installed." },
-        "service_stopping_fmt" => if is_jp { "サービス '{}' を停止しています..." } else { "Stopping service '{}'..." },
-        "service_stopped" => if is_jp { "サービスが停止しました。" } else { "Service stopped." },
-        "service_waiting_stop" => if is_jp { "サービスの停止を待機しています..." } else { "Waiting for service to stop..." },
-        "service_not_running" => if is_jp { "サービスが起動していません。" } else { "Service is not running." },
-        "service_uninstalled_fmt" => if is_jp { "サービス '{}' が正常にアンインストールされました。" } else { "Service '{}' uninstalled successfully." },
-        "log_service_failed_fmt" => if is_jp { "サービスの実行に失敗しました: {}" } else { "Service failed to run: {}" },
-        "log_service_started" => if is_jp { "サービスを開始しました。" } else { "Service started." },
-        "log_service_config_missing" => if is_jp { "MasterIDまたはパスワードが設定されていません。サービスを停止します。" } else { "MasterID or Password is not set. Service will stop." },
-        "log_service_stopping" => if is_jp { "サービスを停止します。" } else { "Service stopping." },
-        "admin_required_restart" => if is_jp { "サービスの再起動には管理者権限が必要です。管理者として実行してください。" } else { "Administrator privileges are required to restart the service. Please run as administrator." },
-        "service_restarted_successfully" => if is_jp { "サービスを再起動しました。" } else { "Service restarted successfully." },
-
-        // notify.rs
-        "log_notify_start" => if is_jp { "即時通知を開始します。" } else { "Starting immediate notification." },
-        "log_config_missing" => if is_jp { "MasterIDまたはパスワードが設定されていません。先に設定モードを実行してください。" } else { "MasterID or Password is not set. Please run configuration mode first." },
-        "log_notify_finish" => if is_jp { "即時通知が完了しました。" } else { "Immediate notification finished." },
-        "log_ipv4_fail_fmt" => if is_jp { "IPv4通知に失敗しました: {}" } else { "IPv4 Notification failed: {}" },
-        "log_ipv6_fail_fmt" => if is_jp { "IPv6通知に失敗しました: {}" } else { "IPv6 Notification failed: {}" },
-        "log_notify_status_fmt" => if is_jp { "通知完了 {}: ステータス {}" } else { "Notified {}: Status {}" },
-
-        _ => key,
+/// メッセージを先頭から1回だけ走査するため、値に `{}` や `{id}` が含まれていても、それ以上は置き換えません。
+/// 値の指定がないプレースホルダーは、そのまま残します。
+pub fn format_template(template: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let arg = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            args.iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, value)| (value, end))
+        });
+        match arg {
+            Some((value, end)) => {
+                let _ = write!(out, "{}", value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
     }
+    out.push_str(rest);
+    out
+}
+
+/// メッセージキーと言語に基づいて、具体的なメッセージ文字列を返します。
+///
+/// 指定した言語のカタログにキーがなければ、英語のカタログのメッセージを返します。
+fn get_msg_lang(key: MessageKey, lang: Lang) -> &'static str {
+    let catalogs = catalogs();
+    catalogs[lang as usize]
+        .get(&key)
+        .or_else(|| catalogs[Lang::En as usize].get(&key))
+        .map_or(key.name(), |message| message.as_ref())
+}
+
+/// 言語ごとのカタログを返します。初回の呼び出しで、埋め込みの表と外部のカタログから作成します。
+fn catalogs() -> &'static [Catalog; Lang::ALL.len()] {
+    static CATALOGS: OnceLock<[Catalog; Lang::ALL.len()]> = OnceLock::new();
+    CATALOGS.get_or_init(load_catalogs)
+}
+
+/// 埋め込みの表からカタログを作成し、外部のカタログがあればその内容で上書きします。
+///
+/// ログの記録はメッセージの取得を伴うため、ここでは使えません。
+/// 外部のカタログを読み込めない場合は、標準エラー出力に警告を表示して、埋め込みの表だけを使います。
+fn load_catalogs() -> [Catalog; Lang::ALL.len()] {
+    let mut catalogs: [Catalog; Lang::ALL.len()] = Default::default();
+    for &key in MessageKey::ALL {
+        let (ja, en) = key.embedded();
+        catalogs[Lang::Ja as usize].insert(key, Cow::Borrowed(ja));
+        catalogs[Lang::En as usize].insert(key, Cow::Borrowed(en));
+    }
+    let translations = [
+        (Lang::Zh, zh::MESSAGES),
+        (Lang::Ko, ko::MESSAGES),
+        (Lang::De, de::MESSAGES),
+        (Lang::Fr, fr::MESSAGES),
+        (Lang::Es, es::MESSAGES),
+    ];
+    for (lang, messages) in translations {
+        for &(key, message) in messages {
+            catalogs[lang as usize].insert(key, Cow::Borrowed(message));
+        }
+    }
+
+    let Some(dir) = catalog_dir() else {
+        return catalogs;
+    };
+    for lang in Lang::ALL {
+        let path = dir.join(format!("{}.json", lang.code()));
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => {
+                eprintln!("Warning: could not read {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        match parse_catalog(&text) {
+            Ok(entries) => {
+                let catalog = &mut catalogs[lang as usize];
+                for (name, message) in entries {
+                    match MessageKey::from_name(&name) {
+                        Some(key) => {
+                            catalog.insert(key, Cow::Owned(message));
+                        }
+                        None => eprintln!(
+                            "Warning: unknown message key \"{}\" in {}",
+                            name,
+                            path.display()
+                        ),
+                    }
+                }
+            }
+            Err(e) => eprintln!("Warning: ignored {}: {}", path.display(), e),
+        }
+    }
+    catalogs
+}
+
+/// 外部のカタログを置くディレクトリ（実行ファイルと同じディレクトリの `lang`）を返します。
+fn catalog_dir() -> Option<PathBuf> {
+    let exe = env::current_exe().ok()?;
+    Some(exe.parent()?.join(CATALOG_DIR))
+}
+
+/// カタログのJSONを解釈し、キーとメッセージの組を返します。
+///
+/// トップレベルはオブジェクトで、値はすべて文字列でなければなりません。
+fn parse_catalog(text: &str) -> Result<Vec<(String, String)>, String> {
+    let Json::Object(members) = json::parse(text.trim_start_matches('\u{feff}'))? else {
+        return Err("top level must be an object".to_string());
+    };
+    members
+        .into_iter()
+        .map(|(key, value)| match value {
+            Json::String(message) => Ok((key, message)),
+            _ => Err(format!("value for \"{}\" must be a string", key)),
+        })
+        .collect()
+}
+
+// 埋め込みのメッセージの表。列挙子、外部のカタログで使うキーの名前、日本語、英語の順に並べる。
+// アプリケーション内で使用されるすべての静的文字列を集中管理します。
+messages! {
+    // main.rs
+    LangInvalid = "lang_invalid" => ("サポートしていない言語です。ja、en、zh、ko、de、fr、es のいずれかを指定してください。", "Unsupported language. Use ja, en, zh, ko, de, fr or es."),
+    ConfigTitle = "config_title" => ("--- MyDNS Adapter 設定 ---", "--- MyDNS Adapter Configuration ---"),
+    ConfigLoaded = "config_loaded" => ("\n現在の設定を読み込みました。変更しない項目はEnterキーを押してください。", "\nCurrent configuration loaded. Press Enter to keep current values."),
+    MasterIdPrompt = "master_id_prompt" => ("MasterID", "MasterID"),
+    PasswordPrompt = "password_prompt" => ("パスワード", "Password"),
+    Ipv4NotifyPrompt = "ipv4_notify_prompt" => ("IPv4通知を有効にしますか？", "Enable IPv4 notification?"),
+    Ipv6NotifyPrompt = "ipv6_notify_prompt" => ("IPv6通知を有効にしますか？", "Enable IPv6 notification?"),
+    KeepAlivePrompt = "keep_alive_prompt" => ("強制通知の間隔（時間、0で無効）", "Forced keep-alive interval in hours (0 to disable)"),
+    NumberInvalid = "number_invalid" => ("0以上の整数を入力してください。", "Please enter a non-negative integer."),
+    RegistrySaveSuccess = "registry_save_success" => ("\n[成功] 設定をレジストリに保存しました。", "\n[Success] Configuration saved to registry."),
+    RegistrySaveFailFmt = "registry_save_fail_fmt" => ("\n[失敗] レジストリ保存エラー: {error}", "\n[Failed] Registry save error: {error}"),
+    InputPromptPwFmt = "input_prompt_pw_fmt" => ("{item}を入力してください (現在値: {current}, 変更しない場合はEnter): ", "Enter {item} (Current: {current}, Enter to keep): "),
+    InputPromptFmt = "input_prompt_fmt" => ("{item}を入力してください (現在値: {current}): ", "Enter {item} (Current: {current}): "),
+    InputPromptNewFmt = "input_prompt_new_fmt" => ("{item}を入力してください: ", "Enter {item}: "),
+    NotSet = "not_set" => ("(未設定)", "(Not set)"),
+    YesNoPromptFmt = "yes_no_prompt_fmt" => ("{question} (現在値: {current}) {hint}: ", "{question} (Current: {current}) {hint}: "),
+    YesNoHintTrue = "yes_no_hint_true" => ("(Y/n)", "(Y/n)"),
+    YesNoHintFalse = "yes_no_hint_false" => ("(y/N)", "(y/N)"),
+    YesNoInvalid = "yes_no_invalid" => ("'y' または 'n'（「はい」または「いいえ」）を入力するか、Enterキーを押してください。", "Please enter 'y' or 'n', or press Enter."),
+    YesNoAffirmativeWords = "yes_no_affirmative_words" => ("y,yes,はい,うん", "y,yes"),
+    YesNoNegativeWords = "yes_no_negative_words" => ("n,no,いいえ,いや", "n,no"),
+    ViewTitle = "view_title" => ("--- 現在のMyDNS Adapter設定 ---", "--- Current MyDNS Settings ---"),
+    ViewMasterIdFmt = "view_master_id_fmt" => ("MasterID: {id}", "MasterID: {id}"),
+    ViewPasswordFmt = "view_password_fmt" => ("パスワード: {password}", "Password: {password}"),
+    ViewIpv4Fmt = "view_ipv4_fmt" => ("IPv4 Notify: {value}", "IPv4 Notify: {value}"),
+    ViewIpv6Fmt = "view_ipv6_fmt" => ("IPv6 Notify: {value}", "IPv6 Notify: {value}"),
+    Yes = "yes" => ("Yes", "Yes"),
+    No = "no" => ("No", "No"),
+    ViewNoAccounts = "view_no_accounts" => ("アカウントが設定されていません。", "No accounts are configured."),
+    ViewListFmt = "view_list_fmt" => ("MasterID: {id},  パスワード: {pw},  IPv4 Notify: {v4},  IPv6 Notify: {v6}", "MasterID: {id},  Password: {pw},  IPv4 Notify: {v4},  IPv6 Notify: {v6}"),
+    AddTitle = "add_title" => ("--- 新規アカウント追加 ---", "--- Add New Account ---"),
+    EditTitle = "edit_title" => ("--- アカウント編集 ---", "--- Edit Account ---"),
+    RemoveTitle = "remove_title" => ("--- アカウント削除 ---", "--- Remove Account ---"),
+    AccountExistsFmt = "account_exists_fmt" => ("アカウント '{id}' は既に存在します。", "Account '{id}' already exists."),
+    AccountNotFoundFmt = "account_not_found_fmt" => ("アカウント '{id}' は見つかりませんでした。", "Account '{id}' not found."),
+    SelectAccountPrompt = "select_account_prompt" => ("編集するアカウントを選択してください:", "Select an account to edit:"),
+    SelectAccountIndexPrompt = "select_account_index_prompt" => ("番号またはMasterIDを入力してください: ", "Enter number or MasterID: "),
+    InvalidSelection = "invalid_selection" => ("無効な選択です。", "Invalid selection."),
+    RemoveNeedsYes = "remove_needs_yes" => ("標準入力が端末ではないため、削除の確認ができません。確認せずに削除するには --yes を指定してください。", "Cannot confirm the removal because standard input is not a terminal. Use --yes to remove without confirmation."),
+    ConfirmRemoveFmt = "confirm_remove_fmt" => ("本当にアカウント '{id}' を削除しますか？", "Are you sure you want to remove account '{id}'?"),
+    ConfirmPromptFmt = "confirm_prompt_fmt" => ("{question} {hint}: ", "{question} {hint}: "),
+    RemoveSuccess = "remove_success" => ("[成功] アカウントを削除しました。", "[Success] Account removed successfully."),
+    RemoveFailFmt = "remove_fail_fmt" => ("[失敗] アカウント削除エラー: {error}", "[Failed] Failed to remove account: {error}"),
+    BatchRowErrorFmt = "batch_row_error_fmt" => ("{line}行目: {error}", "Line {line}: {error}"),
+    BatchInvalidFmt = "batch_invalid_fmt" => ("{count}行に問題があるため、アカウントを追加しませんでした。", "No accounts were added because {count} line(s) have errors."),
+    BatchEmpty = "batch_empty" => ("追加するアカウントがありません。", "There are no accounts to add."),
+    BatchSuccessFmt = "batch_success_fmt" => ("[成功] {count}件のアカウントを追加しました。", "[Success] Added {count} account(s)."),
+    BatchColumnCountFmt = "batch_column_count_fmt" => ("列の数が正しくありません（{count}列）。「MasterID,パスワード,IPv4,IPv6」の形式で記述してください。", "Wrong number of columns ({count}). Use the form \"MasterID,password,IPv4,IPv6\"."),
+    BatchInvalidFlagFmt = "batch_invalid_flag_fmt" => ("{name} の値を解釈できません: {value}（yes / no を指定してください）", "Invalid {name} value: {value} (use yes or no)"),
+    BatchUnterminatedQuote = "batch_unterminated_quote" => ("\" で囲んだ列が閉じられていません。", "A quoted column is not closed."),
+    RenameSuccessFmt = "rename_success_fmt" => ("[成功] アカウント '{old}' のMasterIDを '{new}' に変更しました。", "[Success] Renamed account '{old}' to '{new}'."),
+    AccountEnabledFmt = "account_enabled_fmt" => ("[成功] アカウント '{id}' の通知を再開しました。", "[Success] Resumed notifications for account '{id}'."),
+    AccountNotSuspendedFmt = "account_not_suspended_fmt" => ("アカウント '{id}' の通知は停止されていません。", "Notifications for account '{id}' are not suspended."),
+    DryRunTitle = "dry_run_title" => ("[ドライラン] 次のリクエストを送信します（実際には送信しません）。", "[Dry run] The following requests would be sent (nothing is sent):"),
+    DryRunRequestFmt = "dry_run_request_fmt" => ("  {id} {proto}: {method} {url}  （{auth}）", "  {id} {proto}: {method} {url}  ({auth})"),
+    DryRunBasicAuthFmt = "dry_run_basic_auth_fmt" => ("Basic認証: {user} / ********", "Basic auth: {user} / ********"),
+    DryRunLoginAuthFmt = "dry_run_login_auth_fmt" => ("ログイン: {user} / ********（平文）", "Login: {user} / ******** (plaintext)"),
+    DryRunNoAuth = "dry_run_no_auth" => ("認証なし", "no credentials"),
+    DryRunBindFmt = "dry_run_bind_fmt" => ("    送信元: {addr}", "    Source address: {addr}"),
+    DryRunSkippedFmt = "dry_run_skipped_fmt" => ("  {id} {proto}: 送信しません。{reason}", "  {id} {proto}: not sent. {reason}"),
+    DryRunSuspended = "dry_run_suspended" => ("認証エラーが続いたため、通知を停止しています（--force で送信します）。", "Notifications are suspended after repeated authentication failures (--force sends anyway)."),
+    DryRunPrivateIpv4 = "dry_run_private_ipv4" => ("現在のIPv4アドレスがインターネットから届かないアドレスのため、通知を見送ります。", "The current IPv4 address is not reachable from the internet, so the update is skipped."),
+    DryRunNone = "dry_run_none" => ("送信するリクエストはありません。", "No requests would be sent."),
+    ElevationConfirm = "elevation_confirm" => ("この操作には管理者権限が必要です。管理者として実行し直しますか？", "This operation requires administrator privileges. Run it again as administrator?"),
+    ElevationRequired = "elevation_required" => ("この操作には管理者権限が必要です。管理者としてコマンドプロンプトを開いて実行してください。", "This operation requires administrator privileges. Run it from an elevated command prompt."),
+    ElevationCancelled = "elevation_cancelled" => ("管理者としての実行がキャンセルされました。", "Running as administrator was cancelled."),
+    ElevatedPressEnter = "elevated_press_enter" => ("\nEnterキーを押すとウィンドウを閉じます...", "\nPress Enter to close this window..."),
+    ServicePasswordPromptFmt = "service_password_prompt_fmt" => ("アカウント '{account}' のパスワード", "the password for account '{account}'"),
+    ServicePasswordNotNeededFmt = "service_password_not_needed_fmt" => ("アカウント '{account}' にはパスワードを指定できません。", "A password cannot be specified for account '{account}'."),
+    ConfirmPurge = "confirm_purge" => ("保存されているすべてのアカウントと設定、ログファイルを削除します。元に戻せません。よろしいですか？", "All stored accounts, settings and log files will be deleted. This cannot be undone. Continue?"),
+    PurgeNeedsYes = "purge_needs_yes" => ("標準入力が端末ではないため、削除の確認ができません。確認せずに削除するには --yes を指定してください。", "Cannot confirm the purge because standard input is not a terminal. Use --yes to purge without confirmation."),
+    PurgeSuccess = "purge_success" => ("[成功] 保存されていたアカウントと設定、ログファイルを削除しました。", "[Success] Stored accounts, settings and log files were deleted."),
+    AddSuccess = "add_success" => ("[成功] アカウントを追加しました。", "[Success] Account added successfully."),
+    NoAccountsAddPrompt = "no_accounts_add_prompt" => ("アカウントが見つかりません。新規作成しますか？", "No accounts found. Create new?"),
+    OperationCancelled = "operation_cancelled" => ("操作をキャンセルしました。", "Operation cancelled."),
+    EditTargetFmt = "edit_target_fmt" => ("対象アカウント: {id}", "Target Account: {id}"),
+    ViewRuntimeFmt = "view_runtime_fmt" => ("    状態: {state},  最終試行: {time},  結果: {result}", "    State: {state},  Last attempt: {time},  Result: {result}"),
+    ViewRuntimeInFlight = "view_runtime_in_flight" => ("通知中", "Notifying"),
+    ViewRuntimeIdle = "view_runtime_idle" => ("待機中", "Idle"),
+    ViewPaused = "view_paused" => ("\n[注意] 定期通知は一時停止中です。--resume で再開できます。", "\n[Note] Periodic notification is paused. Use --resume to resume."),
+    PauseSuccess = "pause_success" => ("[成功] 定期通知を一時停止しました。", "[Success] Periodic notification paused."),
+    ResumeSuccess = "resume_success" => ("[成功] 定期通知を再開しました。", "[Success] Periodic notification resumed."),
+    ViewIntervalFmt = "view_interval_fmt" => ("定期通知の間隔: {minutes}分", "Notification interval: {minutes} minutes"),
+    IntervalSetFmt = "interval_set_fmt" => ("定期通知の間隔を{minutes}分に設定しました。稼働中のサービスには、次回の定期通知の後から反映されます。", "Notification interval set to {minutes} minutes. A running service applies it after its next scheduled notification."),
+    IntervalOutOfRangeFmt = "interval_out_of_range_fmt" => ("定期通知の間隔は{min}分から{max}分の範囲で指定してください。", "The notification interval must be between {min} and {max} minutes."),
+    ViewRetryFmt = "view_retry_fmt" => ("通知の最大試行回数: {count}回", "Maximum notification attempts: {count}"),
+    RetrySetFmt = "retry_set_fmt" => ("通知の最大試行回数を{count}回に設定しました。", "Maximum notification attempts set to {count}."),
+    RetryOutOfRangeFmt = "retry_out_of_range_fmt" => ("通知の最大試行回数は{min}回から{max}回の範囲で指定してください。", "The maximum number of notification attempts must be between {min} and {max}."),
+    ViewConcurrencyFmt = "view_concurrency_fmt" => ("同時に通知するアカウントの数: {count}", "Accounts notified concurrently: {count}"),
+    ConcurrencySetFmt = "concurrency_set_fmt" => ("同時に通知するアカウントの数を{count}に設定しました。", "Notification concurrency set to {count}."),
+    ConcurrencyOutOfRangeFmt = "concurrency_out_of_range_fmt" => ("同時に通知するアカウントの数は{min}から{max}の範囲で指定してください。", "The notification concurrency must be between {min} and {max}."),
+    ViewRequestTimeoutFmt = "view_request_timeout_fmt" => ("通知リクエストのタイムアウト: {seconds}秒", "Notification request timeout: {seconds} seconds"),
+    RequestTimeoutSetFmt = "request_timeout_set_fmt" => ("通知リクエストのタイムアウトを{seconds}秒に設定しました。", "Notification request timeout set to {seconds} seconds."),
+    RequestTimeoutOutOfRangeFmt = "request_timeout_out_of_range_fmt" => ("通知リクエストのタイムアウトは{min}秒から{max}秒の範囲で指定してください。", "The notification request timeout must be between {min} and {max} seconds."),
+    Ipv4UrlPrompt = "ipv4_url_prompt" => ("IPv4の通知先URL", "IPv4 notification URL"),
+    Ipv6UrlPrompt = "ipv6_url_prompt" => ("IPv6の通知先URL", "IPv6 notification URL"),
+    EndpointUrlInvalidFmt = "endpoint_url_invalid_fmt" => ("「{url}」はURLとして正しくありません。現在の値を維持します。", "\"{url}\" is not a valid URL. Keeping the current value."),
+    ViewEndpointFmt = "view_endpoint_fmt" => ("  通知先URL ({proto}): {url}", "  Notification URL ({proto}): {url}"),
+    HostnamePrompt = "hostname_prompt" => ("DNSの反映を確認するホスト名（空欄で確認しない、-で解除）", "Hostname to verify in DNS (blank: skip, -: clear)"),
+    SetHostnameInvalidFmt = "set_hostname_invalid_fmt" => ("「{host}」はホスト名として正しくありません。", "\"{host}\" is not a valid hostname."),
+    SetEndpointUrlInvalidFmt = "set_endpoint_url_invalid_fmt" => ("「{url}」はURLとして正しくありません。", "\"{url}\" is not a valid URL."),
+    HostnameInvalidFmt = "hostname_invalid_fmt" => ("「{host}」はホスト名として正しくありません。現在の値を維持します。", "\"{host}\" is not a valid hostname. Keeping the current value."),
+    ViewLastSuccessFmt = "view_last_success_fmt" => ("  最終成功: {time}  (IPv4: {v4},  IPv6: {v6})", "  Last success: {time}  (IPv4: {v4},  IPv6: {v6})"),
+    ViewHostnameFmt = "view_hostname_fmt" => ("  DNS確認のホスト名: {host}", "  Hostname to verify: {host}"),
+    ProviderPrompt = "provider_prompt" => ("通知先のサービス（mydns: MyDNS.JP、generic: 更新URLを指定する汎用のサービス）", "Update service (mydns: MyDNS.JP, generic: any service with an update URL)"),
+    ProviderInvalid = "provider_invalid" => ("サービスは mydns か generic を指定してください。", "The service must be mydns or generic."),
+    UsernamePrompt = "username_prompt" => ("ユーザー名（アカウントの名前にもなります）", "Username (also used as the account name)"),
+    GenericIpv4UrlPrompt = "generic_ipv4_url_prompt" => ("IPv4の更新URL（{ip}と{host}を置き換えます）", "IPv4 update URL ({ip} and {host} are replaced)"),
+    GenericIpv6UrlPrompt = "generic_ipv6_url_prompt" => ("IPv6の更新URL（空欄でIPv4と同じURL、-で解除）", "IPv6 update URL (blank: same as IPv4, -: clear)"),
+    GenericUrlRequired = "generic_url_required" => ("汎用のサービスには、IPv4の更新URLが必要です。", "A generic service needs an IPv4 update URL."),
+    ViewProviderGeneric = "view_provider_generic" => ("  サービス: 汎用（更新URLを使用）", "  Service: generic (update URL)"),
+    MethodPrompt = "method_prompt" => ("通知の方法（http: HTTPS、pop3/imap/ftp: サーバーへのログイン）", "Update method (http: HTTPS, pop3/imap/ftp: log in to the server)"),
+    MethodInvalid = "method_invalid" => ("通知の方法は http、pop3、imap、ftp のいずれかを指定してください。", "The update method must be http, pop3, imap or ftp."),
+    MethodRequiresMyDns = "method_requires_mydns" => ("POP3/IMAP/FTPでの通知は、MyDNS.JPのアカウントだけで使えます。", "Updating by POP3/IMAP/FTP login is only available for MyDNS.JP accounts."),
+    MethodPlaintextWarning = "method_plaintext_warning" => ("注意: POP3/IMAP/FTPでのログインでは、パスワードが暗号化されずに送られます。", "Note: POP3/IMAP/FTP logins send the password unencrypted."),
+    ViewMethodFmt = "view_method_fmt" => ("  通知の方法: {method}（ログイン）", "  Update method: {method} (login)"),
+    PingUrlPrompt = "ping_url_prompt" => ("通知に成功したときにpingを送る死活監視のURL（空欄で送らない、-で解除）", "Monitoring ping URL to request after a successful update (blank: none, -: clear)"),
+    ViewAccountPingUrlFmt = "view_account_ping_url_fmt" => ("  ping URL: {url}", "  Ping URL: {url}"),
+    InterfacePrompt = "interface_prompt" => ("通知を送るネットワークインターフェイスの名前、またはローカルアドレス（空欄で選ばない、-で解除）", "Network interface name or local address to send updates from (blank: any, -: clear)"),
+    ViewInterfaceFmt = "view_interface_fmt" => ("  送信元: {interface}", "  Send from: {interface}"),
+    SchedulePrompt = "schedule_prompt" => ("定期通知の代わりに使う通知のスケジュール（例: */10 * * * *、at 04:05 daily。空欄で定期通知、-で解除）", "Notification schedule to use instead of the interval (e.g. */10 * * * *, at 04:05 daily; blank: interval, -: clear)"),
+    ScheduleInvalidFmt = "schedule_invalid_fmt" => ("スケジュールの式を解釈できません（cronの5つのフィールドか、at HH:MM daily の形式で指定してください）: {schedule}", "Could not parse the schedule (use five cron fields or at HH:MM daily): {schedule}"),
+    ViewScheduleFmt = "view_schedule_fmt" => ("  スケジュール: {schedule}", "  Schedule: {schedule}"),
+    ViewLogPathFmt = "view_log_path_fmt" => ("ログファイル: {path}", "Log file: {path}"),
+    LogPathSetFmt = "log_path_set_fmt" => ("ログファイルの場所を {path} に設定しました。稼働中のサービスには、再起動後に反映されます。", "Log file location set to {path}. A running service applies it after a restart."),
+    ViewLogArchiveFmt = "view_log_archive_fmt" => ("ログのアーカイブ: {count}個まで保存（圧縮: {compress}）", "Log archives: keep {count} (compressed: {compress})"),
+    LogArchiveSetFmt = "log_archive_set_fmt" => ("ログのアーカイブを{count}個まで保存するように設定しました（圧縮: {compress}）。", "Log archives set to keep {count} (compressed: {compress})."),
+    LogArchivesOutOfRangeFmt = "log_archives_out_of_range_fmt" => ("ログのアーカイブの数は{min}から{max}の範囲で指定してください。", "The number of log archives must be between {min} and {max}."),
+    ViewProxyFmt = "view_proxy_fmt" => ("プロキシ: {proxy}", "Proxy: {proxy}"),
+    ProxyAuto = "proxy_auto" => ("自動（WinHTTP/システムの設定）", "Automatic (WinHTTP/system settings)"),
+    ViewMetricsFmt = "view_metrics_fmt" => ("メトリクス: http://127.0.0.1:{port}/metrics", "Metrics: http://127.0.0.1:{port}/metrics"),
+    MetricsSetFmt = "metrics_set_fmt" => ("メトリクスを http://127.0.0.1:{port}/metrics で公開するように設定しました。稼働中のサービスには、再起動後に反映されます。", "Metrics will be served at http://127.0.0.1:{port}/metrics. A running service applies it after a restart."),
+    MetricsDisabled = "metrics_disabled" => ("メトリクスの公開を無効にしました。稼働中のサービスには、再起動後に反映されます。", "Metrics endpoint disabled. A running service applies it after a restart."),
+    ViewAdminApiFmt = "view_admin_api_fmt" => ("管理API: http://127.0.0.1:{port}/api/", "Admin API: http://127.0.0.1:{port}/api/"),
+    AdminApiSetFmt = "admin_api_set_fmt" => ("管理APIを http://127.0.0.1:{port}/api/ で公開するように設定しました。稼働中のサービスには、再起動後に反映されます。\nリクエストには「Authorization: Bearer {token}」ヘッダーを付けてください。", "The admin API will be served at http://127.0.0.1:{port}/api/. A running service applies it after a restart.\nSend requests with the header \"Authorization: Bearer {token}\"."),
+    AdminApiDisabled = "admin_api_disabled" => ("管理APIの公開を無効にしました。稼働中のサービスには、再起動後に反映されます。", "Admin API disabled. A running service applies it after a restart."),
+    ViewPingUrlFmt = "view_ping_url_fmt" => ("死活監視のping URL: {url}", "Monitoring ping URL: {url}"),
+    PingUrlSetFmt = "ping_url_set_fmt" => ("すべてのアカウントの通知に成功したときに「{url}」へpingを送るように設定しました。", "A ping will be sent to \"{url}\" after every notification round in which all accounts succeed."),
+    PingUrlCleared = "ping_url_cleared" => ("死活監視のping URLの設定を解除しました。", "Monitoring ping URL cleared."),
+    ViewWebhookUrlFmt = "view_webhook_url_fmt" => ("Webhook: {url}", "Webhook: {url}"),
+    WebhookUrlSetFmt = "webhook_url_set_fmt" => ("通知の失敗・回復・アドレスの変化を「{url}」に知らせるように設定しました。", "Failures, recoveries and address changes will be posted to \"{url}\"."),
+    WebhookUrlCleared = "webhook_url_cleared" => ("Webhookの設定を解除しました。", "Webhook URL cleared."),
+    ViewToastFmt = "view_toast_fmt" => ("トースト通知: 通知が{count}回続けて失敗したとき", "Toast notification: after {count} consecutive failures"),
+    ToastSetFmt = "toast_set_fmt" => ("通知が{count}回続けて失敗したら、トースト通知で知らせるように設定しました。", "A toast notification will be shown after {count} consecutive failures."),
+    ToastDisabled = "toast_disabled" => ("トースト通知を無効にしました。", "Toast notifications disabled."),
+    ViewMailFmt = "view_mail_fmt" => ("メール通知: {to}（{server}:{port}、{minutes}分以上失敗が続いたとき）", "Mail alerts: {to} (via {server}:{port}, after {minutes} minutes of failures)"),
+    MailSettingsSaved = "mail_settings_saved" => ("メール通知の設定を保存しました。--mail-test でテストメールを送信できます。", "Mail alert settings saved. Use --mail-test to send a test message."),
+    MailDisabled = "mail_disabled" => ("メール通知を無効にしました。", "Mail alerts disabled."),
+    MailNotConfigured = "mail_not_configured" => ("メール通知を使うには、--smtp-server と --mail-to の両方を設定してください。", "Set both --smtp-server and --mail-to to use mail alerts."),
+    SmtpServerInvalidFmt = "smtp_server_invalid_fmt" => ("「{server}」はSMTPサーバーとして正しくありません。HOST または HOST:PORT の形式で指定してください。", "\"{server}\" is not a valid SMTP server. Use HOST or HOST:PORT."),
+    MailAddressInvalidFmt = "mail_address_invalid_fmt" => ("「{address}」はメールアドレスとして正しくありません。", "\"{address}\" is not a valid mail address."),
+    ViewCheckIpv4UrlsFmt = "view_check_ipv4_urls_fmt" => ("IPv4の確認サービス: {urls}", "IPv4 check-IP services: {urls}"),
+    ViewCheckIpv6UrlsFmt = "view_check_ipv6_urls_fmt" => ("IPv6の確認サービス: {urls}", "IPv6 check-IP services: {urls}"),
+    ViewCheckIpPollFmt = "view_check_ip_poll_fmt" => ("グローバルアドレスの確認: {minutes}分ごと", "Global address check: every {minutes} minutes"),
+    CheckIpSettingsSaved = "check_ip_settings_saved" => ("グローバルアドレスの確認サービスの設定を保存しました。", "Check-IP service settings saved."),
+    ViewSkipPrivateIpv4 = "view_skip_private_ipv4" => ("CGNAT・プライベートのIPv4アドレス: 通知しない", "CGNAT/private IPv4 addresses: not notified"),
+    SkipPrivateIpv4Enabled = "skip_private_ipv4_enabled" => ("確認サービスで求めたIPv4アドレスがCGNATやプライベートのアドレスだった場合は、通知を見送ります。", "Updates will be skipped when the looked-up IPv4 address is a CGNAT or private address."),
+    SkipPrivateIpv4Disabled = "skip_private_ipv4_disabled" => ("CGNATやプライベートのIPv4アドレスも、警告を記録したうえで通知します。", "CGNAT and private IPv4 addresses will be notified, with a warning in the log."),
+    ViewDnsCheckFmt = "view_dns_check_fmt" => ("DNSの自己確認: {minutes}分ごと", "DNS self-check: every {minutes} minutes"),
+    DnsCheckSetFmt = "dns_check_set_fmt" => ("ホスト名のDNSレコードを、{minutes}分ごとに公開リゾルバーで確認します。", "Hostname DNS records will be checked against public resolvers every {minutes} minutes."),
+    DnsCheckDisabled = "dns_check_disabled" => ("DNSの自己確認を無効にしました。", "DNS self-check disabled."),
+    ViewMeteredFmt = "view_metered_fmt" => ("従量制課金の接続: {policy}", "Metered connections: {policy}"),
+    MeteredPolicyInvalid = "metered_policy_invalid" => ("従量制課金の接続での扱いは ignore、reduce、skip のいずれかを指定してください。", "The metered connection policy must be ignore, reduce, or skip."),
+    MeteredIgnore = "metered_ignore" => ("従量制課金の接続でも、通常どおり通知します。", "Notifications will run as usual on metered connections."),
+    MeteredReduce = "metered_reduce" => ("従量制課金の接続では、定期通知を1時間に1回までに減らします。", "On metered connections, scheduled notifications will run at most once an hour."),
+    MeteredSkip = "metered_skip" => ("従量制課金の接続では、サービスによる自動の通知を見送ります。", "On metered connections, the service will skip automatic notifications."),
+    ViewAllowedNetworksFmt = "view_allowed_networks_fmt" => ("通知するネットワーク: {networks}", "Allowed networks: {networks}"),
+    AllowedNetworksSetFmt = "allowed_networks_set_fmt" => ("サービスは、次のネットワークに接続しているときだけ通知します: {networks}", "The service will notify only while connected to: {networks}"),
+    AllowedNetworksCleared = "allowed_networks_cleared" => ("通知するネットワークの制限を解除しました。", "Allowed networks cleared; the service will notify on any network."),
+    ConnectedNetworksFmt = "connected_networks_fmt" => ("現在接続中のネットワーク: {networks}", "Currently connected networks: {networks}"),
+    ViewQuietHoursFmt = "view_quiet_hours_fmt" => ("静穏時間帯: {hours}", "Quiet hours: {hours}"),
+    QuietHoursInvalidFmt = "quiet_hours_invalid_fmt" => ("時間帯は 23:00-06:00 のように HH:MM-HH:MM の形式で、開始と終了を別の時刻にしてください: {hours}", "Quiet hours must be in HH:MM-HH:MM format (such as 23:00-06:00) with different start and end times: {hours}"),
+    QuietHoursSetFmt = "quiet_hours_set_fmt" => ("毎日 {hours} の間は、サービスによる自動の通知を見送ります。", "The service will hold automatic notifications every day during {hours}."),
+    QuietHoursCleared = "quiet_hours_cleared" => ("静穏時間帯を解除しました。", "Quiet hours cleared."),
+    ViewJitterFmt = "view_jitter_fmt" => ("通知の時刻のずらし: ±{seconds}秒", "Notification jitter: ±{seconds} seconds"),
+    JitterSetFmt = "jitter_set_fmt" => ("サービスの通知の時刻を、最大±{seconds}秒の範囲でランダムにずらします（定期通知の間隔の半分まで）。", "The service will shift notification times randomly by up to ±{seconds} seconds (at most half the interval)."),
+    JitterDisabled = "jitter_disabled" => ("通知の時刻をずらさないようにしました。", "Notification jitter disabled."),
+    ViewUserAgentFmt = "view_user_agent_fmt" => ("User-Agent: {ua}", "User-Agent: {ua}"),
+    UserAgentIdSetFmt = "user_agent_id_set_fmt" => ("User-Agentに識別子を付けました: {ua}", "User-Agent identifier set: {ua}"),
+    UserAgentIdCleared = "user_agent_id_cleared" => ("User-Agentの識別子を解除しました。", "User-Agent identifier cleared."),
+    UserAgentIdInvalidFmt = "user_agent_id_invalid_fmt" => ("User-Agentの識別子には、{max}文字までの半角英数字と記号、空白だけを指定してください。", "The User-Agent identifier must be at most {max} printable ASCII characters."),
+    ViewHistoryDatabase = "view_history_database" => ("履歴のデータベース: 記録する", "History database: enabled"),
+    HistoryDbEnabled = "history_db_enabled" => ("通知の履歴を、データベースにも記録します。", "Notification history will also be recorded in the database."),
+    HistoryDbDisabled = "history_db_disabled" => ("通知の履歴を、データベースに記録しません。記録済みの履歴は残ります。", "Notification history will no longer be recorded in the database. Existing records are kept."),
+    HistoryDbUnsupported = "history_db_unsupported" => ("このビルドは履歴のデータベースに対応していません。`--features sqlite` を付けてビルドしてください。", "This build does not support the history database. Build with `--features sqlite`."),
+    StatusHistorySummaryFmt = "status_history_summary_fmt" => ("  直近24時間: 試行 {attempts}回、成功 {successes}回、平均 {ms} ms", "  Last 24 hours: {attempts} attempts, {successes} succeeded, {ms} ms on average"),
+    ProxySetFmt = "proxy_set_fmt" => ("通知に使うプロキシを「{url}」に設定しました。", "Notification proxy set to \"{url}\"."),
+    ProxyCleared = "proxy_cleared" => ("プロキシの設定を解除しました。WinHTTP/システムの設定に従います。", "Proxy setting cleared. WinHTTP/system settings will be used."),
+    ProxyPrompt = "proxy_prompt" => ("プロキシURL（空欄で全体の設定、directで直接接続、-で解除）", "Proxy URL (blank: global setting, direct: no proxy, -: clear)"),
+    ExportSuccessFmt = "export_success_fmt" => ("[成功] {count}件のアカウント設定を {path} に書き出しました。", "[Success] Exported {count} account(s) to {path}."),
+    ExportPasswordWarning = "export_password_warning" => ("[注意] パスワードが平文で含まれています。ファイルの取り扱いに注意してください。（--redact-passwords で除外できます）", "[Warning] The file contains passwords in plain text. Handle it with care. (Use --redact-passwords to exclude them.)"),
+    ImportTitle = "import_title" => ("--- 設定の読み込み ---", "--- Import Configuration ---"),
+    ImportParseErrorFmt = "import_parse_error_fmt" => ("ファイルを解釈できませんでした: {error}", "Could not parse the file: {error}"),
+    ImportUnsupportedVersionFmt = "import_unsupported_version_fmt" => ("対応していない形式のバージョンです: {version}", "Unsupported file format version: {version}"),
+    ImportDuplicateFmt = "import_duplicate_fmt" => ("MasterID {id} が複数回含まれています。", "MasterID {id} appears more than once."),
+    ImportUnknownFieldFmt = "import_unknown_field_fmt" => ("不明な項目です: {field}", "Unknown field: {field}"),
+    ImportMissingFieldFmt = "import_missing_field_fmt" => ("必須の項目がありません: {field}", "Missing required field: {field}"),
+    ImportInvalidTypeFmt = "import_invalid_type_fmt" => ("項目の値の型が正しくありません: {field}", "Invalid value type for field: {field}"),
+    ImportMissingPasswordFmt = "import_missing_password_fmt" => ("新しいアカウント {id} にはパスワードが必要です。", "A password is required for new account {id}."),
+    ImportDiffNewFmt = "import_diff_new_fmt" => ("+ {id}（新規）", "+ {id} (new)"),
+    ImportDiffChangedFmt = "import_diff_changed_fmt" => ("~ {id}（変更）", "~ {id} (changed)"),
+    ImportDiffUnchangedFmt = "import_diff_unchanged_fmt" => ("= {id}（変更なし）", "= {id} (unchanged)"),
+    ImportNothingToDo = "import_nothing_to_do" => ("反映する変更はありません。", "There are no changes to apply."),
+    ImportConfirmFmt = "import_confirm_fmt" => ("{count}件のアカウント設定を反映しますか？", "Apply {count} account change(s)?"),
+    ImportSuccessFmt = "import_success_fmt" => ("[成功] {count}件のアカウント設定を反映しました。", "[Success] Applied {count} account change(s)."),
+    UserScopeServiceConflict = "user_scope_service_conflict" => ("サービスはユーザーごとの設定を参照しないため、サービスの操作に --user は指定できません。", "--user cannot be used with service commands because the service does not read per-user settings."),
+    StatusTitle = "status_title" => ("--- MyDNS Adapterの状態 ---", "--- MyDNS Adapter Status ---"),
+    StatusUptimeFmt = "status_uptime_fmt" => ("稼働時間: {days}日 {hours}時間 {minutes}分（{since} に開始）", "Uptime: {days}d {hours}h {minutes}m (started {since})"),
+    ViewLastErrorFmt = "view_last_error_fmt" => ("  最終エラー: {time}  [{kind}] {detail}", "  Last error: {time}  [{kind}] {detail}"),
+    ViewSuspendedFmt = "view_suspended_fmt" => ("  通知を停止中: {time} から（認証エラーが続いたため）。`account enable {id}` で再開します。", "  Suspended: since {time} (repeated authentication failures). Resume with `account enable {id}`."),
+    StatusLastIpFmt = "status_last_ip_fmt" => ("  最終通知アドレス: IPv4 {v4},  IPv6 {v6}", "  Last notified address: IPv4 {v4},  IPv6 {v6}"),
+    StatusAccountFmt = "status_account_fmt" => ("{id}: 最終通知: {time},  結果: {result},  最終成功: {success}", "{id}: Last notification: {time},  Result: {result},  Last success: {success}"),
+    UserScopeFallback = "user_scope_fallback" => ("[情報] 管理者権限がないため、ユーザーごとの設定（HKCU）を使用します。サービスはこの設定を参照しません。", "[Info] Not running as administrator; using per-user settings (HKCU). The service does not read these settings."),
+    ViewUserScope = "view_user_scope" => ("(ユーザーごとの設定 HKCU を表示しています)", "(Showing per-user settings from HKCU)"),
+    ViewConfigFileFmt = "view_config_file_fmt" => ("(設定ファイル {path} を表示しています)", "(Showing accounts from the configuration file {path})"),
+    ConfigFileInvalidFmt = "config_file_invalid_fmt" => ("設定ファイル {path} を読み込めませんでした: {error}", "Could not read the configuration file {path}: {error}"),
+    PasswordFromEnvFmt = "password_from_env_fmt" => ("環境変数 {var} のパスワードを使用します。", "Using the password from the {var} environment variable."),
+    KickSuccess = "kick_success" => ("[成功] サービスに即時通知を要求しました。", "[Success] Immediate notification requested from the service."),
+    UpgradeTitle = "upgrade_title" => ("--- 設定の更新 ---", "--- Upgrade Configuration ---"),
+    UpgradeAccountFmt = "upgrade_account_fmt" => ("アカウント '{id}':", "Account '{id}':"),
+    UpgradeNoChanges = "upgrade_no_changes" => ("変更はありません。", "No changes."),
+    UpgradeAddedDefaultFmt = "upgrade_added_default_fmt" => ("{name}: 値がなかったため、既定値 {value} を書き込みました。", "{name}: missing, wrote default value {value}."),
+    UpgradeMigratedTypeFmt = "upgrade_migrated_type_fmt" => ("{name}: 文字列から数値 {value} に変換しました。", "{name}: converted from string to number {value}."),
+    UpgradeEmptyPassword = "upgrade_empty_password" => ("[警告] パスワードが設定されていません。account edit で設定してください。", "[Warning] Password is not set. Use 'account edit' to set it."),
+    LogFollowHint = "log_follow_hint" => ("--- 新しいログを待っています（Ctrl+Cで終了） ---", "--- Waiting for new log entries (Ctrl+C to exit) ---"),
+    LogEmpty = "log_empty" => ("ログはまだ記録されていません。", "No log entries have been recorded yet."),
+    InvalidMasterIdPrefix = "invalid_master_id_prefix" => ("MasterIDは 'mydns' で始まる必要があります。", "MasterID must start with 'mydns'."),
+
+    // winservice.rs
+    AdminRequiredInstall = "admin_required_install" => ("サービスのインストールには管理者権限が必要です。管理者として実行してください。", "Administrator privileges are required to install the service. Please run as administrator."),
+    ServiceInstallingFmt = "service_installing_fmt" => ("サービス '{name}' をインストールしています...", "Service '{name}' installing..."),
+    ServiceInstalledFmt = "service_installed_fmt" => ("サービス '{name}' が正常にインストールされ、開始されました。", "Service '{name}' installed and started successfully."),
+    AdminRequiredUninstall = "admin_required_uninstall" => ("サービスのアンインストールには管理者権限が必要です。管理者として実行してください。", "Administrator privileges are required to uninstall the service. Please run as administrator."),
+    ServiceNotInstalledFmt = "service_not_installed_fmt" => ("サービス '{name}' はインストールされていません。", "Service '{name}' is not installed."),
+    ServiceStoppingFmt = "service_stopping_fmt" => ("サービス '{name}' を停止しています...", "Stopping service '{name}'..."),
+    ServiceStopped = "service_stopped" => ("サービスが停止しました。", "Service stopped."),
+    ServiceWaitingStop = "service_waiting_stop" => ("サービスの停止を待機しています...", "Waiting for service to stop..."),
+    ServiceNotRunning = "service_not_running" => ("サービスが起動していません。", "Service is not running."),
+    ServiceUninstalledFmt = "service_uninstalled_fmt" => ("サービス '{name}' が正常にアンインストールされました。", "Service '{name}' uninstalled successfully."),
+    LogServiceFailedFmt = "log_service_failed_fmt" => ("サービスの実行に失敗しました: {error}", "Service failed to run: {error}"),
+    LogServiceStarted = "log_service_started" => ("サービスを開始しました。", "Service started."),
+    LogServiceConfigMissing = "log_service_config_missing" => ("アカウントが1件も設定されていません。アカウントが追加されるまで通知せずに待機します。", "No accounts are configured. Waiting without notifying until an account is added."),
+    LogAccountsAddedNotify = "log_accounts_added_notify" => ("アカウントが追加されたため、通知を実行します。", "Accounts were added. Notifying."),
+    LogServiceStopping = "log_service_stopping" => ("サービスを停止します。", "Service stopping."),
+    AdminRequiredRestart = "admin_required_restart" => ("サービスの再起動には管理者権限が必要です。管理者として実行してください。", "Administrator privileges are required to restart the service. Please run as administrator."),
+    LogServiceKickReceived = "log_service_kick_received" => ("即時通知の要求を受け付けました。", "Immediate notification requested."),
+    LogAccountBackoffFmt = "log_account_backoff_fmt" => ("{until} まで通知を見送ります。", "Backing off until {until}."),
+    LogKeepAliveForcedFmt = "log_keep_alive_forced_fmt" => ("最後の通知成功から{hours}時間以上経過したため、通知を強制します。", "Forcing notification: no successful update in the last {hours} hours."),
+    LogKeepAliveOverrideFmt = "log_keep_alive_override_fmt" => ("強制通知の期限を過ぎた{count}件のアカウントは、見送らずに通知します。", "Notifying {count} account(s) past their keep-alive deadline despite the hold."),
+    LogAddressWatchFailedFmt = "log_address_watch_failed_fmt" => ("IPアドレス変更の監視を開始できませんでした。定期通知のみで動作します: {error}", "Could not watch for IP address changes; falling back to periodic notification only: {error}"),
+    LogServicePaused = "log_service_paused" => ("サービスが一時停止されました。再開されるまで定期通知を行いません。", "Service paused. Periodic notifications are suspended until continued."),
+    LogServiceContinued = "log_service_continued" => ("サービスが再開されました。", "Service continued."),
+    LogServiceContinueNotify = "log_service_continue_notify" => ("サービスの再開に伴い、通知を実行します。", "Notifying after the service was continued."),
+    LogPowerResumed = "log_power_resumed" => ("スリープまたは休止状態からの復帰を検出しました。", "Resume from sleep or hibernation detected."),
+    LogPowerResumeNotify = "log_power_resume_notify" => ("スリープまたは休止状態からの復帰に伴い、通知を実行します。", "Notifying after resume from sleep or hibernation."),
+    LogAddressChanged = "log_address_changed" => ("IPアドレスの変更を検出しました。通知を実行します。", "IP address change detected. Notifying."),
+    LogRoundStillRunning = "log_round_still_running" => ("前回の通知処理が終わっていないため、今回の定期通知を見送ります。", "Previous notification round is still running; skipping this scheduled round."),
+    LogServiceShutdown = "log_service_shutdown" => ("システムのシャットダウンのため、サービスを停止します。", "Stopping the service for system shutdown."),
+    LogRoundFlushed = "log_round_flushed" => ("実行中の通知処理の完了を待ってから停止します。", "Waited for the running notification round to finish before stopping."),
+    LogRoundStartedFmt = "log_round_started_fmt" => ("通知ラウンドを開始します（アカウント数: {count}）。", "Notification round started ({count} accounts)."),
+    LogRoundFinishedFmt = "log_round_finished_fmt" => ("通知ラウンドが完了しました（成功: {ok}、失敗: {failed}）。", "Notification round finished ({ok} succeeded, {failed} failed)."),
+    LogMeteredRoundSkipped = "log_metered_round_skipped" => ("従量制課金の接続のため、今回の通知ラウンドを見送ります。", "Skipping this notification round on a metered connection."),
+    LogRoundSkippedOffline = "log_round_skipped_offline" => ("ネットワークに接続されていないため、今回の通知ラウンドを見送ります。", "Network is unavailable; skipping this notification round."),
+    LogNetworkNotAllowedFmt = "log_network_not_allowed_fmt" => ("接続中のネットワーク（{networks}）は許可されたネットワークではないため、今回の通知ラウンドを見送ります。", "Connected networks ({networks}) are not in the allowed list; skipping this notification round."),
+    LogNetworkNamesFailedFmt = "log_network_names_failed_fmt" => ("接続中のネットワークを確認できなかったため、そのまま通知します: {error}", "Could not determine the connected networks; notifying anyway: {error}"),
+    LogQuietHoursSkipped = "log_quiet_hours_skipped" => ("静穏時間帯のため、時間帯が終わるまで自動の通知を見送ります。", "Quiet hours in effect; automatic notifications are held until they end."),
+    LogQuietHoursEndedNotify = "log_quiet_hours_ended_notify" => ("静穏時間帯が終わったため、見送っていた通知を実行します。", "Quiet hours ended; running the held notification."),
+    LogScheduleDueFmt = "log_schedule_due_fmt" => ("スケジュールの時刻になった{count}件のアカウントを通知します。", "Notifying {count} account(s) on their schedule."),
+    LogRoundAborted = "log_round_aborted" => ("停止要求を受けたため、実行中の通知処理を中断しました。", "Notification round aborted due to service stop."),
+    LogConfigWatchFailedFmt = "log_config_watch_failed_fmt" => ("アカウント設定の変更を監視できません。設定の変更はサービスの再起動後に反映されます: {error}", "Cannot watch account settings for changes. Changes take effect after the service restarts: {error}"),
+    LogConfigReloadedFmt = "log_config_reloaded_fmt" => ("アカウント設定の変更を検出し、{count}件のアカウントを読み込み直しました。", "Account settings changed; reloaded {count} account(s)."),
+    LogConfigReloadedEmpty = "log_config_reloaded_empty" => ("アカウント設定の変更を検出しましたが、アカウントが1件もありません。アカウントが追加されるまで通知しません。", "Account settings changed, but no accounts are configured. Nothing will be notified until an account is added."),
+    LogConfigReloadFailedFmt = "log_config_reload_failed_fmt" => ("アカウント設定を読み込み直せませんでした。これまでの設定を使い続けます: {error}", "Failed to reload account settings; keeping the previous settings: {error}"),
+    ServiceStatusFmt = "service_status_fmt" => ("サービス '{name}': {state}", "Service '{name}': {state}"),
+    ServiceStateRunning = "service_state_running" => ("実行中", "Running"),
+    ServiceStateStopped = "service_state_stopped" => ("停止", "Stopped"),
+    ServiceStateStartPending = "service_state_start_pending" => ("開始処理中", "Starting"),
+    ServiceStateStopPending = "service_state_stop_pending" => ("停止処理中", "Stopping"),
+    ServiceStatePaused = "service_state_paused" => ("一時停止", "Paused"),
+    ServiceStatePausePending = "service_state_pause_pending" => ("一時停止処理中", "Pausing"),
+    ServiceStateContinuePending = "service_state_continue_pending" => ("再開処理中", "Resuming"),
+    ServiceStateUnknown = "service_state_unknown" => ("不明", "Unknown"),
+    ServiceDescription = "service_description" => ("IPv4/IPv6アドレスを定期的にMyDNS.JPへ通知し、ダイナミックDNSのレコードを最新に保ちます。", "Periodically notifies MyDNS.JP of this machine's IPv4/IPv6 addresses to keep its dynamic DNS records up to date."),
+    ServiceLogonFailedFmt = "service_logon_failed_fmt" => ("サービスはインストールされましたが、アカウント '{account}' でログオンできなかったため開始できませんでした。パスワードと「サービスとしてログオン」の権利を確認し、サービスを開始し直してください。", "The service was installed but could not start because logon as '{account}' failed. Check the password and the \"Log on as a service\" right, then start the service again."),
+    ServiceKeyAccessGrantedFmt = "service_key_access_granted_fmt" => ("アカウント '{account}' に、{key} の読み書きを許可しました。", "Granted account '{account}' read and write access to {key}."),
+    ServiceKeyAccessFailedFmt = "service_key_access_failed_fmt" => ("アカウント '{account}' に {key} への書き込みを許可できませんでした: {error}。サービスは通知の結果や自動停止を記録できません。regedit で手動で許可してください。", "Could not grant account '{account}' write access to {key}: {error}. The service will not be able to record notification results or automatic suspensions. Grant access manually with regedit."),
+    ServiceDataDirAccessGrantedFmt = "service_data_dir_access_granted_fmt" => ("アカウント '{account}' に、{path} への書き込みを許可しました。", "Granted account '{account}' write access to {path}."),
+    ServiceDataDirAccessFailedFmt = "service_data_dir_access_failed_fmt" => ("アカウント '{account}' に {path} への書き込みを許可できませんでした: {error}。サービスは実行時状態や通知の履歴を記録できません。エクスプローラーの「セキュリティ」で手動で許可してください。", "Could not grant account '{account}' write access to {path}: {error}. The service will not be able to record its runtime state or notification history. Grant access manually in the Security tab in Explorer."),
+    DataDirShareFailedFmt = "data_dir_share_failed_fmt" => ("実行時データのディレクトリ {path} への書き込みをユーザーに許可できませんでした: {error}。管理者権限のないユーザーは --pause や --kick を使えません。", "Could not grant users write access to the runtime data directory {path}: {error}. Users without administrator rights will not be able to use --pause or --kick."),
+    AdminRequiredStart = "admin_required_start" => ("サービスの開始には管理者権限が必要です。管理者として実行してください。", "Administrator privileges are required to start the service. Please run as administrator."),
+    AdminRequiredStop = "admin_required_stop" => ("サービスの停止には管理者権限が必要です。管理者として実行してください。", "Administrator privileges are required to stop the service. Please run as administrator."),
+    ServiceStartingFmt = "service_starting_fmt" => ("サービス '{name}' を開始しています...", "Starting service '{name}'..."),
+    ServiceStarted = "service_started" => ("サービスが開始されました。", "Service started."),
+    ServiceAlreadyRunning = "service_already_running" => ("サービスは既に実行中です。", "Service is already running."),
+    ServiceStartFailedFmt = "service_start_failed_fmt" => ("サービスは開始処理の途中で停止しました（終了コード: {code}）。ログを確認してください。", "The service stopped while starting (exit code: {code}). Check the log for details."),
+    ServiceStoppedAfterStartFmt = "service_stopped_after_start_fmt" => ("サービスは開始直後に停止しました（終了コード: {code}）。ログを確認してください。", "The service stopped right after starting (exit code: {code}). Check the log for details."),
+    ServiceStartTimeout = "service_start_timeout" => ("サービスの開始を待機しましたが、時間内に実行中になりませんでした。", "Timed out waiting for the service to start."),
+    LogPipeServerFailedFmt = "log_pipe_server_failed_fmt" => ("名前付きパイプによる要求の受け付けを開始できませんでした: {error}", "Failed to start accepting requests on the named pipe: {error}"),
+    LogPipeAlreadyExists = "log_pipe_already_exists" => ("名前付きパイプはほかのプロセスがすでに作成しているため、要求の受け付けを開始しませんでした。サービスになりすましているプロセスがないか確認してください。", "Did not start accepting requests because another process already created the named pipe. Check for a process impersonating the service."),
+    LogMetricsServerStartedFmt = "log_metrics_server_started_fmt" => ("メトリクスを http://127.0.0.1:{port}/metrics で公開しています。", "Serving metrics at http://127.0.0.1:{port}/metrics."),
+    LogMetricsServerFailedFmt = "log_metrics_server_failed_fmt" => ("ポート{port}でメトリクスの公開を開始できませんでした: {error}", "Failed to start serving metrics on port {port}: {error}"),
+    LogAdminApiStartedFmt = "log_admin_api_started_fmt" => ("管理APIを http://127.0.0.1:{port}/api/ で公開しています。", "Serving the admin API at http://127.0.0.1:{port}/api/."),
+    LogAdminApiFailedFmt = "log_admin_api_failed_fmt" => ("ポート{port}で管理APIの公開を開始できませんでした: {error}", "Failed to start serving the admin API on port {port}: {error}"),
+    ForegroundStarted = "foreground_started" => ("サービスと同じ処理をコンソールで実行しています。Ctrl+C で停止します。", "Running the service loop in this console. Press Ctrl+C to stop."),
+    ForegroundStopped = "foreground_stopped" => ("停止しました。", "Stopped."),
+    ForegroundServiceRunning = "foreground_service_running" => ("[警告] サービスが動作中です。同じアカウントに二重に通知するおそれがあります。", "[Warning] The service is running. Accounts may be notified twice."),
+    LogForegroundStarted = "log_foreground_started" => ("サービスの処理をコンソールで開始しました（--run-foreground）。", "Service loop started in the console (--run-foreground)."),
+    ServicePanicked = "service_panicked" => ("サービスの処理でパニックが発生しました。", "The service loop panicked."),
+    ServiceRestartedSuccessfully = "service_restarted_successfully" => ("サービスを再起動しました。", "Service restarted successfully."),
+
+    // notify.rs
+    LogNotifyStart = "log_notify_start" => ("即時通知を開始します。", "Starting immediate notification."),
+    LogNotifyAccountNotFoundFmt = "log_notify_account_not_found_fmt" => ("指定されたアカウント {id} が見つからないため、即時通知を中止します。", "Aborting immediate notification: account {id} was not found."),
+    NotifySummaryTitle = "notify_summary_title" => ("通知の結果:", "Notification results:"),
+    NotifySummaryRowFmt = "notify_summary_row_fmt" => ("  {id}  {proto}  {result}  {ms} ミリ秒（{attempts}回）{detail}", "  {id}  {proto}  {result}  {ms} ms ({attempts} attempts){detail}"),
+    NotifySummarySkippedFmt = "notify_summary_skipped_fmt" => ("  {id}  -     {reason}", "  {id}  -     {reason}"),
+    NotifySummarySuspended = "notify_summary_suspended" => ("認証エラーが続いたため停止中（通知していません）", "Suspended after repeated authentication failures (not sent)"),
+    NotifySummaryInvalidProxy = "notify_summary_invalid_proxy" => ("プロキシURLが正しくありません（通知していません）", "Invalid proxy URL (not sent)"),
+    NotifySummaryAborted = "notify_summary_aborted" => ("通知の処理が異常終了しました", "The notification task ended abnormally"),
+    NotifySummaryNoProtocol = "notify_summary_no_protocol" => ("通知するプロトコルがありません", "No protocol to notify"),
+    NotifySummaryTotalFmt = "notify_summary_total_fmt" => ("成功 {ok} 件、失敗 {failed} 件", "{ok} succeeded, {failed} failed"),
+    LogNotifyDryRunFinish = "log_notify_dry_run_finish" => ("ドライランのため、リクエストを送信せずに終了しました。", "Dry run finished without sending any requests."),
+    LogNotifySkippedOffline = "log_notify_skipped_offline" => ("ネットワークに接続されていないため、通知を見送ります。", "Network is unavailable; skipping notification."),
+    LogConfigMissing = "log_config_missing" => ("MasterIDまたはパスワードが設定されていません。先に設定モードを実行してください。", "MasterID or Password is not set. Please run configuration mode first."),
+    LogNotifyFinish = "log_notify_finish" => ("即時通知が完了しました。", "Immediate notification finished."),
+    LogProxyInvalidFmt = "log_proxy_invalid_fmt" => ("プロキシの設定が正しくないため、通知できません: {error}", "Cannot notify because the proxy setting is invalid: {error}"),
+    LogNotifyBodyUnrecognizedFmt = "log_notify_body_unrecognized_fmt" => ("{url} の応答本文から通知の結果を判定できませんでした。成功とみなします: {body}", "Could not determine the result from the response body of {url}; assuming success: {body}"),
+    LogCheckIpFailedFmt = "log_check_ip_failed_fmt" => ("確認サービスで現在のアドレスを確認できませんでした: {error}", "Could not look up the current address with the check-IP services: {error}"),
+    LogPrivateIpv4SkippedFmt = "log_private_ipv4_skipped_fmt" => ("確認サービスで求めたIPv4アドレス {ip} は {range} の範囲にあり、インターネットからは届かないため、通知を見送りました。CGNATや上位のルーターの内側にある可能性があります。", "Skipped the IPv4 update: the looked-up address {ip} is in {range} and cannot be reached from the internet. The connection may be behind carrier-grade NAT or another router."),
+    LogPrivateIpv4NotifiedFmt = "log_private_ipv4_notified_fmt" => ("通知したIPv4アドレス {ip} は {range} の範囲にあり、インターネットからは届きません。CGNATや上位のルーターの内側にある可能性があります。", "The notified IPv4 address {ip} is in {range} and cannot be reached from the internet. The connection may be behind carrier-grade NAT or another router."),
+    NotifyKindNetwork = "notify_kind_network" => ("通信エラー", "Network error"),
+    NotifyKindAuth = "notify_kind_auth" => ("認証エラー", "Authentication failed"),
+    NotifyKindServer = "notify_kind_server" => ("サーバーエラー", "Server error"),
+    NotifyKindRateLimited = "notify_kind_rate_limited" => ("リクエスト過多", "Rate limited"),
+    NotifyKindHttp = "notify_kind_http" => ("HTTPエラー", "HTTP error"),
+    NotifyKindSkipped = "notify_kind_skipped" => ("見送り", "Skipped"),
+    LogNotifyRetryFmt = "log_notify_retry_fmt" => ("{url} への通知に失敗しました（{attempt}/{max}回目）。{delay}秒後に再試行します: {error}", "Notification to {url} failed (attempt {attempt}/{max}). Retrying in {delay}s: {error}"),
+    LogRetryAfterCooldownFmt = "log_retry_after_cooldown_fmt" => ("サーバーから{seconds}秒待つよう指示されたため（Retry-After）、{until} までサービスの通知を見送ります。", "The server asked to wait {seconds}s (Retry-After); the service will hold notifications until {until}."),
+    LogIpv4FailFmt = "log_ipv4_fail_fmt" => ("IPv4通知に失敗しました: {error}", "IPv4 Notification failed: {error}"),
+    LogIpv6FailFmt = "log_ipv6_fail_fmt" => ("IPv6通知に失敗しました: {error}", "IPv6 Notification failed: {error}"),
+    TestSendingFmt = "test_sending_fmt" => ("アカウント '{id}' の認証情報を確認しています（{url}）...", "Checking the credentials of account '{id}' ({url})..."),
+    TestOkFmt = "test_ok_fmt" => ("認証に成功しました。MyDNS.JPが受け付けたアドレス: {addr}", "Credentials are valid. Address accepted by MyDNS.JP: {addr}"),
+    TestAuthFailedFmt = "test_auth_failed_fmt" => ("認証に失敗しました。MasterIDまたはパスワードが正しくありません。（{detail}）", "Authentication failed. The MasterID or password is incorrect. ({detail})"),
+    TestInconclusiveFmt = "test_inconclusive_fmt" => ("認証情報を確認できませんでした。{kind}: {detail}", "Could not verify the credentials. {kind}: {detail}"),
+    LogLoginRequestFmt = "log_login_request_fmt" => ("LOGIN {url}", "LOGIN {url}"),
+    LogLoginNotifiedFmt = "log_login_notified_fmt" => ("ログインで通知完了 {url}", "Notified by login {url}"),
+    LogHttpRequestFmt = "log_http_request_fmt" => ("GET {url}", "GET {url}"),
+    LogHttpResponseFmt = "log_http_response_fmt" => ("{version} {status}（{ms} ミリ秒）", "{version} {status} ({ms} ms)"),
+    LogHttpBodyFmt = "log_http_body_fmt" => ("応答本文（{bytes} バイト）: {body}", "Response body ({bytes} bytes): {body}"),
+    LogLastErrorSaveFailedFmt = "log_last_error_save_failed_fmt" => ("最終エラーの記録を保存先に書き込めませんでした: {error}", "Could not write the last error to the account store: {error}"),
+    LogAccountSuspendedFmt = "log_account_suspended_fmt" => ("アカウント {id} の通知が{count}回続けて認証エラーになったため、このアカウントの通知を停止しました。MasterIDとパスワードを確認してから、`account enable {id}` で再開してください。", "Suspended notifications for account {id} after {count} consecutive authentication failures. Check the MasterID and password, then resume with `account enable {id}`."),
+    LogAccountSuspendedSkippedFmt = "log_account_suspended_skipped_fmt" => ("認証エラーが続いたため、{time} から通知を停止しています。`account enable {id}` で再開できます。", "Notifications suspended since {time} after repeated authentication failures. Resume with `account enable {id}`."),
+    LogAccountResumed = "log_account_resumed" => ("通知に成功したため、このアカウントの通知の停止を解除しました。", "Notification succeeded; lifted the suspension of this account."),
+    LogSuspendSaveFailedFmt = "log_suspend_save_failed_fmt" => ("認証エラーが続いていますが、通知の停止を保存先に記録できませんでした: {error}", "Authentication keeps failing, but the suspension could not be written to the account store: {error}"),
+    LogLastSuccessSaveFailedFmt = "log_last_success_save_failed_fmt" => ("最終成功の記録を保存先に書き込めませんでした: {error}", "Could not write the last success to the account store: {error}"),
+    LogNotifyStatusFmt = "log_notify_status_fmt" => ("通知完了 {url}: ステータス {status}", "Notified {url}: Status {status}"),
+
+    // dnsverify.rs
+    LogDnsVerifiedFmt = "log_dns_verified_fmt" => ("{host} のDNSレコードが通知したアドレス（{addr}）に更新されたことを確認しました。", "Verified that DNS for {host} resolves to the notified address ({addr})."),
+    LogDnsMismatchFmt = "log_dns_mismatch_fmt" => ("{host} のDNSレコード（{resolved}）が、通知したアドレス（{addr}）とまだ一致しません（{attempt}/{max}回目）。", "DNS for {host} ({resolved}) does not match the notified address ({addr}) yet (attempt {attempt}/{max})."),
+    LogDnsResolveFailedFmt = "log_dns_resolve_failed_fmt" => ("{host} の名前解決に失敗しました（{attempt}/{max}回目）: {error}", "Failed to resolve {host} (attempt {attempt}/{max}): {error}"),
+    LogDnsNotConvergedFmt = "log_dns_not_converged_fmt" => ("{host} のDNSレコードが、通知したアドレス（{addr}）に反映されていません。ホスト名の設定とMyDNS.JPの登録内容を確認してください。", "DNS for {host} has not converged to the notified address ({addr}). Check the hostname setting and the MyDNS.JP registration."),
+
+    // connectivity.rs
+    LogConnectivityNlmFailedFmt = "log_connectivity_nlm_failed_fmt" => ("ネットワーク一覧マネージャーで接続の状態を確認できませんでした: {error}", "Could not query connectivity from the Network List Manager: {error}"),
+    LogConnectivityCostFailedFmt = "log_connectivity_cost_failed_fmt" => ("接続のコストの情報を取得できませんでした: {error}", "Could not get the connection cost information: {error}"),
+    LogConnectivityProbeFailedFmt = "log_connectivity_probe_failed_fmt" => ("接続の確認のためのリクエスト（{url}）に失敗しました: {error}", "Connectivity probe to {url} failed: {error}"),
+
+    // dnscheck.rs
+    LogDnsCheckDivergedFmt = "log_dns_check_diverged_fmt" => ("{host} のDNSレコード（{resolver} の応答: {resolved}）が、最後に通知したアドレス（{addr}）と食い違っています（{total}件中{count}件のリゾルバー）。通知し直します。", "DNS for {host} ({resolver} answered {resolved}) differs from the last notified address ({addr}) at {count} of {total} resolvers. Notifying again."),
+    LogDnsCheckQueryFailedFmt = "log_dns_check_query_failed_fmt" => ("{resolver} で {host} を確認できませんでした: {error}", "Could not check {host} at {resolver}: {error}"),
+    LogDnsCheckRenotifyFmt = "log_dns_check_renotify_fmt" => ("DNSレコードが食い違っていた{count}件のアカウントを、通知し直します。", "Notifying {count} account(s) whose DNS records diverged."),
+
+    // ping.rs
+    LogPingSentFmt = "log_ping_sent_fmt" => ("死活監視のping URL {url} にpingを送りました。", "Sent a ping to {url}."),
+    LogPingFailedFmt = "log_ping_failed_fmt" => ("死活監視のping URL {url} にpingを送れませんでした: {error}", "Failed to send a ping to {url}: {error}"),
+
+    // webhook.rs
+    WebhookFailureFmt = "webhook_failure_fmt" => ("{id} の通知に失敗しました: {error}", "Notification for {id} failed: {error}"),
+    WebhookRecoveryFmt = "webhook_recovery_fmt" => ("{id} の通知が回復しました（{ip}）。", "Notification for {id} recovered ({ip})."),
+    WebhookIpChangedFmt = "webhook_ip_changed_fmt" => ("{id} のアドレスが {ip} に変わりました。", "The address of {id} changed to {ip}."),
+    WebhookExpiryFmt = "webhook_expiry_fmt" => ("{id} の最後の通知成功から{hours}時間が経過しました。あと約{remaining}時間でMyDNS.JPのレコードが失効します。", "No successful notification for {id} in {hours} hours. The MyDNS.JP record expires in about {remaining} hours."),
+    LogWebhookSentFmt = "log_webhook_sent_fmt" => ("Webhook（{event}）を {url} に送信しました。", "Sent the {event} webhook to {url}."),
+    LogWebhookFailedFmt = "log_webhook_failed_fmt" => ("Webhook（{event}）を {url} に送信できませんでした: {error}", "Failed to send the {event} webhook to {url}: {error}"),
+
+    // toast.rs
+    ToastTitle = "toast_title" => ("MyDNS Adapter: 通知の失敗", "MyDNS Adapter: notification failing"),
+    ToastFailuresFmt = "toast_failures_fmt" => ("アカウント {id} の通知が{count}回続けて失敗しました。{error}", "Notification for account {id} failed {count} times in a row. {error}"),
+    ToastExpiryTitle = "toast_expiry_title" => ("MyDNS Adapter: レコードの失効が近づいています", "MyDNS Adapter: record about to expire"),
+    ToastExpiryFmt = "toast_expiry_fmt" => ("アカウント {id} は{hours}時間通知に成功していません。あと約{remaining}時間でMyDNS.JPのレコードが失効します。", "Account {id} has not been updated for {hours} hours. The MyDNS.JP record expires in about {remaining} hours."),
+    ToastMonitorStarted = "toast_monitor_started" => ("--- 通知の失敗を監視しています（Ctrl+Cで終了） ---", "--- Watching for notification failures (Ctrl+C to exit) ---"),
+    LogToastFailedFmt = "log_toast_failed_fmt" => ("トースト通知を表示できませんでした: {error}", "Could not show a toast notification: {error}"),
+
+    // mail.rs
+    MailAlertSubjectFmt = "mail_alert_subject_fmt" => ("[MyDNS Adapter] {host}: 通知の失敗が続いています", "[MyDNS Adapter] {host}: notifications keep failing"),
+    MailAlertIntroFmt = "mail_alert_intro_fmt" => ("{host} のMyDNS Adapterで、次のアカウントの通知が{minutes}分以上失敗し続けています。", "MyDNS Adapter on {host} has been failing to notify the following accounts for {minutes} minutes or more."),
+    MailAlertAccountFmt = "mail_alert_account_fmt" => ("- {id}: {since} から{count}回失敗: {error}", "- {id}: failed {count} times since {since}: {error}"),
+    MailTestSubjectFmt = "mail_test_subject_fmt" => ("[MyDNS Adapter] {host}: テストメール", "[MyDNS Adapter] {host}: test message"),
+    MailTestBody = "mail_test_body" => ("MyDNS Adapterのメール通知の設定を確認するためのテストメールです。", "This is a test message to check the mail alert settings of MyDNS Adapter."),
+    MailTestSentFmt = "mail_test_sent_fmt" => ("[成功] テストメールを {to} に送信しました。", "[Success] Sent a test message to {to}."),
+    MailTestFailedFmt = "mail_test_failed_fmt" => ("テストメールを送信できませんでした: {error}", "Failed to send the test message: {error}"),
+    LogMailAlertSentFmt = "log_mail_alert_sent_fmt" => ("通知の失敗が続いている{count}件のアカウントを、メールで {to} に知らせました。", "Mailed {to} about {count} account(s) that keep failing."),
+    LogMailAlertFailedFmt = "log_mail_alert_failed_fmt" => ("SMTPサーバー {server} でメールを送信できませんでした。次の通知の後にもう一度送ります: {error}", "Failed to send mail via {server}; will retry after the next notification: {error}"),
+
+    // netif.rs
+    LogBindInterfaceNotFoundFmt = "log_bind_interface_not_found_fmt" => ("送信元のインターフェイス「{interface}」が見つかりません。", "Interface \"{interface}\" to send from was not found."),
+    LogBindAddressMissingFmt = "log_bind_address_missing_fmt" => ("送信元のインターフェイス「{interface}」に{proto}アドレスがありません。", "Interface \"{interface}\" to send from has no {proto} address."),
+
+    // checkip.rs
+    LogCheckIpDisagreeFmt = "log_check_ip_disagree_fmt" => ("確認サービスが返したアドレスが一致しませんでした: {answers}", "The check-IP services returned different addresses: {answers}"),
+    LogCheckIpUndecidedFmt = "log_check_ip_undecided_fmt" => ("確認サービスが返したアドレスを、多数決で1つに決められませんでした: {answers}", "Could not pick one address by majority from the check-IP services: {answers}"),
+    LogGlobalAddressChangedFmt = "log_global_address_changed_fmt" => ("グローバルアドレスが {old} から {new} に変わりました。", "The global address changed from {old} to {new}."),
+
+    // expiry.rs
+    LogExpiryWarningFmt = "log_expiry_warning_fmt" => ("最後の通知成功から{hours}時間が経過しました。あと約{remaining}時間でMyDNS.JPのレコードが失効します。", "No successful notification for {hours} hours. The MyDNS.JP record expires in about {remaining} hours."),
+
+    // simulate.rs
+    SimulateTitle = "simulate_title" => ("--- シミュレーションモード ---", "--- Simulation Mode ---"),
+    SimulateServerFmt = "simulate_server_fmt" => ("模擬サーバーを {addr} で起動しました。", "Mock server started at {addr}."),
+    SimulateResultFmt = "simulate_result_fmt" => ("[{mark}] {name}: {result}", "[{mark}] {name}: {result}"),
+    SimulateSucceeded = "simulate_succeeded" => ("通知成功", "notification succeeded"),
+    SimulateFailed = "simulate_failed" => ("通知失敗", "notification failed"),
+    SimulateSummaryFmt = "simulate_summary_fmt" => ("\n{passed} / {total} シナリオが期待どおりに動作しました。", "\n{passed} of {total} scenarios behaved as expected."),
+    SimulateFailedSummary = "simulate_failed_summary" => ("一部のシナリオが期待どおりに動作しませんでした。", "Some scenarios did not behave as expected."),
+
+    // doctor.rs
+    DoctorTitle = "doctor_title" => ("--- 動作環境の診断 ---", "--- Diagnostics ---"),
+    DoctorHeaderFmt = "doctor_header_fmt" => ("バージョン: {version},  実行日時: {time},  設定の保存先: {scope}", "Version: {version},  Run at: {time},  Configuration scope: {scope}"),
+    DoctorItemElevation = "doctor_item_elevation" => ("管理者権限", "Elevation"),
+    DoctorItemRegistry = "doctor_item_registry" => ("アカウント設定", "Account settings"),
+    DoctorItemSettings = "doctor_item_settings" => ("全体の設定", "Global settings"),
+    DoctorItemRegistryWrite = "doctor_item_registry_write" => ("設定の書き込み", "Settings write access"),
+    DoctorItemLog = "doctor_item_log" => ("ログファイル", "Log file"),
+    DoctorItemIpv4 = "doctor_item_ipv4" => ("IPv4の接続（ipv4.mydns.jp）", "IPv4 connectivity (ipv4.mydns.jp)"),
+    DoctorItemIpv6 = "doctor_item_ipv6" => ("IPv6の接続（ipv6.mydns.jp）", "IPv6 connectivity (ipv6.mydns.jp)"),
+    DoctorItemService = "doctor_item_service" => ("サービス", "Service"),
+    DoctorItemClock = "doctor_item_clock" => ("時計", "Clock"),
+    DoctorElevated = "doctor_elevated" => ("管理者として実行されています。", "Running as administrator."),
+    DoctorNotElevated = "doctor_not_elevated" => ("管理者として実行されていません。設定の変更とサービスの操作はできません。", "Not running as administrator. Settings changes and service operations are unavailable."),
+    DoctorAccountsFmt = "doctor_accounts_fmt" => ("{count}件のアカウント", "{count} account(s)"),
+    DoctorReadable = "doctor_readable" => ("読み込めます。", "Readable."),
+    DoctorWritable = "doctor_writable" => ("書き込めます。", "Writable."),
+    DoctorRegistryReadOnly = "doctor_registry_read_only" => ("書き込む権限がありません。", "No permission to write."),
+    DoctorReachableFmt = "doctor_reachable_fmt" => ("HTTP {status}（{ms} ミリ秒）", "HTTP {status} ({ms} ms)"),
+    DoctorServiceNotInstalled = "doctor_service_not_installed" => ("インストールされていません。", "Not installed."),
+    DoctorClockSkewFmt = "doctor_clock_skew_fmt" => ("MyDNS.JPのサーバーとのずれ: {seconds} 秒", "Offset from the MyDNS.JP server: {seconds} s"),
+    DoctorClockUnknown = "doctor_clock_unknown" => ("MyDNS.JPに接続できなかったため、確認できませんでした。", "Could not be checked because MyDNS.JP was unreachable."),
+    DoctorSummaryFmt = "doctor_summary_fmt" => ("\n失敗: {fail} 件、警告: {warn} 件", "\nFailures: {fail}, Warnings: {warn}"),
+
+    // history.rs
+    HistoryTitle = "history_title" => ("--- 通知の履歴 ---", "--- Notification History ---"),
+    HistoryEmpty = "history_empty" => ("履歴はありません。", "No history recorded."),
+    HistoryRowFmt = "history_row_fmt" => ("{time}  {id}  {proto}  {ms} ミリ秒  {result}", "{time}  {id}  {proto}  {ms} ms  {result}"),
+
+    // ipc.rs
+    IpcServiceUnavailable = "ipc_service_unavailable" => ("サービスに接続できません。サービスが実行中か確認してください。", "Cannot connect to the service. Make sure the service is running."),
+    IpcServiceBusy = "ipc_service_busy" => ("サービスが他の要求を処理中のため、接続できませんでした。しばらくしてから再度実行してください。", "The service is busy with other requests. Please try again later."),
+    IpcServerNotServiceFmt = "ipc_server_not_service_fmt" => ("名前付きパイプのサーバー（PID {pid}）がサービスのプロセスではないため、要求を送信しませんでした。", "Did not send the request because the named pipe server (PID {pid}) is not the service process."),
+    IpcInvalidResponse = "ipc_invalid_response" => ("サービスからの応答を解釈できませんでした。", "Could not parse the response from the service."),
+    IpcRequestFailedFmt = "ipc_request_failed_fmt" => ("サービスが要求を処理できませんでした: {error}", "The service could not process the request: {error}"),
+    IpcNotifyNowSuccess = "ipc_notify_now_success" => ("[成功] サービスに即時通知を要求しました。", "[Success] Requested an immediate notification from the service."),
+    IpcReloadSuccessFmt = "ipc_reload_success_fmt" => ("[成功] サービスがアカウント設定を読み込み直しました（アカウント数: {count}）。", "[Success] The service reloaded the account settings ({count} accounts)."),
+    IpcStatusTitle = "ipc_status_title" => ("--- サービスの状態 ---", "--- Service Status ---"),
+    IpcStatusVersion = "ipc_status_version" => ("バージョン", "Version"),
+    IpcStatusStarted = "ipc_status_started" => ("開始時刻", "Started"),
+    IpcStatusAccounts = "ipc_status_accounts" => ("アカウント数", "Accounts"),
+    IpcStatusPaused = "ipc_status_paused" => ("一時停止中", "Paused"),
+    IpcStatusRoundRunning = "ipc_status_round_running" => ("通知中", "Notifying"),
+    IpcStatusNextRound = "ipc_status_next_round" => ("次の定期通知", "Next periodic notification"),
+
+    // schtask.rs
+    AdminRequiredTask = "admin_required_task" => ("タスクの登録と削除には管理者権限が必要です。管理者として実行してください。", "Administrator privileges are required to register or remove the scheduled task. Please run as administrator."),
+    TaskServiceInstalledWarning = "task_service_installed_warning" => ("[警告] サービスもインストールされています。二重に通知しないよう、どちらか一方だけを使ってください。", "[Warning] The service is also installed. Use only one of them to avoid duplicate notifications."),
+    TaskInstalledFmt = "task_installed_fmt" => ("タスク '{name}' を登録しました。起動時、ネットワークへの接続時、{minutes}分ごとに通知します。", "Scheduled task '{name}' registered. It notifies at startup, on network connection, and every {minutes} minutes."),
+    TaskUninstalledFmt = "task_uninstalled_fmt" => ("タスク '{name}' を削除しました。", "Scheduled task '{name}' removed."),
+    TaskNotInstalledFmt = "task_not_installed_fmt" => ("タスク '{name}' は登録されていません。", "Scheduled task '{name}' is not registered."),
+    TaskDescription = "task_description" => ("MyDNS.JPにIPアドレスを通知します（MyDNS.JP Adapter）。", "Notifies MyDNS.JP of this machine's IP address (MyDNS.JP Adapter)."),
+
+    // crash.rs
+    PanicOccurredFmt = "panic_occurred_fmt" => ("予期しないエラーが発生しました。詳細をログとイベントログに記録しました: {summary}", "An unexpected error occurred. Details were written to the log and the Event Log: {summary}"),
+    LogPanicFmt = "log_panic_fmt" => ("パニックが発生しました（バージョン {version}、スレッド {thread}、{location}）: {message}", "Panic (version {version}, thread {thread}, at {location}): {message}"),
+
+    // stats.rs
+    StatsTitle = "stats_title" => ("--- 利用統計 ---", "--- Usage Statistics ---"),
+    StatsEnabled = "stats_enabled" => ("[成功] 利用統計の記録を有効にしました。統計はこのマシンの外に送信されません。", "[Success] Usage statistics enabled. Statistics never leave this machine."),
+    StatsDisabled = "stats_disabled" => ("[成功] 利用統計の記録を無効にしました。", "[Success] Usage statistics disabled."),
+    StatsNotEnabled = "stats_not_enabled" => ("利用統計の記録は無効です。有効にするには --stats --opt-in を実行してください。", "Usage statistics are disabled. Run --stats --opt-in to enable them."),
+    StatsEmpty = "stats_empty" => ("記録された統計はありません。", "No statistics have been recorded."),
+    StatsMonthFmt = "stats_month_fmt" => ("{month}: 通知 {total}回 (成功 {ok} / 失敗 {ng}),  IP変更 {ip}回,  稼働 {hours}時間", "{month}: {total} notifications ({ok} ok / {ng} failed),  {ip} IP changes,  {hours} h uptime"),
+    StatsYearFmt = "stats_year_fmt" => ("\n{year}年は {total}回通知し、IPアドレスは {ip}回変わりました。", "\nIn {year}: {total} notifications, your IP changed {ip} times."),
+
+    // bench.rs
+    BenchTitle = "bench_title" => ("--- ベンチマーク ---", "--- Benchmark ---"),
+    BenchResultFmt = "bench_result_fmt" => ("{label}: {n}回, 平均 {avg} ms, {rate} 回/秒", "{label}: {n} runs, avg {avg} ms, {rate} ops/s"),
+    BenchSkippedFmt = "bench_skipped_fmt" => ("{label}: スキップしました ({error})", "{label}: skipped ({error})"),
+    BenchLogWrite = "bench_log_write" => ("ログ書き込み", "Log write"),
+    BenchLogRotate = "bench_log_rotate" => ("ログ書き込み（ローテーションあり）", "Log write with rotation"),
+    BenchConfigLoad = "bench_config_load" => ("設定の読み込み", "Config load"),
+    BenchConfigSave = "bench_config_save" => ("設定の保存", "Config save"),
 }
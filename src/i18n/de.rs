@@ -0,0 +1,367 @@
+//! ドイツ語のメッセージ。
+//!
+//! ログと開発者向けの機能（`--simulate`、`--bench`）のメッセージは翻訳せず、英語のメッセージを使います。
+
+use super::MessageKey;
+
+/// キーとドイツ語のメッセージの組。
+#[rustfmt::skip]
+pub(super) static MESSAGES: &[(MessageKey, &str)] = &[
+    // main.rs
+    (MessageKey::LangInvalid, "Nicht unterstützte Sprache. Verwenden Sie ja, en, zh, ko, de, fr oder es."),
+    (MessageKey::ConfigTitle, "--- MyDNS Adapter Konfiguration ---"),
+    (MessageKey::ConfigLoaded, "\nAktuelle Konfiguration geladen. Drücken Sie die Eingabetaste, um Werte beizubehalten."),
+    (MessageKey::MasterIdPrompt, "MasterID"),
+    (MessageKey::PasswordPrompt, "Passwort"),
+    (MessageKey::Ipv4NotifyPrompt, "IPv4-Benachrichtigung aktivieren?"),
+    (MessageKey::Ipv6NotifyPrompt, "IPv6-Benachrichtigung aktivieren?"),
+    (MessageKey::KeepAlivePrompt, "Intervall für erzwungene Benachrichtigung in Stunden (0 zum Deaktivieren)"),
+    (MessageKey::NumberInvalid, "Bitte geben Sie eine nicht negative ganze Zahl ein."),
+    (MessageKey::RegistrySaveSuccess, "\n[Erfolg] Konfiguration in der Registrierung gespeichert."),
+    (MessageKey::RegistrySaveFailFmt, "\n[Fehler] Fehler beim Speichern in der Registrierung: {error}"),
+    (MessageKey::InputPromptPwFmt, "{item} eingeben (Aktuell: {current}, Eingabetaste zum Beibehalten): "),
+    (MessageKey::InputPromptFmt, "{item} eingeben (Aktuell: {current}): "),
+    (MessageKey::InputPromptNewFmt, "{item} eingeben: "),
+    (MessageKey::NotSet, "(Nicht festgelegt)"),
+    (MessageKey::YesNoPromptFmt, "{question} (Aktuell: {current}) {hint}: "),
+    (MessageKey::YesNoHintTrue, "(J/n)"),
+    (MessageKey::YesNoHintFalse, "(j/N)"),
+    (MessageKey::YesNoInvalid, "Bitte geben Sie 'j' oder 'n' ein oder drücken Sie die Eingabetaste."),
+    (MessageKey::YesNoAffirmativeWords, "y,yes,j,ja"),
+    (MessageKey::YesNoNegativeWords, "n,no,nein"),
+    (MessageKey::ViewTitle, "--- Aktuelle MyDNS-Einstellungen ---"),
+    (MessageKey::ViewMasterIdFmt, "MasterID: {id}"),
+    (MessageKey::ViewPasswordFmt, "Passwort: {password}"),
+    (MessageKey::ViewIpv4Fmt, "IPv4-Benachrichtigung: {value}"),
+    (MessageKey::ViewIpv6Fmt, "IPv6-Benachrichtigung: {value}"),
+    (MessageKey::Yes, "Ja"),
+    (MessageKey::No, "Nein"),
+    (MessageKey::ViewNoAccounts, "Es sind keine Konten eingerichtet."),
+    (MessageKey::ViewListFmt, "MasterID: {id},  Passwort: {pw},  IPv4: {v4},  IPv6: {v6}"),
+    (MessageKey::AddTitle, "--- Neues Konto hinzufügen ---"),
+    (MessageKey::EditTitle, "--- Konto bearbeiten ---"),
+    (MessageKey::RemoveTitle, "--- Konto entfernen ---"),
+    (MessageKey::AccountExistsFmt, "Das Konto '{id}' existiert bereits."),
+    (MessageKey::AccountNotFoundFmt, "Das Konto '{id}' wurde nicht gefunden."),
+    (MessageKey::SelectAccountPrompt, "Wählen Sie das zu bearbeitende Konto:"),
+    (MessageKey::SelectAccountIndexPrompt, "Nummer oder MasterID eingeben: "),
+    (MessageKey::InvalidSelection, "Ungültige Auswahl."),
+    (MessageKey::RemoveNeedsYes, "Das Entfernen kann nicht bestätigt werden, da die Standardeingabe kein Terminal ist. Verwenden Sie --yes, um ohne Bestätigung zu entfernen."),
+    (MessageKey::ConfirmRemoveFmt, "Möchten Sie das Konto '{id}' wirklich entfernen?"),
+    (MessageKey::ConfirmPromptFmt, "{question} {hint}: "),
+    (MessageKey::RemoveSuccess, "[Erfolg] Das Konto wurde entfernt."),
+    (MessageKey::RemoveFailFmt, "[Fehler] Das Konto konnte nicht entfernt werden: {error}"),
+    (MessageKey::BatchRowErrorFmt, "Zeile {line}: {error}"),
+    (MessageKey::BatchInvalidFmt, "Es wurden keine Konten hinzugefügt, da {count} Zeile(n) Fehler enthalten."),
+    (MessageKey::BatchEmpty, "Es gibt keine Konten zum Hinzufügen."),
+    (MessageKey::BatchSuccessFmt, "[Erfolg] {count} Konto/Konten hinzugefügt."),
+    (MessageKey::BatchColumnCountFmt, "Falsche Anzahl von Spalten ({count}). Verwenden Sie das Format \"MasterID,Passwort,IPv4,IPv6\"."),
+    (MessageKey::BatchInvalidFlagFmt, "Ungültiger Wert für {name}: {value} (verwenden Sie yes oder no)"),
+    (MessageKey::BatchUnterminatedQuote, "Eine Spalte in Anführungszeichen ist nicht geschlossen."),
+    (MessageKey::RenameSuccessFmt, "[Erfolg] Das Konto '{old}' wurde in '{new}' umbenannt."),
+    (MessageKey::AccountEnabledFmt, "[Erfolg] Die Benachrichtigungen für das Konto '{id}' wurden fortgesetzt."),
+    (MessageKey::AccountNotSuspendedFmt, "Die Benachrichtigungen für das Konto '{id}' sind nicht ausgesetzt."),
+    (MessageKey::DryRunTitle, "[Probelauf] Die folgenden Anfragen würden gesendet (es wird nichts gesendet):"),
+    (MessageKey::DryRunRequestFmt, "  {id} {proto}: {method} {url}  ({auth})"),
+    (MessageKey::DryRunBasicAuthFmt, "Basic-Authentifizierung: {user} / ********"),
+    (MessageKey::DryRunLoginAuthFmt, "Anmeldung: {user} / ******** (Klartext)"),
+    (MessageKey::DryRunNoAuth, "keine Anmeldedaten"),
+    (MessageKey::DryRunBindFmt, "    Absenderadresse: {addr}"),
+    (MessageKey::DryRunSkippedFmt, "  {id} {proto}: wird nicht gesendet. {reason}"),
+    (MessageKey::DryRunSuspended, "Die Benachrichtigungen sind nach wiederholten Authentifizierungsfehlern ausgesetzt (--force sendet trotzdem)."),
+    (MessageKey::DryRunPrivateIpv4, "Die aktuelle IPv4-Adresse ist aus dem Internet nicht erreichbar, daher wird die Aktualisierung übersprungen."),
+    (MessageKey::DryRunNone, "Es würden keine Anfragen gesendet."),
+    (MessageKey::NotifySummaryTitle, "Benachrichtigungsergebnisse:"),
+    (MessageKey::NotifySummaryRowFmt, "  {id}  {proto}  {result}  {ms} ms ({attempts} Versuche){detail}"),
+    (MessageKey::NotifySummarySkippedFmt, "  {id}  -     {reason}"),
+    (MessageKey::NotifySummarySuspended, "Nach wiederholten Authentifizierungsfehlern ausgesetzt (nicht gesendet)"),
+    (MessageKey::NotifySummaryInvalidProxy, "Ungültige Proxy-URL (nicht gesendet)"),
+    (MessageKey::NotifySummaryAborted, "Die Benachrichtigungsaufgabe wurde abnormal beendet"),
+    (MessageKey::NotifySummaryNoProtocol, "Kein Protokoll zu benachrichtigen"),
+    (MessageKey::NotifySummaryTotalFmt, "{ok} erfolgreich, {failed} fehlgeschlagen"),
+    (MessageKey::ElevationConfirm, "Dieser Vorgang erfordert Administratorrechte. Als Administrator erneut ausführen?"),
+    (MessageKey::ElevationRequired, "Dieser Vorgang erfordert Administratorrechte. Führen Sie ihn in einer Eingabeaufforderung mit erhöhten Rechten aus."),
+    (MessageKey::ElevationCancelled, "Die Ausführung als Administrator wurde abgebrochen."),
+    (MessageKey::ElevatedPressEnter, "\nDrücken Sie die Eingabetaste, um dieses Fenster zu schließen..."),
+    (MessageKey::ServicePasswordPromptFmt, "das Passwort für das Konto '{account}'"),
+    (MessageKey::ServicePasswordNotNeededFmt, "Für das Konto '{account}' kann kein Passwort angegeben werden."),
+    (MessageKey::ConfirmPurge, "Alle gespeicherten Konten, Einstellungen und Protokolldateien werden gelöscht. Dies kann nicht rückgängig gemacht werden. Fortfahren?"),
+    (MessageKey::PurgeNeedsYes, "Das Löschen kann nicht bestätigt werden, da die Standardeingabe kein Terminal ist. Verwenden Sie --yes, um ohne Bestätigung zu löschen."),
+    (MessageKey::PurgeSuccess, "[Erfolg] Gespeicherte Konten, Einstellungen und Protokolldateien wurden gelöscht."),
+    (MessageKey::AddSuccess, "[Erfolg] Das Konto wurde hinzugefügt."),
+    (MessageKey::NoAccountsAddPrompt, "Keine Konten gefunden. Neues Konto anlegen?"),
+    (MessageKey::OperationCancelled, "Vorgang abgebrochen."),
+    (MessageKey::EditTargetFmt, "Zielkonto: {id}"),
+    (MessageKey::ViewRuntimeFmt, "    Status: {state},  Letzter Versuch: {time},  Ergebnis: {result}"),
+    (MessageKey::ViewRuntimeInFlight, "Benachrichtigung läuft"),
+    (MessageKey::ViewRuntimeIdle, "Bereit"),
+    (MessageKey::ViewPaused, "\n[Hinweis] Die regelmäßige Benachrichtigung ist angehalten. Mit --resume fortsetzen."),
+    (MessageKey::PauseSuccess, "[Erfolg] Die regelmäßige Benachrichtigung wurde angehalten."),
+    (MessageKey::ResumeSuccess, "[Erfolg] Die regelmäßige Benachrichtigung wurde fortgesetzt."),
+    (MessageKey::ViewIntervalFmt, "Benachrichtigungsintervall: {minutes} Minuten"),
+    (MessageKey::IntervalSetFmt, "Das Benachrichtigungsintervall wurde auf {minutes} Minuten gesetzt. Ein laufender Dienst übernimmt es nach der nächsten planmäßigen Benachrichtigung."),
+    (MessageKey::IntervalOutOfRangeFmt, "Das Benachrichtigungsintervall muss zwischen {min} und {max} Minuten liegen."),
+    (MessageKey::ViewRetryFmt, "Maximale Anzahl von Benachrichtigungsversuchen: {count}"),
+    (MessageKey::RetrySetFmt, "Die maximale Anzahl von Benachrichtigungsversuchen wurde auf {count} gesetzt."),
+    (MessageKey::RetryOutOfRangeFmt, "Die maximale Anzahl von Benachrichtigungsversuchen muss zwischen {min} und {max} liegen."),
+    (MessageKey::ViewConcurrencyFmt, "Gleichzeitig benachrichtigte Konten: {count}"),
+    (MessageKey::ConcurrencySetFmt, "Die Anzahl gleichzeitig benachrichtigter Konten wurde auf {count} gesetzt."),
+    (MessageKey::ConcurrencyOutOfRangeFmt, "Die Anzahl gleichzeitig benachrichtigter Konten muss zwischen {min} und {max} liegen."),
+    (MessageKey::ViewRequestTimeoutFmt, "Zeitlimit für Benachrichtigungsanfragen: {seconds} Sekunden"),
+    (MessageKey::RequestTimeoutSetFmt, "Das Zeitlimit für Benachrichtigungsanfragen wurde auf {seconds} Sekunden gesetzt."),
+    (MessageKey::RequestTimeoutOutOfRangeFmt, "Das Zeitlimit für Benachrichtigungsanfragen muss zwischen {min} und {max} Sekunden liegen."),
+    (MessageKey::Ipv4UrlPrompt, "IPv4-Benachrichtigungs-URL"),
+    (MessageKey::Ipv6UrlPrompt, "IPv6-Benachrichtigungs-URL"),
+    (MessageKey::EndpointUrlInvalidFmt, "\"{url}\" ist keine gültige URL. Der aktuelle Wert wird beibehalten."),
+    (MessageKey::ViewEndpointFmt, "  Benachrichtigungs-URL ({proto}): {url}"),
+    (MessageKey::HostnamePrompt, "Im DNS zu prüfender Hostname (leer: überspringen, -: löschen)"),
+    (MessageKey::SetHostnameInvalidFmt, "\"{host}\" ist kein gültiger Hostname."),
+    (MessageKey::SetEndpointUrlInvalidFmt, "\"{url}\" ist keine gültige URL."),
+    (MessageKey::HostnameInvalidFmt, "\"{host}\" ist kein gültiger Hostname. Der aktuelle Wert wird beibehalten."),
+    (MessageKey::ViewLastSuccessFmt, "  Letzter Erfolg: {time}  (IPv4: {v4},  IPv6: {v6})"),
+    (MessageKey::ViewHostnameFmt, "  Zu prüfender Hostname: {host}"),
+    (MessageKey::ProviderPrompt, "Update-Dienst (mydns: MyDNS.JP, generic: beliebiger Dienst mit Update-URL)"),
+    (MessageKey::ProviderInvalid, "Der Dienst muss mydns oder generic sein."),
+    (MessageKey::UsernamePrompt, "Benutzername (wird auch als Kontoname verwendet)"),
+    (MessageKey::GenericIpv4UrlPrompt, "IPv4-Update-URL ({ip} und {host} werden ersetzt)"),
+    (MessageKey::GenericIpv6UrlPrompt, "IPv6-Update-URL (leer: wie IPv4, -: entfernen)"),
+    (MessageKey::GenericUrlRequired, "Ein generischer Dienst benötigt eine IPv4-Update-URL."),
+    (MessageKey::ViewProviderGeneric, "  Dienst: generisch (Update-URL)"),
+    (MessageKey::MethodPrompt, "Aktualisierungsmethode (http: HTTPS, pop3/imap/ftp: Anmeldung am Server)"),
+    (MessageKey::MethodInvalid, "Die Aktualisierungsmethode muss http, pop3, imap oder ftp sein."),
+    (MessageKey::MethodRequiresMyDns, "Die Aktualisierung per POP3/IMAP/FTP-Anmeldung ist nur für MyDNS.JP-Konten verfügbar."),
+    (MessageKey::MethodPlaintextWarning, "Hinweis: Bei POP3/IMAP/FTP-Anmeldungen wird das Passwort unverschlüsselt gesendet."),
+    (MessageKey::ViewMethodFmt, "  Aktualisierungsmethode: {method} (Anmeldung)"),
+    (MessageKey::ViewLogPathFmt, "Protokolldatei: {path}"),
+    (MessageKey::ViewLogArchiveFmt, "Protokollarchive: {count} behalten (komprimiert: {compress})"),
+    (MessageKey::ViewProxyFmt, "Proxy: {proxy}"),
+    (MessageKey::ProxyAuto, "Automatisch (WinHTTP-/Systemeinstellungen)"),
+    (MessageKey::ViewMetricsFmt, "Metriken: http://127.0.0.1:{port}/metrics"),
+    (MessageKey::MetricsSetFmt, "Metriken werden unter http://127.0.0.1:{port}/metrics bereitgestellt. Ein laufender Dienst übernimmt dies nach einem Neustart."),
+    (MessageKey::MetricsDisabled, "Der Metrik-Endpunkt wurde deaktiviert. Ein laufender Dienst übernimmt dies nach einem Neustart."),
+    (MessageKey::ViewAdminApiFmt, "Admin-API: http://127.0.0.1:{port}/api/"),
+    (MessageKey::AdminApiSetFmt, "Die Admin-API wird unter http://127.0.0.1:{port}/api/ bereitgestellt. Ein laufender Dienst übernimmt dies nach einem Neustart.\nSenden Sie Anfragen mit dem Header \"Authorization: Bearer {token}\"."),
+    (MessageKey::AdminApiDisabled, "Die Admin-API wurde deaktiviert. Ein laufender Dienst übernimmt dies nach einem Neustart."),
+    (MessageKey::PingUrlPrompt, "Überwachungs-Ping-URL, die nach einer erfolgreichen Aktualisierung aufgerufen wird (leer: keine, -: entfernen)"),
+    (MessageKey::ViewAccountPingUrlFmt, "  Ping-URL: {url}"),
+    (MessageKey::InterfacePrompt, "Name der Netzwerkschnittstelle oder lokale Adresse für den Versand (leer: beliebig, -: entfernen)"),
+    (MessageKey::ViewInterfaceFmt, "  Senden über: {interface}"),
+    (MessageKey::SchedulePrompt, "Meldezeitplan anstelle des Intervalls (z. B. */10 * * * *, at 04:05 daily; leer: Intervall, -: entfernen)"),
+    (MessageKey::ScheduleInvalidFmt, "Der Zeitplan konnte nicht gelesen werden (fünf Cron-Felder oder at HH:MM daily verwenden): {schedule}"),
+    (MessageKey::ViewScheduleFmt, "  Zeitplan: {schedule}"),
+    (MessageKey::ViewPingUrlFmt, "Überwachungs-Ping-URL: {url}"),
+    (MessageKey::PingUrlSetFmt, "Nach jeder Benachrichtigungsrunde, in der alle Konten erfolgreich sind, wird ein Ping an \"{url}\" gesendet."),
+    (MessageKey::PingUrlCleared, "Die Überwachungs-Ping-URL wurde entfernt."),
+    (MessageKey::ViewWebhookUrlFmt, "Webhook: {url}"),
+    (MessageKey::WebhookUrlSetFmt, "Fehler, Wiederherstellungen und Adressänderungen werden an \"{url}\" gesendet."),
+    (MessageKey::WebhookUrlCleared, "Die Webhook-URL wurde entfernt."),
+    (MessageKey::ViewToastFmt, "Toast-Benachrichtigung: nach {count} aufeinanderfolgenden Fehlern"),
+    (MessageKey::ToastSetFmt, "Nach {count} aufeinanderfolgenden Fehlern wird eine Toast-Benachrichtigung angezeigt."),
+    (MessageKey::ToastDisabled, "Toast-Benachrichtigungen wurden deaktiviert."),
+    (MessageKey::ViewMailFmt, "E-Mail-Warnungen: {to} (über {server}:{port}, nach {minutes} Minuten mit Fehlern)"),
+    (MessageKey::MailSettingsSaved, "Die E-Mail-Einstellungen wurden gespeichert. Mit --mail-test können Sie eine Testnachricht senden."),
+    (MessageKey::MailDisabled, "E-Mail-Warnungen wurden deaktiviert."),
+    (MessageKey::MailNotConfigured, "Legen Sie sowohl --smtp-server als auch --mail-to fest, um E-Mail-Warnungen zu verwenden."),
+    (MessageKey::SmtpServerInvalidFmt, "\"{server}\" ist kein gültiger SMTP-Server. Verwenden Sie HOST oder HOST:PORT."),
+    (MessageKey::MailAddressInvalidFmt, "\"{address}\" ist keine gültige E-Mail-Adresse."),
+    (MessageKey::MailTestSentFmt, "[Erfolg] Eine Testnachricht wurde an {to} gesendet."),
+    (MessageKey::MailTestFailedFmt, "Die Testnachricht konnte nicht gesendet werden: {error}"),
+    (MessageKey::ViewCheckIpv4UrlsFmt, "IPv4-Adressprüfdienste: {urls}"),
+    (MessageKey::ViewCheckIpv6UrlsFmt, "IPv6-Adressprüfdienste: {urls}"),
+    (MessageKey::ViewCheckIpPollFmt, "Prüfung der globalen Adresse: alle {minutes} Minuten"),
+    (MessageKey::CheckIpSettingsSaved, "Die Einstellungen der Adressprüfdienste wurden gespeichert."),
+    (MessageKey::ViewSkipPrivateIpv4, "CGNAT-/private IPv4-Adressen: werden nicht gemeldet"),
+    (MessageKey::SkipPrivateIpv4Enabled, "Aktualisierungen werden übersprungen, wenn die ermittelte IPv4-Adresse eine CGNAT- oder private Adresse ist."),
+    (MessageKey::SkipPrivateIpv4Disabled, "CGNAT- und private IPv4-Adressen werden gemeldet und im Protokoll als Warnung vermerkt."),
+    (MessageKey::ViewDnsCheckFmt, "DNS-Selbstprüfung: alle {minutes} Minuten"),
+    (MessageKey::DnsCheckSetFmt, "Die DNS-Einträge der Hostnamen werden alle {minutes} Minuten bei öffentlichen Resolvern geprüft."),
+    (MessageKey::DnsCheckDisabled, "Die DNS-Selbstprüfung wurde deaktiviert."),
+    (MessageKey::ViewMeteredFmt, "Getaktete Verbindungen: {policy}"),
+    (MessageKey::MeteredPolicyInvalid, "Die Richtlinie für getaktete Verbindungen muss ignore, reduce oder skip sein."),
+    (MessageKey::MeteredIgnore, "Auf getakteten Verbindungen wird wie gewohnt gemeldet."),
+    (MessageKey::MeteredReduce, "Auf getakteten Verbindungen werden geplante Meldungen höchstens einmal pro Stunde ausgeführt."),
+    (MessageKey::MeteredSkip, "Auf getakteten Verbindungen überspringt der Dienst automatische Meldungen."),
+    (MessageKey::ViewAllowedNetworksFmt, "Zulässige Netzwerke: {networks}"),
+    (MessageKey::AllowedNetworksSetFmt, "Der Dienst meldet nur, solange eine Verbindung zu folgenden Netzwerken besteht: {networks}"),
+    (MessageKey::AllowedNetworksCleared, "Die Einschränkung auf zulässige Netzwerke wurde aufgehoben."),
+    (MessageKey::ConnectedNetworksFmt, "Derzeit verbundene Netzwerke: {networks}"),
+    (MessageKey::ViewQuietHoursFmt, "Ruhezeiten: {hours}"),
+    (MessageKey::QuietHoursInvalidFmt, "Ruhezeiten müssen im Format HH:MM-HH:MM (z. B. 23:00-06:00) mit unterschiedlicher Start- und Endzeit angegeben werden: {hours}"),
+    (MessageKey::QuietHoursSetFmt, "Der Dienst hält automatische Meldungen täglich während {hours} zurück."),
+    (MessageKey::QuietHoursCleared, "Die Ruhezeiten wurden entfernt."),
+    (MessageKey::ViewJitterFmt, "Zeitliche Streuung der Meldungen: ±{seconds} Sekunden"),
+    (MessageKey::JitterSetFmt, "Der Dienst verschiebt die Meldezeiten zufällig um bis zu ±{seconds} Sekunden (höchstens das halbe Intervall)."),
+    (MessageKey::JitterDisabled, "Die zeitliche Streuung der Meldungen wurde deaktiviert."),
+    (MessageKey::ViewUserAgentFmt, "User-Agent: {ua}"),
+    (MessageKey::UserAgentIdSetFmt, "Die Kennung im User-Agent wurde festgelegt: {ua}"),
+    (MessageKey::UserAgentIdCleared, "Die Kennung im User-Agent wurde entfernt."),
+    (MessageKey::UserAgentIdInvalidFmt, "Die Kennung im User-Agent darf höchstens {max} druckbare ASCII-Zeichen enthalten."),
+    (MessageKey::ViewHistoryDatabase, "Verlaufsdatenbank: aktiviert"),
+    (MessageKey::HistoryDbEnabled, "Der Benachrichtigungsverlauf wird zusätzlich in der Datenbank gespeichert."),
+    (MessageKey::HistoryDbDisabled, "Der Benachrichtigungsverlauf wird nicht mehr in der Datenbank gespeichert. Vorhandene Einträge bleiben erhalten."),
+    (MessageKey::HistoryDbUnsupported, "Dieser Build unterstützt die Verlaufsdatenbank nicht. Bitte mit `--features sqlite` bauen."),
+    (MessageKey::StatusHistorySummaryFmt, "  Letzte 24 Stunden: {attempts} Versuche, {successes} erfolgreich, durchschnittlich {ms} ms"),
+    (MessageKey::ToastTitle, "MyDNS Adapter: Benachrichtigung schlägt fehl"),
+    (MessageKey::ToastFailuresFmt, "Die Benachrichtigung für Konto {id} ist {count} Mal hintereinander fehlgeschlagen. {error}"),
+    (MessageKey::ToastExpiryTitle, "MyDNS Adapter: Eintrag läuft bald ab"),
+    (MessageKey::ToastExpiryFmt, "Konto {id} wurde seit {hours} Stunden nicht erfolgreich aktualisiert. Der MyDNS.JP-Eintrag läuft in etwa {remaining} Stunden ab."),
+    (MessageKey::ToastMonitorStarted, "--- Überwache Benachrichtigungsfehler (Strg+C zum Beenden) ---"),
+    (MessageKey::ProxySetFmt, "Der Proxy für Benachrichtigungen wurde auf \"{url}\" gesetzt."),
+    (MessageKey::ProxyCleared, "Die Proxy-Einstellung wurde entfernt. Es werden die WinHTTP-/Systemeinstellungen verwendet."),
+    (MessageKey::ProxyPrompt, "Proxy-URL (leer: globale Einstellung, direct: kein Proxy, -: löschen)"),
+    (MessageKey::ExportSuccessFmt, "[Erfolg] {count} Konto/Konten nach {path} exportiert."),
+    (MessageKey::ExportPasswordWarning, "[Warnung] Die Datei enthält Passwörter im Klartext. Gehen Sie sorgfältig damit um. (Mit --redact-passwords ausschließen.)"),
+    (MessageKey::ImportTitle, "--- Konfiguration importieren ---"),
+    (MessageKey::ImportParseErrorFmt, "Die Datei konnte nicht gelesen werden: {error}"),
+    (MessageKey::ImportUnsupportedVersionFmt, "Nicht unterstützte Version des Dateiformats: {version}"),
+    (MessageKey::ImportDuplicateFmt, "Die MasterID {id} kommt mehrfach vor."),
+    (MessageKey::ImportUnknownFieldFmt, "Unbekanntes Feld: {field}"),
+    (MessageKey::ImportMissingFieldFmt, "Pflichtfeld fehlt: {field}"),
+    (MessageKey::ImportInvalidTypeFmt, "Ungültiger Werttyp für das Feld: {field}"),
+    (MessageKey::ImportMissingPasswordFmt, "Für das neue Konto {id} ist ein Passwort erforderlich."),
+    (MessageKey::ImportDiffNewFmt, "+ {id} (neu)"),
+    (MessageKey::ImportDiffChangedFmt, "~ {id} (geändert)"),
+    (MessageKey::ImportDiffUnchangedFmt, "= {id} (unverändert)"),
+    (MessageKey::ImportNothingToDo, "Es gibt keine Änderungen zum Übernehmen."),
+    (MessageKey::ImportConfirmFmt, "{count} Kontoänderung(en) übernehmen?"),
+    (MessageKey::ImportSuccessFmt, "[Erfolg] {count} Kontoänderung(en) übernommen."),
+    (MessageKey::UserScopeServiceConflict, "--user kann nicht mit Dienstbefehlen verwendet werden, da der Dienst keine benutzerbezogenen Einstellungen liest."),
+    (MessageKey::StatusTitle, "--- MyDNS Adapter Status ---"),
+    (MessageKey::StatusUptimeFmt, "Laufzeit: {days} T {hours} Std. {minutes} Min. (gestartet {since})"),
+    (MessageKey::ViewLastErrorFmt, "  Letzter Fehler: {time}  [{kind}] {detail}"),
+    (MessageKey::ViewSuspendedFmt, "  Ausgesetzt: seit {time} (wiederholte Authentifizierungsfehler). Fortsetzen mit `account enable {id}`."),
+    (MessageKey::StatusLastIpFmt, "  Zuletzt gemeldete Adresse: IPv4 {v4},  IPv6 {v6}"),
+    (MessageKey::StatusAccountFmt, "{id}: Letzte Benachrichtigung: {time},  Ergebnis: {result},  Letzter Erfolg: {success}"),
+    (MessageKey::UserScopeFallback, "[Info] Keine Administratorrechte; es werden benutzerbezogene Einstellungen (HKCU) verwendet. Der Dienst liest diese Einstellungen nicht."),
+    (MessageKey::ViewUserScope, "(Benutzerbezogene Einstellungen aus HKCU)"),
+    (MessageKey::ViewConfigFileFmt, "(Konten aus der Konfigurationsdatei {path} werden angezeigt)"),
+    (MessageKey::ConfigFileInvalidFmt, "Die Konfigurationsdatei {path} konnte nicht gelesen werden: {error}"),
+    (MessageKey::PasswordFromEnvFmt, "Das Passwort aus der Umgebungsvariablen {var} wird verwendet."),
+    (MessageKey::KickSuccess, "[Erfolg] Sofortige Benachrichtigung beim Dienst angefordert."),
+    (MessageKey::UpgradeTitle, "--- Konfiguration aktualisieren ---"),
+    (MessageKey::UpgradeAccountFmt, "Konto '{id}':"),
+    (MessageKey::UpgradeNoChanges, "Keine Änderungen."),
+    (MessageKey::UpgradeAddedDefaultFmt, "{name}: fehlte, Standardwert {value} geschrieben."),
+    (MessageKey::UpgradeMigratedTypeFmt, "{name}: von Zeichenfolge in die Zahl {value} umgewandelt."),
+    (MessageKey::UpgradeEmptyPassword, "[Warnung] Es ist kein Passwort festgelegt. Legen Sie es mit 'account edit' fest."),
+    (MessageKey::InvalidMasterIdPrefix, "Die MasterID muss mit 'mydns' beginnen."),
+
+    // winservice.rs
+    (MessageKey::AdminRequiredInstall, "Zum Installieren des Dienstes sind Administratorrechte erforderlich. Bitte als Administrator ausführen."),
+    (MessageKey::ServiceInstallingFmt, "Dienst '{name}' wird installiert..."),
+    (MessageKey::ServiceInstalledFmt, "Der Dienst '{name}' wurde installiert und gestartet."),
+    (MessageKey::AdminRequiredUninstall, "Zum Deinstallieren des Dienstes sind Administratorrechte erforderlich. Bitte als Administrator ausführen."),
+    (MessageKey::ServiceNotInstalledFmt, "Der Dienst '{name}' ist nicht installiert."),
+    (MessageKey::ServiceStoppingFmt, "Dienst '{name}' wird beendet..."),
+    (MessageKey::ServiceStopped, "Der Dienst wurde beendet."),
+    (MessageKey::ServiceWaitingStop, "Warten auf das Beenden des Dienstes..."),
+    (MessageKey::ServiceNotRunning, "Der Dienst wird nicht ausgeführt."),
+    (MessageKey::ServiceUninstalledFmt, "Der Dienst '{name}' wurde deinstalliert."),
+    (MessageKey::AdminRequiredRestart, "Zum Neustarten des Dienstes sind Administratorrechte erforderlich. Bitte als Administrator ausführen."),
+    (MessageKey::ServiceStatusFmt, "Dienst '{name}': {state}"),
+    (MessageKey::ServiceStateRunning, "Wird ausgeführt"),
+    (MessageKey::ServiceStateStopped, "Beendet"),
+    (MessageKey::ServiceStateStartPending, "Wird gestartet"),
+    (MessageKey::ServiceStateStopPending, "Wird beendet"),
+    (MessageKey::ServiceStatePaused, "Angehalten"),
+    (MessageKey::ServiceStatePausePending, "Wird angehalten"),
+    (MessageKey::ServiceStateContinuePending, "Wird fortgesetzt"),
+    (MessageKey::ServiceStateUnknown, "Unbekannt"),
+    (MessageKey::ServiceDescription, "Meldet MyDNS.JP regelmäßig die IPv4-/IPv6-Adressen dieses Computers, um die dynamischen DNS-Einträge aktuell zu halten."),
+    (MessageKey::ServiceLogonFailedFmt, "Der Dienst wurde installiert, konnte aber nicht gestartet werden, da die Anmeldung als '{account}' fehlgeschlagen ist. Prüfen Sie das Passwort und das Recht \"Anmelden als Dienst\" und starten Sie den Dienst erneut."),
+    (MessageKey::ServiceKeyAccessGrantedFmt, "Dem Konto '{account}' wurde Lese- und Schreibzugriff auf {key} gewährt."),
+    (MessageKey::ServiceKeyAccessFailedFmt, "Dem Konto '{account}' konnte kein Schreibzugriff auf {key} gewährt werden: {error}. Der Dienst kann die Ergebnisse der Benachrichtigungen und automatische Sperren nicht speichern. Gewähren Sie den Zugriff manuell mit regedit."),
+    (MessageKey::ServiceDataDirAccessGrantedFmt, "Dem Konto '{account}' wurde Schreibzugriff auf {path} gewährt."),
+    (MessageKey::ServiceDataDirAccessFailedFmt, "Dem Konto '{account}' konnte kein Schreibzugriff auf {path} gewährt werden: {error}. Der Dienst kann seinen Laufzeitstatus und den Benachrichtigungsverlauf nicht speichern. Gewähren Sie den Zugriff manuell auf der Registerkarte „Sicherheit“ im Explorer."),
+    (MessageKey::DataDirShareFailedFmt, "Benutzern konnte kein Schreibzugriff auf das Laufzeitdatenverzeichnis {path} gewährt werden: {error}. Benutzer ohne Administratorrechte können --pause und --kick nicht verwenden."),
+    (MessageKey::AdminRequiredStart, "Zum Starten des Dienstes sind Administratorrechte erforderlich. Bitte als Administrator ausführen."),
+    (MessageKey::AdminRequiredStop, "Zum Beenden des Dienstes sind Administratorrechte erforderlich. Bitte als Administrator ausführen."),
+    (MessageKey::ServiceStartingFmt, "Dienst '{name}' wird gestartet..."),
+    (MessageKey::ServiceStarted, "Der Dienst wurde gestartet."),
+    (MessageKey::ServiceAlreadyRunning, "Der Dienst wird bereits ausgeführt."),
+    (MessageKey::ServiceStartFailedFmt, "Der Dienst wurde beim Starten beendet (Exitcode: {code}). Details finden Sie im Protokoll."),
+    (MessageKey::ServiceStoppedAfterStartFmt, "Der Dienst wurde direkt nach dem Start beendet (Exitcode: {code}). Details finden Sie im Protokoll."),
+    (MessageKey::ServiceStartTimeout, "Zeitüberschreitung beim Warten auf den Start des Dienstes."),
+    (MessageKey::ForegroundStarted, "Die Dienstschleife läuft in dieser Konsole. Mit Strg+C beenden."),
+    (MessageKey::ForegroundStopped, "Beendet."),
+    (MessageKey::ForegroundServiceRunning, "[Warnung] Der Dienst wird ausgeführt. Konten werden möglicherweise doppelt gemeldet."),
+    (MessageKey::ServicePanicked, "In der Dienstschleife ist ein Panic aufgetreten."),
+    (MessageKey::ServiceRestartedSuccessfully, "Der Dienst wurde neu gestartet."),
+
+    // notify.rs
+    (MessageKey::NotifyKindNetwork, "Netzwerkfehler"),
+    (MessageKey::NotifyKindAuth, "Authentifizierung fehlgeschlagen"),
+    (MessageKey::NotifyKindServer, "Serverfehler"),
+    (MessageKey::NotifyKindRateLimited, "Zu viele Anfragen"),
+    (MessageKey::NotifyKindHttp, "HTTP-Fehler"),
+    (MessageKey::NotifyKindSkipped, "Übersprungen"),
+    (MessageKey::TestSendingFmt, "Anmeldedaten des Kontos '{id}' werden geprüft ({url})..."),
+    (MessageKey::TestOkFmt, "Die Anmeldedaten sind gültig. Von MyDNS.JP akzeptierte Adresse: {addr}"),
+    (MessageKey::TestAuthFailedFmt, "Authentifizierung fehlgeschlagen. MasterID oder Passwort ist falsch. ({detail})"),
+    (MessageKey::TestInconclusiveFmt, "Die Anmeldedaten konnten nicht geprüft werden. {kind}: {detail}"),
+
+    // doctor.rs
+    (MessageKey::DoctorTitle, "--- Diagnose ---"),
+    (MessageKey::DoctorHeaderFmt, "Version: {version},  Ausgeführt: {time},  Konfigurationsbereich: {scope}"),
+    (MessageKey::DoctorItemElevation, "Rechte"),
+    (MessageKey::DoctorItemRegistry, "Kontoeinstellungen"),
+    (MessageKey::DoctorItemSettings, "Globale Einstellungen"),
+    (MessageKey::DoctorItemRegistryWrite, "Schreibzugriff auf Einstellungen"),
+    (MessageKey::DoctorItemLog, "Protokolldatei"),
+    (MessageKey::DoctorItemIpv4, "IPv4-Verbindung (ipv4.mydns.jp)"),
+    (MessageKey::DoctorItemIpv6, "IPv6-Verbindung (ipv6.mydns.jp)"),
+    (MessageKey::DoctorItemService, "Dienst"),
+    (MessageKey::DoctorItemClock, "Uhr"),
+    (MessageKey::DoctorElevated, "Wird als Administrator ausgeführt."),
+    (MessageKey::DoctorNotElevated, "Wird nicht als Administrator ausgeführt. Änderungen an Einstellungen und Dienstvorgänge sind nicht möglich."),
+    (MessageKey::DoctorAccountsFmt, "{count} Konto/Konten"),
+    (MessageKey::DoctorReadable, "Lesbar."),
+    (MessageKey::DoctorWritable, "Beschreibbar."),
+    (MessageKey::DoctorRegistryReadOnly, "Keine Schreibberechtigung."),
+    (MessageKey::DoctorReachableFmt, "HTTP {status} ({ms} ms)"),
+    (MessageKey::DoctorServiceNotInstalled, "Nicht installiert."),
+    (MessageKey::DoctorClockSkewFmt, "Abweichung vom MyDNS.JP-Server: {seconds} s"),
+    (MessageKey::DoctorClockUnknown, "Konnte nicht geprüft werden, da MyDNS.JP nicht erreichbar war."),
+    (MessageKey::DoctorSummaryFmt, "\nFehler: {fail}, Warnungen: {warn}"),
+
+    // history.rs
+    (MessageKey::HistoryTitle, "--- Benachrichtigungsverlauf ---"),
+    (MessageKey::HistoryEmpty, "Kein Verlauf vorhanden."),
+    (MessageKey::HistoryRowFmt, "{time}  {id}  {proto}  {ms} ms  {result}"),
+
+    // ipc.rs
+    (MessageKey::IpcServiceUnavailable, "Keine Verbindung zum Dienst. Stellen Sie sicher, dass der Dienst ausgeführt wird."),
+    (MessageKey::IpcServiceBusy, "Der Dienst bearbeitet gerade andere Anfragen. Bitte versuchen Sie es später erneut."),
+    (MessageKey::IpcServerNotServiceFmt, "Die Anfrage wurde nicht gesendet, da der Named-Pipe-Server (PID {pid}) nicht der Dienstprozess ist."),
+    (MessageKey::IpcInvalidResponse, "Die Antwort des Dienstes konnte nicht ausgewertet werden."),
+    (MessageKey::IpcRequestFailedFmt, "Der Dienst konnte die Anfrage nicht bearbeiten: {error}"),
+    (MessageKey::IpcNotifyNowSuccess, "[Erfolg] Sofortige Benachrichtigung beim Dienst angefordert."),
+    (MessageKey::IpcReloadSuccessFmt, "[Erfolg] Der Dienst hat die Kontoeinstellungen neu geladen ({count} Konten)."),
+    (MessageKey::IpcStatusTitle, "--- Dienststatus ---"),
+    (MessageKey::IpcStatusVersion, "Version"),
+    (MessageKey::IpcStatusStarted, "Gestartet"),
+    (MessageKey::IpcStatusAccounts, "Konten"),
+    (MessageKey::IpcStatusPaused, "Angehalten"),
+    (MessageKey::IpcStatusRoundRunning, "Benachrichtigung läuft"),
+    (MessageKey::IpcStatusNextRound, "Nächste regelmäßige Benachrichtigung"),
+
+    // schtask.rs
+    (MessageKey::AdminRequiredTask, "Zum Registrieren oder Entfernen der geplanten Aufgabe sind Administratorrechte erforderlich. Bitte als Administrator ausführen."),
+    (MessageKey::TaskServiceInstalledWarning, "[Warnung] Der Dienst ist ebenfalls installiert. Verwenden Sie nur eines von beiden, um doppelte Benachrichtigungen zu vermeiden."),
+    (MessageKey::TaskInstalledFmt, "Die geplante Aufgabe '{name}' wurde registriert. Sie meldet beim Start, bei einer Netzwerkverbindung und alle {minutes} Minuten."),
+    (MessageKey::TaskUninstalledFmt, "Die geplante Aufgabe '{name}' wurde entfernt."),
+    (MessageKey::TaskNotInstalledFmt, "Die geplante Aufgabe '{name}' ist nicht registriert."),
+    (MessageKey::TaskDescription, "Meldet MyDNS.JP die IP-Adresse dieses Computers (MyDNS.JP Adapter)."),
+
+    // crash.rs
+    (MessageKey::PanicOccurredFmt, "Ein unerwarteter Fehler ist aufgetreten. Details wurden in das Protokoll und das Ereignisprotokoll geschrieben: {summary}"),
+
+    // stats.rs
+    (MessageKey::StatsTitle, "--- Nutzungsstatistik ---"),
+    (MessageKey::StatsEnabled, "[Erfolg] Nutzungsstatistik aktiviert. Die Statistik verlässt diesen Computer nie."),
+    (MessageKey::StatsDisabled, "[Erfolg] Nutzungsstatistik deaktiviert."),
+    (MessageKey::StatsNotEnabled, "Die Nutzungsstatistik ist deaktiviert. Führen Sie --stats --opt-in aus, um sie zu aktivieren."),
+    (MessageKey::StatsEmpty, "Es wurde keine Statistik aufgezeichnet."),
+    (MessageKey::StatsMonthFmt, "{month}: {total} Benachrichtigungen ({ok} erfolgreich / {ng} fehlgeschlagen),  {ip} IP-Wechsel,  {hours} Std. Laufzeit"),
+    (MessageKey::StatsYearFmt, "\n{year}: {total} Benachrichtigungen, Ihre IP hat sich {ip}-mal geändert."),
+];
@@ -0,0 +1,367 @@
+//! スペイン語のメッセージ。
+//!
+//! ログと開発者向けの機能（`--simulate`、`--bench`）のメッセージは翻訳せず、英語のメッセージを使います。
+
+use super::MessageKey;
+
+/// キーとスペイン語のメッセージの組。
+#[rustfmt::skip]
+pub(super) static MESSAGES: &[(MessageKey, &str)] = &[
+    // main.rs
+    (MessageKey::LangInvalid, "Idioma no compatible. Use ja, en, zh, ko, de, fr o es."),
+    (MessageKey::ConfigTitle, "--- Configuración de MyDNS Adapter ---"),
+    (MessageKey::ConfigLoaded, "\nSe cargó la configuración actual. Pulse Intro para conservar los valores actuales."),
+    (MessageKey::MasterIdPrompt, "MasterID"),
+    (MessageKey::PasswordPrompt, "Contraseña"),
+    (MessageKey::Ipv4NotifyPrompt, "¿Activar la notificación IPv4?"),
+    (MessageKey::Ipv6NotifyPrompt, "¿Activar la notificación IPv6?"),
+    (MessageKey::KeepAlivePrompt, "Intervalo de notificación forzada en horas (0 para desactivar)"),
+    (MessageKey::NumberInvalid, "Introduzca un número entero no negativo."),
+    (MessageKey::RegistrySaveSuccess, "\n[Correcto] La configuración se guardó en el registro."),
+    (MessageKey::RegistrySaveFailFmt, "\n[Error] Error al guardar en el registro: {error}"),
+    (MessageKey::InputPromptPwFmt, "Introduzca {item} (actual: {current}, Intro para conservar): "),
+    (MessageKey::InputPromptFmt, "Introduzca {item} (actual: {current}): "),
+    (MessageKey::InputPromptNewFmt, "Introduzca {item}: "),
+    (MessageKey::NotSet, "(Sin definir)"),
+    (MessageKey::YesNoPromptFmt, "{question} (actual: {current}) {hint}: "),
+    (MessageKey::YesNoHintTrue, "(S/n)"),
+    (MessageKey::YesNoHintFalse, "(s/N)"),
+    (MessageKey::YesNoInvalid, "Introduzca 's' o 'n', o pulse Intro."),
+    (MessageKey::YesNoAffirmativeWords, "y,yes,s,si,sí"),
+    (MessageKey::YesNoNegativeWords, "n,no"),
+    (MessageKey::ViewTitle, "--- Configuración actual de MyDNS ---"),
+    (MessageKey::ViewMasterIdFmt, "MasterID: {id}"),
+    (MessageKey::ViewPasswordFmt, "Contraseña: {password}"),
+    (MessageKey::ViewIpv4Fmt, "Notificación IPv4: {value}"),
+    (MessageKey::ViewIpv6Fmt, "Notificación IPv6: {value}"),
+    (MessageKey::Yes, "Sí"),
+    (MessageKey::No, "No"),
+    (MessageKey::ViewNoAccounts, "No hay cuentas configuradas."),
+    (MessageKey::ViewListFmt, "MasterID: {id},  Contraseña: {pw},  IPv4: {v4},  IPv6: {v6}"),
+    (MessageKey::AddTitle, "--- Añadir cuenta ---"),
+    (MessageKey::EditTitle, "--- Editar cuenta ---"),
+    (MessageKey::RemoveTitle, "--- Eliminar cuenta ---"),
+    (MessageKey::AccountExistsFmt, "La cuenta '{id}' ya existe."),
+    (MessageKey::AccountNotFoundFmt, "No se encontró la cuenta '{id}'."),
+    (MessageKey::SelectAccountPrompt, "Seleccione la cuenta que desea editar:"),
+    (MessageKey::SelectAccountIndexPrompt, "Introduzca un número o un MasterID: "),
+    (MessageKey::InvalidSelection, "Selección no válida."),
+    (MessageKey::RemoveNeedsYes, "No se puede confirmar la eliminación porque la entrada estándar no es un terminal. Use --yes para eliminar sin confirmación."),
+    (MessageKey::ConfirmRemoveFmt, "¿Seguro que desea eliminar la cuenta '{id}'?"),
+    (MessageKey::ConfirmPromptFmt, "{question} {hint}: "),
+    (MessageKey::RemoveSuccess, "[Correcto] Se eliminó la cuenta."),
+    (MessageKey::RemoveFailFmt, "[Error] No se pudo eliminar la cuenta: {error}"),
+    (MessageKey::BatchRowErrorFmt, "Línea {line}: {error}"),
+    (MessageKey::BatchInvalidFmt, "No se añadió ninguna cuenta porque {count} línea(s) tienen errores."),
+    (MessageKey::BatchEmpty, "No hay cuentas que añadir."),
+    (MessageKey::BatchSuccessFmt, "[Correcto] Se añadieron {count} cuenta(s)."),
+    (MessageKey::BatchColumnCountFmt, "Número de columnas incorrecto ({count}). Use el formato \"MasterID,contraseña,IPv4,IPv6\"."),
+    (MessageKey::BatchInvalidFlagFmt, "Valor de {name} no válido: {value} (use yes o no)"),
+    (MessageKey::BatchUnterminatedQuote, "Una columna entre comillas no está cerrada."),
+    (MessageKey::RenameSuccessFmt, "[Correcto] Se cambió el nombre de la cuenta '{old}' a '{new}'."),
+    (MessageKey::AccountEnabledFmt, "[Éxito] Se han reanudado las notificaciones de la cuenta '{id}'."),
+    (MessageKey::AccountNotSuspendedFmt, "Las notificaciones de la cuenta '{id}' no están suspendidas."),
+    (MessageKey::DryRunTitle, "[Simulación] Se enviarían las siguientes solicitudes (no se envía nada):"),
+    (MessageKey::DryRunRequestFmt, "  {id} {proto}: {method} {url}  ({auth})"),
+    (MessageKey::DryRunBasicAuthFmt, "Autenticación básica: {user} / ********"),
+    (MessageKey::DryRunLoginAuthFmt, "Inicio de sesión: {user} / ******** (texto sin cifrar)"),
+    (MessageKey::DryRunNoAuth, "sin credenciales"),
+    (MessageKey::DryRunBindFmt, "    Dirección de origen: {addr}"),
+    (MessageKey::DryRunSkippedFmt, "  {id} {proto}: no se envía. {reason}"),
+    (MessageKey::DryRunSuspended, "Las notificaciones están suspendidas por errores de autenticación repetidos (--force las envía de todos modos)."),
+    (MessageKey::DryRunPrivateIpv4, "La dirección IPv4 actual no es accesible desde Internet, por lo que se omite la actualización."),
+    (MessageKey::DryRunNone, "No se enviaría ninguna solicitud."),
+    (MessageKey::NotifySummaryTitle, "Resultados de la notificación:"),
+    (MessageKey::NotifySummaryRowFmt, "  {id}  {proto}  {result}  {ms} ms ({attempts} intentos){detail}"),
+    (MessageKey::NotifySummarySkippedFmt, "  {id}  -     {reason}"),
+    (MessageKey::NotifySummarySuspended, "Suspendida tras errores de autenticación repetidos (no enviada)"),
+    (MessageKey::NotifySummaryInvalidProxy, "URL de proxy no válida (no enviada)"),
+    (MessageKey::NotifySummaryAborted, "La tarea de notificación terminó de forma anómala"),
+    (MessageKey::NotifySummaryNoProtocol, "No hay ningún protocolo que notificar"),
+    (MessageKey::NotifySummaryTotalFmt, "{ok} correctas, {failed} fallidas"),
+    (MessageKey::ElevationConfirm, "Esta operación requiere privilegios de administrador. ¿Ejecutarla de nuevo como administrador?"),
+    (MessageKey::ElevationRequired, "Esta operación requiere privilegios de administrador. Ejecútela desde un símbolo del sistema con privilegios elevados."),
+    (MessageKey::ElevationCancelled, "Se canceló la ejecución como administrador."),
+    (MessageKey::ElevatedPressEnter, "\nPulse Intro para cerrar esta ventana..."),
+    (MessageKey::ServicePasswordPromptFmt, "la contraseña de la cuenta '{account}'"),
+    (MessageKey::ServicePasswordNotNeededFmt, "No se puede especificar una contraseña para la cuenta '{account}'."),
+    (MessageKey::ConfirmPurge, "Se eliminarán todas las cuentas, la configuración y los archivos de registro guardados. Esta acción no se puede deshacer. ¿Continuar?"),
+    (MessageKey::PurgeNeedsYes, "No se puede confirmar la purga porque la entrada estándar no es un terminal. Use --yes para purgar sin confirmación."),
+    (MessageKey::PurgeSuccess, "[Correcto] Se eliminaron las cuentas, la configuración y los archivos de registro guardados."),
+    (MessageKey::AddSuccess, "[Correcto] Se añadió la cuenta."),
+    (MessageKey::NoAccountsAddPrompt, "No se encontraron cuentas. ¿Crear una nueva?"),
+    (MessageKey::OperationCancelled, "Operación cancelada."),
+    (MessageKey::EditTargetFmt, "Cuenta de destino: {id}"),
+    (MessageKey::ViewRuntimeFmt, "    Estado: {state},  Último intento: {time},  Resultado: {result}"),
+    (MessageKey::ViewRuntimeInFlight, "Notificando"),
+    (MessageKey::ViewRuntimeIdle, "Inactivo"),
+    (MessageKey::ViewPaused, "\n[Nota] La notificación periódica está en pausa. Use --resume para reanudarla."),
+    (MessageKey::PauseSuccess, "[Correcto] Se pausó la notificación periódica."),
+    (MessageKey::ResumeSuccess, "[Correcto] Se reanudó la notificación periódica."),
+    (MessageKey::ViewIntervalFmt, "Intervalo de notificación: {minutes} minutos"),
+    (MessageKey::IntervalSetFmt, "El intervalo de notificación se estableció en {minutes} minutos. Un servicio en ejecución lo aplicará después de su próxima notificación programada."),
+    (MessageKey::IntervalOutOfRangeFmt, "El intervalo de notificación debe estar entre {min} y {max} minutos."),
+    (MessageKey::ViewRetryFmt, "Número máximo de intentos de notificación: {count}"),
+    (MessageKey::RetrySetFmt, "El número máximo de intentos de notificación se estableció en {count}."),
+    (MessageKey::RetryOutOfRangeFmt, "El número máximo de intentos de notificación debe estar entre {min} y {max}."),
+    (MessageKey::ViewConcurrencyFmt, "Cuentas notificadas simultáneamente: {count}"),
+    (MessageKey::ConcurrencySetFmt, "El número de cuentas notificadas simultáneamente se ha establecido en {count}."),
+    (MessageKey::ConcurrencyOutOfRangeFmt, "El número de cuentas notificadas simultáneamente debe estar entre {min} y {max}."),
+    (MessageKey::ViewRequestTimeoutFmt, "Tiempo de espera de las solicitudes de notificación: {seconds} segundos"),
+    (MessageKey::RequestTimeoutSetFmt, "El tiempo de espera de las solicitudes de notificación se ha establecido en {seconds} segundos."),
+    (MessageKey::RequestTimeoutOutOfRangeFmt, "El tiempo de espera de las solicitudes de notificación debe estar entre {min} y {max} segundos."),
+    (MessageKey::Ipv4UrlPrompt, "URL de notificación IPv4"),
+    (MessageKey::Ipv6UrlPrompt, "URL de notificación IPv6"),
+    (MessageKey::EndpointUrlInvalidFmt, "\"{url}\" no es una URL válida. Se conserva el valor actual."),
+    (MessageKey::ViewEndpointFmt, "  URL de notificación ({proto}): {url}"),
+    (MessageKey::HostnamePrompt, "Nombre de host que se comprobará en el DNS (vacío: omitir, -: borrar)"),
+    (MessageKey::SetHostnameInvalidFmt, "\"{host}\" no es un nombre de host válido."),
+    (MessageKey::SetEndpointUrlInvalidFmt, "\"{url}\" no es una URL válida."),
+    (MessageKey::HostnameInvalidFmt, "\"{host}\" no es un nombre de host válido. Se conserva el valor actual."),
+    (MessageKey::ViewLastSuccessFmt, "  Último éxito: {time}  (IPv4: {v4},  IPv6: {v6})"),
+    (MessageKey::ViewHostnameFmt, "  Nombre de host que se comprobará: {host}"),
+    (MessageKey::ProviderPrompt, "Servicio de actualización (mydns: MyDNS.JP, generic: cualquier servicio con URL de actualización)"),
+    (MessageKey::ProviderInvalid, "El servicio debe ser mydns o generic."),
+    (MessageKey::UsernamePrompt, "Nombre de usuario (también se usa como nombre de la cuenta)"),
+    (MessageKey::GenericIpv4UrlPrompt, "URL de actualización IPv4 (se reemplazan {ip} y {host})"),
+    (MessageKey::GenericIpv6UrlPrompt, "URL de actualización IPv6 (vacío: igual que IPv4, -: borrar)"),
+    (MessageKey::GenericUrlRequired, "Un servicio genérico necesita una URL de actualización IPv4."),
+    (MessageKey::ViewProviderGeneric, "  Servicio: genérico (URL de actualización)"),
+    (MessageKey::MethodPrompt, "Método de actualización (http: HTTPS, pop3/imap/ftp: iniciar sesión en el servidor)"),
+    (MessageKey::MethodInvalid, "El método de actualización debe ser http, pop3, imap o ftp."),
+    (MessageKey::MethodRequiresMyDns, "La actualización mediante inicio de sesión POP3/IMAP/FTP solo está disponible para cuentas de MyDNS.JP."),
+    (MessageKey::MethodPlaintextWarning, "Nota: los inicios de sesión POP3/IMAP/FTP envían la contraseña sin cifrar."),
+    (MessageKey::ViewMethodFmt, "  Método de actualización: {method} (inicio de sesión)"),
+    (MessageKey::ViewLogPathFmt, "Archivo de registro: {path}"),
+    (MessageKey::ViewLogArchiveFmt, "Archivos de registro antiguos: se conservan {count} (comprimidos: {compress})"),
+    (MessageKey::ViewProxyFmt, "Proxy: {proxy}"),
+    (MessageKey::ProxyAuto, "Automático (configuración de WinHTTP/del sistema)"),
+    (MessageKey::ViewMetricsFmt, "Métricas: http://127.0.0.1:{port}/metrics"),
+    (MessageKey::MetricsSetFmt, "Las métricas se publicarán en http://127.0.0.1:{port}/metrics. Un servicio en ejecución lo aplicará tras reiniciarse."),
+    (MessageKey::MetricsDisabled, "Se desactivó el punto de acceso de métricas. Un servicio en ejecución lo aplicará tras reiniciarse."),
+    (MessageKey::ViewAdminApiFmt, "API de administración: http://127.0.0.1:{port}/api/"),
+    (MessageKey::AdminApiSetFmt, "La API de administración se publicará en http://127.0.0.1:{port}/api/. Un servicio en ejecución lo aplicará tras reiniciarse.\nEnvíe las solicitudes con el encabezado \"Authorization: Bearer {token}\"."),
+    (MessageKey::AdminApiDisabled, "Se desactivó la API de administración. Un servicio en ejecución lo aplicará tras reiniciarse."),
+    (MessageKey::PingUrlPrompt, "URL de ping de supervisión que se solicita tras una actualización correcta (vacío: ninguna, -: borrar)"),
+    (MessageKey::ViewAccountPingUrlFmt, "  URL de ping: {url}"),
+    (MessageKey::InterfacePrompt, "Nombre de la interfaz de red o dirección local desde la que enviar (vacío: cualquiera, -: quitar)"),
+    (MessageKey::ViewInterfaceFmt, "  Enviar desde: {interface}"),
+    (MessageKey::SchedulePrompt, "Programación de notificaciones en lugar del intervalo (p. ej. */10 * * * *, at 04:05 daily; vacío: intervalo, -: borrar)"),
+    (MessageKey::ScheduleInvalidFmt, "No se pudo interpretar la programación (use cinco campos de cron o at HH:MM daily): {schedule}"),
+    (MessageKey::ViewScheduleFmt, "  Programación: {schedule}"),
+    (MessageKey::ViewPingUrlFmt, "URL de ping de supervisión: {url}"),
+    (MessageKey::PingUrlSetFmt, "Se enviará un ping a \"{url}\" después de cada ronda de notificación en la que todas las cuentas tengan éxito."),
+    (MessageKey::PingUrlCleared, "Se ha borrado la URL de ping de supervisión."),
+    (MessageKey::ViewWebhookUrlFmt, "Webhook: {url}"),
+    (MessageKey::WebhookUrlSetFmt, "Los fallos, las recuperaciones y los cambios de dirección se enviarán a \"{url}\"."),
+    (MessageKey::WebhookUrlCleared, "Se ha borrado la URL del webhook."),
+    (MessageKey::ViewToastFmt, "Notificación del sistema: tras {count} fallos consecutivos"),
+    (MessageKey::ToastSetFmt, "Se mostrará una notificación del sistema tras {count} fallos consecutivos."),
+    (MessageKey::ToastDisabled, "Se han desactivado las notificaciones del sistema."),
+    (MessageKey::ViewMailFmt, "Alertas por correo: {to} (mediante {server}:{port}, tras {minutes} minutos de fallos)"),
+    (MessageKey::MailSettingsSaved, "Se ha guardado la configuración de correo. Use --mail-test para enviar un mensaje de prueba."),
+    (MessageKey::MailDisabled, "Se han desactivado las alertas por correo."),
+    (MessageKey::MailNotConfigured, "Configure --smtp-server y --mail-to para usar las alertas por correo."),
+    (MessageKey::SmtpServerInvalidFmt, "\"{server}\" no es un servidor SMTP válido. Use HOST o HOST:PUERTO."),
+    (MessageKey::MailAddressInvalidFmt, "\"{address}\" no es una dirección de correo válida."),
+    (MessageKey::MailTestSentFmt, "[Éxito] Se ha enviado un mensaje de prueba a {to}."),
+    (MessageKey::MailTestFailedFmt, "No se pudo enviar el mensaje de prueba: {error}"),
+    (MessageKey::ViewCheckIpv4UrlsFmt, "Servicios de comprobación de IPv4: {urls}"),
+    (MessageKey::ViewCheckIpv6UrlsFmt, "Servicios de comprobación de IPv6: {urls}"),
+    (MessageKey::ViewCheckIpPollFmt, "Comprobación de la dirección global: cada {minutes} minutos"),
+    (MessageKey::CheckIpSettingsSaved, "Se ha guardado la configuración de los servicios de comprobación de IP."),
+    (MessageKey::ViewSkipPrivateIpv4, "Direcciones IPv4 CGNAT/privadas: no se notifican"),
+    (MessageKey::SkipPrivateIpv4Enabled, "Se omitirán las actualizaciones cuando la dirección IPv4 obtenida sea una dirección CGNAT o privada."),
+    (MessageKey::SkipPrivateIpv4Disabled, "Las direcciones IPv4 CGNAT y privadas se notificarán, con una advertencia en el registro."),
+    (MessageKey::ViewDnsCheckFmt, "Autocomprobación de DNS: cada {minutes} minutos"),
+    (MessageKey::DnsCheckSetFmt, "Los registros DNS de los nombres de host se comprobarán con resolvedores públicos cada {minutes} minutos."),
+    (MessageKey::DnsCheckDisabled, "Se ha desactivado la autocomprobación de DNS."),
+    (MessageKey::ViewMeteredFmt, "Conexiones de uso medido: {policy}"),
+    (MessageKey::MeteredPolicyInvalid, "La política para conexiones de uso medido debe ser ignore, reduce o skip."),
+    (MessageKey::MeteredIgnore, "Las notificaciones se enviarán con normalidad en conexiones de uso medido."),
+    (MessageKey::MeteredReduce, "En conexiones de uso medido, las notificaciones programadas se ejecutarán como máximo una vez por hora."),
+    (MessageKey::MeteredSkip, "En conexiones de uso medido, el servicio omitirá las notificaciones automáticas."),
+    (MessageKey::ViewAllowedNetworksFmt, "Redes permitidas: {networks}"),
+    (MessageKey::AllowedNetworksSetFmt, "El servicio solo notificará mientras esté conectado a: {networks}"),
+    (MessageKey::AllowedNetworksCleared, "Se ha eliminado la restricción de redes permitidas."),
+    (MessageKey::ConnectedNetworksFmt, "Redes conectadas actualmente: {networks}"),
+    (MessageKey::ViewQuietHoursFmt, "Horas de silencio: {hours}"),
+    (MessageKey::QuietHoursInvalidFmt, "Las horas de silencio deben tener el formato HH:MM-HH:MM (por ejemplo, 23:00-06:00) con horas de inicio y fin distintas: {hours}"),
+    (MessageKey::QuietHoursSetFmt, "El servicio retendrá las notificaciones automáticas todos los días durante {hours}."),
+    (MessageKey::QuietHoursCleared, "Se han eliminado las horas de silencio."),
+    (MessageKey::ViewJitterFmt, "Variación aleatoria de las notificaciones: ±{seconds} segundos"),
+    (MessageKey::JitterSetFmt, "El servicio desplazará aleatoriamente la hora de las notificaciones hasta ±{seconds} segundos (como máximo la mitad del intervalo)."),
+    (MessageKey::JitterDisabled, "Se ha desactivado la variación aleatoria de las notificaciones."),
+    (MessageKey::ViewUserAgentFmt, "User-Agent: {ua}"),
+    (MessageKey::UserAgentIdSetFmt, "Se ha establecido el identificador del User-Agent: {ua}"),
+    (MessageKey::UserAgentIdCleared, "Se ha eliminado el identificador del User-Agent."),
+    (MessageKey::UserAgentIdInvalidFmt, "El identificador del User-Agent debe tener como máximo {max} caracteres ASCII imprimibles."),
+    (MessageKey::ViewHistoryDatabase, "Base de datos del historial: activada"),
+    (MessageKey::HistoryDbEnabled, "El historial de notificaciones también se registrará en la base de datos."),
+    (MessageKey::HistoryDbDisabled, "El historial de notificaciones ya no se registrará en la base de datos. Los registros existentes se conservan."),
+    (MessageKey::HistoryDbUnsupported, "Esta compilación no admite la base de datos del historial. Compile con `--features sqlite`."),
+    (MessageKey::StatusHistorySummaryFmt, "  Últimas 24 horas: {attempts} intentos, {successes} correctos, {ms} ms de media"),
+    (MessageKey::ToastTitle, "MyDNS Adapter: la notificación falla"),
+    (MessageKey::ToastFailuresFmt, "La notificación de la cuenta {id} ha fallado {count} veces seguidas. {error}"),
+    (MessageKey::ToastExpiryTitle, "MyDNS Adapter: el registro está a punto de caducar"),
+    (MessageKey::ToastExpiryFmt, "La cuenta {id} no se ha actualizado correctamente en {hours} horas. El registro de MyDNS.JP caducará en unas {remaining} horas."),
+    (MessageKey::ToastMonitorStarted, "--- Vigilando los fallos de notificación (Ctrl+C para salir) ---"),
+    (MessageKey::ProxySetFmt, "El proxy de notificación se estableció en \"{url}\"."),
+    (MessageKey::ProxyCleared, "Se borró la configuración del proxy. Se usará la configuración de WinHTTP/del sistema."),
+    (MessageKey::ProxyPrompt, "URL del proxy (vacío: configuración global, direct: sin proxy, -: borrar)"),
+    (MessageKey::ExportSuccessFmt, "[Correcto] Se exportaron {count} cuenta(s) a {path}."),
+    (MessageKey::ExportPasswordWarning, "[Advertencia] El archivo contiene contraseñas en texto sin cifrar. Manéjelo con cuidado. (Use --redact-passwords para excluirlas.)"),
+    (MessageKey::ImportTitle, "--- Importar configuración ---"),
+    (MessageKey::ImportParseErrorFmt, "No se pudo analizar el archivo: {error}"),
+    (MessageKey::ImportUnsupportedVersionFmt, "Versión de formato de archivo no compatible: {version}"),
+    (MessageKey::ImportDuplicateFmt, "El MasterID {id} aparece más de una vez."),
+    (MessageKey::ImportUnknownFieldFmt, "Campo desconocido: {field}"),
+    (MessageKey::ImportMissingFieldFmt, "Falta un campo obligatorio: {field}"),
+    (MessageKey::ImportInvalidTypeFmt, "Tipo de valor no válido para el campo: {field}"),
+    (MessageKey::ImportMissingPasswordFmt, "Se requiere una contraseña para la nueva cuenta {id}."),
+    (MessageKey::ImportDiffNewFmt, "+ {id} (nueva)"),
+    (MessageKey::ImportDiffChangedFmt, "~ {id} (modificada)"),
+    (MessageKey::ImportDiffUnchangedFmt, "= {id} (sin cambios)"),
+    (MessageKey::ImportNothingToDo, "No hay cambios que aplicar."),
+    (MessageKey::ImportConfirmFmt, "¿Aplicar {count} cambio(s) de cuenta?"),
+    (MessageKey::ImportSuccessFmt, "[Correcto] Se aplicaron {count} cambio(s) de cuenta."),
+    (MessageKey::UserScopeServiceConflict, "--user no se puede usar con los comandos del servicio porque el servicio no lee la configuración por usuario."),
+    (MessageKey::StatusTitle, "--- Estado de MyDNS Adapter ---"),
+    (MessageKey::StatusUptimeFmt, "Tiempo en ejecución: {days} d {hours} h {minutes} min (iniciado el {since})"),
+    (MessageKey::ViewLastErrorFmt, "  Último error: {time}  [{kind}] {detail}"),
+    (MessageKey::ViewSuspendedFmt, "  Suspendida: desde {time} (errores de autenticación repetidos). Reanude con `account enable {id}`."),
+    (MessageKey::StatusLastIpFmt, "  Última dirección notificada: IPv4 {v4},  IPv6 {v6}"),
+    (MessageKey::StatusAccountFmt, "{id}: Última notificación: {time},  Resultado: {result},  Último éxito: {success}"),
+    (MessageKey::UserScopeFallback, "[Información] No se está ejecutando como administrador; se usa la configuración por usuario (HKCU). El servicio no lee esta configuración."),
+    (MessageKey::ViewUserScope, "(Configuración por usuario de HKCU)"),
+    (MessageKey::ViewConfigFileFmt, "(Mostrando las cuentas del archivo de configuración {path})"),
+    (MessageKey::ConfigFileInvalidFmt, "No se pudo leer el archivo de configuración {path}: {error}"),
+    (MessageKey::PasswordFromEnvFmt, "Se usa la contraseña de la variable de entorno {var}."),
+    (MessageKey::KickSuccess, "[Correcto] Se solicitó al servicio una notificación inmediata."),
+    (MessageKey::UpgradeTitle, "--- Actualizar configuración ---"),
+    (MessageKey::UpgradeAccountFmt, "Cuenta '{id}':"),
+    (MessageKey::UpgradeNoChanges, "Sin cambios."),
+    (MessageKey::UpgradeAddedDefaultFmt, "{name}: faltaba, se escribió el valor predeterminado {value}."),
+    (MessageKey::UpgradeMigratedTypeFmt, "{name}: se convirtió de cadena al número {value}."),
+    (MessageKey::UpgradeEmptyPassword, "[Advertencia] No hay contraseña definida. Use 'account edit' para definirla."),
+    (MessageKey::InvalidMasterIdPrefix, "El MasterID debe empezar por 'mydns'."),
+
+    // winservice.rs
+    (MessageKey::AdminRequiredInstall, "Se requieren privilegios de administrador para instalar el servicio. Ejecute como administrador."),
+    (MessageKey::ServiceInstallingFmt, "Instalando el servicio '{name}'..."),
+    (MessageKey::ServiceInstalledFmt, "El servicio '{name}' se instaló e inició correctamente."),
+    (MessageKey::AdminRequiredUninstall, "Se requieren privilegios de administrador para desinstalar el servicio. Ejecute como administrador."),
+    (MessageKey::ServiceNotInstalledFmt, "El servicio '{name}' no está instalado."),
+    (MessageKey::ServiceStoppingFmt, "Deteniendo el servicio '{name}'..."),
+    (MessageKey::ServiceStopped, "Servicio detenido."),
+    (MessageKey::ServiceWaitingStop, "Esperando a que se detenga el servicio..."),
+    (MessageKey::ServiceNotRunning, "El servicio no está en ejecución."),
+    (MessageKey::ServiceUninstalledFmt, "El servicio '{name}' se desinstaló correctamente."),
+    (MessageKey::AdminRequiredRestart, "Se requieren privilegios de administrador para reiniciar el servicio. Ejecute como administrador."),
+    (MessageKey::ServiceStatusFmt, "Servicio '{name}': {state}"),
+    (MessageKey::ServiceStateRunning, "En ejecución"),
+    (MessageKey::ServiceStateStopped, "Detenido"),
+    (MessageKey::ServiceStateStartPending, "Iniciando"),
+    (MessageKey::ServiceStateStopPending, "Deteniendo"),
+    (MessageKey::ServiceStatePaused, "En pausa"),
+    (MessageKey::ServiceStatePausePending, "Pausando"),
+    (MessageKey::ServiceStateContinuePending, "Reanudando"),
+    (MessageKey::ServiceStateUnknown, "Desconocido"),
+    (MessageKey::ServiceDescription, "Notifica periódicamente a MyDNS.JP las direcciones IPv4/IPv6 de este equipo para mantener actualizados sus registros de DNS dinámico."),
+    (MessageKey::ServiceLogonFailedFmt, "El servicio se instaló, pero no pudo iniciarse porque falló el inicio de sesión como '{account}'. Compruebe la contraseña y el derecho \"Iniciar sesión como servicio\" y vuelva a iniciar el servicio."),
+    (MessageKey::ServiceKeyAccessGrantedFmt, "Se concedió a la cuenta '{account}' acceso de lectura y escritura a {key}."),
+    (MessageKey::ServiceKeyAccessFailedFmt, "No se pudo conceder a la cuenta '{account}' acceso de escritura a {key}: {error}. El servicio no podrá registrar los resultados de las notificaciones ni las suspensiones automáticas. Conceda el acceso manualmente con regedit."),
+    (MessageKey::ServiceDataDirAccessGrantedFmt, "Se concedió a la cuenta '{account}' acceso de escritura a {path}."),
+    (MessageKey::ServiceDataDirAccessFailedFmt, "No se pudo conceder a la cuenta '{account}' acceso de escritura a {path}: {error}. El servicio no podrá registrar su estado de ejecución ni el historial de notificaciones. Conceda el acceso manualmente en la pestaña Seguridad del Explorador."),
+    (MessageKey::DataDirShareFailedFmt, "No se pudo conceder a los usuarios acceso de escritura al directorio de datos de ejecución {path}: {error}. Los usuarios sin derechos de administrador no podrán usar --pause ni --kick."),
+    (MessageKey::AdminRequiredStart, "Se requieren privilegios de administrador para iniciar el servicio. Ejecute como administrador."),
+    (MessageKey::AdminRequiredStop, "Se requieren privilegios de administrador para detener el servicio. Ejecute como administrador."),
+    (MessageKey::ServiceStartingFmt, "Iniciando el servicio '{name}'..."),
+    (MessageKey::ServiceStarted, "Servicio iniciado."),
+    (MessageKey::ServiceAlreadyRunning, "El servicio ya está en ejecución."),
+    (MessageKey::ServiceStartFailedFmt, "El servicio se detuvo durante el inicio (código de salida: {code}). Consulte el registro para obtener más detalles."),
+    (MessageKey::ServiceStoppedAfterStartFmt, "El servicio se detuvo justo después de iniciarse (código de salida: {code}). Consulte el registro para obtener más detalles."),
+    (MessageKey::ServiceStartTimeout, "Se agotó el tiempo de espera para el inicio del servicio."),
+    (MessageKey::ForegroundStarted, "El bucle del servicio se ejecuta en esta consola. Pulse Ctrl+C para detenerlo."),
+    (MessageKey::ForegroundStopped, "Detenido."),
+    (MessageKey::ForegroundServiceRunning, "[Advertencia] El servicio está en ejecución. Las cuentas podrían notificarse dos veces."),
+    (MessageKey::ServicePanicked, "Se produjo un pánico en el bucle del servicio."),
+    (MessageKey::ServiceRestartedSuccessfully, "El servicio se reinició correctamente."),
+
+    // notify.rs
+    (MessageKey::NotifyKindNetwork, "Error de red"),
+    (MessageKey::NotifyKindAuth, "Error de autenticación"),
+    (MessageKey::NotifyKindServer, "Error del servidor"),
+    (MessageKey::NotifyKindRateLimited, "Demasiadas solicitudes"),
+    (MessageKey::NotifyKindHttp, "Error HTTP"),
+    (MessageKey::NotifyKindSkipped, "Omitido"),
+    (MessageKey::TestSendingFmt, "Comprobando las credenciales de la cuenta '{id}' ({url})..."),
+    (MessageKey::TestOkFmt, "Las credenciales son válidas. Dirección aceptada por MyDNS.JP: {addr}"),
+    (MessageKey::TestAuthFailedFmt, "Error de autenticación. El MasterID o la contraseña no son correctos. ({detail})"),
+    (MessageKey::TestInconclusiveFmt, "No se pudieron comprobar las credenciales. {kind}: {detail}"),
+
+    // doctor.rs
+    (MessageKey::DoctorTitle, "--- Diagnóstico ---"),
+    (MessageKey::DoctorHeaderFmt, "Versión: {version},  Ejecutado: {time},  Ámbito de la configuración: {scope}"),
+    (MessageKey::DoctorItemElevation, "Privilegios"),
+    (MessageKey::DoctorItemRegistry, "Configuración de cuentas"),
+    (MessageKey::DoctorItemSettings, "Configuración global"),
+    (MessageKey::DoctorItemRegistryWrite, "Acceso de escritura a la configuración"),
+    (MessageKey::DoctorItemLog, "Archivo de registro"),
+    (MessageKey::DoctorItemIpv4, "Conectividad IPv4 (ipv4.mydns.jp)"),
+    (MessageKey::DoctorItemIpv6, "Conectividad IPv6 (ipv6.mydns.jp)"),
+    (MessageKey::DoctorItemService, "Servicio"),
+    (MessageKey::DoctorItemClock, "Reloj"),
+    (MessageKey::DoctorElevated, "Se está ejecutando como administrador."),
+    (MessageKey::DoctorNotElevated, "No se está ejecutando como administrador. No se pueden cambiar la configuración ni operar el servicio."),
+    (MessageKey::DoctorAccountsFmt, "{count} cuenta(s)"),
+    (MessageKey::DoctorReadable, "Se puede leer."),
+    (MessageKey::DoctorWritable, "Se puede escribir."),
+    (MessageKey::DoctorRegistryReadOnly, "Sin permiso de escritura."),
+    (MessageKey::DoctorReachableFmt, "HTTP {status} ({ms} ms)"),
+    (MessageKey::DoctorServiceNotInstalled, "No instalado."),
+    (MessageKey::DoctorClockSkewFmt, "Diferencia con el servidor de MyDNS.JP: {seconds} s"),
+    (MessageKey::DoctorClockUnknown, "No se pudo comprobar porque MyDNS.JP no estaba accesible."),
+    (MessageKey::DoctorSummaryFmt, "\nErrores: {fail}, Advertencias: {warn}"),
+
+    // history.rs
+    (MessageKey::HistoryTitle, "--- Historial de notificaciones ---"),
+    (MessageKey::HistoryEmpty, "No hay historial registrado."),
+    (MessageKey::HistoryRowFmt, "{time}  {id}  {proto}  {ms} ms  {result}"),
+
+    // ipc.rs
+    (MessageKey::IpcServiceUnavailable, "No se puede conectar con el servicio. Compruebe que el servicio está en ejecución."),
+    (MessageKey::IpcServiceBusy, "El servicio está ocupado con otras solicitudes. Inténtelo de nuevo más tarde."),
+    (MessageKey::IpcServerNotServiceFmt, "No se envió la solicitud porque el servidor de la canalización con nombre (PID {pid}) no es el proceso del servicio."),
+    (MessageKey::IpcInvalidResponse, "No se pudo interpretar la respuesta del servicio."),
+    (MessageKey::IpcRequestFailedFmt, "El servicio no pudo procesar la solicitud: {error}"),
+    (MessageKey::IpcNotifyNowSuccess, "[Correcto] Se solicitó al servicio una notificación inmediata."),
+    (MessageKey::IpcReloadSuccessFmt, "[Correcto] El servicio volvió a cargar la configuración de las cuentas ({count} cuentas)."),
+    (MessageKey::IpcStatusTitle, "--- Estado del servicio ---"),
+    (MessageKey::IpcStatusVersion, "Versión"),
+    (MessageKey::IpcStatusStarted, "Iniciado"),
+    (MessageKey::IpcStatusAccounts, "Cuentas"),
+    (MessageKey::IpcStatusPaused, "En pausa"),
+    (MessageKey::IpcStatusRoundRunning, "Notificando"),
+    (MessageKey::IpcStatusNextRound, "Próxima notificación periódica"),
+
+    // schtask.rs
+    (MessageKey::AdminRequiredTask, "Se requieren privilegios de administrador para registrar o eliminar la tarea programada. Ejecute como administrador."),
+    (MessageKey::TaskServiceInstalledWarning, "[Advertencia] El servicio también está instalado. Use solo uno de los dos para evitar notificaciones duplicadas."),
+    (MessageKey::TaskInstalledFmt, "Se registró la tarea programada '{name}'. Notifica al iniciar, al conectarse a la red y cada {minutes} minutos."),
+    (MessageKey::TaskUninstalledFmt, "Se eliminó la tarea programada '{name}'."),
+    (MessageKey::TaskNotInstalledFmt, "La tarea programada '{name}' no está registrada."),
+    (MessageKey::TaskDescription, "Notifica a MyDNS.JP la dirección IP de este equipo (MyDNS.JP Adapter)."),
+
+    // crash.rs
+    (MessageKey::PanicOccurredFmt, "Se produjo un error inesperado. Los detalles se escribieron en el registro y en el Visor de eventos: {summary}"),
+
+    // stats.rs
+    (MessageKey::StatsTitle, "--- Estadísticas de uso ---"),
+    (MessageKey::StatsEnabled, "[Correcto] Se activaron las estadísticas de uso. Las estadísticas nunca salen de este equipo."),
+    (MessageKey::StatsDisabled, "[Correcto] Se desactivaron las estadísticas de uso."),
+    (MessageKey::StatsNotEnabled, "Las estadísticas de uso están desactivadas. Ejecute --stats --opt-in para activarlas."),
+    (MessageKey::StatsEmpty, "No se han registrado estadísticas."),
+    (MessageKey::StatsMonthFmt, "{month}: {total} notificaciones ({ok} correctas / {ng} con error),  {ip} cambios de IP,  {hours} h en ejecución"),
+    (MessageKey::StatsYearFmt, "\nEn {year}: {total} notificaciones, su IP cambió {ip} veces."),
+];
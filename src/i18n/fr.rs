@@ -0,0 +1,367 @@
+//! フランス語のメッセージ。
+//!
+//! ログと開発者向けの機能（`--simulate`、`--bench`）のメッセージは翻訳せず、英語のメッセージを使います。
+
+use super::MessageKey;
+
+/// キーとフランス語のメッセージの組。
+#[rustfmt::skip]
+pub(super) static MESSAGES: &[(MessageKey, &str)] = &[
+    // main.rs
+    (MessageKey::LangInvalid, "Langue non prise en charge. Utilisez ja, en, zh, ko, de, fr ou es."),
+    (MessageKey::ConfigTitle, "--- Configuration de MyDNS Adapter ---"),
+    (MessageKey::ConfigLoaded, "\nConfiguration actuelle chargée. Appuyez sur Entrée pour conserver les valeurs actuelles."),
+    (MessageKey::MasterIdPrompt, "MasterID"),
+    (MessageKey::PasswordPrompt, "Mot de passe"),
+    (MessageKey::Ipv4NotifyPrompt, "Activer la notification IPv4 ?"),
+    (MessageKey::Ipv6NotifyPrompt, "Activer la notification IPv6 ?"),
+    (MessageKey::KeepAlivePrompt, "Intervalle de notification forcée en heures (0 pour désactiver)"),
+    (MessageKey::NumberInvalid, "Veuillez saisir un entier positif ou nul."),
+    (MessageKey::RegistrySaveSuccess, "\n[Succès] Configuration enregistrée dans le registre."),
+    (MessageKey::RegistrySaveFailFmt, "\n[Échec] Erreur d'enregistrement dans le registre : {error}"),
+    (MessageKey::InputPromptPwFmt, "Saisissez {item} (actuel : {current}, Entrée pour conserver) : "),
+    (MessageKey::InputPromptFmt, "Saisissez {item} (actuel : {current}) : "),
+    (MessageKey::InputPromptNewFmt, "Saisissez {item} : "),
+    (MessageKey::NotSet, "(Non défini)"),
+    (MessageKey::YesNoPromptFmt, "{question} (actuel : {current}) {hint} : "),
+    (MessageKey::YesNoHintTrue, "(O/n)"),
+    (MessageKey::YesNoHintFalse, "(o/N)"),
+    (MessageKey::YesNoInvalid, "Veuillez saisir 'o' ou 'n', ou appuyer sur Entrée."),
+    (MessageKey::YesNoAffirmativeWords, "y,yes,o,oui"),
+    (MessageKey::YesNoNegativeWords, "n,no,non"),
+    (MessageKey::ViewTitle, "--- Paramètres MyDNS actuels ---"),
+    (MessageKey::ViewMasterIdFmt, "MasterID : {id}"),
+    (MessageKey::ViewPasswordFmt, "Mot de passe : {password}"),
+    (MessageKey::ViewIpv4Fmt, "Notification IPv4 : {value}"),
+    (MessageKey::ViewIpv6Fmt, "Notification IPv6 : {value}"),
+    (MessageKey::Yes, "Oui"),
+    (MessageKey::No, "Non"),
+    (MessageKey::ViewNoAccounts, "Aucun compte n'est configuré."),
+    (MessageKey::ViewListFmt, "MasterID : {id},  Mot de passe : {pw},  IPv4 : {v4},  IPv6 : {v6}"),
+    (MessageKey::AddTitle, "--- Ajouter un compte ---"),
+    (MessageKey::EditTitle, "--- Modifier un compte ---"),
+    (MessageKey::RemoveTitle, "--- Supprimer un compte ---"),
+    (MessageKey::AccountExistsFmt, "Le compte '{id}' existe déjà."),
+    (MessageKey::AccountNotFoundFmt, "Le compte '{id}' est introuvable."),
+    (MessageKey::SelectAccountPrompt, "Sélectionnez le compte à modifier :"),
+    (MessageKey::SelectAccountIndexPrompt, "Saisissez un numéro ou un MasterID : "),
+    (MessageKey::InvalidSelection, "Sélection non valide."),
+    (MessageKey::RemoveNeedsYes, "Impossible de confirmer la suppression, car l'entrée standard n'est pas un terminal. Utilisez --yes pour supprimer sans confirmation."),
+    (MessageKey::ConfirmRemoveFmt, "Voulez-vous vraiment supprimer le compte '{id}' ?"),
+    (MessageKey::ConfirmPromptFmt, "{question} {hint} : "),
+    (MessageKey::RemoveSuccess, "[Succès] Le compte a été supprimé."),
+    (MessageKey::RemoveFailFmt, "[Échec] Impossible de supprimer le compte : {error}"),
+    (MessageKey::BatchRowErrorFmt, "Ligne {line} : {error}"),
+    (MessageKey::BatchInvalidFmt, "Aucun compte n'a été ajouté, car {count} ligne(s) contiennent des erreurs."),
+    (MessageKey::BatchEmpty, "Il n'y a aucun compte à ajouter."),
+    (MessageKey::BatchSuccessFmt, "[Succès] {count} compte(s) ajouté(s)."),
+    (MessageKey::BatchColumnCountFmt, "Nombre de colonnes incorrect ({count}). Utilisez la forme \"MasterID,mot de passe,IPv4,IPv6\"."),
+    (MessageKey::BatchInvalidFlagFmt, "Valeur de {name} non valide : {value} (utilisez yes ou no)"),
+    (MessageKey::BatchUnterminatedQuote, "Une colonne entre guillemets n'est pas fermée."),
+    (MessageKey::RenameSuccessFmt, "[Succès] Le compte '{old}' a été renommé en '{new}'."),
+    (MessageKey::AccountEnabledFmt, "[Succès] Les notifications du compte '{id}' ont repris."),
+    (MessageKey::AccountNotSuspendedFmt, "Les notifications du compte '{id}' ne sont pas suspendues."),
+    (MessageKey::DryRunTitle, "[Simulation] Les requêtes suivantes seraient envoyées (rien n'est envoyé) :"),
+    (MessageKey::DryRunRequestFmt, "  {id} {proto} : {method} {url}  ({auth})"),
+    (MessageKey::DryRunBasicAuthFmt, "Authentification Basic : {user} / ********"),
+    (MessageKey::DryRunLoginAuthFmt, "Connexion : {user} / ******** (en clair)"),
+    (MessageKey::DryRunNoAuth, "sans identifiants"),
+    (MessageKey::DryRunBindFmt, "    Adresse source : {addr}"),
+    (MessageKey::DryRunSkippedFmt, "  {id} {proto} : non envoyé. {reason}"),
+    (MessageKey::DryRunSuspended, "Les notifications sont suspendues après des échecs d'authentification répétés (--force les envoie quand même)."),
+    (MessageKey::DryRunPrivateIpv4, "L'adresse IPv4 actuelle n'est pas joignable depuis Internet ; la mise à jour est ignorée."),
+    (MessageKey::DryRunNone, "Aucune requête ne serait envoyée."),
+    (MessageKey::NotifySummaryTitle, "Résultats de la notification :"),
+    (MessageKey::NotifySummaryRowFmt, "  {id}  {proto}  {result}  {ms} ms ({attempts} tentatives){detail}"),
+    (MessageKey::NotifySummarySkippedFmt, "  {id}  -     {reason}"),
+    (MessageKey::NotifySummarySuspended, "Suspendu après des échecs d'authentification répétés (non envoyé)"),
+    (MessageKey::NotifySummaryInvalidProxy, "URL de proxy non valide (non envoyé)"),
+    (MessageKey::NotifySummaryAborted, "La tâche de notification s'est terminée anormalement"),
+    (MessageKey::NotifySummaryNoProtocol, "Aucun protocole à notifier"),
+    (MessageKey::NotifySummaryTotalFmt, "{ok} réussies, {failed} en échec"),
+    (MessageKey::ElevationConfirm, "Cette opération nécessite des privilèges d'administrateur. La relancer en tant qu'administrateur ?"),
+    (MessageKey::ElevationRequired, "Cette opération nécessite des privilèges d'administrateur. Exécutez-la depuis une invite de commandes élevée."),
+    (MessageKey::ElevationCancelled, "L'exécution en tant qu'administrateur a été annulée."),
+    (MessageKey::ElevatedPressEnter, "\nAppuyez sur Entrée pour fermer cette fenêtre..."),
+    (MessageKey::ServicePasswordPromptFmt, "le mot de passe du compte '{account}'"),
+    (MessageKey::ServicePasswordNotNeededFmt, "Aucun mot de passe ne peut être indiqué pour le compte '{account}'."),
+    (MessageKey::ConfirmPurge, "Tous les comptes, paramètres et fichiers journaux enregistrés seront supprimés. Cette action est irréversible. Continuer ?"),
+    (MessageKey::PurgeNeedsYes, "Impossible de confirmer la purge, car l'entrée standard n'est pas un terminal. Utilisez --yes pour purger sans confirmation."),
+    (MessageKey::PurgeSuccess, "[Succès] Les comptes, paramètres et fichiers journaux enregistrés ont été supprimés."),
+    (MessageKey::AddSuccess, "[Succès] Le compte a été ajouté."),
+    (MessageKey::NoAccountsAddPrompt, "Aucun compte trouvé. En créer un ?"),
+    (MessageKey::OperationCancelled, "Opération annulée."),
+    (MessageKey::EditTargetFmt, "Compte ciblé : {id}"),
+    (MessageKey::ViewRuntimeFmt, "    État : {state},  Dernière tentative : {time},  Résultat : {result}"),
+    (MessageKey::ViewRuntimeInFlight, "Notification en cours"),
+    (MessageKey::ViewRuntimeIdle, "Inactif"),
+    (MessageKey::ViewPaused, "\n[Remarque] La notification périodique est suspendue. Utilisez --resume pour la reprendre."),
+    (MessageKey::PauseSuccess, "[Succès] La notification périodique a été suspendue."),
+    (MessageKey::ResumeSuccess, "[Succès] La notification périodique a repris."),
+    (MessageKey::ViewIntervalFmt, "Intervalle de notification : {minutes} minutes"),
+    (MessageKey::IntervalSetFmt, "L'intervalle de notification est défini sur {minutes} minutes. Un service en cours d'exécution l'appliquera après sa prochaine notification planifiée."),
+    (MessageKey::IntervalOutOfRangeFmt, "L'intervalle de notification doit être compris entre {min} et {max} minutes."),
+    (MessageKey::ViewRetryFmt, "Nombre maximal de tentatives de notification : {count}"),
+    (MessageKey::RetrySetFmt, "Le nombre maximal de tentatives de notification est défini sur {count}."),
+    (MessageKey::RetryOutOfRangeFmt, "Le nombre maximal de tentatives de notification doit être compris entre {min} et {max}."),
+    (MessageKey::ViewConcurrencyFmt, "Comptes notifiés simultanément : {count}"),
+    (MessageKey::ConcurrencySetFmt, "Le nombre de comptes notifiés simultanément a été défini à {count}."),
+    (MessageKey::ConcurrencyOutOfRangeFmt, "Le nombre de comptes notifiés simultanément doit être compris entre {min} et {max}."),
+    (MessageKey::ViewRequestTimeoutFmt, "Délai d'expiration des requêtes de notification : {seconds} secondes"),
+    (MessageKey::RequestTimeoutSetFmt, "Le délai d'expiration des requêtes de notification a été défini à {seconds} secondes."),
+    (MessageKey::RequestTimeoutOutOfRangeFmt, "Le délai d'expiration des requêtes de notification doit être compris entre {min} et {max} secondes."),
+    (MessageKey::Ipv4UrlPrompt, "URL de notification IPv4"),
+    (MessageKey::Ipv6UrlPrompt, "URL de notification IPv6"),
+    (MessageKey::EndpointUrlInvalidFmt, "\"{url}\" n'est pas une URL valide. La valeur actuelle est conservée."),
+    (MessageKey::ViewEndpointFmt, "  URL de notification ({proto}) : {url}"),
+    (MessageKey::HostnamePrompt, "Nom d'hôte à vérifier dans le DNS (vide : ignorer, - : effacer)"),
+    (MessageKey::SetHostnameInvalidFmt, "\"{host}\" n'est pas un nom d'hôte valide."),
+    (MessageKey::SetEndpointUrlInvalidFmt, "\"{url}\" n'est pas une URL valide."),
+    (MessageKey::HostnameInvalidFmt, "\"{host}\" n'est pas un nom d'hôte valide. La valeur actuelle est conservée."),
+    (MessageKey::ViewLastSuccessFmt, "  Dernier succès : {time}  (IPv4 : {v4},  IPv6 : {v6})"),
+    (MessageKey::ViewHostnameFmt, "  Nom d'hôte à vérifier : {host}"),
+    (MessageKey::ProviderPrompt, "Service de mise à jour (mydns : MyDNS.JP, generic : tout service avec une URL de mise à jour)"),
+    (MessageKey::ProviderInvalid, "Le service doit être mydns ou generic."),
+    (MessageKey::UsernamePrompt, "Nom d'utilisateur (sert aussi de nom de compte)"),
+    (MessageKey::GenericIpv4UrlPrompt, "URL de mise à jour IPv4 ({ip} et {host} sont remplacés)"),
+    (MessageKey::GenericIpv6UrlPrompt, "URL de mise à jour IPv6 (vide : comme IPv4, - : effacer)"),
+    (MessageKey::GenericUrlRequired, "Un service générique nécessite une URL de mise à jour IPv4."),
+    (MessageKey::ViewProviderGeneric, "  Service : générique (URL de mise à jour)"),
+    (MessageKey::MethodPrompt, "Méthode de mise à jour (http : HTTPS, pop3/imap/ftp : connexion au serveur)"),
+    (MessageKey::MethodInvalid, "La méthode de mise à jour doit être http, pop3, imap ou ftp."),
+    (MessageKey::MethodRequiresMyDns, "La mise à jour par connexion POP3/IMAP/FTP n'est disponible que pour les comptes MyDNS.JP."),
+    (MessageKey::MethodPlaintextWarning, "Remarque : les connexions POP3/IMAP/FTP envoient le mot de passe en clair."),
+    (MessageKey::ViewMethodFmt, "  Méthode de mise à jour : {method} (connexion)"),
+    (MessageKey::ViewLogPathFmt, "Fichier journal : {path}"),
+    (MessageKey::ViewLogArchiveFmt, "Archives du journal : {count} conservées (compressées : {compress})"),
+    (MessageKey::ViewProxyFmt, "Proxy : {proxy}"),
+    (MessageKey::ProxyAuto, "Automatique (paramètres WinHTTP/système)"),
+    (MessageKey::ViewMetricsFmt, "Métriques : http://127.0.0.1:{port}/metrics"),
+    (MessageKey::MetricsSetFmt, "Les métriques seront servies sur http://127.0.0.1:{port}/metrics. Un service en cours d'exécution l'appliquera après un redémarrage."),
+    (MessageKey::MetricsDisabled, "Point d'accès des métriques désactivé. Un service en cours d'exécution l'appliquera après un redémarrage."),
+    (MessageKey::ViewAdminApiFmt, "API d'administration : http://127.0.0.1:{port}/api/"),
+    (MessageKey::AdminApiSetFmt, "L'API d'administration sera servie sur http://127.0.0.1:{port}/api/. Un service en cours d'exécution l'appliquera après un redémarrage.\nEnvoyez les requêtes avec l'en-tête \"Authorization: Bearer {token}\"."),
+    (MessageKey::AdminApiDisabled, "API d'administration désactivée. Un service en cours d'exécution l'appliquera après un redémarrage."),
+    (MessageKey::PingUrlPrompt, "URL de ping de surveillance appelée après une mise à jour réussie (vide : aucune, - : effacer)"),
+    (MessageKey::ViewAccountPingUrlFmt, "  URL de ping : {url}"),
+    (MessageKey::InterfacePrompt, "Nom de l'interface réseau ou adresse locale d'envoi (vide : toutes, - : supprimer)"),
+    (MessageKey::ViewInterfaceFmt, "  Envoi depuis : {interface}"),
+    (MessageKey::SchedulePrompt, "Planification des notifications à la place de l'intervalle (ex. */10 * * * *, at 04:05 daily ; vide : intervalle, - : effacer)"),
+    (MessageKey::ScheduleInvalidFmt, "Impossible d'interpréter la planification (utilisez cinq champs cron ou at HH:MM daily) : {schedule}"),
+    (MessageKey::ViewScheduleFmt, "  Planification : {schedule}"),
+    (MessageKey::ViewPingUrlFmt, "URL de ping de surveillance : {url}"),
+    (MessageKey::PingUrlSetFmt, "Un ping sera envoyé à \"{url}\" après chaque série de notifications où tous les comptes réussissent."),
+    (MessageKey::PingUrlCleared, "L'URL de ping de surveillance a été effacée."),
+    (MessageKey::ViewWebhookUrlFmt, "Webhook : {url}"),
+    (MessageKey::WebhookUrlSetFmt, "Les échecs, rétablissements et changements d'adresse seront envoyés à \"{url}\"."),
+    (MessageKey::WebhookUrlCleared, "L'URL du webhook a été effacée."),
+    (MessageKey::ViewToastFmt, "Notification toast : après {count} échecs consécutifs"),
+    (MessageKey::ToastSetFmt, "Une notification toast sera affichée après {count} échecs consécutifs."),
+    (MessageKey::ToastDisabled, "Les notifications toast ont été désactivées."),
+    (MessageKey::ViewMailFmt, "Alertes par e-mail : {to} (via {server}:{port}, après {minutes} minutes d'échecs)"),
+    (MessageKey::MailSettingsSaved, "Les paramètres d'e-mail ont été enregistrés. Utilisez --mail-test pour envoyer un message de test."),
+    (MessageKey::MailDisabled, "Les alertes par e-mail ont été désactivées."),
+    (MessageKey::MailNotConfigured, "Définissez --smtp-server et --mail-to pour utiliser les alertes par e-mail."),
+    (MessageKey::SmtpServerInvalidFmt, "\"{server}\" n'est pas un serveur SMTP valide. Utilisez HOST ou HOST:PORT."),
+    (MessageKey::MailAddressInvalidFmt, "\"{address}\" n'est pas une adresse e-mail valide."),
+    (MessageKey::MailTestSentFmt, "[Succès] Un message de test a été envoyé à {to}."),
+    (MessageKey::MailTestFailedFmt, "Impossible d'envoyer le message de test : {error}"),
+    (MessageKey::ViewCheckIpv4UrlsFmt, "Services de vérification IPv4 : {urls}"),
+    (MessageKey::ViewCheckIpv6UrlsFmt, "Services de vérification IPv6 : {urls}"),
+    (MessageKey::ViewCheckIpPollFmt, "Vérification de l'adresse globale : toutes les {minutes} minutes"),
+    (MessageKey::CheckIpSettingsSaved, "Les paramètres des services de vérification d'IP ont été enregistrés."),
+    (MessageKey::ViewSkipPrivateIpv4, "Adresses IPv4 CGNAT/privées : non notifiées"),
+    (MessageKey::SkipPrivateIpv4Enabled, "Les mises à jour seront ignorées lorsque l'adresse IPv4 obtenue est une adresse CGNAT ou privée."),
+    (MessageKey::SkipPrivateIpv4Disabled, "Les adresses IPv4 CGNAT et privées seront notifiées, avec un avertissement dans le journal."),
+    (MessageKey::ViewDnsCheckFmt, "Autovérification DNS : toutes les {minutes} minutes"),
+    (MessageKey::DnsCheckSetFmt, "Les enregistrements DNS des noms d'hôte seront vérifiés auprès de résolveurs publics toutes les {minutes} minutes."),
+    (MessageKey::DnsCheckDisabled, "L'autovérification DNS a été désactivée."),
+    (MessageKey::ViewMeteredFmt, "Connexions limitées : {policy}"),
+    (MessageKey::MeteredPolicyInvalid, "La stratégie pour les connexions limitées doit être ignore, reduce ou skip."),
+    (MessageKey::MeteredIgnore, "Les notifications s'exécuteront normalement sur les connexions limitées."),
+    (MessageKey::MeteredReduce, "Sur les connexions limitées, les notifications planifiées s'exécuteront au plus une fois par heure."),
+    (MessageKey::MeteredSkip, "Sur les connexions limitées, le service ignorera les notifications automatiques."),
+    (MessageKey::ViewAllowedNetworksFmt, "Réseaux autorisés : {networks}"),
+    (MessageKey::AllowedNetworksSetFmt, "Le service ne notifiera que lorsqu'il est connecté à : {networks}"),
+    (MessageKey::AllowedNetworksCleared, "La restriction aux réseaux autorisés a été supprimée."),
+    (MessageKey::ConnectedNetworksFmt, "Réseaux actuellement connectés : {networks}"),
+    (MessageKey::ViewQuietHoursFmt, "Heures calmes : {hours}"),
+    (MessageKey::QuietHoursInvalidFmt, "Les heures calmes doivent être au format HH:MM-HH:MM (par exemple 23:00-06:00) avec des heures de début et de fin différentes : {hours}"),
+    (MessageKey::QuietHoursSetFmt, "Le service suspendra les notifications automatiques chaque jour pendant {hours}."),
+    (MessageKey::QuietHoursCleared, "Les heures calmes ont été supprimées."),
+    (MessageKey::ViewJitterFmt, "Décalage aléatoire des notifications : ±{seconds} secondes"),
+    (MessageKey::JitterSetFmt, "Le service décalera aléatoirement l'heure des notifications jusqu'à ±{seconds} secondes (au plus la moitié de l'intervalle)."),
+    (MessageKey::JitterDisabled, "Le décalage aléatoire des notifications a été désactivé."),
+    (MessageKey::ViewUserAgentFmt, "User-Agent : {ua}"),
+    (MessageKey::UserAgentIdSetFmt, "L'identifiant du User-Agent a été défini : {ua}"),
+    (MessageKey::UserAgentIdCleared, "L'identifiant du User-Agent a été supprimé."),
+    (MessageKey::UserAgentIdInvalidFmt, "L'identifiant du User-Agent doit comporter au plus {max} caractères ASCII imprimables."),
+    (MessageKey::ViewHistoryDatabase, "Base de données de l'historique : activée"),
+    (MessageKey::HistoryDbEnabled, "L'historique des notifications sera également enregistré dans la base de données."),
+    (MessageKey::HistoryDbDisabled, "L'historique des notifications ne sera plus enregistré dans la base de données. Les enregistrements existants sont conservés."),
+    (MessageKey::HistoryDbUnsupported, "Cette version ne prend pas en charge la base de données de l'historique. Compilez avec `--features sqlite`."),
+    (MessageKey::StatusHistorySummaryFmt, "  Dernières 24 heures : {attempts} tentatives, {successes} réussies, {ms} ms en moyenne"),
+    (MessageKey::ToastTitle, "MyDNS Adapter : échec des notifications"),
+    (MessageKey::ToastFailuresFmt, "La notification du compte {id} a échoué {count} fois de suite. {error}"),
+    (MessageKey::ToastExpiryTitle, "MyDNS Adapter : enregistrement bientôt expiré"),
+    (MessageKey::ToastExpiryFmt, "Le compte {id} n'a pas été mis à jour depuis {hours} heures. L'enregistrement MyDNS.JP expire dans environ {remaining} heures."),
+    (MessageKey::ToastMonitorStarted, "--- Surveillance des échecs de notification (Ctrl+C pour quitter) ---"),
+    (MessageKey::ProxySetFmt, "Le proxy de notification est défini sur \"{url}\"."),
+    (MessageKey::ProxyCleared, "Le paramètre de proxy a été effacé. Les paramètres WinHTTP/système seront utilisés."),
+    (MessageKey::ProxyPrompt, "URL du proxy (vide : paramètre global, direct : sans proxy, - : effacer)"),
+    (MessageKey::ExportSuccessFmt, "[Succès] {count} compte(s) exporté(s) vers {path}."),
+    (MessageKey::ExportPasswordWarning, "[Avertissement] Le fichier contient des mots de passe en clair. Manipulez-le avec précaution. (Utilisez --redact-passwords pour les exclure.)"),
+    (MessageKey::ImportTitle, "--- Importer la configuration ---"),
+    (MessageKey::ImportParseErrorFmt, "Impossible d'analyser le fichier : {error}"),
+    (MessageKey::ImportUnsupportedVersionFmt, "Version de format de fichier non prise en charge : {version}"),
+    (MessageKey::ImportDuplicateFmt, "Le MasterID {id} apparaît plusieurs fois."),
+    (MessageKey::ImportUnknownFieldFmt, "Champ inconnu : {field}"),
+    (MessageKey::ImportMissingFieldFmt, "Champ obligatoire manquant : {field}"),
+    (MessageKey::ImportInvalidTypeFmt, "Type de valeur non valide pour le champ : {field}"),
+    (MessageKey::ImportMissingPasswordFmt, "Un mot de passe est requis pour le nouveau compte {id}."),
+    (MessageKey::ImportDiffNewFmt, "+ {id} (nouveau)"),
+    (MessageKey::ImportDiffChangedFmt, "~ {id} (modifié)"),
+    (MessageKey::ImportDiffUnchangedFmt, "= {id} (inchangé)"),
+    (MessageKey::ImportNothingToDo, "Aucune modification à appliquer."),
+    (MessageKey::ImportConfirmFmt, "Appliquer {count} modification(s) de compte ?"),
+    (MessageKey::ImportSuccessFmt, "[Succès] {count} modification(s) de compte appliquée(s)."),
+    (MessageKey::UserScopeServiceConflict, "--user ne peut pas être utilisé avec les commandes du service, car le service ne lit pas les paramètres propres à l'utilisateur."),
+    (MessageKey::StatusTitle, "--- État de MyDNS Adapter ---"),
+    (MessageKey::StatusUptimeFmt, "Durée de fonctionnement : {days} j {hours} h {minutes} min (démarré le {since})"),
+    (MessageKey::ViewLastErrorFmt, "  Dernière erreur : {time}  [{kind}] {detail}"),
+    (MessageKey::ViewSuspendedFmt, "  Suspendu : depuis {time} (échecs d'authentification répétés). Reprenez avec `account enable {id}`."),
+    (MessageKey::StatusLastIpFmt, "  Dernière adresse notifiée : IPv4 {v4},  IPv6 {v6}"),
+    (MessageKey::StatusAccountFmt, "{id} : Dernière notification : {time},  Résultat : {result},  Dernier succès : {success}"),
+    (MessageKey::UserScopeFallback, "[Info] Exécution sans privilèges d'administrateur ; utilisation des paramètres propres à l'utilisateur (HKCU). Le service ne lit pas ces paramètres."),
+    (MessageKey::ViewUserScope, "(Paramètres propres à l'utilisateur, depuis HKCU)"),
+    (MessageKey::ViewConfigFileFmt, "(Affichage des comptes du fichier de configuration {path})"),
+    (MessageKey::ConfigFileInvalidFmt, "Impossible de lire le fichier de configuration {path} : {error}"),
+    (MessageKey::PasswordFromEnvFmt, "Utilisation du mot de passe de la variable d'environnement {var}."),
+    (MessageKey::KickSuccess, "[Succès] Notification immédiate demandée au service."),
+    (MessageKey::UpgradeTitle, "--- Mise à niveau de la configuration ---"),
+    (MessageKey::UpgradeAccountFmt, "Compte '{id}' :"),
+    (MessageKey::UpgradeNoChanges, "Aucune modification."),
+    (MessageKey::UpgradeAddedDefaultFmt, "{name} : absent, valeur par défaut {value} écrite."),
+    (MessageKey::UpgradeMigratedTypeFmt, "{name} : converti de chaîne en nombre {value}."),
+    (MessageKey::UpgradeEmptyPassword, "[Avertissement] Aucun mot de passe n'est défini. Utilisez 'account edit' pour le définir."),
+    (MessageKey::InvalidMasterIdPrefix, "Le MasterID doit commencer par 'mydns'."),
+
+    // winservice.rs
+    (MessageKey::AdminRequiredInstall, "L'installation du service nécessite des privilèges d'administrateur. Exécutez en tant qu'administrateur."),
+    (MessageKey::ServiceInstallingFmt, "Installation du service '{name}'..."),
+    (MessageKey::ServiceInstalledFmt, "Le service '{name}' a été installé et démarré."),
+    (MessageKey::AdminRequiredUninstall, "La désinstallation du service nécessite des privilèges d'administrateur. Exécutez en tant qu'administrateur."),
+    (MessageKey::ServiceNotInstalledFmt, "Le service '{name}' n'est pas installé."),
+    (MessageKey::ServiceStoppingFmt, "Arrêt du service '{name}'..."),
+    (MessageKey::ServiceStopped, "Service arrêté."),
+    (MessageKey::ServiceWaitingStop, "En attente de l'arrêt du service..."),
+    (MessageKey::ServiceNotRunning, "Le service n'est pas en cours d'exécution."),
+    (MessageKey::ServiceUninstalledFmt, "Le service '{name}' a été désinstallé."),
+    (MessageKey::AdminRequiredRestart, "Le redémarrage du service nécessite des privilèges d'administrateur. Exécutez en tant qu'administrateur."),
+    (MessageKey::ServiceStatusFmt, "Service '{name}' : {state}"),
+    (MessageKey::ServiceStateRunning, "En cours d'exécution"),
+    (MessageKey::ServiceStateStopped, "Arrêté"),
+    (MessageKey::ServiceStateStartPending, "Démarrage"),
+    (MessageKey::ServiceStateStopPending, "Arrêt en cours"),
+    (MessageKey::ServiceStatePaused, "Suspendu"),
+    (MessageKey::ServiceStatePausePending, "Suspension en cours"),
+    (MessageKey::ServiceStateContinuePending, "Reprise en cours"),
+    (MessageKey::ServiceStateUnknown, "Inconnu"),
+    (MessageKey::ServiceDescription, "Notifie régulièrement MyDNS.JP des adresses IPv4/IPv6 de cet ordinateur pour tenir à jour ses enregistrements DNS dynamiques."),
+    (MessageKey::ServiceLogonFailedFmt, "Le service a été installé, mais n'a pas pu démarrer, car l'ouverture de session en tant que '{account}' a échoué. Vérifiez le mot de passe et le droit \"Ouvrir une session en tant que service\", puis redémarrez le service."),
+    (MessageKey::ServiceKeyAccessGrantedFmt, "Le compte '{account}' a reçu l'accès en lecture et en écriture à {key}."),
+    (MessageKey::ServiceKeyAccessFailedFmt, "Impossible d'accorder au compte '{account}' l'accès en écriture à {key} : {error}. Le service ne pourra pas enregistrer les résultats des notifications ni les suspensions automatiques. Accordez l'accès manuellement avec regedit."),
+    (MessageKey::ServiceDataDirAccessGrantedFmt, "Le compte '{account}' a reçu l'accès en écriture à {path}."),
+    (MessageKey::ServiceDataDirAccessFailedFmt, "Impossible d'accorder au compte '{account}' l'accès en écriture à {path} : {error}. Le service ne pourra pas enregistrer son état d'exécution ni l'historique des notifications. Accordez l'accès manuellement dans l'onglet Sécurité de l'Explorateur."),
+    (MessageKey::DataDirShareFailedFmt, "Impossible d'accorder aux utilisateurs l'accès en écriture au répertoire des données d'exécution {path} : {error}. Les utilisateurs sans droits d'administrateur ne pourront pas utiliser --pause ni --kick."),
+    (MessageKey::AdminRequiredStart, "Le démarrage du service nécessite des privilèges d'administrateur. Exécutez en tant qu'administrateur."),
+    (MessageKey::AdminRequiredStop, "L'arrêt du service nécessite des privilèges d'administrateur. Exécutez en tant qu'administrateur."),
+    (MessageKey::ServiceStartingFmt, "Démarrage du service '{name}'..."),
+    (MessageKey::ServiceStarted, "Service démarré."),
+    (MessageKey::ServiceAlreadyRunning, "Le service est déjà en cours d'exécution."),
+    (MessageKey::ServiceStartFailedFmt, "Le service s'est arrêté pendant le démarrage (code de sortie : {code}). Consultez le journal pour plus de détails."),
+    (MessageKey::ServiceStoppedAfterStartFmt, "Le service s'est arrêté juste après le démarrage (code de sortie : {code}). Consultez le journal pour plus de détails."),
+    (MessageKey::ServiceStartTimeout, "Délai dépassé en attendant le démarrage du service."),
+    (MessageKey::ForegroundStarted, "La boucle du service s'exécute dans cette console. Appuyez sur Ctrl+C pour l'arrêter."),
+    (MessageKey::ForegroundStopped, "Arrêté."),
+    (MessageKey::ForegroundServiceRunning, "[Avertissement] Le service est en cours d'exécution. Les comptes risquent d'être notifiés deux fois."),
+    (MessageKey::ServicePanicked, "La boucle du service a paniqué."),
+    (MessageKey::ServiceRestartedSuccessfully, "Le service a été redémarré."),
+
+    // notify.rs
+    (MessageKey::NotifyKindNetwork, "Erreur réseau"),
+    (MessageKey::NotifyKindAuth, "Échec de l'authentification"),
+    (MessageKey::NotifyKindServer, "Erreur du serveur"),
+    (MessageKey::NotifyKindRateLimited, "Trop de requêtes"),
+    (MessageKey::NotifyKindHttp, "Erreur HTTP"),
+    (MessageKey::NotifyKindSkipped, "Ignoré"),
+    (MessageKey::TestSendingFmt, "Vérification des identifiants du compte '{id}' ({url})..."),
+    (MessageKey::TestOkFmt, "Les identifiants sont valides. Adresse acceptée par MyDNS.JP : {addr}"),
+    (MessageKey::TestAuthFailedFmt, "Échec de l'authentification. Le MasterID ou le mot de passe est incorrect. ({detail})"),
+    (MessageKey::TestInconclusiveFmt, "Impossible de vérifier les identifiants. {kind} : {detail}"),
+
+    // doctor.rs
+    (MessageKey::DoctorTitle, "--- Diagnostic ---"),
+    (MessageKey::DoctorHeaderFmt, "Version : {version},  Exécuté le : {time},  Portée de la configuration : {scope}"),
+    (MessageKey::DoctorItemElevation, "Élévation"),
+    (MessageKey::DoctorItemRegistry, "Paramètres des comptes"),
+    (MessageKey::DoctorItemSettings, "Paramètres globaux"),
+    (MessageKey::DoctorItemRegistryWrite, "Accès en écriture aux paramètres"),
+    (MessageKey::DoctorItemLog, "Fichier journal"),
+    (MessageKey::DoctorItemIpv4, "Connectivité IPv4 (ipv4.mydns.jp)"),
+    (MessageKey::DoctorItemIpv6, "Connectivité IPv6 (ipv6.mydns.jp)"),
+    (MessageKey::DoctorItemService, "Service"),
+    (MessageKey::DoctorItemClock, "Horloge"),
+    (MessageKey::DoctorElevated, "Exécution en tant qu'administrateur."),
+    (MessageKey::DoctorNotElevated, "Exécution sans privilèges d'administrateur. La modification des paramètres et les opérations sur le service ne sont pas disponibles."),
+    (MessageKey::DoctorAccountsFmt, "{count} compte(s)"),
+    (MessageKey::DoctorReadable, "Lisible."),
+    (MessageKey::DoctorWritable, "Accessible en écriture."),
+    (MessageKey::DoctorRegistryReadOnly, "Aucune autorisation d'écriture."),
+    (MessageKey::DoctorReachableFmt, "HTTP {status} ({ms} ms)"),
+    (MessageKey::DoctorServiceNotInstalled, "Non installé."),
+    (MessageKey::DoctorClockSkewFmt, "Décalage par rapport au serveur MyDNS.JP : {seconds} s"),
+    (MessageKey::DoctorClockUnknown, "Vérification impossible, car MyDNS.JP était injoignable."),
+    (MessageKey::DoctorSummaryFmt, "\nÉchecs : {fail}, Avertissements : {warn}"),
+
+    // history.rs
+    (MessageKey::HistoryTitle, "--- Historique des notifications ---"),
+    (MessageKey::HistoryEmpty, "Aucun historique enregistré."),
+    (MessageKey::HistoryRowFmt, "{time}  {id}  {proto}  {ms} ms  {result}"),
+
+    // ipc.rs
+    (MessageKey::IpcServiceUnavailable, "Impossible de se connecter au service. Vérifiez que le service est en cours d'exécution."),
+    (MessageKey::IpcServiceBusy, "Le service traite d'autres requêtes. Veuillez réessayer plus tard."),
+    (MessageKey::IpcServerNotServiceFmt, "La requête n'a pas été envoyée, car le serveur du canal nommé (PID {pid}) n'est pas le processus du service."),
+    (MessageKey::IpcInvalidResponse, "Impossible d'interpréter la réponse du service."),
+    (MessageKey::IpcRequestFailedFmt, "Le service n'a pas pu traiter la requête : {error}"),
+    (MessageKey::IpcNotifyNowSuccess, "[Succès] Notification immédiate demandée au service."),
+    (MessageKey::IpcReloadSuccessFmt, "[Succès] Le service a rechargé les paramètres des comptes ({count} comptes)."),
+    (MessageKey::IpcStatusTitle, "--- État du service ---"),
+    (MessageKey::IpcStatusVersion, "Version"),
+    (MessageKey::IpcStatusStarted, "Démarré"),
+    (MessageKey::IpcStatusAccounts, "Comptes"),
+    (MessageKey::IpcStatusPaused, "Suspendu"),
+    (MessageKey::IpcStatusRoundRunning, "Notification en cours"),
+    (MessageKey::IpcStatusNextRound, "Prochaine notification périodique"),
+
+    // schtask.rs
+    (MessageKey::AdminRequiredTask, "L'enregistrement ou la suppression de la tâche planifiée nécessite des privilèges d'administrateur. Exécutez en tant qu'administrateur."),
+    (MessageKey::TaskServiceInstalledWarning, "[Avertissement] Le service est également installé. N'utilisez que l'un des deux pour éviter les notifications en double."),
+    (MessageKey::TaskInstalledFmt, "La tâche planifiée '{name}' a été enregistrée. Elle notifie au démarrage, à la connexion au réseau et toutes les {minutes} minutes."),
+    (MessageKey::TaskUninstalledFmt, "La tâche planifiée '{name}' a été supprimée."),
+    (MessageKey::TaskNotInstalledFmt, "La tâche planifiée '{name}' n'est pas enregistrée."),
+    (MessageKey::TaskDescription, "Notifie MyDNS.JP de l'adresse IP de cet ordinateur (MyDNS.JP Adapter)."),
+
+    // crash.rs
+    (MessageKey::PanicOccurredFmt, "Une erreur inattendue s'est produite. Les détails ont été écrits dans le journal et dans le journal des événements : {summary}"),
+
+    // stats.rs
+    (MessageKey::StatsTitle, "--- Statistiques d'utilisation ---"),
+    (MessageKey::StatsEnabled, "[Succès] Statistiques d'utilisation activées. Elles ne quittent jamais cet ordinateur."),
+    (MessageKey::StatsDisabled, "[Succès] Statistiques d'utilisation désactivées."),
+    (MessageKey::StatsNotEnabled, "Les statistiques d'utilisation sont désactivées. Exécutez --stats --opt-in pour les activer."),
+    (MessageKey::StatsEmpty, "Aucune statistique n'a été enregistrée."),
+    (MessageKey::StatsMonthFmt, "{month} : {total} notifications ({ok} réussies / {ng} en échec),  {ip} changements d'IP,  {hours} h de fonctionnement"),
+    (MessageKey::StatsYearFmt, "\nEn {year} : {total} notifications, votre IP a changé {ip} fois."),
+];
@@ -0,0 +1,367 @@
+//! 韓国語のメッセージ。
+//!
+//! ログと開発者向けの機能（`--simulate`、`--bench`）のメッセージは翻訳せず、英語のメッセージを使います。
+
+use super::MessageKey;
+
+/// キーと韓国語のメッセージの組。
+#[rustfmt::skip]
+pub(super) static MESSAGES: &[(MessageKey, &str)] = &[
+    // main.rs
+    (MessageKey::LangInvalid, "지원하지 않는 언어입니다. ja, en, zh, ko, de, fr, es 중 하나를 지정하십시오."),
+    (MessageKey::ConfigTitle, "--- MyDNS Adapter 설정 ---"),
+    (MessageKey::ConfigLoaded, "\n현재 설정을 불러왔습니다. 변경하지 않을 항목은 Enter 키를 누르십시오."),
+    (MessageKey::MasterIdPrompt, "MasterID"),
+    (MessageKey::PasswordPrompt, "비밀번호"),
+    (MessageKey::Ipv4NotifyPrompt, "IPv4 알림을 사용하시겠습니까?"),
+    (MessageKey::Ipv6NotifyPrompt, "IPv6 알림을 사용하시겠습니까?"),
+    (MessageKey::KeepAlivePrompt, "강제 알림 간격(시간, 0이면 사용 안 함)"),
+    (MessageKey::NumberInvalid, "0 이상의 정수를 입력하십시오."),
+    (MessageKey::RegistrySaveSuccess, "\n[성공] 설정을 레지스트리에 저장했습니다."),
+    (MessageKey::RegistrySaveFailFmt, "\n[실패] 레지스트리 저장 오류: {error}"),
+    (MessageKey::InputPromptPwFmt, "{item}을(를) 입력하십시오 (현재 값: {current}, 변경하지 않으려면 Enter): "),
+    (MessageKey::InputPromptFmt, "{item}을(를) 입력하십시오 (현재 값: {current}): "),
+    (MessageKey::InputPromptNewFmt, "{item}을(를) 입력하십시오: "),
+    (MessageKey::NotSet, "(설정 안 됨)"),
+    (MessageKey::YesNoPromptFmt, "{question} (현재 값: {current}) {hint}: "),
+    (MessageKey::YesNoHintTrue, "(Y/n)"),
+    (MessageKey::YesNoHintFalse, "(y/N)"),
+    (MessageKey::YesNoInvalid, "'y' 또는 'n'(\"예\" 또는 \"아니요\")을 입력하거나 Enter 키를 누르십시오."),
+    (MessageKey::YesNoAffirmativeWords, "y,yes,예,네"),
+    (MessageKey::YesNoNegativeWords, "n,no,아니요,아니오"),
+    (MessageKey::ViewTitle, "--- 현재 MyDNS 설정 ---"),
+    (MessageKey::ViewMasterIdFmt, "MasterID: {id}"),
+    (MessageKey::ViewPasswordFmt, "비밀번호: {password}"),
+    (MessageKey::ViewIpv4Fmt, "IPv4 알림: {value}"),
+    (MessageKey::ViewIpv6Fmt, "IPv6 알림: {value}"),
+    (MessageKey::Yes, "예"),
+    (MessageKey::No, "아니요"),
+    (MessageKey::ViewNoAccounts, "설정된 계정이 없습니다."),
+    (MessageKey::ViewListFmt, "MasterID: {id},  비밀번호: {pw},  IPv4: {v4},  IPv6: {v6}"),
+    (MessageKey::AddTitle, "--- 새 계정 추가 ---"),
+    (MessageKey::EditTitle, "--- 계정 편집 ---"),
+    (MessageKey::RemoveTitle, "--- 계정 삭제 ---"),
+    (MessageKey::AccountExistsFmt, "계정 '{id}'이(가) 이미 있습니다."),
+    (MessageKey::AccountNotFoundFmt, "계정 '{id}'을(를) 찾을 수 없습니다."),
+    (MessageKey::SelectAccountPrompt, "편집할 계정을 선택하십시오:"),
+    (MessageKey::SelectAccountIndexPrompt, "번호 또는 MasterID를 입력하십시오: "),
+    (MessageKey::InvalidSelection, "잘못된 선택입니다."),
+    (MessageKey::RemoveNeedsYes, "표준 입력이 터미널이 아니므로 삭제를 확인할 수 없습니다. 확인 없이 삭제하려면 --yes를 사용하십시오."),
+    (MessageKey::ConfirmRemoveFmt, "계정 '{id}'을(를) 삭제하시겠습니까?"),
+    (MessageKey::ConfirmPromptFmt, "{question} {hint}: "),
+    (MessageKey::RemoveSuccess, "[성공] 계정을 삭제했습니다."),
+    (MessageKey::RemoveFailFmt, "[실패] 계정을 삭제하지 못했습니다: {error}"),
+    (MessageKey::BatchRowErrorFmt, "{line}번째 줄: {error}"),
+    (MessageKey::BatchInvalidFmt, "{count}개 줄에 오류가 있어 계정을 추가하지 않았습니다."),
+    (MessageKey::BatchEmpty, "추가할 계정이 없습니다."),
+    (MessageKey::BatchSuccessFmt, "[성공] 계정 {count}개를 추가했습니다."),
+    (MessageKey::BatchColumnCountFmt, "열 개수가 올바르지 않습니다({count}). \"MasterID,비밀번호,IPv4,IPv6\" 형식을 사용하십시오."),
+    (MessageKey::BatchInvalidFlagFmt, "{name} 값이 올바르지 않습니다: {value} (yes 또는 no를 사용하십시오)"),
+    (MessageKey::BatchUnterminatedQuote, "따옴표로 묶은 열이 닫히지 않았습니다."),
+    (MessageKey::RenameSuccessFmt, "[성공] 계정 '{old}'의 이름을 '{new}'(으)로 변경했습니다."),
+    (MessageKey::AccountEnabledFmt, "[성공] 계정 '{id}'의 통지를 재개했습니다."),
+    (MessageKey::AccountNotSuspendedFmt, "계정 '{id}'의 통지는 중지되어 있지 않습니다."),
+    (MessageKey::DryRunTitle, "[드라이 런] 다음 요청을 보냅니다 (실제로는 보내지 않습니다):"),
+    (MessageKey::DryRunRequestFmt, "  {id} {proto}: {method} {url}  ({auth})"),
+    (MessageKey::DryRunBasicAuthFmt, "Basic 인증: {user} / ********"),
+    (MessageKey::DryRunLoginAuthFmt, "로그인: {user} / ******** (평문)"),
+    (MessageKey::DryRunNoAuth, "인증 없음"),
+    (MessageKey::DryRunBindFmt, "    송신 주소: {addr}"),
+    (MessageKey::DryRunSkippedFmt, "  {id} {proto}: 보내지 않습니다. {reason}"),
+    (MessageKey::DryRunSuspended, "인증 오류가 반복되어 통지가 중지되었습니다 (--force로 보냅니다)."),
+    (MessageKey::DryRunPrivateIpv4, "현재 IPv4 주소는 인터넷에서 도달할 수 없으므로 통지를 건너뜁니다."),
+    (MessageKey::DryRunNone, "보낼 요청이 없습니다."),
+    (MessageKey::NotifySummaryTitle, "통지 결과:"),
+    (MessageKey::NotifySummaryRowFmt, "  {id}  {proto}  {result}  {ms} ms ({attempts}회){detail}"),
+    (MessageKey::NotifySummarySkippedFmt, "  {id}  -     {reason}"),
+    (MessageKey::NotifySummarySuspended, "인증 오류가 반복되어 중지됨 (보내지 않음)"),
+    (MessageKey::NotifySummaryInvalidProxy, "프록시 URL이 올바르지 않음 (보내지 않음)"),
+    (MessageKey::NotifySummaryAborted, "통지 작업이 비정상 종료되었습니다"),
+    (MessageKey::NotifySummaryNoProtocol, "통지할 프로토콜이 없습니다"),
+    (MessageKey::NotifySummaryTotalFmt, "성공 {ok}건, 실패 {failed}건"),
+    (MessageKey::ElevationConfirm, "이 작업에는 관리자 권한이 필요합니다. 관리자로 다시 실행하시겠습니까?"),
+    (MessageKey::ElevationRequired, "이 작업에는 관리자 권한이 필요합니다. 관리자 권한 명령 프롬프트에서 실행하십시오."),
+    (MessageKey::ElevationCancelled, "관리자로 실행하는 것이 취소되었습니다."),
+    (MessageKey::ElevatedPressEnter, "\nEnter 키를 누르면 이 창이 닫힙니다..."),
+    (MessageKey::ServicePasswordPromptFmt, "계정 '{account}'의 비밀번호"),
+    (MessageKey::ServicePasswordNotNeededFmt, "계정 '{account}'에는 비밀번호를 지정할 수 없습니다."),
+    (MessageKey::ConfirmPurge, "저장된 모든 계정, 설정 및 로그 파일이 삭제됩니다. 이 작업은 되돌릴 수 없습니다. 계속하시겠습니까?"),
+    (MessageKey::PurgeNeedsYes, "표준 입력이 터미널이 아니므로 완전 삭제를 확인할 수 없습니다. 확인 없이 삭제하려면 --yes를 사용하십시오."),
+    (MessageKey::PurgeSuccess, "[성공] 저장된 계정, 설정 및 로그 파일을 삭제했습니다."),
+    (MessageKey::AddSuccess, "[성공] 계정을 추가했습니다."),
+    (MessageKey::NoAccountsAddPrompt, "계정이 없습니다. 새로 만드시겠습니까?"),
+    (MessageKey::OperationCancelled, "작업이 취소되었습니다."),
+    (MessageKey::EditTargetFmt, "대상 계정: {id}"),
+    (MessageKey::ViewRuntimeFmt, "    상태: {state},  마지막 시도: {time},  결과: {result}"),
+    (MessageKey::ViewRuntimeInFlight, "알림 중"),
+    (MessageKey::ViewRuntimeIdle, "대기 중"),
+    (MessageKey::ViewPaused, "\n[참고] 정기 알림이 일시 중지되었습니다. 다시 시작하려면 --resume을 사용하십시오."),
+    (MessageKey::PauseSuccess, "[성공] 정기 알림을 일시 중지했습니다."),
+    (MessageKey::ResumeSuccess, "[성공] 정기 알림을 다시 시작했습니다."),
+    (MessageKey::ViewIntervalFmt, "알림 간격: {minutes}분"),
+    (MessageKey::IntervalSetFmt, "알림 간격을 {minutes}분으로 설정했습니다. 실행 중인 서비스는 다음 정기 알림 후에 적용합니다."),
+    (MessageKey::IntervalOutOfRangeFmt, "알림 간격은 {min}분에서 {max}분 사이여야 합니다."),
+    (MessageKey::ViewRetryFmt, "최대 알림 시도 횟수: {count}"),
+    (MessageKey::RetrySetFmt, "최대 알림 시도 횟수를 {count}(으)로 설정했습니다."),
+    (MessageKey::RetryOutOfRangeFmt, "최대 알림 시도 횟수는 {min}에서 {max} 사이여야 합니다."),
+    (MessageKey::ViewConcurrencyFmt, "동시에 통지하는 계정 수: {count}"),
+    (MessageKey::ConcurrencySetFmt, "동시에 통지하는 계정 수를 {count}(으)로 설정했습니다."),
+    (MessageKey::ConcurrencyOutOfRangeFmt, "동시에 통지하는 계정 수는 {min}에서 {max} 사이로 지정하십시오."),
+    (MessageKey::ViewRequestTimeoutFmt, "통지 요청 시간 제한: {seconds}초"),
+    (MessageKey::RequestTimeoutSetFmt, "통지 요청 시간 제한을 {seconds}초로 설정했습니다."),
+    (MessageKey::RequestTimeoutOutOfRangeFmt, "통지 요청 시간 제한은 {min}초에서 {max}초 사이로 지정하십시오."),
+    (MessageKey::Ipv4UrlPrompt, "IPv4 알림 URL"),
+    (MessageKey::Ipv6UrlPrompt, "IPv6 알림 URL"),
+    (MessageKey::EndpointUrlInvalidFmt, "\"{url}\"은(는) 올바른 URL이 아닙니다. 현재 값을 유지합니다."),
+    (MessageKey::ViewEndpointFmt, "  알림 URL ({proto}): {url}"),
+    (MessageKey::HostnamePrompt, "DNS에서 확인할 호스트 이름 (비워 두면 건너뜀, -: 지우기)"),
+    (MessageKey::SetHostnameInvalidFmt, "\"{host}\"은(는) 올바른 호스트 이름이 아닙니다."),
+    (MessageKey::SetEndpointUrlInvalidFmt, "\"{url}\"은(는) 올바른 URL이 아닙니다."),
+    (MessageKey::HostnameInvalidFmt, "\"{host}\"은(는) 올바른 호스트 이름이 아닙니다. 현재 값을 유지합니다."),
+    (MessageKey::ViewLastSuccessFmt, "  마지막 성공: {time}  (IPv4: {v4},  IPv6: {v6})"),
+    (MessageKey::ViewHostnameFmt, "  확인할 호스트 이름: {host}"),
+    (MessageKey::ProviderPrompt, "업데이트 서비스 (mydns: MyDNS.JP, generic: 업데이트 URL을 지정하는 범용 서비스)"),
+    (MessageKey::ProviderInvalid, "서비스는 mydns 또는 generic으로 지정하십시오."),
+    (MessageKey::UsernamePrompt, "사용자 이름 (계정 이름으로도 사용됩니다)"),
+    (MessageKey::GenericIpv4UrlPrompt, "IPv4 업데이트 URL ({ip}와 {host}가 바뀝니다)"),
+    (MessageKey::GenericIpv6UrlPrompt, "IPv6 업데이트 URL (빈칸: IPv4와 같음, -: 해제)"),
+    (MessageKey::GenericUrlRequired, "범용 서비스에는 IPv4 업데이트 URL이 필요합니다."),
+    (MessageKey::ViewProviderGeneric, "  서비스: 범용 (업데이트 URL)"),
+    (MessageKey::MethodPrompt, "통지 방법 (http: HTTPS, pop3/imap/ftp: 서버 로그인)"),
+    (MessageKey::MethodInvalid, "통지 방법은 http, pop3, imap, ftp 중 하나를 지정하십시오."),
+    (MessageKey::MethodRequiresMyDns, "POP3/IMAP/FTP 로그인에 의한 통지는 MyDNS.JP 계정에서만 사용할 수 있습니다."),
+    (MessageKey::MethodPlaintextWarning, "주의: POP3/IMAP/FTP 로그인에서는 비밀번호가 암호화되지 않고 전송됩니다."),
+    (MessageKey::ViewMethodFmt, "  통지 방법: {method} (로그인)"),
+    (MessageKey::ViewLogPathFmt, "로그 파일: {path}"),
+    (MessageKey::ViewLogArchiveFmt, "로그 보관: {count}개 유지 (압축: {compress})"),
+    (MessageKey::ViewProxyFmt, "프록시: {proxy}"),
+    (MessageKey::ProxyAuto, "자동 (WinHTTP/시스템 설정)"),
+    (MessageKey::ViewMetricsFmt, "메트릭: http://127.0.0.1:{port}/metrics"),
+    (MessageKey::MetricsSetFmt, "메트릭을 http://127.0.0.1:{port}/metrics 에서 제공하도록 설정했습니다. 실행 중인 서비스에는 다시 시작한 후에 반영됩니다."),
+    (MessageKey::MetricsDisabled, "메트릭 제공을 사용하지 않도록 설정했습니다. 실행 중인 서비스에는 다시 시작한 후에 반영됩니다."),
+    (MessageKey::ViewAdminApiFmt, "관리 API: http://127.0.0.1:{port}/api/"),
+    (MessageKey::AdminApiSetFmt, "관리 API를 http://127.0.0.1:{port}/api/ 에서 제공하도록 설정했습니다. 실행 중인 서비스에는 다시 시작한 후에 반영됩니다.\n요청에는 \"Authorization: Bearer {token}\" 헤더를 붙이십시오."),
+    (MessageKey::AdminApiDisabled, "관리 API 제공을 사용하지 않도록 설정했습니다. 실행 중인 서비스에는 다시 시작한 후에 반영됩니다."),
+    (MessageKey::PingUrlPrompt, "업데이트에 성공한 후 요청할 모니터링 ping URL (공백: 없음, -: 해제)"),
+    (MessageKey::ViewAccountPingUrlFmt, "  ping URL: {url}"),
+    (MessageKey::InterfacePrompt, "알림을 보낼 네트워크 인터페이스 이름 또는 로컬 주소 (비우면 지정 안 함, -: 해제)"),
+    (MessageKey::ViewInterfaceFmt, "  보내는 곳: {interface}"),
+    (MessageKey::SchedulePrompt, "간격 대신 사용할 통지 일정 (예: */10 * * * *, at 04:05 daily. 비워 두면 간격, -: 해제)"),
+    (MessageKey::ScheduleInvalidFmt, "일정을 해석할 수 없습니다 (cron의 5개 필드 또는 at HH:MM daily 형식으로 지정하세요): {schedule}"),
+    (MessageKey::ViewScheduleFmt, "  일정: {schedule}"),
+    (MessageKey::ViewPingUrlFmt, "모니터링 ping URL: {url}"),
+    (MessageKey::PingUrlSetFmt, "모든 계정의 알림이 성공한 라운드마다 \"{url}\"(으)로 ping을 보내도록 설정했습니다."),
+    (MessageKey::PingUrlCleared, "모니터링 ping URL 설정을 해제했습니다."),
+    (MessageKey::ViewWebhookUrlFmt, "Webhook: {url}"),
+    (MessageKey::WebhookUrlSetFmt, "알림 실패, 복구, 주소 변경을 \"{url}\"(으)로 알리도록 설정했습니다."),
+    (MessageKey::WebhookUrlCleared, "Webhook URL 설정을 해제했습니다."),
+    (MessageKey::ViewToastFmt, "토스트 알림: {count}회 연속 실패 시"),
+    (MessageKey::ToastSetFmt, "알림이 {count}회 연속 실패하면 토스트 알림으로 알리도록 설정했습니다."),
+    (MessageKey::ToastDisabled, "토스트 알림을 비활성화했습니다."),
+    (MessageKey::ViewMailFmt, "메일 알림: {to} ({server}:{port} 경유, {minutes}분 이상 실패 시)"),
+    (MessageKey::MailSettingsSaved, "메일 알림 설정을 저장했습니다. --mail-test로 테스트 메일을 보낼 수 있습니다."),
+    (MessageKey::MailDisabled, "메일 알림을 비활성화했습니다."),
+    (MessageKey::MailNotConfigured, "메일 알림을 사용하려면 --smtp-server와 --mail-to를 모두 설정하십시오."),
+    (MessageKey::SmtpServerInvalidFmt, "\"{server}\"은(는) 올바른 SMTP 서버가 아닙니다. HOST 또는 HOST:PORT 형식으로 지정하십시오."),
+    (MessageKey::MailAddressInvalidFmt, "\"{address}\"은(는) 올바른 메일 주소가 아닙니다."),
+    (MessageKey::MailTestSentFmt, "[성공] 테스트 메일을 {to}(으)로 보냈습니다."),
+    (MessageKey::MailTestFailedFmt, "테스트 메일을 보낼 수 없습니다: {error}"),
+    (MessageKey::ViewCheckIpv4UrlsFmt, "IPv4 주소 확인 서비스: {urls}"),
+    (MessageKey::ViewCheckIpv6UrlsFmt, "IPv6 주소 확인 서비스: {urls}"),
+    (MessageKey::ViewCheckIpPollFmt, "글로벌 주소 확인: {minutes}분마다"),
+    (MessageKey::CheckIpSettingsSaved, "주소 확인 서비스 설정을 저장했습니다."),
+    (MessageKey::ViewSkipPrivateIpv4, "CGNAT/사설 IPv4 주소: 알리지 않음"),
+    (MessageKey::SkipPrivateIpv4Enabled, "확인한 IPv4 주소가 CGNAT 또는 사설 주소이면 알림을 건너뜁니다."),
+    (MessageKey::SkipPrivateIpv4Disabled, "CGNAT 및 사설 IPv4 주소도 로그에 경고를 남기고 알립니다."),
+    (MessageKey::ViewDnsCheckFmt, "DNS 자체 확인: {minutes}분마다"),
+    (MessageKey::DnsCheckSetFmt, "호스트 이름의 DNS 레코드를 {minutes}분마다 공개 리졸버로 확인합니다."),
+    (MessageKey::DnsCheckDisabled, "DNS 자체 확인을 비활성화했습니다."),
+    (MessageKey::ViewMeteredFmt, "데이터 통신 연결: {policy}"),
+    (MessageKey::MeteredPolicyInvalid, "데이터 통신 연결 정책은 ignore, reduce, skip 중 하나여야 합니다."),
+    (MessageKey::MeteredIgnore, "데이터 통신 연결에서도 평소처럼 통지합니다."),
+    (MessageKey::MeteredReduce, "데이터 통신 연결에서는 정기 통지를 1시간에 1회까지로 줄입니다."),
+    (MessageKey::MeteredSkip, "데이터 통신 연결에서는 서비스의 자동 통지를 건너뜁니다."),
+    (MessageKey::ViewAllowedNetworksFmt, "허용된 네트워크: {networks}"),
+    (MessageKey::AllowedNetworksSetFmt, "서비스는 다음 네트워크에 연결되어 있을 때만 통지합니다: {networks}"),
+    (MessageKey::AllowedNetworksCleared, "허용된 네트워크 제한을 해제했습니다."),
+    (MessageKey::ConnectedNetworksFmt, "현재 연결된 네트워크: {networks}"),
+    (MessageKey::ViewQuietHoursFmt, "알림 중지 시간대: {hours}"),
+    (MessageKey::QuietHoursInvalidFmt, "시간대는 23:00-06:00처럼 HH:MM-HH:MM 형식이어야 하며, 시작과 종료 시각이 달라야 합니다: {hours}"),
+    (MessageKey::QuietHoursSetFmt, "서비스는 매일 {hours} 동안 자동 통지를 보류합니다."),
+    (MessageKey::QuietHoursCleared, "알림 중지 시간대를 해제했습니다."),
+    (MessageKey::ViewJitterFmt, "통지 시각 분산: ±{seconds}초"),
+    (MessageKey::JitterSetFmt, "서비스는 통지 시각을 최대 ±{seconds}초 범위에서 무작위로 조정합니다 (정기 통지 간격의 절반까지)."),
+    (MessageKey::JitterDisabled, "통지 시각 분산을 비활성화했습니다."),
+    (MessageKey::ViewUserAgentFmt, "User-Agent: {ua}"),
+    (MessageKey::UserAgentIdSetFmt, "User-Agent에 식별자를 추가했습니다: {ua}"),
+    (MessageKey::UserAgentIdCleared, "User-Agent의 식별자를 해제했습니다."),
+    (MessageKey::UserAgentIdInvalidFmt, "User-Agent의 식별자는 {max}자 이내의 출력 가능한 ASCII 문자만 지정하십시오."),
+    (MessageKey::ViewHistoryDatabase, "기록 데이터베이스: 기록함"),
+    (MessageKey::HistoryDbEnabled, "알림 기록을 데이터베이스에도 기록합니다."),
+    (MessageKey::HistoryDbDisabled, "알림 기록을 데이터베이스에 기록하지 않습니다. 이미 기록된 내용은 유지됩니다."),
+    (MessageKey::HistoryDbUnsupported, "이 빌드는 기록 데이터베이스를 지원하지 않습니다. `--features sqlite`를 지정하여 빌드하십시오."),
+    (MessageKey::StatusHistorySummaryFmt, "  최근 24시간: 시도 {attempts}회, 성공 {successes}회, 평균 {ms} ms"),
+    (MessageKey::ToastTitle, "MyDNS Adapter: 알림 실패"),
+    (MessageKey::ToastFailuresFmt, "계정 {id}의 알림이 {count}회 연속 실패했습니다. {error}"),
+    (MessageKey::ToastExpiryTitle, "MyDNS Adapter: 레코드 만료 임박"),
+    (MessageKey::ToastExpiryFmt, "계정 {id}이(가) {hours}시간 동안 알림에 성공하지 못했습니다. 약 {remaining}시간 후에 MyDNS.JP 레코드가 만료됩니다."),
+    (MessageKey::ToastMonitorStarted, "--- 알림 실패를 감시하고 있습니다 (Ctrl+C로 종료) ---"),
+    (MessageKey::ProxySetFmt, "알림 프록시를 \"{url}\"(으)로 설정했습니다."),
+    (MessageKey::ProxyCleared, "프록시 설정을 지웠습니다. WinHTTP/시스템 설정을 사용합니다."),
+    (MessageKey::ProxyPrompt, "프록시 URL (비워 두면 전체 설정, direct: 프록시 사용 안 함, -: 지우기)"),
+    (MessageKey::ExportSuccessFmt, "[성공] 계정 {count}개를 {path}(으)로 내보냈습니다."),
+    (MessageKey::ExportPasswordWarning, "[경고] 파일에 비밀번호가 평문으로 포함되어 있습니다. 취급에 주의하십시오. (--redact-passwords로 제외할 수 있습니다.)"),
+    (MessageKey::ImportTitle, "--- 설정 가져오기 ---"),
+    (MessageKey::ImportParseErrorFmt, "파일을 해석할 수 없습니다: {error}"),
+    (MessageKey::ImportUnsupportedVersionFmt, "지원하지 않는 파일 형식 버전입니다: {version}"),
+    (MessageKey::ImportDuplicateFmt, "MasterID {id}이(가) 여러 번 나옵니다."),
+    (MessageKey::ImportUnknownFieldFmt, "알 수 없는 필드: {field}"),
+    (MessageKey::ImportMissingFieldFmt, "필수 필드가 없습니다: {field}"),
+    (MessageKey::ImportInvalidTypeFmt, "필드 값의 형식이 올바르지 않습니다: {field}"),
+    (MessageKey::ImportMissingPasswordFmt, "새 계정 {id}에는 비밀번호가 필요합니다."),
+    (MessageKey::ImportDiffNewFmt, "+ {id} (새 계정)"),
+    (MessageKey::ImportDiffChangedFmt, "~ {id} (변경됨)"),
+    (MessageKey::ImportDiffUnchangedFmt, "= {id} (변경 없음)"),
+    (MessageKey::ImportNothingToDo, "적용할 변경 사항이 없습니다."),
+    (MessageKey::ImportConfirmFmt, "계정 변경 {count}건을 적용하시겠습니까?"),
+    (MessageKey::ImportSuccessFmt, "[성공] 계정 변경 {count}건을 적용했습니다."),
+    (MessageKey::UserScopeServiceConflict, "서비스는 사용자별 설정을 읽지 않으므로 --user는 서비스 명령과 함께 사용할 수 없습니다."),
+    (MessageKey::StatusTitle, "--- MyDNS Adapter 상태 ---"),
+    (MessageKey::StatusUptimeFmt, "가동 시간: {days}일 {hours}시간 {minutes}분 (시작: {since})"),
+    (MessageKey::ViewLastErrorFmt, "  마지막 오류: {time}  [{kind}] {detail}"),
+    (MessageKey::ViewSuspendedFmt, "  통지 중지됨: {time}부터 (인증 오류 반복). `account enable {id}`(으)로 재개합니다."),
+    (MessageKey::StatusLastIpFmt, "  마지막으로 알린 주소: IPv4 {v4},  IPv6 {v6}"),
+    (MessageKey::StatusAccountFmt, "{id}: 마지막 알림: {time},  결과: {result},  마지막 성공: {success}"),
+    (MessageKey::UserScopeFallback, "[정보] 관리자 권한이 없으므로 사용자별 설정(HKCU)을 사용합니다. 서비스는 이 설정을 읽지 않습니다."),
+    (MessageKey::ViewUserScope, "(HKCU의 사용자별 설정을 표시합니다)"),
+    (MessageKey::ViewConfigFileFmt, "(설정 파일 {path}의 계정을 표시하고 있습니다)"),
+    (MessageKey::ConfigFileInvalidFmt, "설정 파일 {path}을(를) 읽을 수 없습니다: {error}"),
+    (MessageKey::PasswordFromEnvFmt, "환경 변수 {var}의 비밀번호를 사용합니다."),
+    (MessageKey::KickSuccess, "[성공] 서비스에 즉시 알림을 요청했습니다."),
+    (MessageKey::UpgradeTitle, "--- 설정 업그레이드 ---"),
+    (MessageKey::UpgradeAccountFmt, "계정 '{id}':"),
+    (MessageKey::UpgradeNoChanges, "변경 사항이 없습니다."),
+    (MessageKey::UpgradeAddedDefaultFmt, "{name}: 없어서 기본값 {value}을(를) 기록했습니다."),
+    (MessageKey::UpgradeMigratedTypeFmt, "{name}: 문자열에서 숫자 {value}(으)로 변환했습니다."),
+    (MessageKey::UpgradeEmptyPassword, "[경고] 비밀번호가 설정되지 않았습니다. 'account edit'로 설정하십시오."),
+    (MessageKey::InvalidMasterIdPrefix, "MasterID는 'mydns'로 시작해야 합니다."),
+
+    // winservice.rs
+    (MessageKey::AdminRequiredInstall, "서비스를 설치하려면 관리자 권한이 필요합니다. 관리자로 실행하십시오."),
+    (MessageKey::ServiceInstallingFmt, "서비스 '{name}'을(를) 설치하는 중..."),
+    (MessageKey::ServiceInstalledFmt, "서비스 '{name}'을(를) 설치하고 시작했습니다."),
+    (MessageKey::AdminRequiredUninstall, "서비스를 제거하려면 관리자 권한이 필요합니다. 관리자로 실행하십시오."),
+    (MessageKey::ServiceNotInstalledFmt, "서비스 '{name}'이(가) 설치되어 있지 않습니다."),
+    (MessageKey::ServiceStoppingFmt, "서비스 '{name}'을(를) 중지하는 중..."),
+    (MessageKey::ServiceStopped, "서비스를 중지했습니다."),
+    (MessageKey::ServiceWaitingStop, "서비스가 중지되기를 기다리는 중..."),
+    (MessageKey::ServiceNotRunning, "서비스가 실행 중이 아닙니다."),
+    (MessageKey::ServiceUninstalledFmt, "서비스 '{name}'을(를) 제거했습니다."),
+    (MessageKey::AdminRequiredRestart, "서비스를 다시 시작하려면 관리자 권한이 필요합니다. 관리자로 실행하십시오."),
+    (MessageKey::ServiceStatusFmt, "서비스 '{name}': {state}"),
+    (MessageKey::ServiceStateRunning, "실행 중"),
+    (MessageKey::ServiceStateStopped, "중지됨"),
+    (MessageKey::ServiceStateStartPending, "시작 중"),
+    (MessageKey::ServiceStateStopPending, "중지 중"),
+    (MessageKey::ServiceStatePaused, "일시 중지됨"),
+    (MessageKey::ServiceStatePausePending, "일시 중지 중"),
+    (MessageKey::ServiceStateContinuePending, "다시 시작 중"),
+    (MessageKey::ServiceStateUnknown, "알 수 없음"),
+    (MessageKey::ServiceDescription, "이 컴퓨터의 IPv4/IPv6 주소를 MyDNS.JP에 정기적으로 알려 동적 DNS 레코드를 최신 상태로 유지합니다."),
+    (MessageKey::ServiceLogonFailedFmt, "서비스는 설치되었지만 '{account}'(으)로 로그온하지 못해 시작할 수 없습니다. 비밀번호와 \"서비스로 로그온\" 권한을 확인한 후 서비스를 다시 시작하십시오."),
+    (MessageKey::ServiceKeyAccessGrantedFmt, "계정 '{account}'에 {key} 읽기 및 쓰기 권한을 부여했습니다."),
+    (MessageKey::ServiceKeyAccessFailedFmt, "계정 '{account}'에 {key} 쓰기 권한을 부여하지 못했습니다: {error}. 서비스가 알림 결과와 자동 중지를 기록할 수 없습니다. regedit에서 직접 권한을 부여하십시오."),
+    (MessageKey::ServiceDataDirAccessGrantedFmt, "계정 '{account}'에 {path} 쓰기 권한을 부여했습니다."),
+    (MessageKey::ServiceDataDirAccessFailedFmt, "계정 '{account}'에 {path} 쓰기 권한을 부여하지 못했습니다: {error}. 서비스가 실행 상태와 알림 기록을 저장할 수 없습니다. 탐색기의 [보안] 탭에서 직접 권한을 부여하십시오."),
+    (MessageKey::DataDirShareFailedFmt, "실행 데이터 디렉터리 {path}에 대한 쓰기 권한을 사용자에게 부여하지 못했습니다: {error}. 관리자 권한이 없는 사용자는 --pause와 --kick을 사용할 수 없습니다."),
+    (MessageKey::AdminRequiredStart, "서비스를 시작하려면 관리자 권한이 필요합니다. 관리자로 실행하십시오."),
+    (MessageKey::AdminRequiredStop, "서비스를 중지하려면 관리자 권한이 필요합니다. 관리자로 실행하십시오."),
+    (MessageKey::ServiceStartingFmt, "서비스 '{name}'을(를) 시작하는 중..."),
+    (MessageKey::ServiceStarted, "서비스를 시작했습니다."),
+    (MessageKey::ServiceAlreadyRunning, "서비스가 이미 실행 중입니다."),
+    (MessageKey::ServiceStartFailedFmt, "서비스가 시작하는 도중에 중지되었습니다(종료 코드: {code}). 자세한 내용은 로그를 확인하십시오."),
+    (MessageKey::ServiceStoppedAfterStartFmt, "서비스가 시작 직후에 중지되었습니다(종료 코드: {code}). 자세한 내용은 로그를 확인하십시오."),
+    (MessageKey::ServiceStartTimeout, "서비스 시작을 기다리는 동안 시간이 초과되었습니다."),
+    (MessageKey::ForegroundStarted, "이 콘솔에서 서비스 루프를 실행합니다. 중지하려면 Ctrl+C를 누르십시오."),
+    (MessageKey::ForegroundStopped, "중지했습니다."),
+    (MessageKey::ForegroundServiceRunning, "[경고] 서비스가 실행 중입니다. 계정이 중복으로 알려질 수 있습니다."),
+    (MessageKey::ServicePanicked, "서비스 루프에서 패닉이 발생했습니다."),
+    (MessageKey::ServiceRestartedSuccessfully, "서비스를 다시 시작했습니다."),
+
+    // notify.rs
+    (MessageKey::NotifyKindNetwork, "네트워크 오류"),
+    (MessageKey::NotifyKindAuth, "인증 실패"),
+    (MessageKey::NotifyKindServer, "서버 오류"),
+    (MessageKey::NotifyKindRateLimited, "요청 과다"),
+    (MessageKey::NotifyKindHttp, "HTTP 오류"),
+    (MessageKey::NotifyKindSkipped, "건너뜀"),
+    (MessageKey::TestSendingFmt, "계정 '{id}'의 자격 증명을 확인하는 중 ({url})..."),
+    (MessageKey::TestOkFmt, "자격 증명이 올바릅니다. MyDNS.JP가 받은 주소: {addr}"),
+    (MessageKey::TestAuthFailedFmt, "인증에 실패했습니다. MasterID 또는 비밀번호가 올바르지 않습니다. ({detail})"),
+    (MessageKey::TestInconclusiveFmt, "자격 증명을 확인할 수 없습니다. {kind}: {detail}"),
+
+    // doctor.rs
+    (MessageKey::DoctorTitle, "--- 진단 ---"),
+    (MessageKey::DoctorHeaderFmt, "버전: {version},  실행 시각: {time},  설정 범위: {scope}"),
+    (MessageKey::DoctorItemElevation, "권한"),
+    (MessageKey::DoctorItemRegistry, "계정 설정"),
+    (MessageKey::DoctorItemSettings, "전체 설정"),
+    (MessageKey::DoctorItemRegistryWrite, "설정 쓰기 권한"),
+    (MessageKey::DoctorItemLog, "로그 파일"),
+    (MessageKey::DoctorItemIpv4, "IPv4 연결 (ipv4.mydns.jp)"),
+    (MessageKey::DoctorItemIpv6, "IPv6 연결 (ipv6.mydns.jp)"),
+    (MessageKey::DoctorItemService, "서비스"),
+    (MessageKey::DoctorItemClock, "시계"),
+    (MessageKey::DoctorElevated, "관리자로 실행 중입니다."),
+    (MessageKey::DoctorNotElevated, "관리자로 실행 중이 아닙니다. 설정 변경과 서비스 작업을 할 수 없습니다."),
+    (MessageKey::DoctorAccountsFmt, "계정 {count}개"),
+    (MessageKey::DoctorReadable, "읽을 수 있습니다."),
+    (MessageKey::DoctorWritable, "쓸 수 있습니다."),
+    (MessageKey::DoctorRegistryReadOnly, "쓰기 권한이 없습니다."),
+    (MessageKey::DoctorReachableFmt, "HTTP {status} ({ms}밀리초)"),
+    (MessageKey::DoctorServiceNotInstalled, "설치되어 있지 않습니다."),
+    (MessageKey::DoctorClockSkewFmt, "MyDNS.JP 서버와의 시간 차이: {seconds}초"),
+    (MessageKey::DoctorClockUnknown, "MyDNS.JP에 연결할 수 없어 확인하지 못했습니다."),
+    (MessageKey::DoctorSummaryFmt, "\n실패: {fail}건, 경고: {warn}건"),
+
+    // history.rs
+    (MessageKey::HistoryTitle, "--- 알림 기록 ---"),
+    (MessageKey::HistoryEmpty, "기록이 없습니다."),
+    (MessageKey::HistoryRowFmt, "{time}  {id}  {proto}  {ms}밀리초  {result}"),
+
+    // ipc.rs
+    (MessageKey::IpcServiceUnavailable, "서비스에 연결할 수 없습니다. 서비스가 실행 중인지 확인하십시오."),
+    (MessageKey::IpcServiceBusy, "서비스가 다른 요청을 처리 중이라 연결할 수 없습니다. 잠시 후 다시 시도하십시오."),
+    (MessageKey::IpcServerNotServiceFmt, "명명된 파이프 서버(PID {pid})가 서비스 프로세스가 아니므로 요청을 보내지 않았습니다."),
+    (MessageKey::IpcInvalidResponse, "서비스의 응답을 해석할 수 없습니다."),
+    (MessageKey::IpcRequestFailedFmt, "서비스가 요청을 처리하지 못했습니다: {error}"),
+    (MessageKey::IpcNotifyNowSuccess, "[성공] 서비스에 즉시 알림을 요청했습니다."),
+    (MessageKey::IpcReloadSuccessFmt, "[성공] 서비스가 계정 설정을 다시 불러왔습니다(계정 {count}개)."),
+    (MessageKey::IpcStatusTitle, "--- 서비스 상태 ---"),
+    (MessageKey::IpcStatusVersion, "버전"),
+    (MessageKey::IpcStatusStarted, "시작 시각"),
+    (MessageKey::IpcStatusAccounts, "계정 수"),
+    (MessageKey::IpcStatusPaused, "일시 중지됨"),
+    (MessageKey::IpcStatusRoundRunning, "알림 중"),
+    (MessageKey::IpcStatusNextRound, "다음 정기 알림"),
+
+    // schtask.rs
+    (MessageKey::AdminRequiredTask, "예약된 작업을 등록하거나 삭제하려면 관리자 권한이 필요합니다. 관리자로 실행하십시오."),
+    (MessageKey::TaskServiceInstalledWarning, "[경고] 서비스도 설치되어 있습니다. 중복 알림을 피하려면 둘 중 하나만 사용하십시오."),
+    (MessageKey::TaskInstalledFmt, "예약된 작업 '{name}'을(를) 등록했습니다. 시작할 때, 네트워크에 연결될 때, {minutes}분마다 알립니다."),
+    (MessageKey::TaskUninstalledFmt, "예약된 작업 '{name}'을(를) 삭제했습니다."),
+    (MessageKey::TaskNotInstalledFmt, "예약된 작업 '{name}'이(가) 등록되어 있지 않습니다."),
+    (MessageKey::TaskDescription, "이 컴퓨터의 IP 주소를 MyDNS.JP에 알립니다 (MyDNS.JP Adapter)."),
+
+    // crash.rs
+    (MessageKey::PanicOccurredFmt, "예기치 않은 오류가 발생했습니다. 자세한 내용을 로그와 이벤트 로그에 기록했습니다: {summary}"),
+
+    // stats.rs
+    (MessageKey::StatsTitle, "--- 사용 통계 ---"),
+    (MessageKey::StatsEnabled, "[성공] 사용 통계 기록을 켰습니다. 통계는 이 컴퓨터 밖으로 전송되지 않습니다."),
+    (MessageKey::StatsDisabled, "[성공] 사용 통계 기록을 껐습니다."),
+    (MessageKey::StatsNotEnabled, "사용 통계 기록이 꺼져 있습니다. 켜려면 --stats --opt-in을 실행하십시오."),
+    (MessageKey::StatsEmpty, "기록된 통계가 없습니다."),
+    (MessageKey::StatsMonthFmt, "{month}: 알림 {total}회 (성공 {ok} / 실패 {ng}),  IP 변경 {ip}회,  가동 {hours}시간"),
+    (MessageKey::StatsYearFmt, "\n{year}년에는 {total}회 알렸고, IP 주소가 {ip}회 바뀌었습니다."),
+];
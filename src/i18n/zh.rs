@@ -0,0 +1,367 @@
+//! 中国語（簡体字）のメッセージ。
+//!
+//! ログと開発者向けの機能（`--simulate`、`--bench`）のメッセージは翻訳せず、英語のメッセージを使います。
+
+use super::MessageKey;
+
+/// キーと中国語（簡体字）のメッセージの組。
+#[rustfmt::skip]
+pub(super) static MESSAGES: &[(MessageKey, &str)] = &[
+    // main.rs
+    (MessageKey::LangInvalid, "不支持的语言。请指定 ja、en、zh、ko、de、fr 或 es。"),
+    (MessageKey::ConfigTitle, "--- MyDNS Adapter 配置 ---"),
+    (MessageKey::ConfigLoaded, "\n已加载当前配置。按 Enter 键保留当前值。"),
+    (MessageKey::MasterIdPrompt, "MasterID"),
+    (MessageKey::PasswordPrompt, "密码"),
+    (MessageKey::Ipv4NotifyPrompt, "是否启用 IPv4 通知？"),
+    (MessageKey::Ipv6NotifyPrompt, "是否启用 IPv6 通知？"),
+    (MessageKey::KeepAlivePrompt, "强制通知间隔（小时，0 表示禁用）"),
+    (MessageKey::NumberInvalid, "请输入非负整数。"),
+    (MessageKey::RegistrySaveSuccess, "\n[成功] 配置已保存到注册表。"),
+    (MessageKey::RegistrySaveFailFmt, "\n[失败] 保存到注册表时出错: {error}"),
+    (MessageKey::InputPromptPwFmt, "请输入{item}（当前值: {current}，按 Enter 键保留）: "),
+    (MessageKey::InputPromptFmt, "请输入{item}（当前值: {current}）: "),
+    (MessageKey::InputPromptNewFmt, "请输入{item}: "),
+    (MessageKey::NotSet, "（未设置）"),
+    (MessageKey::YesNoPromptFmt, "{question}（当前值: {current}）{hint}: "),
+    (MessageKey::YesNoHintTrue, "(Y/n)"),
+    (MessageKey::YesNoHintFalse, "(y/N)"),
+    (MessageKey::YesNoInvalid, "请输入 'y' 或 'n'（“是”或“否”），或按 Enter 键。"),
+    (MessageKey::YesNoAffirmativeWords, "y,yes,是,好"),
+    (MessageKey::YesNoNegativeWords, "n,no,否,不"),
+    (MessageKey::ViewTitle, "--- 当前 MyDNS 设置 ---"),
+    (MessageKey::ViewMasterIdFmt, "MasterID: {id}"),
+    (MessageKey::ViewPasswordFmt, "密码: {password}"),
+    (MessageKey::ViewIpv4Fmt, "IPv4 通知: {value}"),
+    (MessageKey::ViewIpv6Fmt, "IPv6 通知: {value}"),
+    (MessageKey::Yes, "是"),
+    (MessageKey::No, "否"),
+    (MessageKey::ViewNoAccounts, "尚未配置任何账户。"),
+    (MessageKey::ViewListFmt, "MasterID: {id}，  密码: {pw}，  IPv4: {v4}，  IPv6: {v6}"),
+    (MessageKey::AddTitle, "--- 添加新账户 ---"),
+    (MessageKey::EditTitle, "--- 编辑账户 ---"),
+    (MessageKey::RemoveTitle, "--- 删除账户 ---"),
+    (MessageKey::AccountExistsFmt, "账户 '{id}' 已存在。"),
+    (MessageKey::AccountNotFoundFmt, "找不到账户 '{id}'。"),
+    (MessageKey::SelectAccountPrompt, "请选择要编辑的账户:"),
+    (MessageKey::SelectAccountIndexPrompt, "请输入编号或 MasterID: "),
+    (MessageKey::InvalidSelection, "选择无效。"),
+    (MessageKey::RemoveNeedsYes, "标准输入不是终端，无法确认删除。如需不经确认直接删除，请使用 --yes。"),
+    (MessageKey::ConfirmRemoveFmt, "确定要删除账户 '{id}' 吗？"),
+    (MessageKey::ConfirmPromptFmt, "{question} {hint}: "),
+    (MessageKey::RemoveSuccess, "[成功] 已删除账户。"),
+    (MessageKey::RemoveFailFmt, "[失败] 无法删除账户: {error}"),
+    (MessageKey::BatchRowErrorFmt, "第 {line} 行: {error}"),
+    (MessageKey::BatchInvalidFmt, "有 {count} 行存在错误，因此未添加任何账户。"),
+    (MessageKey::BatchEmpty, "没有要添加的账户。"),
+    (MessageKey::BatchSuccessFmt, "[成功] 已添加 {count} 个账户。"),
+    (MessageKey::BatchColumnCountFmt, "列数不正确（{count}）。请使用 \"MasterID,密码,IPv4,IPv6\" 格式。"),
+    (MessageKey::BatchInvalidFlagFmt, "{name} 的值无效: {value}（请使用 yes 或 no）"),
+    (MessageKey::BatchUnterminatedQuote, "带引号的列未闭合。"),
+    (MessageKey::RenameSuccessFmt, "[成功] 已将账户 '{old}' 重命名为 '{new}'。"),
+    (MessageKey::AccountEnabledFmt, "[成功] 已恢复账户 '{id}' 的通知。"),
+    (MessageKey::AccountNotSuspendedFmt, "账户 '{id}' 的通知未被暂停。"),
+    (MessageKey::DryRunTitle, "[试运行] 将发送以下请求（实际不会发送）："),
+    (MessageKey::DryRunRequestFmt, "  {id} {proto}：{method} {url}  （{auth}）"),
+    (MessageKey::DryRunBasicAuthFmt, "Basic 认证：{user} / ********"),
+    (MessageKey::DryRunLoginAuthFmt, "登录：{user} / ********（明文）"),
+    (MessageKey::DryRunNoAuth, "无凭据"),
+    (MessageKey::DryRunBindFmt, "    源地址：{addr}"),
+    (MessageKey::DryRunSkippedFmt, "  {id} {proto}：不发送。{reason}"),
+    (MessageKey::DryRunSuspended, "由于认证反复失败，通知已暂停（使用 --force 仍会发送）。"),
+    (MessageKey::DryRunPrivateIpv4, "当前 IPv4 地址无法从互联网访问，因此跳过更新。"),
+    (MessageKey::DryRunNone, "没有要发送的请求。"),
+    (MessageKey::NotifySummaryTitle, "通知结果："),
+    (MessageKey::NotifySummaryRowFmt, "  {id}  {proto}  {result}  {ms} 毫秒（{attempts} 次）{detail}"),
+    (MessageKey::NotifySummarySkippedFmt, "  {id}  -     {reason}"),
+    (MessageKey::NotifySummarySuspended, "因认证反复失败而暂停（未发送）"),
+    (MessageKey::NotifySummaryInvalidProxy, "代理 URL 无效（未发送）"),
+    (MessageKey::NotifySummaryAborted, "通知任务异常结束"),
+    (MessageKey::NotifySummaryNoProtocol, "没有要通知的协议"),
+    (MessageKey::NotifySummaryTotalFmt, "成功 {ok} 个，失败 {failed} 个"),
+    (MessageKey::ElevationConfirm, "此操作需要管理员权限。是否以管理员身份重新运行？"),
+    (MessageKey::ElevationRequired, "此操作需要管理员权限。请在以管理员身份运行的命令提示符中执行。"),
+    (MessageKey::ElevationCancelled, "已取消以管理员身份运行。"),
+    (MessageKey::ElevatedPressEnter, "\n按 Enter 键关闭此窗口..."),
+    (MessageKey::ServicePasswordPromptFmt, "账户 '{account}' 的密码"),
+    (MessageKey::ServicePasswordNotNeededFmt, "无法为账户 '{account}' 指定密码。"),
+    (MessageKey::ConfirmPurge, "将删除所有已保存的账户、设置和日志文件。此操作无法撤消。是否继续？"),
+    (MessageKey::PurgeNeedsYes, "标准输入不是终端，无法确认清除。如需不经确认直接清除，请使用 --yes。"),
+    (MessageKey::PurgeSuccess, "[成功] 已删除保存的账户、设置和日志文件。"),
+    (MessageKey::AddSuccess, "[成功] 已添加账户。"),
+    (MessageKey::NoAccountsAddPrompt, "未找到账户。是否新建？"),
+    (MessageKey::OperationCancelled, "操作已取消。"),
+    (MessageKey::EditTargetFmt, "目标账户: {id}"),
+    (MessageKey::ViewRuntimeFmt, "    状态: {state}，  上次尝试: {time}，  结果: {result}"),
+    (MessageKey::ViewRuntimeInFlight, "通知中"),
+    (MessageKey::ViewRuntimeIdle, "空闲"),
+    (MessageKey::ViewPaused, "\n[注意] 定期通知已暂停。使用 --resume 恢复。"),
+    (MessageKey::PauseSuccess, "[成功] 已暂停定期通知。"),
+    (MessageKey::ResumeSuccess, "[成功] 已恢复定期通知。"),
+    (MessageKey::ViewIntervalFmt, "通知间隔: {minutes} 分钟"),
+    (MessageKey::IntervalSetFmt, "通知间隔已设置为 {minutes} 分钟。正在运行的服务将在下一次定期通知后应用此设置。"),
+    (MessageKey::IntervalOutOfRangeFmt, "通知间隔必须在 {min} 到 {max} 分钟之间。"),
+    (MessageKey::ViewRetryFmt, "最大通知尝试次数: {count}"),
+    (MessageKey::RetrySetFmt, "最大通知尝试次数已设置为 {count}。"),
+    (MessageKey::RetryOutOfRangeFmt, "最大通知尝试次数必须在 {min} 到 {max} 之间。"),
+    (MessageKey::ViewConcurrencyFmt, "同时通知的账户数：{count}"),
+    (MessageKey::ConcurrencySetFmt, "已将同时通知的账户数设置为 {count}。"),
+    (MessageKey::ConcurrencyOutOfRangeFmt, "同时通知的账户数必须在 {min} 到 {max} 之间。"),
+    (MessageKey::ViewRequestTimeoutFmt, "通知请求超时：{seconds} 秒"),
+    (MessageKey::RequestTimeoutSetFmt, "已将通知请求超时设置为 {seconds} 秒。"),
+    (MessageKey::RequestTimeoutOutOfRangeFmt, "通知请求超时必须在 {min} 到 {max} 秒之间。"),
+    (MessageKey::Ipv4UrlPrompt, "IPv4 通知 URL"),
+    (MessageKey::Ipv6UrlPrompt, "IPv6 通知 URL"),
+    (MessageKey::EndpointUrlInvalidFmt, "\"{url}\" 不是有效的 URL。保留当前值。"),
+    (MessageKey::ViewEndpointFmt, "  通知 URL（{proto}）: {url}"),
+    (MessageKey::HostnamePrompt, "要在 DNS 中验证的主机名（留空: 跳过，-: 清除）"),
+    (MessageKey::SetHostnameInvalidFmt, "\"{host}\" 不是有效的主机名。"),
+    (MessageKey::SetEndpointUrlInvalidFmt, "\"{url}\" 不是有效的 URL。"),
+    (MessageKey::HostnameInvalidFmt, "\"{host}\" 不是有效的主机名。保留当前值。"),
+    (MessageKey::ViewLastSuccessFmt, "  上次成功: {time}  （IPv4: {v4}，  IPv6: {v6}）"),
+    (MessageKey::ViewHostnameFmt, "  要验证的主机名: {host}"),
+    (MessageKey::ProviderPrompt, "更新服务（mydns: MyDNS.JP，generic: 指定更新 URL 的通用服务）"),
+    (MessageKey::ProviderInvalid, "服务必须是 mydns 或 generic。"),
+    (MessageKey::UsernamePrompt, "用户名（也用作账户名）"),
+    (MessageKey::GenericIpv4UrlPrompt, "IPv4 更新 URL（替换 {ip} 和 {host}）"),
+    (MessageKey::GenericIpv6UrlPrompt, "IPv6 更新 URL（留空: 与 IPv4 相同，-: 清除）"),
+    (MessageKey::GenericUrlRequired, "通用服务需要 IPv4 更新 URL。"),
+    (MessageKey::ViewProviderGeneric, "  服务: 通用（更新 URL）"),
+    (MessageKey::MethodPrompt, "通知方式（http：HTTPS，pop3/imap/ftp：登录服务器）"),
+    (MessageKey::MethodInvalid, "通知方式必须是 http、pop3、imap 或 ftp。"),
+    (MessageKey::MethodRequiresMyDns, "通过 POP3/IMAP/FTP 登录进行通知仅适用于 MyDNS.JP 账户。"),
+    (MessageKey::MethodPlaintextWarning, "注意：POP3/IMAP/FTP 登录会以未加密的方式发送密码。"),
+    (MessageKey::ViewMethodFmt, "  通知方式：{method}（登录）"),
+    (MessageKey::ViewLogPathFmt, "日志文件: {path}"),
+    (MessageKey::ViewLogArchiveFmt, "日志归档: 保留 {count} 个（压缩: {compress}）"),
+    (MessageKey::ViewProxyFmt, "代理: {proxy}"),
+    (MessageKey::ProxyAuto, "自动（WinHTTP/系统设置）"),
+    (MessageKey::ViewMetricsFmt, "指标: http://127.0.0.1:{port}/metrics"),
+    (MessageKey::MetricsSetFmt, "已设置在 http://127.0.0.1:{port}/metrics 提供指标。正在运行的服务将在重启后应用。"),
+    (MessageKey::MetricsDisabled, "已禁用指标端点。正在运行的服务将在重启后应用。"),
+    (MessageKey::ViewAdminApiFmt, "管理 API: http://127.0.0.1:{port}/api/"),
+    (MessageKey::AdminApiSetFmt, "已设置在 http://127.0.0.1:{port}/api/ 提供管理 API。正在运行的服务将在重启后应用。\n请在请求中附加 \"Authorization: Bearer {token}\" 头。"),
+    (MessageKey::AdminApiDisabled, "已禁用管理 API。正在运行的服务将在重启后应用。"),
+    (MessageKey::PingUrlPrompt, "更新成功后请求的监控 ping URL（留空：不发送，-：清除）"),
+    (MessageKey::ViewAccountPingUrlFmt, "  ping URL：{url}"),
+    (MessageKey::InterfacePrompt, "用于发送通知的网络接口名称或本地地址（留空：不指定，-：清除）"),
+    (MessageKey::ViewInterfaceFmt, "  发送接口：{interface}"),
+    (MessageKey::SchedulePrompt, "代替间隔使用的通知计划（例如 */10 * * * *、at 04:05 daily；留空：按间隔，-：清除）"),
+    (MessageKey::ScheduleInvalidFmt, "无法解析计划（请使用 cron 的 5 个字段或 at HH:MM daily 格式）：{schedule}"),
+    (MessageKey::ViewScheduleFmt, "  计划：{schedule}"),
+    (MessageKey::ViewPingUrlFmt, "监控 ping URL：{url}"),
+    (MessageKey::PingUrlSetFmt, "每轮通知中所有账户都成功后，将向“{url}”发送 ping。"),
+    (MessageKey::PingUrlCleared, "已清除监控 ping URL。"),
+    (MessageKey::ViewWebhookUrlFmt, "Webhook：{url}"),
+    (MessageKey::WebhookUrlSetFmt, "通知失败、恢复和地址变化将发送到“{url}”。"),
+    (MessageKey::WebhookUrlCleared, "已清除 Webhook URL。"),
+    (MessageKey::ViewToastFmt, "Toast 通知：连续失败 {count} 次后"),
+    (MessageKey::ToastSetFmt, "通知连续失败 {count} 次后将显示 Toast 通知。"),
+    (MessageKey::ToastDisabled, "已禁用 Toast 通知。"),
+    (MessageKey::ViewMailFmt, "邮件提醒：{to}（通过 {server}:{port}，失败持续 {minutes} 分钟后）"),
+    (MessageKey::MailSettingsSaved, "已保存邮件提醒设置。可以使用 --mail-test 发送测试邮件。"),
+    (MessageKey::MailDisabled, "已禁用邮件提醒。"),
+    (MessageKey::MailNotConfigured, "要使用邮件提醒，请同时设置 --smtp-server 和 --mail-to。"),
+    (MessageKey::SmtpServerInvalidFmt, "“{server}”不是有效的 SMTP 服务器。请使用 HOST 或 HOST:PORT 格式。"),
+    (MessageKey::MailAddressInvalidFmt, "“{address}”不是有效的邮件地址。"),
+    (MessageKey::MailTestSentFmt, "[成功] 已向 {to} 发送测试邮件。"),
+    (MessageKey::MailTestFailedFmt, "无法发送测试邮件：{error}"),
+    (MessageKey::ViewCheckIpv4UrlsFmt, "IPv4 地址检查服务：{urls}"),
+    (MessageKey::ViewCheckIpv6UrlsFmt, "IPv6 地址检查服务：{urls}"),
+    (MessageKey::ViewCheckIpPollFmt, "全局地址检查：每 {minutes} 分钟"),
+    (MessageKey::CheckIpSettingsSaved, "已保存地址检查服务的设置。"),
+    (MessageKey::ViewSkipPrivateIpv4, "CGNAT/私有 IPv4 地址：不通知"),
+    (MessageKey::SkipPrivateIpv4Enabled, "当查询到的 IPv4 地址为 CGNAT 或私有地址时，将跳过更新。"),
+    (MessageKey::SkipPrivateIpv4Disabled, "CGNAT 和私有 IPv4 地址也会通知，并在日志中记录警告。"),
+    (MessageKey::ViewDnsCheckFmt, "DNS 自检：每 {minutes} 分钟"),
+    (MessageKey::DnsCheckSetFmt, "将每 {minutes} 分钟通过公共解析器检查主机名的 DNS 记录。"),
+    (MessageKey::DnsCheckDisabled, "已禁用 DNS 自检。"),
+    (MessageKey::ViewMeteredFmt, "按流量计费的连接：{policy}"),
+    (MessageKey::MeteredPolicyInvalid, "按流量计费连接的策略必须为 ignore、reduce 或 skip。"),
+    (MessageKey::MeteredIgnore, "在按流量计费的连接上也照常通知。"),
+    (MessageKey::MeteredReduce, "在按流量计费的连接上，定期通知每小时最多执行一次。"),
+    (MessageKey::MeteredSkip, "在按流量计费的连接上，服务将跳过自动通知。"),
+    (MessageKey::ViewAllowedNetworksFmt, "允许的网络：{networks}"),
+    (MessageKey::AllowedNetworksSetFmt, "服务仅在连接到以下网络时通知：{networks}"),
+    (MessageKey::AllowedNetworksCleared, "已解除允许网络的限制。"),
+    (MessageKey::ConnectedNetworksFmt, "当前连接的网络：{networks}"),
+    (MessageKey::ViewQuietHoursFmt, "静默时段：{hours}"),
+    (MessageKey::QuietHoursInvalidFmt, "静默时段必须为 HH:MM-HH:MM 格式（例如 23:00-06:00），且开始和结束时间不能相同：{hours}"),
+    (MessageKey::QuietHoursSetFmt, "服务将在每天 {hours} 期间暂缓自动通知。"),
+    (MessageKey::QuietHoursCleared, "已解除静默时段。"),
+    (MessageKey::ViewJitterFmt, "通知时间抖动：±{seconds} 秒"),
+    (MessageKey::JitterSetFmt, "服务将在最多 ±{seconds} 秒的范围内随机调整通知时间（最多为通知间隔的一半）。"),
+    (MessageKey::JitterDisabled, "已禁用通知时间抖动。"),
+    (MessageKey::ViewUserAgentFmt, "User-Agent：{ua}"),
+    (MessageKey::UserAgentIdSetFmt, "已设置 User-Agent 标识：{ua}"),
+    (MessageKey::UserAgentIdCleared, "已清除 User-Agent 标识。"),
+    (MessageKey::UserAgentIdInvalidFmt, "User-Agent 标识最多只能包含 {max} 个可打印的 ASCII 字符。"),
+    (MessageKey::ViewHistoryDatabase, "历史数据库：已启用"),
+    (MessageKey::HistoryDbEnabled, "通知历史也将记录到数据库中。"),
+    (MessageKey::HistoryDbDisabled, "通知历史将不再记录到数据库中。已有的记录会保留。"),
+    (MessageKey::HistoryDbUnsupported, "此版本不支持历史数据库。请使用 `--features sqlite` 构建。"),
+    (MessageKey::StatusHistorySummaryFmt, "  最近 24 小时：尝试 {attempts} 次，成功 {successes} 次，平均 {ms} ms"),
+    (MessageKey::ToastTitle, "MyDNS Adapter：通知失败"),
+    (MessageKey::ToastFailuresFmt, "账户 {id} 的通知已连续失败 {count} 次。{error}"),
+    (MessageKey::ToastExpiryTitle, "MyDNS Adapter：记录即将过期"),
+    (MessageKey::ToastExpiryFmt, "账户 {id} 已有 {hours} 小时未成功通知。MyDNS.JP 记录将在约 {remaining} 小时后过期。"),
+    (MessageKey::ToastMonitorStarted, "--- 正在监视通知失败（按 Ctrl+C 退出） ---"),
+    (MessageKey::ProxySetFmt, "通知代理已设置为 \"{url}\"。"),
+    (MessageKey::ProxyCleared, "已清除代理设置。将使用 WinHTTP/系统设置。"),
+    (MessageKey::ProxyPrompt, "代理 URL（留空: 使用全局设置，direct: 不使用代理，-: 清除）"),
+    (MessageKey::ExportSuccessFmt, "[成功] 已将 {count} 个账户导出到 {path}。"),
+    (MessageKey::ExportPasswordWarning, "[警告] 文件中包含明文密码，请妥善保管。（使用 --redact-passwords 可排除密码。）"),
+    (MessageKey::ImportTitle, "--- 导入配置 ---"),
+    (MessageKey::ImportParseErrorFmt, "无法解析文件: {error}"),
+    (MessageKey::ImportUnsupportedVersionFmt, "不支持的文件格式版本: {version}"),
+    (MessageKey::ImportDuplicateFmt, "MasterID {id} 出现了多次。"),
+    (MessageKey::ImportUnknownFieldFmt, "未知字段: {field}"),
+    (MessageKey::ImportMissingFieldFmt, "缺少必填字段: {field}"),
+    (MessageKey::ImportInvalidTypeFmt, "字段的值类型无效: {field}"),
+    (MessageKey::ImportMissingPasswordFmt, "新账户 {id} 需要密码。"),
+    (MessageKey::ImportDiffNewFmt, "+ {id}（新增）"),
+    (MessageKey::ImportDiffChangedFmt, "~ {id}（已更改）"),
+    (MessageKey::ImportDiffUnchangedFmt, "= {id}（未更改）"),
+    (MessageKey::ImportNothingToDo, "没有要应用的更改。"),
+    (MessageKey::ImportConfirmFmt, "是否应用 {count} 项账户更改？"),
+    (MessageKey::ImportSuccessFmt, "[成功] 已应用 {count} 项账户更改。"),
+    (MessageKey::UserScopeServiceConflict, "服务不读取每用户设置，因此 --user 不能与服务命令一起使用。"),
+    (MessageKey::StatusTitle, "--- MyDNS Adapter 状态 ---"),
+    (MessageKey::StatusUptimeFmt, "运行时间: {days} 天 {hours} 小时 {minutes} 分钟（启动于 {since}）"),
+    (MessageKey::ViewLastErrorFmt, "  上次错误: {time}  [{kind}] {detail}"),
+    (MessageKey::ViewSuspendedFmt, "  已暂停通知：自 {time} 起（认证反复失败）。使用 `account enable {id}` 恢复。"),
+    (MessageKey::StatusLastIpFmt, "  上次通知的地址: IPv4 {v4}，  IPv6 {v6}"),
+    (MessageKey::StatusAccountFmt, "{id}: 上次通知: {time}，  结果: {result}，  上次成功: {success}"),
+    (MessageKey::UserScopeFallback, "[信息] 未以管理员身份运行，将使用每用户设置（HKCU）。服务不会读取这些设置。"),
+    (MessageKey::ViewUserScope, "（显示 HKCU 中的每用户设置）"),
+    (MessageKey::ViewConfigFileFmt, "（正在显示配置文件 {path} 中的账户）"),
+    (MessageKey::ConfigFileInvalidFmt, "无法读取配置文件 {path}：{error}"),
+    (MessageKey::PasswordFromEnvFmt, "使用环境变量 {var} 中的密码。"),
+    (MessageKey::KickSuccess, "[成功] 已请求服务立即通知。"),
+    (MessageKey::UpgradeTitle, "--- 升级配置 ---"),
+    (MessageKey::UpgradeAccountFmt, "账户 '{id}':"),
+    (MessageKey::UpgradeNoChanges, "无更改。"),
+    (MessageKey::UpgradeAddedDefaultFmt, "{name}: 不存在，已写入默认值 {value}。"),
+    (MessageKey::UpgradeMigratedTypeFmt, "{name}: 已从字符串转换为数值 {value}。"),
+    (MessageKey::UpgradeEmptyPassword, "[警告] 未设置密码。请使用 'account edit' 设置密码。"),
+    (MessageKey::InvalidMasterIdPrefix, "MasterID 必须以 'mydns' 开头。"),
+
+    // winservice.rs
+    (MessageKey::AdminRequiredInstall, "安装服务需要管理员权限。请以管理员身份运行。"),
+    (MessageKey::ServiceInstallingFmt, "正在安装服务 '{name}'..."),
+    (MessageKey::ServiceInstalledFmt, "服务 '{name}' 已安装并启动。"),
+    (MessageKey::AdminRequiredUninstall, "卸载服务需要管理员权限。请以管理员身份运行。"),
+    (MessageKey::ServiceNotInstalledFmt, "服务 '{name}' 未安装。"),
+    (MessageKey::ServiceStoppingFmt, "正在停止服务 '{name}'..."),
+    (MessageKey::ServiceStopped, "服务已停止。"),
+    (MessageKey::ServiceWaitingStop, "正在等待服务停止..."),
+    (MessageKey::ServiceNotRunning, "服务未运行。"),
+    (MessageKey::ServiceUninstalledFmt, "服务 '{name}' 已卸载。"),
+    (MessageKey::AdminRequiredRestart, "重新启动服务需要管理员权限。请以管理员身份运行。"),
+    (MessageKey::ServiceStatusFmt, "服务 '{name}': {state}"),
+    (MessageKey::ServiceStateRunning, "正在运行"),
+    (MessageKey::ServiceStateStopped, "已停止"),
+    (MessageKey::ServiceStateStartPending, "正在启动"),
+    (MessageKey::ServiceStateStopPending, "正在停止"),
+    (MessageKey::ServiceStatePaused, "已暂停"),
+    (MessageKey::ServiceStatePausePending, "正在暂停"),
+    (MessageKey::ServiceStateContinuePending, "正在恢复"),
+    (MessageKey::ServiceStateUnknown, "未知"),
+    (MessageKey::ServiceDescription, "定期将本机的 IPv4/IPv6 地址通知给 MyDNS.JP，使动态 DNS 记录保持最新。"),
+    (MessageKey::ServiceLogonFailedFmt, "服务已安装，但以 '{account}' 身份登录失败，无法启动。请检查密码和“作为服务登录”权限，然后重新启动服务。"),
+    (MessageKey::ServiceKeyAccessGrantedFmt, "已授予帐户 '{account}' 对 {key} 的读写权限。"),
+    (MessageKey::ServiceKeyAccessFailedFmt, "无法授予帐户 '{account}' 对 {key} 的写入权限：{error}。服务将无法记录通知结果和自动停止。请使用 regedit 手动授予权限。"),
+    (MessageKey::ServiceDataDirAccessGrantedFmt, "已授予帐户 '{account}' 对 {path} 的写入权限。"),
+    (MessageKey::ServiceDataDirAccessFailedFmt, "无法授予帐户 '{account}' 对 {path} 的写入权限：{error}。服务将无法记录运行状态和通知历史。请在资源管理器的“安全”选项卡中手动授予权限。"),
+    (MessageKey::DataDirShareFailedFmt, "无法授予用户对运行数据目录 {path} 的写入权限：{error}。没有管理员权限的用户将无法使用 --pause 和 --kick。"),
+    (MessageKey::AdminRequiredStart, "启动服务需要管理员权限。请以管理员身份运行。"),
+    (MessageKey::AdminRequiredStop, "停止服务需要管理员权限。请以管理员身份运行。"),
+    (MessageKey::ServiceStartingFmt, "正在启动服务 '{name}'..."),
+    (MessageKey::ServiceStarted, "服务已启动。"),
+    (MessageKey::ServiceAlreadyRunning, "服务已在运行。"),
+    (MessageKey::ServiceStartFailedFmt, "服务在启动过程中停止（退出代码: {code}）。详细信息请查看日志。"),
+    (MessageKey::ServiceStoppedAfterStartFmt, "服务在启动后立即停止（退出代码: {code}）。详细信息请查看日志。"),
+    (MessageKey::ServiceStartTimeout, "等待服务启动超时。"),
+    (MessageKey::ForegroundStarted, "正在此控制台中运行服务循环。按 Ctrl+C 停止。"),
+    (MessageKey::ForegroundStopped, "已停止。"),
+    (MessageKey::ForegroundServiceRunning, "[警告] 服务正在运行。账户可能会被重复通知。"),
+    (MessageKey::ServicePanicked, "服务循环发生了 panic。"),
+    (MessageKey::ServiceRestartedSuccessfully, "服务已重新启动。"),
+
+    // notify.rs
+    (MessageKey::NotifyKindNetwork, "网络错误"),
+    (MessageKey::NotifyKindAuth, "身份验证失败"),
+    (MessageKey::NotifyKindServer, "服务器错误"),
+    (MessageKey::NotifyKindRateLimited, "请求过多"),
+    (MessageKey::NotifyKindHttp, "HTTP 错误"),
+    (MessageKey::NotifyKindSkipped, "已跳过"),
+    (MessageKey::TestSendingFmt, "正在检查账户 '{id}' 的凭据（{url}）..."),
+    (MessageKey::TestOkFmt, "凭据有效。MyDNS.JP 接受的地址: {addr}"),
+    (MessageKey::TestAuthFailedFmt, "身份验证失败。MasterID 或密码不正确。（{detail}）"),
+    (MessageKey::TestInconclusiveFmt, "无法验证凭据。{kind}: {detail}"),
+
+    // doctor.rs
+    (MessageKey::DoctorTitle, "--- 诊断 ---"),
+    (MessageKey::DoctorHeaderFmt, "版本: {version}，  运行时间: {time}，  配置范围: {scope}"),
+    (MessageKey::DoctorItemElevation, "权限"),
+    (MessageKey::DoctorItemRegistry, "账户设置"),
+    (MessageKey::DoctorItemSettings, "全局设置"),
+    (MessageKey::DoctorItemRegistryWrite, "设置的写入权限"),
+    (MessageKey::DoctorItemLog, "日志文件"),
+    (MessageKey::DoctorItemIpv4, "IPv4 连通性（ipv4.mydns.jp）"),
+    (MessageKey::DoctorItemIpv6, "IPv6 连通性（ipv6.mydns.jp）"),
+    (MessageKey::DoctorItemService, "服务"),
+    (MessageKey::DoctorItemClock, "时钟"),
+    (MessageKey::DoctorElevated, "正在以管理员身份运行。"),
+    (MessageKey::DoctorNotElevated, "未以管理员身份运行。无法更改设置或操作服务。"),
+    (MessageKey::DoctorAccountsFmt, "{count} 个账户"),
+    (MessageKey::DoctorReadable, "可读取。"),
+    (MessageKey::DoctorWritable, "可写入。"),
+    (MessageKey::DoctorRegistryReadOnly, "没有写入权限。"),
+    (MessageKey::DoctorReachableFmt, "HTTP {status}（{ms} 毫秒）"),
+    (MessageKey::DoctorServiceNotInstalled, "未安装。"),
+    (MessageKey::DoctorClockSkewFmt, "与 MyDNS.JP 服务器的时间差: {seconds} 秒"),
+    (MessageKey::DoctorClockUnknown, "无法连接 MyDNS.JP，因此无法检查。"),
+    (MessageKey::DoctorSummaryFmt, "\n失败: {fail} 项，警告: {warn} 项"),
+
+    // history.rs
+    (MessageKey::HistoryTitle, "--- 通知历史记录 ---"),
+    (MessageKey::HistoryEmpty, "没有历史记录。"),
+    (MessageKey::HistoryRowFmt, "{time}  {id}  {proto}  {ms} 毫秒  {result}"),
+
+    // ipc.rs
+    (MessageKey::IpcServiceUnavailable, "无法连接到服务。请确认服务正在运行。"),
+    (MessageKey::IpcServiceBusy, "服务正忙于处理其他请求，无法连接。请稍后重试。"),
+    (MessageKey::IpcServerNotServiceFmt, "命名管道的服务器（PID {pid}）不是服务进程，因此未发送请求。"),
+    (MessageKey::IpcInvalidResponse, "无法解析服务的响应。"),
+    (MessageKey::IpcRequestFailedFmt, "服务无法处理该请求: {error}"),
+    (MessageKey::IpcNotifyNowSuccess, "[成功] 已请求服务立即通知。"),
+    (MessageKey::IpcReloadSuccessFmt, "[成功] 服务已重新加载账户设置（账户数: {count}）。"),
+    (MessageKey::IpcStatusTitle, "--- 服务状态 ---"),
+    (MessageKey::IpcStatusVersion, "版本"),
+    (MessageKey::IpcStatusStarted, "启动时间"),
+    (MessageKey::IpcStatusAccounts, "账户数"),
+    (MessageKey::IpcStatusPaused, "已暂停"),
+    (MessageKey::IpcStatusRoundRunning, "通知中"),
+    (MessageKey::IpcStatusNextRound, "下一次定期通知"),
+
+    // schtask.rs
+    (MessageKey::AdminRequiredTask, "注册或删除计划任务需要管理员权限。请以管理员身份运行。"),
+    (MessageKey::TaskServiceInstalledWarning, "[警告] 服务也已安装。为避免重复通知，请只使用其中之一。"),
+    (MessageKey::TaskInstalledFmt, "已注册计划任务 '{name}'。它将在启动时、连接网络时以及每 {minutes} 分钟进行通知。"),
+    (MessageKey::TaskUninstalledFmt, "已删除计划任务 '{name}'。"),
+    (MessageKey::TaskNotInstalledFmt, "计划任务 '{name}' 未注册。"),
+    (MessageKey::TaskDescription, "将本机的 IP 地址通知给 MyDNS.JP（MyDNS.JP Adapter）。"),
+
+    // crash.rs
+    (MessageKey::PanicOccurredFmt, "发生了意外错误。详细信息已写入日志和事件日志: {summary}"),
+
+    // stats.rs
+    (MessageKey::StatsTitle, "--- 使用统计 ---"),
+    (MessageKey::StatsEnabled, "[成功] 已启用使用统计。统计数据不会发送到本机以外。"),
+    (MessageKey::StatsDisabled, "[成功] 已禁用使用统计。"),
+    (MessageKey::StatsNotEnabled, "使用统计已禁用。如需启用，请运行 --stats --opt-in。"),
+    (MessageKey::StatsEmpty, "没有记录任何统计数据。"),
+    (MessageKey::StatsMonthFmt, "{month}: 通知 {total} 次（成功 {ok} / 失败 {ng}），  IP 变更 {ip} 次，  运行 {hours} 小时"),
+    (MessageKey::StatsYearFmt, "\n{year} 年共通知 {total} 次，IP 地址变更了 {ip} 次。"),
+];
@@ -0,0 +1,467 @@
+//! CLIと実行中のサービスの間で、名前付きパイプ（`\\.\pipe\MyDNSAdapter`）を使って要求をやり取りするモジュール。
+//!
+//! サービスはパイプのサーバーとして待ち受け、CLIの `service notify-now`、`service reload-config`、
+//! `service query-status`、`service tail-log` から要求を受け取って、結果を返します。
+//! ログの読み込みなどはサービスの実行アカウントで行うため、管理者権限のないユーザーでも状態やログを確認できます。
+//!
+//! 要求は `notify-now` のようなコマンド名を1行で送ります（`tail-log` は行数を続けて指定します）。
+//! 応答は `OK <バイト数>` または `ERR <バイト数>` の1行に続けて、そのバイト数の結果またはエラーの内容を返します。
+//! パイプには、このマシンにログオンしているユーザーだけが接続できます。
+//!
+//! ほかのプロセスが先に同じ名前のパイプを作成してサービスになりすますことを防ぐため、
+//! サービスはパイプの最初のインスタンスとしてだけ作成し、CLIは接続したパイプのサーバーが
+//! SCMに登録されたサービスのプロセスであることを確認してから要求を送ります。
+
+use crate::error::AdapterError;
+use crate::i18n::{MessageKey, get_msg};
+use crate::winservice::query_service_pid;
+use crate::{format_msg, outln};
+use chrono::{DateTime, Local};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::windows::io::AsRawHandle;
+use std::thread::{self, JoinHandle};
+use windows::Win32::Foundation::{
+    CloseHandle, ERROR_IO_PENDING, ERROR_PIPE_BUSY, ERROR_PIPE_CONNECTED, HANDLE, HLOCAL,
+    INVALID_HANDLE_VALUE, LocalFree, WAIT_OBJECT_0,
+};
+use windows::Win32::Security::Authorization::{
+    ConvertStringSecurityDescriptorToSecurityDescriptorW, SDDL_REVISION_1,
+};
+use windows::Win32::Security::{PSECURITY_DESCRIPTOR, SECURITY_ATTRIBUTES};
+use windows::Win32::Storage::FileSystem::{
+    FILE_FLAG_FIRST_PIPE_INSTANCE, FILE_FLAG_OVERLAPPED, PIPE_ACCESS_DUPLEX, ReadFile, WriteFile,
+};
+use windows::Win32::System::IO::{CancelIoEx, GetOverlappedResult, OVERLAPPED};
+use windows::Win32::System::Pipes::{
+    ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe, GetNamedPipeServerProcessId,
+    PIPE_READMODE_BYTE, PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE, PIPE_WAIT, WaitNamedPipeW,
+};
+use windows::Win32::System::Threading::{
+    CreateEventW, INFINITE, ResetEvent, SetEvent, WaitForMultipleObjects,
+};
+use windows::core::{HRESULT, HSTRING, w};
+
+/// サービスが待ち受ける名前付きパイプの名前。
+const PIPE_NAME: &str = r"\\.\pipe\MyDNSAdapter";
+/// 1回の読み書きを待つ最大時間（ミリ秒）。応答しないクライアントでサーバーが止まらないようにする。
+const IO_TIMEOUT_MS: u32 = 5000;
+/// 他の要求を処理している間、パイプが空くのを待つ最大時間（ミリ秒）。
+const BUSY_WAIT_MS: u32 = 5000;
+/// 受け付ける要求の最大の長さ（バイト）。
+const MAX_REQUEST_LEN: usize = 1024;
+
+/// CLIからサービスへの要求。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Request {
+    /// 定期通知を待たずに、すぐに通知する。
+    NotifyNow,
+    /// レジストリからアカウント設定を読み込み直す。
+    ReloadConfig,
+    /// サービスの状態を問い合わせる。
+    QueryStatus,
+    /// ログファイルの末尾の、指定した行数を取得する。
+    TailLog(usize),
+}
+
+impl Request {
+    /// 要求を、パイプに送る1行に変換します。
+    fn to_line(self) -> String {
+        match self {
+            Request::NotifyNow => "notify-now".to_string(),
+            Request::ReloadConfig => "reload-config".to_string(),
+            Request::QueryStatus => "query-status".to_string(),
+            Request::TailLog(lines) => format!("tail-log {}", lines),
+        }
+    }
+
+    /// パイプから受け取った1行を、要求として解釈します。解釈できない場合は `None` を返します。
+    fn parse(line: &str) -> Option<Self> {
+        let mut words = line.split_whitespace();
+        let request = match words.next()? {
+            "notify-now" => Request::NotifyNow,
+            "reload-config" => Request::ReloadConfig,
+            "query-status" => Request::QueryStatus,
+            "tail-log" => Request::TailLog(words.next()?.parse().ok()?),
+            _ => return None,
+        };
+        words.next().is_none().then_some(request)
+    }
+}
+
+/// 名前付きパイプのサーバーを表すハンドル。
+///
+/// 破棄（`drop`）されると待ち受けを終了し、サーバーのスレッドの終了を待ちます。
+pub struct PipeServer {
+    /// サーバーのスレッドに終了を伝えるイベント。
+    stop_event: HANDLE,
+    /// 要求を待ち受けるスレッド。
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PipeServer {
+    /// 名前付きパイプを作成し、要求の待ち受けを開始します。
+    ///
+    /// `handler` は、要求を受け取るたびにサーバーのスレッドから呼び出され、
+    /// 成功した場合は結果を、失敗した場合はエラーの内容を返します。
+    /// 要求は1つずつ順に処理し、処理中に接続したクライアントは、パイプが空くまで待ちます。
+    pub fn start<F>(handler: F) -> windows::core::Result<Self>
+    where
+        F: Fn(Request) -> Result<String, String> + Send + 'static,
+    {
+        // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+        // 作成したハンドルは、失敗時はここで、成功時はサーバーのスレッドと `drop` でクローズします。
+        unsafe {
+            let pipe = create_pipe()?;
+            let stop_event = match CreateEventW(None, true, false, None) {
+                Ok(event) => event,
+                Err(e) => {
+                    let _ = CloseHandle(pipe);
+                    return Err(e);
+                }
+            };
+            let io_event = match CreateEventW(None, true, false, None) {
+                Ok(event) => event,
+                Err(e) => {
+                    let _ = CloseHandle(stop_event);
+                    let _ = CloseHandle(pipe);
+                    return Err(e);
+                }
+            };
+
+            // ハンドルはスレッド間で送れない型のため、値として渡してスレッド側で復元する。
+            let (raw_pipe, raw_io, raw_stop) =
+                (pipe.0 as usize, io_event.0 as usize, stop_event.0 as usize);
+            let thread = thread::spawn(move || {
+                let pipe = HANDLE(raw_pipe as _);
+                let io_event = HANDLE(raw_io as _);
+                let stop_event = HANDLE(raw_stop as _);
+                serve_loop(pipe, io_event, stop_event, handler);
+                let _ = CloseHandle(io_event);
+                let _ = CloseHandle(pipe);
+            });
+            Ok(PipeServer {
+                stop_event,
+                thread: Some(thread),
+            })
+        }
+    }
+}
+
+impl Drop for PipeServer {
+    fn drop(&mut self) {
+        // サーバーのスレッドに終了を伝え、スレッドが終了してからイベントをクローズする。
+        unsafe {
+            let _ = SetEvent(self.stop_event);
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        unsafe {
+            let _ = CloseHandle(self.stop_event);
+        }
+    }
+}
+
+/// ローカルのログオン済みユーザーだけが読み書きできる、名前付きパイプのインスタンスを作成します。
+///
+/// 既定のセキュリティでは一般のユーザーが要求を書き込めないため、SYSTEMと管理者にフルコントロール、
+/// ログオン済みのユーザー（Authenticated Users）に読み書きを許可します。
+/// 同じ名前のパイプがすでに存在する場合は、`ERROR_ACCESS_DENIED` で失敗します。
+unsafe fn create_pipe() -> windows::core::Result<HANDLE> {
+    unsafe {
+        let mut descriptor = PSECURITY_DESCRIPTOR::default();
+        ConvertStringSecurityDescriptorToSecurityDescriptorW(
+            w!("D:(A;;GA;;;SY)(A;;GA;;;BA)(A;;GRGW;;;AU)"),
+            SDDL_REVISION_1,
+            &mut descriptor,
+            None,
+        )?;
+        let attributes = SECURITY_ATTRIBUTES {
+            nLength: size_of::<SECURITY_ATTRIBUTES>() as u32,
+            lpSecurityDescriptor: descriptor.0,
+            bInheritHandle: false.into(),
+        };
+        let pipe = CreateNamedPipeW(
+            &HSTRING::from(PIPE_NAME),
+            PIPE_ACCESS_DUPLEX | FILE_FLAG_OVERLAPPED | FILE_FLAG_FIRST_PIPE_INSTANCE,
+            PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+            1,
+            4096,
+            4096,
+            0,
+            Some(&attributes),
+        );
+        let _ = LocalFree(HLOCAL(descriptor.0));
+        if pipe == INVALID_HANDLE_VALUE {
+            return Err(windows::core::Error::from_win32());
+        }
+        Ok(pipe)
+    }
+}
+
+/// 重複I/Oの待機が、完了以外の理由で終わったことを表す。
+enum IoFailure {
+    /// サーバーの終了を伝えられた。
+    Stopped,
+    /// I/Oが失敗した、または時間内に完了しなかった。
+    Failed,
+}
+
+/// 終了を伝えられるまで、クライアントの接続を待ち受けて要求を処理します。
+///
+/// 1つのパイプのインスタンスを、切断してから次の接続に使い回します。
+unsafe fn serve_loop<F>(pipe: HANDLE, io_event: HANDLE, stop_event: HANDLE, handler: F)
+where
+    F: Fn(Request) -> Result<String, String>,
+{
+    loop {
+        // 接続を待つ。クライアントが先に接続していた場合も、接続済みとして扱う。
+        let connected = unsafe {
+            wait_io(pipe, io_event, stop_event, INFINITE, |overlapped| {
+                ConnectNamedPipe(pipe, Some(overlapped))
+            })
+        };
+        match connected {
+            Ok(_) => {
+                // 個々の要求の失敗は、そのクライアントとの接続を切るだけにとどめる。
+                if let Err(IoFailure::Stopped) =
+                    unsafe { serve_client(pipe, io_event, stop_event, &handler) }
+                {
+                    return;
+                }
+            }
+            Err(IoFailure::Stopped) => return,
+            Err(IoFailure::Failed) => {}
+        }
+        unsafe {
+            let _ = DisconnectNamedPipe(pipe);
+        }
+    }
+}
+
+/// 接続したクライアントから要求を1つ読み込み、処理して応答を返します。
+unsafe fn serve_client<F>(
+    pipe: HANDLE,
+    io_event: HANDLE,
+    stop_event: HANDLE,
+    handler: &F,
+) -> Result<(), IoFailure>
+where
+    F: Fn(Request) -> Result<String, String>,
+{
+    // 要求の1行を読み込む。
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 256];
+    while !request.contains(&b'\n') && request.len() <= MAX_REQUEST_LEN {
+        let read = unsafe {
+            wait_io(pipe, io_event, stop_event, IO_TIMEOUT_MS, |overlapped| {
+                ReadFile(pipe, Some(&mut buffer), None, Some(overlapped))
+            })?
+        };
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read as usize]);
+    }
+    let line = String::from_utf8_lossy(&request);
+    let line = line.lines().next().unwrap_or_default().trim();
+    let (status, body) = match Request::parse(line).map(handler) {
+        Some(Ok(body)) => ("OK", body),
+        Some(Err(error)) => ("ERR", error),
+        None => ("ERR", format!("Unknown request: {}", line)),
+    };
+
+    // 応答を書き込む。
+    let response = format!("{} {}\n{}", status, body.len(), body);
+    let mut written = 0;
+    while written < response.len() {
+        written += unsafe {
+            wait_io(pipe, io_event, stop_event, IO_TIMEOUT_MS, |overlapped| {
+                WriteFile(
+                    pipe,
+                    Some(&response.as_bytes()[written..]),
+                    None,
+                    Some(overlapped),
+                )
+            })?
+        } as usize;
+    }
+
+    // クライアントが応答を読み終えて閉じるのを待つ。先に切断すると、読まれていない応答が失われる。
+    // クライアントが閉じると読み込みが失敗するため、結果は問わない。
+    let _ = unsafe {
+        wait_io(pipe, io_event, stop_event, IO_TIMEOUT_MS, |overlapped| {
+            ReadFile(pipe, Some(&mut buffer), None, Some(overlapped))
+        })
+    };
+    Ok(())
+}
+
+/// 重複I/Oを開始し、完了、終了の要求、タイムアウトのいずれかまで待ちます。
+///
+/// 完了した場合は、転送したバイト数を返します。
+/// 完了する前に待機を終える場合は、I/Oを取り消し、取り消しが終わってから戻ります。
+unsafe fn wait_io<F>(
+    pipe: HANDLE,
+    io_event: HANDLE,
+    stop_event: HANDLE,
+    timeout_ms: u32,
+    start: F,
+) -> Result<u32, IoFailure>
+where
+    F: FnOnce(*mut OVERLAPPED) -> windows::core::Result<()>,
+{
+    unsafe {
+        let _ = ResetEvent(io_event);
+        let mut overlapped = OVERLAPPED {
+            hEvent: io_event,
+            ..Default::default()
+        };
+        match start(&mut overlapped) {
+            Ok(()) => {}
+            Err(e) if e.code() == HRESULT::from(ERROR_IO_PENDING) => {
+                // 終了の要求を先に並べ、完了と同時に届いた場合も終了を優先する。
+                let signaled = WaitForMultipleObjects(&[stop_event, io_event], false, timeout_ms);
+                if signaled.0 != WAIT_OBJECT_0.0 + 1 {
+                    let mut transferred = 0;
+                    let _ = CancelIoEx(pipe, Some(&overlapped));
+                    let _ = GetOverlappedResult(pipe, &overlapped, &mut transferred, true);
+                    return Err(if signaled == WAIT_OBJECT_0 {
+                        IoFailure::Stopped
+                    } else {
+                        IoFailure::Failed
+                    });
+                }
+            }
+            // 接続を待ち始める前に、クライアントが接続していた。
+            Err(e) if e.code() == HRESULT::from(ERROR_PIPE_CONNECTED) => return Ok(0),
+            Err(_) => return Err(IoFailure::Failed),
+        }
+        let mut transferred = 0;
+        GetOverlappedResult(pipe, &overlapped, &mut transferred, false)
+            .map_err(|_| IoFailure::Failed)?;
+        Ok(transferred)
+    }
+}
+
+/// 実行中のサービスに要求を送り、結果を受け取ります。
+///
+/// # 戻り値
+/// サービスが要求を処理できた場合は、その結果。
+/// サービスに接続できない場合や、パイプのサーバーがサービスのプロセスでない場合、
+/// サービスがエラーを返した場合はエラー。
+pub fn send_request(request: Request) -> Result<String, AdapterError> {
+    let mut pipe = open_pipe()?;
+    verify_server(&pipe)?;
+    pipe.write_all(format!("{}\n", request.to_line()).as_bytes())?;
+
+    // 応答の1行目（状態と、続く内容のバイト数）を読み込む。
+    let mut header = Vec::new();
+    let mut byte = [0u8; 1];
+    while header.len() <= MAX_REQUEST_LEN {
+        pipe.read_exact(&mut byte)?;
+        if byte[0] == b'\n' {
+            break;
+        }
+        header.push(byte[0]);
+    }
+    let header = String::from_utf8_lossy(&header);
+    let (status, length) = header
+        .split_once(' ')
+        .and_then(|(status, length)| Some((status, length.parse::<usize>().ok()?)))
+        .ok_or_else(|| get_msg(MessageKey::IpcInvalidResponse))?;
+    let mut body = vec![0u8; length];
+    pipe.read_exact(&mut body)?;
+    let body = String::from_utf8_lossy(&body).into_owned();
+    if status == "OK" {
+        Ok(body)
+    } else {
+        Err(format_msg!(MessageKey::IpcRequestFailedFmt, error = body).into())
+    }
+}
+
+/// 接続したパイプのサーバーが、SCMに登録されたサービスのプロセスであることを確認します。
+///
+/// ほかのプロセスが作成したパイプに、要求やその結果を渡さないようにします。
+fn verify_server(pipe: &File) -> Result<(), AdapterError> {
+    let mut server_pid = 0;
+    // SAFETY: `pipe` は開いている名前付きパイプのハンドルで、`server_pid` は有効な書き込み先のため安全。
+    unsafe { GetNamedPipeServerProcessId(HANDLE(pipe.as_raw_handle()), &mut server_pid)? };
+    match query_service_pid()? {
+        Some(service_pid) if service_pid == server_pid => Ok(()),
+        _ => Err(format_msg!(MessageKey::IpcServerNotServiceFmt, pid = server_pid).into()),
+    }
+}
+
+/// サービスの名前付きパイプに接続します。
+///
+/// 他の要求の処理中でパイプが空いていない場合は、空くのを待ってから接続し直します。
+fn open_pipe() -> Result<File, AdapterError> {
+    for _ in 0..3 {
+        match OpenOptions::new().read(true).write(true).open(PIPE_NAME) {
+            Ok(pipe) => return Ok(pipe),
+            Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY.0 as i32) => {
+                // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+                // 待機に失敗した場合も、接続し直して結果を確かめる。
+                unsafe {
+                    let _ = WaitNamedPipeW(&HSTRING::from(PIPE_NAME), BUSY_WAIT_MS);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Err(get_msg(MessageKey::IpcServiceUnavailable).into());
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Err(get_msg(MessageKey::IpcServiceBusy).into())
+}
+
+/// `service notify-now` などのサブコマンドで指定された要求をサービスに送り、結果を表示します。
+pub fn pipe_command_mode(request: Request) -> Result<(), AdapterError> {
+    let body = send_request(request)?;
+    match request {
+        Request::NotifyNow => outln!("{}", get_msg(MessageKey::IpcNotifyNowSuccess)),
+        Request::ReloadConfig => outln!(
+            "{}",
+            format_msg!(MessageKey::IpcReloadSuccessFmt, count = body.trim())
+        ),
+        Request::QueryStatus => {
+            println!("{}", get_msg(MessageKey::IpcStatusTitle));
+            for line in body.lines() {
+                let Some((key, value)) = line.split_once('=') else {
+                    continue;
+                };
+                let label = match key {
+                    "version" => get_msg(MessageKey::IpcStatusVersion),
+                    "started" => get_msg(MessageKey::IpcStatusStarted),
+                    "accounts" => get_msg(MessageKey::IpcStatusAccounts),
+                    "paused" => get_msg(MessageKey::IpcStatusPaused),
+                    "round_running" => get_msg(MessageKey::IpcStatusRoundRunning),
+                    "next_round" => get_msg(MessageKey::IpcStatusNextRound),
+                    _ => key,
+                };
+                // 真偽値と時刻は、表示用の形式に変換する。
+                let value = match value {
+                    "true" => get_msg(MessageKey::Yes).to_string(),
+                    "false" => get_msg(MessageKey::No).to_string(),
+                    _ => DateTime::parse_from_rfc3339(value).map_or_else(
+                        |_| value.to_string(),
+                        |time| {
+                            time.with_timezone(&Local)
+                                .format("%Y-%m-%d %H:%M:%S")
+                                .to_string()
+                        },
+                    ),
+                };
+                println!("  {}: {}", label, value);
+            }
+        }
+        Request::TailLog(_) => {
+            for line in body.lines() {
+                println!("{}", line);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,310 @@
+//! サービスがループバックアドレスで待ち受ける、小さなHTTP/1.1サーバーのモジュール。
+//!
+//! メトリクスの公開（`metrics` モジュール）と管理API（`adminapi` モジュール）で共有します。
+//! 1つの接続で1つのリクエストだけを処理して閉じる、最小限の実装です。
+//! 待ち受けるのはループバックアドレスだけで、他のマシンからは接続できません。
+//! リクエストの行とヘッダーの長さ、1つの接続にかける時間には上限があり、超えたクライアントの接続は閉じます。
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// 1回の読み書きを待つ最大時間。応答しないクライアントでサーバーが止まらないようにする。
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+/// 1つの接続を処理する最大時間。少しずつ送り続けるクライアントでサーバーが止まらないようにする。
+const CONNECTION_DEADLINE: Duration = Duration::from_secs(15);
+/// リクエストの行とヘッダーを合わせた最大の長さ（バイト）。
+const MAX_HEADER_LEN: u64 = 8 * 1024;
+/// 読み捨てるリクエストの本文の最大の長さ（バイト）。
+const MAX_BODY_LEN: u64 = 64 * 1024;
+
+/// 受け取ったリクエスト。
+#[derive(Clone, Debug, Default)]
+pub struct HttpRequest {
+    /// メソッド（`GET`、`POST` など）。
+    pub method: String,
+    /// パス。クエリ文字列を含まない。
+    pub path: String,
+    /// `?` より後のクエリ文字列。
+    pub query: String,
+    /// ヘッダーの名前（小文字）と値の組。
+    pub headers: Vec<(String, String)>,
+}
+
+impl HttpRequest {
+    /// 指定された名前のヘッダーの値を返します。名前の大文字と小文字は区別しません。
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 指定された名前のクエリパラメーターの値を、パーセントエンコーディングを解いて返します。
+    pub fn query_param(&self, name: &str) -> Option<String> {
+        self.query.split('&').find_map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key == name).then(|| percent_decode(value))
+        })
+    }
+}
+
+/// 返す応答。
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    /// ステータス行の、コードと理由句（`200 OK` など）。
+    pub status: &'static str,
+    /// `Content-Type` ヘッダーの値。
+    pub content_type: &'static str,
+    /// 本文。
+    pub body: String,
+}
+
+/// ループバックアドレスで待ち受けるHTTPサーバーを表すハンドル。
+///
+/// 破棄（`drop`）されると待ち受けを終了し、サーバーのスレッドの終了を待ちます。
+pub struct LocalHttpServer {
+    /// 待ち受けているアドレス。
+    addr: SocketAddr,
+    /// サーバーのスレッドに終了を伝えるフラグ。
+    stop: Arc<AtomicBool>,
+    /// 接続を待ち受けるスレッド。
+    thread: Option<JoinHandle<()>>,
+}
+
+impl LocalHttpServer {
+    /// ループバックアドレスの指定されたポートで、リクエストの待ち受けを開始します。
+    ///
+    /// `handler` は、リクエストを受け取るたびにサーバーのスレッドから呼び出され、返す応答を決めます。
+    /// 接続は1つずつ順に処理します。
+    pub fn start<F>(port: u16, handler: F) -> io::Result<Self>
+    where
+        F: Fn(&HttpRequest) -> HttpResponse + Send + 'static,
+    {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        let addr = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let thread = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if thread_stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                // 個々の接続の失敗は、その接続を閉じるだけにとどめる。
+                if let Ok(stream) = stream {
+                    let _ = serve_client(stream, &handler);
+                }
+            }
+        });
+        Ok(LocalHttpServer {
+            addr,
+            stop,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for LocalHttpServer {
+    fn drop(&mut self) {
+        // 接続の待機から抜けさせるため、終了を伝えてから自分自身に接続する。
+        self.stop.store(true, Ordering::SeqCst);
+        let _ = TcpStream::connect_timeout(&self.addr, IO_TIMEOUT);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// 接続したクライアントからリクエストを1つ読み込み、応答を返します。
+///
+/// リクエストの行とヘッダーが長すぎる場合は `431`、形式が正しくない場合は `400` を返して閉じます。
+fn serve_client<F>(stream: TcpStream, handler: &F) -> io::Result<()>
+where
+    F: Fn(&HttpRequest) -> HttpResponse,
+{
+    let deadline = Instant::now() + CONNECTION_DEADLINE;
+    let mut head = BufReader::new(DeadlineReader {
+        stream: &stream,
+        deadline,
+    })
+    .take(MAX_HEADER_LEN);
+    let request = match read_head(&mut head) {
+        Ok(Some(request)) => request,
+        // 何も送らずに閉じた接続には応答しない。
+        Ok(None) => return Ok(()),
+        Err(HeadError::TooLarge) => {
+            return write_response(
+                &stream,
+                deadline,
+                &error_response("431 Request Header Fields Too Large"),
+            );
+        }
+        Err(HeadError::Malformed) => {
+            return write_response(&stream, deadline, &error_response("400 Bad Request"));
+        }
+        Err(HeadError::Io(e)) => return Err(e),
+    };
+
+    // 本文は使わないが、クライアントが送り終えるまで読み捨てる。
+    let body_len = request
+        .header("content-length")
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(0);
+    io::copy(
+        &mut head.into_inner().take(body_len.min(MAX_BODY_LEN)),
+        &mut io::sink(),
+    )?;
+
+    let response = handler(&request);
+    write_response(&stream, deadline, &response)
+}
+
+/// リクエストの行とヘッダーを読み込めなかった理由。
+enum HeadError {
+    /// 上限の長さまでに、ヘッダーの終わりの空行が届かなかった。
+    TooLarge,
+    /// リクエストの行が正しくない、またはUTF-8として解釈できない。
+    Malformed,
+    /// 読み込みに失敗した、または接続の期限を過ぎた。
+    Io(io::Error),
+}
+
+/// リクエストの行と、ヘッダーを空行まで読み込みます。
+///
+/// # 戻り値
+/// 読み込んだリクエスト。クライアントが何も送らずに接続を閉じた場合は `None`。
+fn read_head<R: BufRead>(head: &mut io::Take<R>) -> Result<Option<HttpRequest>, HeadError> {
+    let mut request_line = String::new();
+    if read_head_line(head, &mut request_line)? == 0 {
+        return Ok(None);
+    }
+    let mut words = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (words.next(), words.next()) else {
+        return Err(HeadError::Malformed);
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut request = HttpRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        query: query.to_string(),
+        headers: Vec::new(),
+    };
+
+    loop {
+        let mut line = String::new();
+        if read_head_line(head, &mut line)? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            request
+                .headers
+                .push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+    Ok(Some(request))
+}
+
+/// リクエストの行、またはヘッダーを1行読み込みます。
+///
+/// 上限の長さに達して行が途中で切れた場合は、`HeadError::TooLarge` を返します。
+fn read_head_line<R: BufRead>(
+    head: &mut io::Take<R>,
+    line: &mut String,
+) -> Result<usize, HeadError> {
+    let read = match head.read_line(line) {
+        Ok(read) => read,
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => return Err(HeadError::Malformed),
+        Err(e) => return Err(HeadError::Io(e)),
+    };
+    if head.limit() == 0 && !line.ends_with('\n') {
+        return Err(HeadError::TooLarge);
+    }
+    Ok(read)
+}
+
+/// ステータスだけを本文にした、エラーの応答を作成します。
+fn error_response(status: &'static str) -> HttpResponse {
+    HttpResponse {
+        status,
+        content_type: "text/plain; charset=utf-8",
+        body: format!("{status}\n"),
+    }
+}
+
+/// 応答をクライアントに書き込みます。
+fn write_response(
+    stream: &TcpStream,
+    deadline: Instant,
+    response: &HttpResponse,
+) -> io::Result<()> {
+    stream.set_write_timeout(Some(remaining(deadline)?.min(IO_TIMEOUT)))?;
+    let text = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.content_type,
+        response.body.len(),
+        response.body
+    );
+    let mut stream = stream;
+    stream.write_all(text.as_bytes())
+}
+
+/// 接続の期限を過ぎると読み込みを失敗させる、`TcpStream` の読み込み側。
+///
+/// 1回の読み込みを待つ時間は、`IO_TIMEOUT` と期限までの残り時間の短いほうになります。
+struct DeadlineReader<'a> {
+    /// 読み込む接続。
+    stream: &'a TcpStream,
+    /// 接続を処理する期限。
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stream
+            .set_read_timeout(Some(remaining(self.deadline)?.min(IO_TIMEOUT)))?;
+        let mut stream = self.stream;
+        stream.read(buf)
+    }
+}
+
+/// 期限までの残り時間を返します。期限を過ぎている場合は `TimedOut` のエラー。
+fn remaining(deadline: Instant) -> io::Result<Duration> {
+    deadline
+        .checked_duration_since(Instant::now())
+        .filter(|left| !left.is_zero())
+        .ok_or_else(|| io::Error::from(io::ErrorKind::TimedOut))
+}
+
+/// `%XX` の形式でエンコードされた文字と、空白を表す `+` を元に戻します。
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => match value
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(b) => {
+                    decoded.push(b);
+                    i += 3;
+                    continue;
+                }
+                None => decoded.push(b'%'),
+            },
+            b'+' => decoded.push(b' '),
+            b => decoded.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
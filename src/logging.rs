@@ -1,43 +1,159 @@
 //! アプリケーションのログ記録機能を管理するモジュール。
 //!
-//! 実行ファイルと同じディレクトリに `mydns.log` という名前でログファイルを作成します。
-//! ログファイルは指定された最大行数に達すると、古い行から自動的に削除されます（ログローテーション）。
+//! ログファイルは、既定では `%ProgramData%\MyDNSAdapter\mydns.log` に作成します。
+//! `--log-path` で別の場所を設定することもできます。サービスとCLIは同じログファイルに記録します。
+//! 以前のバージョンが実行ファイルと同じディレクトリに作成したログは、初回の書き込み時に移動します。
+//!
+//! サービスとCLIが同時に書き込んでも行が混ざったり失われたりしないよう、書き込みとローテーションは
+//! プロセス内ではミューテックスで、プロセス間ではロックファイル（`mydns.log.lock`）で直列化します。
+//!
+//! ログを安全に共有できるよう、書き込む前にアカウントのパスワードとBasic認証の資格情報を伏せ字にします。
+//! ログは常にファイルの末尾に追記し、ファイルが指定されたサイズに達すると、
+//! `mydns-YYYYMMDD.log` という日付付きの名前に変えてアーカイブします（ログローテーション）。
+//! アーカイブは設定に応じてgzipで圧縮し、設定された世代数を超えた古いものから削除します。
+//!
+//! `--verbose` が指定されている場合は、ログファイルに記録する行を標準エラー出力にも表示します。
+//! `--run-foreground` でサービスの処理をコンソールで実行している間は、標準出力に表示します。
+//!
+//! 通知処理の1回分（ラウンド）には短い相関ID（`[run 1a2b3c]`）を割り当て、その処理中に記録する行に付けます。
+//! 複数のアカウントを並行して通知したときに、入り混じったログから1回分の処理をたどれるようにするためです。
 
+use crate::console;
+use crate::gzip;
+use crate::registry::{Config, load_settings};
+use crate::store::load_all_configs;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use chrono::Local;
 use std::collections::VecDeque;
+use std::collections::hash_map::RandomState;
 use std::env;
-use std::fs::{File, OpenOptions};
-use std::io::{self, BufRead, BufReader, Write};
-use std::path::PathBuf;
+use std::fs::{self, File, OpenOptions};
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+use tokio::task::futures::TaskLocalFuture;
 
-/// ログファイルに保持する最大行数。これを超えると古いエントリが削除される。
-const MAX_LOG_LINES: usize = 10_000;
+/// ログファイル1つあたりの最大サイズ（バイト）。これを超える書き込みの前にローテーションする。
+pub const MAX_LOG_SIZE: u64 = 1024 * 1024;
 /// ログファイルの名前。
 const LOG_FILE_NAME: &str = "mydns.log";
+/// 既定のログファイルを置く、`%ProgramData%` の下のディレクトリ名。
+const LOG_DIR_NAME: &str = "MyDNSAdapter";
+/// ログの末尾を読み込む際に、一度にファイルの後方から読み込むバイト数。
+const TAIL_CHUNK_SIZE: u64 = 8 * 1024;
+/// ログの追跡（`--follow`）で、ファイルへの追記を確認する間隔。
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// ローテーションと追記を、プロセス内で直列化するためのロック。
+/// 複数のスレッドが同時にローテーションすると、世代の名前の付け替えが競合してしまうため。
+/// 他のプロセスとの直列化には、`lock_log_file` によるファイルロックを併用する。
+static LOG_LOCK: Mutex<()> = Mutex::new(());
+/// 決定済みのログファイルのパス。設定の読み込みと移行は、プロセスごとに最初の1回だけ行う。
+static LOG_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+/// ログに書き込む前に伏せ字にする文字列（パスワードと、Basic認証の資格情報）。
+/// 最初のログの書き込み時にアカウント設定から読み込み、`set_log_secrets` で更新する。
+static LOG_SECRETS: Mutex<Option<Vec<String>>> = Mutex::new(None);
+/// ログファイルに記録する行を、標準出力にも表示するかどうか。`set_console_echo` で切り替える。
+static CONSOLE_ECHO: AtomicBool = AtomicBool::new(false);
+tokio::task_local! {
+    /// 実行中の通知ラウンドの相関ID。`with_round_id` で設定し、空の場合はラウンドの外とみなす。
+    static ROUND_ID: String;
+}
+/// 伏せ字にした部分に書き込む文字列。
+const REDACTED: &str = "********";
+/// 伏せ字にする秘密の最短の長さ。短すぎる値を伏せると、関係のない文字まで消えてしまうため。
+const MIN_SECRET_LEN: usize = 4;
 
 /// ログファイルのフルパスを取得します。
 ///
-/// ログファイルは、アプリケーションの実行ファイルと同じディレクトリに配置されます。
+/// 設定でログファイルの場所が指定されていればそのパスを、なければ
+/// `%ProgramData%\MyDNSAdapter\mydns.log` を使います。
+/// 設定の変更は、次に起動したプロセスから反映されます。
 ///
 /// # Returns
 ///
 /// 成功した場合はログファイルの `PathBuf` を、失敗した場合は `io::Error` を返します。
-fn get_log_path() -> io::Result<PathBuf> {
-    // 現在の実行ファイルのパスを取得
-    let mut path = env::current_exe()?;
-    // パスからファイル名部分を削除し、ディレクトリパスにする
-    path.pop();
-    // ディレクトリパスにログファイル名を追加
-    path.push(LOG_FILE_NAME);
+pub fn get_log_path() -> io::Result<PathBuf> {
+    let mut cached = LOG_PATH.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(path) = cached.as_ref() {
+        return Ok(path.clone());
+    }
+    let configured = load_settings().unwrap_or_default().log_path;
+    let path = if configured.is_empty() {
+        default_log_path()
+    } else {
+        PathBuf::from(configured)
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    migrate_legacy_log(&path);
+    *cached = Some(path.clone());
     Ok(path)
 }
 
+/// このプロセスのログの書き込み先を、設定にかかわらず指定したファイルに変更します。
+///
+/// シミュレーションモードが、模擬アカウントの通知を実際のログに残さないために使います。
+pub fn set_log_path(path: PathBuf) {
+    *LOG_PATH.lock().unwrap_or_else(PoisonError::into_inner) = Some(path);
+}
+
+/// 既定のログファイルのパス（`%ProgramData%\MyDNSAdapter\mydns.log`）を返します。
+///
+/// サービスは `Program Files` など書き込みが制限された場所から実行されることがあるため、
+/// 実行ファイルのディレクトリではなく、マシン全体のデータ用のディレクトリを使います。
+pub fn default_log_path() -> PathBuf {
+    let program_data =
+        env::var_os("ProgramData").map_or_else(|| PathBuf::from(r"C:\ProgramData"), PathBuf::from);
+    program_data.join(LOG_DIR_NAME).join(LOG_FILE_NAME)
+}
+
+/// 以前のバージョンが実行ファイルと同じディレクトリに作成したログを、新しい場所に移動します。
+///
+/// 新しい場所にログがまだない場合だけ移動します。
+/// 移動に失敗しても、ログの記録は新しい場所で続けます。
+fn migrate_legacy_log(log_path: &Path) {
+    let Ok(exe) = env::current_exe() else {
+        return;
+    };
+    let legacy_path = exe.with_file_name(LOG_FILE_NAME);
+    if legacy_path == log_path || !legacy_path.exists() || log_path.exists() {
+        return;
+    }
+    // ドライブをまたぐ場合は名前の変更ができないため、コピーしてから削除する。
+    if fs::rename(&legacy_path, log_path).is_err() && fs::copy(&legacy_path, log_path).is_ok() {
+        let _ = fs::remove_file(&legacy_path);
+    }
+}
+
 /// 情報レベルのメッセージをログファイルに記録します。
 ///
 /// 内部で `log_to_file` を呼び出します。ファイルへの書き込みに失敗した場合は、
 /// 標準エラー出力にフォールバックしてエラーメッセージを表示します。
 pub fn log_info(message: &str) {
-    if let Err(e) = log_to_file("INFO", message) {
+    if let Err(e) = log_to_file(LogLevel::Info, message) {
+        // ログファイルへの書き込みに失敗した場合のフォールバック処理。
+        eprintln!(
+            "[{}] [LOG-ERROR] Failed to write to log file: {}",
+            Local::now().format("%Y-%m-%d %H:%M:%S"),
+            e
+        );
+    }
+}
+
+/// 警告レベルのメッセージをログファイルに記録します。
+///
+/// 内部で `log_to_file` を呼び出します。ファイルへの書き込みに失敗した場合は、
+/// 標準エラー出力にフォールバックしてエラーメッセージを表示します。
+pub fn log_warn(message: &str) {
+    if let Err(e) = log_to_file(LogLevel::Warn, message) {
         // ログファイルへの書き込みに失敗した場合のフォールバック処理。
         eprintln!(
             "[{}] [LOG-ERROR] Failed to write to log file: {}",
@@ -52,7 +168,7 @@ pub fn log_info(message: &str) {
 /// 内部で `log_to_file` を呼び出します。ファイルへの書き込みに失敗した場合は、
 /// 標準エラー出力にフォールバックしてエラーメッセージを表示します。
 pub fn log_error(message: &str) {
-    if let Err(e) = log_to_file("ERROR", message) {
+    if let Err(e) = log_to_file(LogLevel::Error, message) {
         // ログファイルへの書き込みに失敗した場合のフォールバック処理。
         eprintln!(
             "[{}] [LOG-ERROR] Failed to write to log file: {}",
@@ -62,52 +178,555 @@ pub fn log_error(message: &str) {
     }
 }
 
+/// `--verbose` が指定されている場合だけ、詳細な情報をログファイルに記録します。
+///
+/// HTTP通信の内容など、普段はログを読みにくくするだけの情報に使います。
+pub fn log_detail(message: &str) {
+    if console::verbosity() == console::Verbosity::Verbose {
+        log_info(message);
+    }
+}
+
 /// ログファイルへの書き込みとローテーションを行う中心的な関数。
 ///
 /// この関数は、以下の手順でログを追記・管理します。
-/// 1. 既存のログファイルをすべて読み込む。
-/// 2. 新しいログメッセージを末尾に追加する。
-/// 3. ログの総行数が `MAX_LOG_LINES` を超えた場合、超過分を古い行から削除する。
-/// 4. 更新されたログ内容でファイル全体を上書きする。
-///
-/// NOTE: この実装は、ログファイルが巨大になるとパフォーマンスに影響を与える可能性がありますが、
-///       シンプルさと堅牢性を優先しています。
-fn log_to_file(level: &str, message: &str) -> io::Result<()> {
+/// 1. 新しい行を追記するとファイルが `MAX_LOG_SIZE` を超える場合、ローテーションする。
+/// 2. 新しいログメッセージをファイルの末尾に追記する。
+///
+/// 既存の内容を読み込まずに追記するため、ログファイルの大きさに関係なく一定の時間で書き込めます。
+fn log_to_file(level: LogLevel, message: &str) -> io::Result<()> {
+    let message = match current_round_id() {
+        Some(id) => format!("[run {}] {}", id, redact(message)),
+        None => redact(message),
+    };
+    if CONSOLE_ECHO.load(Ordering::Relaxed) {
+        println!(
+            "[{}] [{}] {}",
+            Local::now().format("%Y-%m-%d %H:%M:%S"),
+            level.as_str(),
+            message
+        );
+    } else if console::verbosity() == console::Verbosity::Verbose {
+        eprintln!(
+            "[{}] [{}] {}",
+            Local::now().format("%Y-%m-%d %H:%M:%S"),
+            level.as_str(),
+            message
+        );
+    }
     let log_path = get_log_path()?;
-    let now = Local::now().format("%Y-%m-%d %H:%M:%S");
-    let new_line = format!("[{}] [{}] {}", now, level, message);
+    write_log_line(&log_path, level.as_str(), &message)
+}
 
-    // 手順1: ファイルが存在する場合、すべての行を読み込んでVecDequeに格納する。
-    let mut lines: VecDeque<String> = if log_path.exists() {
-        let file = File::open(&log_path)?;
-        let reader = BufReader::new(file);
-        reader.lines().collect::<Result<_, _>>()?
-    } else {
-        // ファイルが存在しない場合は空のVecDequeから開始する。
-        VecDeque::new()
-    };
+/// 通知ラウンドの相関IDを新しく作成します。
+///
+/// ログの行を見分けるためだけに使うため、暗号学的な強度は不要です。
+/// 標準ライブラリの `RandomState` が生成するランダムな鍵から、16進数6桁を作ります。
+pub fn new_round_id() -> String {
+    format!(
+        "{:06x}",
+        RandomState::new().build_hasher().finish() & 0xff_ffff
+    )
+}
 
-    // 手順2: 新しいログ行を末尾に追加する。
-    lines.push_back(new_line);
+/// 相関IDを設定して `future` を実行します。実行中に記録するログの行には、このIDが付きます。
+pub fn with_round_id<F: Future>(id: String, future: F) -> TaskLocalFuture<String, F> {
+    ROUND_ID.scope(id, future)
+}
 
-    // 手順3: 行数が上限を超えている場合、古い行を先頭から削除する。
-    if lines.len() > MAX_LOG_LINES {
-        lines.drain(0..(lines.len() - MAX_LOG_LINES));
+/// 現在の相関IDを引き継いで `future` を実行します。
+///
+/// 別のタスクとして実行する処理（`tokio::spawn` など）にも、同じIDを付けるために使います。
+/// ラウンドの外で呼び出した場合は、IDを付けずに実行します。
+pub fn inherit_round_id<F: Future>(future: F) -> TaskLocalFuture<String, F> {
+    ROUND_ID.scope(current_round_id().unwrap_or_default(), future)
+}
+
+/// 現在の通知ラウンドの相関IDを返します。ラウンドの外では `None` を返します。
+fn current_round_id() -> Option<String> {
+    ROUND_ID
+        .try_with(|id| id.clone())
+        .ok()
+        .filter(|id| !id.is_empty())
+}
+
+/// ログファイルに記録する行を、標準出力にも表示するかどうかを切り替えます。
+///
+/// サービスの処理をコンソールで実行する `--run-foreground` で、動作をその場で確認できるようにします。
+pub fn set_console_echo(enabled: bool) {
+    CONSOLE_ECHO.store(enabled, Ordering::Relaxed);
+}
+
+/// 書き込み中のログがあれば終わるのを待ち、ログファイルの内容をディスクに書き出します。
+///
+/// サービスの停止の直前に呼び出し、シャットダウンで電源が切れてもログの末尾が失われないようにします。
+/// 失敗しても停止処理を妨げないよう、エラーは無視します。
+pub fn flush_logs() {
+    let _guard = LOG_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Ok(log_path) = get_log_path()
+        && let Ok(file) = OpenOptions::new().write(true).open(log_path)
+    {
+        let _ = file.sync_all();
     }
+}
 
-    // 手順4: ファイルを上書きモードで開き、更新されたすべての行を書き戻す。
+/// ログで伏せ字にする秘密を、アカウント設定から設定し直します。
+///
+/// アカウント設定を読み込み直したときに呼び出し、新しいパスワードも伏せ字にされるようにします。
+pub fn set_log_secrets(configs: &[Config]) {
+    let secrets = configs
+        .iter()
+        .filter(|c| !c.password.is_empty())
+        .flat_map(|c| {
+            // Basic認証のヘッダーに含まれる「MasterID:パスワード」のBase64表現も伏せる。
+            let credentials = BASE64.encode(format!("{}:{}", c.master_id, c.password));
+            [c.password.clone(), credentials]
+        })
+        .filter(|s| s.len() >= MIN_SECRET_LEN)
+        .collect();
+    *LOG_SECRETS.lock().unwrap_or_else(PoisonError::into_inner) = Some(secrets);
+}
+
+/// メッセージに含まれるパスワードとBasic認証の資格情報を伏せ字にします。
+fn redact(message: &str) -> String {
+    let mut secrets = LOG_SECRETS.lock().unwrap_or_else(PoisonError::into_inner);
+    if secrets.is_none() {
+        drop(secrets);
+        set_log_secrets(&load_all_configs().unwrap_or_default());
+        secrets = LOG_SECRETS.lock().unwrap_or_else(PoisonError::into_inner);
+    }
+    let mut redacted = message.to_string();
+    // 長い秘密から先に置き換え、他の秘密の一部だけが残らないようにする。
+    let mut sorted: Vec<&String> = secrets.iter().flatten().collect();
+    sorted.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    for secret in sorted {
+        if redacted.contains(secret.as_str()) {
+            redacted = redacted.replace(secret.as_str(), REDACTED);
+        }
+    }
+    redact_basic_auth(&redacted)
+}
+
+/// `Basic <Base64>` の形式の資格情報を伏せ字にします。
+///
+/// 登録されていない資格情報（設定ファイルにないアカウントなど）が、リクエストの記録などから
+/// ログに紛れ込んだ場合に備えます。
+fn redact_basic_auth(message: &str) -> String {
+    const SCHEME: &str = "basic ";
+    let lower = message.to_ascii_lowercase();
+    let mut result = String::with_capacity(message.len());
+    let mut rest = 0;
+    let mut search = 0;
+    while let Some(found) = lower[search..].find(SCHEME) {
+        let token_start = search + found + SCHEME.len();
+        let token_len = message[token_start..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '+' || c == '/' || c == '='))
+            .unwrap_or(message.len() - token_start);
+        // 「Basic認証」のような通常の文章は伏せないよう、十分な長さのトークンだけを対象にする。
+        if token_len >= MIN_SECRET_LEN * 2 {
+            result.push_str(&message[rest..token_start]);
+            result.push_str(REDACTED);
+            rest = token_start + token_len;
+        }
+        search = token_start + token_len;
+    }
+    result.push_str(&message[rest..]);
+    result
+}
+
+/// 指定されたパスのログファイルに1行を追記し、必要に応じてローテーションを行います。
+///
+/// 処理の詳細は `log_to_file` を参照してください。ベンチマークなど、
+/// 通常のログファイル以外を対象にしたい場合に直接呼び出します。
+pub fn write_log_line(log_path: &Path, level: &str, message: &str) -> io::Result<()> {
+    let now = Local::now().format("%Y-%m-%d %H:%M:%S");
+    let new_line = format!("[{}] [{}] {}\n", now, level, message);
+    let _guard = LOG_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    // ロックファイルを用意できない場合も、ログが失われないよう書き込みは続ける。
+    let _file_lock = lock_log_file(log_path).ok();
+
+    // 手順1: 追記によって上限を超える場合は、先にローテーションする。
+    // 空のファイルには、上限を超える1行でもそのまま書き込む。
+    let current_size = fs::metadata(log_path).map(|m| m.len()).unwrap_or(0);
+    if current_size > 0 && current_size + new_line.len() as u64 > MAX_LOG_SIZE {
+        rotate_log_files(log_path)?;
+    }
+
+    // 手順2: ファイルを追記モードで開き、新しい行を書き込む。
     // create(true): ファイルがなければ新規作成する。
-    // truncate(true): ファイルを開く際に内容を空にする。
+    // 1行を1回の書き込みで追記し、他のプロセスの行と混ざらないようにする。
     let mut file = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(log_path)?;
+    file.write_all(new_line.as_bytes())
+}
+
+/// ログファイルと、そのアーカイブ、ロックファイルをすべて削除します。
+///
+/// 既定の場所（`%ProgramData%\MyDNSAdapter`）のディレクトリは、空になれば削除します。
+/// ログの場所の設定を読み込むため、レジストリの設定を削除する前に呼び出してください。
+/// 削除した後にログを記録すると、ログファイルが作り直されます。
+pub fn delete_log_files() -> io::Result<()> {
+    let log_path = get_log_path()?;
+    let _guard = LOG_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    for path in log_files_for(&log_path)
+        .into_iter()
+        .chain([log_lock_path(&log_path)])
+    {
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+    if log_path == default_log_path()
+        && let Some(dir) = log_path.parent()
+    {
+        // 他のファイルが残っている場合は失敗するが、問題ではない。
+        let _ = fs::remove_dir(dir);
+    }
+    Ok(())
+}
+
+/// ログファイルに対応するロックファイルのパス（`mydns.log.lock`）を返します。
+pub fn log_lock_path(log_path: &Path) -> PathBuf {
+    let mut name = log_path.as_os_str().to_owned();
+    name.push(".lock");
+    PathBuf::from(name)
+}
+
+/// ロックファイルを排他的にロックし、他のプロセスのログの書き込みが終わるのを待ちます。
+///
+/// ロックは、返された `File` が破棄されたときに解除されます。
+/// ロックファイルは他のアカウントのプロセスが作成したものでも使えるよう、
+/// 書き込みできない場合は読み取り専用で開いてロックします。
+fn lock_log_file(log_path: &Path) -> io::Result<File> {
+    let lock_path = log_lock_path(log_path);
+    let file = OpenOptions::new()
+        .read(true)
         .write(true)
         .create(true)
-        .truncate(true)
-        .open(&log_path)?;
+        .truncate(false)
+        .open(&lock_path)
+        .or_else(|_| File::open(&lock_path))?;
+    file.lock()?;
+    Ok(file)
+}
+
+/// 現在のログファイルを日付付きのアーカイブに切り替えます。
+///
+/// `mydns.log` を `mydns-YYYYMMDD.log` に名前を変更し（同じ日に複数回ローテーションした場合は
+/// `mydns-YYYYMMDD-1.log` のように番号を付けます）、設定に応じてgzipで圧縮します。
+/// その後、設定された世代数を超える古いアーカイブを削除します。
+fn rotate_log_files(log_path: &Path) -> io::Result<()> {
+    let settings = load_settings().unwrap_or_default();
+    let archive = next_archive_path(log_path);
+    fs::rename(log_path, &archive)?;
 
-    // 更新されたログの内容をファイルに書き込む。
-    for line in lines {
-        writeln!(file, "{}", line)?;
+    // 圧縮に失敗した場合は、圧縮していないアーカイブをそのまま残す。
+    if settings.log_archive_compress {
+        let mut gz_name = archive.as_os_str().to_owned();
+        gz_name.push(".gz");
+        let compressed =
+            fs::read(&archive).and_then(|data| fs::write(&gz_name, gzip::compress(&data)));
+        if compressed.is_ok() {
+            let _ = fs::remove_file(&archive);
+        } else {
+            let _ = fs::remove_file(&gz_name);
+        }
     }
 
+    for (old, _) in list_archives(log_path)
+        .into_iter()
+        .skip(settings.log_archive_count as usize)
+    {
+        let _ = fs::remove_file(old);
+    }
     Ok(())
 }
+
+/// ログファイルの名前を、アーカイブの名前を組み立てるための語幹と拡張子（`.log`）に分けます。
+fn split_log_name(log_path: &Path) -> (String, String) {
+    let stem = log_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = log_path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (stem, extension)
+}
+
+/// 今日の日付のアーカイブのうち、まだ使われていない名前を返します。
+fn next_archive_path(log_path: &Path) -> PathBuf {
+    let (stem, extension) = split_log_name(log_path);
+    let date = Local::now().format("%Y%m%d");
+    (0..)
+        .map(|n| {
+            let suffix = if n == 0 {
+                String::new()
+            } else {
+                format!("-{}", n)
+            };
+            log_path.with_file_name(format!("{}-{}{}{}", stem, date, suffix, extension))
+        })
+        .find(|candidate| {
+            let mut gz_name = candidate.as_os_str().to_owned();
+            gz_name.push(".gz");
+            !candidate.exists() && !Path::new(&gz_name).exists()
+        })
+        .unwrap_or_else(|| log_path.with_extension("old"))
+}
+
+/// ログファイルと同じディレクトリにあるアーカイブを、新しい順に返します。
+///
+/// 各要素は、アーカイブのパスと、並べ替えに使う（日付, 番号）の組です。
+fn list_archives(log_path: &Path) -> Vec<(PathBuf, (String, u32))> {
+    let (stem, extension) = split_log_name(log_path);
+    let prefix = format!("{}-", stem);
+    let dir = log_path.parent().unwrap_or(Path::new("."));
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut archives: Vec<_> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let rest = name.strip_prefix(&prefix)?;
+            let rest = rest.strip_suffix(".gz").unwrap_or(rest);
+            let rest = rest.strip_suffix(extension.as_str())?;
+            let (date, number) = match rest.split_once('-') {
+                Some((date, n)) => (date, n.parse().ok()?),
+                None => (rest, 0),
+            };
+            if date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            Some((entry.path(), (date.to_string(), number)))
+        })
+        .collect();
+    archives.sort_by(|a, b| b.1.cmp(&a.1));
+    archives
+}
+
+/// 指定されたログファイルと、そのアーカイブのパスを、新しい順に返します。
+///
+/// `mydns.log` に対しては `mydns.log` と、存在する `mydns-YYYYMMDD.log`（`.gz` を含む）を返します。
+/// 現在のログファイル自体が存在するかどうかは確認しません。
+pub fn log_files_for(log_path: &Path) -> Vec<PathBuf> {
+    let mut files = vec![log_path.to_path_buf()];
+    files.extend(list_archives(log_path).into_iter().map(|(path, _)| path));
+    files
+}
+
+/// ログの重大度。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// 情報。
+    Info,
+    /// 警告。
+    Warn,
+    /// エラー。
+    Error,
+}
+
+impl LogLevel {
+    /// ログファイルに記録される表記（`INFO` など）を返します。
+    fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    /// コマンドラインで指定された重大度を解釈します。大文字と小文字は区別しません。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "info" => Ok(LogLevel::Info),
+            "warn" | "warning" => Ok(LogLevel::Warn),
+            "error" => Ok(LogLevel::Error),
+            _ => Err(format!("unknown log level: {} (info, warn, error)", s)),
+        }
+    }
+}
+
+/// ログを表示するときの絞り込み条件。
+#[derive(Clone, Debug, Default)]
+pub struct LogFilter {
+    /// この重大度以上の行だけを表示する。
+    pub min_level: Option<LogLevel>,
+    /// このMasterIDに関する行だけを表示する。
+    pub account: Option<String>,
+}
+
+impl LogFilter {
+    /// 絞り込み条件が何も指定されていないかどうか。
+    fn is_empty(&self) -> bool {
+        self.min_level.is_none() && self.account.is_none()
+    }
+
+    /// ログの1行が条件に一致するかどうかを判定します。
+    ///
+    /// 行は `[日時] [重大度] [MasterID] メッセージ` の形式です。
+    /// アカウントに関係しない行には、MasterIDの部分がありません。
+    pub fn matches(&self, line: &str) -> bool {
+        if let Some(min_level) = self.min_level {
+            let level = line
+                .split_once("] [")
+                .and_then(|(_, rest)| rest.split_once(']'))
+                .and_then(|(level, _)| level.parse::<LogLevel>().ok());
+            if level.is_none_or(|level| level < min_level) {
+                return false;
+            }
+        }
+        if let Some(account) = &self.account
+            && !line.contains(&format!("[{}]", account))
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// ログの末尾から最大 `count` 行を、古い順に並べて返します。
+///
+/// 絞り込み条件がない場合は、ファイル全体を読み込まず、末尾からシークして必要な分だけ読み込むため、
+/// ログファイルが大きくても即座に結果を返します。
+/// 絞り込み条件がある場合は、条件に一致する行を探してファイル全体を読み込みます。
+/// ローテーションされた古いファイルがある場合は、新しいファイルで行数が足りなければ
+/// 古いファイルへとさかのぼって読み込みます。
+pub fn read_log_tail(count: usize, filter: &LogFilter) -> io::Result<Vec<String>> {
+    let mut lines: VecDeque<String> = VecDeque::new();
+    for path in log_files_newest_first()? {
+        if lines.len() >= count {
+            break;
+        }
+        // 圧縮されたアーカイブは読み込めないため、そこでさかのぼるのをやめる。
+        if path.extension().is_some_and(|e| e == "gz") {
+            break;
+        }
+        if !path.exists() {
+            continue;
+        }
+        let remaining = count - lines.len();
+        let tail = if filter.is_empty() {
+            tail_file(&path, remaining)?
+        } else {
+            let matched: Vec<String> = BufReader::new(File::open(&path)?)
+                .lines()
+                .collect::<io::Result<Vec<_>>>()?
+                .into_iter()
+                .filter(|line| filter.matches(line))
+                .collect();
+            let skip = matched.len().saturating_sub(remaining);
+            matched.into_iter().skip(skip).collect()
+        };
+        // 新しいファイルから読んだ行の前に、古いファイルの行を差し込んでいく。
+        for line in tail.into_iter().rev() {
+            lines.push_front(line);
+        }
+    }
+    Ok(lines.into())
+}
+
+/// ログファイルへの追記を監視し、条件に一致する新しい行を `on_line` に渡し続けます。
+///
+/// 呼び出した時点のファイルの末尾から監視を始め、プロセスが終了する（Ctrl+C）まで戻りません。
+/// ローテーションによってファイルが小さくなった場合は、新しいファイルの先頭から読み直します。
+pub fn follow_log<F: FnMut(&str)>(filter: &LogFilter, mut on_line: F) -> io::Result<()> {
+    let path = get_log_path()?;
+    let mut pos = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    // 書き込みの途中で読み込んだ、改行で終わっていない行の断片。
+    let mut partial: Vec<u8> = Vec::new();
+    loop {
+        thread::sleep(FOLLOW_POLL_INTERVAL);
+        let Ok(mut file) = File::open(&path) else {
+            continue;
+        };
+        let len = file.metadata()?.len();
+        if len < pos {
+            // ローテーションされた。
+            pos = 0;
+            partial.clear();
+        }
+        if len == pos {
+            continue;
+        }
+        file.seek(SeekFrom::Start(pos))?;
+        let mut chunk = Vec::new();
+        file.take(len - pos).read_to_end(&mut chunk)?;
+        pos = len;
+        partial.extend_from_slice(&chunk);
+        // 最後の改行までを行として処理し、残りは次回に持ち越す。
+        let Some(last_newline) = partial.iter().rposition(|&b| b == b'\n') else {
+            continue;
+        };
+        let rest = partial.split_off(last_newline + 1);
+        for line in partial.split(|&b| b == b'\n') {
+            let line = String::from_utf8_lossy(line);
+            let line = line.trim_end_matches('\r');
+            if !line.is_empty() && filter.matches(line) {
+                on_line(line);
+            }
+        }
+        partial = rest;
+    }
+}
+
+/// 現在のログファイルと、ローテーションされた古いログファイルのパスを、新しい順に返します。
+fn log_files_newest_first() -> io::Result<Vec<PathBuf>> {
+    Ok(log_files_for(&get_log_path()?))
+}
+
+/// ひとつのファイルの末尾から最大 `count` 行を読み込みます。
+///
+/// ファイルの末尾から `TAIL_CHUNK_SIZE` ずつさかのぼって読み込み、
+/// 必要な行数分の改行が見つかった時点で読み込みをやめます。
+fn tail_file(path: &Path, count: usize) -> io::Result<Vec<String>> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    let mut file = File::open(path)?;
+    let mut pos = file.metadata()?.len();
+    // ファイル中の `pos` 以降のバイト列。
+    let mut buf: Vec<u8> = Vec::new();
+    let mut newlines = 0;
+
+    // 末尾の改行を含めて `count + 1` 個の改行が見つかれば、`count` 行が完全に揃う。
+    while pos > 0 && newlines <= count {
+        let read_size = TAIL_CHUNK_SIZE.min(pos);
+        pos -= read_size;
+        file.seek(SeekFrom::Start(pos))?;
+        let mut chunk = vec![0u8; read_size as usize];
+        file.read_exact(&mut chunk)?;
+        newlines += chunk.iter().filter(|&&b| b == b'\n').count();
+        chunk.extend_from_slice(&buf);
+        buf = chunk;
+    }
+
+    // 行の区切りはバイト列で判定し、文字コードの変換は行ごとに行う。
+    let mut lines: Vec<String> = buf
+        .split(|&b| b == b'\n')
+        .map(|line| {
+            String::from_utf8_lossy(line)
+                .trim_end_matches('\r')
+                .to_string()
+        })
+        .collect();
+    // 末尾の改行の後ろにできる空要素を取り除く。
+    if lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    // ファイルの途中から読み込んだ場合、先頭の行は途中から始まっているため捨てる。
+    if pos > 0 && !lines.is_empty() {
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(count);
+    Ok(lines.split_off(skip))
+}
@@ -0,0 +1,291 @@
+//! POP3/IMAP/FTPでのログインによって、MyDNS.JPにアドレスを通知するモジュール。
+//!
+//! MyDNS.JPは、MasterIDとパスワードでPOP3/IMAP/FTPのサーバーにログインした接続元のアドレスも、
+//! HTTPSでの通知と同じように受け付けます。HTTPが遮断されたネットワークからでも通知できるよう、
+//! アカウントの通知の方法（`NotifyMethod`）で選べます。
+//!
+//! いずれのプロトコルも暗号化しない接続でログインするため、パスワードは平文で送られます。
+//! プロキシは使わず、送信元のインターフェイスが設定されていれば、そのアドレスから直接接続します。
+
+use crate::registry::NotifyMethod;
+use std::fmt;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpSocket, TcpStream, lookup_host};
+use tokio::time;
+
+/// IPv4アドレスを通知するときにログインするサーバー。
+const IPV4_LOGIN_HOST: &str = "ipv4.mydns.jp";
+/// IPv6アドレスを通知するときにログインするサーバー。
+const IPV6_LOGIN_HOST: &str = "ipv6.mydns.jp";
+/// IMAPの `LOGIN` コマンドに付けるタグ。
+const IMAP_TAG: &str = "a1";
+
+/// ログインによる通知の失敗。
+#[derive(Debug)]
+pub enum LoginFailure {
+    /// 名前の解決、接続、送受信の失敗、またはタイムアウト。
+    Io(io::Error),
+    /// サーバーがログインを拒否した（MasterIDまたはパスワードの誤り）。
+    Rejected(String),
+    /// サーバーの応答が想定した形式でなかった。
+    Protocol(String),
+}
+
+impl fmt::Display for LoginFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoginFailure::Io(e) => write!(f, "{}", e),
+            LoginFailure::Rejected(reply) | LoginFailure::Protocol(reply) => write!(f, "{}", reply),
+        }
+    }
+}
+
+impl From<io::Error> for LoginFailure {
+    fn from(e: io::Error) -> Self {
+        LoginFailure::Io(e)
+    }
+}
+
+/// 通知の方法とプロトコルから、ログインするサーバーとポートを返します。
+///
+/// HTTPでの通知の場合は `None` を返します。
+fn login_server(method: NotifyMethod, ipv6: bool) -> Option<(&'static str, u16)> {
+    let host = if ipv6 {
+        IPV6_LOGIN_HOST
+    } else {
+        IPV4_LOGIN_HOST
+    };
+    match method {
+        NotifyMethod::Http => None,
+        NotifyMethod::Pop3 => Some((host, 110)),
+        NotifyMethod::Imap => Some((host, 143)),
+        NotifyMethod::Ftp => Some((host, 21)),
+    }
+}
+
+/// ログや画面に表示する、ログインするサーバーを表すURL（`pop3://ipv4.mydns.jp:110` など）を返します。
+///
+/// HTTPでの通知の場合は `None` を返します。
+pub fn login_endpoint(method: NotifyMethod, ipv6: bool) -> Option<&'static str> {
+    Some(match (method, ipv6) {
+        (NotifyMethod::Http, _) => return None,
+        (NotifyMethod::Pop3, false) => "pop3://ipv4.mydns.jp:110",
+        (NotifyMethod::Pop3, true) => "pop3://ipv6.mydns.jp:110",
+        (NotifyMethod::Imap, false) => "imap://ipv4.mydns.jp:143",
+        (NotifyMethod::Imap, true) => "imap://ipv6.mydns.jp:143",
+        (NotifyMethod::Ftp, false) => "ftp://ipv4.mydns.jp:21",
+        (NotifyMethod::Ftp, true) => "ftp://ipv6.mydns.jp:21",
+    })
+}
+
+/// MyDNS.JPのサーバーにログインして、接続元のアドレスを通知します。
+///
+/// `ipv6` で通知するプロトコルを選び、そのプロトコルのアドレスだけに接続します。
+/// `local` が指定されている場合は、そのアドレスから接続します。
+/// 接続からログアウトまでを `timeout` 以内に終えられなければ、タイムアウトとして失敗します。
+/// MasterIDやパスワードが改行を含む場合は、送信せずに拒否として扱います。
+/// ログインに成功した後のログアウトの失敗は無視します。
+pub async fn login(
+    method: NotifyMethod,
+    ipv6: bool,
+    user: &str,
+    password: &str,
+    local: Option<IpAddr>,
+    timeout: Duration,
+) -> Result<(), LoginFailure> {
+    let Some((host, port)) = login_server(method, ipv6) else {
+        return Ok(());
+    };
+    // 改行を含む値は、続けて別のコマンドを送ることになるため送信しない。
+    if [user, password].iter().any(|v| v.contains(['\r', '\n'])) {
+        return Err(LoginFailure::Rejected(
+            "credentials contain a line break".to_string(),
+        ));
+    }
+    time::timeout(timeout, async {
+        let mut session = Session::connect(host, port, ipv6, local).await?;
+        match method {
+            NotifyMethod::Http => Ok(()),
+            NotifyMethod::Pop3 => session.pop3(user, password).await,
+            NotifyMethod::Imap => session.imap(user, password).await,
+            NotifyMethod::Ftp => session.ftp(user, password).await,
+        }
+    })
+    .await
+    .map_err(|_| LoginFailure::Io(io::ErrorKind::TimedOut.into()))?
+}
+
+/// サーバーとの1つの接続。
+struct Session {
+    stream: BufReader<TcpStream>,
+}
+
+impl Session {
+    /// サーバーの名前を解決し、指定したプロトコルのアドレスに接続します。
+    async fn connect(
+        host: &str,
+        port: u16,
+        ipv6: bool,
+        local: Option<IpAddr>,
+    ) -> Result<Self, LoginFailure> {
+        let addr = lookup_host((host, port))
+            .await?
+            .find(|addr| addr.is_ipv6() == ipv6)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!(
+                        "{}: no {} address",
+                        host,
+                        if ipv6 { "IPv6" } else { "IPv4" }
+                    ),
+                )
+            })?;
+        let socket = if ipv6 {
+            TcpSocket::new_v6()?
+        } else {
+            TcpSocket::new_v4()?
+        };
+        if let Some(local) = local {
+            socket.bind(SocketAddr::new(local, 0))?;
+        }
+        Ok(Session {
+            stream: BufReader::new(socket.connect(addr).await?),
+        })
+    }
+
+    /// 1行の応答を読み取り、末尾の改行を除いて返します。接続が閉じられた場合はエラーを返します。
+    async fn read_line(&mut self) -> Result<String, LoginFailure> {
+        let mut line = String::new();
+        if self.stream.read_line(&mut line).await? == 0 {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        Ok(line.trim_end().to_string())
+    }
+
+    /// 1行のコマンドを送信します。
+    async fn send(&mut self, command: &str) -> Result<(), LoginFailure> {
+        let stream = self.stream.get_mut();
+        stream
+            .write_all(format!("{}\r\n", command).as_bytes())
+            .await?;
+        stream.flush().await?;
+        Ok(())
+    }
+
+    /// POP3の `USER`/`PASS` でログインします。
+    async fn pop3(&mut self, user: &str, password: &str) -> Result<(), LoginFailure> {
+        let expect_ok = |reply: String| {
+            if reply.starts_with("+OK") {
+                Ok(())
+            } else if reply.starts_with("-ERR") {
+                Err(LoginFailure::Rejected(reply))
+            } else {
+                Err(LoginFailure::Protocol(reply))
+            }
+        };
+        let greeting = self.read_line().await?;
+        if !greeting.starts_with("+OK") {
+            return Err(LoginFailure::Protocol(greeting));
+        }
+        self.send(&format!("USER {}", user)).await?;
+        expect_ok(self.read_line().await?)?;
+        self.send(&format!("PASS {}", password)).await?;
+        expect_ok(self.read_line().await?)?;
+        let _ = self.send("QUIT").await;
+        Ok(())
+    }
+
+    /// IMAPの `LOGIN` でログインします。
+    async fn imap(&mut self, user: &str, password: &str) -> Result<(), LoginFailure> {
+        let greeting = self.read_line().await?;
+        if !greeting.starts_with("* OK") {
+            return Err(LoginFailure::Protocol(greeting));
+        }
+        self.send(&format!(
+            "{} LOGIN {} {}",
+            IMAP_TAG,
+            imap_quote(user),
+            imap_quote(password)
+        ))
+        .await?;
+        // タグの付いた応答が届くまで、途中の未タグの応答（`* CAPABILITY` など）は読み飛ばす。
+        let reply = loop {
+            let line = self.read_line().await?;
+            if let Some(rest) = line.strip_prefix(IMAP_TAG)
+                && rest.starts_with(' ')
+            {
+                break rest.trim_start().to_string();
+            }
+        };
+        if reply.starts_with("OK") {
+            let _ = self.send("a2 LOGOUT").await;
+            Ok(())
+        } else if reply.starts_with("NO") {
+            Err(LoginFailure::Rejected(reply))
+        } else {
+            Err(LoginFailure::Protocol(reply))
+        }
+    }
+
+    /// FTPの `USER`/`PASS` でログインします。
+    async fn ftp(&mut self, user: &str, password: &str) -> Result<(), LoginFailure> {
+        let (code, greeting) = self.read_ftp_reply().await?;
+        if code != 220 {
+            return Err(LoginFailure::Protocol(greeting));
+        }
+        self.send(&format!("USER {}", user)).await?;
+        let (code, reply) = self.read_ftp_reply().await?;
+        // パスワードなしでログインできた場合は、そのまま成功とする。
+        if code != 230 {
+            if code != 331 {
+                return Err(ftp_failure(code, reply));
+            }
+            self.send(&format!("PASS {}", password)).await?;
+            let (code, reply) = self.read_ftp_reply().await?;
+            if code != 230 {
+                return Err(ftp_failure(code, reply));
+            }
+        }
+        let _ = self.send("QUIT").await;
+        Ok(())
+    }
+
+    /// FTPの応答を、複数行の応答（`220-...` から `220 ...` まで）も含めて読み取ります。
+    ///
+    /// # 戻り値
+    /// 応答コードと、最後の行。
+    async fn read_ftp_reply(&mut self) -> Result<(u16, String), LoginFailure> {
+        let first = self.read_line().await?;
+        let Some(code) = first.get(..3).and_then(|c| c.parse::<u16>().ok()) else {
+            return Err(LoginFailure::Protocol(first));
+        };
+        let mut last = first;
+        if last.as_bytes().get(3) == Some(&b'-') {
+            let end = format!("{} ", code);
+            while !last.starts_with(&end) {
+                last = self.read_line().await?;
+            }
+        }
+        Ok((code, last))
+    }
+}
+
+/// FTPの応答コードから、ログインの失敗を分類します。
+///
+/// 530（ログインできない）は拒否、それ以外は想定外の応答として扱います。
+fn ftp_failure(code: u16, reply: String) -> LoginFailure {
+    if code == 530 {
+        LoginFailure::Rejected(reply)
+    } else {
+        LoginFailure::Protocol(reply)
+    }
+}
+
+/// IMAPのコマンドの引数として、文字列を引用符で囲みます。
+fn imap_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
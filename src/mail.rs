@@ -0,0 +1,188 @@
+//! 通知の失敗が続いていることを、メールで知らせるモジュール。
+//!
+//! `--smtp-server` と `--mail-to` を設定すると、アカウントの通知が `--mail-after` で設定した時間（既定60分）
+//! 以上失敗し続けたときに、該当するアカウントをまとめた1通のメールを送ります。
+//! ログを見ないヘッドレスなサーバーでも、DDNSの不調に気付けるようにするためのものです。
+//! 同じ失敗が続いている間は繰り返し送らず、通知に成功すると再び送るようになります。
+//!
+//! ポート465ではSMTPS（最初からTLS）で、それ以外のポートではSTARTTLSで接続します。
+//! ユーザー名が設定されている場合は、SMTP認証を行います。
+
+use crate::error::AdapterError;
+use crate::i18n::{MessageKey, get_msg, get_msg_log};
+use crate::logging::{log_info, log_warn};
+use crate::registry::{Config, Settings, load_settings};
+use crate::state::{load_state, update_state};
+use crate::{format_msg, format_msg_log, outln};
+use chrono::{Local, TimeDelta};
+use lettre::message::header::ContentType;
+use lettre::message::{Mailbox, Message};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+use std::env;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// SMTPS（最初からTLSで接続する）のポート番号。
+const SMTPS_PORT: u16 = 465;
+/// メールの送信を待つ最大時間。
+const MAIL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// メールアドレスとして解釈できるかどうかを返します。`名前 <address>` の形式も受け付けます。
+pub fn is_valid_address(address: &str) -> bool {
+    address.parse::<Mailbox>().is_ok()
+}
+
+/// メールを送るのに必要な設定（SMTPサーバーと宛先）がそろっているかどうかを返します。
+pub fn is_mail_configured(settings: &Settings) -> bool {
+    !settings.smtp_server.is_empty() && !settings.mail_to.is_empty()
+}
+
+/// 設定されたSMTPサーバーで、設定された宛先にメールを送ります。
+///
+/// 差出人が設定されていない場合は、宛先と同じアドレスを差出人にします。
+///
+/// # 戻り値
+/// 送信に失敗した場合は、その内容を表す文字列。
+pub async fn send_mail(settings: &Settings, subject: &str, body: String) -> Result<(), String> {
+    let to = settings
+        .mail_to
+        .parse::<Mailbox>()
+        .map_err(|e| e.to_string())?;
+    let from = if settings.mail_from.is_empty() {
+        to.clone()
+    } else {
+        settings
+            .mail_from
+            .parse::<Mailbox>()
+            .map_err(|e| e.to_string())?
+    };
+    let message = Message::builder()
+        .from(from)
+        .to(to)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(body)
+        .map_err(|e| e.to_string())?;
+
+    let builder = if settings.smtp_port == SMTPS_PORT {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(&settings.smtp_server)
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&settings.smtp_server)
+    }
+    .map_err(|e| e.to_string())?
+    .port(settings.smtp_port)
+    .timeout(Some(MAIL_TIMEOUT));
+    let builder = if settings.smtp_username.is_empty() {
+        builder
+    } else {
+        builder.credentials(Credentials::new(
+            settings.smtp_username.clone(),
+            settings.smtp_password.clone(),
+        ))
+    };
+    builder
+        .build()
+        .send(message)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// 設定された時間以上通知に失敗し続けているアカウントを、メールで知らせます。
+///
+/// まだ知らせていないアカウントだけをまとめて1通のメールにし、送信に成功したら知らせたことを記録します。
+/// 送信に失敗した場合は警告を記録し、次の通知の後にもう一度送ります。
+pub async fn send_failure_alerts(configs: &[Config]) {
+    let settings = load_settings().unwrap_or_default();
+    if !is_mail_configured(&settings) {
+        return;
+    }
+    let threshold = TimeDelta::minutes(i64::from(settings.mail_after_minutes));
+    let now = Local::now();
+    let state = load_state().unwrap_or_default();
+    let mut alerted = Vec::new();
+    let mut lines = Vec::new();
+    for config in configs {
+        let Some(account) = state.accounts.get(&config.master_id) else {
+            continue;
+        };
+        let Some(since) = account.failing_since else {
+            continue;
+        };
+        if account.mail_alert_sent || now - since < threshold {
+            continue;
+        }
+        lines.push(format_msg_log!(
+            MessageKey::MailAlertAccountFmt,
+            id = config.master_id,
+            since = since.format("%Y-%m-%d %H:%M:%S"),
+            count = account.consecutive_failures,
+            error = account.last_result.as_deref().unwrap_or_default()
+        ));
+        alerted.push(config.master_id.clone());
+    }
+    if alerted.is_empty() {
+        return;
+    }
+
+    let host = computer_name();
+    let subject = format_msg_log!(MessageKey::MailAlertSubjectFmt, host = host);
+    let body = format!(
+        "{}\n\n{}\n",
+        format_msg_log!(
+            MessageKey::MailAlertIntroFmt,
+            host = host,
+            minutes = settings.mail_after_minutes
+        ),
+        lines.join("\n")
+    );
+    match send_mail(&settings, &subject, body).await {
+        Ok(()) => {
+            let _ = update_state(|s| {
+                for id in &alerted {
+                    s.accounts.entry(id.clone()).or_default().mail_alert_sent = true;
+                }
+            });
+            log_info(&format_msg_log!(
+                MessageKey::LogMailAlertSentFmt,
+                to = settings.mail_to,
+                count = alerted.len()
+            ));
+        }
+        Err(e) => log_warn(&format_msg_log!(
+            MessageKey::LogMailAlertFailedFmt,
+            server = settings.smtp_server,
+            error = e
+        )),
+    }
+}
+
+/// 設定を確かめるための、テストメールを送ります（`--mail-test`）。
+pub fn mail_test_mode() -> Result<(), AdapterError> {
+    let settings = load_settings()?;
+    if !is_mail_configured(&settings) {
+        return Err(get_msg(MessageKey::MailNotConfigured).into());
+    }
+    let host = computer_name();
+    let result = Runtime::new()?.block_on(send_mail(
+        &settings,
+        &format_msg_log!(MessageKey::MailTestSubjectFmt, host = host),
+        format!("{}\n", get_msg_log(MessageKey::MailTestBody)),
+    ));
+    match result {
+        Ok(()) => {
+            outln!(
+                "{}",
+                format_msg!(MessageKey::MailTestSentFmt, to = settings.mail_to)
+            );
+            Ok(())
+        }
+        Err(e) => Err(format_msg!(MessageKey::MailTestFailedFmt, error = e).into()),
+    }
+}
+
+/// メールの件名と本文に使う、このコンピューターの名前を返します。
+fn computer_name() -> String {
+    env::var("COMPUTERNAME").unwrap_or_else(|_| "localhost".to_string())
+}
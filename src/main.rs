@@ -1,488 +1,3668 @@
 //! アプリケーションのメインエントリーポイントとコマンドラインインターフェース（CLI）を定義するモジュール。
 //!
 //! このモジュールは以下の役割を担います。
-//! - `clap`クレートを用いて、`--add`, `--edit`, `--install` などのコマンドライン引数を解析します。
+//! - `clap`クレートを用いて、`account add`, `service install` などのサブコマンドとコマンドライン引数を解析します。
+//!   以前の `--add`, `--install` などのフラグも、非表示の別名として受け付けます。
 //! - 解析された引数に基づき、`winservice`、`registry`、`notify` などの各モジュールに対応する処理をディスパッチします。
 //! - アカウントの追加や編集など、ユーザーとの対話的な設定処理を実装します。
 //! - Windowsサービスとして実行するための特別なエントリーポイント (`--service` フラグの処理) を提供します。
 
 use std::env;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-use clap::Parser;
+use clap::{ArgGroup, CommandFactory, Parser, Subcommand};
 use rpassword::read_password;
 
 // --- アプリケーションの各機能を実装したモジュール群 ---
+mod adminapi;
+mod batch;
+mod bench;
+mod checkip;
+mod connectivity;
+mod console;
+mod crash;
+mod dnscheck;
+mod dnsverify;
+mod doctor;
+mod elevation;
+mod error;
+mod eventlog;
+mod exitcode;
+mod expiry;
+mod filestore;
+mod gzip;
+mod history;
+#[cfg(feature = "sqlite")]
+mod historydb;
 mod i18n;
+mod ipc;
+mod json;
+mod localhttp;
 mod logging;
+mod login;
+mod mail;
+mod metrics;
+mod netif;
+mod netprofile;
+mod netwatch;
 mod notify;
+mod ping;
+mod proxy;
 mod registry;
+mod regwatch;
+mod schedule;
+mod schtask;
+mod simulate;
+mod state;
+mod stats;
+mod store;
+mod toast;
+mod transfer;
+mod webhook;
 mod winservice;
 
 // --- 各モジュールから必要な関数や構造体をインポート ---
-use i18n::get_msg;
-use logging::{log_error, log_info};
-use notify::notify_now_mode;
-use registry::{delete_config, load_all_configs, save_to_registry};
-use winservice::{install_service, restart_service, run_service, uninstall_service};
+use adminapi::generate_token;
+use batch::add_batch_mode;
+use bench::bench_mode;
+use console::{Color, paint};
+use doctor::doctor_mode;
+use elevation::{is_elevated, is_relaunched, relaunch_elevated};
+use error::AdapterError;
+use exitcode::{ExitError, ExitReason};
+use filestore::FileStore;
+use history::{history_mode, rename_history};
+use i18n::{Lang, MessageKey, format_template, get_msg, set_lang_override};
+use ipc::{Request, pipe_command_mode};
+use logging::{
+    LogFilter, LogLevel, default_log_path, delete_log_files, follow_log, get_log_path, log_error,
+    log_info, read_log_tail,
+};
+use mail::{is_mail_configured, is_valid_address, mail_test_mode};
+use netprofile::connected_network_names;
+use notify::{
+    FailureKind, IPV4_NOTIFY_URL, IPV6_NOTIFY_URL, NotifyOptions, USER_AGENT_ID_MAX_LEN,
+    build_client, endpoint_or_default, is_valid_user_agent_id, notify_now_mode,
+    test_credentials_mode, user_agent,
+};
+use registry::{
+    Config, ConfigScope, DEFAULT_KEEP_ALIVE_HOURS, DEFAULT_SMTP_PORT, LOG_ARCHIVE_COUNT_RANGE,
+    MeteredPolicy, NOTIFY_CONCURRENCY_RANGE, NOTIFY_INTERVAL_RANGE_MINUTES, NotifyMethod, Provider,
+    QuietHours, REQUEST_TIMEOUT_RANGE_SECONDS, RETRY_MAX_ATTEMPTS_RANGE, Settings, UpgradeChange,
+    config_scope, delete_all_settings, load_settings, machine_scope_writable, save_settings,
+    set_config_scope, upgrade_account,
+};
+use schedule::Schedule;
+use schtask::{install_task, uninstall_task};
+use simulate::simulate_mode;
+use state::{load_state, update_account_state, update_state};
+use stats::{print_stats_summary, set_stats_enabled};
+use store::{
+    config_file, delete_config, load_all_configs, load_last_error, load_last_success,
+    load_suspended, rename_config, save_config, save_suspended, set_config_file,
+};
+use toast::toast_monitor_mode;
+use transfer::{ImportChange, export_mode, plan_import, quote};
+use winservice::{
+    ServiceLogon, builtin_service_account, install_service, kick_service, restart_service,
+    run_foreground, run_service, service_status, start_service, stop_service, uninstall_service,
+};
+
+/// アカウントのパスワードを渡すための環境変数の名前。
+const PASSWORD_ENV_VAR: &str = "MYDNS_PASSWORD";
+
+/// `--view` の出力形式。
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ViewFormat {
+    /// 人が読むための、翻訳された表形式。
+    Table,
+    /// スクリプトから読み込むためのJSON形式。
+    Json,
+    /// 表計算ソフトなどで読み込むためのCSV形式（ヘッダー行付き）。
+    Csv,
+}
 
 /// clapクレートを利用してコマンドライン引数を定義する構造体。
 /// 各フィールドが、アプリケーションが受け付けるコマンドラインオプションに対応します。
+///
+/// アカウント・サービスの操作と即時通知はサブコマンド（`Command`）で指定します。
+/// 以前の `--add` や `--install` などのフラグも、互換性のために非表示の別名として受け付けます。
 #[derive(Parser, Debug)]
 #[command(author, version, about = "MyDNS.JP Adapter for Windows", long_about = None)]
+// サブコマンドの前に指定されたオプションは無視されてしまうため、サブコマンドとは併用できない。
+// 共通のオプション（`--user` など）は、サブコマンドの後に指定する。
+#[command(args_conflicts_with_subcommands = true)]
+// 旧形式の操作フラグは、それぞれ別の操作を表すため互いに併用できない。
+#[command(group(
+    ArgGroup::new("legacy_mode")
+        .args([
+            "add", "edit", "remove", "rename", "enable", "view", "list", "install", "uninstall",
+            "restart", "start", "stop",
+        ])
+        .conflicts_with_all(["MailEdits", "CheckIpEdits"])
+))]
+// `--account` は、ログの絞り込みと、旧形式のサービスのインストールと即時通知で使う。
+#[command(group(
+    ArgGroup::new("account_users")
+        .multiple(true)
+        .args(["log", "install", "notify", "ipv4", "ipv6"])
+))]
+// `--yes` は、アカウントの削除と、設定の削除（`--purge`）で使う。
+#[command(group(ArgGroup::new("yes_users").multiple(true).args(["remove", "purge"])))]
+// `--force` は、旧形式の即時通知（`--notify`、`-4`、`-6`）とだけ併用できる。
+#[command(group(
+    ArgGroup::new("legacy_notify")
+        .multiple(true)
+        .args(["notify", "ipv4", "ipv6"])
+        .conflicts_with_all(["MailEdits", "CheckIpEdits"])
+))]
+// 項目ごとの変更は、旧形式では `--edit` とだけ併用できる。
+#[command(group(
+    ArgGroup::new("legacy_field_edits")
+        .multiple(true)
+        .requires("edit")
+        .conflicts_with_all([
+            "add", "remove", "rename", "enable", "view", "list", "install", "uninstall", "restart",
+            "start", "stop",
+        ])
+        .args([
+            "set_password_stdin", "set_ipv4", "set_ipv6", "set_keep_alive", "set_proxy",
+            "set_ipv4_url", "set_ipv6_url", "set_hostname", "set_provider", "set_method",
+            "set_ping_url", "set_interface", "set_schedule",
+        ])
+))]
+// 設定や操作のモードは、それぞれ別の処理を表すため互いに併用できない。
+// 複数の値を一度に設定するモード（ログのアーカイブ、メール、確認サービス）は、それぞれ別のグループで扱う。
+#[command(group(
+    ArgGroup::new("settings_mode")
+        .args([
+            "retry_attempts", "concurrency", "request_timeout", "log_path", "metrics_port",
+            "admin_api_port", "ping_url", "webhook_url", "skip_private_ipv4", "dns_check", "metered",
+            "allowed_networks", "quiet_hours", "jitter", "user_agent_id", "history_db", "toast_after",
+            "toast_monitor", "mail_test", "pause", "resume", "kick", "status", "run_foreground",
+            "install_task", "uninstall_task", "test", "simulate", "log", "upgrade_config", "stats",
+            "export", "import", "bench",
+        ])
+        .conflicts_with_all([
+            "service_settings", "log_archive", "MailEdits", "CheckIpEdits", "legacy_mode",
+            "legacy_notify",
+        ])
+))]
+// `--interval` と `--proxy` は、旧形式の `--install` と同じく一度に設定できる。
+#[command(group(
+    ArgGroup::new("service_settings")
+        .multiple(true)
+        .args(["interval", "proxy"])
+        .conflicts_with_all([
+            "log_archive", "MailEdits", "CheckIpEdits", "legacy_notify", "add", "edit", "remove",
+            "rename", "enable", "view", "list", "uninstall", "restart", "start", "stop",
+        ])
+))]
+#[command(group(
+    ArgGroup::new("log_archive")
+        .multiple(true)
+        .args(["log_archives", "log_compress"])
+        .conflicts_with_all(["MailEdits", "CheckIpEdits", "legacy_mode", "legacy_notify"])
+))]
 struct Args {
-    /// 新しいアカウント設定を追加します。
-    #[arg(short, long)]
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// （旧形式）`account add` と同じです。
+    #[arg(short, long, hide = true)]
     add: bool,
 
-    /// 既存のアカウント設定を編集します。MasterIDを省略した場合は、対話的に選択します。
-    #[arg(short, long, num_args(0..=1), default_missing_value = "_INTERACTIVE_")]
+    /// （旧形式）`account add/edit --password-stdin` と同じです。
+    #[arg(long, hide = true)]
+    password_stdin: bool,
+
+    /// （旧形式）`account add --batch <FILE>` と同じです。
+    #[arg(
+        long,
+        hide = true,
+        value_name = "FILE",
+        requires = "add",
+        conflicts_with = "password_stdin"
+    )]
+    batch: Option<PathBuf>,
+
+    #[command(flatten)]
+    field_edits: FieldEdits,
+
+    #[command(flatten)]
+    mail_edits: MailEdits,
+
+    #[command(flatten)]
+    check_ip_edits: CheckIpEdits,
+
+    /// （旧形式）`account edit [MASTER_ID]` と同じです。
+    #[arg(short, long, hide = true, num_args(0..=1), default_missing_value = "_INTERACTIVE_")]
     edit: Option<String>,
 
-    /// 指定されたMasterIDのアカウント設定を削除します。
-    #[arg(short, long)]
+    /// （旧形式）`account remove <MASTER_ID>` と同じです。
+    #[arg(short, long, hide = true)]
     remove: Option<String>,
 
-    /// 現在の設定を一覧表示します。
-    #[arg(short, long)]
+    /// （旧形式）`account remove --yes`、`service uninstall --purge --yes` と同じです。
+    #[arg(short, long, hide = true, requires = "yes_users")]
+    yes: bool,
+
+    /// アカウントのMasterIDを変更します。`account rename <OLD> <NEW>` と同じです。
+    #[arg(long, num_args = 2, value_names = ["OLD", "NEW"])]
+    rename: Option<Vec<String>>,
+
+    /// 認証エラーが続いて自動で停止されたアカウントの通知を再開します。`account enable <MASTER_ID>` と同じです。
+    #[arg(long, value_name = "MASTER_ID")]
+    enable: Option<String>,
+
+    /// （旧形式）`account list` と同じです。
+    #[arg(short, long, hide = true)]
     view: bool,
 
-    /// 現在の設定を1行で簡潔に一覧表示します。（--viewのエイリアス）
-    #[arg(short, long)]
+    /// （旧形式）`account list` と同じです。
+    #[arg(short, long, hide = true)]
     list: bool,
 
-    /// IPv4とIPv6の両方のアドレスを即時通知します。
-    #[arg(short, long)]
+    /// （旧形式）`account list --format` と同じです。
+    #[arg(long, hide = true, value_enum, default_value_t = ViewFormat::Table)]
+    format: ViewFormat,
+
+    /// （旧形式）`notify` と同じです。
+    #[arg(short, long, hide = true, conflicts_with = "legacy_mode")]
     notify: bool,
 
-    /// IPv4アドレスを即時通知します。
-    #[arg(short = '4', long)]
+    /// （旧形式）`notify -4` と同じです。
+    #[arg(short = '4', long, hide = true, conflicts_with = "legacy_mode")]
     ipv4: bool,
 
-    /// IPv6アドレスを即時通知します。
-    #[arg(short = '6', long)]
+    /// （旧形式）`notify -6` と同じです。
+    #[arg(short = '6', long, hide = true, conflicts_with = "legacy_mode")]
     ipv6: bool,
 
-    /// アプリケーションをWindowsサービスとしてインストールします。
-    #[arg(long)]
+    /// （旧形式）`notify --force` と同じです。
+    #[arg(long, hide = true, requires = "legacy_notify")]
+    force: bool,
+
+    /// （旧形式）`notify --dry-run` と同じです。
+    #[arg(long, hide = true, requires = "legacy_notify")]
+    dry_run: bool,
+
+    /// （旧形式）`service install` と同じです。
+    #[arg(long, hide = true)]
     install: bool,
 
-    /// Windowsサービスをアンインストールします。
-    #[arg(long)]
+    /// （旧形式）`service uninstall` と同じです。
+    #[arg(long, hide = true)]
     uninstall: bool,
 
-    /// Restart the Windows service.
-    #[arg(long)]
+    /// （旧形式）`service uninstall --purge` と同じです。
+    #[arg(long, hide = true, requires = "uninstall")]
+    purge: bool,
+
+    /// （旧形式）`service restart` と同じです。
+    #[arg(long, hide = true)]
     restart: bool,
+
+    /// （旧形式）`service start` と同じです。
+    #[arg(long, hide = true)]
+    start: bool,
+
+    /// （旧形式）`service stop` と同じです。
+    #[arg(long, hide = true)]
+    stop: bool,
+
+    /// サービスによる定期通知の間隔（分）を設定します。
+    #[arg(long, value_name = "MINUTES")]
+    interval: Option<u32>,
+
+    /// 一時的な障害で通知に失敗した場合の、最初の送信を含めた最大試行回数を設定します。
+    #[arg(long, value_name = "COUNT")]
+    retry_attempts: Option<u32>,
+
+    /// 1回の通知処理で、同時に通知するアカウントの最大数を設定します。
+    #[arg(long, value_name = "COUNT")]
+    concurrency: Option<u32>,
+
+    /// 1回の通知リクエストのタイムアウト（秒）を設定します。
+    #[arg(long, value_name = "SECONDS")]
+    request_timeout: Option<u32>,
+
+    /// 通知に使うHTTPプロキシのURLを設定します。"direct"で直接接続、"-"で設定を解除します。
+    #[arg(long, value_name = "URL", allow_hyphen_values = true)]
+    proxy: Option<String>,
+
+    /// ログファイルの場所を設定します。"-"で既定の場所（%ProgramData%\MyDNSAdapter\mydns.log）に戻します。
+    #[arg(long, value_name = "PATH", allow_hyphen_values = true)]
+    log_path: Option<PathBuf>,
+
+    /// ローテーションで残すログのアーカイブ（mydns-YYYYMMDD.log）の数を設定します。
+    #[arg(long, value_name = "COUNT")]
+    log_archives: Option<u32>,
+
+    /// ログのアーカイブをgzipで圧縮するかどうか（true/false）を設定します。
+    #[arg(long, value_name = "BOOL")]
+    log_compress: Option<bool>,
+
+    /// サービスがPrometheus形式のメトリクスを公開するポート（127.0.0.1）を設定します。0で無効にします。
+    #[arg(long, value_name = "PORT")]
+    metrics_port: Option<u16>,
+
+    /// サービスが管理API（JSON）を公開するポート（127.0.0.1）を設定し、アクセスに必要なトークンを表示します。0で無効にします。
+    #[arg(long, value_name = "PORT")]
+    admin_api_port: Option<u16>,
+
+    /// すべてのアカウントの通知に成功したときにサービスがGETリクエストを送る、死活監視サービスのURLを設定します。"-"で解除します。
+    #[arg(long, value_name = "URL", allow_hyphen_values = true)]
+    ping_url: Option<String>,
+
+    /// 通知の失敗・回復・アドレスの変化をJSONでPOSTする、WebhookのURLを設定します。"-"で解除します。
+    #[arg(long, value_name = "URL", allow_hyphen_values = true)]
+    webhook_url: Option<String>,
+
+    /// 確認サービスで求めたIPv4アドレスがCGNATやプライベートのアドレスだった場合に、通知を見送るかどうか（true/false）を設定します。
+    #[arg(long, value_name = "BOOL")]
+    skip_private_ipv4: Option<bool>,
+
+    /// サービスがホスト名のDNSレコードを公開リゾルバーで確認する間隔（分）を設定します。0で確認しません。
+    #[arg(long, value_name = "MINUTES")]
+    dns_check: Option<u32>,
+
+    /// 従量制課金の接続での、サービスによる通知の扱い（ignore/reduce/skip）を設定します。
+    #[arg(long, value_name = "POLICY", value_parser = parse_metered_policy)]
+    metered: Option<MeteredPolicy>,
+
+    /// サービスが通知するネットワーク（ネットワークプロファイルの名前またはSSID）を、カンマ区切りで設定します。"-"で解除します。
+    #[arg(long, value_name = "NAME,...", allow_hyphen_values = true)]
+    allowed_networks: Option<String>,
+
+    /// サービスが自動の通知を見送る毎日の時間帯を、HH:MM-HH:MM の形式で設定します。"-"で解除します。
+    #[arg(long, value_name = "HH:MM-HH:MM", allow_hyphen_values = true)]
+    quiet_hours: Option<String>,
+
+    /// サービスの定期通知と開始時の通知の時刻を、前後にランダムにずらす最大の秒数を設定します。0でずらしません。
+    #[arg(long, value_name = "SECONDS")]
+    jitter: Option<u32>,
+
+    /// HTTPリクエストのUser-Agentの末尾に付ける識別子を設定します。"-"で解除します。
+    #[arg(long, value_name = "TEXT", allow_hyphen_values = true)]
+    user_agent_id: Option<String>,
+
+    /// 通知の履歴を、SQLiteのデータベースにも記録するかどうか（true/false）を設定します。`sqlite` フィーチャーでビルドした場合だけ有効にできます。
+    #[arg(long, value_name = "BOOL")]
+    history_db: Option<bool>,
+
+    /// 通知が何回続けて失敗したらトースト通知で知らせるかを設定します。0で無効にします。
+    #[arg(long, value_name = "COUNT")]
+    toast_after: Option<u32>,
+
+    /// 稼働中のサービスの通知の失敗が続いたときに、トースト通知で知らせます（Ctrl+C で終了）。
+    #[arg(long)]
+    toast_monitor: bool,
+
+    /// 設定したSMTPサーバーと宛先で、テストメールを送信します。
+    #[arg(long)]
+    mail_test: bool,
+
+    /// 稼働中のサービスによる定期通知を一時停止します。
+    #[arg(long)]
+    pause: bool,
+
+    /// 一時停止した定期通知を再開します。
+    #[arg(long)]
+    resume: bool,
+
+    /// 稼働中のサービスに即時通知を要求します。
+    #[arg(long)]
+    kick: bool,
+
+    /// サービスの状態と稼働時間、アカウントごとの直近の通知結果をまとめて表示します。
+    #[arg(long)]
+    status: bool,
+
+    /// サービスと同じ処理を、インストールせずにコンソールで実行します（Ctrl+C で停止）。
+    #[arg(long)]
+    run_foreground: bool,
+
+    /// サービスの代わりに、起動時・ネットワーク接続時・定期的に通知するタスクをタスクスケジューラに登録します。
+    #[arg(long, conflicts_with = "uninstall_task")]
+    install_task: bool,
+
+    /// `--install-task` で登録したタスクを削除します。
+    #[arg(long)]
+    uninstall_task: bool,
+
+    /// 指定したアカウントで1回だけ通知リクエストを送信し、認証情報が正しいかを確認します。
+    #[arg(long, value_name = "MASTER_ID")]
+    test: Option<String>,
+
+    /// ローカルの模擬サーバーを相手に、通知処理のエンドツーエンドのシミュレーションを実行します。
+    #[arg(long)]
+    simulate: bool,
+
+    /// ログファイルの末尾を表示します。行数を省略した場合は50行を表示します。
+    #[arg(long, value_name = "LINES", num_args(0..=1), default_missing_value = "50")]
+    log: Option<usize>,
+
+    /// 指定した重大度（info, warn, error）以上のログだけを表示します。（--logと併用）
+    #[arg(long, value_name = "LEVEL", requires = "log")]
+    level: Option<LogLevel>,
+
+    /// 指定したMasterIDに関するログだけを表示します。（--logと併用）
+    /// （旧形式）`--install` と併用した場合は、`service install --account` と同じです。
+    /// （旧形式）`--notify` と併用した場合は、`notify --account` と同じです。
+    #[arg(long, value_name = "MASTER_ID", requires = "account_users")]
+    account: Option<String>,
+
+    /// （旧形式）`service install --service-password` と同じです。
+    #[arg(long, hide = true, value_name = "PASSWORD", requires_all = ["install", "account"])]
+    service_password: Option<String>,
+
+    /// ログを表示した後も、新しく記録されるログを表示し続けます。Ctrl+Cで終了します。（--logと併用）
+    #[arg(short, long, requires = "log")]
+    follow: bool,
+
+    /// すべてのアカウント設定を検証し、現在の形式に更新して結果を報告します。
+    #[arg(long)]
+    upgrade_config: bool,
+
+    /// ローカルの利用統計を操作します。--summary, --opt-in, --opt-out と組み合わせて使用します。
+    #[arg(long)]
+    stats: bool,
+
+    /// 利用統計の概要を表示します。（--statsと併用）
+    #[arg(long, requires = "stats")]
+    summary: bool,
+
+    /// 利用統計の記録を有効にします。統計はこのマシンの外に送信されません。（--statsと併用）
+    #[arg(long, requires = "stats", conflicts_with = "opt_out")]
+    opt_in: bool,
+
+    /// 利用統計の記録を無効にします。（--statsと併用）
+    #[arg(long, requires = "stats")]
+    opt_out: bool,
+
+    /// すべてのアカウント設定をファイルに書き出します。拡張子が.tomlならTOML、それ以外はJSON形式です。
+    #[arg(long, value_name = "FILE")]
+    export: Option<PathBuf>,
+
+    /// --exportで書き出したファイルを読み込み、差分を確認してからアカウント設定に反映します。
+    #[arg(long, value_name = "FILE")]
+    import: Option<PathBuf>,
+
+    /// 書き出すファイルにパスワードを含めません。（--exportと併用）
+    #[arg(long, requires = "export")]
+    redact_passwords: bool,
+
+    /// 設定をユーザーごとの保存先（HKCU）で読み書きします。管理者権限は不要ですが、サービスからは参照されません。
+    #[arg(long, global = true)]
+    user: bool,
+
+    /// アカウントの設定を、レジストリの代わりに指定したTOMLファイルで読み書きします。サービスは既定の場所のファイルだけを参照します。
+    #[arg(long, global = true, value_name = "PATH", conflicts_with = "user")]
+    config_file: Option<PathBuf>,
+
+    /// 処理の経過や結果のメッセージを表示しません。エラーは表示します。（スクリプト向け）
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// ログに記録する内容とHTTP通信の詳細を、コンソールにも表示します。
+    #[arg(long, global = true)]
+    verbose: bool,
+
+    /// 表示に色を付けません。環境変数 NO_COLOR が設定されている場合も色を付けません。
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// メッセージとログの言語（ja / en / zh / ko / de / fr / es）を指定します。環境変数 MYDNS_LANG でも指定できます。
+    #[arg(long, global = true, value_name = "LANG", value_parser = parse_lang)]
+    lang: Option<Lang>,
+
+    /// UACで昇格して実行し直したプロセスであることを示します。（内部用）
+    #[arg(long, global = true, hide = true)]
+    elevated: bool,
+
+    /// ログ記録と設定の読み書きの性能を計測します。（開発者向け）
+    #[arg(long, hide = true)]
+    bench: bool,
 }
 
-/// アプリケーションのメインエントリーポイント。
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Windowsサービスとして実行するための特別な引数チェック。
-    // `windows-service`クレートは、`--service`引数でサービスディスパッチャを起動します。
-    // このチェックは、clapによる通常の引数解析の前に行う必要があります。
-    if env::args().any(|arg| arg == "--service" || arg == "-s") {
-        // サービス実行ループに入り、サービスが停止するまで制御を返しません。
-        run_service()?;
-        return Ok(());
-    }
+/// サブコマンド。
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// アカウント設定を操作します。
+    #[command(subcommand)]
+    Account(AccountCommand),
 
-    // サービスモードでない場合は、通常のCLIアプリケーションとして引数を解析します。
-    let args = Args::parse();
+    /// Windowsサービスを操作します。
+    #[command(subcommand)]
+    Service(ServiceCommand),
 
-    // 解析された引数に基づいて、対応する処理モードに分岐します。
-    // 各モードは排他的に実行されるため、if-else ifで順に評価します。
-    if args.install {
-        install_service()?;
-    } else if args.uninstall {
-        uninstall_service()?;
-    } else if args.restart {
-        restart_service()?;
-    } else if args.add {
-        // アカウント追加モード
-        add_mode()?;
-    } else if let Some(id) = args.remove {
-        // アカウント削除モード
-        remove_mode(&id)?;
-    } else if let Some(id_arg) = args.edit {
-        // アカウント編集モード
-        // `edit`引数は値を持つ場合と持たない場合があります。
-        // `default_missing_value`により、値なしの場合は特殊な文字列が入ります。
-        let target = if id_arg == "_INTERACTIVE_" {
-            // `--edit` のようにIDが指定されなかった場合、対話的な選択モードに入ります。
-            None
-        } else {
-            // `--edit <ID>` のようにIDが指定された場合、そのIDをターゲットにします。
-            Some(id_arg)
-        };
-        edit_mode(target)?;
-    } else if args.view || args.list {
-        // 設定表示モード (`--view` と `--list` は同じ機能です)
-        view_mode()?;
-    } else if args.notify || args.ipv4 || args.ipv6 {
-        // 即時通知モード
-        // -n (--notify) はIPv4/v6両方を有効化
-        // -4 (--ipv4) はIPv4のみを有効化
-        // -6 (--ipv6) はIPv6のみを有効化
-        let use_ipv4 = args.notify || args.ipv4;
-        let use_ipv6 = args.notify || args.ipv6;
-        notify_now_mode(use_ipv4, use_ipv6)?;
-    } else {
-        // 引数が何も指定されなかった場合のデフォルト動作。
-        // ユーザーが設定を手軽に変更できるよう、対話的な編集モードを開始します。
-        edit_mode(None)?;
-    }
-    Ok(())
+    /// 現在のIPアドレスを即時通知します。-4/-6を省略した場合は、両方を通知します。
+    Notify {
+        /// IPv4アドレスを通知します。
+        #[arg(short = '4', long)]
+        ipv4: bool,
+
+        /// IPv6アドレスを通知します。
+        #[arg(short = '6', long)]
+        ipv6: bool,
+
+        /// ネットワークへの接続の確認を省き、認証エラーが続いて自動で停止されたアカウントも通知します。
+        /// サービスでの定期的な強制通知は、アカウントの強制通知（キープアライブ）の間隔で設定します。
+        #[arg(long)]
+        force: bool,
+
+        /// 指定したMasterIDのアカウントだけを通知します。カンマ区切りで複数指定できます。
+        #[arg(long, value_name = "MASTER_ID,...", value_delimiter = ',')]
+        account: Vec<String>,
+
+        /// 送信するリクエスト（URL、プロトコル、伏せ字にした資格情報）を表示するだけで、実際には送信しません。
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// 動作環境（管理者権限、レジストリ、ログファイル、MyDNS.JPへの接続、サービス、時計）を診断します。
+    Doctor,
+
+    /// 通知の試行の履歴（時刻、アカウント、プロトコル、結果、所要時間）を表示します。
+    History {
+        /// 指定したMasterIDのアカウントの履歴だけを表示します。
+        #[arg(long, value_name = "MASTER_ID")]
+        account: Option<String>,
+
+        /// 表示する件数（新しいものから数えます）。
+        #[arg(long, value_name = "COUNT", default_value_t = 50)]
+        count: usize,
+    },
 }
 
-/// 新しいアカウント設定を追加するための対話モードを処理します。
-fn add_mode() -> io::Result<()> {
-    println!("{}", get_msg("add_title"));
+/// `account` サブコマンドの操作。
+#[derive(Subcommand, Debug)]
+enum AccountCommand {
+    /// 新しいアカウント設定を追加します。
+    Add {
+        /// パスワードを対話的に入力する代わりに、標準入力の1行目から読み込みます。
+        #[arg(long)]
+        password_stdin: bool,
 
-    // MasterIDの入力
-    let master_id = ask_with_default(get_msg("master_id_prompt"), "", false)?;
+        /// 「MasterID,パスワード,IPv4,IPv6」の行を並べたCSVファイルから、アカウントをまとめて追加します。
+        /// `-` を指定すると標準入力から読み込みます。
+        #[arg(long, value_name = "FILE", conflicts_with = "password_stdin")]
+        batch: Option<PathBuf>,
+    },
 
-    // 重複チェック
-    let configs = load_all_configs().unwrap_or_else(|_| Vec::new());
-    if configs.iter().any(|c| c.master_id == master_id) {
-        println!(
-            "{}",
-            get_msg("account_exists_fmt").replace("{}", &master_id)
-        );
-        return Ok(());
-    }
+    /// 既存のアカウント設定を編集します。MasterIDを省略した場合は、対話的に選択します。
+    Edit {
+        master_id: Option<String>,
 
-    // MasterIDの基本的な形式を検証します。
-    if !master_id.starts_with("mydns") {
-        println!("{}", get_msg("invalid_master_id_prefix"));
-        return Ok(());
-    }
+        /// パスワードを対話的に入力する代わりに、標準入力の1行目から読み込みます。
+        #[arg(long)]
+        password_stdin: bool,
 
-    // パスワードの入力
-    let password = ask_with_default(get_msg("password_prompt"), "", true)?;
+        #[command(flatten)]
+        fields: FieldEdits,
+    },
 
-    // IPv4/IPv6通知の入力
-    let ipv4_notify = ask_yes_no_simple(get_msg("ipv4_notify_prompt"), true)?;
-    let ipv6_notify = ask_yes_no_simple(get_msg("ipv6_notify_prompt"), true)?;
+    /// 指定されたMasterIDのアカウント設定を削除します。
+    Remove {
+        master_id: String,
 
-    // 新しい設定をレジストリに保存します。
-    match save_to_registry(&master_id, &password, ipv4_notify, ipv6_notify) {
-        Ok(_) => {
-            let msg = get_msg("add_success");
-            println!("{}", msg);
-            log_info(&format!("Account added: {}", master_id));
+        /// 確認せずに削除します。スクリプトからの実行向けです。
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// アカウントのMasterIDを変更します。設定の値と、通知の記録・履歴を引き継ぎます。
+    Rename { old_id: String, new_id: String },
+
+    /// 認証エラーが続いて自動で停止されたアカウントの通知を再開します。（--enableと同じ）
+    Enable { master_id: String },
+
+    /// 指定したアカウントで1回だけ通知リクエストを送信し、認証情報が正しいかを確認します。（--testと同じ）
+    Test { master_id: String },
+
+    /// 現在の設定を一覧表示します。
+    List {
+        /// 出力形式（table, json, csv）。json/csvはスクリプトからの利用向けです。
+        #[arg(long, value_enum, value_name = "FORMAT", default_value_t = ViewFormat::Table)]
+        format: ViewFormat,
+    },
+}
+
+/// `account edit` で、対話的な入力を行わずに個別の項目だけを変更するための引数。
+///
+/// いずれかが指定された場合は、指定された項目だけを変更して保存します。
+#[derive(clap::Args, Clone, Debug, Default)]
+struct FieldEdits {
+    /// パスワードを、標準入力の1行目から読み込んだ値に変更します。
+    #[arg(long)]
+    set_password_stdin: bool,
+
+    /// IPv4アドレスを通知するかどうか（true/false）を変更します。
+    #[arg(long, value_name = "BOOL")]
+    set_ipv4: Option<bool>,
+
+    /// IPv6アドレスを通知するかどうか（true/false）を変更します。
+    #[arg(long, value_name = "BOOL")]
+    set_ipv6: Option<bool>,
+
+    /// 強制通知の間隔（時間、0で無効）を変更します。
+    #[arg(long, value_name = "HOURS")]
+    set_keep_alive: Option<u32>,
+
+    /// アカウント個別のプロキシURLを変更します。"direct"で直接接続、"-"で解除します。
+    #[arg(long, value_name = "URL", allow_hyphen_values = true)]
+    set_proxy: Option<String>,
+
+    /// IPv4アドレスの通知先URLを変更します。"-"で既定のURLに戻します。
+    /// 汎用のサービスでは、`{ip}` と `{host}` を含められる更新URLです。
+    #[arg(long, value_name = "URL", allow_hyphen_values = true)]
+    set_ipv4_url: Option<String>,
+
+    /// IPv6アドレスの通知先URLを変更します。"-"で既定のURLに戻します。
+    /// 汎用のサービスでは、"-"でIPv4の更新URLを使うようにします。
+    #[arg(long, value_name = "URL", allow_hyphen_values = true)]
+    set_ipv6_url: Option<String>,
+
+    /// DNSの反映を確認するホスト名を変更します。"-"で解除します。
+    #[arg(long, value_name = "HOST", allow_hyphen_values = true)]
+    set_hostname: Option<String>,
+
+    /// 通知先のサービス（mydns/generic）を変更します。
+    /// 変更した場合、URLを同時に指定しなければ通知先URLは設定し直しになります。
+    #[arg(long, value_name = "PROVIDER", value_parser = parse_provider)]
+    set_provider: Option<Provider>,
+
+    /// MyDNS.JPへの通知の方法（http/pop3/imap/ftp）を変更します。
+    /// POP3/IMAP/FTPでは、パスワードが暗号化されずに送られます。
+    #[arg(long, value_name = "METHOD", value_parser = parse_method)]
+    set_method: Option<NotifyMethod>,
+
+    /// 通知に成功したときにGETリクエストを送る、死活監視サービスのURLを変更します。"-"で解除します。
+    #[arg(long, value_name = "URL", allow_hyphen_values = true)]
+    set_ping_url: Option<String>,
+
+    /// 通知を送るネットワークインターフェイスの名前、またはローカルアドレスを変更します。"-"で解除します。
+    #[arg(long, value_name = "NAME|ADDRESS", allow_hyphen_values = true)]
+    set_interface: Option<String>,
+
+    /// 定期通知の代わりに使う、通知のスケジュール（"*/10 * * * *" や "at 04:05 daily"）を変更します。"-"で解除します。
+    #[arg(long, value_name = "EXPR", allow_hyphen_values = true)]
+    set_schedule: Option<String>,
+}
+
+impl FieldEdits {
+    /// 変更する項目が1つも指定されていないかどうかを返します。
+    fn is_empty(&self) -> bool {
+        !self.set_password_stdin
+            && self.set_ipv4.is_none()
+            && self.set_ipv6.is_none()
+            && self.set_keep_alive.is_none()
+            && self.set_proxy.is_none()
+            && self.set_ipv4_url.is_none()
+            && self.set_ipv6_url.is_none()
+            && self.set_hostname.is_none()
+            && self.set_provider.is_none()
+            && self.set_method.is_none()
+            && self.set_ping_url.is_none()
+            && self.set_interface.is_none()
+            && self.set_schedule.is_none()
+    }
+
+    /// 指定された項目だけを `config` に反映します。
+    ///
+    /// 値が正しくない項目があった場合は、何も変更せずにエラーを返します。
+    fn apply(&self, config: &Config) -> io::Result<Config> {
+        let mut config = config.clone();
+        if self.set_password_stdin {
+            config.password = ask_password(&config.password, true)?;
         }
-        Err(e) => {
-            let msg = get_msg("registry_save_fail_fmt").replace("{}", &e.to_string());
-            println!("{}", msg);
-            log_error(&format!("Failed to add account {}: {}", master_id, e));
+        if let Some(enabled) = self.set_ipv4 {
+            config.ipv4_notify = enabled;
+        }
+        if let Some(enabled) = self.set_ipv6 {
+            config.ipv6_notify = enabled;
+        }
+        if let Some(hours) = self.set_keep_alive {
+            config.keep_alive_hours = hours;
+        }
+        if let Some(url) = &self.set_proxy {
+            config.proxy_url = normalize_proxy_url(url);
+        }
+        if let Some(provider) = self.set_provider
+            && provider != config.provider
+        {
+            // 通知先URLの意味はサービスごとに異なるため、引き継がない。
+            config.provider = provider;
+            config.method = NotifyMethod::Http;
+            config.ipv4_url.clear();
+            config.ipv6_url.clear();
+        }
+        if let Some(method) = self.set_method {
+            config.method = method;
+        }
+        if config.provider == Provider::Generic && config.method != NotifyMethod::Http {
+            return Err(io::Error::other(get_msg(MessageKey::MethodRequiresMyDns)));
+        }
+        for (input, url, default) in [
+            (&self.set_ipv4_url, &mut config.ipv4_url, IPV4_NOTIFY_URL),
+            (&self.set_ipv6_url, &mut config.ipv6_url, IPV6_NOTIFY_URL),
+        ] {
+            if let Some(input) = input {
+                let normalized = match config.provider {
+                    Provider::MyDns => normalize_endpoint_url(input, default),
+                    Provider::Generic => normalize_generic_url(input),
+                };
+                *url = normalized.ok_or_else(|| {
+                    io::Error::other(format_msg!(
+                        MessageKey::SetEndpointUrlInvalidFmt,
+                        url = input
+                    ))
+                })?;
+            }
+        }
+        if config.provider == Provider::Generic && config.ipv4_url.is_empty() {
+            return Err(io::Error::other(get_msg(MessageKey::GenericUrlRequired)));
+        }
+        if let Some(hostname) = &self.set_hostname {
+            config.hostname = normalize_hostname(hostname).ok_or_else(|| {
+                io::Error::other(format_msg!(
+                    MessageKey::SetHostnameInvalidFmt,
+                    host = hostname
+                ))
+            })?;
+        }
+        if let Some(url) = &self.set_ping_url {
+            config.ping_url = normalize_optional_url(url).ok_or_else(|| {
+                io::Error::other(format_msg!(MessageKey::SetEndpointUrlInvalidFmt, url = url))
+            })?;
         }
+        if let Some(interface) = &self.set_interface {
+            config.bind_interface = normalize_interface(interface);
+        }
+        if let Some(schedule) = &self.set_schedule {
+            config.schedule = normalize_schedule(schedule).ok_or_else(|| {
+                io::Error::other(format_msg!(
+                    MessageKey::ScheduleInvalidFmt,
+                    schedule = schedule.trim()
+                ))
+            })?;
+        }
+        Ok(config)
     }
-
-    Ok(())
 }
 
-/// 既存のアカウント設定を編集するための対話モードを処理します。
-/// `target_id`が`Some`の場合はそのアカウントを直接編集し、`None`の場合はリストから選択させます。
-fn edit_mode(target_id: Option<String>) -> io::Result<()> {
-    println!("{}", get_msg("edit_title"));
+/// 通知の失敗を知らせるメールの設定を変更するための引数。
+///
+/// いずれかが指定された場合は、指定された項目だけを変更して保存します。
+#[derive(clap::Args, Clone, Debug, Default)]
+#[group(conflicts_with = "CheckIpEdits")]
+struct MailEdits {
+    /// 失敗を知らせるメールを送るSMTPサーバー（HOST または HOST:PORT、既定のポートは587）を設定します。"-"でメールを無効にします。
+    #[arg(long, value_name = "HOST[:PORT]", allow_hyphen_values = true)]
+    smtp_server: Option<String>,
 
-    let configs = load_all_configs().unwrap_or_else(|_| Vec::new());
-    if configs.is_empty() {
-        // 設定が一つもない場合は、新規追加モードに移行するか確認します。
-        if ask_yes_no(get_msg("no_accounts_add_prompt"), true)? {
-            return add_mode();
-        } else {
-            return Ok(());
-        }
+    /// SMTP認証のユーザー名を設定します。"-"で認証しないようにします。
+    #[arg(long, value_name = "USER", allow_hyphen_values = true)]
+    smtp_user: Option<String>,
+
+    /// SMTP認証のパスワードを、標準入力の1行目から読み込んだ値に変更します。
+    #[arg(long)]
+    smtp_password_stdin: bool,
+
+    /// メールの宛先を設定します。
+    #[arg(long, value_name = "ADDRESS")]
+    mail_to: Option<String>,
+
+    /// メールの差出人を設定します。"-"で宛先と同じアドレスを使います。
+    #[arg(long, value_name = "ADDRESS", allow_hyphen_values = true)]
+    mail_from: Option<String>,
+
+    /// 通知が何分以上失敗し続けたらメールで知らせるかを設定します。
+    #[arg(long, value_name = "MINUTES")]
+    mail_after: Option<u32>,
+}
+
+impl MailEdits {
+    /// 変更する項目が1つも指定されていないかどうかを返します。
+    fn is_empty(&self) -> bool {
+        self.smtp_server.is_none()
+            && self.smtp_user.is_none()
+            && !self.smtp_password_stdin
+            && self.mail_to.is_none()
+            && self.mail_from.is_none()
+            && self.mail_after.is_none()
     }
 
-    // 編集対象の設定を決定します。
-    let config_to_edit = match target_id {
-        Some(id) => {
-            // コマンドラインでIDが指定された場合、そのIDを持つ設定を探します。
-            if let Some(c) = configs.iter().find(|c| c.master_id == id) {
-                c.clone()
+    /// 指定された項目だけを `settings` に反映します。
+    ///
+    /// 値が正しくない項目があった場合は、何も変更せずにエラーを返します。
+    fn apply(&self, settings: &Settings) -> io::Result<Settings> {
+        let mut settings = settings.clone();
+        if let Some(server) = &self.smtp_server {
+            let server = server.trim();
+            if server == "-" {
+                settings.smtp_server.clear();
             } else {
-                // 指定されたIDが見つからなかった場合。
-                println!("{}", get_msg("account_not_found_fmt").replace("{}", &id));
-                return Ok(());
+                let (host, port) = match server.rsplit_once(':') {
+                    Some((host, port)) => (host, port.parse::<u16>().ok().filter(|p| *p != 0)),
+                    None => (server, Some(DEFAULT_SMTP_PORT)),
+                };
+                let Some(port) = port.filter(|_| !host.is_empty()) else {
+                    return Err(io::Error::other(format_msg!(
+                        MessageKey::SmtpServerInvalidFmt,
+                        server = server
+                    )));
+                };
+                settings.smtp_server = host.to_string();
+                settings.smtp_port = port;
             }
         }
-        None => {
-            // IDが指定されなかった場合、対話的に選択させます。
-            println!("{}", get_msg("select_account_prompt"));
-            for (i, c) in configs.iter().enumerate() {
-                println!("{}. {}", i + 1, c.master_id);
+        if let Some(user) = &self.smtp_user {
+            let user = user.trim();
+            settings.smtp_username = if user == "-" {
+                String::new()
+            } else {
+                user.to_string()
+            };
+        }
+        if self.smtp_password_stdin {
+            let mut buffer = String::new();
+            io::stdin().read_line(&mut buffer)?;
+            settings.smtp_password = buffer.trim_end_matches(['\r', '\n']).to_string();
+        }
+        for (input, address, allow_clear) in [
+            (&self.mail_to, &mut settings.mail_to, false),
+            (&self.mail_from, &mut settings.mail_from, true),
+        ] {
+            if let Some(input) = input {
+                let input = input.trim();
+                if allow_clear && input == "-" {
+                    address.clear();
+                } else if is_valid_address(input) {
+                    *address = input.to_string();
+                } else {
+                    return Err(io::Error::other(format_msg!(
+                        MessageKey::MailAddressInvalidFmt,
+                        address = input
+                    )));
+                }
             }
-            print!("{}", get_msg("select_account_index_prompt"));
-            io::stdout().flush()?;
-            let mut input = String::new();
-            io::stdin().read_line(&mut input)?;
-            let input = input.trim();
+        }
+        if let Some(minutes) = self.mail_after {
+            settings.mail_after_minutes = minutes;
+        }
+        Ok(settings)
+    }
+}
 
-            // ユーザーはリストの番号か、MasterID文字列のどちらでも入力できます。
-            if let Ok(index) = input.parse::<usize>() {
-                if index > 0 && index <= configs.len() {
-                    configs[index - 1].clone()
-                } else {
-                    println!("{}", get_msg("invalid_selection"));
-                    return Ok(());
+/// グローバルアドレスの確認サービスの設定を変更するための引数。
+///
+/// いずれかが指定された場合は、指定された項目だけを変更して保存します。
+#[derive(clap::Args, Clone, Debug, Default)]
+struct CheckIpEdits {
+    /// IPv4アドレスの確認サービスのURLを、問い合わせる順にカンマ区切りで設定します。"-"で既定のサービスに戻します。
+    #[arg(long, value_name = "URL,...", allow_hyphen_values = true)]
+    check_ipv4_urls: Option<String>,
+
+    /// IPv6アドレスの確認サービスのURLを、問い合わせる順にカンマ区切りで設定します。"-"で既定のサービスに戻します。
+    #[arg(long, value_name = "URL,...", allow_hyphen_values = true)]
+    check_ipv6_urls: Option<String>,
+
+    /// サービスがグローバルアドレスの変化を確認する間隔（分）を設定します。0で確認しません。
+    #[arg(long, value_name = "MINUTES")]
+    check_ip_poll: Option<u32>,
+}
+
+impl CheckIpEdits {
+    /// 変更する項目が1つも指定されていないかどうかを返します。
+    fn is_empty(&self) -> bool {
+        self.check_ipv4_urls.is_none()
+            && self.check_ipv6_urls.is_none()
+            && self.check_ip_poll.is_none()
+    }
+
+    /// 指定された項目だけを `settings` に反映します。
+    ///
+    /// URLとして正しくないものがあった場合は、何も変更せずにエラーを返します。
+    fn apply(&self, settings: &Settings) -> io::Result<Settings> {
+        let mut settings = settings.clone();
+        for (input, urls) in [
+            (&self.check_ipv4_urls, &mut settings.check_ipv4_urls),
+            (&self.check_ipv6_urls, &mut settings.check_ipv6_urls),
+        ] {
+            let Some(input) = input else {
+                continue;
+            };
+            let mut parsed = Vec::new();
+            for url in input.split([',', ' ']).filter(|url| !url.trim().is_empty()) {
+                match normalize_optional_url(url) {
+                    Some(url) if !url.is_empty() => parsed.push(url),
+                    // 単独の "-" は、既定のサービスに戻すことを表す。
+                    Some(_) if input.trim() == "-" => {}
+                    _ => {
+                        return Err(io::Error::other(format_msg!(
+                            MessageKey::SetEndpointUrlInvalidFmt,
+                            url = url.trim()
+                        )));
+                    }
                 }
-            } else if let Some(c) = configs.iter().find(|c| c.master_id == input) {
-                c.clone()
-            } else {
-                println!("{}", get_msg("invalid_selection"));
-                return Ok(());
+            }
+            *urls = parsed;
+        }
+        if let Some(minutes) = self.check_ip_poll {
+            settings.check_ip_poll_minutes = minutes;
+        }
+        Ok(settings)
+    }
+}
+
+/// `service` サブコマンドの操作。
+#[derive(Subcommand, Debug)]
+enum ServiceCommand {
+    /// アプリケーションをWindowsサービスとしてインストールし、開始します。
+    Install {
+        /// 定期通知の間隔（分）を、サービスの開始前に設定します。
+        #[arg(long, value_name = "MINUTES")]
+        interval: Option<u32>,
+
+        /// 通知に使うHTTPプロキシのURLを、サービスの開始前に設定します。
+        #[arg(long, value_name = "URL", allow_hyphen_values = true)]
+        proxy: Option<String>,
+
+        /// サービスを実行するアカウント（DOMAIN\user など）。LocalService、NetworkService も指定できます。
+        /// 省略した場合は LocalSystem で実行します。
+        #[arg(long, value_name = "NAME")]
+        account: Option<String>,
+
+        /// `--account` に指定したアカウントのパスワード。省略した場合は入力を求めます。
+        #[arg(long, value_name = "PASSWORD", requires = "account")]
+        service_password: Option<String>,
+    },
+
+    /// Windowsサービスを停止し、アンインストールします。
+    Uninstall {
+        /// 保存されたアカウントと設定（レジストリの `Software\MyDNSAdapter`）と、ログファイルも削除します。
+        #[arg(long)]
+        purge: bool,
+
+        /// `--purge` で削除する前に確認しません。スクリプトからの実行向けです。
+        #[arg(short, long, requires = "purge")]
+        yes: bool,
+    },
+
+    /// Windowsサービスを再起動します。
+    Restart,
+
+    /// インストール済みのWindowsサービスを開始します。
+    Start,
+
+    /// Windowsサービスを停止します。アンインストールはしません。
+    Stop,
+
+    /// 実行中のサービスに、定期通知を待たずにすぐ通知するよう要求します。
+    NotifyNow,
+
+    /// 実行中のサービスに、アカウント設定を読み込み直すよう要求します。
+    ReloadConfig,
+
+    /// 実行中のサービスの状態（開始時刻、アカウント数、次の定期通知の予定時刻など）を問い合わせます。
+    QueryStatus,
+
+    /// 実行中のサービスを通じて、ログファイルの末尾を表示します。
+    TailLog {
+        /// 表示する行数。
+        #[arg(long, value_name = "LINES", default_value_t = 50)]
+        lines: usize,
+    },
+
+    /// Windowsサービスの状態と、アカウントごとの直近の通知結果を表示します。（--statusと同じ）
+    Status,
+}
+
+impl Args {
+    /// 旧形式のフラグ（`--add` や `--install` など）で指定された操作を、対応するサブコマンドに変換します。
+    fn legacy_command(&self) -> Option<Command> {
+        if self.install {
+            Some(Command::Service(ServiceCommand::Install {
+                interval: self.interval,
+                proxy: self.proxy.clone(),
+                account: self.account.clone(),
+                service_password: self.service_password.clone(),
+            }))
+        } else if self.uninstall {
+            Some(Command::Service(ServiceCommand::Uninstall {
+                purge: self.purge,
+                yes: self.yes,
+            }))
+        } else if self.restart {
+            Some(Command::Service(ServiceCommand::Restart))
+        } else if self.start {
+            Some(Command::Service(ServiceCommand::Start))
+        } else if self.stop {
+            Some(Command::Service(ServiceCommand::Stop))
+        } else if self.add {
+            Some(Command::Account(AccountCommand::Add {
+                password_stdin: self.password_stdin,
+                batch: self.batch.clone(),
+            }))
+        } else if let Some(id) = &self.remove {
+            Some(Command::Account(AccountCommand::Remove {
+                master_id: id.clone(),
+                yes: self.yes,
+            }))
+        } else if let Some([old_id, new_id]) = self.rename.as_deref() {
+            Some(Command::Account(AccountCommand::Rename {
+                old_id: old_id.clone(),
+                new_id: new_id.clone(),
+            }))
+        } else if let Some(id) = &self.enable {
+            Some(Command::Account(AccountCommand::Enable {
+                master_id: id.clone(),
+            }))
+        } else if let Some(id_arg) = &self.edit {
+            // `--edit` の値は省略できます。
+            // `default_missing_value`により、値なしの場合は特殊な文字列が入ります。
+            Some(Command::Account(AccountCommand::Edit {
+                master_id: (id_arg != "_INTERACTIVE_").then(|| id_arg.clone()),
+                password_stdin: self.password_stdin,
+                fields: self.field_edits.clone(),
+            }))
+        } else if self.view || self.list {
+            // `--view` と `--list` は同じ機能です。
+            Some(Command::Account(AccountCommand::List {
+                format: self.format,
+            }))
+        } else if self.notify || self.ipv4 || self.ipv6 {
+            // -n (--notify) はIPv4/v6両方、-4 (--ipv4) はIPv4のみ、-6 (--ipv6) はIPv6のみを通知します。
+            Some(Command::Notify {
+                ipv4: self.notify || self.ipv4,
+                ipv6: self.notify || self.ipv6,
+                force: self.force,
+                account: self
+                    .account
+                    .iter()
+                    .flat_map(|ids| ids.split(','))
+                    .map(str::to_string)
+                    .collect(),
+                dry_run: self.dry_run,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// `--lang` オプションの値を解釈します。
+fn parse_lang(code: &str) -> Result<Lang, String> {
+    Lang::from_code(code).ok_or_else(|| get_msg(MessageKey::LangInvalid).to_string())
+}
+
+/// `--set-provider` オプションの値を解釈します。
+fn parse_provider(code: &str) -> Result<Provider, String> {
+    Provider::from_code(code).ok_or_else(|| get_msg(MessageKey::ProviderInvalid).to_string())
+}
+
+/// `--set-method` オプションの値を解釈します。
+fn parse_method(code: &str) -> Result<NotifyMethod, String> {
+    NotifyMethod::from_code(code).ok_or_else(|| get_msg(MessageKey::MethodInvalid).to_string())
+}
+
+/// `--metered` オプションの値を解釈します。
+fn parse_metered_policy(code: &str) -> Result<MeteredPolicy, String> {
+    MeteredPolicy::from_code(code)
+        .ok_or_else(|| get_msg(MessageKey::MeteredPolicyInvalid).to_string())
+}
+
+/// アプリケーションのメインエントリーポイント。
+///
+/// 処理の結果に応じて、`exitcode` モジュールで定義した終了コードでプロセスを終了します。
+fn main() -> ExitCode {
+    crash::install_panic_hook();
+    // パニックの内容はフックで記録済みのため、ここでは終了コードだけを決める。
+    let reason = match panic::catch_unwind(run) {
+        Ok(Ok(reason)) => reason,
+        Ok(Err(e)) => {
+            eprintln!("Error: {}", e);
+            e.exit_reason()
+        }
+        Err(_) => ExitReason::Error,
+    };
+    // 昇格して実行し直したプロセスは新しいコンソールウィンドウで動くため、
+    // ウィンドウが閉じる前に結果を読めるよう、Enterキーの入力を待ちます。
+    if is_relaunched() {
+        print!("{}", get_msg(MessageKey::ElevatedPressEnter));
+        let _ = io::stdout().flush();
+        let _ = io::stdin().read_line(&mut String::new());
+    }
+    reason.into()
+}
+
+/// 引数を解析し、対応する処理を実行します。
+///
+/// # 戻り値
+/// 処理の結果に応じたプロセスの終了の理由。
+fn run() -> Result<ExitReason, AdapterError> {
+    // Windowsサービスとして実行するための特別な引数チェック。
+    // `windows-service`クレートは、`--service`引数でサービスディスパッチャを起動します。
+    // このチェックは、clapによる通常の引数解析の前に行う必要があります。
+    if env::args().any(|arg| arg == "--service" || arg == "-s") {
+        // インストール時に指定された言語は、サービスのコマンドラインで `--lang` として渡されます。
+        if let Some(lang) = env::args()
+            .skip_while(|arg| arg != "--lang")
+            .nth(1)
+            .and_then(|code| Lang::from_code(&code))
+        {
+            set_lang_override(lang);
+        }
+        // サービス実行ループに入り、サービスが停止するまで制御を返しません。
+        run_service()?;
+        return Ok(ExitReason::Success);
+    }
+
+    // サービスモードでない場合は、通常のCLIアプリケーションとして引数を解析します。
+    let mut args = Args::parse();
+    if let Some(lang) = args.lang {
+        set_lang_override(lang);
+    }
+    if args.quiet {
+        console::set_verbosity(console::Verbosity::Quiet);
+    } else if args.verbose {
+        console::set_verbosity(console::Verbosity::Verbose);
+    }
+    console::init_color(args.no_color);
+    // サブコマンドが指定されていなければ、旧形式のフラグから操作を決定します。
+    let command = args.command.take().or_else(|| args.legacy_command());
+
+    // サービスはユーザーごとの設定を参照しないため、サービスの操作と `--user` は併用できません。
+    if args.user && matches!(command, Some(Command::Service(_))) {
+        Args::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                get_msg(MessageKey::UserScopeServiceConflict),
+            )
+            .exit();
+    }
+
+    // 設定の保存先を決定します。管理者権限がなく、マシン全体の設定にアカウントもない場合は、
+    // UACなしで使えるように、ユーザーごとの保存先に自動的に切り替えます。
+    let installing = matches!(
+        command,
+        Some(Command::Service(ServiceCommand::Install { .. }))
+    ) || args.install_task;
+    if let Some(path) = &args.config_file {
+        set_config_file(path.clone());
+    }
+    if args.user {
+        set_config_scope(ConfigScope::User);
+    } else if !installing
+        && !machine_scope_writable()
+        && load_all_configs().map_or(true, |configs| configs.is_empty())
+    {
+        set_config_scope(ConfigScope::User);
+        // `account list --format json` などの出力を壊さないよう、標準エラー出力に表示します。
+        eprintln!("{}", get_msg(MessageKey::UserScopeFallback));
+    }
+
+    // 管理者権限が必要な操作を権限のないコンソールから実行した場合は、エラーで終了する代わりに、
+    // 確認のうえUACで昇格して実行し直します。
+    if !args.elevated && needs_elevation(&command, &args) && io::stdin().is_terminal() {
+        if ask_yes_no_simple(get_msg(MessageKey::ElevationConfirm), true)? {
+            return relaunch_elevated();
+        }
+        return Err(ExitError::new(
+            ExitReason::AdminRequired,
+            get_msg(MessageKey::ElevationRequired),
+        )
+        .into());
+    }
+
+    // 解析された引数に基づいて、対応する処理モードに分岐します。
+    // 各モードは排他的に実行されるため（併用はclapの引数グループで拒否されます）、if-else ifで順に評価します。
+    // 終了コードで結果を伝えるモードは、`reason` を書き換えます。
+    let mut reason = ExitReason::Success;
+    if let Some(command) = command {
+        // サブコマンド（または旧形式のフラグ）で指定された操作
+        reason = run_command(command)?;
+    } else if args.interval.is_some() || args.proxy.is_some() {
+        // 定期通知の間隔とプロキシの設定モード。両方を指定した場合は、両方を設定します。
+        if let Some(minutes) = args.interval {
+            interval_mode(minutes)?;
+        }
+        if let Some(url) = &args.proxy {
+            proxy_mode(url)?;
+        }
+    } else if let Some(count) = args.retry_attempts {
+        // 通知の最大試行回数の設定モード
+        retry_attempts_mode(count)?;
+    } else if let Some(count) = args.concurrency {
+        // 同時に通知するアカウントの数の設定モード
+        concurrency_mode(count)?;
+    } else if let Some(seconds) = args.request_timeout {
+        // 通知リクエストのタイムアウトの設定モード
+        request_timeout_mode(seconds)?;
+    } else if let Some(path) = &args.log_path {
+        // ログファイルの場所の設定モード
+        log_path_mode(path)?;
+    } else if args.log_archives.is_some() || args.log_compress.is_some() {
+        // ログのアーカイブの設定モード
+        log_archive_mode(args.log_archives, args.log_compress)?;
+    } else if let Some(port) = args.metrics_port {
+        // メトリクスの公開の設定モード
+        metrics_port_mode(port)?;
+    } else if let Some(port) = args.admin_api_port {
+        // 管理APIの公開の設定モード
+        admin_api_mode(port)?;
+    } else if let Some(url) = &args.ping_url {
+        // 死活監視のping URLの設定モード
+        ping_url_mode(url)?;
+    } else if let Some(url) = &args.webhook_url {
+        // WebhookのURLの設定モード
+        webhook_url_mode(url)?;
+    } else if let Some(skip) = args.skip_private_ipv4 {
+        // 非公開のIPv4アドレスの扱いの設定モード
+        skip_private_ipv4_mode(skip)?;
+    } else if let Some(minutes) = args.dns_check {
+        // DNSの自己確認の設定モード
+        dns_check_mode(minutes)?;
+    } else if let Some(policy) = args.metered {
+        // 従量制課金の接続での通知の扱いの設定モード
+        metered_mode(policy)?;
+    } else if let Some(networks) = args.allowed_networks.as_deref() {
+        // 通知するネットワークの設定モード
+        allowed_networks_mode(networks)?;
+    } else if let Some(hours) = args.quiet_hours.as_deref() {
+        // 静穏時間帯の設定モード
+        quiet_hours_mode(hours)?;
+    } else if let Some(seconds) = args.jitter {
+        // 通知の時刻をずらす範囲の設定モード
+        jitter_mode(seconds)?;
+    } else if let Some(id) = &args.user_agent_id {
+        // User-Agentに付ける識別子の設定モード
+        user_agent_id_mode(id)?;
+    } else if let Some(enabled) = args.history_db {
+        // 履歴のデータベースの設定モード
+        history_db_mode(enabled)?;
+    } else if let Some(count) = args.toast_after {
+        // トースト通知の設定モード
+        toast_after_mode(count)?;
+    } else if args.toast_monitor {
+        // トースト通知による失敗の監視モード
+        toast_monitor_mode()?;
+    } else if !args.mail_edits.is_empty() {
+        // メールの設定モード
+        mail_settings_mode(&args.mail_edits)?;
+    } else if !args.check_ip_edits.is_empty() {
+        // グローバルアドレスの確認サービスの設定モード
+        check_ip_settings_mode(&args.check_ip_edits)?;
+    } else if args.mail_test {
+        // テストメールの送信モード
+        mail_test_mode()?;
+    } else if args.pause || args.resume {
+        // 一時停止・再開モード
+        pause_mode(args.pause)?;
+    } else if args.kick {
+        // 即時通知要求モード
+        kick_mode()?;
+    } else if args.status {
+        // 状態表示モード
+        status_mode()?;
+    } else if args.run_foreground {
+        // サービスの処理をコンソールで実行するモード
+        run_foreground()?;
+    } else if args.install_task {
+        // タスクの登録モード
+        install_task()?;
+    } else if args.uninstall_task {
+        // タスクの削除モード
+        uninstall_task()?;
+    } else if let Some(id) = &args.test {
+        // 認証情報の確認モード
+        reason = test_credentials_mode(id)?;
+    } else if args.simulate {
+        // シミュレーションモード
+        simulate_mode()?;
+    } else if let Some(count) = args.log {
+        // ログ表示モード
+        let filter = LogFilter {
+            min_level: args.level,
+            account: args.account.clone(),
+        };
+        log_mode(count, &filter, args.follow)?;
+    } else if args.upgrade_config {
+        // 設定の更新モード
+        upgrade_config_mode()?;
+    } else if args.stats {
+        // 利用統計モード
+        // --opt-in / --opt-out で記録の有効・無効を切り替え、それ以外は概要を表示します。
+        if args.opt_in || args.opt_out {
+            set_stats_enabled(args.opt_in)?;
+        } else {
+            print_stats_summary()?;
+        }
+    } else if let Some(path) = &args.export {
+        // 設定の書き出しモード
+        export_mode(path, args.redact_passwords)?;
+    } else if let Some(path) = &args.import {
+        // 設定の読み込みモード
+        import_mode(path)?;
+    } else if args.bench {
+        // ベンチマークモード
+        bench_mode()?;
+    } else {
+        // 引数が何も指定されなかった場合のデフォルト動作。
+        // ユーザーが設定を手軽に変更できるよう、対話的な編集モードを開始します。
+        edit_mode(None, false, &FieldEdits::default())?;
+    }
+    Ok(reason)
+}
+
+/// 指定された操作に管理者権限が必要で、現在のプロセスにその権限がないかどうかを判定します。
+///
+/// サービスとタスクスケジューラのタスクの操作（状態の表示を除く）には、常に管理者権限が必要です。
+/// 設定を変更する操作は、マシン全体の設定（HKLM）に書き込めない場合にだけ必要です。
+fn needs_elevation(command: &Option<Command>, args: &Args) -> bool {
+    let writes_settings = match command {
+        // 状態の確認と、名前付きパイプを通じたサービスへの要求には、管理者権限は不要。
+        Some(Command::Service(
+            ServiceCommand::Status
+            | ServiceCommand::NotifyNow
+            | ServiceCommand::ReloadConfig
+            | ServiceCommand::QueryStatus
+            | ServiceCommand::TailLog { .. },
+        )) => false,
+        Some(Command::Service(_)) => return !is_elevated(),
+        Some(Command::Account(
+            AccountCommand::Add { .. }
+            | AccountCommand::Edit { .. }
+            | AccountCommand::Remove { .. }
+            | AccountCommand::Rename { .. }
+            | AccountCommand::Enable { .. },
+        )) => true,
+        Some(_) => false,
+        // タスクの登録と削除は、サービスの操作と同じく常に管理者権限が必要。
+        None if args.install_task || args.uninstall_task => return !is_elevated(),
+        None => {
+            args.interval.is_some()
+                || args.retry_attempts.is_some()
+                || args.concurrency.is_some()
+                || args.request_timeout.is_some()
+                || args.proxy.is_some()
+                || args.log_path.is_some()
+                || args.log_archives.is_some()
+                || args.log_compress.is_some()
+                || args.metrics_port.is_some()
+                || args.admin_api_port.is_some()
+                || args.ping_url.is_some()
+                || args.webhook_url.is_some()
+                || args.skip_private_ipv4.is_some()
+                || args.dns_check.is_some()
+                || args.metered.is_some()
+                || args.allowed_networks.is_some()
+                || args.quiet_hours.is_some()
+                || args.jitter.is_some()
+                || args.user_agent_id.is_some()
+                || args.history_db.is_some()
+                || args.toast_after.is_some()
+                || !args.mail_edits.is_empty()
+                || !args.check_ip_edits.is_empty()
+                || args.upgrade_config
+                || args.import.is_some()
+                || (args.stats && (args.opt_in || args.opt_out))
+        }
+    };
+    writes_settings && config_scope() == ConfigScope::Machine && !machine_scope_writable()
+}
+
+/// サブコマンドで指定された操作を実行します。
+///
+/// # 戻り値
+/// 操作の結果に応じたプロセスの終了の理由。
+fn run_command(command: Command) -> Result<ExitReason, AdapterError> {
+    match command {
+        Command::Account(AccountCommand::Add {
+            batch: Some(path), ..
+        }) => add_batch_mode(&path)?,
+        Command::Account(AccountCommand::Add { password_stdin, .. }) => add_mode(password_stdin)?,
+        Command::Account(AccountCommand::Edit {
+            master_id,
+            password_stdin,
+            fields,
+        }) => edit_mode(master_id, password_stdin, &fields)?,
+        Command::Account(AccountCommand::Remove { master_id, yes }) => {
+            remove_mode(&master_id, yes)?
+        }
+        Command::Account(AccountCommand::Rename { old_id, new_id }) => {
+            rename_mode(&old_id, &new_id)?
+        }
+        Command::Account(AccountCommand::Enable { master_id }) => enable_mode(&master_id)?,
+        Command::Account(AccountCommand::Test { master_id }) => {
+            return test_credentials_mode(&master_id);
+        }
+        Command::Account(AccountCommand::List { format }) => match format {
+            ViewFormat::Table => view_mode()?,
+            format => view_machine_mode(format)?,
+        },
+        Command::Service(ServiceCommand::Install {
+            interval,
+            proxy,
+            account,
+            service_password,
+        }) => {
+            // 実行するアカウントとパスワードは、設定を変更する前に確定させておきます。
+            let logon = account
+                .as_deref()
+                .map(|name| service_logon(name, service_password))
+                .transpose()?;
+            // インストール時に間隔やプロキシが指定された場合は、サービスの開始前に保存しておきます。
+            if let Some(minutes) = interval {
+                interval_mode(minutes)?;
+            }
+            if let Some(url) = &proxy {
+                proxy_mode(url)?;
+            }
+            install_service(logon.as_ref())?;
+        }
+        Command::Service(ServiceCommand::Uninstall { purge, yes }) => {
+            // 削除を取り消せるよう、サービスを解除する前に確認する。
+            if purge && !confirm_purge(yes)? {
+                outln!("{}", get_msg(MessageKey::OperationCancelled));
+                return Ok(ExitReason::Success);
+            }
+            uninstall_service()?;
+            if purge {
+                purge_mode()?;
+            }
+        }
+        Command::Service(ServiceCommand::Restart) => restart_service()?,
+        Command::Service(ServiceCommand::Start) => start_service()?,
+        Command::Service(ServiceCommand::Stop) => stop_service()?,
+        Command::Service(ServiceCommand::NotifyNow) => pipe_command_mode(Request::NotifyNow)?,
+        Command::Service(ServiceCommand::ReloadConfig) => pipe_command_mode(Request::ReloadConfig)?,
+        Command::Service(ServiceCommand::QueryStatus) => pipe_command_mode(Request::QueryStatus)?,
+        Command::Service(ServiceCommand::TailLog { lines }) => {
+            pipe_command_mode(Request::TailLog(lines))?
+        }
+        Command::Service(ServiceCommand::Status) => status_mode()?,
+        Command::Notify {
+            ipv4,
+            ipv6,
+            force,
+            account,
+            dry_run,
+        } => {
+            // どちらも指定されなかった場合は、IPv4とIPv6の両方を通知します。
+            let both = !ipv4 && !ipv6;
+            return notify_now_mode(&NotifyOptions {
+                ipv4: ipv4 || both,
+                ipv6: ipv6 || both,
+                force,
+                accounts: account,
+                dry_run,
+            });
+        }
+        Command::Doctor => return doctor_mode(),
+        Command::History { account, count } => history_mode(account.as_deref(), count)?,
+    }
+    Ok(ExitReason::Success)
+}
+
+/// 新しいアカウント設定を追加するための対話モードを処理します。
+fn add_mode(password_stdin: bool) -> io::Result<()> {
+    outln!("{}", get_msg(MessageKey::AddTitle));
+
+    // 通知先のサービスの入力（通常はEnterキーでMyDNS.JPを選ぶ）
+    let provider = ask_provider(Provider::MyDns)?;
+
+    // MasterIDの入力。汎用のサービスでは、Basic認証のユーザー名をアカウントの名前にも使う。
+    let id_prompt = match provider {
+        Provider::MyDns => MessageKey::MasterIdPrompt,
+        Provider::Generic => MessageKey::UsernamePrompt,
+    };
+    let master_id = ask_with_default(get_msg(id_prompt), "", false)?;
+
+    // 重複チェック
+    let configs = load_all_configs().unwrap_or_else(|_| Vec::new());
+    if configs.iter().any(|c| c.master_id == master_id) {
+        outln!(
+            "{}",
+            format_msg!(MessageKey::AccountExistsFmt, id = master_id)
+        );
+        return Ok(());
+    }
+
+    // MasterIDの基本的な形式を検証します。
+    if provider == Provider::MyDns && !master_id.starts_with("mydns") {
+        outln!("{}", get_msg(MessageKey::InvalidMasterIdPrefix));
+        return Ok(());
+    }
+
+    // パスワードの入力
+    let password = ask_password("", password_stdin)?;
+
+    // IPv4/IPv6通知の入力
+    let ipv4_notify = ask_yes_no_simple(get_msg(MessageKey::Ipv4NotifyPrompt), true)?;
+    let ipv6_notify = ask_yes_no_simple(get_msg(MessageKey::Ipv6NotifyPrompt), true)?;
+
+    // 強制通知（キープアライブ）間隔の入力
+    let keep_alive_hours = ask_number_with_default(
+        get_msg(MessageKey::KeepAlivePrompt),
+        DEFAULT_KEEP_ALIVE_HOURS,
+    )?;
+
+    // アカウント個別のプロキシの入力（通常は空欄のまま全体の設定に従う）
+    let proxy_url = ask_proxy_url("")?;
+
+    // 通知の方法の入力（MyDNS.JPだけ。通常はEnterキーでHTTPSを使う）
+    let method = ask_method(provider, NotifyMethod::Http)?;
+
+    // 通知先URLの入力（MyDNS.JPのHTTPSでの通知では、通常はEnterキーで既定のURLを使う）
+    let Some((ipv4_url, ipv6_url)) = ask_provider_urls(provider, method, "", "")? else {
+        return Ok(());
+    };
+
+    // 通知後にDNSの反映を確認するホスト名の入力（空欄なら確認しない）
+    let hostname = ask_hostname("")?;
+
+    // 通知に成功したときにpingを送るURLの入力（空欄なら送らない）
+    let ping_url = ask_ping_url("")?;
+
+    // 通知を送るインターフェイスの入力（空欄なら選ばない）
+    let bind_interface = ask_interface("")?;
+
+    // 通知のスケジュールの入力（空欄なら定期通知で通知する）
+    let schedule = ask_schedule("")?;
+
+    // 新しい設定をレジストリに保存します。
+    let config = Config {
+        master_id: master_id.clone(),
+        password,
+        ipv4_notify,
+        ipv6_notify,
+        keep_alive_hours,
+        proxy_url,
+        ipv4_url,
+        ipv6_url,
+        hostname,
+        provider,
+        method,
+        ping_url,
+        bind_interface,
+        schedule,
+    };
+    match save_config(&config) {
+        Ok(_) => {
+            let msg = get_msg(MessageKey::AddSuccess);
+            outln!("{}", msg);
+            log_info(&format!("Account added: {}", master_id));
+        }
+        Err(e) => {
+            let msg = format_msg!(MessageKey::RegistrySaveFailFmt, error = e);
+            outln!("{}", msg);
+            log_error(&format!("Failed to add account {}: {}", master_id, e));
+        }
+    }
+
+    Ok(())
+}
+
+/// 既存のアカウント設定を編集するための対話モードを処理します。
+/// `target_id`が`Some`の場合はそのアカウントを直接編集し、`None`の場合はリストから選択させます。
+/// `fields` で変更する項目が指定された場合は、対話的な入力を行わずにその項目だけを変更します。
+fn edit_mode(
+    target_id: Option<String>,
+    password_stdin: bool,
+    fields: &FieldEdits,
+) -> io::Result<()> {
+    outln!("{}", get_msg(MessageKey::EditTitle));
+
+    let configs = load_all_configs().unwrap_or_else(|_| Vec::new());
+    if configs.is_empty() {
+        // 設定が一つもない場合は、新規追加モードに移行するか確認します。
+        if ask_yes_no(get_msg(MessageKey::NoAccountsAddPrompt), true)? {
+            return add_mode(password_stdin);
+        } else {
+            return Ok(());
+        }
+    }
+
+    // 編集対象の設定を決定します。
+    let config_to_edit = match target_id {
+        Some(id) => {
+            // コマンドラインでIDが指定された場合、そのIDを持つ設定を探します。
+            if let Some(c) = configs.iter().find(|c| c.master_id == id) {
+                c.clone()
+            } else {
+                // 指定されたIDが見つからなかった場合。
+                outln!("{}", format_msg!(MessageKey::AccountNotFoundFmt, id = id));
+                return Ok(());
+            }
+        }
+        None => {
+            // IDが指定されなかった場合、対話的に選択させます。
+            println!("{}", get_msg(MessageKey::SelectAccountPrompt));
+            for (i, c) in configs.iter().enumerate() {
+                println!("{}. {}", i + 1, c.master_id);
+            }
+            print!("{}", get_msg(MessageKey::SelectAccountIndexPrompt));
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            // IMEが有効なまま全角で入力された番号も受け付けられるよう、半角に正規化します。
+            let input = normalize_width(&input);
+            let input = input.trim();
+
+            // ユーザーはリストの番号か、MasterID文字列のどちらでも入力できます。
+            if let Ok(index) = input.parse::<usize>() {
+                if index > 0 && index <= configs.len() {
+                    configs[index - 1].clone()
+                } else {
+                    println!("{}", get_msg(MessageKey::InvalidSelection));
+                    return Ok(());
+                }
+            } else if let Some(c) = configs.iter().find(|c| c.master_id == input) {
+                c.clone()
+            } else {
+                println!("{}", get_msg(MessageKey::InvalidSelection));
+                return Ok(());
+            }
+        }
+    };
+
+    outln!(
+        "{}",
+        format_msg!(MessageKey::EditTargetFmt, id = config_to_edit.master_id)
+    );
+
+    // 変更する項目が指定された場合は、その項目だけを変更して保存します。
+    if !fields.is_empty() {
+        let config = fields.apply(&config_to_edit)?;
+        save_edited_config(&config);
+        return Ok(());
+    }
+
+    // 各設定項目を、現在の値をデフォルトとしてユーザーに再入力させます。
+    let password = ask_password(&config_to_edit.password, password_stdin)?;
+    let ipv4_notify = ask_yes_no(
+        get_msg(MessageKey::Ipv4NotifyPrompt),
+        config_to_edit.ipv4_notify,
+    )?;
+    let ipv6_notify = ask_yes_no(
+        get_msg(MessageKey::Ipv6NotifyPrompt),
+        config_to_edit.ipv6_notify,
+    )?;
+    let keep_alive_hours = ask_number_with_default(
+        get_msg(MessageKey::KeepAlivePrompt),
+        config_to_edit.keep_alive_hours,
+    )?;
+    let proxy_url = ask_proxy_url(&config_to_edit.proxy_url)?;
+    let provider = ask_provider(config_to_edit.provider)?;
+    // 通知先URLの意味はサービスごとに異なるため、サービスを変えた場合は引き継がない。
+    let (current_ipv4_url, current_ipv6_url) = if provider == config_to_edit.provider {
+        (
+            config_to_edit.ipv4_url.as_str(),
+            config_to_edit.ipv6_url.as_str(),
+        )
+    } else {
+        ("", "")
+    };
+    let method = ask_method(provider, config_to_edit.method)?;
+    let Some((ipv4_url, ipv6_url)) =
+        ask_provider_urls(provider, method, current_ipv4_url, current_ipv6_url)?
+    else {
+        return Ok(());
+    };
+    let hostname = ask_hostname(&config_to_edit.hostname)?;
+    let ping_url = ask_ping_url(&config_to_edit.ping_url)?;
+    let bind_interface = ask_interface(&config_to_edit.bind_interface)?;
+    let schedule = ask_schedule(&config_to_edit.schedule)?;
+
+    // 更新された設定を保存します。
+    // MasterIDはレジストリのキー名であるため、変更はできません。
+    let config = Config {
+        password,
+        ipv4_notify,
+        ipv6_notify,
+        keep_alive_hours,
+        proxy_url,
+        ipv4_url,
+        ipv6_url,
+        hostname,
+        provider,
+        method,
+        ping_url,
+        bind_interface,
+        schedule,
+        ..config_to_edit.clone()
+    };
+    save_edited_config(&config);
+
+    Ok(())
+}
+
+/// 編集したアカウント設定を保存し、結果を表示してログに記録します。
+fn save_edited_config(config: &Config) {
+    match save_config(config) {
+        Ok(_) => {
+            let msg = get_msg(MessageKey::RegistrySaveSuccess);
+            outln!("{}", msg);
+            log_info(&format!("Account edited: {}", config.master_id));
+        }
+        Err(e) => {
+            let msg = format_msg!(MessageKey::RegistrySaveFailFmt, error = e);
+            outln!("{}", msg);
+            log_error(&format!(
+                "Failed to edit account {}: {}",
+                config.master_id, e
+            ));
+        }
+    }
+}
+
+/// 指定されたIDのアカウント設定を削除する処理を行います。
+///
+/// `yes` が `true` の場合は、確認せずに削除します。
+/// 確認が必要なのに標準入力が端末でない場合は、回答を得られないため削除せずにエラーで終了します。
+fn remove_mode(id: &str, yes: bool) -> io::Result<()> {
+    outln!("{}", get_msg(MessageKey::RemoveTitle));
+
+    if !yes && !io::stdin().is_terminal() {
+        return Err(io::Error::other(get_msg(MessageKey::RemoveNeedsYes)));
+    }
+    // 破壊的な操作であるため、`--yes` が指定されない限り、実行前に必ず確認を求めます。
+    if yes || ask_yes_no_simple(&format_msg!(MessageKey::ConfirmRemoveFmt, id = id), false)? {
+        match delete_config(id) {
+            Ok(_) => {
+                let msg = get_msg(MessageKey::RemoveSuccess);
+                outln!("{}", msg);
+                log_info(&format!("Account removed: {}", id));
+            }
+            Err(e) => {
+                let msg = format_msg!(MessageKey::RemoveFailFmt, error = e);
+                outln!("{}", msg);
+                log_error(&format!("Failed to remove account {}: {}", id, e));
+            }
+        }
+    } else {
+        outln!("{}", get_msg(MessageKey::OperationCancelled));
+    }
+    Ok(())
+}
+
+/// サービスのアンインストールとともに、保存されたアカウントと設定、ログファイルを削除してよいか確認します。
+///
+/// `yes` が `true` の場合は、確認せずに削除します。
+/// 確認が必要なのに標準入力が端末でない場合は、回答を得られないためエラーで終了します。
+fn confirm_purge(yes: bool) -> io::Result<bool> {
+    if yes {
+        return Ok(true);
+    }
+    if !io::stdin().is_terminal() {
+        return Err(io::Error::other(get_msg(MessageKey::PurgeNeedsYes)));
+    }
+    ask_yes_no_simple(get_msg(MessageKey::ConfirmPurge), false)
+}
+
+/// 保存されたアカウントと設定（レジストリ）と、ログファイルを削除します。
+///
+/// ログの場所は設定から決まるため、ログファイルを先に削除します。
+/// 削除したログファイルを作り直さないよう、この後はログに記録しません。
+fn purge_mode() -> Result<(), AdapterError> {
+    delete_log_files()?;
+    delete_all_settings()?;
+    // 設定ファイルにはパスワードが平文で含まれるため、レジストリと同じように削除する。
+    if let Some(path) = config_file() {
+        FileStore::new(path).delete_files()?;
+    }
+    outln!("{}", get_msg(MessageKey::PurgeSuccess));
+    Ok(())
+}
+
+/// アカウントのMasterIDを変更します。
+///
+/// レジストリのキーの名前を変更し、実行時状態（最終成功時刻など）と通知の履歴も新しいMasterIDに引き継ぎます。
+fn rename_mode(old_id: &str, new_id: &str) -> Result<(), AdapterError> {
+    let new_id = new_id.trim();
+    let configs = load_all_configs().unwrap_or_default();
+    let Some(old) = configs.iter().find(|c| c.master_id == old_id) else {
+        return Err(format_msg!(MessageKey::AccountNotFoundFmt, id = old_id).into());
+    };
+    if old.provider == Provider::MyDns && !new_id.starts_with("mydns") {
+        return Err(get_msg(MessageKey::InvalidMasterIdPrefix).into());
+    }
+    if configs.iter().any(|c| c.master_id == new_id) {
+        return Err(format_msg!(MessageKey::AccountExistsFmt, id = new_id).into());
+    }
+
+    if let Err(e) = rename_config(old_id, new_id) {
+        log_error(&format!(
+            "Failed to rename account {} to {}: {}",
+            old_id, new_id, e
+        ));
+        return Err(format_msg!(MessageKey::RegistrySaveFailFmt, error = e).into());
+    }
+    // 実行時状態と履歴は補助的な情報のため、引き継ぎに失敗してもアカウントの変更は取り消さない。
+    let _ = update_state(|s| {
+        if let Some(account) = s.accounts.remove(old_id) {
+            s.accounts.insert(new_id.to_string(), account);
+        }
+    });
+    let _ = rename_history(old_id, new_id);
+
+    outln!(
+        "{}",
+        format_msg!(MessageKey::RenameSuccessFmt, old = old_id, new = new_id)
+    );
+    log_info(&format!("Account renamed: {} -> {}", old_id, new_id));
+    Ok(())
+}
+
+/// 認証エラーが続いて自動で停止されたアカウントの通知を再開します。
+///
+/// 停止の記録を消し、続けて認証エラーになった回数もリセットします。
+/// サービスは通知のたびに停止の記録を読み込むため、次の通知から再開します。
+fn enable_mode(master_id: &str) -> Result<(), AdapterError> {
+    let configs = load_all_configs().unwrap_or_default();
+    if !configs.iter().any(|c| c.master_id == master_id) {
+        return Err(format_msg!(MessageKey::AccountNotFoundFmt, id = master_id).into());
+    }
+    if load_suspended(master_id).is_none() {
+        outln!(
+            "{}",
+            format_msg!(MessageKey::AccountNotSuspendedFmt, id = master_id)
+        );
+        return Ok(());
+    }
+    if let Err(e) = save_suspended(master_id, None) {
+        log_error(&format!("Failed to resume account {}: {}", master_id, e));
+        return Err(format_msg!(MessageKey::RegistrySaveFailFmt, error = e).into());
+    }
+    let _ = update_account_state(master_id, |s| s.consecutive_auth_failures = 0);
+    outln!(
+        "{}",
+        format_msg!(MessageKey::AccountEnabledFmt, id = master_id)
+    );
+    log_info(&format!("Account resumed: {}", master_id));
+    Ok(())
+}
+
+/// アカウント個別のプロキシURLの入力を求めるヘルパー関数。
+/// 空欄の場合は現在の値を維持し、`-` が入力された場合は設定を解除します（全体の設定に従う）。
+fn ask_proxy_url(current: &str) -> io::Result<String> {
+    let input = ask_with_default(get_msg(MessageKey::ProxyPrompt), current, false)?;
+    Ok(normalize_proxy_url(&input))
+}
+
+/// プロキシURLの入力値を、保存する値に変換します。`-` は設定の解除（空文字列）を表します。
+fn normalize_proxy_url(input: &str) -> String {
+    let input = input.trim();
+    if input == "-" {
+        String::new()
+    } else {
+        input.to_string()
+    }
+}
+
+/// 通知後にDNSの反映を確認するホスト名の入力を求めるヘルパー関数。
+/// "-" が入力された場合は、設定を解除したものとして空文字列を返します。
+/// ホスト名として使えない文字を含む値が入力された場合は、メッセージを表示して現在の値を維持します。
+fn ask_hostname(current: &str) -> io::Result<String> {
+    let input = ask_with_default(get_msg(MessageKey::HostnamePrompt), current, false)?;
+    match normalize_hostname(&input) {
+        Some(hostname) => Ok(hostname),
+        None => {
+            println!(
+                "{}",
+                format_msg!(MessageKey::HostnameInvalidFmt, host = input.trim())
+            );
+            Ok(current.to_string())
+        }
+    }
+}
+
+/// ホスト名の入力値を、保存する値に変換します。
+///
+/// "-" は設定の解除（空文字列）を表します。末尾の "." を取り除き、小文字にそろえます。
+/// ホスト名として使えない文字を含む場合は `None` を返します。
+fn normalize_hostname(input: &str) -> Option<String> {
+    let input = input.trim().trim_end_matches('.');
+    if input == "-" {
+        return Some(String::new());
+    }
+    input
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+        .then(|| input.to_ascii_lowercase())
+}
+
+/// 通知を送るネットワークインターフェイスの入力を求めるヘルパー関数。
+/// "-" が入力された場合は、設定を解除したものとして空文字列を返します。
+fn ask_interface(current: &str) -> io::Result<String> {
+    let input = ask_with_default(get_msg(MessageKey::InterfacePrompt), current, false)?;
+    Ok(normalize_interface(&input))
+}
+
+/// 送信元のインターフェイスの入力値を、保存する値に変換します。"-" は設定の解除（空文字列）を表します。
+///
+/// インターフェイスは接続していない間（VPNなど）は見つからないため、存在するかどうかは通知のときに確かめます。
+fn normalize_interface(input: &str) -> String {
+    match input.trim() {
+        "-" => String::new(),
+        input => input.to_string(),
+    }
+}
+
+/// 通知のスケジュールの入力を求めるヘルパー関数。
+/// "-" が入力された場合は、設定を解除したものとして空文字列を返します。
+/// 解釈できない式が入力された場合は、メッセージを表示して現在の値を維持します。
+fn ask_schedule(current: &str) -> io::Result<String> {
+    let input = ask_with_default(get_msg(MessageKey::SchedulePrompt), current, false)?;
+    match normalize_schedule(&input) {
+        Some(schedule) => Ok(schedule),
+        None => {
+            println!(
+                "{}",
+                format_msg!(MessageKey::ScheduleInvalidFmt, schedule = input.trim())
+            );
+            Ok(current.to_string())
+        }
+    }
+}
+
+/// 通知のスケジュールの入力値を、保存する値に変換します。
+///
+/// 空欄と "-" は設定の解除（空文字列）を表します。式として解釈できない場合は `None` を返します。
+fn normalize_schedule(input: &str) -> Option<String> {
+    let input = input.trim();
+    if input.is_empty() || input == "-" {
+        return Some(String::new());
+    }
+    Schedule::parse(input).map(|_| input.to_string())
+}
+
+/// 通知に成功したときにpingを送るURLの入力を求めるヘルパー関数。
+/// "-" が入力された場合は、設定を解除したものとして空文字列を返します。
+/// URLとして解釈できない値が入力された場合は、メッセージを表示して現在の値を維持します。
+fn ask_ping_url(current: &str) -> io::Result<String> {
+    let input = ask_with_default(get_msg(MessageKey::PingUrlPrompt), current, false)?;
+    match normalize_optional_url(&input) {
+        Some(url) => Ok(url),
+        None => {
+            println!(
+                "{}",
+                format_msg!(MessageKey::EndpointUrlInvalidFmt, url = input.trim())
+            );
+            Ok(current.to_string())
+        }
+    }
+}
+
+/// ping URLやWebhookのURLの入力値を、保存する値に変換します。
+///
+/// 空欄と "-" は設定の解除（空文字列）を表します。http/httpsのURLとして解釈できない場合は `None` を返します。
+fn normalize_optional_url(input: &str) -> Option<String> {
+    let input = input.trim();
+    if input.is_empty() || input == "-" {
+        return Some(String::new());
+    }
+    match reqwest::Url::parse(input) {
+        Ok(url) if url.scheme() == "http" || url.scheme() == "https" => Some(input.to_string()),
+        _ => None,
+    }
+}
+
+/// アカウントの通知先URLの入力を求めるヘルパー関数。
+///
+/// 現在の値（未設定なら既定のURL）をデフォルトとして表示します。
+/// URLとして解釈できない値が入力された場合は、メッセージを表示して現在の値を維持します。
+fn ask_endpoint_url(prompt: &str, current: &str, default: &str) -> io::Result<String> {
+    let input = ask_with_default(prompt, endpoint_or_default(current, default), false)?;
+    match normalize_endpoint_url(&input, default) {
+        Some(url) => Ok(url),
+        None => {
+            println!(
+                "{}",
+                format_msg!(MessageKey::EndpointUrlInvalidFmt, url = input.trim())
+            );
+            Ok(current.to_string())
+        }
+    }
+}
+
+/// 通知先URLの入力値を、保存する値に変換します。
+///
+/// 既定のURLと同じ値と "-" は空文字列（既定値を使う）とし、将来既定のURLが変わっても追従できるようにします。
+/// http/httpsのURLとして解釈できない場合は `None` を返します。
+fn normalize_endpoint_url(input: &str, default: &str) -> Option<String> {
+    let input = input.trim();
+    if input == default || input == "-" {
+        return Some(String::new());
+    }
+    match reqwest::Url::parse(input) {
+        Ok(url) if url.scheme() == "http" || url.scheme() == "https" => Some(input.to_string()),
+        _ => None,
+    }
+}
+
+/// 通知先のサービスの入力を求めるヘルパー関数。
+///
+/// 現在の値をデフォルトとして表示します。知らないサービスが入力された場合は、
+/// メッセージを表示して現在の値を維持します。
+fn ask_provider(current: Provider) -> io::Result<Provider> {
+    let input = ask_with_default(get_msg(MessageKey::ProviderPrompt), current.code(), false)?;
+    match Provider::from_code(&input) {
+        Some(provider) => Ok(provider),
+        None => {
+            println!("{}", get_msg(MessageKey::ProviderInvalid));
+            Ok(current)
+        }
+    }
+}
+
+/// MyDNS.JPへの通知の方法の入力を求めるヘルパー関数。
+///
+/// 汎用のサービスでは入力を求めず、HTTPでの通知を返します。知らない方法が入力された場合は、
+/// メッセージを表示して現在の値を維持します。ログインでの通知を選んだ場合は、パスワードが平文で送られることを表示します。
+fn ask_method(provider: Provider, current: NotifyMethod) -> io::Result<NotifyMethod> {
+    if provider != Provider::MyDns {
+        return Ok(NotifyMethod::Http);
+    }
+    let input = ask_with_default(get_msg(MessageKey::MethodPrompt), current.code(), false)?;
+    let method = NotifyMethod::from_code(&input).unwrap_or_else(|| {
+        println!("{}", get_msg(MessageKey::MethodInvalid));
+        current
+    });
+    if method != NotifyMethod::Http {
+        println!("{}", get_msg(MessageKey::MethodPlaintextWarning));
+    }
+    Ok(method)
+}
+
+/// サービスに合わせて、IPv4とIPv6の通知先URLの入力を求めます。
+///
+/// MyDNS.JPにログインで通知する場合は通知先URLを使わないため、入力を求めずに現在の値を返します。
+/// 汎用のサービスでIPv4の更新URLが入力されなかった場合は、メッセージを表示して `None` を返します。
+fn ask_provider_urls(
+    provider: Provider,
+    method: NotifyMethod,
+    current_ipv4: &str,
+    current_ipv6: &str,
+) -> io::Result<Option<(String, String)>> {
+    match provider {
+        Provider::MyDns if method != NotifyMethod::Http => {
+            Ok(Some((current_ipv4.to_string(), current_ipv6.to_string())))
+        }
+        Provider::MyDns => Ok(Some((
+            ask_endpoint_url(
+                get_msg(MessageKey::Ipv4UrlPrompt),
+                current_ipv4,
+                IPV4_NOTIFY_URL,
+            )?,
+            ask_endpoint_url(
+                get_msg(MessageKey::Ipv6UrlPrompt),
+                current_ipv6,
+                IPV6_NOTIFY_URL,
+            )?,
+        ))),
+        Provider::Generic => {
+            let ipv4_url =
+                ask_generic_url(get_msg(MessageKey::GenericIpv4UrlPrompt), current_ipv4)?;
+            if ipv4_url.is_empty() {
+                outln!("{}", get_msg(MessageKey::GenericUrlRequired));
+                return Ok(None);
+            }
+            let ipv6_url =
+                ask_generic_url(get_msg(MessageKey::GenericIpv6UrlPrompt), current_ipv6)?;
+            Ok(Some((ipv4_url, ipv6_url)))
+        }
+    }
+}
+
+/// 汎用のサービスの更新URLの入力を求めるヘルパー関数。
+///
+/// URLとして解釈できない値が入力された場合は、メッセージを表示して現在の値を維持します。
+fn ask_generic_url(prompt: &str, current: &str) -> io::Result<String> {
+    let input = ask_with_default(prompt, current, false)?;
+    match normalize_generic_url(&input) {
+        Some(url) => Ok(url),
+        None => {
+            println!(
+                "{}",
+                format_msg!(MessageKey::EndpointUrlInvalidFmt, url = input.trim())
+            );
+            Ok(current.to_string())
+        }
+    }
+}
+
+/// 汎用のサービスの更新URLの入力値を、保存する値に変換します。
+///
+/// "-" は設定の解除（空文字列）を表します。`{ip}` と `{host}` を例の値に置き換えたうえで、
+/// http/httpsのURLとして解釈できない場合は `None` を返します。
+fn normalize_generic_url(input: &str) -> Option<String> {
+    let input = input.trim();
+    if input.is_empty() || input == "-" {
+        return Some(String::new());
+    }
+    let sample = format_template(input, &[("ip", &"192.0.2.1"), ("host", &"example.com")]);
+    match reqwest::Url::parse(&sample) {
+        Ok(url) if url.scheme() == "http" || url.scheme() == "https" => Some(input.to_string()),
+        _ => None,
+    }
+}
+
+/// アカウントのパスワードを取得するヘルパー関数。
+///
+/// `from_stdin` が `true` の場合は標準入力の1行目を、環境変数 `MYDNS_PASSWORD` が設定されている場合は
+/// その値を使い、どちらもない場合は対話的に入力を求めます。
+/// 自動化のためにパスワードをコマンドライン（プロセス一覧から見える）に書かずに済むようにします。
+/// 標準入力から空行を読み込んだ場合は、現在の値を維持します。
+fn ask_password(current: &str, from_stdin: bool) -> io::Result<String> {
+    if from_stdin {
+        let mut buffer = String::new();
+        io::stdin().read_line(&mut buffer)?;
+        let password = buffer.trim_end_matches(['\r', '\n']);
+        return Ok(if password.is_empty() {
+            current.to_string()
+        } else {
+            password.to_string()
+        });
+    }
+    if let Ok(password) = env::var(PASSWORD_ENV_VAR)
+        && !password.is_empty()
+    {
+        outln!(
+            "{}",
+            format_msg!(MessageKey::PasswordFromEnvFmt, var = PASSWORD_ENV_VAR)
+        );
+        return Ok(password);
+    }
+    ask_with_default(get_msg(MessageKey::PasswordPrompt), current, true)
+}
+
+/// `service install --account` で指定されたアカウントの、ログオンの情報を確定させます。
+///
+/// 組み込みのアカウント（LocalService など）と、グループの管理されたサービスアカウント（末尾が `$`）には
+/// パスワードが不要なため、指定された場合はエラーとします。
+/// それ以外のアカウントでパスワードが省略された場合は、対話的に入力を求めます。
+fn service_logon(name: &str, password: Option<String>) -> Result<ServiceLogon, AdapterError> {
+    let builtin = builtin_service_account(name);
+    let account = builtin.map_or_else(|| name.to_string(), str::to_string);
+    if builtin.is_some() || name.ends_with('$') {
+        if password.is_some() {
+            return Err(
+                format_msg!(MessageKey::ServicePasswordNotNeededFmt, account = name).into(),
+            );
+        }
+        return Ok(ServiceLogon {
+            account,
+            password: None,
+        });
+    }
+    let password = match password {
+        Some(password) => password,
+        None => ask_with_default(
+            &format_msg!(MessageKey::ServicePasswordPromptFmt, account = name),
+            "",
+            true,
+        )?,
+    };
+    Ok(ServiceLogon {
+        account,
+        password: Some(password),
+    })
+}
+
+/// デフォルト値付きでユーザーからの入力を求めるヘルパー関数。
+/// ユーザーが何も入力せずにEnterキーを押した場合、`default`値が返されます。
+/// `is_password`がtrueの場合、コンソールに入力がエコーバックされません。
+fn ask_with_default(prompt: &str, default: &str, is_password: bool) -> io::Result<String> {
+    // プロンプトを表示します。
+    if default.is_empty() {
+        print!(
+            "{}",
+            format_msg!(MessageKey::InputPromptNewFmt, item = prompt)
+        );
+    } else if is_password {
+        let masked_pw = mask_password(default); // パスワードはマスクして表示
+        print!(
+            "{}",
+            format_msg!(
+                MessageKey::InputPromptPwFmt,
+                item = prompt,
+                current = masked_pw
+            )
+        );
+    } else {
+        print!(
+            "{}",
+            format_msg!(MessageKey::InputPromptFmt, item = prompt, current = default)
+        );
+    }
+    io::stdout().flush()?;
+
+    // ユーザーからの入力を読み取ります。
+    let input = if is_password {
+        read_password()? // rpasswordクレートを使い、安全にパスワードを読み取る
+    } else {
+        let mut buffer = String::new();
+        io::stdin().read_line(&mut buffer)?;
+        buffer
+    };
+
+    let trimmed = input.trim();
+    // 入力が空であればデフォルト値を、そうでなければ入力された値を返します。
+    if trimmed.is_empty() {
+        Ok(default.to_string())
+    } else {
+        Ok(trimmed.to_string())
+    }
+}
+
+/// デフォルト値付きで、ユーザーから0以上の整数の入力を求めるヘルパー関数。
+/// 数値として解釈できない入力の場合は、再入力を求めます。
+fn ask_number_with_default(prompt: &str, default: u32) -> io::Result<u32> {
+    loop {
+        let input = ask_with_default(prompt, &default.to_string(), false)?;
+        // 全角で入力された数字も受け付けられるよう、半角に正規化してから解釈します。
+        match normalize_width(&input).trim().parse::<u32>() {
+            Ok(value) => return Ok(value),
+            Err(_) => println!("{}", get_msg(MessageKey::NumberInvalid)),
+        }
+    }
+}
+
+/// パスワード文字列を、コンソール表示用にマスクします。
+/// 機密情報が画面に平文で表示されるのを防ぎます。
+fn mask_password(pw: &str) -> String {
+    let len = pw.chars().count();
+    if len == 0 {
+        return get_msg(MessageKey::NotSet).to_string();
+    }
+    // 短すぎるパスワードは、文字数すら推測されないように全てマスクします。
+    if len <= 4 {
+        return "*".repeat(len);
+    }
+
+    let mut chars: Vec<char> = pw.chars().collect();
+    // ユーザーがパスワードが設定されていることを認識できるよう、最初・真ん中・最後の文字だけ表示します。
+    for (i, c) in chars.iter_mut().enumerate() {
+        if i != 0 && i != len - 1 && i != len / 2 {
+            *c = '*';
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Yes/No形式の質問をユーザーに問いかけ、現在の設定値も表示します。
+fn ask_yes_no(prompt: &str, default: bool) -> io::Result<bool> {
+    let current_value = if default {
+        get_msg(MessageKey::Yes)
+    } else {
+        get_msg(MessageKey::No)
+    };
+    let hint = if default {
+        // デフォルト値が大文字で表示されるヒント (Y/n)
+        get_msg(MessageKey::YesNoHintTrue)
+    } else {
+        // (y/N)
+        get_msg(MessageKey::YesNoHintFalse)
+    };
+    loop {
+        print!(
+            "{}",
+            format_msg!(
+                MessageKey::YesNoPromptFmt,
+                question = prompt,
+                current = current_value,
+                hint = hint
+            )
+        );
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        // 全角で入力された場合も受け付けられるよう、半角に正規化してから判定します。
+        let trimmed = normalize_width(&input).trim().to_lowercase();
+
+        if trimmed.is_empty() {
+            // ユーザーがEnterキーのみを押した場合、デフォルト値を返します。
+            return Ok(default);
+        }
+
+        match parse_yes_no(&trimmed) {
+            Some(answer) => return Ok(answer),
+            None => println!("{}", get_msg(MessageKey::YesNoInvalid)),
+        }
+    }
+}
+
+/// 「現在の値」を表示しない、シンプルなYes/No形式の確認をユーザーに求めます。
+fn ask_yes_no_simple(prompt: &str, default: bool) -> io::Result<bool> {
+    let hint = if default {
+        get_msg(MessageKey::YesNoHintTrue)
+    } else {
+        get_msg(MessageKey::YesNoHintFalse)
+    };
+    loop {
+        print!(
+            "{}",
+            format_msg!(MessageKey::ConfirmPromptFmt, question = prompt, hint = hint)
+        );
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        // 全角で入力された場合も受け付けられるよう、半角に正規化してから判定します。
+        let trimmed = normalize_width(&input).trim().to_lowercase();
+
+        if trimmed.is_empty() {
+            return Ok(default);
+        }
+
+        match parse_yes_no(&trimmed) {
+            Some(answer) => return Ok(answer),
+            None => println!("{}", get_msg(MessageKey::YesNoInvalid)),
+        }
+    }
+}
+
+/// Yes/No形式の質問への回答を解釈します。
+///
+/// 'y'/'n' に加えて、`i18n` で言語ごとに定義された肯定・否定の語（「はい」「いいえ」など）を受け付けます。
+/// 解釈できない場合は `None` を返します。
+fn parse_yes_no(answer: &str) -> Option<bool> {
+    let matches = |key: MessageKey| get_msg(key).split(',').any(|word| word == answer);
+    if matches(MessageKey::YesNoAffirmativeWords) {
+        Some(true)
+    } else if matches(MessageKey::YesNoNegativeWords) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// 全角の英数字・記号・空白を、対応する半角文字に変換します。
+///
+/// IMEが有効なまま入力された値を、半角で入力された場合と同じように扱うために使用します。
+fn normalize_width(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| match c {
+            // 全角の '！'(U+FF01) から '～'(U+FF5E) は、半角の '!'(U+0021) から '~'(U+007E) に対応する。
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            // 全角スペース
+            '\u{3000}' => ' ',
+            _ => c,
+        })
+        .collect()
+}
+
+/// 最後に通知したアドレスを表示用の文字列に変換します。記録がない場合は「未設定」を返します。
+fn format_last_ip<T: ToString>(address: Option<T>) -> String {
+    address.map_or_else(
+        || get_msg(MessageKey::NotSet).to_string(),
+        |a| a.to_string(),
+    )
+}
+
+/// アカウントのキーに記録された、最後に通知に失敗した時刻と内容があれば表示します。
+///
+/// 失敗の分類は、表示言語の分類名に置き換えます。未知の分類は記録されたまま表示します。
+fn print_last_error(master_id: &str) {
+    let Some(last) = load_last_error(master_id) else {
+        return;
+    };
+    let kind =
+        FailureKind::from_code(&last.kind).map_or(last.kind.as_str(), |k| get_msg(k.label_key()));
+    println!(
+        "{}",
+        paint(
+            &format_msg!(
+                MessageKey::ViewLastErrorFmt,
+                time = last.time.format("%Y-%m-%d %H:%M:%S"),
+                kind = kind,
+                detail = last.detail
+            ),
+            Color::Red
+        )
+    );
+}
+
+/// 認証エラーが続いてアカウントの通知を自動で停止している場合に、停止した時刻と再開の方法を表示します。
+fn print_suspended(master_id: &str) {
+    if let Some(since) = load_suspended(master_id) {
+        println!(
+            "{}",
+            paint(
+                &format_msg!(
+                    MessageKey::ViewSuspendedFmt,
+                    time = since.format("%Y-%m-%d %H:%M:%S"),
+                    id = master_id
+                ),
+                Color::Red
+            )
+        );
+    }
+}
+
+/// 設定されているすべてのアカウント情報を、整形されたリストとして表示します。
+fn view_mode() -> io::Result<()> {
+    println!("{}", get_msg(MessageKey::ViewTitle));
+    if config_scope() == ConfigScope::User {
+        println!("{}", get_msg(MessageKey::ViewUserScope));
+    }
+    if let Some(path) = config_file() {
+        println!(
+            "{}",
+            format_msg!(MessageKey::ViewConfigFileFmt, path = path.display())
+        );
+    }
+    let configs = load_all_configs().unwrap_or_else(|_| Vec::new());
+    let state = load_state().unwrap_or_default();
+
+    if configs.is_empty() {
+        println!("{}", get_msg(MessageKey::ViewNoAccounts));
+        return Ok(());
+    }
+
+    for config in &configs {
+        // 各値を指定の長さにフォーマットする
+        let master_id_val = format!("{:<11.11}", &config.master_id);
+        let password_val = format!("{:<11.11}", mask_password(&config.password));
+        let ipv4_val = paint_flag(config.ipv4_notify);
+        let ipv6_val = paint_flag(config.ipv6_notify);
+
+        // 国際化されたフォーマット文字列を使って、一行の情報を組み立てて表示します。
+        println!(
+            "{}",
+            format_msg!(
+                MessageKey::ViewListFmt,
+                id = master_id_val,
+                pw = password_val,
+                v4 = ipv4_val,
+                v6 = ipv6_val
+            )
+        );
+
+        // 汎用のサービスのアカウントであれば、そのことを表示します。
+        if config.provider == Provider::Generic {
+            println!("{}", get_msg(MessageKey::ViewProviderGeneric));
+        }
+
+        // ログインで通知するアカウントであれば、その方法を表示します。
+        if config.method != NotifyMethod::Http {
+            println!(
+                "{}",
+                format_msg!(MessageKey::ViewMethodFmt, method = config.method.code())
+            );
+        }
+
+        // 既定以外の通知先URLが設定されていれば、続けて表示します。
+        for (proto, url) in [("IPv4", &config.ipv4_url), ("IPv6", &config.ipv6_url)] {
+            if !url.is_empty() {
+                println!(
+                    "{}",
+                    format_msg!(MessageKey::ViewEndpointFmt, proto = proto, url = url)
+                );
+            }
+        }
+
+        // DNSの反映を確認するホスト名が設定されていれば、続けて表示します。
+        if !config.hostname.is_empty() {
+            println!(
+                "{}",
+                format_msg!(MessageKey::ViewHostnameFmt, host = config.hostname)
+            );
+        }
+
+        // 通知に成功したときにpingを送るURLが設定されていれば、続けて表示します。
+        if !config.ping_url.is_empty() {
+            println!(
+                "{}",
+                format_msg!(MessageKey::ViewAccountPingUrlFmt, url = config.ping_url)
+            );
+        }
+
+        // 通知を送るインターフェイスが設定されていれば、続けて表示します。
+        if !config.bind_interface.is_empty() {
+            println!(
+                "{}",
+                format_msg!(
+                    MessageKey::ViewInterfaceFmt,
+                    interface = config.bind_interface
+                )
+            );
+        }
+
+        // 通知のスケジュールが設定されていれば、続けて表示します。
+        if !config.schedule.is_empty() {
+            println!(
+                "{}",
+                format_msg!(MessageKey::ViewScheduleFmt, schedule = config.schedule)
+            );
+        }
+
+        // アカウントのキーに記録された、最後に通知に成功した時刻とアドレスがあれば、続けて表示します。
+        let last = load_last_success(&config.master_id);
+        if let Some(time) = last.time {
+            println!(
+                "{}",
+                format_msg!(
+                    MessageKey::ViewLastSuccessFmt,
+                    time = time.format("%Y-%m-%d %H:%M:%S"),
+                    v4 = format_last_ip(last.ipv4),
+                    v6 = format_last_ip(last.ipv6)
+                )
+            );
+        }
+        print_last_error(&config.master_id);
+        print_suspended(&config.master_id);
+
+        // 稼働中のサービスが記録した実行時状態があれば、続けて表示します。
+        if let Some(account) = state.accounts.get(&config.master_id) {
+            let activity = if account.in_flight {
+                get_msg(MessageKey::ViewRuntimeInFlight)
+            } else {
+                get_msg(MessageKey::ViewRuntimeIdle)
+            };
+            let last_attempt = account
+                .last_attempt
+                .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_else(|| get_msg(MessageKey::NotSet).to_string());
+            let last_result = paint_result(account.last_result.as_deref());
+            println!(
+                "{}",
+                format_msg!(
+                    MessageKey::ViewRuntimeFmt,
+                    state = activity,
+                    time = last_attempt,
+                    result = last_result
+                )
+            );
+        }
+    }
+
+    let settings = load_settings().unwrap_or_default();
+    println!(
+        "{}",
+        format_msg!(
+            MessageKey::ViewIntervalFmt,
+            minutes = settings.notify_interval_minutes
+        )
+    );
+    println!(
+        "{}",
+        format_msg!(
+            MessageKey::ViewRetryFmt,
+            count = settings.retry_max_attempts
+        )
+    );
+    println!(
+        "{}",
+        format_msg!(
+            MessageKey::ViewConcurrencyFmt,
+            count = settings.notify_concurrency
+        )
+    );
+    println!(
+        "{}",
+        format_msg!(
+            MessageKey::ViewRequestTimeoutFmt,
+            seconds = settings.request_timeout_seconds
+        )
+    );
+    let proxy = if settings.proxy_url.is_empty() {
+        get_msg(MessageKey::ProxyAuto)
+    } else {
+        &settings.proxy_url
+    };
+    println!("{}", format_msg!(MessageKey::ViewProxyFmt, proxy = proxy));
+    println!(
+        "{}",
+        format_msg!(
+            MessageKey::ViewUserAgentFmt,
+            ua = user_agent(&settings.user_agent_id)
+        )
+    );
+    if settings.metrics_port != 0 {
+        println!(
+            "{}",
+            format_msg!(MessageKey::ViewMetricsFmt, port = settings.metrics_port)
+        );
+    }
+    if settings.admin_api_port != 0 {
+        println!(
+            "{}",
+            format_msg!(MessageKey::ViewAdminApiFmt, port = settings.admin_api_port)
+        );
+    }
+    if !settings.ping_url.is_empty() {
+        println!(
+            "{}",
+            format_msg!(MessageKey::ViewPingUrlFmt, url = settings.ping_url)
+        );
+    }
+    if !settings.webhook_url.is_empty() {
+        println!(
+            "{}",
+            format_msg!(MessageKey::ViewWebhookUrlFmt, url = settings.webhook_url)
+        );
+    }
+    if is_mail_configured(&settings) {
+        println!(
+            "{}",
+            format_msg!(
+                MessageKey::ViewMailFmt,
+                to = settings.mail_to,
+                server = settings.smtp_server,
+                port = settings.smtp_port,
+                minutes = settings.mail_after_minutes
+            )
+        );
+    }
+    for (urls, key) in [
+        (&settings.check_ipv4_urls, MessageKey::ViewCheckIpv4UrlsFmt),
+        (&settings.check_ipv6_urls, MessageKey::ViewCheckIpv6UrlsFmt),
+    ] {
+        if !urls.is_empty() {
+            println!("{}", format_msg!(key, urls = urls.join(", ")));
+        }
+    }
+    if settings.check_ip_poll_minutes != 0 {
+        println!(
+            "{}",
+            format_msg!(
+                MessageKey::ViewCheckIpPollFmt,
+                minutes = settings.check_ip_poll_minutes
+            )
+        );
+    }
+    if settings.skip_private_ipv4 {
+        println!("{}", get_msg(MessageKey::ViewSkipPrivateIpv4));
+    }
+    if settings.history_database {
+        println!("{}", get_msg(MessageKey::ViewHistoryDatabase));
+    }
+    if settings.dns_check_minutes != 0 {
+        println!(
+            "{}",
+            format_msg!(
+                MessageKey::ViewDnsCheckFmt,
+                minutes = settings.dns_check_minutes
+            )
+        );
+    }
+    if settings.metered_policy != MeteredPolicy::Ignore {
+        println!(
+            "{}",
+            format_msg!(
+                MessageKey::ViewMeteredFmt,
+                policy = settings.metered_policy.code()
+            )
+        );
+    }
+    if !settings.allowed_networks.is_empty() {
+        println!(
+            "{}",
+            format_msg!(
+                MessageKey::ViewAllowedNetworksFmt,
+                networks = settings.allowed_networks.join(", ")
+            )
+        );
+    }
+    if let Some(quiet) = settings.quiet_hours {
+        println!(
+            "{}",
+            format_msg!(MessageKey::ViewQuietHoursFmt, hours = quiet.code())
+        );
+    }
+    if settings.jitter_seconds != 0 {
+        println!(
+            "{}",
+            format_msg!(MessageKey::ViewJitterFmt, seconds = settings.jitter_seconds)
+        );
+    }
+    if settings.toast_failure_threshold != 0 {
+        println!(
+            "{}",
+            format_msg!(
+                MessageKey::ViewToastFmt,
+                count = settings.toast_failure_threshold
+            )
+        );
+    }
+    if let Ok(log_path) = get_log_path() {
+        println!(
+            "{}",
+            format_msg!(MessageKey::ViewLogPathFmt, path = log_path.display())
+        );
+    }
+    println!(
+        "{}",
+        format_msg!(
+            MessageKey::ViewLogArchiveFmt,
+            count = settings.log_archive_count,
+            compress = if settings.log_archive_compress {
+                get_msg(MessageKey::Yes)
+            } else {
+                get_msg(MessageKey::No)
+            }
+        )
+    );
+    if state.paused {
+        println!("{}", paint(get_msg(MessageKey::ViewPaused), Color::Yellow));
+    }
+
+    Ok(())
+}
+
+/// 通知の有効・無効を、桁をそろえて色を付けた「はい」「いいえ」で表します。
+fn paint_flag(enabled: bool) -> String {
+    if enabled {
+        paint(&format!("{:<3.3}", get_msg(MessageKey::Yes)), Color::Green)
+    } else {
+        paint(&format!("{:<3.3}", get_msg(MessageKey::No)), Color::Gray)
+    }
+}
+
+/// 直近の通知の結果を、成功なら緑、失敗なら赤で表します。未記録の場合は「未設定」を表します。
+fn paint_result(last_result: Option<&str>) -> String {
+    match last_result {
+        Some("OK") => paint("OK", Color::Green),
+        Some(result) => paint(result, Color::Red),
+        None => paint(get_msg(MessageKey::NotSet), Color::Gray),
+    }
+}
+
+/// アカウントの設定と実行時状態を、スクリプトから読み込みやすいJSONまたはCSV形式で表示します。
+///
+/// 表示内容は翻訳されず、時刻はRFC 3339形式、未記録の値はJSONでは `null`、CSVでは空欄になります。
+/// パスワードは出力しません。
+fn view_machine_mode(format: ViewFormat) -> io::Result<()> {
+    let configs = load_all_configs().unwrap_or_else(|_| Vec::new());
+    let state = load_state().unwrap_or_default();
+
+    let mut rows = Vec::new();
+    for config in &configs {
+        let account = state.accounts.get(&config.master_id);
+        let last_success = account.and_then(|a| a.last_success).map(|t| t.to_rfc3339());
+        let last_result = account.and_then(|a| a.last_result.clone());
+        let last_ip: Vec<String> = account
+            .map(|a| a.last_ip.iter().map(|ip| ip.to_string()).collect())
+            .unwrap_or_default();
+        rows.push((config, last_success, last_result, last_ip));
+    }
+
+    match format {
+        ViewFormat::Json => {
+            let optional =
+                |value: &Option<String>| value.as_deref().map_or("null".to_string(), quote);
+            println!("{{");
+            println!("  \"paused\": {},", state.paused);
+            print!("  \"accounts\": [");
+            for (i, (config, last_success, last_result, last_ip)) in rows.iter().enumerate() {
+                println!("{}", if i == 0 { "" } else { "," });
+                println!("    {{");
+                println!("      \"master_id\": {},", quote(&config.master_id));
+                println!("      \"provider\": {},", quote(config.provider.code()));
+                println!("      \"method\": {},", quote(config.method.code()));
+                println!("      \"ipv4_notify\": {},", config.ipv4_notify);
+                println!("      \"ipv6_notify\": {},", config.ipv6_notify);
+                println!(
+                    "      \"enabled\": {},",
+                    config.ipv4_notify || config.ipv6_notify
+                );
+                println!(
+                    "      \"suspended\": {},",
+                    load_suspended(&config.master_id).is_some()
+                );
+                println!("      \"last_success\": {},", optional(last_success));
+                println!(
+                    "      \"last_ip\": [{}],",
+                    last_ip
+                        .iter()
+                        .map(|ip| quote(ip))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                println!("      \"last_result\": {}", optional(last_result));
+                print!("    }}");
+            }
+            println!("{}  ]", if rows.is_empty() { "" } else { "\n" });
+            println!("}}");
+        }
+        _ => {
+            println!("master_id,ipv4_notify,ipv6_notify,enabled,last_success,last_ip,last_result");
+            for (config, last_success, last_result, last_ip) in &rows {
+                println!(
+                    "{},{},{},{},{},{},{}",
+                    csv_field(&config.master_id),
+                    config.ipv4_notify,
+                    config.ipv6_notify,
+                    config.ipv4_notify || config.ipv6_notify,
+                    last_success.as_deref().unwrap_or(""),
+                    // 1つの列に収めるため、複数のアドレスは空白で区切る。
+                    csv_field(&last_ip.join(" ")),
+                    csv_field(last_result.as_deref().unwrap_or(""))
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// CSVの1つの値として出力できるよう、必要な場合は二重引用符で囲みます。
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// すべてのアカウント設定を検証・更新し、アカウントごとに変更内容を報告します。
+///
+/// 形式の古い値や欠けている値は自動的に書き換え、自動では直せない問題（MasterIDの形式、
+/// 空のパスワードなど）は警告として表示します。
+fn upgrade_config_mode() -> io::Result<()> {
+    outln!("{}", get_msg(MessageKey::UpgradeTitle));
+
+    let configs = load_all_configs().unwrap_or_else(|_| Vec::new());
+    if configs.is_empty() {
+        outln!("{}", get_msg(MessageKey::ViewNoAccounts));
+        return Ok(());
+    }
+
+    for config in &configs {
+        outln!(
+            "{}",
+            format_msg!(MessageKey::UpgradeAccountFmt, id = config.master_id)
+        );
+
+        // 自動では直せない問題を警告する。
+        if config.provider == Provider::MyDns && !config.master_id.starts_with("mydns") {
+            outln!("    {}", get_msg(MessageKey::InvalidMasterIdPrefix));
+        }
+        if config.provider == Provider::MyDns && config.password.is_empty() {
+            outln!("    {}", get_msg(MessageKey::UpgradeEmptyPassword));
+        }
+
+        match upgrade_account(&config.master_id) {
+            Ok(changes) if changes.is_empty() => {
+                outln!("    {}", get_msg(MessageKey::UpgradeNoChanges));
+            }
+            Ok(changes) => {
+                for change in &changes {
+                    let line = match change {
+                        UpgradeChange::AddedDefault { name, value } => {
+                            format_msg!(
+                                MessageKey::UpgradeAddedDefaultFmt,
+                                name = name,
+                                value = value
+                            )
+                        }
+                        UpgradeChange::MigratedType { name, value } => {
+                            format_msg!(
+                                MessageKey::UpgradeMigratedTypeFmt,
+                                name = name,
+                                value = value
+                            )
+                        }
+                    };
+                    outln!("    {}", line);
+                }
+                log_info(&format!(
+                    "Account upgraded: {} ({} changes)",
+                    config.master_id,
+                    changes.len()
+                ));
+            }
+            Err(e) => {
+                outln!(
+                    "    {}",
+                    format_msg!(MessageKey::RegistrySaveFailFmt, error = e).trim()
+                );
+                log_error(&format!(
+                    "Failed to upgrade account {}: {}",
+                    config.master_id, e
+                ));
             }
         }
-    };
+    }
+    Ok(())
+}
 
-    println!(
-        "{}",
-        get_msg("edit_target_fmt").replace("{}", &config_to_edit.master_id)
-    );
+/// ファイルからアカウント設定を読み込み、差分を表示して確認を求めてから、レジストリに保存します。
+///
+/// ファイル全体の検証に成功した場合にのみ保存を始めます。
+/// ファイルに含まれないアカウントは変更しません。
+fn import_mode(path: &std::path::Path) -> Result<(), AdapterError> {
+    outln!("{}", get_msg(MessageKey::ImportTitle));
 
-    // 各設定項目を、現在の値をデフォルトとしてユーザーに再入力させます。
-    let password = ask_with_default(get_msg("password_prompt"), &config_to_edit.password, true)?;
-    let ipv4_notify = ask_yes_no(get_msg("ipv4_notify_prompt"), config_to_edit.ipv4_notify)?;
-    let ipv6_notify = ask_yes_no(get_msg("ipv6_notify_prompt"), config_to_edit.ipv6_notify)?;
+    let existing = load_all_configs().unwrap_or_default();
+    let plans = plan_import(path, &existing)?;
 
-    // 更新された設定を保存します。
-    // MasterIDはレジストリのキー名であるため、変更はできません。
-    match save_to_registry(
-        &config_to_edit.master_id,
-        &password,
-        ipv4_notify,
-        ipv6_notify,
-    ) {
-        Ok(_) => {
-            let msg = get_msg("registry_save_success");
-            println!("{}", msg);
-            log_info(&format!("Account edited: {}", config_to_edit.master_id));
+    // 差分を表示する。
+    for plan in &plans {
+        let id = &plan.config.master_id;
+        match &plan.change {
+            ImportChange::New => {
+                println!("{}", format_msg!(MessageKey::ImportDiffNewFmt, id = id));
+            }
+            ImportChange::Changed(fields) => {
+                println!("{}", format_msg!(MessageKey::ImportDiffChangedFmt, id = id));
+                for (name, old, new) in fields {
+                    println!("    {}: {} -> {}", name, old, new);
+                }
+            }
+            ImportChange::Unchanged => {
+                println!(
+                    "{}",
+                    format_msg!(MessageKey::ImportDiffUnchangedFmt, id = id)
+                );
+            }
         }
-        Err(e) => {
-            let msg = get_msg("registry_save_fail_fmt").replace("{}", &e.to_string());
-            println!("{}", msg);
+    }
+
+    let targets: Vec<_> = plans
+        .iter()
+        .filter(|p| !matches!(p.change, ImportChange::Unchanged))
+        .collect();
+    if targets.is_empty() {
+        outln!("{}", get_msg(MessageKey::ImportNothingToDo));
+        return Ok(());
+    }
+    if !ask_yes_no_simple(
+        &format_msg!(MessageKey::ImportConfirmFmt, count = targets.len()),
+        false,
+    )? {
+        outln!("{}", get_msg(MessageKey::OperationCancelled));
+        return Ok(());
+    }
+
+    // 検証済みのすべてのアカウントを、まとめて保存する。
+    for plan in &targets {
+        if let Err(e) = save_config(&plan.config) {
             log_error(&format!(
-                "Failed to edit account {}: {}",
-                config_to_edit.master_id, e
+                "Failed to import account {}: {}",
+                plan.config.master_id, e
             ));
+            return Err(format_msg!(MessageKey::RegistrySaveFailFmt, error = e).into());
         }
     }
-
+    outln!(
+        "{}",
+        format_msg!(MessageKey::ImportSuccessFmt, count = targets.len())
+    );
+    log_info(&format!(
+        "Imported {} account(s) from {}.",
+        targets.len(),
+        path.display()
+    ));
     Ok(())
 }
 
-/// 指定されたIDのアカウント設定を削除する処理を行います。
-fn remove_mode(id: &str) -> io::Result<()> {
-    println!("{}", get_msg("remove_title"));
+/// ログファイルの末尾から、条件に一致する行を指定行数だけ表示します。
+/// `follow` が `true` の場合は、その後も新しく記録される行を表示し続けます。
+fn log_mode(count: usize, filter: &LogFilter, follow: bool) -> io::Result<()> {
+    let lines = read_log_tail(count, filter)?;
+    if lines.is_empty() && !follow {
+        println!("{}", get_msg(MessageKey::LogEmpty));
+        return Ok(());
+    }
+    for line in lines {
+        println!("{}", line);
+    }
+    if follow {
+        println!("{}", get_msg(MessageKey::LogFollowHint));
+        follow_log(filter, |line| println!("{}", line))?;
+    }
+    Ok(())
+}
 
-    // 破壊的な操作であるため、実行前に必ず確認を求めます。
-    if ask_yes_no_simple(&get_msg("confirm_remove_fmt").replace("{}", id), false)? {
-        match delete_config(id) {
-            Ok(_) => {
-                let msg = get_msg("remove_success");
-                println!("{}", msg);
-                log_info(&format!("Account removed: {}", id));
-            }
-            Err(e) => {
-                let msg = get_msg("remove_fail_fmt").replace("{}", &e.to_string());
-                println!("{}", msg);
-                log_error(&format!("Failed to remove account {}: {}", id, e));
-            }
-        }
+/// 稼働中のサービスによる定期通知の一時停止・再開を、共有状態を介して要求します。
+fn pause_mode(pause: bool) -> io::Result<()> {
+    update_state(|s| s.paused = pause)?;
+    if pause {
+        outln!("{}", get_msg(MessageKey::PauseSuccess));
+        log_info("Periodic notification paused.");
     } else {
-        println!("{}", get_msg("operation_cancelled"));
+        outln!("{}", get_msg(MessageKey::ResumeSuccess));
+        log_info("Periodic notification resumed.");
     }
     Ok(())
 }
 
-/// デフォルト値付きでユーザーからの入力を求めるヘルパー関数。
-/// ユーザーが何も入力せずにEnterキーを押した場合、`default`値が返されます。
-/// `is_password`がtrueの場合、コンソールに入力がエコーバックされません。
-fn ask_with_default(prompt: &str, default: &str, is_password: bool) -> io::Result<String> {
-    // プロンプトのフォーマット文字列を国際化メッセージから取得します。
-    let fmt = if is_password {
-        get_msg("input_prompt_pw_fmt")
+/// サービスによる定期通知の間隔（分）を検証し、レジストリに保存します。
+///
+/// 稼働中のサービスは定期通知のたびに設定を読み直すため、再起動は必要ありません。
+fn interval_mode(minutes: u32) -> Result<(), AdapterError> {
+    if !NOTIFY_INTERVAL_RANGE_MINUTES.contains(&minutes) {
+        return Err(format_msg!(
+            MessageKey::IntervalOutOfRangeFmt,
+            min = NOTIFY_INTERVAL_RANGE_MINUTES.start(),
+            max = NOTIFY_INTERVAL_RANGE_MINUTES.end()
+        )
+        .into());
+    }
+    let mut settings = load_settings()?;
+    settings.notify_interval_minutes = minutes;
+    save_settings(&settings)?;
+    outln!(
+        "{}",
+        format_msg!(MessageKey::IntervalSetFmt, minutes = minutes)
+    );
+    log_info(&format!(
+        "Notification interval set to {} minutes.",
+        minutes
+    ));
+    Ok(())
+}
+
+/// サービスがメトリクスを公開するポートをレジストリに保存します。0は公開しないことを表します。
+///
+/// 待ち受けはサービスの開始時に始めるため、稼働中のサービスには再起動後に反映されます。
+fn metrics_port_mode(port: u16) -> Result<(), AdapterError> {
+    let mut settings = load_settings()?;
+    settings.metrics_port = port;
+    save_settings(&settings)?;
+    if port == 0 {
+        outln!("{}", get_msg(MessageKey::MetricsDisabled));
+        log_info("Metrics endpoint disabled.");
     } else {
-        get_msg("input_prompt_fmt")
-    };
-    let fmt_new = get_msg("input_prompt_new_fmt");
+        outln!("{}", format_msg!(MessageKey::MetricsSetFmt, port = port));
+        log_info(&format!("Metrics endpoint set to port {}.", port));
+    }
+    Ok(())
+}
 
-    // プロンプトを表示します。
-    if is_password {
-        if default.is_empty() {
-            print!("{}", fmt_new.replace("{}", prompt));
-        } else {
-            let masked_pw = mask_password(default); // パスワードはマスクして表示
-            print!(
-                "{}",
-                fmt.replacen("{}", prompt, 1).replacen("{}", &masked_pw, 1)
-            );
-        }
-    } else if default.is_empty() {
-        print!("{}", fmt_new.replace("{}", prompt));
+/// サービスが管理APIを公開するポートをレジストリに保存します。0は公開しないことを表します。
+///
+/// トークンがまだなければ作成し、APIの呼び出しに使えるよう表示します。
+/// 作成済みのトークンは、ポートを変えても引き継ぎます。
+/// 待ち受けはサービスの開始時に始めるため、稼働中のサービスには再起動後に反映されます。
+fn admin_api_mode(port: u16) -> Result<(), AdapterError> {
+    let mut settings = load_settings()?;
+    settings.admin_api_port = port;
+    if port != 0 && settings.admin_api_token.is_empty() {
+        settings.admin_api_token = generate_token()?;
+    }
+    save_settings(&settings)?;
+    if port == 0 {
+        outln!("{}", get_msg(MessageKey::AdminApiDisabled));
+        log_info("Admin API disabled.");
     } else {
-        print!(
+        // トークンは求められた情報であるため、`--quiet` でも表示する。
+        println!(
             "{}",
-            fmt.replacen("{}", prompt, 1).replacen("{}", default, 1)
+            format_msg!(
+                MessageKey::AdminApiSetFmt,
+                port = port,
+                token = settings.admin_api_token
+            )
         );
+        log_info(&format!("Admin API set to port {}.", port));
     }
-    io::stdout().flush()?;
+    Ok(())
+}
 
-    // ユーザーからの入力を読み取ります。
-    let input = if is_password {
-        read_password()? // rpasswordクレートを使い、安全にパスワードを読み取る
-    } else {
-        let mut buffer = String::new();
-        io::stdin().read_line(&mut buffer)?;
-        buffer
+/// 通知ラウンドの成功時にサービスがpingを送るURLを検証し、レジストリに保存します。
+///
+/// 空文字列または `-` を指定すると設定を解除します。
+/// サービスは通知ラウンドのたびに設定を読み込むため、稼働中のサービスにもすぐに反映されます。
+fn ping_url_mode(url: &str) -> Result<(), AdapterError> {
+    let Some(url) = normalize_optional_url(url) else {
+        return Err(format_msg!(MessageKey::SetEndpointUrlInvalidFmt, url = url.trim()).into());
     };
-
-    let trimmed = input.trim();
-    // 入力が空であればデフォルト値を、そうでなければ入力された値を返します。
-    if trimmed.is_empty() {
-        Ok(default.to_string())
+    let mut settings = load_settings()?;
+    settings.ping_url = url.clone();
+    save_settings(&settings)?;
+    if url.is_empty() {
+        outln!("{}", get_msg(MessageKey::PingUrlCleared));
+        log_info("Ping URL cleared.");
     } else {
-        Ok(trimmed.to_string())
+        outln!("{}", format_msg!(MessageKey::PingUrlSetFmt, url = url));
+        log_info(&format!("Ping URL set to {}.", url));
     }
+    Ok(())
 }
 
-/// パスワード文字列を、コンソール表示用にマスクします。
-/// 機密情報が画面に平文で表示されるのを防ぎます。
-fn mask_password(pw: &str) -> String {
-    let len = pw.chars().count();
-    if len == 0 {
-        return get_msg("not_set").to_string();
-    }
-    // 短すぎるパスワードは、文字数すら推測されないように全てマスクします。
-    if len <= 4 {
-        return "*".repeat(len);
+/// 通知の状況の変化を知らせるWebhookのURLを検証し、レジストリに保存します。
+///
+/// 空文字列または `-` を指定すると設定を解除します。
+/// 設定は通知のたびに読み込むため、稼働中のサービスにもすぐに反映されます。
+fn webhook_url_mode(url: &str) -> Result<(), AdapterError> {
+    let Some(url) = normalize_optional_url(url) else {
+        return Err(format_msg!(MessageKey::SetEndpointUrlInvalidFmt, url = url.trim()).into());
+    };
+    let mut settings = load_settings()?;
+    settings.webhook_url = url.clone();
+    save_settings(&settings)?;
+    if url.is_empty() {
+        outln!("{}", get_msg(MessageKey::WebhookUrlCleared));
+        log_info("Webhook URL cleared.");
+    } else {
+        outln!("{}", format_msg!(MessageKey::WebhookUrlSetFmt, url = url));
+        log_info(&format!("Webhook URL set to {}.", url));
     }
+    Ok(())
+}
 
-    let mut chars: Vec<char> = pw.chars().collect();
-    // ユーザーがパスワードが設定されていることを認識できるよう、最初・真ん中・最後の文字だけ表示します。
-    for (i, c) in chars.iter_mut().enumerate() {
-        if i != 0 && i != len - 1 && i != len / 2 {
-            *c = '*';
-        }
+/// CGNATやプライベートのIPv4アドレスの通知を見送るかどうかを、レジストリに保存します。
+fn skip_private_ipv4_mode(skip: bool) -> Result<(), AdapterError> {
+    let mut settings = load_settings()?;
+    settings.skip_private_ipv4 = skip;
+    save_settings(&settings)?;
+    if skip {
+        outln!("{}", get_msg(MessageKey::SkipPrivateIpv4Enabled));
+        log_info("Skipping non-public IPv4 addresses enabled.");
+    } else {
+        outln!("{}", get_msg(MessageKey::SkipPrivateIpv4Disabled));
+        log_info("Skipping non-public IPv4 addresses disabled.");
     }
-    chars.into_iter().collect()
+    Ok(())
 }
 
-/// Yes/No形式の質問をユーザーに問いかけ、現在の設定値も表示します。
-fn ask_yes_no(prompt: &str, default: bool) -> io::Result<bool> {
-    let current_value = if default {
-        get_msg("yes")
+/// サービスがDNSの自己確認を行う間隔を、レジストリに保存します。0は確認しないことを表します。
+fn dns_check_mode(minutes: u32) -> Result<(), AdapterError> {
+    let mut settings = load_settings()?;
+    settings.dns_check_minutes = minutes;
+    save_settings(&settings)?;
+    if minutes == 0 {
+        outln!("{}", get_msg(MessageKey::DnsCheckDisabled));
+        log_info("DNS self-check disabled.");
     } else {
-        get_msg("no")
+        outln!(
+            "{}",
+            format_msg!(MessageKey::DnsCheckSetFmt, minutes = minutes)
+        );
+        log_info(&format!("DNS self-check set to every {} minutes.", minutes));
+    }
+    Ok(())
+}
+
+/// 従量制課金の接続での、サービスによる通知の扱いを、レジストリに保存します。
+fn metered_mode(policy: MeteredPolicy) -> Result<(), AdapterError> {
+    let mut settings = load_settings()?;
+    settings.metered_policy = policy;
+    save_settings(&settings)?;
+    let key = match policy {
+        MeteredPolicy::Ignore => MessageKey::MeteredIgnore,
+        MeteredPolicy::Reduce => MessageKey::MeteredReduce,
+        MeteredPolicy::Skip => MessageKey::MeteredSkip,
     };
-    let hint = if default {
-        // デフォルト値が大文字で表示されるヒント (Y/n)
-        get_msg("yes_no_hint_true")
+    outln!("{}", get_msg(key));
+    log_info(&format!(
+        "Metered connection policy set to {}.",
+        policy.code()
+    ));
+    Ok(())
+}
+
+/// サービスが通知するネットワークの名前を、レジストリに保存します。"-" は制限しないことを表します。
+///
+/// 名前を確かめやすいよう、設定した後に現在接続中のネットワークの名前も表示します。
+fn allowed_networks_mode(input: &str) -> Result<(), AdapterError> {
+    let mut settings = load_settings()?;
+    settings.allowed_networks = if input.trim() == "-" {
+        Vec::new()
     } else {
-        // (y/N)
-        get_msg("yes_no_hint_false")
+        input
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect()
     };
-    loop {
-        print!(
+    save_settings(&settings)?;
+    if settings.allowed_networks.is_empty() {
+        outln!("{}", get_msg(MessageKey::AllowedNetworksCleared));
+        log_info("Allowed networks cleared.");
+    } else {
+        let networks = settings.allowed_networks.join(", ");
+        outln!(
             "{}",
-            get_msg("yes_no_prompt_fmt")
-                .replacen("{}", prompt, 1)
-                .replacen("{}", current_value, 1)
-                .replacen("{}", hint, 1)
+            format_msg!(MessageKey::AllowedNetworksSetFmt, networks = networks)
         );
-        io::stdout().flush()?;
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        let trimmed = input.trim().to_lowercase();
+        log_info(&format!("Allowed networks set to {}.", networks));
+    }
+    if let Ok(names) = connected_network_names() {
+        outln!(
+            "{}",
+            format_msg!(
+                MessageKey::ConnectedNetworksFmt,
+                networks = if names.is_empty() {
+                    "-".to_string()
+                } else {
+                    names.join(", ")
+                }
+            )
+        );
+    }
+    Ok(())
+}
 
-        if trimmed.is_empty() {
-            // ユーザーがEnterキーのみを押した場合、デフォルト値を返します。
-            return Ok(default);
+/// サービスが自動の通知を見送る時間帯を、レジストリに保存します。"-" は時間帯を設けないことを表します。
+fn quiet_hours_mode(input: &str) -> Result<(), AdapterError> {
+    let quiet =
+        if input.trim() == "-" {
+            None
+        } else {
+            Some(QuietHours::from_code(input).ok_or_else(|| {
+                format_msg!(MessageKey::QuietHoursInvalidFmt, hours = input.trim())
+            })?)
+        };
+    let mut settings = load_settings()?;
+    settings.quiet_hours = quiet;
+    save_settings(&settings)?;
+    match quiet {
+        Some(quiet) => {
+            outln!(
+                "{}",
+                format_msg!(MessageKey::QuietHoursSetFmt, hours = quiet.code())
+            );
+            log_info(&format!("Quiet hours set to {}.", quiet.code()));
         }
-
-        match trimmed.as_str() {
-            "y" => return Ok(true),
-            "n" => return Ok(false),
-            _ => println!("{}", get_msg("yes_no_invalid")),
+        None => {
+            outln!("{}", get_msg(MessageKey::QuietHoursCleared));
+            log_info("Quiet hours cleared.");
         }
     }
+    Ok(())
 }
 
-/// 「現在の値」を表示しない、シンプルなYes/No形式の確認をユーザーに求めます。
-fn ask_yes_no_simple(prompt: &str, default: bool) -> io::Result<bool> {
-    let hint = if default {
-        get_msg("yes_no_hint_true")
+/// サービスの通知の時刻をランダムにずらす最大の秒数を、レジストリに保存します。0はずらさないことを表します。
+fn jitter_mode(seconds: u32) -> Result<(), AdapterError> {
+    let mut settings = load_settings()?;
+    settings.jitter_seconds = seconds;
+    save_settings(&settings)?;
+    if seconds == 0 {
+        outln!("{}", get_msg(MessageKey::JitterDisabled));
+        log_info("Notification jitter disabled.");
     } else {
-        get_msg("yes_no_hint_false")
+        outln!(
+            "{}",
+            format_msg!(MessageKey::JitterSetFmt, seconds = seconds)
+        );
+        log_info(&format!("Notification jitter set to {} seconds.", seconds));
+    }
+    Ok(())
+}
+
+/// HTTPリクエストのUser-Agentの末尾に付ける識別子を検証し、レジストリに保存します。
+///
+/// 空文字列または `-` を指定すると設定を解除します。
+/// サービスは通知処理のたびにクライアントを作成するため、稼働中のサービスにもすぐに反映されます。
+fn user_agent_id_mode(id: &str) -> Result<(), AdapterError> {
+    let id = match id.trim() {
+        "-" => "",
+        id => id,
     };
-    loop {
-        print!(
+    if !is_valid_user_agent_id(id) {
+        return Err(format_msg!(
+            MessageKey::UserAgentIdInvalidFmt,
+            max = USER_AGENT_ID_MAX_LEN
+        )
+        .into());
+    }
+    let mut settings = load_settings()?;
+    settings.user_agent_id = id.to_string();
+    save_settings(&settings)?;
+    if id.is_empty() {
+        outln!("{}", get_msg(MessageKey::UserAgentIdCleared));
+        log_info("User-Agent identifier cleared.");
+    } else {
+        outln!(
             "{}",
-            get_msg("confirm_prompt_fmt")
-                .replacen("{}", prompt, 1)
-                .replacen("{}", hint, 1)
+            format_msg!(MessageKey::UserAgentIdSetFmt, ua = user_agent(id))
         );
-        io::stdout().flush()?;
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        let trimmed = input.trim().to_lowercase();
+        log_info(&format!("User-Agent identifier set to {}.", id));
+    }
+    Ok(())
+}
 
-        if trimmed.is_empty() {
-            return Ok(default);
-        }
+/// 通知の履歴をSQLiteのデータベースにも記録するかどうかを、レジストリに保存します。
+///
+/// 有効にする場合は、先にデータベースを作成して書き込めることを確かめます。
+/// `sqlite` フィーチャーなしでビルドした場合は、有効にできません。
+fn history_db_mode(enabled: bool) -> Result<(), AdapterError> {
+    if enabled {
+        #[cfg(not(feature = "sqlite"))]
+        return Err(get_msg(MessageKey::HistoryDbUnsupported).into());
+        #[cfg(feature = "sqlite")]
+        historydb::initialize()?;
+    }
+    let mut settings = load_settings()?;
+    settings.history_database = enabled;
+    save_settings(&settings)?;
+    if enabled {
+        outln!("{}", get_msg(MessageKey::HistoryDbEnabled));
+        log_info("History database enabled.");
+    } else {
+        outln!("{}", get_msg(MessageKey::HistoryDbDisabled));
+        log_info("History database disabled.");
+    }
+    Ok(())
+}
 
-        match trimmed.as_str() {
-            "y" => return Ok(true),
-            "n" => return Ok(false),
-            _ => println!("{}", get_msg("yes_no_invalid")),
+/// 通知が何回続けて失敗したらトースト通知で知らせるかを、レジストリに保存します。0は知らせないことを表します。
+fn toast_after_mode(count: u32) -> Result<(), AdapterError> {
+    let mut settings = load_settings()?;
+    settings.toast_failure_threshold = count;
+    save_settings(&settings)?;
+    if count == 0 {
+        outln!("{}", get_msg(MessageKey::ToastDisabled));
+        log_info("Failure toasts disabled.");
+    } else {
+        outln!("{}", format_msg!(MessageKey::ToastSetFmt, count = count));
+        log_info(&format!(
+            "Failure toasts set to {} consecutive failures.",
+            count
+        ));
+    }
+    Ok(())
+}
+
+/// 通知の失敗を知らせるメールの設定を検証し、レジストリに保存します。
+///
+/// 設定は通知のたびに読み込むため、稼働中のサービスにもすぐに反映されます。
+fn mail_settings_mode(edits: &MailEdits) -> Result<(), AdapterError> {
+    let settings = edits.apply(&load_settings()?)?;
+    save_settings(&settings)?;
+    if settings.smtp_server.is_empty() {
+        outln!("{}", get_msg(MessageKey::MailDisabled));
+        log_info("Mail alerts disabled.");
+    } else {
+        outln!("{}", get_msg(MessageKey::MailSettingsSaved));
+        if !is_mail_configured(&settings) {
+            outln!("{}", get_msg(MessageKey::MailNotConfigured));
         }
+        log_info(&format!(
+            "Mail alert settings changed (server {}:{}).",
+            settings.smtp_server, settings.smtp_port
+        ));
     }
+    Ok(())
 }
 
-/// 設定されているすべてのアカウント情報を、整形されたリストとして表示します。
-fn view_mode() -> io::Result<()> {
-    println!("{}", get_msg("view_title"));
-    let configs = load_all_configs().unwrap_or_else(|_| Vec::new());
+/// グローバルアドレスの確認サービスの設定を検証し、レジストリに保存します。
+///
+/// 設定は確認のたびに読み込むため、稼働中のサービスにもすぐに反映されます。
+fn check_ip_settings_mode(edits: &CheckIpEdits) -> Result<(), AdapterError> {
+    let settings = edits.apply(&load_settings()?)?;
+    save_settings(&settings)?;
+    outln!("{}", get_msg(MessageKey::CheckIpSettingsSaved));
+    log_info(&format!(
+        "Check-IP settings changed ({} IPv4 and {} IPv6 custom services, poll every {} minutes).",
+        settings.check_ipv4_urls.len(),
+        settings.check_ipv6_urls.len(),
+        settings.check_ip_poll_minutes
+    ));
+    Ok(())
+}
 
-    if configs.is_empty() {
-        println!("{}", get_msg("view_no_accounts"));
-        return Ok(());
+/// ログのアーカイブを残す数と圧縮の有無を検証し、レジストリに保存します。
+///
+/// 指定されなかった項目は変更しません。設定はローテーションのたびに読み込まれるため、
+/// 稼働中のサービスにもすぐに反映されます。
+fn log_archive_mode(count: Option<u32>, compress: Option<bool>) -> Result<(), AdapterError> {
+    if let Some(count) = count
+        && !LOG_ARCHIVE_COUNT_RANGE.contains(&count)
+    {
+        return Err(format_msg!(
+            MessageKey::LogArchivesOutOfRangeFmt,
+            min = LOG_ARCHIVE_COUNT_RANGE.start(),
+            max = LOG_ARCHIVE_COUNT_RANGE.end()
+        )
+        .into());
     }
-
-    for config in &configs {
-        // 各値を指定の長さにフォーマットする
-        let master_id_val = format!("{:<11.11}", &config.master_id);
-        let password_val = format!("{:<11.11}", mask_password(&config.password));
-        let ipv4_val = format!(
-            "{:<3.3}",
-            if config.ipv4_notify {
-                get_msg("yes")
-            } else {
-                get_msg("no")
-            }
-        );
-        let ipv6_val = format!(
-            "{:<3.3}",
-            if config.ipv6_notify {
-                get_msg("yes")
+    let mut settings = load_settings()?;
+    if let Some(count) = count {
+        settings.log_archive_count = count;
+    }
+    if let Some(compress) = compress {
+        settings.log_archive_compress = compress;
+    }
+    save_settings(&settings)?;
+    outln!(
+        "{}",
+        format_msg!(
+            MessageKey::LogArchiveSetFmt,
+            count = settings.log_archive_count,
+            compress = if settings.log_archive_compress {
+                get_msg(MessageKey::Yes)
             } else {
-                get_msg("no")
+                get_msg(MessageKey::No)
             }
+        )
+    );
+    log_info(&format!(
+        "Log archive settings changed: keep {}, compress {}.",
+        settings.log_archive_count, settings.log_archive_compress
+    ));
+    Ok(())
+}
+
+/// 一時的な障害で通知に失敗した場合の最大試行回数を検証し、レジストリに保存します。
+///
+/// 設定は通知のたびに読み込まれるため、稼働中のサービスにもすぐに反映されます。
+fn retry_attempts_mode(count: u32) -> Result<(), AdapterError> {
+    if !RETRY_MAX_ATTEMPTS_RANGE.contains(&count) {
+        return Err(format_msg!(
+            MessageKey::RetryOutOfRangeFmt,
+            min = RETRY_MAX_ATTEMPTS_RANGE.start(),
+            max = RETRY_MAX_ATTEMPTS_RANGE.end()
+        )
+        .into());
+    }
+    let mut settings = load_settings()?;
+    settings.retry_max_attempts = count;
+    save_settings(&settings)?;
+    outln!("{}", format_msg!(MessageKey::RetrySetFmt, count = count));
+    log_info(&format!("Maximum notification attempts set to {}.", count));
+    Ok(())
+}
+
+/// 1回の通知処理で同時に通知するアカウントの最大数を検証し、レジストリに保存します。
+///
+/// 設定は通知処理のたびに読み込まれるため、稼働中のサービスにもすぐに反映されます。
+fn concurrency_mode(count: u32) -> Result<(), AdapterError> {
+    if !NOTIFY_CONCURRENCY_RANGE.contains(&count) {
+        return Err(format_msg!(
+            MessageKey::ConcurrencyOutOfRangeFmt,
+            min = NOTIFY_CONCURRENCY_RANGE.start(),
+            max = NOTIFY_CONCURRENCY_RANGE.end()
+        )
+        .into());
+    }
+    let mut settings = load_settings()?;
+    settings.notify_concurrency = count;
+    save_settings(&settings)?;
+    outln!(
+        "{}",
+        format_msg!(MessageKey::ConcurrencySetFmt, count = count)
+    );
+    log_info(&format!("Notification concurrency set to {}.", count));
+    Ok(())
+}
+
+/// 1回の通知リクエストのタイムアウトを検証し、レジストリに保存します。
+///
+/// サービスは通知処理のたびにクライアントを作成するため、稼働中のサービスにもすぐに反映されます。
+fn request_timeout_mode(seconds: u32) -> Result<(), AdapterError> {
+    if !REQUEST_TIMEOUT_RANGE_SECONDS.contains(&seconds) {
+        return Err(format_msg!(
+            MessageKey::RequestTimeoutOutOfRangeFmt,
+            min = REQUEST_TIMEOUT_RANGE_SECONDS.start(),
+            max = REQUEST_TIMEOUT_RANGE_SECONDS.end()
+        )
+        .into());
+    }
+    let mut settings = load_settings()?;
+    settings.request_timeout_seconds = seconds;
+    save_settings(&settings)?;
+    outln!(
+        "{}",
+        format_msg!(MessageKey::RequestTimeoutSetFmt, seconds = seconds)
+    );
+    log_info(&format!(
+        "Notification request timeout set to {} seconds.",
+        seconds
+    ));
+    Ok(())
+}
+
+/// 通知に使うプロキシのURLを検証し、レジストリに保存します。
+///
+/// 空文字列または `-` を指定すると設定を解除し、WinHTTPやシステムの設定に従うようにします。
+/// サービスは通知のたびにクライアントを作成するため、稼働中のサービスにもすぐに反映されます。
+fn proxy_mode(url: &str) -> Result<(), AdapterError> {
+    let url = url.trim();
+    let url = if url == "-" { "" } else { url };
+    // 実際に通知で使うのと同じ方法でクライアントを作成し、URLの形式を検証する。
+    if !url.is_empty() {
+        build_client(url)?;
+    }
+    let mut settings = load_settings()?;
+    settings.proxy_url = url.to_string();
+    save_settings(&settings)?;
+    if url.is_empty() {
+        outln!("{}", get_msg(MessageKey::ProxyCleared));
+        log_info("Notification proxy cleared.");
+    } else {
+        outln!("{}", format_msg!(MessageKey::ProxySetFmt, url = url));
+        log_info(&format!("Notification proxy set to {}.", url));
+    }
+    Ok(())
+}
+
+/// ログファイルの場所を設定します。
+///
+/// 相対パスは絶対パスに変換して保存します。"-" が指定された場合は設定を解除し、既定の場所に戻します。
+/// 稼働中のサービスには、再起動後に反映されます。
+fn log_path_mode(path: &Path) -> Result<(), AdapterError> {
+    let path = if path.as_os_str() == "-" {
+        None
+    } else {
+        Some(std::path::absolute(path)?)
+    };
+    let mut settings = load_settings()?;
+    settings.log_path = path
+        .as_ref()
+        .map(|p| p.display().to_string())
+        .unwrap_or_default();
+    save_settings(&settings)?;
+    let effective = path.unwrap_or_else(default_log_path);
+    outln!(
+        "{}",
+        format_msg!(MessageKey::LogPathSetFmt, path = effective.display())
+    );
+    log_info(&format!(
+        "Log file location set to {}.",
+        effective.display()
+    ));
+    Ok(())
+}
+
+/// サービスの状態と稼働時間、アカウントごとの直近の通知の時刻と結果をまとめて表示します。
+///
+/// サービスの状態はSCMに問い合わせ、稼働時間と通知の結果はサービスが記録した実行時状態から求めます。
+fn status_mode() -> Result<(), AdapterError> {
+    println!("{}", get_msg(MessageKey::StatusTitle));
+    let state = load_state().unwrap_or_default();
+
+    // 開始時刻は停止時に消去されるが、異常終了した場合に備えて実行中のときだけ表示する。
+    if service_status()?
+        && let Some(started) = state.service_started
+    {
+        let minutes = (chrono::Local::now() - started).num_minutes().max(0);
+        println!(
+            "{}",
+            format_msg!(
+                MessageKey::StatusUptimeFmt,
+                days = minutes / (24 * 60),
+                hours = minutes / 60 % 24,
+                minutes = minutes % 60,
+                since = started.format("%Y-%m-%d %H:%M:%S")
+            )
         );
+    }
+    if state.paused {
+        println!("{}", paint(get_msg(MessageKey::ViewPaused), Color::Yellow));
+    }
 
-        // 国際化されたフォーマット文字列を使って、一行の情報を組み立てて表示します。
+    let configs = load_all_configs().unwrap_or_else(|_| Vec::new());
+    if configs.is_empty() {
+        println!("{}", get_msg(MessageKey::ViewNoAccounts));
+        return Ok(());
+    }
+    let format_time = |time: Option<chrono::DateTime<chrono::Local>>| {
+        time.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| get_msg(MessageKey::NotSet).to_string())
+    };
+    // 履歴のデータベースが有効な場合は、直近24時間の試行をアカウントごとに集計して表示する。
+    #[cfg(feature = "sqlite")]
+    let summaries = if historydb::is_enabled() {
+        historydb::summarize_since(chrono::Local::now() - chrono::Duration::hours(24)).ok()
+    } else {
+        None
+    };
+    for config in &configs {
+        let account = state.accounts.get(&config.master_id);
+        let last_result = paint_result(account.and_then(|a| a.last_result.as_deref()));
+        // 最終成功時刻は、実行時状態とアカウントのキーの記録のうち、新しいほうを表示する。
+        let last = load_last_success(&config.master_id);
+        let last_success = account.and_then(|a| a.last_success).max(last.time);
         println!(
             "{}",
-            get_msg("view_list_fmt")
-                .replace("{id}", &master_id_val)
-                .replace("{pw}", &password_val)
-                .replace("{v4}", &ipv4_val)
-                .replace("{v6}", &ipv6_val)
+            format_msg!(
+                MessageKey::StatusAccountFmt,
+                id = config.master_id,
+                time = format_time(account.and_then(|a| a.last_attempt)),
+                result = last_result,
+                success = format_time(last_success)
+            )
         );
+        if last.ipv4.is_some() || last.ipv6.is_some() {
+            println!(
+                "{}",
+                format_msg!(
+                    MessageKey::StatusLastIpFmt,
+                    v4 = format_last_ip(last.ipv4),
+                    v6 = format_last_ip(last.ipv6)
+                )
+            );
+        }
+        #[cfg(feature = "sqlite")]
+        if let Some(summaries) = &summaries {
+            let summary = summaries
+                .get(&config.master_id)
+                .copied()
+                .unwrap_or_default();
+            println!(
+                "{}",
+                format_msg!(
+                    MessageKey::StatusHistorySummaryFmt,
+                    attempts = summary.attempts,
+                    successes = summary.successes,
+                    ms = summary.average_latency_ms
+                )
+            );
+        }
+        print_last_error(&config.master_id);
+        print_suspended(&config.master_id);
     }
+    Ok(())
+}
 
+/// 稼働中のサービスに、定期通知を待たずにすぐ通知するよう要求します。
+///
+/// サービスが実行中でない場合はエラーを返します。
+fn kick_mode() -> Result<(), AdapterError> {
+    kick_service()?;
+    outln!("{}", get_msg(MessageKey::KickSuccess));
+    log_info("Immediate notification requested.");
     Ok(())
 }
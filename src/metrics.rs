@@ -0,0 +1,228 @@
+//! サービスの通知の結果を、Prometheusのテキスト形式で公開するモジュール。
+//!
+//! `--metrics-port <PORT>` で有効にすると、サービスは `http://127.0.0.1:<PORT>/metrics` で待ち受け、
+//! アカウントとプロトコルごとの通知の試行・成功・失敗の回数、最後に成功した時刻、
+//! 1回のリクエストの所要時間の分布（ヒストグラム）を返します。
+//! 値はサービスのプロセスの中だけで集計するため、サービスを再起動すると0から数え直します。
+//! 待ち受けるのはループバックアドレスだけで、他のマシンからは接続できません。
+//!
+//! 履歴のデータベース（`historydb` モジュール）が有効な場合は、再起動の影響を受けない
+//! 直近24時間の試行と成功の回数も、データベースから集計して返します。
+
+use crate::localhttp::{HttpResponse, LocalHttpServer};
+use chrono::{DateTime, Local};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+/// メトリクスを返すパス。
+const METRICS_PATH: &str = "/metrics";
+/// リクエストの所要時間のヒストグラムの区切り（秒）。
+const LATENCY_BUCKETS: [f64; 8] = [0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+/// アカウントとプロトコルの組ごとの集計。
+#[derive(Default)]
+struct Series {
+    /// 通知リクエストを送信した回数（再試行を含む）。
+    attempts: u64,
+    /// 成功したリクエストの回数。
+    successes: u64,
+    /// 失敗したリクエストの回数。
+    failures: u64,
+    /// 所要時間が各区切り以下だったリクエストの回数。
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    /// 所要時間の合計（秒）。
+    latency_sum: f64,
+}
+
+/// カウンターの名前、説明、集計から値を取り出す関数の組。
+type Counter = (&'static str, &'static str, fn(&Series) -> u64);
+
+/// プロセス内で集計したメトリクス。
+struct Metrics {
+    /// キーは（MasterID、プロトコル）。
+    series: BTreeMap<(String, &'static str), Series>,
+    /// アカウントごとの、最後に通知に成功した時刻（UNIX時間の秒）。
+    last_success: BTreeMap<String, i64>,
+}
+
+/// 集計中のメトリクス。
+static METRICS: Mutex<Metrics> = Mutex::new(Metrics {
+    series: BTreeMap::new(),
+    last_success: BTreeMap::new(),
+});
+
+/// 1回の通知リクエストの結果を集計します。
+pub fn observe_attempt(master_id: &str, protocol: &'static str, success: bool, latency: Duration) {
+    let mut metrics = METRICS.lock().unwrap_or_else(PoisonError::into_inner);
+    let series = metrics
+        .series
+        .entry((master_id.to_string(), protocol))
+        .or_default();
+    series.attempts += 1;
+    if success {
+        series.successes += 1;
+    } else {
+        series.failures += 1;
+    }
+    let secs = latency.as_secs_f64();
+    for (count, bound) in series.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+        if secs <= bound {
+            *count += 1;
+        }
+    }
+    series.latency_sum += secs;
+}
+
+/// アカウントの通知に成功した時刻を記録します。
+pub fn observe_success(master_id: &str, time: DateTime<Local>) {
+    METRICS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .last_success
+        .insert(master_id.to_string(), time.timestamp());
+}
+
+/// 集計したメトリクスを、Prometheusのテキスト形式（バージョン0.0.4）で出力します。
+fn render() -> String {
+    let metrics = METRICS.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut out = String::new();
+    // `String` への書き込みは失敗しないため、結果は無視する。
+    let counters: [Counter; 3] = [
+        (
+            "mydns_notify_attempts_total",
+            "Notification requests sent, including retries.",
+            |s| s.attempts,
+        ),
+        (
+            "mydns_notify_successes_total",
+            "Notification requests that succeeded.",
+            |s| s.successes,
+        ),
+        (
+            "mydns_notify_failures_total",
+            "Notification requests that failed.",
+            |s| s.failures,
+        ),
+    ];
+    for (name, help, value) in counters {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} counter", name);
+        for ((id, protocol), series) in &metrics.series {
+            let _ = writeln!(
+                out,
+                "{}{{account=\"{}\",protocol=\"{}\"}} {}",
+                name,
+                escape_label(id),
+                protocol,
+                value(series)
+            );
+        }
+    }
+
+    let name = "mydns_last_success_timestamp_seconds";
+    let _ = writeln!(
+        out,
+        "# HELP {} Time of the last successful notification.",
+        name
+    );
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+    for (id, time) in &metrics.last_success {
+        let _ = writeln!(out, "{}{{account=\"{}\"}} {}", name, escape_label(id), time);
+    }
+
+    let name = "mydns_notify_request_duration_seconds";
+    let _ = writeln!(out, "# HELP {} Time taken by a notification request.", name);
+    let _ = writeln!(out, "# TYPE {} histogram", name);
+    for ((id, protocol), series) in &metrics.series {
+        let labels = format!("account=\"{}\",protocol=\"{}\"", escape_label(id), protocol);
+        for (count, bound) in series.buckets.iter().zip(LATENCY_BUCKETS) {
+            let _ = writeln!(
+                out,
+                "{}_bucket{{{},le=\"{}\"}} {}",
+                name, labels, bound, count
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{}_bucket{{{},le=\"+Inf\"}} {}",
+            name, labels, series.attempts
+        );
+        let _ = writeln!(out, "{}_sum{{{}}} {}", name, labels, series.latency_sum);
+        let _ = writeln!(out, "{}_count{{{}}} {}", name, labels, series.attempts);
+    }
+    drop(metrics);
+    #[cfg(feature = "sqlite")]
+    render_history_database(&mut out);
+    out
+}
+
+/// 履歴のデータベースが有効な場合に、直近24時間の試行と成功の回数を出力します。
+#[cfg(feature = "sqlite")]
+fn render_history_database(out: &mut String) {
+    use crate::historydb::{self, AttemptSummary};
+
+    /// ゲージの名前、説明、集計から値を取り出す関数の組。
+    type Gauge = (&'static str, &'static str, fn(&AttemptSummary) -> u64);
+
+    if !historydb::is_enabled() {
+        return;
+    }
+    let Ok(summaries) = historydb::summarize_since(Local::now() - chrono::Duration::hours(24))
+    else {
+        return;
+    };
+    let gauges: [Gauge; 2] = [
+        (
+            "mydns_notify_attempts_24h",
+            "Notification requests sent in the last 24 hours, from the history database.",
+            |s| s.attempts,
+        ),
+        (
+            "mydns_notify_successes_24h",
+            "Notification requests that succeeded in the last 24 hours, from the history database.",
+            |s| s.successes,
+        ),
+    ];
+    for (name, help, value) in gauges {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} gauge", name);
+        for (id, summary) in &summaries {
+            let _ = writeln!(
+                out,
+                "{}{{account=\"{}\"}} {}",
+                name,
+                escape_label(id),
+                value(summary)
+            );
+        }
+    }
+}
+
+/// ラベルの値に使えるよう、バックスラッシュ、二重引用符、改行をエスケープします。
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// ループバックアドレスの指定されたポートで、メトリクスの公開を開始します。
+///
+/// 返されたサーバーを破棄すると、公開を終了します。
+pub fn start_metrics_server(port: u16) -> io::Result<LocalHttpServer> {
+    LocalHttpServer::start(port, |request| {
+        let (status, body) = match (request.method.as_str(), request.path.as_str()) {
+            ("GET", METRICS_PATH) => ("200 OK", render()),
+            ("GET", _) => ("404 Not Found", "Not Found\n".to_string()),
+            _ => ("405 Method Not Allowed", "Method Not Allowed\n".to_string()),
+        };
+        HttpResponse {
+            status,
+            content_type: "text/plain; version=0.0.4; charset=utf-8",
+            body,
+        }
+    })
+}
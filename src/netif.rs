@@ -0,0 +1,136 @@
+//! アカウントの通知を送る、ネットワークインターフェイスのアドレスを求めるモジュール。
+//!
+//! 複数の回線やVPNにつながっているマシンでは、アカウントごとに通知を送るインターフェイスを選べます。
+//! アカウントの送信元には、インターフェイスの名前（エイリアス、`Get-NetAdapter` の `Name`）か、
+//! このマシンのIPアドレスを指定します。通知のリクエストは、そのアドレスをローカルアドレスとして送信します。
+
+use crate::format_msg_log;
+use crate::i18n::MessageKey;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use windows::Win32::Foundation::{ERROR_BUFFER_OVERFLOW, ERROR_SUCCESS, WIN32_ERROR};
+use windows::Win32::NetworkManagement::IpHelper::{
+    GAA_FLAG_SKIP_ANYCAST, GAA_FLAG_SKIP_DNS_SERVER, GAA_FLAG_SKIP_MULTICAST, GetAdaptersAddresses,
+    IP_ADAPTER_ADDRESSES_LH,
+};
+use windows::Win32::Networking::WinSock::{
+    AF_INET, AF_INET6, AF_UNSPEC, SOCKADDR_IN, SOCKADDR_IN6, SOCKET_ADDRESS,
+};
+
+/// `GetAdaptersAddresses` に最初に渡すバッファの大きさ（バイト）。Microsoftの推奨値。
+const INITIAL_BUFFER_SIZE: u32 = 15 * 1024;
+
+/// アカウントの送信元の設定から、指定したプロトコルの通知に使うローカルアドレスを求めます。
+///
+/// 送信元が設定されていない場合は `None` を返します。IPアドレスを指定している場合は、
+/// そのプロトコルの通知だけに使い、もう一方のプロトコルでは `None` を返します。
+/// インターフェイスの名前を指定している場合は、そのインターフェイスのアドレスのうち、
+/// リンクローカルでない最初のアドレスを返します。
+///
+/// # 戻り値
+/// インターフェイスが見つからない場合や、そのプロトコルのアドレスを持たない場合は、その内容を表す文字列。
+pub fn resolve_bind_address(bind: &str, ipv6: bool) -> Result<Option<IpAddr>, String> {
+    if bind.is_empty() {
+        return Ok(None);
+    }
+    if let Ok(addr) = bind.parse::<IpAddr>() {
+        return Ok((addr.is_ipv6() == ipv6).then_some(addr));
+    }
+    let Some(addresses) = interface_addresses(bind).map_err(|e| e.to_string())? else {
+        return Err(format_msg_log!(
+            MessageKey::LogBindInterfaceNotFoundFmt,
+            interface = bind
+        ));
+    };
+    addresses
+        .into_iter()
+        .find(|addr| addr.is_ipv6() == ipv6 && !is_link_local(addr))
+        .map(Some)
+        .ok_or_else(|| {
+            format_msg_log!(
+                MessageKey::LogBindAddressMissingFmt,
+                interface = bind,
+                proto = if ipv6 { "IPv6" } else { "IPv4" }
+            )
+        })
+}
+
+/// 指定した名前のインターフェイスに割り当てられた、ユニキャストアドレスを返します。
+///
+/// 名前の大文字と小文字は区別しません。インターフェイスが見つからない場合は `None` を返します。
+fn interface_addresses(name: &str) -> windows::core::Result<Option<Vec<IpAddr>>> {
+    // 必要な大きさが足りなければ、Windowsが示した大きさで取得し直す。
+    // 構造体の境界をそろえるため、u64の配列をバッファとして使う。
+    let mut size = INITIAL_BUFFER_SIZE;
+    let mut buffer: Vec<u64>;
+    loop {
+        buffer = vec![0; (size as usize).div_ceil(size_of::<u64>())];
+        // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+        // バッファの大きさは `size` として渡しており、それを超えて書き込まれることはない。
+        let result = unsafe {
+            GetAdaptersAddresses(
+                AF_UNSPEC.0.into(),
+                GAA_FLAG_SKIP_ANYCAST | GAA_FLAG_SKIP_MULTICAST | GAA_FLAG_SKIP_DNS_SERVER,
+                None,
+                Some(buffer.as_mut_ptr().cast::<IP_ADAPTER_ADDRESSES_LH>()),
+                &mut size,
+            )
+        };
+        match result {
+            r if r == ERROR_SUCCESS.0 => break,
+            r if r == ERROR_BUFFER_OVERFLOW.0 => continue,
+            r => return Err(WIN32_ERROR(r).to_hresult().into()),
+        }
+    }
+
+    // 取得したアダプターの一覧は連結リストになっており、すべて `buffer` の中を指している。
+    let mut adapter = buffer.as_ptr().cast::<IP_ADAPTER_ADDRESSES_LH>();
+    while !adapter.is_null() {
+        // `adapter` は `GetAdaptersAddresses` が `buffer` の中に書き込んだ要素を指している。
+        let entry = unsafe { &*adapter };
+        let friendly_name = unsafe { entry.FriendlyName.to_string() }.unwrap_or_default();
+        if friendly_name.eq_ignore_ascii_case(name.trim()) {
+            let mut addresses = Vec::new();
+            let mut unicast = entry.FirstUnicastAddress;
+            while !unicast.is_null() {
+                // ユニキャストアドレスの連結リストも、`buffer` の中を指している。
+                let item = unsafe { &*unicast };
+                addresses.extend(socket_address_to_ip(&item.Address));
+                unicast = item.Next;
+            }
+            return Ok(Some(addresses));
+        }
+        adapter = entry.Next;
+    }
+    Ok(None)
+}
+
+/// `SOCKET_ADDRESS` が指すソケットアドレスを、IPアドレスに変換します。
+fn socket_address_to_ip(address: &SOCKET_ADDRESS) -> Option<IpAddr> {
+    if address.lpSockaddr.is_null() {
+        return None;
+    }
+    // `lpSockaddr` は、アドレスファミリーに応じた大きさのソケットアドレスを指している。
+    unsafe {
+        let family = (*address.lpSockaddr).sa_family;
+        if family == AF_INET {
+            let sin = &*address.lpSockaddr.cast::<SOCKADDR_IN>();
+            Some(IpAddr::V4(Ipv4Addr::from(u32::from_be(
+                sin.sin_addr.S_un.S_addr,
+            ))))
+        } else if family == AF_INET6 {
+            let sin6 = &*address.lpSockaddr.cast::<SOCKADDR_IN6>();
+            Some(IpAddr::V6(Ipv6Addr::from(sin6.sin6_addr.u.Byte)))
+        } else {
+            None
+        }
+    }
+}
+
+/// リンクローカルアドレス（169.254.0.0/16、fe80::/10）かどうかを返します。
+/// 外部のサーバーへの通知には使えないため、送信元の候補から除きます。
+fn is_link_local(addr: &IpAddr) -> bool {
+    match addr {
+        IpAddr::V4(v4) => v4.is_link_local(),
+        IpAddr::V6(v6) => v6.is_unicast_link_local(),
+    }
+}
@@ -0,0 +1,150 @@
+//! 接続中のネットワークの名前（ネットワークプロファイルの名前とWi-FiのSSID）を求めるモジュール。
+//!
+//! ノートPCで、自宅のネットワークにいるときだけアドレスを通知するために使います。
+//! `--allowed-networks` で許可するネットワークの名前を設定すると、サービスは通知ラウンドの前に
+//! 接続中のネットワークを確かめ、許可されたネットワークに接続していなければラウンドを見送ります。
+//! カフェのWi-Fiやテザリング中のアドレスを、DNSに登録してしまうことを防げます。
+//!
+//! ネットワークプロファイルの名前はネットワーク一覧マネージャー（NLM）から、
+//! SSIDはWLAN APIから取得します。Wi-Fiのプロファイルの名前は、通常はSSIDと同じですが、
+//! 同じ名前のネットワークがあると「Home 2」のように番号が付くため、両方を候補にします。
+
+use windows::Win32::Foundation::{ERROR_SUCCESS, HANDLE, WIN32_ERROR};
+use windows::Win32::NetworkManagement::WiFi::{
+    WLAN_CONNECTION_ATTRIBUTES, WLAN_INTERFACE_INFO, WLAN_INTERFACE_INFO_LIST, WlanCloseHandle,
+    WlanEnumInterfaces, WlanFreeMemory, WlanOpenHandle, WlanQueryInterface,
+    wlan_interface_state_connected, wlan_intf_opcode_current_connection,
+};
+use windows::Win32::Networking::NetworkListManager::{
+    INetwork, INetworkListManager, NLM_ENUM_NETWORK_CONNECTED, NetworkListManager,
+};
+use windows::Win32::System::Com::{
+    CLSCTX_ALL, COINIT_MULTITHREADED, CoCreateInstance, CoInitializeEx, CoUninitialize,
+};
+
+/// `WlanOpenHandle` に渡す、WLAN APIのクライアントのバージョン（Windows Vista以降）。
+const WLAN_CLIENT_VERSION: u32 = 2;
+
+/// 接続中のネットワークが、許可されたネットワークのいずれかに当たるかどうかを返します。
+///
+/// ネットワークプロファイルの名前かSSIDのどれか1つが一致すれば、許可されたものとします。
+/// 名前の大文字と小文字は区別しません。許可するネットワークが設定されていない場合は、常に `true` を返します。
+pub fn is_allowed_network(allowed: &[String], connected: &[String]) -> bool {
+    allowed.is_empty()
+        || connected.iter().any(|name| {
+            allowed
+                .iter()
+                .any(|allowed| allowed.trim().eq_ignore_ascii_case(name.trim()))
+        })
+}
+
+/// 接続中のネットワークプロファイルの名前と、接続中のWi-FiのSSIDを重複なく返します。
+///
+/// Wi-Fiのアダプターがないマシンや、WLAN AutoConfigサービスが止まっている場合は、
+/// ネットワークプロファイルの名前だけを返します。
+pub fn connected_network_names() -> windows::core::Result<Vec<String>> {
+    let mut names = connected_profile_names()?;
+    for ssid in connected_ssids().unwrap_or_default() {
+        if !names.contains(&ssid) {
+            names.push(ssid);
+        }
+    }
+    Ok(names)
+}
+
+/// NLMから、接続中のネットワークプロファイルの名前を取得します。
+///
+/// COMの初期化と終了は、この関数の中で行います。
+fn connected_profile_names() -> windows::core::Result<Vec<String>> {
+    // COMのAPIを呼び出すため、unsafeブロックを使用する。
+    // 取得したインターフェイスは、COMを終了する前に解放する。
+    unsafe {
+        CoInitializeEx(None, COINIT_MULTITHREADED).ok()?;
+        let result = (|| {
+            let manager: INetworkListManager =
+                CoCreateInstance(&NetworkListManager, None, CLSCTX_ALL)?;
+            let networks = manager.GetNetworks(NLM_ENUM_NETWORK_CONNECTED)?;
+            let mut names = Vec::new();
+            loop {
+                let mut network: [Option<INetwork>; 1] = [None];
+                let mut fetched = 0;
+                networks.Next(&mut network, Some(&mut fetched))?;
+                match network {
+                    [Some(network)] if fetched > 0 => names.push(network.GetName()?.to_string()),
+                    _ => break,
+                }
+            }
+            Ok(names)
+        })();
+        CoUninitialize();
+        result
+    }
+}
+
+/// WLAN APIから、接続中のWi-FiのSSIDを取得します。
+fn connected_ssids() -> windows::core::Result<Vec<String>> {
+    let mut negotiated = 0;
+    let mut handle = HANDLE::default();
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // 開いたハンドルは、この関数を抜ける前に閉じる。
+    let result = unsafe { WlanOpenHandle(WLAN_CLIENT_VERSION, None, &mut negotiated, &mut handle) };
+    if result != ERROR_SUCCESS.0 {
+        return Err(WIN32_ERROR(result).to_hresult().into());
+    }
+    let ssids = interface_ssids(handle);
+    // 閉じられなくても、取得した結果には影響しない。
+    unsafe { WlanCloseHandle(handle, None) };
+    ssids
+}
+
+/// 開いたWLAN APIのハンドルで、接続中のインターフェイスのSSIDを列挙します。
+fn interface_ssids(handle: HANDLE) -> windows::core::Result<Vec<String>> {
+    let mut list: *mut WLAN_INTERFACE_INFO_LIST = std::ptr::null_mut();
+    // `list` はWLAN APIが確保するメモリを指し、使い終わったら `WlanFreeMemory` で解放する。
+    let result = unsafe { WlanEnumInterfaces(handle, None, &mut list) };
+    if result != ERROR_SUCCESS.0 {
+        return Err(WIN32_ERROR(result).to_hresult().into());
+    }
+    // `InterfaceInfo` は可変長の配列で、`dwNumberOfItems` 個の要素が続いている。
+    let interfaces: &[WLAN_INTERFACE_INFO] = unsafe {
+        std::slice::from_raw_parts(
+            (*list).InterfaceInfo.as_ptr(),
+            (*list).dwNumberOfItems as usize,
+        )
+    };
+    let mut ssids = Vec::new();
+    for interface in interfaces
+        .iter()
+        .filter(|i| i.isState == wlan_interface_state_connected)
+    {
+        let mut size = 0;
+        let mut data: *mut core::ffi::c_void = std::ptr::null_mut();
+        // `data` もWLAN APIが確保するメモリを指し、読み終わったら解放する。
+        let result = unsafe {
+            WlanQueryInterface(
+                handle,
+                &interface.InterfaceGuid,
+                wlan_intf_opcode_current_connection,
+                None,
+                &mut size,
+                &mut data,
+                None,
+            )
+        };
+        if result != ERROR_SUCCESS.0 || data.is_null() {
+            continue;
+        }
+        let ssid = unsafe {
+            let attributes = &*data.cast::<WLAN_CONNECTION_ATTRIBUTES>();
+            let ssid = &attributes.wlanAssociationAttributes.dot11Ssid;
+            let len = (ssid.uSSIDLength as usize).min(ssid.ucSSID.len());
+            String::from_utf8_lossy(&ssid.ucSSID[..len]).into_owned()
+        };
+        unsafe { WlanFreeMemory(data) };
+        if !ssid.is_empty() {
+            ssids.push(ssid);
+        }
+    }
+    unsafe { WlanFreeMemory(list.cast()) };
+    Ok(ssids)
+}
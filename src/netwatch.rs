@@ -0,0 +1,82 @@
+//! IPアドレスの変更をWindowsから通知してもらうためのモジュール。
+//!
+//! `NotifyUnicastIpAddressChange`（IP Helper API）にコールバックを登録し、
+//! このマシンのユニキャストIPアドレスが追加・削除されたときに、登録されたクロージャを呼び出します。
+//! サービスはこれを使い、定期通知のタイマーを待たずに数秒でアドレスの変更に反応します。
+
+use std::ffi::c_void;
+
+use windows::Win32::Foundation::{BOOLEAN, HANDLE};
+use windows::Win32::NetworkManagement::IpHelper::{
+    CancelMibChangeNotify2, MIB_NOTIFICATION_TYPE, MIB_UNICASTIPADDRESS_ROW, MibAddInstance,
+    MibDeleteInstance, NotifyUnicastIpAddressChange,
+};
+use windows::Win32::Networking::WinSock::AF_UNSPEC;
+
+/// アドレス変更時に呼び出されるクロージャの型。
+/// コールバックはWindowsが管理するスレッドから呼び出されるため、`Send + Sync` が必要です。
+type ChangeHandler = Box<dyn Fn() + Send + Sync + 'static>;
+
+/// IPアドレスの変更の監視を表すハンドル。
+///
+/// 破棄（`drop`）されると監視を解除します。
+pub struct AddressChangeWatcher {
+    /// `NotifyUnicastIpAddressChange` が返した通知ハンドル。
+    handle: HANDLE,
+    /// コールバックに渡しているクロージャへのポインタ。監視の解除後に解放する。
+    context: *mut ChangeHandler,
+}
+
+impl AddressChangeWatcher {
+    /// IPv4とIPv6の両方について、アドレスの変更の監視を開始します。
+    ///
+    /// `on_change` は、アドレスが追加または削除されるたびに呼び出されます。
+    pub fn start<F: Fn() + Send + Sync + 'static>(on_change: F) -> windows::core::Result<Self> {
+        let context: *mut ChangeHandler = Box::into_raw(Box::new(Box::new(on_change)));
+        let mut handle = HANDLE::default();
+        // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+        // `context` は監視を解除するまで解放しないため、コールバックから安全に参照できます。
+        let result = unsafe {
+            NotifyUnicastIpAddressChange(
+                AF_UNSPEC,
+                Some(on_address_change),
+                Some(context as *const c_void),
+                BOOLEAN(0),
+                &mut handle,
+            )
+        };
+        if let Err(e) = result.ok() {
+            // 登録に失敗した場合は、コールバックが呼ばれることはないため、ここで解放する。
+            drop(unsafe { Box::from_raw(context) });
+            return Err(e);
+        }
+        Ok(AddressChangeWatcher { handle, context })
+    }
+}
+
+impl Drop for AddressChangeWatcher {
+    fn drop(&mut self) {
+        // `CancelMibChangeNotify2` は実行中のコールバックの完了を待ってから戻るため、
+        // その後であればクロージャを安全に解放できます。
+        unsafe {
+            let _ = CancelMibChangeNotify2(self.handle);
+            drop(Box::from_raw(self.context));
+        }
+    }
+}
+
+/// `NotifyUnicastIpAddressChange` に登録するコールバック関数。
+///
+/// アドレスの追加・削除の通知だけを、登録されたクロージャに伝えます。
+unsafe extern "system" fn on_address_change(
+    context: *const c_void,
+    _row: *const MIB_UNICASTIPADDRESS_ROW,
+    notification_type: MIB_NOTIFICATION_TYPE,
+) {
+    if notification_type != MibAddInstance && notification_type != MibDeleteInstance {
+        return;
+    }
+    // `context` は `AddressChangeWatcher::start` で登録したクロージャを指しています。
+    let handler = unsafe { &*(context as *const ChangeHandler) };
+    handler();
+}
@@ -1,51 +1,750 @@
 //! IPアドレスの更新通知をMyDNS.JPサーバーに送信するロジックを管理するモジュール。
 //!
 //! このモジュールは、以下の機能を提供します。
-//! - コマンドラインからの即時通知 (`--notify`, `--ipv4`, `--ipv6`) の実行
+//! - コマンドラインからの即時通知 (`notify` サブコマンド) の実行
 //! - Windowsサービスからの定期的な通知実行
 //! - 指定されたURLへのHTTP Basic認証を用いた通知リクエストの送信
+//! - POP3/IMAP/FTPでのログインによる通知（`login` モジュール）
+//! - 1つのアカウントの認証情報の確認（`--test`）
+//! - 一時的な障害（接続エラー、5xx、429など）に対する、指数バックオフによる再試行
+//! - ホスト名が設定されたアカウントの、通知後のDNSの反映の確認（`dnsverify` モジュール）
+//! - 認証エラーが続くアカウントの通知の自動停止（`account enable` で再開）
 //!
-//! 通知処理は、`reqwest`クレートを利用して同期的（ブロッキング）に実行されます。
+//! 通知処理は、`reqwest`クレートと`tokio`ランタイムを利用して非同期に実行されます。
+//! 複数のアカウントは並行して通知され、各リクエストにはタイムアウトが設定されます。
+//! 同時に通知するアカウントの数は `--concurrency` で、タイムアウトは `--request-timeout` で変更できます。
+//! 通知に使うプロキシは `proxy` モジュールで決定します。
 
-use crate::i18n::get_msg_en;
-use crate::logging::{log_error, log_info};
-use crate::registry::{Config, load_all_configs};
-use reqwest::blocking::Client;
-use std::io;
+use crate::checkip::{lookup_global_address, non_public_ipv4_range};
+use crate::connectivity::is_network_available;
+use crate::console::{Color, paint};
+use crate::dnsverify::verify_dns;
+use crate::error::AdapterError;
+use crate::eventlog::report_event;
+use crate::exitcode::ExitReason;
+use crate::expiry::check_expiry_warnings;
+use crate::history::{HistoryEntry, record_attempt};
+use crate::i18n::{MessageKey, format_template, get_msg, get_msg_log};
+use crate::logging::{
+    inherit_round_id, log_detail, log_error, log_info, log_warn, new_round_id, with_round_id,
+};
+use crate::login::{LoginFailure, login, login_endpoint};
+use crate::mail::send_failure_alerts;
+use crate::metrics::{observe_attempt, observe_success};
+use crate::netif::resolve_bind_address;
+use crate::ping::send_ping;
+use crate::proxy::{ProxyChoice, resolve_proxy};
+use crate::registry::{Config, NotifyMethod, Provider, load_settings};
+use crate::state::update_account_state;
+use crate::stats::{record_ip_change, record_notification};
+use crate::store::{
+    load_all_configs, load_suspended, save_last_error, save_last_success, save_suspended,
+};
+use crate::toast::{toast_expiry_alerts, toast_failure_streaks};
+use crate::webhook::WebhookTracker;
+use crate::{format_msg, format_msg_log, outln};
+use chrono::{DateTime, Local, TimeDelta};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Client, ClientBuilder, NoProxy, Proxy, Response, StatusCode};
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time::{self, Instant};
+use windows::Wdk::System::SystemServices::RtlGetVersion;
+use windows::Win32::System::EventLog::EVENTLOG_ERROR_TYPE;
+use windows::Win32::System::SystemInformation::OSVERSIONINFOW;
+
+/// MyDNS.JPのIPv4アドレス通知用URL。アカウントに通知先URLが設定されていない場合に使う。
+pub const IPV4_NOTIFY_URL: &str = "https://ipv4.mydns.jp/login.html";
+/// MyDNS.JPのIPv6アドレス通知用URL。アカウントに通知先URLが設定されていない場合に使う。
+pub const IPV6_NOTIFY_URL: &str = "https://ipv6.mydns.jp/login.html";
+/// 再試行の待ち時間の基準値。再試行のたびに2倍になる。
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+/// 再試行の待ち時間の上限。
+/// サーバーが `Retry-After` でこれより長い待ち時間を指定した場合は、再試行せずに次の定期通知に任せる。
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+/// `Retry-After` に従って通知を見送る時間の上限。誤った値で、いつまでも通知しなくなることを防ぐ。
+const RETRY_AFTER_MAX_COOLDOWN: Duration = Duration::from_secs(6 * 60 * 60);
+/// `--user-agent-id` で付けられる識別子の最大文字数。
+pub const USER_AGENT_ID_MAX_LEN: usize = 64;
+/// 認証エラーがこの回数続いたら、誤った資格情報で送り続けないよう、アカウントの通知を自動で停止する。
+const AUTH_FAILURE_SUSPEND_THRESHOLD: u32 = 5;
+
+/// 通知に失敗したときの再試行の方針。
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// 最初の送信を含めた、1回の通知での最大試行回数。1の場合は再試行しない。
+    pub max_attempts: u32,
+    /// 再試行の待ち時間の基準値。
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// レジストリに保存された設定から再試行の方針を作成します。
+    /// 設定を読み込めない場合は既定値を使います。
+    pub fn load() -> Self {
+        RetryPolicy {
+            max_attempts: load_settings().unwrap_or_default().retry_max_attempts,
+            base_delay: RETRY_BASE_DELAY,
+        }
+    }
+
+    /// `attempt` 回目の試行に失敗した後、次の試行までの待ち時間を求めます。
+    ///
+    /// 待ち時間は試行のたびに2倍になり（指数バックオフ）、その後半分の範囲でランダムに揺らします（ジッター）。
+    /// 複数のマシンやアカウントの再試行が同じ時刻に集中しないようにするためです。
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+            .min(RETRY_MAX_DELAY);
+        delay / 2 + random_duration(delay / 2)
+    }
+}
+
+/// 通知に使うHTTPクライアントを作成します。
+///
+/// `proxy_url` は全体の設定のプロキシURLです。空の場合は、WinHTTPやシステムの設定に従います。
+/// クライアントは内部で接続を使い回すため、1回の通知処理の中で共有します。
+pub fn build_client(proxy_url: &str) -> reqwest::Result<Client> {
+    client_builder(proxy_url)?.build()
+}
+
+/// プロキシとタイムアウト、User-Agentを設定した、HTTPクライアントのビルダーを作成します。
+///
+/// タイムアウトは `--request-timeout` の設定に従い、応答のない接続で通知処理が止まり続けないようにします。
+fn client_builder(proxy_url: &str) -> reqwest::Result<ClientBuilder> {
+    let settings = load_settings().unwrap_or_default();
+    let builder = Client::builder()
+        .timeout(Duration::from_secs(u64::from(
+            settings.request_timeout_seconds,
+        )))
+        .user_agent(user_agent(&settings.user_agent_id));
+    Ok(match resolve_proxy(proxy_url) {
+        ProxyChoice::Direct => builder.no_proxy(),
+        ProxyChoice::Url { url, bypass } => builder
+            .proxy(Proxy::all(url)?.no_proxy(bypass.as_deref().and_then(NoProxy::from_string))),
+        ProxyChoice::System => builder,
+    })
+}
+
+/// HTTPリクエストに付けるUser-Agentを返します。
+///
+/// `mydns-adapter-win11/1.2.3 (Windows NT 10.0.26100; x86_64)` のように、アプリケーションの名前とバージョン、
+/// OSのバージョン、CPUのアーキテクチャを含めます。MyDNS.JPの運営者や自宅のプロキシが、このクライアントからの
+/// リクエストを見分けられるようにするためです。`id`（`--user-agent-id`）が空でなければ、末尾に付けます。
+pub fn user_agent(id: &str) -> String {
+    let base = format!(
+        "{}/{} (Windows NT {}; {})",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        os_version(),
+        std::env::consts::ARCH
+    );
+    if id.is_empty() {
+        base
+    } else {
+        format!("{} {}", base, id)
+    }
+}
+
+/// User-Agentに付けられる識別子かどうかを判定します。
+///
+/// HTTPヘッダーの値にそのまま使えるよう、空白を含むASCIIの表示可能な文字だけを、
+/// `USER_AGENT_ID_MAX_LEN` 文字まで受け付けます。
+pub fn is_valid_user_agent_id(id: &str) -> bool {
+    id.len() <= USER_AGENT_ID_MAX_LEN && id.chars().all(|c| c.is_ascii_graphic() || c == ' ')
+}
+
+/// OSのバージョンを `10.0.26100` の形式で返します。
+///
+/// `GetVersionEx` は互換性のためにバージョンを偽ることがあるため、`RtlGetVersion` で求めます。
+fn os_version() -> String {
+    let mut info = OSVERSIONINFOW {
+        dwOSVersionInfoSize: std::mem::size_of::<OSVERSIONINFOW>() as u32,
+        ..Default::default()
+    };
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // `info` は呼び出しの間だけ有効な、この関数のローカル変数を指している。
+    if unsafe { RtlGetVersion(&mut info) }.is_err() {
+        return "unknown".to_string();
+    }
+    format!(
+        "{}.{}.{}",
+        info.dwMajorVersion, info.dwMinorVersion, info.dwBuildNumber
+    )
+}
+
+/// アカウントに送信元のインターフェイスが設定されている場合に、そのアドレスから送信するクライアントを作成します。
+///
+/// 送信元が設定されていない（またはこのプロトコルには使わない）場合は `None` を返し、共有のクライアントを使います。
+/// インターフェイスのアドレスは通知のたびに求め直すため、アドレスの変更にも追従します。
+fn bound_client(config: &Config, ipv6: bool) -> Result<Option<Client>, NotifyFailure> {
+    let network_failure = |detail: String| NotifyFailure {
+        kind: FailureKind::Network,
+        detail,
+        retry_after: None,
+    };
+    let Some(local) =
+        resolve_bind_address(&config.bind_interface, ipv6).map_err(network_failure)?
+    else {
+        return Ok(None);
+    };
+    // アカウント個別のプロキシがあればそれを、なければ全体の設定を使います。
+    let proxy_url = if config.proxy_url.is_empty() {
+        load_settings().unwrap_or_default().proxy_url
+    } else {
+        config.proxy_url.clone()
+    };
+    client_builder(&proxy_url)
+        .and_then(|builder| builder.local_address(local).build())
+        .map(Some)
+        .map_err(|e| network_failure(e.to_string()))
+}
 
 /// 「即時通知モード」を処理します。
 ///
-/// この関数は `--notify`, `--ipv4`, `--ipv6` いずれかのフラグが指定されたときに呼び出されます。
+/// この関数は `notify` サブコマンド（または旧形式の `--notify`, `--ipv4`, `--ipv6`）で呼び出されます。
 /// すべての設定を読み込み、各アカウントに対して一度だけ通知処理を実行します。
 /// 通知を行うかどうかは、コマンドラインフラグと各アカウントの設定の両方が有効である必要があります。
+/// ネットワークに接続されていない場合は、通知せずに通信エラーとして終了します。
 ///
-/// # 引数
-/// * `use_ipv4` - `--notify` または `--ipv4` が指定された場合に `true`。
-/// * `use_ipv6` - `--notify` または `--ipv6` が指定された場合に `true`。
-pub fn notify_now_mode(use_ipv4: bool, use_ipv6: bool) -> io::Result<()> {
-    log_info(get_msg_en("log_notify_start"));
-    let configs = load_all_configs().unwrap_or_else(|_| Vec::new());
+/// `options.accounts` に見つからないMasterIDが含まれる場合は、どのアカウントも通知せずに終了します。
+/// `options.dry_run` が `true` の場合は、送信するリクエストを表示するだけで、MyDNS.JPには接続しません。
+///
+/// 開始から完了までのログの行には、この実行の相関IDを付けます。
+///
+/// # 戻り値
+/// 通知の結果に応じたプロセスの終了の理由。
+pub fn notify_now_mode(options: &NotifyOptions) -> Result<ExitReason, AdapterError> {
+    Runtime::new()?.block_on(with_round_id(new_round_id(), notify_now(options)))
+}
+
+/// `notify` サブコマンドで指定された、即時通知の方法。
+#[derive(Clone, Debug, Default)]
+pub struct NotifyOptions {
+    /// IPv4アドレスを通知するかどうか。
+    pub ipv4: bool,
+    /// IPv6アドレスを通知するかどうか。
+    pub ipv6: bool,
+    /// ネットワークへの接続の確認を省き、自動で停止されたアカウントも通知するかどうか（`--force`）。
+    pub force: bool,
+    /// 通知するアカウントのMasterID（`--account`）。空の場合はすべてのアカウントを通知する。
+    pub accounts: Vec<String>,
+    /// 送信するリクエストを表示するだけで、実際には送信しないかどうか（`--dry-run`）。
+    pub dry_run: bool,
+}
+
+/// `notify_now_mode` の本体。すべてのアカウント（または指定されたアカウント）に一度だけ通知します。
+async fn notify_now(options: &NotifyOptions) -> Result<ExitReason, AdapterError> {
+    log_info(get_msg_log(MessageKey::LogNotifyStart));
+    let mut configs = load_all_configs().unwrap_or_else(|_| Vec::new());
+    if !options.accounts.is_empty() {
+        let accounts: Vec<&str> = options.accounts.iter().map(|id| id.trim()).collect();
+        // 打ち間違えたMasterIDに気付けるよう、見つからないものがあれば何も通知しない。
+        if let Some(missing) = accounts
+            .iter()
+            .find(|id| !configs.iter().any(|c| c.master_id == **id))
+        {
+            outln!(
+                "{}",
+                format_msg!(MessageKey::AccountNotFoundFmt, id = missing)
+            );
+            log_error(&format_msg_log!(
+                MessageKey::LogNotifyAccountNotFoundFmt,
+                id = missing
+            ));
+            return Ok(ExitReason::ConfigMissing);
+        }
+        configs.retain(|c| accounts.contains(&c.master_id.as_str()));
+    }
     if configs.is_empty() {
         // 設定されているアカウントがなければ、何もせずに終了します。
-        log_error(get_msg_en("log_config_missing"));
-        return Ok(());
+        log_error(get_msg_log(MessageKey::LogConfigMissing));
+        return Ok(ExitReason::ConfigMissing);
+    }
+
+    let settings = load_settings().unwrap_or_default();
+    let client = build_client(&settings.proxy_url)?;
+    let configs = configs
+        .into_iter()
+        .map(|config| {
+            // Consider settings file values as well
+            // この通知実行のための一時的な設定を作成します。
+            // 通知が実行されるのは、コマンドラインフラグが有効で、かつ
+            // アカウント自体の設定も有効になっている場合のみです。
+            let mut temp_config = config.clone();
+            temp_config.ipv4_notify = options.ipv4 && config.ipv4_notify;
+            temp_config.ipv6_notify = options.ipv6 && config.ipv6_notify;
+            temp_config
+        })
+        .collect::<Vec<_>>();
+    if options.dry_run {
+        print_dry_run(&client, &configs, options.force).await;
+        log_info(get_msg_log(MessageKey::LogNotifyDryRunFinish));
+        return Ok(ExitReason::Success);
+    }
+    if !options.force && !is_network_available(&client).await {
+        // 回線が切れている場合は、アカウントごとの接続エラーを記録せずに終了します。
+        log_info(get_msg_log(MessageKey::LogNotifySkippedOffline));
+        return Ok(ExitReason::NetworkFailure);
     }
+    let report = notify_all(&client, configs.clone(), options.force).await;
+    // コンソールから実行した場合は、失敗が続いているアカウントをトースト通知でも知らせる。
+    toast_failure_streaks(&configs);
+    send_failure_alerts(&configs).await;
+    // レコードの失効が近づいているアカウントを警告し、段階が上がった場合はトースト通知でも知らせる。
+    toast_expiry_alerts(&check_expiry_warnings(&client, &configs).await);
+
+    log_info(get_msg_log(MessageKey::LogNotifyFinish));
+    report.print_summary();
+    Ok(report.exit_reason())
+}
 
-    let client = Client::new();
+/// 即時通知で送信するリクエストを、アカウントとプロトコルごとに表示します（`notify --dry-run`）。
+///
+/// 送信元のインターフェイスのアドレスや、汎用のサービスの更新URLに埋め込む現在のアドレスは、
+/// 実際の通知と同じ方法で求めます（確認サービスには問い合わせます）。MyDNS.JPや更新URLには接続しません。
+/// パスワードは表示せず、伏せ字に置き換えます。
+async fn print_dry_run(client: &Client, configs: &[Config], force: bool) {
+    outln!("{}", get_msg(MessageKey::DryRunTitle));
+    let mut planned = 0;
     for config in configs {
-        // Consider settings file values as well
-        // この通知実行のための一時的な設定を作成します。
-        // 通知が実行されるのは、コマンドラインフラグが有効で、かつ
-        // アカウント自体の設定も有効になっている場合のみです。
-        let mut temp_config = config.clone();
-        temp_config.ipv4_notify = use_ipv4 && config.ipv4_notify;
-        temp_config.ipv6_notify = use_ipv6 && config.ipv6_notify;
+        let id = &config.master_id;
+        if !force && load_suspended(id).is_some() {
+            outln!(
+                "{}",
+                format_msg!(
+                    MessageKey::DryRunSkippedFmt,
+                    id = id,
+                    proto = "-",
+                    reason = get_msg(MessageKey::DryRunSuspended)
+                )
+            );
+            continue;
+        }
+        let login = config.provider == Provider::MyDns && config.method != NotifyMethod::Http;
+        let auth = if login {
+            format_msg!(MessageKey::DryRunLoginAuthFmt, user = id)
+        } else if config.provider == Provider::MyDns || !config.password.is_empty() {
+            format_msg!(MessageKey::DryRunBasicAuthFmt, user = id)
+        } else {
+            get_msg(MessageKey::DryRunNoAuth).to_string()
+        };
+        for ipv6 in [false, true] {
+            if !(if ipv6 {
+                config.ipv6_notify
+            } else {
+                config.ipv4_notify
+            }) {
+                continue;
+            }
+            let proto = if ipv6 { "IPv6" } else { "IPv4" };
+            match plan_request(client, config, ipv6).await {
+                Ok(Some((url, local))) => {
+                    planned += 1;
+                    outln!(
+                        "{}",
+                        format_msg!(
+                            MessageKey::DryRunRequestFmt,
+                            id = id,
+                            proto = proto,
+                            method = if login { "LOGIN" } else { "GET" },
+                            url = url,
+                            auth = auth
+                        )
+                    );
+                    if let Some(local) = local {
+                        outln!("{}", format_msg!(MessageKey::DryRunBindFmt, addr = local));
+                    }
+                }
+                Ok(None) => outln!(
+                    "{}",
+                    format_msg!(
+                        MessageKey::DryRunSkippedFmt,
+                        id = id,
+                        proto = proto,
+                        reason = get_msg(MessageKey::DryRunPrivateIpv4)
+                    )
+                ),
+                Err(e) => outln!(
+                    "{}",
+                    format_msg!(
+                        MessageKey::DryRunSkippedFmt,
+                        id = id,
+                        proto = proto,
+                        reason = e
+                    )
+                ),
+            }
+        }
+    }
+    if planned == 0 {
+        outln!("{}", get_msg(MessageKey::DryRunNone));
+    }
+}
 
-        perform_notification(&client, &temp_config);
+/// 1つのアカウントの1つのプロトコルで送信するリクエストのURLと、送信元のアドレスを求めます。
+///
+/// URLの組み立ては `send_update` と同じです。URLに含まれるパスワードは伏せ字に置き換えます。
+///
+/// # 戻り値
+/// 送信するURLと、送信元のインターフェイスが設定されていればそのアドレス。
+/// 確認サービスで求めたアドレスがプライベートのアドレスで、通知を見送る場合は `None`。
+async fn plan_request(
+    client: &Client,
+    config: &Config,
+    ipv6: bool,
+) -> Result<Option<(String, Option<IpAddr>)>, String> {
+    let local = resolve_bind_address(&config.bind_interface, ipv6)?;
+    let url = account_endpoint(config, ipv6);
+    let url = if config.provider == Provider::MyDns {
+        // 通知と同じく、送信元のアドレスがCGNATやプライベートのアドレスなら見送る。確認できなければ通知する。
+        if !ipv6 && load_settings().unwrap_or_default().skip_private_ipv4 {
+            let bound = bound_client(config, false).map_err(|e| e.detail)?;
+            if let Ok(addr) = lookup_global_address(bound.as_ref().unwrap_or(client), false).await
+                && non_public_ipv4_range(&addr).is_some()
+            {
+                return Ok(None);
+            }
+        }
+        url.to_string()
+    } else {
+        let bound = bound_client(config, ipv6).map_err(|e| e.detail)?;
+        let client = bound.as_ref().unwrap_or(client);
+        let ip = if url.contains("{ip}") {
+            let addr = lookup_global_address(client, ipv6)
+                .await
+                .map_err(|e| format_msg_log!(MessageKey::LogCheckIpFailedFmt, error = e))?;
+            if non_public_ipv4_range(&addr).is_some()
+                && load_settings().unwrap_or_default().skip_private_ipv4
+            {
+                return Ok(None);
+            }
+            addr.to_string()
+        } else {
+            String::new()
+        };
+        format_template(url, &[("ip", &ip), ("host", &config.hostname)])
+    };
+    let url = if config.password.is_empty() {
+        url
+    } else {
+        url.replace(&config.password, "********")
+    };
+    Ok(Some((url, local)))
+}
+
+/// 「認証情報の確認モード」を処理します。
+///
+/// 指定されたアカウントだけで1回だけ通知リクエストを送信し、応答から認証情報が正しいかを判定して表示します。
+/// 確認のためのリクエストも通常の通知としてMyDNS.JPに受け付けられますが、再試行は行わず、
+/// 実行時状態や利用統計にも記録しません。
+///
+/// # 戻り値
+/// 確認の結果に応じたプロセスの終了の理由。
+pub fn test_credentials_mode(master_id: &str) -> Result<ExitReason, AdapterError> {
+    let configs = load_all_configs().unwrap_or_else(|_| Vec::new());
+    let Some(config) = configs.into_iter().find(|c| c.master_id == master_id) else {
+        outln!(
+            "{}",
+            format_msg!(MessageKey::AccountNotFoundFmt, id = master_id)
+        );
+        return Ok(ExitReason::ConfigMissing);
+    };
+
+    // アカウント個別のプロキシがあればそれを、なければ全体の設定を使います。
+    let proxy_url = if config.proxy_url.is_empty() {
+        load_settings().unwrap_or_default().proxy_url
+    } else {
+        config.proxy_url.clone()
+    };
+    let client = build_client(&proxy_url)?;
+    // IPv4の通知が無効なアカウントでは、IPv6の通知先で確認します。
+    let ipv6 = config.ipv6_notify && !config.ipv4_notify;
+    let url = account_endpoint(&config, ipv6);
+
+    outln!(
+        "{}",
+        format_msg!(MessageKey::TestSendingFmt, id = master_id, url = url)
+    );
+    let result = Runtime::new()?.block_on(send_update(&client, url, ipv6, &config));
+    match result {
+        Ok(addr) => {
+            let addr = addr.map_or_else(
+                || get_msg(MessageKey::NotSet).to_string(),
+                |a| a.to_string(),
+            );
+            outln!("{}", format_msg!(MessageKey::TestOkFmt, addr = addr));
+            Ok(ExitReason::Success)
+        }
+        Err(failure) if failure.kind == FailureKind::Auth => {
+            outln!(
+                "{}",
+                format_msg!(MessageKey::TestAuthFailedFmt, detail = failure.detail)
+            );
+            Ok(ExitReason::AuthFailure)
+        }
+        Err(failure) => {
+            outln!(
+                "{}",
+                format_msg!(
+                    MessageKey::TestInconclusiveFmt,
+                    kind = get_msg(failure.kind.label_key()),
+                    detail = failure.detail
+                )
+            );
+            Ok(ExitReason::NetworkFailure)
+        }
+    }
+}
+
+/// 複数のアカウントの通知を並行して実行し、すべて完了するまで待ちます。
+///
+/// 同時に通知するアカウントは `--concurrency` で設定した数までとし、残りのアカウントは空きが出るまで待たせます。
+/// アカウントが多い場合に、MyDNS.JPやプロキシへ一度に大量の接続を張らないようにするためです。
+///
+/// アカウントに個別のプロキシが設定されている場合は、そのアカウント専用のクライアントを作成します。
+/// アカウントにping URLが設定されている場合は、通知に成功した後にpingを送ります。
+/// 認証エラーが続いて自動で停止されたアカウントは通知せず、認証エラーとして扱います。
+/// `force` が `true` の場合は、停止されたアカウントも通知します。
+/// 各アカウントの通知は別のタスクで実行するため、呼び出し元の相関IDを引き継ぎます。
+/// この関数が返す前に中断（`abort`）された場合、実行中の通知もすべて中断されます。
+///
+/// # 戻り値
+/// アカウントごとの通知の結果（MasterIDの順）。
+pub async fn notify_all(client: &Client, configs: Vec<Config>, force: bool) -> NotifyReport {
+    let mut accounts = Vec::new();
+    let mut tasks = JoinSet::new();
+    // タスクが異常終了した場合に、どのアカウントの通知だったかを知るための対応。
+    let mut task_ids = HashMap::new();
+    let limit = load_settings().unwrap_or_default().notify_concurrency;
+    let permits = Arc::new(Semaphore::new(limit.max(1) as usize));
+    for config in configs {
+        if !force && let Some(since) = load_suspended(&config.master_id) {
+            log_detail(&format!(
+                "[{}] {}",
+                config.master_id,
+                format_msg_log!(
+                    MessageKey::LogAccountSuspendedSkippedFmt,
+                    id = config.master_id,
+                    time = since.format("%Y-%m-%d %H:%M:%S")
+                )
+            ));
+            accounts.push(AccountReport {
+                skipped: Some(SkipReason::Suspended),
+                ..AccountReport::new(&config.master_id, Err(FailureKind::Auth))
+            });
+            continue;
+        }
+        let client = if config.proxy_url.is_empty() {
+            client.clone()
+        } else {
+            match build_client(&config.proxy_url) {
+                Ok(client) => client,
+                Err(e) => {
+                    // プロキシURLの誤りは設定の問題であるため、このアカウントの通知は行わない。
+                    log_error(&format!(
+                        "[{}] {}",
+                        config.master_id,
+                        format_msg_log!(MessageKey::LogProxyInvalidFmt, error = e)
+                    ));
+                    // 通信できないため、通信エラーとして扱う。
+                    accounts.push(AccountReport {
+                        skipped: Some(SkipReason::InvalidProxy),
+                        ..AccountReport::new(&config.master_id, Err(FailureKind::Network))
+                    });
+                    continue;
+                }
+            }
+        };
+        let permits = Arc::clone(&permits);
+        let master_id = config.master_id.clone();
+        let task = tasks.spawn(inherit_round_id(async move {
+            // セマフォは閉じないため、許可の取得に失敗することはない。
+            let _permit = permits.acquire_owned().await;
+            let report = perform_notification(&client, &config).await;
+            if report.result.is_ok() && !config.ping_url.is_empty() {
+                send_ping(&client, Some(&config.master_id), &config.ping_url).await;
+            }
+            report
+        }));
+        task_ids.insert(task.id(), master_id);
+    }
+    while let Some(joined) = tasks.join_next_with_id().await {
+        match joined {
+            Ok((_, report)) => accounts.push(report),
+            // タスクが異常終了した場合は、結果がわからないため失敗として扱う。
+            Err(e) => accounts.push(AccountReport {
+                skipped: Some(SkipReason::Aborted),
+                ..AccountReport::new(
+                    task_ids.get(&e.id()).map_or("", String::as_str),
+                    Err(FailureKind::Network),
+                )
+            }),
+        }
+    }
+    accounts.sort_by(|a, b| a.master_id.cmp(&b.master_id));
+    NotifyReport { accounts }
+}
+
+/// 1回の通知処理（`notify` や、サービスの1回の通知ラウンド）の結果。
+#[derive(Clone, Debug, Default)]
+pub struct NotifyReport {
+    /// アカウントごとの結果。
+    pub accounts: Vec<AccountReport>,
+}
+
+/// 1つのアカウントの通知の結果。
+#[derive(Clone, Debug)]
+pub struct AccountReport {
+    /// アカウントのMasterID。
+    pub master_id: String,
+    /// 通知したプロトコルごとの結果。通知しなかった場合は空。
+    pub protocols: Vec<ProtocolReport>,
+    /// アカウント全体の結果。失敗した場合は、その分類（認証エラーがあればそれを優先）。
+    pub result: Result<(), FailureKind>,
+    /// 通知しなかった、または結果がわからなかった理由。
+    pub skipped: Option<SkipReason>,
+}
+
+/// 1つのプロトコルの通知の結果。
+#[derive(Clone, Debug)]
+pub struct ProtocolReport {
+    /// プロトコルの名前（`IPv4` または `IPv6`）。
+    pub protocol: &'static str,
+    /// 通知の結果。成功した場合は、受け付けられたアドレス（わかった場合）。
+    pub result: Result<Option<IpAddr>, FailureKind>,
+    /// 失敗した場合の詳細（エラーメッセージやステータスコード）。成功した場合は空。
+    pub detail: String,
+    /// 再試行を含めて送信した回数。
+    pub attempts: u32,
+    /// 再試行の待ち時間を含めた、通知にかかった時間。
+    pub latency: Duration,
+}
+
+/// アカウントを通知しなかった、または結果がわからなかった理由。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    /// 認証エラーが続いたため、通知が停止されている。
+    Suspended,
+    /// アカウントのプロキシURLが正しくない。
+    InvalidProxy,
+    /// 通知のタスクが異常終了した。
+    Aborted,
+}
+
+impl AccountReport {
+    /// プロトコルごとの結果を持たない、アカウントの結果を作成します。
+    fn new(master_id: &str, result: Result<(), FailureKind>) -> Self {
+        AccountReport {
+            master_id: master_id.to_string(),
+            protocols: Vec::new(),
+            result,
+            skipped: None,
+        }
+    }
+}
+
+impl NotifyReport {
+    /// 通知に失敗したアカウントの数を返します。
+    pub fn failed(&self) -> usize {
+        self.accounts.iter().filter(|a| a.result.is_err()).count()
+    }
+
+    /// アカウントごとの結果から、プロセスの終了の理由を決定します。
+    ///
+    /// すべて失敗した場合は、設定の見直しが必要な認証エラーを、通信エラーより優先して報告します。
+    pub fn exit_reason(&self) -> ExitReason {
+        let failures: Vec<FailureKind> = self
+            .accounts
+            .iter()
+            .filter_map(|a| a.result.err())
+            .collect();
+        if failures.is_empty() {
+            ExitReason::Success
+        } else if failures.len() < self.accounts.len() {
+            ExitReason::PartialFailure
+        } else if failures.contains(&FailureKind::Auth) {
+            ExitReason::AuthFailure
+        } else {
+            ExitReason::NetworkFailure
+        }
     }
 
-    log_info(get_msg_en("log_notify_finish"));
-    Ok(())
+    /// アカウントとプロトコルごとの結果（結果の分類、所要時間、送信した回数、失敗の詳細）を表で表示します。
+    pub fn print_summary(&self) {
+        outln!("{}", get_msg(MessageKey::NotifySummaryTitle));
+        let width = self
+            .accounts
+            .iter()
+            .map(|a| a.master_id.chars().count())
+            .max()
+            .unwrap_or(0);
+        for account in &self.accounts {
+            let id = format!("{:<width$}", account.master_id);
+            if account.protocols.is_empty() {
+                let reason = match account.skipped {
+                    Some(SkipReason::Suspended) => MessageKey::NotifySummarySuspended,
+                    Some(SkipReason::InvalidProxy) => MessageKey::NotifySummaryInvalidProxy,
+                    Some(SkipReason::Aborted) => MessageKey::NotifySummaryAborted,
+                    None => MessageKey::NotifySummaryNoProtocol,
+                };
+                let reason = match account.result {
+                    Ok(()) => get_msg(reason).to_string(),
+                    Err(_) => paint(get_msg(reason), Color::Red),
+                };
+                outln!(
+                    "{}",
+                    format_msg!(
+                        MessageKey::NotifySummarySkippedFmt,
+                        id = id,
+                        reason = reason
+                    )
+                );
+                continue;
+            }
+            for protocol in &account.protocols {
+                let result = match protocol.result {
+                    Ok(_) => paint("OK", Color::Green),
+                    Err(kind) => paint(get_msg(kind.label_key()), Color::Red),
+                };
+                let detail = if protocol.detail.is_empty() {
+                    String::new()
+                } else {
+                    format!("  {}", protocol.detail)
+                };
+                outln!(
+                    "{}",
+                    format_msg!(
+                        MessageKey::NotifySummaryRowFmt,
+                        id = id,
+                        proto = protocol.protocol,
+                        result = result,
+                        ms = protocol.latency.as_millis(),
+                        attempts = protocol.attempts,
+                        detail = detail
+                    )
+                );
+            }
+        }
+        let failed = self.failed();
+        outln!(
+            "{}",
+            format_msg!(
+                MessageKey::NotifySummaryTotalFmt,
+                ok = self.accounts.len() - failed,
+                failed = failed
+            )
+        );
+    }
 }
 
 /// ひとつのアカウント設定に基づいて、IPアドレスの通知を実行します。
@@ -53,63 +752,830 @@ pub fn notify_now_mode(use_ipv4: bool, use_ipv6: bool) -> io::Result<()> {
 /// この関数は「即時通知モード」とWindowsサービスの定期実行ループの両方から呼び出されます。
 /// 引数で渡された`Config`構造体の`ipv4_notify`と`ipv6_notify`フラグをチェックし、
 /// 有効になっているプロトコルの通知処理をそれぞれ呼び出します。
-pub fn perform_notification(client: &Client, config: &Config) {
-    if config.ipv4_notify {
-        // IPv4通知が有効な場合
-        if let Err(e) = notify(
-            client,
-            "https://ipv4.mydns.jp/login.html",
-            &config.master_id,
-            &config.password,
-        ) {
-            let msg = get_msg_en("log_ipv4_fail_fmt").replace("{}", &e.to_string());
-            // エラーが発生した場合はログに記録します。
-            log_error(&format!("[{}] {}", config.master_id, msg));
+/// 通知の開始と結果は、共有の実行時状態（`state`モジュール）にも記録されます。
+/// 結果によって失敗・回復・アドレスの変化があった場合は、Webhookで知らせます。
+pub async fn perform_notification(client: &Client, config: &Config) -> AccountReport {
+    if !config.ipv4_notify && !config.ipv6_notify {
+        return AccountReport::new(&config.master_id, Ok(()));
+    }
+    let webhook = WebhookTracker::begin(&config.master_id);
+    let report = perform_notification_to(
+        client,
+        config,
+        account_endpoint(config, false),
+        account_endpoint(config, true),
+        &RetryPolicy::load(),
+    )
+    .await;
+    // 利用統計が有効な場合は、通知の結果を記録します。
+    record_notification(report.result.is_ok());
+    track_auth_failures(&config.master_id, report.result);
+    if let Some(webhook) = webhook {
+        webhook
+            .finish(client, &config.master_id, report.result.is_ok())
+            .await;
+    }
+    report
+}
+
+/// 続けて認証エラーになった回数を数え、`AUTH_FAILURE_SUSPEND_THRESHOLD` 回に達したらアカウントの通知を停止します。
+///
+/// 停止したことは、ログに加えてWindowsのイベントログにも記録します。
+/// 停止の記録を保存先に書き込めなかった場合は、次の認証エラーで停止し直します。
+/// 停止中のアカウントを `notify --force` で通知して成功した場合は、資格情報が直ったものとして停止を解除します。
+fn track_auth_failures(master_id: &str, result: Result<(), FailureKind>) {
+    if result.is_ok()
+        && load_suspended(master_id).is_some()
+        && save_suspended(master_id, None).is_ok()
+    {
+        log_info(&format!(
+            "[{}] {}",
+            master_id,
+            get_msg_log(MessageKey::LogAccountResumed)
+        ));
+    }
+    let mut count = 0;
+    let _ = update_account_state(master_id, |s| {
+        s.consecutive_auth_failures = if result == Err(FailureKind::Auth) {
+            s.consecutive_auth_failures + 1
+        } else {
+            0
+        };
+        count = s.consecutive_auth_failures;
+    });
+    if count < AUTH_FAILURE_SUSPEND_THRESHOLD {
+        return;
+    }
+    if let Err(e) = save_suspended(master_id, Some(Local::now())) {
+        log_error(&format!(
+            "[{}] {}",
+            master_id,
+            format_msg_log!(MessageKey::LogSuspendSaveFailedFmt, error = e)
+        ));
+        return;
+    }
+    let msg = format_msg_log!(
+        MessageKey::LogAccountSuspendedFmt,
+        id = master_id,
+        count = count
+    );
+    log_error(&format!("[{}] {}", master_id, msg));
+    report_event(EVENTLOG_ERROR_TYPE, &msg);
+}
+
+/// アカウントに設定された通知先URLを返します。設定されていない場合は `default` を返します。
+pub fn endpoint_or_default<'a>(custom: &'a str, default: &'a str) -> &'a str {
+    if custom.is_empty() { default } else { custom }
+}
+
+/// アカウントの、指定したプロトコルの通知先URLを返します。
+///
+/// MyDNS.JPのアカウントでは、設定されていなければMyDNS.JPのURLを返します。
+/// POP3/IMAP/FTPでのログインで通知するアカウントでは、ログインするサーバーを表すURLを返します。
+/// 汎用のサービスのアカウントでは更新URLのテンプレートを返し、IPv6の更新URLがなければIPv4の更新URLを使います。
+pub fn account_endpoint(config: &Config, ipv6: bool) -> &str {
+    if config.provider == Provider::MyDns
+        && let Some(endpoint) = login_endpoint(config.method, ipv6)
+    {
+        return endpoint;
+    }
+    match (config.provider, ipv6) {
+        (Provider::MyDns, false) => endpoint_or_default(&config.ipv4_url, IPV4_NOTIFY_URL),
+        (Provider::MyDns, true) => endpoint_or_default(&config.ipv6_url, IPV6_NOTIFY_URL),
+        (Provider::Generic, false) => &config.ipv4_url,
+        (Provider::Generic, true) => endpoint_or_default(&config.ipv6_url, &config.ipv4_url),
+    }
+}
+
+/// 通知先のURLを指定して、ひとつのアカウント設定に基づくIPアドレスの通知を実行します。
+///
+/// 通常は `perform_notification` を介して、アカウントの通知先URL（既定ではMyDNS.JPのURL）で呼び出されます。
+/// シミュレーションモードでは、ローカルの模擬サーバーのURLを指定して呼び出されます。
+/// 一時的な障害で失敗した通知は、`retry` に従ってプロトコルごとに再試行します。
+/// それでも失敗し、サーバーが `Retry-After`（429や503の応答）で待ち時間を指定していた場合は、
+/// その時刻まで実行時状態にバックオフ期限を記録し、サービスの以降の通知を見送らせます。
+/// アカウントにホスト名が設定されている場合は、通知の成功後にDNSの反映を確認します。
+///
+/// # 戻り値
+/// アカウントの通知の結果。有効なすべてのプロトコルで通知に成功した場合は、`result` が `Ok` になります。
+/// 失敗した場合は、その分類（認証エラーがあればそれを優先）が `result` に入ります。
+pub async fn perform_notification_to(
+    client: &Client,
+    config: &Config,
+    ipv4_url: &str,
+    ipv6_url: &str,
+    retry: &RetryPolicy,
+) -> AccountReport {
+    let mut report = AccountReport::new(&config.master_id, Ok(()));
+    if !config.ipv4_notify && !config.ipv6_notify {
+        // 通知するプロトコルがなければ、実行時状態も更新しません。
+        return report;
+    }
+
+    // 実行時状態に「通知中」であることを記録します。状態の書き込み失敗は通知を妨げません。
+    let _ = update_account_state(&config.master_id, |s| {
+        s.in_flight = true;
+        s.last_attempt = Some(Local::now());
+    });
+
+    let mut failures = Vec::new();
+    let mut failure_kinds = Vec::new();
+    // サーバーが指定した、次の通知まで待つべき時間（プロトコルごとの指定のうち長いほう）。
+    let mut cooldown: Option<Duration> = None;
+    // MyDNS.JPが受け付けたアドレス。DNSの反映の確認に使う。
+    let mut notified = Vec::new();
+    for (enabled, url, ipv6, fail_key) in [
+        (
+            config.ipv4_notify,
+            ipv4_url,
+            false,
+            MessageKey::LogIpv4FailFmt,
+        ),
+        (
+            config.ipv6_notify,
+            ipv6_url,
+            true,
+            MessageKey::LogIpv6FailFmt,
+        ),
+    ] {
+        if !enabled {
+            continue;
         }
+        let started = Instant::now();
+        let (result, attempts) = notify_with_retry(client, url, ipv6, config, retry).await;
+        let mut protocol = ProtocolReport {
+            protocol: if ipv6 { "IPv6" } else { "IPv4" },
+            result: Ok(None),
+            detail: String::new(),
+            attempts,
+            latency: started.elapsed(),
+        };
+        match result {
+            Ok(addr) => {
+                notified.extend(addr);
+                protocol.result = Ok(addr);
+            }
+            Err(e) => {
+                let msg = format_msg_log!(fail_key, error = e);
+                // エラーが発生した場合はログに記録します。
+                log_error(&format!("[{}] {}", config.master_id, msg));
+                failures.push(msg);
+                failure_kinds.push(e.kind);
+                cooldown = cooldown.max(e.cooldown());
+                protocol.result = Err(e.kind);
+                protocol.detail = e.detail;
+            }
+        }
+        report.protocols.push(protocol);
     }
-    if config.ipv6_notify {
-        // IPv6通知が有効な場合
-        if let Err(e) = notify(
-            client,
-            "https://ipv6.mydns.jp/login.html",
-            &config.master_id,
-            &config.password,
-        ) {
-            let msg = get_msg_en("log_ipv6_fail_fmt").replace("{}", &e.to_string());
-            // エラーが発生した場合はログに記録します。
-            log_error(&format!("[{}] {}", config.master_id, msg));
+
+    // 通知の結果を実行時状態に記録します。
+    let succeeded = failures.is_empty();
+    let now = Local::now();
+    let last_result = if succeeded {
+        "OK".to_string()
+    } else {
+        failures.join("; ")
+    };
+    let backoff_until = cooldown.and_then(|c| TimeDelta::from_std(c).ok().map(|c| now + c));
+    if let (Some(cooldown), Some(until)) = (cooldown, backoff_until) {
+        log_warn(&format!(
+            "[{}] {}",
+            config.master_id,
+            format_msg_log!(
+                MessageKey::LogRetryAfterCooldownFmt,
+                seconds = cooldown.as_secs(),
+                until = until.format("%Y-%m-%d %H:%M:%S")
+            )
+        ));
+    }
+    // 前回受け付けられたアドレスから変わったかどうか。利用統計のIPアドレスの変更回数に数える。
+    let mut ip_changed = false;
+    let _ = update_account_state(&config.master_id, |s| {
+        s.in_flight = false;
+        s.last_result = Some(last_result.clone());
+        s.backoff_until = backoff_until;
+        if succeeded {
+            s.last_success = Some(now);
+            s.expiry_warn_level = 0;
+            s.consecutive_failures = 0;
+            s.failing_since = None;
+            s.mail_alert_sent = false;
+            // 応答本文からアドレスがわからなかった場合は、前回の値を残す。
+            if !notified.is_empty() {
+                ip_changed = !s.last_ip.is_empty() && s.last_ip != notified;
+                s.last_ip = notified.clone();
+            }
+        } else {
+            s.consecutive_failures += 1;
+            s.failing_since.get_or_insert(now);
         }
+    });
+
+    if succeeded {
+        observe_success(&config.master_id, now);
+    }
+    if ip_changed {
+        record_ip_change();
+    }
+
+    // インターネットから届かないアドレスを通知した場合は、名前を引いても接続できないため警告します。
+    for addr in &notified {
+        if let Some(range) = non_public_ipv4_range(addr) {
+            log_warn(&format!(
+                "[{}] {}",
+                config.master_id,
+                format_msg_log!(
+                    MessageKey::LogPrivateIpv4NotifiedFmt,
+                    ip = addr,
+                    range = range
+                )
+            ));
+        }
+    }
+
+    // 成功した時刻とアドレスは、アカウントの保存先（レジストリのキーか設定ファイルの記録）にも記録します。
+    // サービスの実行アカウントによっては書き込めないため、失敗しても通知の結果には影響させません。
+    if succeeded && let Err(e) = save_last_success(&config.master_id, now, &notified) {
+        log_detail(&format!(
+            "[{}] {}",
+            config.master_id,
+            format_msg_log!(MessageKey::LogLastSuccessSaveFailedFmt, error = e)
+        ));
+    }
+
+    // 応答本文から受け付けたアドレスがわかった場合だけ、DNSの反映を確認します。
+    if succeeded && !config.hostname.is_empty() && !notified.is_empty() {
+        verify_dns(&config.master_id, &config.hostname, &notified).await;
     }
+    let result = match failure_kinds.first() {
+        None => Ok(()),
+        Some(_) if failure_kinds.contains(&FailureKind::Auth) => Err(FailureKind::Auth),
+        Some(&kind) => Err(kind),
+    };
+    report.result = result;
+
+    // 失敗した時刻と内容も、ログを探さずに確認できるようアカウントの保存先に記録します。
+    if let Err(kind) = result
+        && let Err(e) = save_last_error(&config.master_id, now, kind.code(), &last_result)
+    {
+        log_detail(&format!(
+            "[{}] {}",
+            config.master_id,
+            format_msg_log!(MessageKey::LogLastErrorSaveFailedFmt, error = e)
+        ));
+    }
+    report
 }
 
-/// MyDNS.JPのエンドポイントに単一の通知リクエストを送信します。
+/// 再試行の方針に従って、通知リクエストを成功するまで送信します。
+///
+/// `ipv6` は通知するプロトコルを表します。再試行を含め、すべての試行をプロトコルの名前とともに履歴に記録します。
+/// 再試行するのは、接続エラーやタイムアウト、408/429/5xx、サーバーのエラー表示などの一時的な障害の場合だけです。
+/// 認証エラーなどは何度送っても結果が変わらないため、すぐに諦めます。
+/// サーバーが `Retry-After` を返した場合は、バックオフの代わりにその待ち時間に従います。
+///
+/// # 戻り値
+/// 最後の試行の結果と、試行した回数。成功した場合は、MyDNS.JPが受け付けたアドレス（わかった場合）。
+async fn notify_with_retry(
+    client: &Client,
+    url: &str,
+    ipv6: bool,
+    config: &Config,
+    retry: &RetryPolicy,
+) -> (Result<Option<IpAddr>, NotifyFailure>, u32) {
+    let protocol = if ipv6 { "IPv6" } else { "IPv4" };
+    let mut attempt = 1;
+    loop {
+        let time = Local::now();
+        let started = Instant::now();
+        let result = send_update(client, url, ipv6, config).await;
+        let latency = started.elapsed();
+        observe_attempt(&config.master_id, protocol, result.is_ok(), latency);
+        record_attempt(HistoryEntry {
+            time,
+            master_id: config.master_id.clone(),
+            protocol: protocol.to_string(),
+            result: match &result {
+                Ok(_) => "OK".to_string(),
+                // 送信しなかったことが分かるよう、失敗とは区別して記録する。
+                Err(failure) if failure.kind == FailureKind::Skipped => {
+                    format!("SKIPPED ({})", failure.detail)
+                }
+                Err(failure) => failure.to_string(),
+            },
+            latency,
+        });
+        let failure = match result {
+            Ok(addr) => return (Ok(addr), attempt),
+            Err(failure) => failure,
+        };
+        if attempt >= retry.max_attempts || !failure.is_transient() {
+            return (Err(failure), attempt);
+        }
+        let delay = match failure.retry_after {
+            // サーバーの指定が長すぎる場合は、この通知での再試行を諦める。
+            Some(after) if after > RETRY_MAX_DELAY => return (Err(failure), attempt),
+            Some(after) => after,
+            None => retry.backoff(attempt),
+        };
+        log_warn(&format!(
+            "[{}] {}",
+            config.master_id,
+            format_msg_log!(
+                MessageKey::LogNotifyRetryFmt,
+                url = url,
+                attempt = attempt,
+                max = retry.max_attempts,
+                delay = format!("{:.1}", delay.as_secs_f64()),
+                error = failure
+            )
+        ));
+        time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// 通知の失敗の分類。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailureKind {
+    /// 接続エラーやタイムアウトなど、通信上の障害。
+    Network,
+    /// 認証の失敗（MasterIDまたはパスワードの誤り）。
+    Auth,
+    /// サーバー側の障害（5xx、または応答本文中のエラー表示）。
+    Server,
+    /// リクエストが多すぎるとしてサーバーに拒否された（429）。
+    RateLimited,
+    /// 上記以外のHTTPエラー。
+    Http,
+    /// 通知するIPv4アドレスがCGNATやプライベートのアドレスだったため、設定に従って送信しなかった。
+    Skipped,
+}
+
+impl FailureKind {
+    /// HTTPステータスコードから失敗の分類を決定します。
+    fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => FailureKind::Auth,
+            StatusCode::TOO_MANY_REQUESTS => FailureKind::RateLimited,
+            StatusCode::REQUEST_TIMEOUT => FailureKind::Network,
+            s if s.is_server_error() => FailureKind::Server,
+            _ => FailureKind::Http,
+        }
+    }
+
+    /// レジストリに記録する、分類を表す固定の文字列。
+    pub fn code(self) -> &'static str {
+        match self {
+            FailureKind::Network => "network",
+            FailureKind::Auth => "auth",
+            FailureKind::Server => "server",
+            FailureKind::RateLimited => "rate_limited",
+            FailureKind::Http => "http",
+            FailureKind::Skipped => "skipped",
+        }
+    }
+
+    /// `code` が返す文字列から分類を復元します。未知の文字列の場合は `None` を返します。
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "network" => Some(FailureKind::Network),
+            "auth" => Some(FailureKind::Auth),
+            "server" => Some(FailureKind::Server),
+            "rate_limited" => Some(FailureKind::RateLimited),
+            "http" => Some(FailureKind::Http),
+            "skipped" => Some(FailureKind::Skipped),
+            _ => None,
+        }
+    }
+
+    /// ログや画面に表示する分類名のメッセージキー。
+    pub fn label_key(self) -> MessageKey {
+        match self {
+            FailureKind::Network => MessageKey::NotifyKindNetwork,
+            FailureKind::Auth => MessageKey::NotifyKindAuth,
+            FailureKind::Server => MessageKey::NotifyKindServer,
+            FailureKind::RateLimited => MessageKey::NotifyKindRateLimited,
+            FailureKind::Http => MessageKey::NotifyKindHttp,
+            FailureKind::Skipped => MessageKey::NotifyKindSkipped,
+        }
+    }
+}
+
+/// 1回の通知リクエストの失敗。
+struct NotifyFailure {
+    /// 失敗の分類。
+    kind: FailureKind,
+    /// 失敗の詳細（エラーメッセージやステータスコード）。
+    detail: String,
+    /// サーバーが `Retry-After` ヘッダーで指定した待ち時間。
+    retry_after: Option<Duration>,
+}
+
+impl NotifyFailure {
+    /// 時間をおいて再試行すれば成功する見込みのある、一時的な障害かどうかを判定します。
+    fn is_transient(&self) -> bool {
+        matches!(
+            self.kind,
+            FailureKind::Network | FailureKind::Server | FailureKind::RateLimited
+        )
+    }
+
+    /// サーバーが `Retry-After` で指定した、次の通知まで待つべき時間を返します。
+    ///
+    /// 一時的な障害（429や503など）の場合だけ、`RETRY_AFTER_MAX_COOLDOWN` を上限として返します。
+    fn cooldown(&self) -> Option<Duration> {
+        self.retry_after
+            .filter(|after| self.is_transient() && !after.is_zero())
+            .map(|after| after.min(RETRY_AFTER_MAX_COOLDOWN))
+    }
+}
+
+impl fmt::Display for NotifyFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({})",
+            get_msg_log(self.kind.label_key()),
+            self.detail
+        )
+    }
+}
+
+/// 更新サービスの応答本文から読み取った通知の結果。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum BodyVerdict {
+    /// 通知が受け付けられたことを示す表示があった。
+    Accepted,
+    /// 認証に失敗したことを示す表示があった。
+    AuthFailed,
+    /// サーバー側のエラーを示す表示があった。
+    ServerError,
+    /// 更新の要求そのものが拒否されたことを示す表示があった（ホスト名の誤りなど）。
+    Rejected,
+    /// 既知の表示が見つからなかった。
+    Unrecognized,
+}
+
+/// 応答本文中で認証の失敗を示す表示（小文字で比較する）。
+const BODY_AUTH_FAILURE_MARKERS: &[&str] = &[
+    "login failed",
+    "login error",
+    "authentication failed",
+    "authorization required",
+    "unauthorized",
+];
+/// 応答本文中でサーバー側のエラーを示す表示（小文字で比較する）。
+const BODY_SERVER_ERROR_MARKERS: &[&str] = &[
+    "server error",
+    "system error",
+    "maintenance",
+    "try again later",
+];
+/// 応答本文中で通知の受け付けを示す表示（小文字で比較する）。
+const BODY_SUCCESS_MARKERS: &[&str] = &["login ok", "notify ok", "remote address"];
+
+/// MyDNS.JPの応答本文（HTMLまたはテキスト）を解析し、通知の結果を判定します。
+///
+/// MyDNS.JPは、論理的な失敗でもHTTP 200を返すことがあるため、ステータスコードだけでは判断できません。
+/// 失敗を示す表示を成功を示す表示より優先して判定します。
+fn classify_body(body: &str) -> BodyVerdict {
+    let body = body.to_lowercase();
+    let contains_any = |markers: &[&str]| markers.iter().any(|m| body.contains(m));
+    if contains_any(BODY_AUTH_FAILURE_MARKERS) {
+        BodyVerdict::AuthFailed
+    } else if contains_any(BODY_SERVER_ERROR_MARKERS) {
+        BodyVerdict::ServerError
+    } else if contains_any(BODY_SUCCESS_MARKERS) {
+        BodyVerdict::Accepted
+    } else {
+        BodyVerdict::Unrecognized
+    }
+}
+
+/// 汎用のサービスの応答本文を、広く使われているDynDNS互換の応答コードで判定します。
+///
+/// No-IPなどは `good 192.0.2.1` のように、応答コードに続けてアドレスを返します。
+fn classify_generic_body(body: &str) -> BodyVerdict {
+    let code = body
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    match code.as_str() {
+        "good" | "nochg" => BodyVerdict::Accepted,
+        "badauth" => BodyVerdict::AuthFailed,
+        "911" | "dnserr" => BodyVerdict::ServerError,
+        "nohost" | "notfqdn" | "numhost" | "abuse" | "badagent" | "!donator" => {
+            BodyVerdict::Rejected
+        }
+        _ if code.starts_with("error") => BodyVerdict::Rejected,
+        _ => BodyVerdict::Unrecognized,
+    }
+}
+
+/// アカウントの通知先のサービスに合わせて、1回の通知リクエストを送信します。
+///
+/// 汎用のサービスでは、更新URLの `{ip}` を確認サービスで求めた現在のアドレスに、
+/// `{host}` をアカウントのホスト名に置き換えてから送信します。
+/// アドレスを確認できなかった場合は、再試行の対象となる通信エラーとして扱います。
+/// アカウントに送信元のインターフェイスが設定されている場合は、確認サービスへの問い合わせも含めて、
+/// そのインターフェイスのアドレスから送信します。
+/// MyDNS.JPのアカウントでPOP3/IMAP/FTPでのログインを選んでいる場合は、HTTPの代わりにログインで通知します。
+async fn send_update(
+    client: &Client,
+    url: &str,
+    ipv6: bool,
+    config: &Config,
+) -> Result<Option<IpAddr>, NotifyFailure> {
+    // MyDNS.JPは送信元のアドレスを登録するため、確認サービスで同じアドレスを求めて見送るかどうかを決める。
+    // 確認できなかった場合は、見送らずに通知する。
+    if config.provider == Provider::MyDns
+        && !ipv6
+        && load_settings().unwrap_or_default().skip_private_ipv4
+    {
+        let bound = bound_client(config, false)?;
+        if let Ok(addr) = lookup_global_address(bound.as_ref().unwrap_or(client), false).await
+            && let Some(skipped) = private_ipv4_skip(&addr)
+        {
+            return Err(skipped);
+        }
+    }
+    if config.provider == Provider::MyDns && config.method != NotifyMethod::Http {
+        return notify_by_login(url, ipv6, config).await;
+    }
+    let bound = bound_client(config, ipv6)?;
+    let client = bound.as_ref().unwrap_or(client);
+    if config.provider == Provider::MyDns {
+        return notify(client, url, config).await;
+    }
+    let address = if url.contains("{ip}") {
+        match lookup_global_address(client, ipv6).await {
+            // CGNATやプライベートのアドレスは、設定によっては通知せずに見送る。
+            Ok(addr) if let Some(skipped) = private_ipv4_skip(&addr) => return Err(skipped),
+            Ok(addr) => Some(addr),
+            Err(e) => {
+                return Err(NotifyFailure {
+                    kind: FailureKind::Network,
+                    detail: format_msg_log!(MessageKey::LogCheckIpFailedFmt, error = e),
+                    retry_after: None,
+                });
+            }
+        }
+    } else {
+        None
+    };
+    let ip = address.map(|a| a.to_string()).unwrap_or_default();
+    let url = format_template(url, &[("ip", &ip), ("host", &config.hostname)]);
+    // 応答本文にアドレスがなければ、送信したアドレスが受け付けられたものとみなす。
+    Ok(notify(client, &url, config).await?.or(address))
+}
+
+/// 確認サービスで求めたIPv4アドレスがCGNATやプライベートのアドレスで、設定（`--skip-private-ipv4`）により
+/// 通知を見送る場合は、見送りを表す失敗を返します。
+///
+/// 見送りは失敗として扱い、最終成功時刻やキープアライブの起点を進めず、レコードの失効の警告も止めません。
+fn private_ipv4_skip(addr: &IpAddr) -> Option<NotifyFailure> {
+    let range = non_public_ipv4_range(addr)?;
+    load_settings()
+        .unwrap_or_default()
+        .skip_private_ipv4
+        .then(|| NotifyFailure {
+            kind: FailureKind::Skipped,
+            detail: format_msg_log!(
+                MessageKey::LogPrivateIpv4SkippedFmt,
+                ip = addr,
+                range = range
+            ),
+            retry_after: None,
+        })
+}
+
+/// 更新サービスのエンドポイントに単一の通知リクエストを送信します。
 ///
 /// 指定されたURLに対して、Basic認証を用いてGETリクエストを送信します。
-/// リクエストの成功・失敗の結果をログに記録します。
+/// 汎用のサービスでパスワードが空の場合は、URLのトークンなどで認証するものとして資格情報を送りません。
+/// HTTPステータスコードに加えて応答本文も解析し、リクエストの結果をログに記録します。
+/// 応答本文から結果を判定できなかった場合は、ステータスコードに従って成功とみなします。
+/// MyDNS.JPでは応答の形式が変わった可能性があるため、警告を記録します。
 ///
 /// # 引数
-/// * `client` - リクエストに使用する`reqwest::blocking::Client`インスタンス。
-/// * `url` - MyDNS.JPの通知用URL（IPv4またはIPv6用）。
-/// * `id` - 認証に使用するMasterID。
-/// * `pw` - 認証に使用するパスワード。
+/// * `client` - リクエストに使用する`reqwest::Client`インスタンス。
+/// * `url` - 通知用URL（IPv4またはIPv6用）。汎用のサービスでは、プレースホルダーを置き換えたもの。
+/// * `config` - 認証情報と通知先のサービスを持つアカウント設定。
 ///
 /// # 戻り値
-/// HTTPリクエストの成否を示す`reqwest::Result`。
-fn notify(client: &Client, url: &str, id: &str, pw: &str) -> reqwest::Result<()> {
+/// 通知の成否。成功した場合は、応答本文に示された受け付けたアドレス（わかった場合）を返します。
+/// 失敗した場合は、失敗の分類と再試行の判断に使う情報を返します。
+async fn notify(
+    client: &Client,
+    url: &str,
+    config: &Config,
+) -> Result<Option<IpAddr>, NotifyFailure> {
+    let id = &config.master_id;
+    let network_failure = |error: reqwest::Error| NotifyFailure {
+        kind: FailureKind::Network,
+        detail: error.to_string(),
+        retry_after: None,
+    };
+
     // Basic認証情報を付与してGETリクエストを送信します。
-    let res = client.get(url).basic_auth(id, Some(pw)).send()?;
+    log_detail(&format!(
+        "[{}] {}",
+        id,
+        format_msg_log!(MessageKey::LogHttpRequestFmt, url = url)
+    ));
+    let started = Instant::now();
+    let mut request = client.get(url);
+    if config.provider == Provider::MyDns || !config.password.is_empty() {
+        request = request.basic_auth(id, Some(&config.password));
+    }
+    let res = request.send().await.map_err(network_failure)?;
     let status = res.status();
-    // HTTPステータスコードが2xx台（成功）かどうかをチェックします。
-    if status.is_success() {
-        let msg = get_msg_en("log_notify_status_fmt")
-            .replacen("{}", url, 1)
-            .replacen("{}", &status.to_string(), 1);
-        log_info(&format!("[{}] {}", id, msg));
-        Ok(())
+    log_detail(&format!(
+        "[{}] {}",
+        id,
+        format_msg_log!(
+            MessageKey::LogHttpResponseFmt,
+            status = status,
+            ms = started.elapsed().as_millis(),
+            version = format!("{:?}", res.version())
+        )
+    ));
+    // HTTPステータスコードが2xx台（成功）でない場合（401認証エラー、500サーバーエラーなど）は、
+    // ステータスコードから失敗を分類します。
+    if !status.is_success() {
+        return Err(NotifyFailure {
+            kind: FailureKind::from_status(status),
+            detail: format!("HTTP {}", status),
+            retry_after: parse_retry_after(&res),
+        });
+    }
+
+    // 2xxであっても、応答本文が論理的な失敗を示していないかを確認します。
+    let body = res.text().await.map_err(network_failure)?;
+    log_detail(&format!(
+        "[{}] {}",
+        id,
+        format_msg_log!(
+            MessageKey::LogHttpBodyFmt,
+            bytes = body.len(),
+            body = summarize_body(&body)
+        )
+    ));
+    let body_failure = |kind| NotifyFailure {
+        kind,
+        detail: format!("HTTP {}: {}", status, summarize_body(&body)),
+        retry_after: None,
+    };
+    let verdict = match config.provider {
+        Provider::MyDns => classify_body(&body),
+        Provider::Generic => classify_generic_body(&body),
+    };
+    match verdict {
+        BodyVerdict::AuthFailed => return Err(body_failure(FailureKind::Auth)),
+        BodyVerdict::ServerError => return Err(body_failure(FailureKind::Server)),
+        BodyVerdict::Rejected => return Err(body_failure(FailureKind::Http)),
+        BodyVerdict::Unrecognized => {
+            // 汎用のサービスの応答の形式はさまざまであるため、詳細ログにだけ記録する。
+            let log = match config.provider {
+                Provider::MyDns => log_warn,
+                Provider::Generic => log_detail,
+            };
+            log(&format!(
+                "[{}] {}",
+                id,
+                format_msg_log!(
+                    MessageKey::LogNotifyBodyUnrecognizedFmt,
+                    url = url,
+                    body = summarize_body(&body)
+                )
+            ))
+        }
+        BodyVerdict::Accepted => {}
+    }
+    let msg = format_msg_log!(MessageKey::LogNotifyStatusFmt, url = url, status = status);
+    log_info(&format!("[{}] {}", id, msg));
+    Ok(match config.provider {
+        Provider::MyDns => parse_remote_address(&body),
+        Provider::Generic => parse_first_address(&body),
+    })
+}
+
+/// MyDNS.JPのサーバーにPOP3/IMAP/FTPでログインして、1回の通知を行います。
+///
+/// `url` はログに記録する、ログインするサーバーを表すURLです。タイムアウトは `--request-timeout` の設定に従います。
+/// ログインの拒否は認証エラー、想定外の応答はサーバー側の障害、接続の失敗やタイムアウトは通信エラーとして扱います。
+///
+/// # 戻り値
+/// 通知の成否。ログインでは受け付けたアドレスがわからないため、成功した場合は常に `None` を返します。
+async fn notify_by_login(
+    url: &str,
+    ipv6: bool,
+    config: &Config,
+) -> Result<Option<IpAddr>, NotifyFailure> {
+    let id = &config.master_id;
+    let failure = |kind, detail: String| NotifyFailure {
+        kind,
+        detail,
+        retry_after: None,
+    };
+    let local = resolve_bind_address(&config.bind_interface, ipv6)
+        .map_err(|detail| failure(FailureKind::Network, detail))?;
+    let timeout = Duration::from_secs(u64::from(
+        load_settings().unwrap_or_default().request_timeout_seconds,
+    ));
+    log_detail(&format!(
+        "[{}] {}",
+        id,
+        format_msg_log!(MessageKey::LogLoginRequestFmt, url = url)
+    ));
+    login(config.method, ipv6, id, &config.password, local, timeout)
+        .await
+        .map_err(|e| match e {
+            LoginFailure::Io(e) => failure(FailureKind::Network, e.to_string()),
+            LoginFailure::Rejected(reply) => failure(FailureKind::Auth, reply),
+            LoginFailure::Protocol(reply) => failure(FailureKind::Server, reply),
+        })?;
+    log_info(&format!(
+        "[{}] {}",
+        id,
+        format_msg_log!(MessageKey::LogLoginNotifiedFmt, url = url)
+    ));
+    Ok(None)
+}
+
+/// MyDNS.JPの応答本文から、通知を受け付けたアドレス（`REMOTE ADDRESS:` の値）を取り出します。
+fn parse_remote_address(body: &str) -> Option<IpAddr> {
+    const LABEL: &str = "remote address";
+    // ASCIIの小文字化はバイト位置を変えないため、元の本文と同じ位置で切り出せる。
+    let start = body.to_ascii_lowercase().find(LABEL)? + LABEL.len();
+    let rest = body[start..].trim_start_matches(|c: char| c == ':' || c.is_whitespace());
+    let end = rest
+        .find(|c: char| !(c.is_ascii_hexdigit() || c == '.' || c == ':'))
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+/// 汎用のサービスの応答本文から、最初に現れるIPアドレスを取り出します。
+fn parse_first_address(body: &str) -> Option<IpAddr> {
+    body.split(|c: char| !(c.is_ascii_hexdigit() || c == '.' || c == ':'))
+        .find_map(|token| token.parse().ok())
+}
+
+/// 応答本文を、ログに記録できる長さの1行に要約します。
+///
+/// HTMLのタグを取り除き、連続する空白を1つにまとめてから、先頭の一部だけを残します。
+fn summarize_body(body: &str) -> String {
+    /// 要約に残す最大文字数。
+    const MAX_CHARS: usize = 120;
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in body.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > MAX_CHARS {
+        format!("{}...", text.chars().take(MAX_CHARS).collect::<String>())
     } else {
-        // ステータスが成功でない場合（401認証エラー、500サーバーエラーなど）、
-        // `error_for_status()`はレスポンスを`Err`に変換します。
-        // `is_success()`が`false`なので、`unwrap_err()`は常に安全です。
-        Err(res.error_for_status().unwrap_err())
+        text
+    }
+}
+
+/// レスポンスの `Retry-After` ヘッダーを待ち時間として解釈します。
+///
+/// 値は秒数またはHTTP日付のどちらの形式でも受け付けます。
+/// 日付が既に過ぎている場合は、待ち時間なしとして扱います。
+fn parse_retry_after(res: &Response) -> Option<Duration> {
+    let value = res.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Local) - Local::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+/// 0から `max` までのランダムな時間を返します。
+///
+/// 暗号学的な強度は不要なため、標準ライブラリの `RandomState` が生成するランダムな鍵を乱数源として使います。
+pub fn random_duration(max: Duration) -> Duration {
+    let nanos = max.as_nanos() as u64;
+    if nanos == 0 {
+        return Duration::ZERO;
     }
+    Duration::from_nanos(RandomState::new().build_hasher().finish() % nanos)
 }
@@ -0,0 +1,41 @@
+//! 通知に成功したことを、外部の死活監視サービスに伝えるモジュール。
+//!
+//! Healthchecks.ioのような、一定時間pingが届かなければ警告する監視サービス（デッドマンスイッチ）と連携します。
+//! サービスは、通知ラウンドのすべてのアカウントで通知に成功したときに全体のping URLへ、
+//! アカウントの通知に成功したときにそのアカウントのping URLへ、GETリクエストを送ります。
+//! アダプターが止まったり通知に失敗し続けたりするとpingが途絶えるため、監視サービスがそれを検知できます。
+
+use crate::format_msg_log;
+use crate::i18n::MessageKey;
+use crate::logging::{log_detail, log_warn};
+use reqwest::Client;
+
+/// ping URLにGETリクエストを送ります。
+///
+/// `master_id` は、アカウントのping URLの場合にログの行の先頭に付けるMasterIDです。
+/// 結果はログに記録するだけで、通知の成否には影響しません。
+pub async fn send_ping(client: &Client, master_id: Option<&str>, url: &str) {
+    let result = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|res| res.error_for_status());
+    match result {
+        Ok(_) => log_detail(&with_prefix(
+            master_id,
+            format_msg_log!(MessageKey::LogPingSentFmt, url = url),
+        )),
+        Err(e) => log_warn(&with_prefix(
+            master_id,
+            format_msg_log!(MessageKey::LogPingFailedFmt, url = url, error = e),
+        )),
+    }
+}
+
+/// アカウントのMasterIDがあれば、ログの行の先頭に付けます。
+fn with_prefix(master_id: Option<&str>, msg: String) -> String {
+    match master_id {
+        Some(id) => format!("[{}] {}", id, msg),
+        None => msg,
+    }
+}
@@ -0,0 +1,124 @@
+//! 通知に使うHTTPプロキシを決定するモジュール。
+//!
+//! プロキシは、次の優先順位で決定します。
+//! 1. アカウントごとに設定されたプロキシURL
+//! 2. `--proxy` で設定した、アプリケーション全体のプロキシURL
+//! 3. WinHTTPの既定のプロキシ（`netsh winhttp set proxy` で設定されるもの）
+//! 4. 環境変数やログオン中のユーザーのインターネット設定（`reqwest` が自動的に参照する）
+//!
+//! サービスは通常、ユーザーのインターネット設定を持たないLocalSystemアカウントで動作するため、
+//! マシン全体の設定であるWinHTTPのプロキシを明示的に読み込みます。
+
+use windows::Win32::Foundation::{GlobalFree, HGLOBAL};
+use windows::Win32::Networking::WinHttp::{
+    WINHTTP_ACCESS_TYPE_NAMED_PROXY, WINHTTP_PROXY_INFO, WinHttpGetDefaultProxyConfiguration,
+};
+use windows::core::PWSTR;
+
+/// プロキシURLの代わりに指定すると、プロキシを使わずに直接接続することを表す値。
+pub const PROXY_DIRECT: &str = "direct";
+
+/// 通知に使うプロキシの決定結果。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProxyChoice {
+    /// プロキシを使わずに直接接続する。
+    Direct,
+    /// 指定されたプロキシを使う。
+    Url {
+        /// プロキシのURL（`http://host:port` 形式）。
+        url: String,
+        /// プロキシを経由しないホストの一覧（カンマ区切り）。
+        bypass: Option<String>,
+    },
+    /// `reqwest` の既定の動作（環境変数とユーザーのインターネット設定）に任せる。
+    System,
+}
+
+/// 設定値からプロキシを決定します。
+///
+/// `configured` には、アカウントの設定、なければ全体の設定のプロキシURLを渡します。
+/// 空文字列の場合は、WinHTTPの既定のプロキシ、システムの設定の順に参照します。
+pub fn resolve_proxy(configured: &str) -> ProxyChoice {
+    let configured = configured.trim();
+    if configured.eq_ignore_ascii_case(PROXY_DIRECT) {
+        return ProxyChoice::Direct;
+    }
+    if !configured.is_empty() {
+        return ProxyChoice::Url {
+            url: normalize_proxy_url(configured),
+            bypass: None,
+        };
+    }
+    winhttp_default_proxy().unwrap_or(ProxyChoice::System)
+}
+
+/// WinHTTPの既定のプロキシ設定を読み込みます。
+///
+/// プロキシが設定されていない場合や、読み込みに失敗した場合は `None` を返します。
+fn winhttp_default_proxy() -> Option<ProxyChoice> {
+    let mut info = WINHTTP_PROXY_INFO::default();
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // APIが確保した文字列は、内容をコピーした後に `GlobalFree` で解放します。
+    unsafe {
+        WinHttpGetDefaultProxyConfiguration(&mut info).ok()?;
+        let proxy = take_global_string(info.lpszProxy);
+        let bypass = take_global_string(info.lpszProxyBypass);
+        if info.dwAccessType != WINHTTP_ACCESS_TYPE_NAMED_PROXY {
+            return None;
+        }
+        let url = select_https_proxy(&proxy?)?;
+        Some(ProxyChoice::Url {
+            url: normalize_proxy_url(&url),
+            // WinHTTPの除外リストはセミコロン区切り。
+            bypass: bypass.map(|b| b.replace(';', ",")),
+        })
+    }
+}
+
+/// WinHTTP APIが確保した文字列を `String` にコピーし、元のメモリを解放します。
+unsafe fn take_global_string(ptr: PWSTR) -> Option<String> {
+    if ptr.is_null() {
+        return None;
+    }
+    unsafe {
+        let text = ptr.to_string().ok();
+        let _ = GlobalFree(HGLOBAL(ptr.0 as _));
+        text.filter(|t| !t.trim().is_empty())
+    }
+}
+
+/// WinHTTPのプロキシ指定から、HTTPSの通信に使うプロキシを選びます。
+///
+/// 指定は `host:port` の単独形式か、`http=host:port;https=host:port` のような
+/// スキームごとの形式です。スキームごとの形式では `https=` を優先し、なければ `http=` を使います。
+fn select_https_proxy(list: &str) -> Option<String> {
+    let entries: Vec<&str> = list
+        .split([';', ' '])
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .collect();
+    let scheme_entry = |scheme: &str| {
+        entries.iter().find_map(|e| {
+            e.split_once('=')
+                .filter(|(s, _)| s.eq_ignore_ascii_case(scheme))
+                .map(|(_, proxy)| proxy.to_string())
+        })
+    };
+    scheme_entry("https")
+        .or_else(|| scheme_entry("http"))
+        .or_else(|| {
+            entries
+                .iter()
+                .find(|e| !e.contains('='))
+                .map(|e| e.to_string())
+        })
+}
+
+/// スキームが省略されたプロキシ指定（`host:port`）に `http://` を補います。
+fn normalize_proxy_url(proxy: &str) -> String {
+    if proxy.contains("://") {
+        proxy.to_string()
+    } else {
+        format!("http://{}", proxy)
+    }
+}
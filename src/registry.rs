@@ -1,23 +1,216 @@
 //! レジストリを介したアプリケーション設定の永続化を管理するモジュール。
-//! 設定は `HKEY_LOCAL_MACHINE\Software\MyDNSAdapter` 以下に保存されます。
+//! 設定は既定で `HKEY_LOCAL_MACHINE\Software\MyDNSAdapter` 以下に保存されます。
+//! 管理者権限のないユーザー向けに、`HKEY_CURRENT_USER\Software\MyDNSAdapter` を使う
+//! ユーザーごとの保存先（`ConfigScope::User`）にも切り替えられます。
+//!
+//! 最後に通知に成功した時刻（`LastSuccess`、FILETIMEのREG_QWORD）とそのとき通知したアドレス
+//! （`LastIPv4` / `LastIPv6`）、最後に失敗した時刻（`LastErrorTime`）とその分類と内容
+//! （`LastErrorKind` / `LastError`）、自動停止（`SuspendedTime`）は、設定とは別の
+//! `Software\MyDNSAdapterRecords\<MasterID>` のキーに記録します。サービスの実行アカウントには、
+//! 設定を書き換えさせずにこのキーへの書き込みだけを許可できます。これらは設定ではないため、
+//! `Config` には含めず、`save_last_success` や `load_last_error` などで読み書きします。
+//! 以前のバージョンがアカウントのキーに記録した値は、最初に書き込むときに記録のキーへコピーします。
+//!
+//! アカウントの設定の読み書きは、`store` モジュールの `ConfigStore` トレイトの実装（`RegistryStore`）として提供し、
+//! 呼び出し元は `store::load_all_configs` などを通して使います。
 
+// --- アプリケーション内のモジュール ---
+use crate::error::AdapterError;
+use crate::regwatch::RegistryChangeWatcher;
+use crate::store::{ConfigStore, StoreWatcher};
 // --- Win32 API関連の定数や型をインポート ---
 // Foundation: エラーコードなど基本的な型
-use windows::Win32::Foundation::{ERROR_FILE_NOT_FOUND, ERROR_NO_MORE_ITEMS, WIN32_ERROR};
+use windows::Win32::Foundation::{
+    CloseHandle, ERROR_ALREADY_EXISTS, ERROR_FILE_NOT_FOUND, ERROR_NO_MORE_ITEMS, HANDLE, HLOCAL,
+    LocalFree, WIN32_ERROR,
+};
+// Security: サービスの実行アカウントに、キーへの書き込みを許可するためのアクセス制御
+use windows::Win32::Security::Authorization::{
+    EXPLICIT_ACCESS_W, GRANT_ACCESS, GetSecurityInfo, SE_REGISTRY_KEY, SetEntriesInAclW,
+    SetSecurityInfo, TRUSTEE_IS_NAME, TRUSTEE_IS_UNKNOWN, TRUSTEE_W,
+};
+use windows::Win32::Security::{
+    ACL, DACL_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR, PSID, SUB_CONTAINERS_AND_OBJECTS_INHERIT,
+};
+// Storage::FileSystem: 複数の値をまとめて書き込むためのトランザクション（KTM）
+use windows::Win32::Storage::FileSystem::{CommitTransaction, CreateTransaction, WRITE_DAC};
 // System::Registry: レジストリ操作に必要な関数、定数、型
 use windows::Win32::System::Registry::{
-    HKEY, HKEY_LOCAL_MACHINE, KEY_READ, KEY_WRITE, REG_DWORD, REG_OPTION_NON_VOLATILE, REG_SZ,
-    REG_VALUE_TYPE, RegCloseKey, RegCreateKeyExW, RegDeleteKeyW, RegEnumKeyExW, RegOpenKeyExW,
-    RegQueryValueExW, RegSetValueExW,
+    HKEY, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, KEY_ALL_ACCESS, KEY_CREATE_SUB_KEY, KEY_READ,
+    KEY_WOW64_64KEY, KEY_WRITE, REG_CREATED_NEW_KEY, REG_DWORD, REG_OPTION_NON_VOLATILE, REG_QWORD,
+    REG_SAM_FLAGS, REG_SZ, REG_VALUE_TYPE, RegCloseKey, RegCopyTreeW, RegCreateKeyExW,
+    RegCreateKeyTransactedW, RegDeleteKeyExW, RegDeleteKeyTransactedW, RegDeleteTreeW,
+    RegEnumKeyExW, RegOpenKeyExW, RegOpenKeyTransactedW, RegQueryValueExW, RegSetValueExW,
 };
 // core: Win32 APIで文字列を扱うための型 (HSTRING, PCWSTRなど)
 use windows::core::{HSTRING, PCWSTR, PWSTR, w};
+// 日時の扱い
+use chrono::{DateTime, Local, NaiveTime};
+// 標準ライブラリ
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 設定の保存先。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigScope {
+    /// マシン全体の設定（`HKEY_LOCAL_MACHINE`）。サービスはこちらを使う。書き込みには管理者権限が必要。
+    Machine,
+    /// ユーザーごとの設定（`HKEY_CURRENT_USER`）。管理者権限なしで読み書きできる。
+    User,
+}
+
+/// レジストリを開くときに指定する、レジストリのビュー。
+///
+/// 64ビット版のWindowsでは、32ビットのプロセスが `HKLM\Software` を開くと、既定では
+/// `HKLM\Software\WOW6432Node` の別のキーにリダイレクトされます。32ビット版と64ビット版の実行ファイルで
+/// 同じアカウントと設定を使えるよう、すべてのキーを常に64ビットのビューで開きます。
+/// 32ビット版のWindowsでは、このフラグは無視されます。
+pub const REGISTRY_VIEW: REG_SAM_FLAGS = KEY_WOW64_64KEY;
+
+/// 現在の保存先がユーザーごとの設定かどうか。起動時に一度だけ設定される。
+static USER_SCOPE: AtomicBool = AtomicBool::new(false);
+
+/// 以降のレジストリ操作で使う保存先を設定します。
+pub fn set_config_scope(scope: ConfigScope) {
+    USER_SCOPE.store(scope == ConfigScope::User, Ordering::Relaxed);
+}
+
+/// 現在の保存先を返します。
+pub fn config_scope() -> ConfigScope {
+    if USER_SCOPE.load(Ordering::Relaxed) {
+        ConfigScope::User
+    } else {
+        ConfigScope::Machine
+    }
+}
+
+impl ConfigScope {
+    /// 保存先に対応するレジストリのルートキーを返します。
+    pub fn hkey(self) -> HKEY {
+        match self {
+            ConfigScope::Machine => HKEY_LOCAL_MACHINE,
+            ConfigScope::User => HKEY_CURRENT_USER,
+        }
+    }
+}
+
+/// 通知の結果と自動停止の記録を置くキーのパス。アカウントごとに、MasterIDのサブキーに記録する。
+const RECORD_KEY_PATH: &str = "Software\\MyDNSAdapterRecords";
+
+/// 画面に表示する、マシン全体の記録のキーの場所を返します。
+pub fn machine_record_location() -> String {
+    format!("HKEY_LOCAL_MACHINE\\{}", RECORD_KEY_PATH)
+}
+
+/// サービスを実行するアカウントに、マシン全体の記録のキーと、その下のアカウントごとのキーの読み書きを許可します。
+///
+/// LocalSystem 以外のアカウントは既定では `HKEY_LOCAL_MACHINE` に書き込めず、サービスが通知の結果
+/// （`LastSuccess` / `LastError`）や自動停止（`SuspendedTime`）を記録できないため、インストール時に呼び出します。
+/// 許可するのは記録のキーだけで、アカウントの設定やアプリケーション全体の設定は書き換えられません。
+/// 許可はサブキーにも継承されます。キーがまだなければ作成します。管理者権限が必要です。
+pub fn grant_record_key_access(account: &str) -> Result<(), AdapterError> {
+    let path = HSTRING::from(RECORD_KEY_PATH);
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // 取得したセキュリティ記述子と作成したACLは `LocalFree` で、キーのハンドルは最後にクローズします。
+    unsafe {
+        let mut hkey: HKEY = HKEY::default();
+        RegCreateKeyExW(
+            HKEY_LOCAL_MACHINE,
+            PCWSTR(path.as_ptr()),
+            0,
+            None,
+            REG_OPTION_NON_VOLATILE,
+            KEY_READ | REG_SAM_FLAGS(WRITE_DAC.0) | REGISTRY_VIEW,
+            None,
+            &mut hkey,
+            None,
+        )
+        .ok()?;
+        let handle = HANDLE(hkey.0);
+
+        let mut old_dacl: *mut ACL = std::ptr::null_mut();
+        let mut descriptor = PSECURITY_DESCRIPTOR::default();
+        let result = GetSecurityInfo(
+            handle,
+            SE_REGISTRY_KEY,
+            DACL_SECURITY_INFORMATION,
+            None,
+            None,
+            Some(&mut old_dacl),
+            None,
+            Some(&mut descriptor),
+        )
+        .ok()
+        .and_then(|()| {
+            let mut name: Vec<u16> = account.encode_utf16().chain(std::iter::once(0)).collect();
+            let access = EXPLICIT_ACCESS_W {
+                grfAccessPermissions: (KEY_READ | KEY_WRITE).0,
+                grfAccessMode: GRANT_ACCESS,
+                grfInheritance: SUB_CONTAINERS_AND_OBJECTS_INHERIT,
+                Trustee: TRUSTEE_W {
+                    TrusteeForm: TRUSTEE_IS_NAME,
+                    TrusteeType: TRUSTEE_IS_UNKNOWN,
+                    ptstrName: PWSTR(name.as_mut_ptr()),
+                    ..Default::default()
+                },
+            };
+            let mut new_dacl: *mut ACL = std::ptr::null_mut();
+            let result = SetEntriesInAclW(Some(&[access]), Some(old_dacl), &mut new_dacl)
+                .ok()
+                .and_then(|()| {
+                    SetSecurityInfo(
+                        handle,
+                        SE_REGISTRY_KEY,
+                        DACL_SECURITY_INFORMATION,
+                        PSID::default(),
+                        PSID::default(),
+                        Some(new_dacl),
+                        None,
+                    )
+                    .ok()
+                });
+            let _ = LocalFree(HLOCAL(new_dacl.cast()));
+            result
+        });
+        let _ = LocalFree(HLOCAL(descriptor.0));
+        let _ = RegCloseKey(hkey);
+        Ok(result?)
+    }
+}
+
+/// 現在の保存先に対応するレジストリのルートキーを返します。
+fn root_hkey() -> HKEY {
+    config_scope().hkey()
+}
+
+/// マシン全体の設定（HKLM）に書き込めるかどうかを判定します。
+///
+/// `HKLM\Software` にサブキーを作成する権限があるかを調べるだけで、実際には何も作成しません。
+pub fn machine_scope_writable() -> bool {
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // 開いたハンドルはすぐにクローズするため安全です。
+    unsafe {
+        let mut hkey: HKEY = HKEY::default();
+        let result = RegOpenKeyExW(
+            HKEY_LOCAL_MACHINE,
+            w!("Software"),
+            0,
+            KEY_CREATE_SUB_KEY | REGISTRY_VIEW,
+            &mut hkey,
+        );
+        if result.is_err() {
+            return false;
+        }
+        let _ = RegCloseKey(hkey);
+        true
+    }
+}
 
 /// アプリケーションの設定情報を保持する構造体。
 ///
 /// レジストリの各サブキー（MasterIDごと）に対応し、
 /// そのキーに含まれる値をフィールドとして持ちます。
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Config {
     /// MyDNSのマスターID。レジストリではサブキー名として使用される。
     pub master_id: String,
@@ -27,13 +220,518 @@ pub struct Config {
     pub ipv4_notify: bool,
     /// IPv6アドレスの通知を有効にするかどうか。
     pub ipv6_notify: bool,
+    /// 最後の通知成功からこの時間（時間単位）が経過したら、状況に関わらず通知を強制する。0で無効。
+    pub keep_alive_hours: u32,
+    /// このアカウントの通知に使うプロキシURL。空の場合は全体の設定に従う。
+    pub proxy_url: String,
+    /// IPv4アドレスの通知先URL。空の場合はMyDNS.JPの既定のURLを使う。
+    /// 汎用のサービスでは、`{ip}` と `{host}` を含められる更新URLのテンプレート。
+    pub ipv4_url: String,
+    /// IPv6アドレスの通知先URL。空の場合はMyDNS.JPの既定のURLを使う。
+    /// 汎用のサービスでは更新URLのテンプレートで、空の場合はIPv4の更新URLを使う。
+    pub ipv6_url: String,
+    /// 通知後にDNSの反映を確認するホスト名。空の場合は確認しない。
+    /// 汎用のサービスでは、更新URLの `{host}` にも使う。
+    pub hostname: String,
+    /// 通知先のサービスの種類。
+    pub provider: Provider,
+    /// MyDNS.JPへの通知の方法（HTTPS、またはPOP3/IMAP/FTPでのログイン）。汎用のサービスでは常に `Http`。
+    pub method: NotifyMethod,
+    /// このアカウントの通知に成功したときにGETリクエストを送る、死活監視サービスのURL。空の場合は送らない。
+    pub ping_url: String,
+    /// 通知を送るネットワークインターフェイスの名前、またはローカルアドレス。空の場合は選ばない。
+    pub bind_interface: String,
+    /// 定期通知の代わりに使う、通知のスケジュールの式（`schedule` モジュール）。空の場合は定期通知で通知する。
+    pub schedule: String,
+}
+
+/// アカウントの通知先のサービスの種類。
+///
+/// レジストリには `code` が返す文字列で保存します。値がないアカウントはMyDNS.JPとして扱います。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Provider {
+    /// MyDNS.JP。MasterIDとパスワードのBasic認証で、接続元のアドレスを通知する。
+    #[default]
+    MyDns,
+    /// 任意の更新URLを使うサービス（No-IP、FreeDNSなど）。
+    /// 更新URLの `{ip}` と `{host}` を置き換えて、パスワードがあればBasic認証付きで送信する。
+    Generic,
+}
+
+impl Provider {
+    /// レジストリや設定ファイルに保存する、種類を表す固定の文字列。
+    pub fn code(self) -> &'static str {
+        match self {
+            Provider::MyDns => "mydns",
+            Provider::Generic => "generic",
+        }
+    }
+
+    /// `code` が返す文字列から種類を求めます。大文字と小文字は区別しません。
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_ascii_lowercase().as_str() {
+            "mydns" => Some(Provider::MyDns),
+            "generic" => Some(Provider::Generic),
+            _ => None,
+        }
+    }
+}
+
+/// MyDNS.JPにアドレスを通知する方法。
+///
+/// MyDNS.JPは、HTTPSのリクエストのほか、POP3/IMAP/FTPでのログインでもアドレスの通知を受け付けます。
+/// HTTPが遮断されたネットワークからでも通知できるよう、アカウントごとに選べます。
+/// レジストリには `code` が返す文字列で保存します。値がないアカウントは `Http` として扱います。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NotifyMethod {
+    /// 通知先URLへのBasic認証付きのHTTP(S)リクエスト。
+    #[default]
+    Http,
+    /// POP3サーバーへのログイン（`USER`/`PASS`）。
+    Pop3,
+    /// IMAPサーバーへのログイン（`LOGIN`）。
+    Imap,
+    /// FTPサーバーへのログイン（`USER`/`PASS`）。
+    Ftp,
+}
+
+impl NotifyMethod {
+    /// レジストリや設定ファイルに保存する、方法を表す固定の文字列。
+    pub fn code(self) -> &'static str {
+        match self {
+            NotifyMethod::Http => "http",
+            NotifyMethod::Pop3 => "pop3",
+            NotifyMethod::Imap => "imap",
+            NotifyMethod::Ftp => "ftp",
+        }
+    }
+
+    /// `code` が返す文字列から方法を求めます。大文字と小文字は区別しません。
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_ascii_lowercase().as_str() {
+            "http" | "https" => Some(NotifyMethod::Http),
+            "pop3" | "pop" => Some(NotifyMethod::Pop3),
+            "imap" => Some(NotifyMethod::Imap),
+            "ftp" => Some(NotifyMethod::Ftp),
+            _ => None,
+        }
+    }
+}
+
+/// 従量制課金の接続（モバイル回線やテザリングなど）での、サービスによる通知の扱い。
+///
+/// レジストリには `code` が返す文字列で保存します。値がない場合は `Ignore` として扱います。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MeteredPolicy {
+    /// 従量制課金の接続でも、通常どおり通知する。
+    #[default]
+    Ignore,
+    /// 定期通知を1時間に1回までに減らす。アドレスの変更などによる通知は、通常どおり行う。
+    Reduce,
+    /// サービスからの自動の通知をすべて見送る。`--kick` による要求は通知する。
+    Skip,
+}
+
+impl MeteredPolicy {
+    /// レジストリに保存する、扱いを表す固定の文字列。
+    pub fn code(self) -> &'static str {
+        match self {
+            MeteredPolicy::Ignore => "ignore",
+            MeteredPolicy::Reduce => "reduce",
+            MeteredPolicy::Skip => "skip",
+        }
+    }
+
+    /// `code` が返す文字列から扱いを求めます。大文字と小文字は区別しません。
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_ascii_lowercase().as_str() {
+            "ignore" => Some(MeteredPolicy::Ignore),
+            "reduce" => Some(MeteredPolicy::Reduce),
+            "skip" => Some(MeteredPolicy::Skip),
+            _ => None,
+        }
+    }
+}
+
+/// サービスが自動の通知を見送る、毎日の時間帯（静穏時間帯）。
+///
+/// レジストリには `code` が返す `HH:MM-HH:MM` の形式の文字列で保存します。
+/// 終了の時刻が開始の時刻より前の場合は、日付をまたぐ時間帯（`23:00-06:00` など）を表します。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuietHours {
+    /// 時間帯の開始の時刻（この時刻を含む）。
+    pub start: NaiveTime,
+    /// 時間帯の終了の時刻（この時刻を含まない）。
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    /// レジストリに保存する、`HH:MM-HH:MM` の形式の文字列。
+    pub fn code(&self) -> String {
+        format!(
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+
+    /// `HH:MM-HH:MM` の形式の文字列から時間帯を求めます。開始と終了が同じ時刻の場合は `None` を返します。
+    pub fn from_code(code: &str) -> Option<Self> {
+        let (start, end) = code.trim().split_once('-')?;
+        let start = NaiveTime::parse_from_str(start.trim(), "%H:%M").ok()?;
+        let end = NaiveTime::parse_from_str(end.trim(), "%H:%M").ok()?;
+        (start != end).then_some(QuietHours { start, end })
+    }
+
+    /// 指定した時刻が、この時間帯に含まれるかどうかを返します。
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            self.start <= time || time < self.end
+        }
+    }
+}
+
+/// アカウントに依存しない、アプリケーション全体の設定。
+///
+/// `Software\MyDNSAdapter` キー自体の値として保存されます。
+/// （アカウントごとの設定は、同じキーのサブキーとして保存されます。）
+#[derive(Clone, Debug)]
+pub struct Settings {
+    /// ローカルの利用統計の記録を有効にするかどうか（オプトイン）。
+    pub stats_enabled: bool,
+    /// サービスによる定期通知の間隔（分）。
+    pub notify_interval_minutes: u32,
+    /// 一時的な障害で通知に失敗した場合の、最初の送信を含めた最大試行回数。
+    pub retry_max_attempts: u32,
+    /// 1回の通知処理で、同時に通知するアカウントの最大数。
+    pub notify_concurrency: u32,
+    /// 1回の通知リクエストのタイムアウト（秒）。
+    pub request_timeout_seconds: u32,
+    /// 通知に使うプロキシURL。空の場合はWinHTTPやシステムの設定に従う。
+    pub proxy_url: String,
+    /// ログファイルのパス。空の場合は既定の場所（`%ProgramData%\MyDNSAdapter\mydns.log`）を使う。
+    pub log_path: String,
+    /// ローテーションで残すログのアーカイブの数。
+    pub log_archive_count: u32,
+    /// ログのアーカイブをgzipで圧縮するかどうか。
+    pub log_archive_compress: bool,
+    /// サービスがメトリクスを公開するループバックアドレスのポート。0の場合は公開しない。
+    pub metrics_port: u16,
+    /// サービスが管理APIを公開するループバックアドレスのポート。0の場合は公開しない。
+    pub admin_api_port: u16,
+    /// 管理APIのリクエストに必要なトークン。
+    pub admin_api_token: String,
+    /// 通知ラウンドのすべてのアカウントで通知に成功したときにGETリクエストを送る、死活監視サービスのURL。
+    /// 空の場合は送らない。
+    pub ping_url: String,
+    /// 通知の失敗・回復・アドレスの変化を知らせるWebhookのURL。空の場合は送らない。
+    pub webhook_url: String,
+    /// 通知がこの回数続けて失敗したら、トースト通知で知らせる。0で無効。
+    pub toast_failure_threshold: u32,
+    /// 失敗を知らせるメールを送るSMTPサーバーのホスト名。空の場合はメールを送らない。
+    pub smtp_server: String,
+    /// SMTPサーバーのポート。465ではSMTPS、それ以外ではSTARTTLSで接続する。
+    pub smtp_port: u16,
+    /// SMTP認証のユーザー名。空の場合は認証しない。
+    pub smtp_username: String,
+    /// SMTP認証のパスワード。
+    pub smtp_password: String,
+    /// メールの差出人。空の場合は宛先と同じアドレスを使う。
+    pub mail_from: String,
+    /// メールの宛先。空の場合はメールを送らない。
+    pub mail_to: String,
+    /// 通知がこの時間（分）以上失敗し続けたら、メールで知らせる。
+    pub mail_after_minutes: u32,
+    /// IPv4アドレスの確認サービスのURLを、問い合わせる順に並べたもの。空の場合は既定のチェーンを使う。
+    pub check_ipv4_urls: Vec<String>,
+    /// IPv6アドレスの確認サービスのURLを、問い合わせる順に並べたもの。空の場合は既定のチェーンを使う。
+    pub check_ipv6_urls: Vec<String>,
+    /// サービスがグローバルアドレスの変化を確認する間隔（分）。0で確認しない。
+    pub check_ip_poll_minutes: u32,
+    /// 確認サービスで求めたIPv4アドレスがCGNATやプライベートのアドレスだった場合に、通知を見送るかどうか。
+    pub skip_private_ipv4: bool,
+    /// サービスがアカウントのDNSレコードを公開リゾルバーで確認する間隔（分）。0で確認しない。
+    pub dns_check_minutes: u32,
+    /// 従量制課金の接続での、サービスによる通知の扱い。
+    pub metered_policy: MeteredPolicy,
+    /// サービスが通知する、ネットワークプロファイルの名前またはSSID。空の場合はどのネットワークでも通知する。
+    pub allowed_networks: Vec<String>,
+    /// サービスが自動の通知を見送る、毎日の時間帯。`None` の場合は見送らない。
+    pub quiet_hours: Option<QuietHours>,
+    /// サービスの定期通知と開始時の通知の時刻を、ランダムにずらす最大の時間（秒）。0でずらさない。
+    pub jitter_seconds: u32,
+    /// HTTPリクエストのUser-Agentの末尾に付ける、利用者が決めた識別子。空の場合は付けない。
+    pub user_agent_id: String,
+    /// 通知の履歴を、SQLiteのデータベースにも記録するかどうか。`sqlite` フィーチャーが必要。
+    pub history_database: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            stats_enabled: false,
+            notify_interval_minutes: DEFAULT_NOTIFY_INTERVAL_MINUTES,
+            retry_max_attempts: DEFAULT_RETRY_MAX_ATTEMPTS,
+            notify_concurrency: DEFAULT_NOTIFY_CONCURRENCY,
+            request_timeout_seconds: DEFAULT_REQUEST_TIMEOUT_SECONDS,
+            proxy_url: String::new(),
+            log_path: String::new(),
+            log_archive_count: DEFAULT_LOG_ARCHIVE_COUNT,
+            log_archive_compress: false,
+            metrics_port: 0,
+            admin_api_port: 0,
+            admin_api_token: String::new(),
+            ping_url: String::new(),
+            webhook_url: String::new(),
+            toast_failure_threshold: DEFAULT_TOAST_FAILURE_THRESHOLD,
+            smtp_server: String::new(),
+            smtp_port: DEFAULT_SMTP_PORT,
+            smtp_username: String::new(),
+            smtp_password: String::new(),
+            mail_from: String::new(),
+            mail_to: String::new(),
+            mail_after_minutes: DEFAULT_MAIL_AFTER_MINUTES,
+            check_ipv4_urls: Vec::new(),
+            check_ipv6_urls: Vec::new(),
+            check_ip_poll_minutes: 0,
+            skip_private_ipv4: false,
+            dns_check_minutes: 0,
+            metered_policy: MeteredPolicy::Ignore,
+            allowed_networks: Vec::new(),
+            quiet_hours: None,
+            jitter_seconds: 0,
+            user_agent_id: String::new(),
+            history_database: false,
+        }
+    }
+}
+
+/// 定期通知の間隔の既定値（分）。
+pub const DEFAULT_NOTIFY_INTERVAL_MINUTES: u32 = 5;
+/// 定期通知の間隔として指定できる範囲（分）。最短1分、最長1日。
+pub const NOTIFY_INTERVAL_RANGE_MINUTES: std::ops::RangeInclusive<u32> = 1..=24 * 60;
+/// 通知の最大試行回数の既定値。
+pub const DEFAULT_RETRY_MAX_ATTEMPTS: u32 = 3;
+/// 通知の最大試行回数として指定できる範囲。1は再試行しないことを表す。
+pub const RETRY_MAX_ATTEMPTS_RANGE: std::ops::RangeInclusive<u32> = 1..=10;
+/// 同時に通知するアカウントの数の既定値。
+pub const DEFAULT_NOTIFY_CONCURRENCY: u32 = 4;
+/// 同時に通知するアカウントの数として指定できる範囲。1はアカウントを1つずつ順に通知することを表す。
+pub const NOTIFY_CONCURRENCY_RANGE: std::ops::RangeInclusive<u32> = 1..=32;
+/// 通知リクエストのタイムアウトの既定値（秒）。
+pub const DEFAULT_REQUEST_TIMEOUT_SECONDS: u32 = 30;
+/// 通知リクエストのタイムアウトとして指定できる範囲（秒）。
+pub const REQUEST_TIMEOUT_RANGE_SECONDS: std::ops::RangeInclusive<u32> = 5..=300;
+/// ログのアーカイブを残す数の既定値。
+pub const DEFAULT_LOG_ARCHIVE_COUNT: u32 = 5;
+/// ログのアーカイブを残す数として指定できる範囲。
+pub const LOG_ARCHIVE_COUNT_RANGE: std::ops::RangeInclusive<u32> = 1..=365;
+
+/// トースト通知で知らせる、連続した失敗の回数の既定値。
+pub const DEFAULT_TOAST_FAILURE_THRESHOLD: u32 = 3;
+
+/// SMTPサーバーのポートの既定値（メールの投稿用のポート）。
+pub const DEFAULT_SMTP_PORT: u16 = 587;
+/// 失敗をメールで知らせるまでの時間の既定値（分）。
+pub const DEFAULT_MAIL_AFTER_MINUTES: u32 = 60;
+
+/// FILETIMEの起点（1601年1月1日）からUNIXエポック（1970年1月1日）までの、100ナノ秒単位の時間。
+const FILETIME_UNIX_EPOCH: i64 = 116_444_736_000_000_000;
+
+/// 保存先に記録された、最後に通知に成功した時刻とアドレス。
+///
+/// 記録がない項目は `None` です。
+#[derive(Clone, Debug, Default)]
+pub struct LastSuccess {
+    /// 最後に通知に成功した時刻。
+    pub time: Option<DateTime<Local>>,
+    /// 最後に通知したIPv4アドレス。
+    pub ipv4: Option<Ipv4Addr>,
+    /// 最後に通知したIPv6アドレス。
+    pub ipv6: Option<Ipv6Addr>,
+}
+
+/// 保存先に記録された、最後に通知に失敗した時刻と内容。
+#[derive(Clone, Debug)]
+pub struct LastError {
+    /// 最後に通知に失敗した時刻。
+    pub time: DateTime<Local>,
+    /// 失敗の分類（`auth` など）。
+    pub kind: String,
+    /// 失敗の内容。
+    pub detail: String,
+}
+
+/// 強制通知（キープアライブ）間隔の既定値（時間）。
+/// MyDNS.JPは一定期間通知のないレコードを失効させるため、1日1回は必ず通知する。
+pub const DEFAULT_KEEP_ALIVE_HOURS: u32 = 24;
+
+/// アカウントの設定をレジストリに保存する `ConfigStore` の実装。
+///
+/// 設定は、保存先（`ConfigScope`）のルートキーの `Software\MyDNSAdapter` の下に、
+/// MasterIDごとのサブキーとして保存します。
+#[derive(Clone, Debug)]
+pub struct RegistryStore {
+    /// 読み書きするルートキー。
+    root: HKEY,
+    /// 画面やログに表示する、アカウントの設定を保存するキーの場所。
+    location: String,
+}
+
+impl RegistryStore {
+    /// 指定された保存先のレジストリを読み書きするストアを作成します。
+    pub fn new(scope: ConfigScope) -> Self {
+        let location = match scope {
+            ConfigScope::Machine => r"HKEY_LOCAL_MACHINE\Software\MyDNSAdapter",
+            ConfigScope::User => r"HKEY_CURRENT_USER\Software\MyDNSAdapter",
+        };
+        RegistryStore {
+            root: scope.hkey(),
+            location: location.to_string(),
+        }
+    }
+}
+
+/// 一時的に使うキー（`HKEY_CURRENT_USER\Software\MyDNSAdapterScratch`）のパス。
+const SCRATCH_KEY_PATH: &str = "Software\\MyDNSAdapterScratch";
+
+/// ベンチマークなどで一時的に使う、実際の保存先とは別のレジストリのキー。
+///
+/// このキーをルートキーとする `RegistryStore` は、実際のアカウントにも、
+/// 稼働中のサービスが監視しているキーにも影響しません。破棄（`drop`）されると、キーごと削除します。
+pub struct ScratchRegistryKey(HKEY);
+
+impl ScratchRegistryKey {
+    /// 一時的に使うキーを作成します。前回の残りがあれば、そのまま開きます。
+    pub fn create() -> windows::core::Result<Self> {
+        let subkey = HSTRING::from(SCRATCH_KEY_PATH);
+        // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+        // オープンしたキーのハンドルは `drop` でクローズします。
+        unsafe {
+            let mut hkey: HKEY = HKEY::default();
+            RegCreateKeyExW(
+                HKEY_CURRENT_USER,
+                PCWSTR(subkey.as_ptr()),
+                0,
+                None,
+                REG_OPTION_NON_VOLATILE,
+                KEY_ALL_ACCESS | REGISTRY_VIEW,
+                None,
+                &mut hkey,
+                None,
+            )
+            .ok()?;
+            Ok(ScratchRegistryKey(hkey))
+        }
+    }
+
+    /// このキーの下を読み書きするストアを返します。
+    pub fn store(&self) -> RegistryStore {
+        RegistryStore {
+            root: self.0,
+            location: format!(
+                "HKEY_CURRENT_USER\\{}\\Software\\MyDNSAdapter",
+                SCRATCH_KEY_PATH
+            ),
+        }
+    }
+}
+
+impl Drop for ScratchRegistryKey {
+    fn drop(&mut self) {
+        // 中身を削除してからハンドルをクローズし、空になったキー自体を削除する。
+        let subkey = HSTRING::from(SCRATCH_KEY_PATH);
+        unsafe {
+            let _ = RegDeleteTreeW(self.0, PCWSTR::null());
+            let _ = RegCloseKey(self.0);
+            let _ = RegDeleteKeyExW(
+                HKEY_CURRENT_USER,
+                PCWSTR(subkey.as_ptr()),
+                REGISTRY_VIEW.0,
+                0,
+            );
+        }
+    }
+}
+
+impl ConfigStore for RegistryStore {
+    fn load_all(&self) -> Result<Vec<Config>, AdapterError> {
+        Ok(load_configs(self.root)?)
+    }
+
+    fn save(&self, config: &Config) -> Result<(), AdapterError> {
+        Ok(save_config_key(self.root, config)?)
+    }
+
+    fn delete(&self, master_id: &str) -> Result<(), AdapterError> {
+        Ok(delete_config_key(self.root, master_id)?)
+    }
+
+    fn rename(&self, old_id: &str, new_id: &str) -> Result<(), AdapterError> {
+        Ok(rename_config_key(self.root, old_id, new_id)?)
+    }
+
+    fn watch(&self, on_change: Box<dyn Fn() + Send>) -> Result<StoreWatcher, AdapterError> {
+        Ok(Box::new(RegistryChangeWatcher::start(
+            self.root, on_change,
+        )?))
+    }
+
+    fn save_last_success(
+        &self,
+        master_id: &str,
+        time: DateTime<Local>,
+        addresses: &[IpAddr],
+    ) -> Result<(), AdapterError> {
+        Ok(save_last_success_key(
+            self.root, master_id, time, addresses,
+        )?)
+    }
+
+    fn load_last_success(&self, master_id: &str) -> LastSuccess {
+        load_last_success_key(self.root, master_id)
+    }
+
+    fn save_last_error(
+        &self,
+        master_id: &str,
+        time: DateTime<Local>,
+        kind: &str,
+        detail: &str,
+    ) -> Result<(), AdapterError> {
+        Ok(save_last_error_key(
+            self.root, master_id, time, kind, detail,
+        )?)
+    }
+
+    fn load_last_error(&self, master_id: &str) -> Option<LastError> {
+        load_last_error_key(self.root, master_id)
+    }
+
+    fn save_suspended(
+        &self,
+        master_id: &str,
+        time: Option<DateTime<Local>>,
+    ) -> Result<(), AdapterError> {
+        Ok(save_suspended_key(self.root, master_id, time)?)
+    }
+
+    fn load_suspended(&self, master_id: &str) -> Option<DateTime<Local>> {
+        load_suspended_key(self.root, master_id)
+    }
+
+    fn location(&self) -> String {
+        self.location.clone()
+    }
 }
 
 /// レジストリからすべての設定を読み込みます。
 ///
-/// `HKLM\Software\MyDNSAdapter` の下の各サブキーを個別の設定として読み込み、
+/// `root` の `Software\MyDNSAdapter` の下の各サブキーを個別の設定として読み込み、
 /// `Config` 構造体のベクターとして返します。
-pub fn load_all_configs() -> windows::core::Result<Vec<Config>> {
+fn load_configs(root: HKEY) -> windows::core::Result<Vec<Config>> {
     // Win32 APIを直接呼び出すため、unsafeブロックが必要。
     // 各API呼び出しはWindowsのドキュメントに従っており、
     // ハンドルのライフサイクル管理（オープンとクローズ）も適切に行われているため安全です。
@@ -43,7 +741,13 @@ pub fn load_all_configs() -> windows::core::Result<Vec<Config>> {
         let subkey_root = w!("Software\\MyDNSAdapter");
 
         // ルートキーを開く
-        let result = RegOpenKeyExW(HKEY_LOCAL_MACHINE, subkey_root, 0, KEY_READ, &mut hkey_root);
+        let result = RegOpenKeyExW(
+            root,
+            subkey_root,
+            0,
+            KEY_READ | REGISTRY_VIEW,
+            &mut hkey_root,
+        );
         // ルートキーが存在しない場合は、設定がまだないと判断し、空のVecを返す。
         if result == ERROR_FILE_NOT_FOUND {
             return Ok(configs);
@@ -93,7 +797,7 @@ pub fn load_all_configs() -> windows::core::Result<Vec<Config>> {
                 hkey_root,
                 PCWSTR(sub_name.as_ptr()),
                 0,
-                KEY_READ,
+                KEY_READ | REGISTRY_VIEW,
                 &mut hkey_sub,
             ) == WIN32_ERROR(0)
             {
@@ -102,6 +806,24 @@ pub fn load_all_configs() -> windows::core::Result<Vec<Config>> {
                 let password = get_reg_string(hkey_sub, "Password").unwrap_or_default();
                 let ipv4_notify_val = get_reg_dword(hkey_sub, "IPv4Notify").unwrap_or(0);
                 let ipv6_notify_val = get_reg_dword(hkey_sub, "IPv6Notify").unwrap_or(0);
+                // 古いバージョンで作成された設定には値がないため、既定値を使用する。
+                let keep_alive_hours = get_reg_dword_opt(hkey_sub, "KeepAliveHours")
+                    .unwrap_or(DEFAULT_KEEP_ALIVE_HOURS);
+                let proxy_url = get_reg_string(hkey_sub, "ProxyUrl").unwrap_or_default();
+                let ipv4_url = get_reg_string(hkey_sub, "IPv4Url").unwrap_or_default();
+                let ipv6_url = get_reg_string(hkey_sub, "IPv6Url").unwrap_or_default();
+                let hostname = get_reg_string(hkey_sub, "Hostname").unwrap_or_default();
+                let provider = get_reg_string(hkey_sub, "Provider")
+                    .ok()
+                    .and_then(|code| Provider::from_code(&code))
+                    .unwrap_or_default();
+                let method = get_reg_string(hkey_sub, "Method")
+                    .ok()
+                    .and_then(|code| NotifyMethod::from_code(&code))
+                    .unwrap_or_default();
+                let ping_url = get_reg_string(hkey_sub, "PingUrl").unwrap_or_default();
+                let bind_interface = get_reg_string(hkey_sub, "BindInterface").unwrap_or_default();
+                let schedule = get_reg_string(hkey_sub, "Schedule").unwrap_or_default();
 
                 // 取得した値からConfig構造体を生成し、ベクターに追加する。
                 // 取得した設定をベクターに追加
@@ -110,6 +832,16 @@ pub fn load_all_configs() -> windows::core::Result<Vec<Config>> {
                     password,
                     ipv4_notify: ipv4_notify_val == 1,
                     ipv6_notify: ipv6_notify_val == 1,
+                    keep_alive_hours,
+                    proxy_url,
+                    ipv4_url,
+                    ipv6_url,
+                    hostname,
+                    provider,
+                    method,
+                    ping_url,
+                    bind_interface,
+                    schedule,
                 });
                 // 開いたサブキーのハンドルをクローズする。
                 let _ = RegCloseKey(hkey_sub);
@@ -175,6 +907,12 @@ fn get_reg_string(hkey: HKEY, name: &str) -> windows::core::Result<String> {
 /// レジストリキーからREG_DWORD（32ビット数値）型の値を取得します。
 /// 値が存在しないか、型が異なる場合は0を返します。
 fn get_reg_dword(hkey: HKEY, name: &str) -> windows::core::Result<u32> {
+    Ok(get_reg_dword_opt(hkey, name).unwrap_or(0))
+}
+
+/// レジストリキーからREG_DWORD（32ビット数値）型の値を取得します。
+/// 値が存在しないか、型が異なる場合は`None`を返します。
+fn get_reg_dword_opt(hkey: HKEY, name: &str) -> Option<u32> {
     // Win32 APIを直接呼び出すため、unsafeブロックが必要。
     // ポインタの指す先はスタック上の`data`変数であり、そのサイズも
     // 正しく指定しているため安全です。
@@ -195,51 +933,366 @@ fn get_reg_dword(hkey: HKEY, name: &str) -> windows::core::Result<u32> {
             Some(&mut data_size),
         );
 
-        // 値が存在しない、または型がREG_DWORDでない場合は0を返す。
+        // 値が存在しない、または型がREG_DWORDでない場合はNoneを返す。
         if res != WIN32_ERROR(0) || data_type != REG_DWORD {
-            return Ok(0);
+            return None;
         }
 
-        Ok(data)
+        Some(data)
+    }
+}
+
+/// レジストリキーからREG_QWORD（64ビット数値）型の値を取得します。
+/// 値が存在しないか、型が異なる場合は`None`を返します。
+fn get_reg_qword_opt(hkey: HKEY, name: &str) -> Option<u64> {
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // ポインタの指す先はスタック上の`data`変数であり、そのサイズも
+    // 正しく指定しているため安全です。
+    unsafe {
+        let name_hstring = HSTRING::from(name);
+        let mut data: u64 = 0;
+        let mut data_size: u32 = std::mem::size_of::<u64>() as u32;
+        let mut data_type = REG_VALUE_TYPE::default();
+
+        let data_ptr = &mut data as *mut u64 as *mut u8;
+        let res = RegQueryValueExW(
+            hkey,
+            &name_hstring,
+            None,
+            Some(&mut data_type),
+            Some(data_ptr),
+            Some(&mut data_size),
+        );
+
+        // 値が存在しない、または型がREG_QWORDでない場合はNoneを返す。
+        if res != WIN32_ERROR(0) || data_type != REG_QWORD {
+            return None;
+        }
+
+        Some(data)
     }
 }
 
 /// 指定された設定をレジストリに保存します。
 ///
 /// 既存のキーがあれば上書きし、なければ新規作成します。
-pub fn save_to_registry(id: &str, pw: &str, v4: bool, v6: bool) -> windows::core::Result<()> {
+/// 途中で失敗した場合、アカウントの設定は保存前の状態のまま残ります。
+fn save_config_key(root: HKEY, config: &Config) -> windows::core::Result<()> {
+    // Software\MyDNSAdapter\<id> のキーに、すべての値をひとつのトランザクションで書き込む。
+    let path = format!("Software\\MyDNSAdapter\\{}", config.master_id);
+    write_key_atomically(root, &path, |hkey| {
+        set_reg_string(hkey, w!("Password"), &config.password)?;
+        set_reg_dword(
+            hkey,
+            w!("IPv4Notify"),
+            if config.ipv4_notify { 1 } else { 0 },
+        )?;
+        set_reg_dword(
+            hkey,
+            w!("IPv6Notify"),
+            if config.ipv6_notify { 1 } else { 0 },
+        )?;
+        set_reg_dword(hkey, w!("KeepAliveHours"), config.keep_alive_hours)?;
+        set_reg_string(hkey, w!("ProxyUrl"), &config.proxy_url)?;
+        set_reg_string(hkey, w!("IPv4Url"), &config.ipv4_url)?;
+        set_reg_string(hkey, w!("IPv6Url"), &config.ipv6_url)?;
+        set_reg_string(hkey, w!("Hostname"), &config.hostname)?;
+        set_reg_string(hkey, w!("Provider"), config.provider.code())?;
+        set_reg_string(hkey, w!("Method"), config.method.code())?;
+        set_reg_string(hkey, w!("PingUrl"), &config.ping_url)?;
+        set_reg_string(hkey, w!("BindInterface"), &config.bind_interface)?;
+        set_reg_string(hkey, w!("Schedule"), &config.schedule)
+    })
+}
+
+/// 通知に成功した時刻と、MyDNS.JPが受け付けたアドレスをアカウントの記録のキーに記録します。
+///
+/// `addresses` に含まれないプロトコルのアドレスは、前回の値を残します。
+fn save_last_success_key(
+    root: HKEY,
+    master_id: &str,
+    time: DateTime<Local>,
+    addresses: &[IpAddr],
+) -> windows::core::Result<()> {
+    write_account_record(root, master_id, |hkey| {
+        set_reg_qword(hkey, w!("LastSuccess"), to_filetime(time))?;
+        for address in addresses {
+            match address {
+                IpAddr::V4(v4) => set_reg_string(hkey, w!("LastIPv4"), &v4.to_string())?,
+                IpAddr::V6(v6) => set_reg_string(hkey, w!("LastIPv6"), &v6.to_string())?,
+            }
+        }
+        Ok(())
+    })
+}
+
+/// アカウントの記録のキーに記録された、最後に通知に成功した時刻とアドレスを読み込みます。
+///
+/// キーを開けない場合や記録がない場合は、その項目を `None` として返します。
+fn load_last_success_key(root: HKEY, master_id: &str) -> LastSuccess {
+    read_account_record(root, master_id, |hkey| LastSuccess {
+        time: get_reg_qword_opt(hkey, "LastSuccess").and_then(from_filetime),
+        ipv4: get_reg_string(hkey, "LastIPv4")
+            .ok()
+            .and_then(|s| s.parse().ok()),
+        ipv6: get_reg_string(hkey, "LastIPv6")
+            .ok()
+            .and_then(|s| s.parse().ok()),
+    })
+    .unwrap_or_default()
+}
+
+/// 通知に失敗した時刻と、失敗の分類と内容をアカウントの記録のキーに記録します。
+///
+/// 記録するのは最後の1件だけで、成功しても消さずに残します。
+fn save_last_error_key(
+    root: HKEY,
+    master_id: &str,
+    time: DateTime<Local>,
+    kind: &str,
+    detail: &str,
+) -> windows::core::Result<()> {
+    write_account_record(root, master_id, |hkey| {
+        set_reg_qword(hkey, w!("LastErrorTime"), to_filetime(time))?;
+        set_reg_string(hkey, w!("LastErrorKind"), kind)?;
+        set_reg_string(hkey, w!("LastError"), detail)
+    })
+}
+
+/// アカウントの記録のキーに記録された、最後に通知に失敗した時刻と内容を読み込みます。
+///
+/// キーを開けない場合や記録がない場合は、`None` を返します。
+fn load_last_error_key(root: HKEY, master_id: &str) -> Option<LastError> {
+    read_account_record(root, master_id, |hkey| {
+        Some(LastError {
+            time: get_reg_qword_opt(hkey, "LastErrorTime").and_then(from_filetime)?,
+            kind: get_reg_string(hkey, "LastErrorKind").unwrap_or_default(),
+            detail: get_reg_string(hkey, "LastError").unwrap_or_default(),
+        })
+    })
+    .flatten()
+}
+
+/// アカウントの通知を自動で停止した時刻を、アカウントの記録のキーに記録します。
+///
+/// `None` を指定すると記録を消し、通知を再開します。値は0を書き込んで消します。
+fn save_suspended_key(
+    root: HKEY,
+    master_id: &str,
+    time: Option<DateTime<Local>>,
+) -> windows::core::Result<()> {
+    write_account_record(root, master_id, |hkey| {
+        set_reg_qword(hkey, w!("SuspendedTime"), time.map_or(0, to_filetime))
+    })
+}
+
+/// アカウントの記録のキーに記録された、通知を自動で停止した時刻を読み込みます。
+///
+/// 停止されていない場合や、キーを開けない場合は `None` を返します。
+fn load_suspended_key(root: HKEY, master_id: &str) -> Option<DateTime<Local>> {
+    read_account_record(root, master_id, |hkey| {
+        get_reg_qword_opt(hkey, "SuspendedTime")
+            .filter(|filetime| *filetime != 0)
+            .and_then(from_filetime)
+    })
+    .flatten()
+}
+
+/// 以前のバージョンがアカウントのキーに記録していた、REG_QWORD型の記録の値。
+const LEGACY_RECORD_QWORDS: &[&str] = &["LastSuccess", "LastErrorTime", "SuspendedTime"];
+/// 以前のバージョンがアカウントのキーに記録していた、REG_SZ型の記録の値。
+const LEGACY_RECORD_STRINGS: &[&str] = &["LastIPv4", "LastIPv6", "LastErrorKind", "LastError"];
+
+/// アカウントの記録のキーに、通知の結果の記録を書き込みます。
+///
+/// 書き込みはひとつのトランザクションで行います。
+/// 通知中にアカウントが削除された場合に記録を作り直さないよう、アカウントのキーがある場合にだけ書き込みます。
+/// 記録のキーを新しく作成した場合は、以前のバージョンがアカウントのキーに記録した値を先にコピーします。
+fn write_account_record<F>(root: HKEY, master_id: &str, write: F) -> windows::core::Result<()>
+where
+    F: FnOnce(HKEY) -> windows::core::Result<()>,
+{
+    let transaction = RegTransaction::begin()?;
+    let account_path = HSTRING::from(format!("Software\\MyDNSAdapter\\{}", master_id));
+    let record_path = HSTRING::from(format!("{}\\{}", RECORD_KEY_PATH, master_id));
     // Win32 APIを直接呼び出すため、unsafeブロックが必要。
-    // 作成・オープンしたレジストリキーのハンドルは、関数の最後で
-    // `RegCloseKey`により確実にクローズされるため安全です。
+    // オープンしたキーのハンドルは、書き込みの成否にかかわらず確定前にクローズします。
     unsafe {
+        let mut account: HKEY = HKEY::default();
+        RegOpenKeyTransactedW(
+            root,
+            PCWSTR(account_path.as_ptr()),
+            0,
+            KEY_READ | REGISTRY_VIEW,
+            &mut account,
+            transaction.0,
+            None,
+        )
+        .ok()?;
         let mut hkey: HKEY = HKEY::default();
-        // HKLM\Software\MyDNSAdapter\<id> のパスを作成
-        let path = format!("Software\\MyDNSAdapter\\{}", id);
-        let subkey = HSTRING::from(&path);
+        let mut disposition = Default::default();
+        let result = RegCreateKeyTransactedW(
+            root,
+            PCWSTR(record_path.as_ptr()),
+            0,
+            None,
+            REG_OPTION_NON_VOLATILE,
+            KEY_WRITE | REGISTRY_VIEW,
+            None,
+            &mut hkey,
+            Some(&mut disposition),
+            transaction.0,
+            None,
+        )
+        .ok()
+        .and_then(|()| {
+            if disposition == REG_CREATED_NEW_KEY {
+                copy_legacy_records(account, hkey)?;
+            }
+            write(hkey)
+        });
+        if !hkey.is_invalid() {
+            let _ = RegCloseKey(hkey);
+        }
+        let _ = RegCloseKey(account);
+        result?;
+    }
+    transaction.commit()
+}
 
-        // キーを作成または開く。書き込み権限を要求する。
-        RegCreateKeyExW(
-            HKEY_LOCAL_MACHINE,
+/// アカウントのキーに残っている、以前のバージョンの記録の値を記録のキーにコピーします。
+fn copy_legacy_records(account: HKEY, record: HKEY) -> windows::core::Result<()> {
+    for &name in LEGACY_RECORD_QWORDS {
+        if let Some(value) = get_reg_qword_opt(account, name) {
+            let name = HSTRING::from(name);
+            set_reg_qword(record, PCWSTR(name.as_ptr()), value)?;
+        }
+    }
+    for &name in LEGACY_RECORD_STRINGS {
+        let value = get_reg_string(account, name).unwrap_or_default();
+        if !value.is_empty() {
+            let name = HSTRING::from(name);
+            set_reg_string(record, PCWSTR(name.as_ptr()), &value)?;
+        }
+    }
+    Ok(())
+}
+
+/// アカウントの記録のキーを読み取り専用で開き、`read` で通知の結果の記録を読み込みます。
+///
+/// 記録のキーがまだない場合は、以前のバージョンが記録したアカウントのキーから読み込みます。
+/// どちらのキーも開けない場合は `None` を返します。
+fn read_account_record<T, F>(root: HKEY, master_id: &str, read: F) -> Option<T>
+where
+    F: FnOnce(HKEY) -> T,
+{
+    let record_path = HSTRING::from(format!("{}\\{}", RECORD_KEY_PATH, master_id));
+    let account_path = HSTRING::from(format!("Software\\MyDNSAdapter\\{}", master_id));
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // オープンしたキーのハンドルは、読み込み後にクローズします。
+    unsafe {
+        let mut hkey: HKEY = HKEY::default();
+        let opened = [&record_path, &account_path].into_iter().any(|path| {
+            RegOpenKeyExW(
+                root,
+                PCWSTR(path.as_ptr()),
+                0,
+                KEY_READ | REGISTRY_VIEW,
+                &mut hkey,
+            ) == WIN32_ERROR(0)
+        });
+        if !opened {
+            return None;
+        }
+        let value = read(hkey);
+        let _ = RegCloseKey(hkey);
+        Some(value)
+    }
+}
+
+/// 日時を、FILETIME（1601年1月1日からの100ナノ秒単位の時間）に変換します。
+fn to_filetime(time: DateTime<Local>) -> u64 {
+    (time.timestamp_micros() * 10 + FILETIME_UNIX_EPOCH) as u64
+}
+
+/// FILETIMEを日時に変換します。表現できない値の場合は `None` を返します。
+fn from_filetime(filetime: u64) -> Option<DateTime<Local>> {
+    let micros = (i64::try_from(filetime).ok()? - FILETIME_UNIX_EPOCH) / 10;
+    DateTime::from_timestamp_micros(micros).map(|t| t.with_timezone(&Local))
+}
+
+/// レジストリのトランザクション（KTM）のハンドル。
+///
+/// `commit` されずに破棄（`drop`）された場合、トランザクション中の変更はすべて取り消されます。
+struct RegTransaction(HANDLE);
+
+impl RegTransaction {
+    /// 新しいトランザクションを開始します。
+    fn begin() -> windows::core::Result<Self> {
+        // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+        // 作成したハンドルは `drop` でクローズします。
+        unsafe {
+            CreateTransaction(
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                0,
+                0,
+                0,
+                0,
+                w!("MyDNSAdapter settings"),
+            )
+            .map(RegTransaction)
+        }
+    }
+
+    /// トランザクション中の変更を確定します。
+    fn commit(self) -> windows::core::Result<()> {
+        unsafe { CommitTransaction(self.0) }
+    }
+}
+
+impl Drop for RegTransaction {
+    fn drop(&mut self) {
+        // 確定前に最後のハンドルをクローズすると、トランザクションはロールバックされる。
+        unsafe {
+            let _ = CloseHandle(self.0);
+        }
+    }
+}
+
+/// `root` の `path` のキーを作成または開き、`write` で値を書き込みます。
+///
+/// 書き込みはひとつのトランザクションで行うため、途中で失敗したりプロセスが終了したりしても、
+/// キーは「すべて書き込まれた」か「まったく変更されていない」かのどちらかになります。
+fn write_key_atomically<F>(root: HKEY, path: &str, write: F) -> windows::core::Result<()>
+where
+    F: FnOnce(HKEY) -> windows::core::Result<()>,
+{
+    let transaction = RegTransaction::begin()?;
+    let subkey = HSTRING::from(path);
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // オープンしたキーのハンドルは、書き込みの成否にかかわらず確定前にクローズします。
+    unsafe {
+        let mut hkey: HKEY = HKEY::default();
+        RegCreateKeyTransactedW(
+            root,
             PCWSTR(subkey.as_ptr()),
             0,
             None,
             REG_OPTION_NON_VOLATILE,
-            KEY_WRITE,
+            KEY_WRITE | REGISTRY_VIEW,
             None,
             &mut hkey,
             None,
+            transaction.0,
+            None,
         )
         .ok()?;
-
-        // 各値を設定する
-        set_reg_string(hkey, w!("Password"), pw)?;
-        set_reg_dword(hkey, w!("IPv4Notify"), if v4 { 1 } else { 0 })?;
-        set_reg_dword(hkey, w!("IPv6Notify"), if v6 { 1 } else { 0 })?;
-
-        // 開いたキーのハンドルをクローズする。
+        let result = write(hkey);
         let _ = RegCloseKey(hkey);
-        Ok(())
+        result?;
     }
+    transaction.commit()
 }
 
 /// レジストリキーにREG_SZ（文字列）型の値を設定します。
@@ -269,8 +1322,24 @@ fn set_reg_dword(hkey: HKEY, name: PCWSTR, value: u32) -> windows::core::Result<
     }
 }
 
+/// レジストリキーにREG_QWORD（64ビット数値）型の値を設定します。
+fn set_reg_qword(hkey: HKEY, name: PCWSTR, value: u64) -> windows::core::Result<()> {
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // `bytemuck::cast_slice` を使ってu64の値を安全にバイトスライスに変換している。
+    unsafe {
+        RegSetValueExW(
+            hkey,
+            name,
+            0,
+            REG_QWORD,
+            Some(bytemuck::cast_slice(&[value])),
+        )
+        .ok()
+    }
+}
+
 /// 指定されたIDの設定をレジストリから削除します。
-pub fn delete_config(id: &str) -> windows::core::Result<()> {
+fn delete_config_key(root: HKEY, id: &str) -> windows::core::Result<()> {
     // Win32 APIを直接呼び出すため、unsafeブロックが必要。
     // オープンしたレジストリキーのハンドルは、関数の最後で
     // `RegCloseKey`により確実にクローズされるため安全です。
@@ -279,13 +1348,469 @@ pub fn delete_config(id: &str) -> windows::core::Result<()> {
         let subkey_root = w!("Software\\MyDNSAdapter");
 
         // 親キーを書き込み権限で開く（サブキーの削除に必要）。
-        RegOpenKeyExW(HKEY_LOCAL_MACHINE, subkey_root, 0, KEY_WRITE, &mut hkey).ok()?;
+        RegOpenKeyExW(root, subkey_root, 0, KEY_WRITE | REGISTRY_VIEW, &mut hkey).ok()?;
 
         let subkey_to_delete = HSTRING::from(id);
         // 指定されたサブキーを削除する。
-        let res = RegDeleteKeyW(hkey, PCWSTR(subkey_to_delete.as_ptr()));
+        let res = RegDeleteKeyExW(hkey, PCWSTR(subkey_to_delete.as_ptr()), REGISTRY_VIEW.0, 0);
 
         let _ = RegCloseKey(hkey);
-        res.ok()
+        res.ok()?;
+
+        // 通知の結果の記録も削除する。記録がない場合や削除できない場合は無視する。
+        let record = HSTRING::from(format!("{}\\{}", RECORD_KEY_PATH, id));
+        let _ = RegDeleteKeyExW(root, PCWSTR(record.as_ptr()), REGISTRY_VIEW.0, 0);
+        Ok(())
+    }
+}
+
+/// 現在の保存先の `Software\MyDNSAdapter` キーを、すべてのアカウントと設定ごと削除します。
+/// 通知の結果の記録（`Software\MyDNSAdapterRecords`）も削除します。
+///
+/// キーが存在しない場合は、削除済みとみなして成功を返します。
+pub fn delete_all_settings() -> windows::core::Result<()> {
+    delete_key_tree(root_hkey(), "Software\\MyDNSAdapter")?;
+    delete_key_tree(root_hkey(), RECORD_KEY_PATH)
+}
+
+/// `root` の `path` のキーを、その下のキーと値ごと削除します。
+///
+/// キーが存在しない場合は、削除済みとみなして成功を返します。
+fn delete_key_tree(root: HKEY, path: &str) -> windows::core::Result<()> {
+    let path = HSTRING::from(path);
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // オープンしたキーのハンドルは、中身の削除後にクローズします。
+    unsafe {
+        let mut hkey: HKEY = HKEY::default();
+        // `RegDeleteTreeW` はビューを指定できないため、ビューを指定して開いたキーの中身を削除してから、
+        // 空になったキー自体を削除する。
+        let result = RegOpenKeyExW(
+            root,
+            PCWSTR(path.as_ptr()),
+            0,
+            KEY_ALL_ACCESS | REGISTRY_VIEW,
+            &mut hkey,
+        );
+        if result == ERROR_FILE_NOT_FOUND {
+            return Ok(());
+        }
+        result.ok()?;
+        let result = RegDeleteTreeW(hkey, PCWSTR::null());
+        let _ = RegCloseKey(hkey);
+        result.ok()?;
+        RegDeleteKeyExW(root, PCWSTR(path.as_ptr()), REGISTRY_VIEW.0, 0).ok()
+    }
+}
+
+/// アカウントのキーの名前（MasterID）を変更します。
+///
+/// 古いキーのすべての値を新しいキーにコピーしてから古いキーを削除する処理を、ひとつのトランザクションで行います。
+/// 途中で失敗した場合、レジストリは変更前の状態のまま残ります。
+/// 新しいMasterIDのキーが既に存在する場合は、`ERROR_ALREADY_EXISTS` のエラーを返します。
+/// 通知の結果の記録のキーも、同じトランザクションで新しいMasterIDに移します。
+fn rename_config_key(root: HKEY, old_id: &str, new_id: &str) -> windows::core::Result<()> {
+    let transaction = RegTransaction::begin()?;
+    move_subkey(root, "Software\\MyDNSAdapter", old_id, new_id, &transaction)?;
+
+    let old_record = HSTRING::from(format!("{}\\{}", RECORD_KEY_PATH, old_id));
+    let new_record = HSTRING::from(format!("{}\\{}", RECORD_KEY_PATH, new_id));
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // 存在を確かめるために開いたキーのハンドルは、すぐにクローズします。
+    let has_record = unsafe {
+        // 削除されたアカウントの記録が残っていれば、新しいMasterIDに引き継がないよう先に削除する。
+        let _ = RegDeleteKeyTransactedW(
+            root,
+            PCWSTR(new_record.as_ptr()),
+            REGISTRY_VIEW.0,
+            0,
+            transaction.0,
+            None,
+        );
+        let mut hkey: HKEY = HKEY::default();
+        let opened = RegOpenKeyTransactedW(
+            root,
+            PCWSTR(old_record.as_ptr()),
+            0,
+            KEY_READ | REGISTRY_VIEW,
+            &mut hkey,
+            transaction.0,
+            None,
+        ) == WIN32_ERROR(0);
+        if opened {
+            let _ = RegCloseKey(hkey);
+        }
+        opened
+    };
+    if has_record {
+        move_subkey(root, RECORD_KEY_PATH, old_id, new_id, &transaction)?;
+    }
+    transaction.commit()
+}
+
+/// `root` の `parent` のキーの下で、`old_id` のサブキーを `new_id` に移します。
+///
+/// 新しいサブキーにすべての値をコピーしてから古いサブキーを削除します。操作は `transaction` に含まれます。
+/// `new_id` のサブキーが既に存在する場合は、`ERROR_ALREADY_EXISTS` のエラーを返します。
+fn move_subkey(
+    root: HKEY,
+    parent: &str,
+    old_id: &str,
+    new_id: &str,
+    transaction: &RegTransaction,
+) -> windows::core::Result<()> {
+    let parent_path = HSTRING::from(parent);
+    let old_subkey = HSTRING::from(old_id);
+    let new_subkey = HSTRING::from(new_id);
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // トランザクションの中で開いたキーの操作は、すべてそのトランザクションに含まれます。
+    // オープンしたキーのハンドルは、成否にかかわらず確定前にクローズします。
+    unsafe {
+        let mut parent: HKEY = HKEY::default();
+        RegOpenKeyTransactedW(
+            root,
+            PCWSTR(parent_path.as_ptr()),
+            0,
+            KEY_READ | KEY_WRITE | REGISTRY_VIEW,
+            &mut parent,
+            transaction.0,
+            None,
+        )
+        .ok()?;
+        let result = (|| {
+            let mut old_key: HKEY = HKEY::default();
+            RegOpenKeyTransactedW(
+                parent,
+                PCWSTR(old_subkey.as_ptr()),
+                0,
+                KEY_READ | REGISTRY_VIEW,
+                &mut old_key,
+                transaction.0,
+                None,
+            )
+            .ok()?;
+            let mut new_key: HKEY = HKEY::default();
+            let mut disposition = Default::default();
+            let created = RegCreateKeyTransactedW(
+                parent,
+                PCWSTR(new_subkey.as_ptr()),
+                0,
+                None,
+                REG_OPTION_NON_VOLATILE,
+                KEY_WRITE | REGISTRY_VIEW,
+                None,
+                &mut new_key,
+                Some(&mut disposition),
+                transaction.0,
+                None,
+            )
+            .ok();
+            let copied = created.and_then(|()| {
+                // 既存のアカウントを上書きしないよう、新しく作成した場合だけコピーする。
+                if disposition != REG_CREATED_NEW_KEY {
+                    return Err(windows::core::Error::from(ERROR_ALREADY_EXISTS));
+                }
+                RegCopyTreeW(old_key, None, new_key).ok()
+            });
+            let _ = RegCloseKey(old_key);
+            if !new_key.is_invalid() {
+                let _ = RegCloseKey(new_key);
+            }
+            copied?;
+            RegDeleteKeyTransactedW(
+                parent,
+                PCWSTR(old_subkey.as_ptr()),
+                REGISTRY_VIEW.0,
+                0,
+                transaction.0,
+                None,
+            )
+            .ok()
+        })();
+        let _ = RegCloseKey(parent);
+        result
+    }
+}
+
+/// アプリケーション全体の設定をレジストリから読み込みます。
+///
+/// キーや値が存在しない場合は、既定値を返します。
+pub fn load_settings() -> windows::core::Result<Settings> {
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // オープンしたレジストリキーのハンドルは、関数の最後で
+    // `RegCloseKey`により確実にクローズされるため安全です。
+    unsafe {
+        let mut hkey: HKEY = HKEY::default();
+        let subkey_root = w!("Software\\MyDNSAdapter");
+
+        let result = RegOpenKeyExW(
+            root_hkey(),
+            subkey_root,
+            0,
+            KEY_READ | REGISTRY_VIEW,
+            &mut hkey,
+        );
+        // ルートキーが存在しない場合は、すべて既定値とする。
+        if result == ERROR_FILE_NOT_FOUND {
+            return Ok(Settings::default());
+        }
+        result.ok()?;
+
+        let settings = Settings {
+            stats_enabled: get_reg_dword(hkey, "StatsEnabled").unwrap_or(0) == 1,
+            // 値がない場合や範囲外の値が書き込まれている場合は、既定値とする。
+            notify_interval_minutes: get_reg_dword_opt(hkey, "NotifyIntervalMinutes")
+                .filter(|m| NOTIFY_INTERVAL_RANGE_MINUTES.contains(m))
+                .unwrap_or(DEFAULT_NOTIFY_INTERVAL_MINUTES),
+            retry_max_attempts: get_reg_dword_opt(hkey, "RetryMaxAttempts")
+                .filter(|n| RETRY_MAX_ATTEMPTS_RANGE.contains(n))
+                .unwrap_or(DEFAULT_RETRY_MAX_ATTEMPTS),
+            notify_concurrency: get_reg_dword_opt(hkey, "NotifyConcurrency")
+                .filter(|n| NOTIFY_CONCURRENCY_RANGE.contains(n))
+                .unwrap_or(DEFAULT_NOTIFY_CONCURRENCY),
+            request_timeout_seconds: get_reg_dword_opt(hkey, "RequestTimeoutSeconds")
+                .filter(|n| REQUEST_TIMEOUT_RANGE_SECONDS.contains(n))
+                .unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECONDS),
+            proxy_url: get_reg_string(hkey, "ProxyUrl").unwrap_or_default(),
+            log_path: get_reg_string(hkey, "LogPath").unwrap_or_default(),
+            log_archive_count: get_reg_dword_opt(hkey, "LogArchiveCount")
+                .filter(|n| LOG_ARCHIVE_COUNT_RANGE.contains(n))
+                .unwrap_or(DEFAULT_LOG_ARCHIVE_COUNT),
+            log_archive_compress: get_reg_dword(hkey, "LogArchiveCompress").unwrap_or(0) == 1,
+            metrics_port: get_reg_dword_opt(hkey, "MetricsPort")
+                .and_then(|p| u16::try_from(p).ok())
+                .unwrap_or(0),
+            admin_api_port: get_reg_dword_opt(hkey, "AdminApiPort")
+                .and_then(|p| u16::try_from(p).ok())
+                .unwrap_or(0),
+            admin_api_token: get_reg_string(hkey, "AdminApiToken").unwrap_or_default(),
+            ping_url: get_reg_string(hkey, "PingUrl").unwrap_or_default(),
+            webhook_url: get_reg_string(hkey, "WebhookUrl").unwrap_or_default(),
+            toast_failure_threshold: get_reg_dword_opt(hkey, "ToastFailureThreshold")
+                .unwrap_or(DEFAULT_TOAST_FAILURE_THRESHOLD),
+            smtp_server: get_reg_string(hkey, "SmtpServer").unwrap_or_default(),
+            smtp_port: get_reg_dword_opt(hkey, "SmtpPort")
+                .and_then(|p| u16::try_from(p).ok())
+                .filter(|p| *p != 0)
+                .unwrap_or(DEFAULT_SMTP_PORT),
+            smtp_username: get_reg_string(hkey, "SmtpUsername").unwrap_or_default(),
+            smtp_password: get_reg_string(hkey, "SmtpPassword").unwrap_or_default(),
+            mail_from: get_reg_string(hkey, "MailFrom").unwrap_or_default(),
+            mail_to: get_reg_string(hkey, "MailTo").unwrap_or_default(),
+            mail_after_minutes: get_reg_dword_opt(hkey, "MailAfterMinutes")
+                .unwrap_or(DEFAULT_MAIL_AFTER_MINUTES),
+            // URLの並びは、空白で区切って1つの文字列に保存している。
+            check_ipv4_urls: get_reg_string(hkey, "CheckIpv4Urls")
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+            check_ipv6_urls: get_reg_string(hkey, "CheckIpv6Urls")
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+            check_ip_poll_minutes: get_reg_dword(hkey, "CheckIpPollMinutes").unwrap_or(0),
+            skip_private_ipv4: get_reg_dword(hkey, "SkipPrivateIpv4").unwrap_or(0) == 1,
+            dns_check_minutes: get_reg_dword(hkey, "DnsCheckMinutes").unwrap_or(0),
+            metered_policy: get_reg_string(hkey, "MeteredPolicy")
+                .ok()
+                .and_then(|code| MeteredPolicy::from_code(&code))
+                .unwrap_or_default(),
+            // SSIDには空白を含められるため、1行に1つずつ保存する。
+            allowed_networks: get_reg_string(hkey, "AllowedNetworks")
+                .unwrap_or_default()
+                .lines()
+                .filter(|name| !name.trim().is_empty())
+                .map(|name| name.to_string())
+                .collect(),
+            quiet_hours: get_reg_string(hkey, "QuietHours")
+                .ok()
+                .and_then(|code| QuietHours::from_code(&code)),
+            jitter_seconds: get_reg_dword(hkey, "JitterSeconds").unwrap_or(0),
+            user_agent_id: get_reg_string(hkey, "UserAgentId").unwrap_or_default(),
+            history_database: get_reg_dword(hkey, "HistoryDatabase").unwrap_or(0) == 1,
+        };
+
+        let _ = RegCloseKey(hkey);
+        Ok(settings)
+    }
+}
+
+/// アプリケーション全体の設定をレジストリに保存します。
+pub fn save_settings(settings: &Settings) -> windows::core::Result<()> {
+    // ルートキーに、すべての値をひとつのトランザクションで書き込む。
+    write_key_atomically(root_hkey(), "Software\\MyDNSAdapter", |hkey| {
+        set_reg_dword(
+            hkey,
+            w!("StatsEnabled"),
+            if settings.stats_enabled { 1 } else { 0 },
+        )?;
+        set_reg_dword(
+            hkey,
+            w!("NotifyIntervalMinutes"),
+            settings.notify_interval_minutes,
+        )?;
+        set_reg_dword(hkey, w!("RetryMaxAttempts"), settings.retry_max_attempts)?;
+        set_reg_dword(hkey, w!("NotifyConcurrency"), settings.notify_concurrency)?;
+        set_reg_dword(
+            hkey,
+            w!("RequestTimeoutSeconds"),
+            settings.request_timeout_seconds,
+        )?;
+        set_reg_string(hkey, w!("ProxyUrl"), &settings.proxy_url)?;
+        set_reg_string(hkey, w!("LogPath"), &settings.log_path)?;
+        set_reg_dword(hkey, w!("LogArchiveCount"), settings.log_archive_count)?;
+        set_reg_dword(
+            hkey,
+            w!("LogArchiveCompress"),
+            if settings.log_archive_compress { 1 } else { 0 },
+        )?;
+        set_reg_dword(hkey, w!("MetricsPort"), settings.metrics_port.into())?;
+        set_reg_dword(hkey, w!("AdminApiPort"), settings.admin_api_port.into())?;
+        set_reg_string(hkey, w!("AdminApiToken"), &settings.admin_api_token)?;
+        set_reg_string(hkey, w!("PingUrl"), &settings.ping_url)?;
+        set_reg_string(hkey, w!("WebhookUrl"), &settings.webhook_url)?;
+        set_reg_dword(
+            hkey,
+            w!("ToastFailureThreshold"),
+            settings.toast_failure_threshold,
+        )?;
+        set_reg_string(hkey, w!("SmtpServer"), &settings.smtp_server)?;
+        set_reg_dword(hkey, w!("SmtpPort"), settings.smtp_port.into())?;
+        set_reg_string(hkey, w!("SmtpUsername"), &settings.smtp_username)?;
+        set_reg_string(hkey, w!("SmtpPassword"), &settings.smtp_password)?;
+        set_reg_string(hkey, w!("MailFrom"), &settings.mail_from)?;
+        set_reg_string(hkey, w!("MailTo"), &settings.mail_to)?;
+        set_reg_dword(hkey, w!("MailAfterMinutes"), settings.mail_after_minutes)?;
+        set_reg_string(
+            hkey,
+            w!("CheckIpv4Urls"),
+            &settings.check_ipv4_urls.join(" "),
+        )?;
+        set_reg_string(
+            hkey,
+            w!("CheckIpv6Urls"),
+            &settings.check_ipv6_urls.join(" "),
+        )?;
+        set_reg_dword(
+            hkey,
+            w!("CheckIpPollMinutes"),
+            settings.check_ip_poll_minutes,
+        )?;
+        set_reg_dword(
+            hkey,
+            w!("SkipPrivateIpv4"),
+            if settings.skip_private_ipv4 { 1 } else { 0 },
+        )?;
+        set_reg_dword(hkey, w!("DnsCheckMinutes"), settings.dns_check_minutes)?;
+        set_reg_string(hkey, w!("MeteredPolicy"), settings.metered_policy.code())?;
+        set_reg_string(
+            hkey,
+            w!("AllowedNetworks"),
+            &settings.allowed_networks.join("\n"),
+        )?;
+        set_reg_string(
+            hkey,
+            w!("QuietHours"),
+            &settings.quiet_hours.map(|q| q.code()).unwrap_or_default(),
+        )?;
+        set_reg_dword(hkey, w!("JitterSeconds"), settings.jitter_seconds)?;
+        set_reg_string(hkey, w!("UserAgentId"), &settings.user_agent_id)?;
+        set_reg_dword(
+            hkey,
+            w!("HistoryDatabase"),
+            if settings.history_database { 1 } else { 0 },
+        )
+    })
+}
+
+/// `--upgrade-config` で検証する、アカウントごとのREG_DWORD値と、値がない場合の既定値。
+///
+/// 既定値は、値がない場合に `load_all_configs` が解釈する値と一致させ、
+/// 移行によって実際の動作が変わらないようにしています。
+const ACCOUNT_DWORD_SCHEMA: &[(&str, u32)] = &[
+    ("IPv4Notify", 0),
+    ("IPv6Notify", 0),
+    ("KeepAliveHours", DEFAULT_KEEP_ALIVE_HOURS),
+];
+
+/// `upgrade_account` がアカウントのレジストリ値に対して行った変更。
+#[derive(Clone, Debug)]
+pub enum UpgradeChange {
+    /// 値が存在しなかったため、既定値を書き込んだ。
+    AddedDefault { name: &'static str, value: u32 },
+    /// 値が古い形式（文字列）で保存されていたため、現在の型に変換した。
+    MigratedType { name: &'static str, value: u32 },
+}
+
+/// 指定されたアカウントのレジストリ値を現在の形式に照らして検証し、必要に応じて書き換えます。
+///
+/// 行った変更の一覧を返します。変更がなければ空のベクターを返します。
+pub fn upgrade_account(master_id: &str) -> windows::core::Result<Vec<UpgradeChange>> {
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // オープンしたレジストリキーのハンドルは、関数の最後で
+    // `RegCloseKey`により確実にクローズされるため安全です。
+    unsafe {
+        let mut hkey: HKEY = HKEY::default();
+        let path = HSTRING::from(format!("Software\\MyDNSAdapter\\{}", master_id));
+        RegOpenKeyExW(
+            root_hkey(),
+            PCWSTR(path.as_ptr()),
+            0,
+            KEY_READ | KEY_WRITE | REGISTRY_VIEW,
+            &mut hkey,
+        )
+        .ok()?;
+
+        let mut changes = Vec::new();
+        for &(name, default) in ACCOUNT_DWORD_SCHEMA {
+            let name_hstring = HSTRING::from(name);
+            let name_pcwstr = PCWSTR(name_hstring.as_ptr());
+            match get_reg_value_type(hkey, name) {
+                // 値がない場合は、既定値を明示的に書き込む。
+                None => {
+                    set_reg_dword(hkey, name_pcwstr, default)?;
+                    changes.push(UpgradeChange::AddedDefault {
+                        name,
+                        value: default,
+                    });
+                }
+                // 文字列で保存されている場合は、数値として解釈してREG_DWORDに変換する。
+                Some(t) if t == REG_SZ => {
+                    let text = get_reg_string(hkey, name).unwrap_or_default();
+                    let value = parse_legacy_dword(&text).unwrap_or(default);
+                    set_reg_dword(hkey, name_pcwstr, value)?;
+                    changes.push(UpgradeChange::MigratedType { name, value });
+                }
+                Some(_) => {}
+            }
+        }
+
+        let _ = RegCloseKey(hkey);
+        Ok(changes)
+    }
+}
+
+/// 文字列で保存された古い形式の値を、REG_DWORDとして解釈します。
+fn parse_legacy_dword(text: &str) -> Option<u32> {
+    match text.trim().to_lowercase().as_str() {
+        "true" | "yes" | "on" => Some(1),
+        "false" | "no" | "off" => Some(0),
+        other => other.parse().ok(),
+    }
+}
+
+/// レジストリ値の型を取得します。値が存在しない場合は `None` を返します。
+fn get_reg_value_type(hkey: HKEY, name: &str) -> Option<REG_VALUE_TYPE> {
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // データは取得せず、型だけを受け取るため安全です。
+    unsafe {
+        let name_hstring = HSTRING::from(name);
+        let mut data_type = REG_VALUE_TYPE::default();
+        let res = RegQueryValueExW(hkey, &name_hstring, None, Some(&mut data_type), None, None);
+        if res != WIN32_ERROR(0) {
+            return None;
+        }
+        Some(data_type)
     }
 }
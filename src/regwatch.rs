@@ -0,0 +1,130 @@
+//! アカウント設定を保存しているレジストリキーの変更を監視するモジュール。
+//!
+//! `RegNotifyChangeKeyValue` で `Software\MyDNSAdapter` 以下の変更を待ち受け、
+//! サブキー（アカウント）の追加・削除や値の変更があったときに、登録されたクロージャを呼び出します。
+//! `registry::RegistryStore` の `ConfigStore::watch` の実装として使われ、
+//! サービスはこれを使って、`account add` や `account edit` による変更を再起動なしで反映します。
+
+use std::thread::{self, JoinHandle};
+
+use crate::registry::REGISTRY_VIEW;
+
+use windows::Win32::Foundation::{CloseHandle, HANDLE, WAIT_OBJECT_0};
+use windows::Win32::System::Registry::{
+    HKEY, KEY_NOTIFY, REG_NOTIFY_CHANGE_LAST_SET, REG_NOTIFY_CHANGE_NAME, RegCloseKey,
+    RegNotifyChangeKeyValue, RegOpenKeyExW,
+};
+use windows::Win32::System::Threading::{CreateEventW, INFINITE, SetEvent, WaitForMultipleObjects};
+use windows::core::w;
+
+/// レジストリキーの変更の監視を表すハンドル。
+///
+/// 破棄（`drop`）されると監視を終了し、監視用のスレッドの終了を待ちます。
+pub struct RegistryChangeWatcher {
+    /// 監視用のスレッドに終了を伝えるイベント。
+    stop_event: HANDLE,
+    /// 変更を待ち受けるスレッド。
+    thread: Option<JoinHandle<()>>,
+}
+
+impl RegistryChangeWatcher {
+    /// `root` の下のアカウント設定のレジストリキーと、そのサブキーすべての変更の監視を開始します。
+    ///
+    /// `on_change` は、キーの追加・削除や値の変更があるたびに、監視用のスレッドから呼び出されます。
+    /// 1回の保存で複数の値が書き込まれるため、続けて何度か呼び出されることがあります。
+    pub fn start<F: Fn() + Send + 'static>(
+        root: HKEY,
+        on_change: F,
+    ) -> windows::core::Result<Self> {
+        // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+        // 作成したハンドルは、失敗時はここで、成功時は監視用のスレッドと `drop` でクローズします。
+        unsafe {
+            let mut hkey = HKEY::default();
+            RegOpenKeyExW(
+                root,
+                w!("Software\\MyDNSAdapter"),
+                0,
+                KEY_NOTIFY | REGISTRY_VIEW,
+                &mut hkey,
+            )
+            .ok()?;
+            let change_event = match CreateEventW(None, false, false, None) {
+                Ok(event) => event,
+                Err(e) => {
+                    let _ = RegCloseKey(hkey);
+                    return Err(e);
+                }
+            };
+            let stop_event = match CreateEventW(None, true, false, None) {
+                Ok(event) => event,
+                Err(e) => {
+                    let _ = CloseHandle(change_event);
+                    let _ = RegCloseKey(hkey);
+                    return Err(e);
+                }
+            };
+
+            // ハンドルはスレッド間で送れない型のため、値として渡してスレッド側で復元する。
+            let (raw_key, raw_change, raw_stop) = (
+                hkey.0 as usize,
+                change_event.0 as usize,
+                stop_event.0 as usize,
+            );
+            let thread = thread::spawn(move || {
+                let hkey = HKEY(raw_key as _);
+                let change_event = HANDLE(raw_change as _);
+                let stop_event = HANDLE(raw_stop as _);
+                watch_loop(hkey, change_event, stop_event, on_change);
+                let _ = CloseHandle(change_event);
+                let _ = RegCloseKey(hkey);
+            });
+            Ok(RegistryChangeWatcher {
+                stop_event,
+                thread: Some(thread),
+            })
+        }
+    }
+}
+
+impl Drop for RegistryChangeWatcher {
+    fn drop(&mut self) {
+        // 監視用のスレッドに終了を伝え、スレッドが終了してからイベントをクローズする。
+        unsafe {
+            let _ = SetEvent(self.stop_event);
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        unsafe {
+            let _ = CloseHandle(self.stop_event);
+        }
+    }
+}
+
+/// 終了を伝えられるまで、レジストリキーの変更を待ち受けてクロージャを呼び出します。
+///
+/// `RegNotifyChangeKeyValue` の通知は1回限りのため、変更を受け取るたびに登録し直します。
+/// 登録に失敗した場合は、監視を終了します。
+unsafe fn watch_loop<F: Fn()>(hkey: HKEY, change_event: HANDLE, stop_event: HANDLE, on_change: F) {
+    loop {
+        let registered = unsafe {
+            RegNotifyChangeKeyValue(
+                hkey,
+                true,
+                REG_NOTIFY_CHANGE_NAME | REG_NOTIFY_CHANGE_LAST_SET,
+                change_event,
+                true,
+            )
+        };
+        if registered.is_err() {
+            return;
+        }
+        // 終了の要求を先に並べ、変更と同時に届いた場合も終了を優先する。
+        let signaled =
+            unsafe { WaitForMultipleObjects(&[stop_event, change_event], false, INFINITE) };
+        if signaled.0 != WAIT_OBJECT_0.0 + 1 {
+            return;
+        }
+        on_change();
+    }
+}
@@ -0,0 +1,175 @@
+//! アカウントごとの通知のスケジュール（cronに似た式）を扱うモジュール。
+//!
+//! 通常、サービスはすべてのアカウントを `--interval` の間隔で通知します。
+//! `account edit <MASTER_ID> --set-schedule <EXPR>` でスケジュールを設定したアカウントは、
+//! 定期通知の代わりに、スケジュールに一致する時刻（分単位）に通知します。
+//! プロバイダーが毎日決まった時刻に再接続する環境で、再接続の直後に通知するといった使い方を想定しています。
+//! アドレスの変更やスリープからの復帰などによる通知は、スケジュールに関係なく行います。
+//!
+//! 式は次のどちらかの形式で指定します。時刻はこのマシンのローカル時刻です。
+//! - cronと同じ5つのフィールド（分 時 日 月 曜日）。`*`、`*/10`、`1-5`、`0,30`、`8-18/2` の形式を使えます。
+//!   曜日は0（日曜日）から6（土曜日）で、7も日曜日を表します。
+//! - `at HH:MM daily`（または `at HH:MM`、`daily at HH:MM`）。毎日その時刻に通知します。
+
+use crate::registry::Config;
+use crate::store::load_all_configs;
+use chrono::{DateTime, Datelike, Local, Timelike};
+use std::time::Duration;
+use tokio::time;
+
+/// 通知のスケジュール。各フィールドは、一致する値のビットを立てたマスクで表す。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Schedule {
+    /// 分（0〜59）。
+    minutes: u64,
+    /// 時（0〜23）。
+    hours: u64,
+    /// 日（1〜31）。
+    days: u64,
+    /// 月（1〜12）。
+    months: u64,
+    /// 曜日（0〜6、0が日曜日）。
+    weekdays: u64,
+    /// 日のフィールドが `*` かどうか。日と曜日の両方が指定された場合は、どちらかに一致すればよい（cronと同じ）。
+    any_day: bool,
+    /// 曜日のフィールドが `*` かどうか。
+    any_weekday: bool,
+}
+
+impl Schedule {
+    /// スケジュールの式を解釈します。解釈できない場合は `None` を返します。
+    pub fn parse(expr: &str) -> Option<Self> {
+        let expr = expr.trim().to_ascii_lowercase();
+        // `daily at HH:MM` の語順も受け付ける。
+        let expr = match expr.strip_prefix("daily ") {
+            Some(rest) => format!("{} daily", rest.trim()),
+            None => expr,
+        };
+        if let Some(time) = expr.strip_prefix("at ") {
+            let time = time.trim().trim_end_matches("daily").trim();
+            let time = chrono::NaiveTime::parse_from_str(time, "%H:%M").ok()?;
+            return Some(Schedule {
+                minutes: 1 << time.minute(),
+                hours: 1 << time.hour(),
+                days: field_mask(1, 31),
+                months: field_mask(1, 12),
+                weekdays: field_mask(0, 6),
+                any_day: true,
+                any_weekday: true,
+            });
+        }
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields.as_slice() else {
+            return None;
+        };
+        let weekdays = parse_field(weekday, 0, 7)?;
+        Some(Schedule {
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days: parse_field(day, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            // 7は日曜日（0）として扱う。
+            weekdays: (weekdays | weekdays >> 7) & field_mask(0, 6),
+            any_day: *day == "*",
+            any_weekday: *weekday == "*",
+        })
+    }
+
+    /// 指定した時刻（分単位）が、このスケジュールに一致するかどうかを返します。
+    pub fn matches(&self, time: &DateTime<Local>) -> bool {
+        let bit = |mask: u64, value: u32| mask & (1 << value) != 0;
+        let day = bit(self.days, time.day());
+        let weekday = bit(self.weekdays, time.weekday().num_days_from_sunday());
+        let day_matches = if self.any_day || self.any_weekday {
+            day && weekday
+        } else {
+            day || weekday
+        };
+        bit(self.minutes, time.minute())
+            && bit(self.hours, time.hour())
+            && bit(self.months, time.month())
+            && day_matches
+    }
+}
+
+/// アカウントに、解釈できるスケジュールが設定されているかどうかを返します。
+///
+/// 解釈できないスケジュールが保存されていた場合は、設定されていないものとして定期通知で通知します。
+pub fn has_schedule(config: &Config) -> bool {
+    !config.schedule.is_empty() && Schedule::parse(&config.schedule).is_some()
+}
+
+/// 毎分の始めに、スケジュールに一致したアカウントを求め続けます。
+///
+/// 一致したアカウントがあれば、そのMasterIDの一覧を渡して `on_due` を呼び出します。
+/// アカウントの設定は毎回読み込み、スケジュールの変更を再起動なしで反映します。このタスクが中断されるまで戻りません。
+pub async fn watch_schedules<F: Fn(Vec<String>)>(on_due: F) {
+    // 同じ分に2回通知しないよう、最後に確認した分を覚えておく。
+    let mut last_checked: Option<DateTime<Local>> = None;
+    loop {
+        // 次の分の始めを少し過ぎるまで待つ。
+        let now = Local::now();
+        let into_minute = u64::from(now.second()) * 1000 + u64::from(now.timestamp_subsec_millis());
+        time::sleep(Duration::from_millis(
+            60_000 - into_minute.min(59_999) + 500,
+        ))
+        .await;
+
+        let now = Local::now();
+        let Some(minute) = now.with_second(0).and_then(|t| t.with_nanosecond(0)) else {
+            continue;
+        };
+        if last_checked == Some(minute) {
+            continue;
+        }
+        last_checked = Some(minute);
+        let due: Vec<String> = load_all_configs()
+            .unwrap_or_default()
+            .iter()
+            .filter(|config| {
+                !config.schedule.is_empty()
+                    && Schedule::parse(&config.schedule).is_some_and(|s| s.matches(&minute))
+            })
+            .map(|config| config.master_id.clone())
+            .collect();
+        if !due.is_empty() {
+            on_due(due);
+        }
+    }
+}
+
+/// `min` から `max` までのすべての値のビットを立てたマスクを返します。
+fn field_mask(min: u32, max: u32) -> u64 {
+    (min..=max).fold(0, |mask, value| mask | 1 << value)
+}
+
+/// cronの1つのフィールドを解釈し、一致する値のビットを立てたマスクを返します。
+///
+/// カンマで区切った各要素は、`*`、`N`、`N-M` に、`/STEP` を付けた形式です。
+/// `N/STEP` は、cronと同じく `N-max/STEP` として扱います。
+fn parse_field(field: &str, min: u32, max: u32) -> Option<u64> {
+    let mut mask = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().ok().filter(|s| *s > 0)?),
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (start.parse().ok()?, end.parse().ok()?),
+                None => {
+                    let start = range.parse().ok()?;
+                    (start, if step > 1 { max } else { start })
+                }
+            },
+        };
+        if start < min || end > max || start > end {
+            return None;
+        }
+        mask |= (start..=end)
+            .step_by(step as usize)
+            .fold(0, |m, v| m | 1 << v);
+    }
+    Some(mask)
+}
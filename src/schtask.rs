@@ -0,0 +1,242 @@
+//! サービスの代わりに、タスクスケジューラのタスクとして定期通知を登録するモジュール。
+//!
+//! 新しいサービスのインストールが禁止されている環境向けに、`--install-task` で
+//! `--notify` を実行するタスクを登録し、`--uninstall-task` で削除します。
+//! タスクはタスクスケジューラのCOM API（`ITaskService`）で、XMLの定義から登録します。
+//!
+//! タスクは SYSTEM アカウントで、次の契機に実行されます。
+//! - システムの起動時（ネットワークの準備を待つため、少し遅らせる）
+//! - ネットワークへの接続時（NetworkProfile のイベントID 10000）
+//! - 設定された定期通知の間隔ごと
+//!
+//! 間隔は登録時の設定から決まるため、`--interval` で変更した後は登録し直す必要があります。
+//! 登録時に `--lang` などで言語を指定した場合は、タスクもその言語でログを記録します。
+
+use crate::elevation::is_elevated;
+use crate::error::AdapterError;
+use crate::exitcode::{ExitError, ExitReason};
+use crate::i18n::{MessageKey, explicit_lang, get_msg};
+use crate::logging::log_info;
+use crate::registry::load_settings;
+use crate::winservice::query_service_state;
+use crate::{format_msg, outln};
+use chrono::Local;
+use std::env;
+use windows::Win32::Foundation::ERROR_FILE_NOT_FOUND;
+use windows::Win32::System::Com::{
+    CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED, CoCreateInstance, CoInitializeEx, CoUninitialize,
+};
+use windows::Win32::System::TaskScheduler::{
+    ITaskFolder, ITaskService, TASK_CREATE_OR_UPDATE, TASK_LOGON_SERVICE_ACCOUNT, TaskScheduler,
+};
+use windows::core::{BSTR, HRESULT, VARIANT};
+
+/// 登録するタスクの名前。タスクスケジューラのルートフォルダーに作成する。
+const TASK_NAME: &str = "MyDNSAdapter";
+/// タスクを実行するアカウント（SYSTEM）のSID。
+const TASK_USER_SID: &str = "S-1-5-18";
+/// 起動時の通知を遅らせる時間。ネットワークの準備ができるのを待つため。
+const BOOT_DELAY: &str = "PT1M";
+/// ネットワークへの接続時の通知を遅らせる時間。アドレスが確定するのを待つため。
+const NETWORK_DELAY: &str = "PT30S";
+/// 1回の実行に許す最大時間。通知が応答しないままタスクが残らないようにする。
+const EXECUTION_TIME_LIMIT: &str = "PT10M";
+
+/// 定期通知を行うタスクを登録します。既に登録されている場合は、現在の設定で更新します。
+///
+/// 管理者権限が必要です。サービスもインストールされている場合は、二重に通知するおそれがあるため警告します。
+pub fn install_task() -> Result<(), AdapterError> {
+    if !is_elevated() {
+        return Err(ExitError::new(
+            ExitReason::AdminRequired,
+            get_msg(MessageKey::AdminRequiredTask),
+        )
+        .into());
+    }
+    if let Ok(Some(_)) = query_service_state() {
+        outln!("{}", get_msg(MessageKey::TaskServiceInstalledWarning));
+    }
+
+    let interval = load_settings()?.notify_interval_minutes;
+    let exe = env::current_exe()?;
+    let xml = task_xml(&exe.to_string_lossy(), interval);
+    with_root_folder(|folder| {
+        // Win32 APIを呼び出すため、unsafeブロックを使用する。
+        unsafe {
+            folder.RegisterTask(
+                &BSTR::from(TASK_NAME),
+                &BSTR::from(xml.as_str()),
+                TASK_CREATE_OR_UPDATE.0,
+                &VARIANT::from(TASK_USER_SID),
+                &VARIANT::default(),
+                TASK_LOGON_SERVICE_ACCOUNT,
+                &VARIANT::default(),
+            )
+        }
+        .map(|_| ())
+    })?;
+
+    outln!(
+        "{}",
+        format_msg!(
+            MessageKey::TaskInstalledFmt,
+            name = TASK_NAME,
+            minutes = interval
+        )
+    );
+    log_info(&format!(
+        "Scheduled task '{}' registered (every {} minutes).",
+        TASK_NAME, interval
+    ));
+    Ok(())
+}
+
+/// 登録したタスクを削除します。
+///
+/// 管理者権限が必要です。タスクが登録されていない場合は、その旨を表示して正常終了します。
+pub fn uninstall_task() -> Result<(), AdapterError> {
+    if !is_elevated() {
+        return Err(ExitError::new(
+            ExitReason::AdminRequired,
+            get_msg(MessageKey::AdminRequiredTask),
+        )
+        .into());
+    }
+    let result = with_root_folder(|folder| {
+        // Win32 APIを呼び出すため、unsafeブロックを使用する。
+        unsafe { folder.DeleteTask(&BSTR::from(TASK_NAME), 0) }
+    });
+    match result {
+        Ok(()) => {
+            outln!(
+                "{}",
+                format_msg!(MessageKey::TaskUninstalledFmt, name = TASK_NAME)
+            );
+            log_info(&format!("Scheduled task '{}' removed.", TASK_NAME));
+        }
+        // タスクが存在しない場合は、削除済みとみなし正常終了。
+        Err(e) if e.code() == HRESULT::from(ERROR_FILE_NOT_FOUND) => {
+            outln!(
+                "{}",
+                format_msg!(MessageKey::TaskNotInstalledFmt, name = TASK_NAME)
+            );
+        }
+        Err(e) => return Err(e.into()),
+    }
+    Ok(())
+}
+
+/// タスクスケジューラに接続し、ルートフォルダーに対して処理を行います。
+///
+/// COMの初期化と終了は、この関数の中で行います。
+fn with_root_folder<T>(
+    f: impl FnOnce(&ITaskFolder) -> windows::core::Result<T>,
+) -> windows::core::Result<T> {
+    // COMのAPIを呼び出すため、unsafeブロックを使用する。
+    // 取得したインターフェイスは、COMを終了する前に解放する。
+    unsafe {
+        CoInitializeEx(None, COINIT_MULTITHREADED).ok()?;
+        let result = (|| {
+            let service: ITaskService =
+                CoCreateInstance(&TaskScheduler, None, CLSCTX_INPROC_SERVER)?;
+            service.Connect(
+                &VARIANT::default(),
+                &VARIANT::default(),
+                &VARIANT::default(),
+                &VARIANT::default(),
+            )?;
+            let folder = service.GetFolder(&BSTR::from("\\"))?;
+            f(&folder)
+        })();
+        CoUninitialize();
+        result
+    }
+}
+
+/// 登録するタスクの定義（タスクスケジューラのXML形式）を作成します。
+///
+/// # 引数
+/// * `exe` - 実行するこのアプリケーションのパス。
+/// * `interval` - 定期通知の間隔（分）。
+fn task_xml(exe: &str, interval: u32) -> String {
+    // ネットワークへの接続を表すイベントを選ぶクエリ。XMLの中に埋め込むため、エスケープする。
+    let subscription = xml_escape(
+        "<QueryList><Query Id=\"0\" Path=\"Microsoft-Windows-NetworkProfile/Operational\">\
+         <Select Path=\"Microsoft-Windows-NetworkProfile/Operational\">\
+         *[System[Provider[@Name='Microsoft-Windows-NetworkProfile'] and EventID=10000]]\
+         </Select></Query></QueryList>",
+    );
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-16"?>
+<Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <RegistrationInfo>
+    <Description>{description}</Description>
+  </RegistrationInfo>
+  <Triggers>
+    <BootTrigger>
+      <Enabled>true</Enabled>
+      <Delay>{boot_delay}</Delay>
+    </BootTrigger>
+    <EventTrigger>
+      <Enabled>true</Enabled>
+      <Subscription>{subscription}</Subscription>
+      <Delay>{network_delay}</Delay>
+    </EventTrigger>
+    <TimeTrigger>
+      <Enabled>true</Enabled>
+      <StartBoundary>{start}</StartBoundary>
+      <Repetition>
+        <Interval>PT{interval}M</Interval>
+      </Repetition>
+    </TimeTrigger>
+  </Triggers>
+  <Principals>
+    <Principal id="Author">
+      <UserId>{user}</UserId>
+      <RunLevel>HighestAvailable</RunLevel>
+    </Principal>
+  </Principals>
+  <Settings>
+    <MultipleInstancesPolicy>IgnoreNew</MultipleInstancesPolicy>
+    <DisallowStartIfOnBatteries>false</DisallowStartIfOnBatteries>
+    <StopIfGoingOnBatteries>false</StopIfGoingOnBatteries>
+    <StartWhenAvailable>true</StartWhenAvailable>
+    <ExecutionTimeLimit>{time_limit}</ExecutionTimeLimit>
+  </Settings>
+  <Actions Context="Author">
+    <Exec>
+      <Command>{command}</Command>
+      <Arguments>{arguments}</Arguments>
+    </Exec>
+  </Actions>
+</Task>"#,
+        description = xml_escape(get_msg(MessageKey::TaskDescription)),
+        boot_delay = BOOT_DELAY,
+        subscription = subscription,
+        network_delay = NETWORK_DELAY,
+        start = Local::now().format("%Y-%m-%dT%H:%M:%S"),
+        interval = interval,
+        user = TASK_USER_SID,
+        time_limit = EXECUTION_TIME_LIMIT,
+        command = xml_escape(exe),
+        arguments = task_arguments(),
+    )
+}
+
+/// タスクで実行する引数を返します。
+///
+/// 言語が明示的に指定されていれば、タスクのログもその言語で記録するよう `--lang` を付けます。
+fn task_arguments() -> String {
+    match explicit_lang() {
+        Some(lang) => format!("--notify --lang {}", lang.code()),
+        None => "--notify".to_string(),
+    }
+}
+
+/// XMLのテキストとして埋め込めるよう、特殊文字をエスケープします。
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,310 @@
+//! 模擬サーバーを使ったエンドツーエンドのシミュレーションモードを提供するモジュール。
+//!
+//! `--simulate` が指定されると、ローカルホスト上にMyDNS.JPの通知エンドポイントを模した
+//! 簡易HTTPサーバーを起動し、実際の通知処理（`perform_notification_to`）をそのサーバーに向けて
+//! 実行します。成功・認証失敗・サーバーエラー・失敗後の再試行といったシナリオを順に実行し、
+//! 結果を一覧表示します。再試行の待ち時間は、シミュレーションが速く終わるよう短くしています。
+//!
+//! シミュレーションで使うアカウントはメモリ上にのみ存在し、レジストリには書き込みません。
+//! 実行時状態と通知の履歴に記録された模擬アカウントの情報も、終了時に削除します。
+
+use crate::error::AdapterError;
+use crate::format_msg;
+use crate::i18n::{MessageKey, get_msg};
+use crate::logging::set_log_path;
+use crate::notify::{RetryPolicy, perform_notification_to};
+use crate::registry::Config;
+use crate::state::{load_state, set_data_dir};
+use crate::store::{load_all_configs, load_last_success, save_config, set_config_file};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use reqwest::Client;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// 模擬サーバーが受け付けるパスワード。
+const SIM_PASSWORD: &str = "simulate-password";
+/// シミュレーションで使う再試行の方針。待ち時間を短くし、最大2回まで試行する。
+const SIM_RETRY: RetryPolicy = RetryPolicy {
+    max_attempts: 2,
+    base_delay: Duration::from_millis(20),
+};
+
+/// ひとつのシミュレーションシナリオ。
+struct Scenario {
+    /// シナリオ名（結果表示用）。
+    name: &'static str,
+    /// 模擬アカウントのMasterID。
+    master_id: &'static str,
+    /// 通知に使うパスワード。
+    password: &'static str,
+    /// 模擬サーバー上のパス。サーバーの振る舞いを決める。
+    path: &'static str,
+    /// 最後の試行で期待される結果（成功なら `true`）。
+    expect_success: bool,
+    /// アカウントを保存先に追加し、読み込み直した設定で通知するかどうか。
+    /// 通知に成功した場合は、成功の記録が保存先に書き込まれたことも確認する。
+    add: bool,
+}
+
+/// 実行するシナリオの一覧。
+const SCENARIOS: &[Scenario] = &[
+    // `--add` と同じく保存先にアカウントを追加し、読み込み直した設定で通知できることを確認する。
+    Scenario {
+        name: "add",
+        master_id: "mydnssim-add",
+        password: SIM_PASSWORD,
+        path: "ok",
+        expect_success: true,
+        add: true,
+    },
+    Scenario {
+        name: "notify",
+        master_id: "mydnssim-notify",
+        password: SIM_PASSWORD,
+        path: "ok",
+        expect_success: true,
+        add: false,
+    },
+    Scenario {
+        name: "auth-failure",
+        master_id: "mydnssim-auth",
+        password: "wrong-password",
+        path: "ok",
+        expect_success: false,
+        add: false,
+    },
+    Scenario {
+        name: "server-error",
+        master_id: "mydnssim-error",
+        password: SIM_PASSWORD,
+        path: "error",
+        expect_success: false,
+        add: false,
+    },
+    // HTTP 200でも、応答本文が認証の失敗を示していれば失敗と判定されることを確認する。
+    Scenario {
+        name: "auth-failure-200",
+        master_id: "mydnssim-soft",
+        password: "wrong-password",
+        path: "soft",
+        expect_success: false,
+        add: false,
+    },
+    // 初回の503を、1回の通知の中での再試行で乗り越えられることを確認する。
+    Scenario {
+        name: "retry",
+        master_id: "mydnssim-retry",
+        password: SIM_PASSWORD,
+        path: "flaky",
+        expect_success: true,
+        add: false,
+    },
+];
+
+/// シミュレーションモードを実行します。
+///
+/// いずれかのシナリオが期待どおりの結果にならなかった場合はエラーを返し、
+/// プロセスの終了コードでCIなどに失敗を伝えます。
+pub fn simulate_mode() -> Result<(), AdapterError> {
+    println!("{}", get_msg(MessageKey::SimulateTitle));
+
+    let addr = start_mock_server()?;
+    println!(
+        "{}",
+        format_msg!(MessageKey::SimulateServerFmt, addr = addr)
+    );
+
+    // 実際のデータに触れないよう、実行時データ・ログ・アカウントの保存先を一時ディレクトリに切り替える。
+    let sandbox = env::temp_dir().join(format!("mydns-simulate-{}", process::id()));
+    fs::create_dir_all(&sandbox)?;
+    set_data_dir(sandbox.clone());
+    set_log_path(sandbox.join("mydns.log"));
+    set_config_file(sandbox.join("config.toml"));
+
+    let result = run_scenarios(addr);
+    let _ = fs::remove_dir_all(&sandbox);
+    let failed = result?;
+
+    println!(
+        "{}",
+        format_msg!(
+            MessageKey::SimulateSummaryFmt,
+            passed = SCENARIOS.len() - failed,
+            total = SCENARIOS.len()
+        )
+    );
+    if failed > 0 {
+        return Err(get_msg(MessageKey::SimulateFailedSummary).into());
+    }
+    Ok(())
+}
+
+/// すべてのシナリオを模擬サーバーに対して実行して結果を表示し、期待どおりでなかったシナリオの数を返します。
+fn run_scenarios(addr: SocketAddr) -> Result<usize, AdapterError> {
+    // システムのプロキシ設定に影響されないよう、ローカルホストへは直接接続する。
+    let client = Client::builder().no_proxy().build()?;
+    let runtime = Runtime::new()?;
+
+    let mut failed = 0;
+    for scenario in SCENARIOS {
+        let url = format!("http://{}/{}/login.html", addr, scenario.path);
+        let mut config = Config {
+            master_id: scenario.master_id.to_string(),
+            password: scenario.password.to_string(),
+            ipv4_notify: true,
+            ipv6_notify: false,
+            keep_alive_hours: 0,
+            ..Config::default()
+        };
+        // 追加するシナリオでは、保存先から読み込み直した設定で通知する。
+        let added = if scenario.add {
+            match add_account(&config) {
+                Some(saved) => {
+                    config = saved;
+                    true
+                }
+                None => false,
+            }
+        } else {
+            true
+        };
+
+        let succeeded = runtime
+            .block_on(perform_notification_to(
+                &client, &config, &url, &url, &SIM_RETRY,
+            ))
+            .result
+            .is_ok();
+
+        // 通知結果が実行時状態にも正しく記録されているかを確認する。
+        let recorded = load_state()
+            .ok()
+            .and_then(|s| s.accounts.get(scenario.master_id).cloned())
+            .is_some_and(|a| !a.in_flight && a.last_result.is_some());
+        // 追加したアカウントの成功は、保存先にも記録されているはず。
+        let stored =
+            !(scenario.add && succeeded) || load_last_success(scenario.master_id).time.is_some();
+
+        let passed = succeeded == scenario.expect_success && recorded && added && stored;
+        if !passed {
+            failed += 1;
+        }
+        println!(
+            "{}",
+            format_msg!(
+                MessageKey::SimulateResultFmt,
+                mark = if passed { "PASS" } else { "FAIL" },
+                name = scenario.name,
+                result = if succeeded {
+                    get_msg(MessageKey::SimulateSucceeded)
+                } else {
+                    get_msg(MessageKey::SimulateFailed)
+                }
+            )
+        );
+    }
+
+    Ok(failed)
+}
+
+/// アカウントを保存先に追加し、保存先から読み込み直した設定を返します。
+///
+/// 保存や読み込みに失敗した場合は `None` を返します。
+fn add_account(config: &Config) -> Option<Config> {
+    save_config(config).ok()?;
+    load_all_configs()
+        .ok()?
+        .into_iter()
+        .find(|c| c.master_id == config.master_id)
+}
+
+/// 模擬MyDNS.JPサーバーをバックグラウンドスレッドで起動し、待ち受けアドレスを返します。
+///
+/// サーバーはプロセスの終了まで動き続けます。
+fn start_mock_server() -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    // "flaky" パスへのリクエスト数。初回だけ失敗させるために使う。
+    let flaky_count = AtomicUsize::new(0);
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            // 応答の失敗はシナリオの結果として現れるため、ここでは無視する。
+            let _ = handle_connection(stream, &flaky_count);
+        }
+    });
+    Ok(addr)
+}
+
+/// 模擬サーバーへの1回のHTTPリクエストを処理します。
+///
+/// パスの先頭要素によって振る舞いを変えます。
+/// - `ok`: Basic認証が正しければ200、誤っていれば401を返す。
+/// - `error`: 常に500を返す。
+/// - `soft`: 常に200を返すが、Basic認証が誤っていれば本文でログインの失敗を示す。
+/// - `flaky`: 初回は503、2回目以降は `ok` と同じ振る舞いをする。
+fn handle_connection(stream: TcpStream, flaky_count: &AtomicUsize) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    // リクエスト行とヘッダーを読み込む。本文は使わないため読まない。
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut authorization = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.trim().eq_ignore_ascii_case("authorization")
+        {
+            authorization = Some(value.trim().to_string());
+        }
+    }
+
+    let path = request_line.split_whitespace().nth(1).unwrap_or("");
+    // Basic認証の資格情報を取り出し、パスワードを照合する。MasterIDは任意の値を受け付ける。
+    let auth_ok = authorization
+        .as_deref()
+        .and_then(|auth| auth.strip_prefix("Basic "))
+        .and_then(|b64| BASE64.decode(b64).ok())
+        .and_then(|raw| String::from_utf8(raw).ok())
+        .is_some_and(|cred| {
+            cred.split_once(':')
+                .is_some_and(|(_, pw)| pw == SIM_PASSWORD)
+        });
+
+    let (status, body) = if path.starts_with("/error/") {
+        ("500 Internal Server Error", "SERVER ERROR")
+    } else if path.starts_with("/flaky/") && flaky_count.fetch_add(1, Ordering::SeqCst) == 0 {
+        ("503 Service Unavailable", "TRY AGAIN LATER")
+    } else if path.starts_with("/ok/") || path.starts_with("/flaky/") {
+        if auth_ok {
+            ("200 OK", "LOGIN OK")
+        } else {
+            ("401 Unauthorized", "LOGIN FAILED")
+        }
+    } else if path.starts_with("/soft/") {
+        ("200 OK", if auth_ok { "LOGIN OK" } else { "LOGIN FAILED" })
+    } else {
+        ("404 Not Found", "NOT FOUND")
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
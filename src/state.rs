@@ -0,0 +1,477 @@
+//! CLIとWindowsサービスの間で共有する実行時状態を管理するモジュール。
+//!
+//! レジストリに保存される設定（`Config`）とは別に、サービスの「今」の状態を
+//! 実行時データのディレクトリ（`%ProgramData%\MyDNSAdapter`）の `mydns.state` に保持します。
+//! - アカウントごとの実行時データ（通知処理中か、バックオフ期限、直近の結果）
+//! - サービス全体への要求（一時停止、即時通知）
+//!
+//! サービスはこのファイルを定期的に読み書きし、CLIは同じファイルを介して
+//! 稼働中のサービスの状態を参照したり、動作に影響を与えたりします。
+//!
+//! CLIの変更（`--pause` など）とサービスの変更が互いに上書きし合わないよう、読み込みから書き戻しまでを
+//! プロセス内ではミューテックスで、プロセス間ではロックファイル（`mydns.state.lock`）で直列化します。
+//!
+//! 実行時データのディレクトリは、管理者権限のないユーザーも一時停止や即時通知を要求できるよう、
+//! サービスのインストール時と開始時にユーザー（`BUILTIN\Users`）の書き込みを許可します。
+//! 以前のバージョンが実行ファイルと同じディレクトリに作成したファイルは、最初に使うときにこのディレクトリへ移します。
+
+use crate::error::AdapterError;
+use chrono::{DateTime, Local};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::{Mutex, Once, OnceLock, PoisonError};
+// サービスの実行アカウントに、ディレクトリへの書き込みを許可するためのアクセス制御
+use windows::Win32::Foundation::{HLOCAL, LocalFree};
+use windows::Win32::Security::Authorization::{
+    EXPLICIT_ACCESS_W, GRANT_ACCESS, GetNamedSecurityInfoW, SE_FILE_OBJECT, SetEntriesInAclW,
+    SetNamedSecurityInfoW, TRUSTEE_IS_NAME, TRUSTEE_IS_UNKNOWN, TRUSTEE_W,
+};
+use windows::Win32::Security::Authorization::{TRUSTEE_IS_SID, TRUSTEE_IS_WELL_KNOWN_GROUP};
+use windows::Win32::Security::{
+    ACL, CreateWellKnownSid, DACL_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR, PSID,
+    SECURITY_MAX_SID_SIZE, SUB_CONTAINERS_AND_OBJECTS_INHERIT, WinBuiltinUsersSid,
+};
+use windows::Win32::Storage::FileSystem::{DELETE, FILE_GENERIC_READ, FILE_GENERIC_WRITE};
+use windows::core::{HSTRING, PWSTR};
+
+/// 状態ファイルの名前。
+const STATE_FILE_NAME: &str = "mydns.state";
+/// 実行時データを置く、`%ProgramData%` の下のディレクトリ名。
+const DATA_DIR_NAME: &str = "MyDNSAdapter";
+/// 以前のバージョンが実行ファイルと同じディレクトリに作成した、実行時データのファイルの名前。
+const LEGACY_DATA_FILES: &[&str] = &[
+    "mydns.state",
+    "mydns.history",
+    "mydns.stats",
+    "mydns-history.db",
+    "mydns-history.db-wal",
+];
+/// 状態ファイルの読み込みから書き戻しまでを、プロセス内で直列化するためのロック。
+/// サービスは複数のアカウントを並行して通知するため、同時に更新すると変更が失われてしまう。
+/// 他のプロセスとの直列化には、`lock_state_file` によるファイルロックを併用する。
+static STATE_LOCK: Mutex<()> = Mutex::new(());
+/// `set_data_dir` で変更された、実行時データを置くディレクトリ。
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// 以前のバージョンの実行時データの移動を、プロセスごとに最初の1回だけ行うためのフラグ。
+static LEGACY_DATA_MIGRATED: Once = Once::new();
+
+/// アカウントごとの実行時データ。
+#[derive(Clone, Debug, Default)]
+pub struct AccountState {
+    /// 現在、このアカウントの通知リクエストを送信中かどうか。
+    pub in_flight: bool,
+    /// この時刻までは通知を見送る（バックオフ中）。
+    pub backoff_until: Option<DateTime<Local>>,
+    /// 直近の通知の結果を表す文字列。
+    pub last_result: Option<String>,
+    /// 直近に通知を試行した時刻。
+    pub last_attempt: Option<DateTime<Local>>,
+    /// 直近に通知が成功した時刻。
+    pub last_success: Option<DateTime<Local>>,
+    /// 直近に通知が成功したときに、MyDNS.JPが受け付けたアドレス。
+    pub last_ip: Vec<IpAddr>,
+    /// 記録済みの失効警告の段階（0は未警告）。通知が成功するとリセットされる。
+    pub expiry_warn_level: u8,
+    /// 続けて通知に失敗した回数。通知が成功するとリセットされる。
+    pub consecutive_failures: u32,
+    /// 続けて失敗している通知の、最初に失敗した時刻。通知が成功するとリセットされる。
+    pub failing_since: Option<DateTime<Local>>,
+    /// 続けて失敗していることを、メールで知らせたかどうか。通知が成功するとリセットされる。
+    pub mail_alert_sent: bool,
+    /// 続けて認証エラーで失敗した回数。認証エラー以外の結果になるとリセットされる。
+    pub consecutive_auth_failures: u32,
+}
+
+/// CLIとサービスで共有する実行時状態全体。
+#[derive(Clone, Debug, Default)]
+pub struct RuntimeState {
+    /// 定期通知が一時停止されているかどうか。
+    pub paused: bool,
+    /// 次のポーリング時に即時通知を行うよう要求されているかどうか。
+    pub kick_requested: bool,
+    /// 稼働中のサービスが開始した時刻。サービスの停止時に消去される。
+    pub service_started: Option<DateTime<Local>>,
+    /// MasterIDをキーとした、アカウントごとの実行時データ。
+    pub accounts: BTreeMap<String, AccountState>,
+}
+
+/// 実行時データ（状態ファイル、通知の履歴、利用統計）を置くディレクトリを変更します。
+///
+/// シミュレーションモードが、実際のデータに模擬アカウントの記録を残さないために使います。
+/// 変更できるのはプロセスで一度だけです。
+pub fn set_data_dir(dir: PathBuf) {
+    let _ = DATA_DIR.set(dir);
+}
+
+/// 実行時データを置くディレクトリを返します。
+///
+/// `set_data_dir` で変更されていなければ、`%ProgramData%\MyDNSAdapter` です。
+/// ディレクトリがまだなければ作成し、以前のバージョンのファイルがあれば移動します。
+pub fn data_dir() -> io::Result<PathBuf> {
+    if let Some(dir) = DATA_DIR.get() {
+        return Ok(dir.clone());
+    }
+    let dir = default_data_dir();
+    fs::create_dir_all(&dir)?;
+    LEGACY_DATA_MIGRATED.call_once(|| migrate_legacy_data(&dir));
+    Ok(dir)
+}
+
+/// 既定の実行時データのディレクトリ（`%ProgramData%\MyDNSAdapter`）を返します。
+///
+/// サービスは `Program Files` など書き込みが制限された場所から実行されることがあるため、
+/// ログファイルや設定ファイルと同じく、マシン全体のデータ用のディレクトリを使います。
+fn default_data_dir() -> PathBuf {
+    let program_data =
+        env::var_os("ProgramData").map_or_else(|| PathBuf::from(r"C:\ProgramData"), PathBuf::from);
+    program_data.join(DATA_DIR_NAME)
+}
+
+/// 以前のバージョンが実行ファイルと同じディレクトリに作成した実行時データを、新しい場所に移動します。
+///
+/// 新しい場所に同じ名前のファイルがまだない場合だけ移動します。
+/// 移動に失敗しても、実行時データの記録は新しい場所で続けます。
+fn migrate_legacy_data(dir: &Path) {
+    let Ok(exe) = env::current_exe() else {
+        return;
+    };
+    for name in LEGACY_DATA_FILES {
+        let legacy_path = exe.with_file_name(name);
+        let path = dir.join(name);
+        if legacy_path == path || !legacy_path.exists() || path.exists() {
+            continue;
+        }
+        // 名前の変更では元の場所のアクセス許可が残ってしまうため、コピーして新しい場所の許可を継承させる。
+        // 元のファイルを削除できなくても（管理者権限がない場合など）、次回からは新しい場所のファイルを使う。
+        if fs::copy(&legacy_path, &path).is_ok() {
+            let _ = fs::remove_file(&legacy_path);
+        }
+    }
+}
+
+/// 実行時データのディレクトリへの書き込みを、コンピューターのユーザー（`BUILTIN\Users`）に許可します。
+///
+/// 管理者権限のないユーザーも、状態ファイルを介して稼働中のサービスに一時停止（`--pause`）や
+/// 即時通知（`--kick`）を要求できるようにするためです。サービスのインストール時と開始時に呼び出します。
+/// 同じ許可がすでにあれば、アクセス制御リストは変わりません。
+pub fn share_data_dir() -> Result<(), AdapterError> {
+    let dir = data_dir()?;
+    let mut sid = [0u8; SECURITY_MAX_SID_SIZE as usize];
+    let mut size = sid.len() as u32;
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // SIDは十分な大きさ（`SECURITY_MAX_SID_SIZE`）のスタック上のバッファに作成します。
+    unsafe {
+        CreateWellKnownSid(
+            WinBuiltinUsersSid,
+            PSID::default(),
+            PSID(sid.as_mut_ptr().cast()),
+            &mut size,
+        )?;
+    }
+    let trustee = TRUSTEE_W {
+        TrusteeForm: TRUSTEE_IS_SID,
+        TrusteeType: TRUSTEE_IS_WELL_KNOWN_GROUP,
+        ptstrName: PWSTR(sid.as_mut_ptr().cast()),
+        ..Default::default()
+    };
+    grant_directory_access(&dir, trustee, DATA_DIR_ACCESS)
+}
+
+/// サービスを実行するアカウントに、実行時データを置くディレクトリへの書き込みを許可します。
+///
+/// LocalSystem 以外のアカウントは既定ではこのディレクトリに書き込めず、サービスが実行時状態や
+/// 通知の履歴、利用統計を記録できないため、インストール時に呼び出します。
+/// 許可はディレクトリの中のファイルにも継承されます。ディレクトリがまだなければ作成します。管理者権限が必要です。
+pub fn grant_data_dir_access(account: &str) -> Result<(), AdapterError> {
+    let dir = data_dir()?;
+    fs::create_dir_all(&dir)?;
+    let mut name: Vec<u16> = account.encode_utf16().chain(std::iter::once(0)).collect();
+    let trustee = TRUSTEE_W {
+        TrusteeForm: TRUSTEE_IS_NAME,
+        TrusteeType: TRUSTEE_IS_UNKNOWN,
+        ptstrName: PWSTR(name.as_mut_ptr()),
+        ..Default::default()
+    };
+    grant_directory_access(&dir, trustee, DATA_DIR_ACCESS)
+}
+
+/// 実行時データのディレクトリに許可するアクセス権。
+/// 書き込みのほか、一時ファイルで置き換えるために削除も許可する。
+const DATA_DIR_ACCESS: u32 = FILE_GENERIC_READ.0 | FILE_GENERIC_WRITE.0 | DELETE.0;
+
+/// `dir` のディレクトリのアクセス制御リストに、`trustee` への `access` の許可を追加します。
+///
+/// 許可は、ディレクトリの中のファイルとサブディレクトリにも継承されます。
+fn grant_directory_access(dir: &Path, trustee: TRUSTEE_W, access: u32) -> Result<(), AdapterError> {
+    let path = HSTRING::from(dir.as_os_str());
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    // 取得したセキュリティ記述子と作成したACLは、最後に `LocalFree` で解放します。
+    unsafe {
+        let mut old_dacl: *mut ACL = std::ptr::null_mut();
+        let mut descriptor = PSECURITY_DESCRIPTOR::default();
+        GetNamedSecurityInfoW(
+            &path,
+            SE_FILE_OBJECT,
+            DACL_SECURITY_INFORMATION,
+            None,
+            None,
+            Some(&mut old_dacl),
+            None,
+            &mut descriptor,
+        )
+        .ok()?;
+        let entry = EXPLICIT_ACCESS_W {
+            grfAccessPermissions: access,
+            grfAccessMode: GRANT_ACCESS,
+            grfInheritance: SUB_CONTAINERS_AND_OBJECTS_INHERIT,
+            Trustee: trustee,
+        };
+        let mut new_dacl: *mut ACL = std::ptr::null_mut();
+        let result = SetEntriesInAclW(Some(&[entry]), Some(old_dacl), &mut new_dacl)
+            .ok()
+            .and_then(|()| {
+                SetNamedSecurityInfoW(
+                    &path,
+                    SE_FILE_OBJECT,
+                    DACL_SECURITY_INFORMATION,
+                    PSID::default(),
+                    PSID::default(),
+                    Some(new_dacl),
+                    None,
+                )
+                .ok()
+            });
+        let _ = LocalFree(HLOCAL(new_dacl.cast()));
+        let _ = LocalFree(HLOCAL(descriptor.0));
+        Ok(result?)
+    }
+}
+
+/// 状態ファイルのフルパスを取得します。
+fn get_state_path() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join(STATE_FILE_NAME))
+}
+
+/// 状態ファイルを読み込みます。
+///
+/// ファイルが存在しない場合は、初期状態（すべて空）を返します。
+pub fn load_state() -> io::Result<RuntimeState> {
+    match fs::read_to_string(get_state_path()?) {
+        Ok(text) => Ok(parse_state(&text)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(RuntimeState::default()),
+        Err(e) => Err(e),
+    }
+}
+
+/// 状態ファイルを書き込みます。
+///
+/// 書き込み途中の内容を他のプロセスが読まないよう、一時ファイルに書き出してから置き換えます。
+/// 一時ファイルの名前にはプロセスIDを含め、他のプロセスの一時ファイルと衝突しないようにします。
+fn save_state(path: &Path, state: &RuntimeState) -> io::Result<()> {
+    let tmp_path = path.with_extension(format!("state.{}.tmp", process::id()));
+    fs::write(&tmp_path, format_state(state))?;
+    fs::rename(&tmp_path, path)
+}
+
+/// 状態ファイルを読み込み、クロージャで変更を加えてから書き戻します。
+///
+/// 読み込みに失敗した場合は、一時停止やバックオフ期限などを初期状態で上書きしないよう、
+/// 変更を加えずにエラーを返します。
+pub fn update_state<F: FnOnce(&mut RuntimeState)>(f: F) -> io::Result<()> {
+    let _guard = STATE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let path = get_state_path()?;
+    let _file_lock = lock_state_file(&path)?;
+    let mut state = load_state()?;
+    f(&mut state);
+    save_state(&path, &state)
+}
+
+/// ロックファイル（`mydns.state.lock`）を排他的にロックし、他のプロセスの更新が終わるのを待ちます。
+///
+/// ロックは、返された `File` が破棄されたときに解除されます。
+/// ロックファイルは他のアカウントのプロセスが作成したものでも使えるよう、
+/// 書き込みできない場合は読み取り専用で開いてロックします。
+fn lock_state_file(state_path: &Path) -> io::Result<File> {
+    let lock_path = state_path.with_extension("state.lock");
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&lock_path)
+        .or_else(|_| File::open(&lock_path))?;
+    file.lock()?;
+    Ok(file)
+}
+
+/// 指定されたアカウントの実行時データを、クロージャで更新します。
+pub fn update_account_state<F: FnOnce(&mut AccountState)>(master_id: &str, f: F) -> io::Result<()> {
+    update_state(|state| f(state.accounts.entry(master_id.to_string()).or_default()))
+}
+
+/// 状態ファイルの内容を `RuntimeState` に変換します。
+///
+/// INIファイルに似た単純な形式です。解釈できない行は無視します。
+/// ```text
+/// [global]
+/// paused=0
+/// kick=0
+/// service_started=2026-01-01T00:00:00+09:00
+/// [account mydns123456]
+/// in_flight=0
+/// backoff_until=2026-01-01T00:00:00+09:00
+/// last_result=OK
+/// last_attempt=2026-01-01T00:00:00+09:00
+/// last_success=2026-01-01T00:00:00+09:00
+/// last_ip=203.0.113.1,2001:db8::1
+/// expiry_warn_level=0
+/// consecutive_failures=0
+/// failing_since=
+/// mail_alert_sent=0
+/// consecutive_auth_failures=0
+/// ```
+fn parse_state(text: &str) -> RuntimeState {
+    let mut state = RuntimeState::default();
+    // 現在読み込み中のセクション。`None` はグローバルセクションを表す。
+    let mut current: Option<String> = None;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line == "[global]" {
+            current = None;
+            continue;
+        }
+        if let Some(id) = line
+            .strip_prefix("[account ")
+            .and_then(|s| s.strip_suffix(']'))
+        {
+            state.accounts.entry(id.to_string()).or_default();
+            current = Some(id.to_string());
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+
+        match &current {
+            None => match key {
+                "paused" => state.paused = value == "1",
+                "kick" => state.kick_requested = value == "1",
+                "service_started" => state.service_started = parse_time(value),
+                _ => {}
+            },
+            Some(id) => {
+                let account = state.accounts.entry(id.clone()).or_default();
+                match key {
+                    "in_flight" => account.in_flight = value == "1",
+                    "backoff_until" => account.backoff_until = parse_time(value),
+                    "last_result" if !value.is_empty() => {
+                        account.last_result = Some(value.to_string())
+                    }
+                    "last_attempt" => account.last_attempt = parse_time(value),
+                    "last_success" => account.last_success = parse_time(value),
+                    "last_ip" => {
+                        account.last_ip = value.split(',').filter_map(|a| a.parse().ok()).collect()
+                    }
+                    "expiry_warn_level" => {
+                        account.expiry_warn_level = value.parse().unwrap_or_default()
+                    }
+                    "consecutive_failures" => {
+                        account.consecutive_failures = value.parse().unwrap_or_default()
+                    }
+                    "failing_since" => account.failing_since = parse_time(value),
+                    "mail_alert_sent" => account.mail_alert_sent = value == "1",
+                    "consecutive_auth_failures" => {
+                        account.consecutive_auth_failures = value.parse().unwrap_or_default()
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    state
+}
+
+/// `RuntimeState` を状態ファイルの形式の文字列に変換します。
+fn format_state(state: &RuntimeState) -> String {
+    let mut out = String::new();
+    out.push_str("[global]\n");
+    out.push_str(&format!("paused={}\n", state.paused as u8));
+    out.push_str(&format!("kick={}\n", state.kick_requested as u8));
+    out.push_str(&format!(
+        "service_started={}\n",
+        format_time(state.service_started)
+    ));
+    for (id, account) in &state.accounts {
+        out.push_str(&format!("[account {}]\n", id));
+        out.push_str(&format!("in_flight={}\n", account.in_flight as u8));
+        out.push_str(&format!(
+            "backoff_until={}\n",
+            format_time(account.backoff_until)
+        ));
+        // 改行を含むと行形式が崩れるため、空白に置き換えて保存する。
+        let last_result = account.last_result.as_deref().unwrap_or("");
+        out.push_str(&format!(
+            "last_result={}\n",
+            last_result.replace(['\r', '\n'], " ")
+        ));
+        out.push_str(&format!(
+            "last_attempt={}\n",
+            format_time(account.last_attempt)
+        ));
+        out.push_str(&format!(
+            "last_success={}\n",
+            format_time(account.last_success)
+        ));
+        out.push_str(&format!(
+            "last_ip={}\n",
+            account
+                .last_ip
+                .iter()
+                .map(IpAddr::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        ));
+        out.push_str(&format!(
+            "expiry_warn_level={}\n",
+            account.expiry_warn_level
+        ));
+        out.push_str(&format!(
+            "consecutive_failures={}\n",
+            account.consecutive_failures
+        ));
+        out.push_str(&format!(
+            "failing_since={}\n",
+            format_time(account.failing_since)
+        ));
+        out.push_str(&format!(
+            "mail_alert_sent={}\n",
+            account.mail_alert_sent as u8
+        ));
+        out.push_str(&format!(
+            "consecutive_auth_failures={}\n",
+            account.consecutive_auth_failures
+        ));
+    }
+    out
+}
+
+/// RFC 3339形式の文字列を時刻に変換します。空文字列や不正な値は `None` になります。
+fn parse_time(value: &str) -> Option<DateTime<Local>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Local))
+}
+
+/// 時刻をRFC 3339形式の文字列に変換します。`None` は空文字列になります。
+fn format_time(time: Option<DateTime<Local>>) -> String {
+    time.map(|t| t.to_rfc3339()).unwrap_or_default()
+}
@@ -0,0 +1,202 @@
+//! ローカルの利用統計を記録・表示するモジュール。
+//!
+//! 利用者が明示的に有効化した場合（オプトイン）にのみ、通知回数、IPアドレスの変更回数、
+//! サービスの稼働時間を月ごとに集計し、実行時データのディレクトリ（`%ProgramData%\MyDNSAdapter`）の
+//! `mydns.stats` に保存します。
+//! 統計はこのマシンの中だけで使われ、ネットワークに送信されることは一切ありません。
+
+use crate::error::AdapterError;
+use crate::i18n::{MessageKey, get_msg};
+use crate::registry::{load_settings, save_settings};
+use crate::state::data_dir;
+use crate::{format_msg, outln};
+use chrono::{Datelike, Local};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+/// 統計ファイルの名前。
+const STATS_FILE_NAME: &str = "mydns.stats";
+/// 統計ファイルの読み込みから書き戻しまでを、プロセス内で直列化するためのロック。
+static STATS_LOCK: Mutex<()> = Mutex::new(());
+
+/// 1か月分の統計。
+#[derive(Clone, Debug, Default)]
+pub struct MonthlyStats {
+    /// 通知を試行した回数。
+    pub notifications: u64,
+    /// 通知に成功した回数。
+    pub successes: u64,
+    /// 通知に失敗した回数。
+    pub failures: u64,
+    /// IPアドレスの変更を検出した回数。
+    pub ip_changes: u64,
+    /// サービスの稼働時間（秒）。
+    pub uptime_secs: u64,
+}
+
+/// 統計全体。キーは "YYYY-MM" 形式の年月。
+type StatsStore = BTreeMap<String, MonthlyStats>;
+
+/// 統計ファイルのフルパスを取得します。
+fn get_stats_path() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join(STATS_FILE_NAME))
+}
+
+/// 統計の記録が有効になっているかどうかを返します。
+fn stats_enabled() -> bool {
+    load_settings().is_ok_and(|s| s.stats_enabled)
+}
+
+/// 統計の記録が有効な場合に限り、今月の統計をクロージャで更新して保存します。
+///
+/// 統計は補助的な情報であるため、読み書きの失敗は無視します。
+fn update_this_month<F: FnOnce(&mut MonthlyStats)>(f: F) {
+    if !stats_enabled() {
+        return;
+    }
+    let _guard = STATS_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut store = load_stats().unwrap_or_default();
+    let month = Local::now().format("%Y-%m").to_string();
+    f(store.entry(month).or_default());
+    let _ = save_stats(&store);
+}
+
+/// 1回の通知の結果を統計に記録します。
+pub fn record_notification(success: bool) {
+    update_this_month(|m| {
+        m.notifications += 1;
+        if success {
+            m.successes += 1;
+        } else {
+            m.failures += 1;
+        }
+    });
+}
+
+/// 通知に成功したアカウントで、受け付けられたIPアドレスが前回から変わったことを統計に記録します。
+pub fn record_ip_change() {
+    update_this_month(|m| m.ip_changes += 1);
+}
+
+/// サービスの稼働時間を統計に加算します。
+pub fn record_uptime(elapsed: Duration) {
+    update_this_month(|m| m.uptime_secs += elapsed.as_secs());
+}
+
+/// 統計ファイルを読み込みます。ファイルが存在しない場合は空の統計を返します。
+fn load_stats() -> io::Result<StatsStore> {
+    let path = get_stats_path()?;
+    if !path.exists() {
+        return Ok(StatsStore::new());
+    }
+    let text = fs::read_to_string(&path)?;
+
+    // `[YYYY-MM]` で始まるセクションに `key=value` が続く単純な形式。
+    let mut store = StatsStore::new();
+    let mut current: Option<String> = None;
+    for line in text.lines().map(str::trim) {
+        if let Some(month) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            store.entry(month.to_string()).or_default();
+            current = Some(month.to_string());
+            continue;
+        }
+        let (Some(month), Some((key, value))) = (&current, line.split_once('=')) else {
+            continue;
+        };
+        let value: u64 = value.parse().unwrap_or(0);
+        let m = store.entry(month.clone()).or_default();
+        match key {
+            "notifications" => m.notifications = value,
+            "successes" => m.successes = value,
+            "failures" => m.failures = value,
+            "ip_changes" => m.ip_changes = value,
+            "uptime_secs" => m.uptime_secs = value,
+            _ => {}
+        }
+    }
+    Ok(store)
+}
+
+/// 統計ファイルを書き込みます。
+fn save_stats(store: &StatsStore) -> io::Result<()> {
+    let mut out = String::new();
+    for (month, m) in store {
+        out.push_str(&format!("[{}]\n", month));
+        out.push_str(&format!("notifications={}\n", m.notifications));
+        out.push_str(&format!("successes={}\n", m.successes));
+        out.push_str(&format!("failures={}\n", m.failures));
+        out.push_str(&format!("ip_changes={}\n", m.ip_changes));
+        out.push_str(&format!("uptime_secs={}\n", m.uptime_secs));
+    }
+    let path = get_stats_path()?;
+    let tmp_path = path.with_extension("stats.tmp");
+    fs::write(&tmp_path, out)?;
+    fs::rename(&tmp_path, &path)
+}
+
+/// 統計の記録を有効化または無効化します。
+pub fn set_stats_enabled(enabled: bool) -> Result<(), AdapterError> {
+    let mut settings = load_settings()?;
+    settings.stats_enabled = enabled;
+    save_settings(&settings)?;
+    if enabled {
+        outln!("{}", get_msg(MessageKey::StatsEnabled));
+    } else {
+        outln!("{}", get_msg(MessageKey::StatsDisabled));
+    }
+    Ok(())
+}
+
+/// 統計の概要を表示します。
+///
+/// 月ごとの内訳と、今年の合計を表示します。
+pub fn print_stats_summary() -> io::Result<()> {
+    println!("{}", get_msg(MessageKey::StatsTitle));
+    if !stats_enabled() {
+        println!("{}", get_msg(MessageKey::StatsNotEnabled));
+    }
+
+    let store = load_stats()?;
+    if store.is_empty() {
+        println!("{}", get_msg(MessageKey::StatsEmpty));
+        return Ok(());
+    }
+
+    for (month, m) in &store {
+        println!(
+            "{}",
+            format_msg!(
+                MessageKey::StatsMonthFmt,
+                month = month,
+                total = m.notifications,
+                ok = m.successes,
+                ng = m.failures,
+                ip = m.ip_changes,
+                hours = format!("{:.1}", m.uptime_secs as f64 / 3600.0)
+            )
+        );
+    }
+
+    // 今年の合計
+    let year = Local::now().year().to_string();
+    let (notifications, ip_changes) = store
+        .iter()
+        .filter(|(month, _)| month.starts_with(&year))
+        .fold((0, 0), |(n, ip), (_, m)| {
+            (n + m.notifications, ip + m.ip_changes)
+        });
+    println!(
+        "{}",
+        format_msg!(
+            MessageKey::StatsYearFmt,
+            year = year,
+            total = notifications,
+            ip = ip_changes
+        )
+    );
+    Ok(())
+}
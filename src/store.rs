@@ -0,0 +1,453 @@
+//! アカウントの設定の保存先（バックエンド）を抽象化するモジュール。
+//!
+//! アカウントの読み込み、保存、削除、名前の変更と、変更の監視を `ConfigStore` トレイトにまとめ、
+//! 呼び出し元が保存先の種類を意識せずに済むようにします。
+//! 通知の結果の記録（最終成功時刻、最後のエラー）と自動停止の記録も、アカウントと同じ保存先に書き込みます。
+//!
+//! 保存先は次の順に決まります。
+//! 1. `--config-file <PATH>` で指定された設定ファイル（`filestore::FileStore`）
+//! 2. マシン全体の保存先で、既定の設定ファイル（`%ProgramData%\MyDNSAdapter\config.toml`）がある場合はそのファイル
+//! 3. `ConfigScope` で選んだレジストリのキー（`registry::RegistryStore`）
+
+use crate::error::AdapterError;
+use crate::filestore::{FileStore, default_config_path};
+#[cfg(test)]
+use crate::format_msg;
+#[cfg(test)]
+use crate::i18n::MessageKey;
+use crate::registry::{Config, ConfigScope, LastError, LastSuccess, RegistryStore, config_scope};
+use chrono::{DateTime, Local};
+use std::any::Any;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+
+/// `--config-file` で指定された設定ファイルのパス。シミュレーションモードでは一時的な設定ファイルに差し替えられる。
+static CONFIG_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// 変更の監視を表すハンドル。破棄（`drop`）されると監視を終了します。
+pub type StoreWatcher = Box<dyn Any>;
+
+/// アカウントの設定の保存先。
+pub trait ConfigStore {
+    /// すべてのアカウントの設定を読み込みます。保存先にまだ何もない場合は、空のベクターを返します。
+    fn load_all(&self) -> Result<Vec<Config>, AdapterError>;
+
+    /// アカウントの設定を保存します。同じMasterIDの設定があれば上書きします。
+    ///
+    /// 途中で失敗した場合、そのアカウントの設定は保存前の状態のまま残ります。
+    fn save(&self, config: &Config) -> Result<(), AdapterError>;
+
+    /// 指定されたMasterIDのアカウントの設定を削除します。
+    fn delete(&self, master_id: &str) -> Result<(), AdapterError>;
+
+    /// アカウントのMasterIDを変更します。
+    ///
+    /// 新しいMasterIDのアカウントが既に存在する場合は、上書きせずにエラーを返します。
+    fn rename(&self, old_id: &str, new_id: &str) -> Result<(), AdapterError>;
+
+    /// アカウントの設定の変更の監視を開始します。
+    ///
+    /// `on_change` は、アカウントの追加・削除や設定の変更があるたびに、監視用のスレッドから呼び出されます。
+    /// 1回の保存で続けて何度か呼び出されることがあります。
+    fn watch(&self, on_change: Box<dyn Fn() + Send>) -> Result<StoreWatcher, AdapterError>;
+
+    /// 通知に成功した時刻と、MyDNS.JPが受け付けたアドレスを記録します。
+    ///
+    /// `addresses` に含まれないプロトコルのアドレスは、前回の値を残します。
+    /// 通知中にアカウントが削除された場合に備え、存在するアカウントにだけ記録します。
+    fn save_last_success(
+        &self,
+        master_id: &str,
+        time: DateTime<Local>,
+        addresses: &[IpAddr],
+    ) -> Result<(), AdapterError>;
+
+    /// 最後に通知に成功した時刻とアドレスを読み込みます。記録がない項目は `None` になります。
+    fn load_last_success(&self, master_id: &str) -> LastSuccess;
+
+    /// 通知に失敗した時刻と、失敗の分類と内容を記録します。記録するのは最後の1件だけです。
+    fn save_last_error(
+        &self,
+        master_id: &str,
+        time: DateTime<Local>,
+        kind: &str,
+        detail: &str,
+    ) -> Result<(), AdapterError>;
+
+    /// 最後に通知に失敗した時刻と内容を読み込みます。記録がない場合は `None` を返します。
+    fn load_last_error(&self, master_id: &str) -> Option<LastError>;
+
+    /// アカウントの通知を自動で停止した時刻を記録します。`None` を指定すると記録を消し、通知を再開します。
+    fn save_suspended(
+        &self,
+        master_id: &str,
+        time: Option<DateTime<Local>>,
+    ) -> Result<(), AdapterError>;
+
+    /// 通知を自動で停止した時刻を読み込みます。停止されていない場合は `None` を返します。
+    fn load_suspended(&self, master_id: &str) -> Option<DateTime<Local>>;
+
+    /// 画面やログに表示する、保存先の場所（レジストリのキーやファイルのパス）を返します。
+    fn location(&self) -> String;
+}
+
+/// 以降のアカウントの設定の読み書きで使う設定ファイルを指定します。
+///
+/// 指定し直した場合は、最後に指定したファイルを使います。
+pub fn set_config_file(path: PathBuf) {
+    *CONFIG_FILE.lock().unwrap_or_else(PoisonError::into_inner) = Some(path);
+}
+
+/// アカウントの設定を設定ファイルに保存する場合は、そのパスを返します。
+///
+/// `--config-file` で指定されていなければ、マシン全体の保存先で既定の設定ファイルがある場合にそのパスを返します。
+pub fn config_file() -> Option<PathBuf> {
+    if let Some(path) = CONFIG_FILE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+    {
+        return Some(path);
+    }
+    let path = default_config_path();
+    (config_scope() == ConfigScope::Machine && path.is_file()).then_some(path)
+}
+
+/// 現在の保存先のストアを返します。
+pub fn active_store() -> Box<dyn ConfigStore> {
+    match config_file() {
+        Some(path) => Box::new(FileStore::new(path)),
+        None => Box::new(RegistryStore::new(config_scope())),
+    }
+}
+
+/// 現在の保存先から、すべてのアカウントの設定を読み込みます。
+pub fn load_all_configs() -> Result<Vec<Config>, AdapterError> {
+    active_store().load_all()
+}
+
+/// 現在の保存先に、アカウントの設定を保存します。
+pub fn save_config(config: &Config) -> Result<(), AdapterError> {
+    active_store().save(config)
+}
+
+/// 現在の保存先から、アカウントの設定を削除します。
+pub fn delete_config(master_id: &str) -> Result<(), AdapterError> {
+    active_store().delete(master_id)
+}
+
+/// 現在の保存先で、アカウントのMasterIDを変更します。
+pub fn rename_config(old_id: &str, new_id: &str) -> Result<(), AdapterError> {
+    active_store().rename(old_id, new_id)
+}
+
+/// 現在の保存先に、通知に成功した時刻とアドレスを記録します。
+pub fn save_last_success(
+    master_id: &str,
+    time: DateTime<Local>,
+    addresses: &[IpAddr],
+) -> Result<(), AdapterError> {
+    active_store().save_last_success(master_id, time, addresses)
+}
+
+/// 現在の保存先から、最後に通知に成功した時刻とアドレスを読み込みます。
+pub fn load_last_success(master_id: &str) -> LastSuccess {
+    active_store().load_last_success(master_id)
+}
+
+/// 現在の保存先に、通知に失敗した時刻と内容を記録します。
+pub fn save_last_error(
+    master_id: &str,
+    time: DateTime<Local>,
+    kind: &str,
+    detail: &str,
+) -> Result<(), AdapterError> {
+    active_store().save_last_error(master_id, time, kind, detail)
+}
+
+/// 現在の保存先から、最後に通知に失敗した時刻と内容を読み込みます。
+pub fn load_last_error(master_id: &str) -> Option<LastError> {
+    active_store().load_last_error(master_id)
+}
+
+/// 現在の保存先に、アカウントの通知を自動で停止した時刻を記録します。
+pub fn save_suspended(master_id: &str, time: Option<DateTime<Local>>) -> Result<(), AdapterError> {
+    active_store().save_suspended(master_id, time)
+}
+
+/// 現在の保存先から、通知を自動で停止した時刻を読み込みます。
+pub fn load_suspended(master_id: &str) -> Option<DateTime<Local>> {
+    active_store().load_suspended(master_id)
+}
+
+/// アカウントの設定と記録をメモリ上にだけ保持する `ConfigStore` の実装。
+///
+/// レジストリやファイルに触れずに、保存先に依存する処理をテストするために使います。
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryStore {
+    /// 保存された順の、アカウントの設定と記録。
+    accounts: Mutex<Vec<MemoryAccount>>,
+}
+
+/// `MemoryStore` に保持する、ひとつのアカウントの設定と記録。
+#[cfg(test)]
+struct MemoryAccount {
+    config: Config,
+    last_success: LastSuccess,
+    last_error: Option<LastError>,
+    suspended: Option<DateTime<Local>>,
+}
+
+#[cfg(test)]
+impl MemoryStore {
+    /// 指定されたアカウントを `update` で変更します。アカウントがない場合はエラーを返します。
+    fn update_account<F: FnOnce(&mut MemoryAccount)>(
+        &self,
+        master_id: &str,
+        update: F,
+    ) -> Result<(), AdapterError> {
+        let mut accounts = self.accounts.lock().unwrap_or_else(PoisonError::into_inner);
+        let account = accounts
+            .iter_mut()
+            .find(|a| a.config.master_id == master_id)
+            .ok_or_else(|| format_msg!(MessageKey::AccountNotFoundFmt, id = master_id))?;
+        update(account);
+        Ok(())
+    }
+
+    /// 指定されたアカウントの記録を `read` で読み出します。アカウントがない場合は `None` を返します。
+    fn read_account<T, F: FnOnce(&MemoryAccount) -> T>(
+        &self,
+        master_id: &str,
+        read: F,
+    ) -> Option<T> {
+        self.accounts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .find(|a| a.config.master_id == master_id)
+            .map(read)
+    }
+}
+
+#[cfg(test)]
+impl ConfigStore for MemoryStore {
+    fn load_all(&self) -> Result<Vec<Config>, AdapterError> {
+        Ok(self
+            .accounts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|a| a.config.clone())
+            .collect())
+    }
+
+    fn save(&self, config: &Config) -> Result<(), AdapterError> {
+        let mut accounts = self.accounts.lock().unwrap_or_else(PoisonError::into_inner);
+        match accounts
+            .iter_mut()
+            .find(|a| a.config.master_id == config.master_id)
+        {
+            Some(existing) => existing.config = config.clone(),
+            None => accounts.push(MemoryAccount {
+                config: config.clone(),
+                last_success: LastSuccess::default(),
+                last_error: None,
+                suspended: None,
+            }),
+        }
+        Ok(())
+    }
+
+    fn delete(&self, master_id: &str) -> Result<(), AdapterError> {
+        let mut accounts = self.accounts.lock().unwrap_or_else(PoisonError::into_inner);
+        let count = accounts.len();
+        accounts.retain(|a| a.config.master_id != master_id);
+        if accounts.len() == count {
+            return Err(format_msg!(MessageKey::AccountNotFoundFmt, id = master_id).into());
+        }
+        Ok(())
+    }
+
+    fn rename(&self, old_id: &str, new_id: &str) -> Result<(), AdapterError> {
+        let mut accounts = self.accounts.lock().unwrap_or_else(PoisonError::into_inner);
+        if accounts.iter().any(|a| a.config.master_id == new_id) {
+            return Err(format_msg!(MessageKey::AccountExistsFmt, id = new_id).into());
+        }
+        let account = accounts
+            .iter_mut()
+            .find(|a| a.config.master_id == old_id)
+            .ok_or_else(|| format_msg!(MessageKey::AccountNotFoundFmt, id = old_id))?;
+        account.config.master_id = new_id.to_string();
+        Ok(())
+    }
+
+    fn watch(&self, _on_change: Box<dyn Fn() + Send>) -> Result<StoreWatcher, AdapterError> {
+        // メモリ上の設定は他のプロセスから変更されないため、監視するものがない。
+        Ok(Box::new(()))
+    }
+
+    fn save_last_success(
+        &self,
+        master_id: &str,
+        time: DateTime<Local>,
+        addresses: &[IpAddr],
+    ) -> Result<(), AdapterError> {
+        self.update_account(master_id, |account| {
+            account.last_success.time = Some(time);
+            for address in addresses {
+                match address {
+                    IpAddr::V4(v4) => account.last_success.ipv4 = Some(*v4),
+                    IpAddr::V6(v6) => account.last_success.ipv6 = Some(*v6),
+                }
+            }
+        })
+    }
+
+    fn load_last_success(&self, master_id: &str) -> LastSuccess {
+        self.read_account(master_id, |a| a.last_success.clone())
+            .unwrap_or_default()
+    }
+
+    fn save_last_error(
+        &self,
+        master_id: &str,
+        time: DateTime<Local>,
+        kind: &str,
+        detail: &str,
+    ) -> Result<(), AdapterError> {
+        self.update_account(master_id, |account| {
+            account.last_error = Some(LastError {
+                time,
+                kind: kind.to_string(),
+                detail: detail.to_string(),
+            });
+        })
+    }
+
+    fn load_last_error(&self, master_id: &str) -> Option<LastError> {
+        self.read_account(master_id, |a| a.last_error.clone())
+            .flatten()
+    }
+
+    fn save_suspended(
+        &self,
+        master_id: &str,
+        time: Option<DateTime<Local>>,
+    ) -> Result<(), AdapterError> {
+        self.update_account(master_id, |account| account.suspended = time)
+    }
+
+    fn load_suspended(&self, master_id: &str) -> Option<DateTime<Local>> {
+        self.read_account(master_id, |a| a.suspended).flatten()
+    }
+
+    fn location(&self) -> String {
+        "memory".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    /// テスト用のアカウントの設定を作成します。
+    fn account(master_id: &str, password: &str) -> Config {
+        Config {
+            master_id: master_id.to_string(),
+            password: password.to_string(),
+            ipv4_notify: true,
+            ..Config::default()
+        }
+    }
+
+    /// すべての保存先の実装に共通する振る舞いを確かめます。
+    fn check_store(store: &dyn ConfigStore) {
+        assert!(store.load_all().unwrap().is_empty());
+
+        // 同じMasterIDの保存は、追加ではなく上書きになる。
+        store.save(&account("mydns000001", "first")).unwrap();
+        store.save(&account("mydns000002", "second")).unwrap();
+        store.save(&account("mydns000001", "updated")).unwrap();
+        let configs = store.load_all().unwrap();
+        assert_eq!(configs.len(), 2);
+        assert_eq!(
+            configs
+                .iter()
+                .find(|c| c.master_id == "mydns000001")
+                .unwrap()
+                .password,
+            "updated"
+        );
+
+        // 記録は、存在するアカウントにだけ書き込める。
+        let now = Local::now();
+        let address: IpAddr = "203.0.113.1".parse().unwrap();
+        store
+            .save_last_success("mydns000001", now, &[address])
+            .unwrap();
+        store
+            .save_last_error("mydns000001", now, "auth", "LOGIN FAILED")
+            .unwrap();
+        store.save_suspended("mydns000001", Some(now)).unwrap();
+        assert!(store.save_last_success("mydns999999", now, &[]).is_err());
+        assert!(store.save_suspended("mydns999999", Some(now)).is_err());
+
+        // 使われているMasterIDへの変更は、上書きせずに失敗する。
+        assert!(store.rename("mydns000001", "mydns000002").is_err());
+        assert_eq!(store.load_all().unwrap().len(), 2);
+        assert!(store.rename("mydns999999", "mydns000003").is_err());
+
+        // 変更したMasterIDに、設定と記録が引き継がれる。
+        store.rename("mydns000001", "mydns000003").unwrap();
+        let ids: Vec<_> = store
+            .load_all()
+            .unwrap()
+            .into_iter()
+            .map(|c| c.master_id)
+            .collect();
+        assert!(ids.contains(&"mydns000003".to_string()));
+        assert!(!ids.contains(&"mydns000001".to_string()));
+        let last_success = store.load_last_success("mydns000003");
+        assert_eq!(
+            last_success.time.map(|t| t.timestamp()),
+            Some(now.timestamp())
+        );
+        assert_eq!(last_success.ipv4, Some("203.0.113.1".parse().unwrap()));
+        assert_eq!(
+            store.load_last_error("mydns000003").map(|e| e.kind),
+            Some("auth".to_string())
+        );
+        assert!(store.load_suspended("mydns000003").is_some());
+        assert!(store.load_last_success("mydns000001").time.is_none());
+
+        // 停止の記録は消せる。
+        store.save_suspended("mydns000003", None).unwrap();
+        assert!(store.load_suspended("mydns000003").is_none());
+
+        // 削除したアカウントの記録は残らない。
+        store.delete("mydns000003").unwrap();
+        assert!(store.delete("mydns000003").is_err());
+        assert!(store.load_last_error("mydns000003").is_none());
+        store.save(&account("mydns000003", "again")).unwrap();
+        assert!(store.load_last_success("mydns000003").time.is_none());
+    }
+
+    #[test]
+    fn memory_store_follows_store_contract() {
+        check_store(&MemoryStore::default());
+    }
+
+    #[test]
+    fn file_store_follows_store_contract() {
+        let path = env::temp_dir().join(format!("mydns-store-test-{}.toml", process::id()));
+        let store = FileStore::new(path);
+        let _ = store.delete_files();
+        check_store(&store);
+        store.delete_files().unwrap();
+    }
+}
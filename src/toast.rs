@@ -0,0 +1,152 @@
+//! 通知の失敗が続いていることを、Windowsのトースト通知でデスクトップのユーザーに知らせるモジュール。
+//!
+//! アカウントの通知が `--toast-after <COUNT>` 回続けて失敗すると、トースト通知を表示します。
+//! MyDNS.JPのレコードの失効が近づいたとき（`expiry` モジュールの `Warning` 以上の段階）も表示します。
+//! サービス（セッション0）や SYSTEM のタスクからはデスクトップに表示できないため、
+//! ユーザーのセッションで動くCLIから表示します。
+//! - `notify` で通知した結果、連続した失敗の回数がちょうど設定の回数に達したとき
+//! - `--toast-monitor` で、稼働中のサービスが記録する実行時状態を監視しているとき
+//!
+//! 連続した失敗の回数は、共有の実行時状態（`state` モジュール）に記録されています。
+
+use crate::expiry::{ExpiryAlert, ExpiryLevel, remaining_hours};
+use crate::i18n::{MessageKey, get_msg};
+use crate::logging::log_detail;
+use crate::registry::{Config, load_settings};
+use crate::state::load_state;
+use crate::{format_msg, format_msg_log, outln};
+use chrono::Local;
+use std::collections::BTreeMap;
+use std::io;
+use std::thread;
+use std::time::Duration;
+use windows::Data::Xml::Dom::XmlDocument;
+use windows::UI::Notifications::{ToastNotification, ToastNotificationManager};
+use windows::core::HSTRING;
+
+/// トースト通知を表示するアプリのID（AppUserModelID）。
+///
+/// インストーラーを持たないアプリは独自のIDを登録できないため、Windows PowerShellのIDを借りて表示する。
+const TOAST_APP_ID: &str =
+    "{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\\WindowsPowerShell\\v1.0\\powershell.exe";
+/// `--toast-monitor` で実行時状態を確認する間隔。
+const MONITOR_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// タイトルと本文を指定して、トースト通知を表示します。
+pub fn show_toast(title: &str, body: &str) -> windows::core::Result<()> {
+    let xml = format!(
+        "<toast><visual><binding template=\"ToastGeneric\"><text>{}</text><text>{}</text></binding></visual></toast>",
+        escape_xml(title),
+        escape_xml(body)
+    );
+    let document = XmlDocument::new()?;
+    document.LoadXml(&HSTRING::from(xml))?;
+    let toast = ToastNotification::CreateToastNotification(&document)?;
+    ToastNotificationManager::CreateToastNotifierWithId(&HSTRING::from(TOAST_APP_ID))?.Show(&toast)
+}
+
+/// `notify` による通知の後に、連続した失敗の回数が設定の回数に達したアカウントを知らせます。
+///
+/// 回数がちょうど設定の回数になったときだけ表示し、失敗が続いても繰り返し表示しません。
+pub fn toast_failure_streaks(configs: &[Config]) {
+    let threshold = load_settings().unwrap_or_default().toast_failure_threshold;
+    if threshold == 0 {
+        return;
+    }
+    let state = load_state().unwrap_or_default();
+    for config in configs {
+        if let Some(account) = state.accounts.get(&config.master_id)
+            && account.consecutive_failures == threshold
+        {
+            toast_failure(
+                &config.master_id,
+                account.consecutive_failures,
+                account.last_result.as_deref().unwrap_or_default(),
+            );
+        }
+    }
+}
+
+/// `notify` による通知の後に、レコードの失効が近づいたアカウントを知らせます。
+pub fn toast_expiry_alerts(alerts: &[ExpiryAlert]) {
+    for alert in alerts {
+        toast_expiry(&alert.master_id, alert.hours, alert.remaining);
+    }
+}
+
+/// 稼働中のサービスが記録する実行時状態を監視し、連続した失敗の回数が設定の回数に達したら知らせます。
+///
+/// ユーザーのログオン時に起動しておく使い方を想定しています。Ctrl+Cで終了するまで戻りません。
+/// 開始した時点ですでに失敗が続いているアカウントも、一度だけ知らせます。
+/// サービスが記録する失効の警告の段階が `Warning` 以上に上がったアカウントも知らせます。
+pub fn toast_monitor_mode() -> io::Result<()> {
+    outln!("{}", get_msg(MessageKey::ToastMonitorStarted));
+    // アカウントごとの、前回確認したときの連続した失敗の回数。
+    let mut previous: BTreeMap<String, u32> = BTreeMap::new();
+    // アカウントごとの、前回確認したときの失効の警告の段階。
+    let mut previous_levels: BTreeMap<String, u8> = BTreeMap::new();
+    loop {
+        // 設定は確認のたびに読み込み、`--toast-after` の変更をすぐに反映する。
+        let threshold = load_settings().unwrap_or_default().toast_failure_threshold;
+        let state = load_state().unwrap_or_default();
+        for (id, account) in &state.accounts {
+            let count = account.consecutive_failures;
+            let before = previous.insert(id.clone(), count).unwrap_or(0);
+            if threshold != 0 && before < threshold && count >= threshold {
+                toast_failure(
+                    id,
+                    count,
+                    account.last_result.as_deref().unwrap_or_default(),
+                );
+            }
+            let level = account.expiry_warn_level;
+            let before = previous_levels.insert(id.clone(), level).unwrap_or(0);
+            if level > before
+                && level >= ExpiryLevel::Warning as u8
+                && let Some(last_success) = account.last_success
+            {
+                let elapsed = Local::now() - last_success;
+                toast_expiry(id, elapsed.num_hours(), remaining_hours(elapsed));
+            }
+        }
+        thread::sleep(MONITOR_POLL_INTERVAL);
+    }
+}
+
+/// アカウントの通知の失敗が続いていることを、トースト通知で表示します。
+///
+/// 表示できなかった場合は、詳細ログに記録するだけにとどめます。
+fn toast_failure(master_id: &str, count: u32, error: &str) {
+    let body = format_msg!(
+        MessageKey::ToastFailuresFmt,
+        id = master_id,
+        count = count,
+        error = error
+    );
+    if let Err(e) = show_toast(get_msg(MessageKey::ToastTitle), &body) {
+        log_detail(&format_msg_log!(MessageKey::LogToastFailedFmt, error = e));
+    }
+}
+
+/// MyDNS.JPのレコードの失効が近づいていることを、トースト通知で表示します。
+///
+/// 表示できなかった場合は、詳細ログに記録するだけにとどめます。
+fn toast_expiry(master_id: &str, hours: i64, remaining: i64) {
+    let body = format_msg!(
+        MessageKey::ToastExpiryFmt,
+        id = master_id,
+        hours = hours,
+        remaining = remaining
+    );
+    if let Err(e) = show_toast(get_msg(MessageKey::ToastExpiryTitle), &body) {
+        log_detail(&format_msg_log!(MessageKey::LogToastFailedFmt, error = e));
+    }
+}
+
+/// XMLの本文に含められるよう、特別な意味を持つ文字をエスケープします。
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
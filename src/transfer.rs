@@ -0,0 +1,738 @@
+//! アカウント設定をファイルに書き出し、またファイルから読み込むためのモジュール。
+//!
+//! `--export <FILE>` で、レジストリに保存されたすべてのアカウント設定をJSONまたはTOML形式で書き出します。
+//! 形式はファイルの拡張子（`.json` / `.toml`）で決まり、それ以外の拡張子ではJSONを使います。
+//! 書き出したファイルは、設定のバックアップや別のマシンへの移行に使えます。
+//!
+//! `--import <FILE>` は、書き出したファイルを読み込んで検証し、現在の設定との差分を求めます。
+//! 読み込みには外部クレートを使わず、JSONは `json` モジュールのパーサーで解釈します。
+//! TOMLは、このモジュールが書き出す形式を解釈できる範囲のサブセットのパーサーを備えています。
+//!
+//! JSONの例:
+//! ```text
+//! {
+//!   "version": 1,
+//!   "accounts": [
+//!     {
+//!       "master_id": "mydns123456",
+//!       "password": "secret",
+//!       "ipv4_notify": true,
+//!       ...
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! TOMLの例:
+//! ```text
+//! version = 1
+//!
+//! [[accounts]]
+//! master_id = "mydns123456"
+//! password = "secret"
+//! ipv4_notify = true
+//! ...
+//! ```
+//!
+//! TOML形式は、アカウントの設定をファイルに保存する `filestore` モジュールの設定ファイルの形式としても使います。
+
+use crate::error::AdapterError;
+use crate::i18n::{MessageKey, get_msg};
+use crate::json::{self, Json};
+use crate::logging::log_info;
+use crate::registry::{Config, DEFAULT_KEEP_ALIVE_HOURS, NotifyMethod, Provider};
+use crate::schedule::Schedule;
+use crate::store::load_all_configs;
+use crate::{format_msg, outln};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// 書き出すファイルの形式のバージョン。形式を変更した場合に増やす。
+/// 読み込みでは、このバージョン以下のファイルを受け付ける。
+const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// 書き出す・読み込むファイルの形式。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FileFormat {
+    Json,
+    Toml,
+}
+
+impl FileFormat {
+    /// ファイルの拡張子から形式を決定します。`.toml` 以外はJSONとして扱います。
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => FileFormat::Toml,
+            _ => FileFormat::Json,
+        }
+    }
+}
+
+/// 設定ファイル中の1つの値。
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Value {
+    Str(String),
+    Bool(bool),
+    Int(u32),
+}
+
+/// アカウント設定を、ファイルに書き出す項目名と値の組に変換します。
+///
+/// `include_password` が `false` の場合、パスワードの項目を含めません。
+fn config_fields(config: &Config, include_password: bool) -> Vec<(&'static str, Value)> {
+    let mut fields = vec![("master_id", Value::Str(config.master_id.clone()))];
+    if include_password {
+        fields.push(("password", Value::Str(config.password.clone())));
+    }
+    fields.extend([
+        ("ipv4_notify", Value::Bool(config.ipv4_notify)),
+        ("ipv6_notify", Value::Bool(config.ipv6_notify)),
+        ("keep_alive_hours", Value::Int(config.keep_alive_hours)),
+        ("proxy_url", Value::Str(config.proxy_url.clone())),
+        ("ipv4_url", Value::Str(config.ipv4_url.clone())),
+        ("ipv6_url", Value::Str(config.ipv6_url.clone())),
+        ("hostname", Value::Str(config.hostname.clone())),
+        ("provider", Value::Str(config.provider.code().to_string())),
+        ("method", Value::Str(config.method.code().to_string())),
+        ("ping_url", Value::Str(config.ping_url.clone())),
+        ("bind_interface", Value::Str(config.bind_interface.clone())),
+        ("schedule", Value::Str(config.schedule.clone())),
+    ]);
+    fields
+}
+
+/// すべてのアカウント設定を、指定されたファイルに書き出します。
+///
+/// `redact_passwords` が `true` の場合、パスワードを含めずに書き出します。
+pub fn export_mode(path: &Path, redact_passwords: bool) -> Result<(), AdapterError> {
+    let configs = load_all_configs()?;
+    if configs.is_empty() {
+        outln!("{}", get_msg(MessageKey::ViewNoAccounts));
+        return Ok(());
+    }
+
+    let accounts: Vec<_> = configs
+        .iter()
+        .map(|c| config_fields(c, !redact_passwords))
+        .collect();
+    let text = match FileFormat::from_path(path) {
+        FileFormat::Json => to_json(&accounts),
+        FileFormat::Toml => to_toml(&accounts),
+    };
+    fs::write(path, text)?;
+
+    outln!(
+        "{}",
+        format_msg!(
+            MessageKey::ExportSuccessFmt,
+            count = configs.len(),
+            path = path.display()
+        )
+    );
+    if !redact_passwords {
+        // パスワードは平文で書き出されるため、ファイルの扱いに注意を促す。
+        outln!("{}", get_msg(MessageKey::ExportPasswordWarning));
+    }
+    log_info(&format!(
+        "Exported {} account(s) to {}.",
+        configs.len(),
+        path.display()
+    ));
+    Ok(())
+}
+
+/// 設定ファイル（`filestore` モジュール）の内容を解釈し、アカウント設定の一覧を返します。
+///
+/// 形式は `--export` で書き出すTOML形式と同じです。省略した項目は、新規追加時の既定値になります。
+/// 1つでも検証に失敗したアカウントがあれば、どのアカウントも返しません。
+pub fn parse_config_file(text: &str) -> Result<Vec<Config>, String> {
+    let file = parse_toml_file(text)?;
+    if file.version == 0 || file.version > EXPORT_FORMAT_VERSION {
+        return Err(format_msg!(
+            MessageKey::ImportUnsupportedVersionFmt,
+            version = file.version
+        ));
+    }
+    let mut configs: Vec<Config> = Vec::new();
+    for (index, fields) in file.accounts.iter().enumerate() {
+        let config =
+            config_from_fields(fields, &[]).map_err(|e| format!("#{}: {}", index + 1, e))?;
+        if configs.iter().any(|c| c.master_id == config.master_id) {
+            return Err(format_msg!(
+                MessageKey::ImportDuplicateFmt,
+                id = config.master_id
+            ));
+        }
+        configs.push(config);
+    }
+    Ok(configs)
+}
+
+/// アカウント設定の一覧を、設定ファイル（`filestore` モジュール）の内容に変換します。
+pub fn format_config_file(configs: &[Config]) -> String {
+    let accounts: Vec<_> = configs.iter().map(|c| config_fields(c, true)).collect();
+    to_toml(&accounts)
+}
+
+/// アカウント設定の一覧をJSON形式の文字列に変換します。
+fn to_json(accounts: &[Vec<(&'static str, Value)>]) -> String {
+    let mut out = String::from("{\n");
+    out.push_str(&format!("  \"version\": {},\n", EXPORT_FORMAT_VERSION));
+    out.push_str("  \"accounts\": [");
+    for (i, fields) in accounts.iter().enumerate() {
+        out.push_str(if i == 0 { "\n" } else { ",\n" });
+        out.push_str("    {\n");
+        for (j, (key, value)) in fields.iter().enumerate() {
+            let separator = if j + 1 < fields.len() { "," } else { "" };
+            out.push_str(&format!(
+                "      {}: {}{}\n",
+                quote(key),
+                format_value(value),
+                separator
+            ));
+        }
+        out.push_str("    }");
+    }
+    out.push_str("\n  ]\n}\n");
+    out
+}
+
+/// アカウント設定の一覧をTOML形式の文字列に変換します。
+fn to_toml(accounts: &[Vec<(&'static str, Value)>]) -> String {
+    let mut out = format!("version = {}\n", EXPORT_FORMAT_VERSION);
+    for fields in accounts {
+        out.push_str("\n[[accounts]]\n");
+        for (key, value) in fields {
+            out.push_str(&format!("{} = {}\n", key, format_value(value)));
+        }
+    }
+    out
+}
+
+/// 値を、JSONとTOMLに共通する表記の文字列に変換します。
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::Str(s) => quote(s),
+        Value::Bool(b) => b.to_string(),
+        Value::Int(n) => n.to_string(),
+    }
+}
+
+/// 読み込んだファイルの1アカウント分の項目。
+type AccountFields = BTreeMap<String, Value>;
+
+/// 読み込んだファイルの内容。
+struct ImportedFile {
+    /// ファイルの形式のバージョン。
+    version: u32,
+    /// アカウントごとの項目。
+    accounts: Vec<AccountFields>,
+}
+
+/// インポートするアカウント設定と、現在の設定との差分。
+pub struct ImportPlan {
+    /// インポート後のアカウント設定。
+    pub config: Config,
+    /// 現在の設定との差分。
+    pub change: ImportChange,
+}
+
+/// インポートによるアカウント設定の変化。
+pub enum ImportChange {
+    /// 新しく追加されるアカウント。
+    New,
+    /// 既存のアカウントの変更。変更される項目の名前と、変更前後の値の表示。
+    Changed(Vec<(&'static str, String, String)>),
+    /// 変更のないアカウント。
+    Unchanged,
+}
+
+/// ファイルを読み込んで検証し、アカウントごとのインポート計画を返します。
+///
+/// `existing` は現在の設定です。ファイルにない項目（パスワードを除外して書き出した場合のパスワードなど）は、
+/// 既存のアカウントであれば現在の値を、新しいアカウントであれば既定値を使います。
+/// ただし、新しいアカウントのパスワードは省略できません。
+/// 検証に失敗した場合は、レジストリを一切変更しないよう、どのアカウントも返しません。
+pub fn plan_import(path: &Path, existing: &[Config]) -> Result<Vec<ImportPlan>, AdapterError> {
+    let text = fs::read_to_string(path)?;
+    let file = match FileFormat::from_path(path) {
+        FileFormat::Json => parse_json_file(&text),
+        FileFormat::Toml => parse_toml_file(&text),
+    }
+    .map_err(|e| format_msg!(MessageKey::ImportParseErrorFmt, error = e))?;
+
+    if file.version == 0 || file.version > EXPORT_FORMAT_VERSION {
+        return Err(format_msg!(
+            MessageKey::ImportUnsupportedVersionFmt,
+            version = file.version
+        )
+        .into());
+    }
+
+    let mut plans: Vec<ImportPlan> = Vec::new();
+    for (index, fields) in file.accounts.iter().enumerate() {
+        let config =
+            config_from_fields(fields, existing).map_err(|e| format!("#{}: {}", index + 1, e))?;
+        if plans.iter().any(|p| p.config.master_id == config.master_id) {
+            return Err(format_msg!(MessageKey::ImportDuplicateFmt, id = config.master_id).into());
+        }
+        let change = match existing.iter().find(|c| c.master_id == config.master_id) {
+            None => ImportChange::New,
+            Some(current) => {
+                let changes = diff_fields(current, &config);
+                if changes.is_empty() {
+                    ImportChange::Unchanged
+                } else {
+                    ImportChange::Changed(changes)
+                }
+            }
+        };
+        plans.push(ImportPlan { config, change });
+    }
+    Ok(plans)
+}
+
+/// 読み込んだ項目からアカウント設定を組み立て、内容を検証します。
+fn config_from_fields(fields: &AccountFields, existing: &[Config]) -> Result<Config, String> {
+    const KNOWN_FIELDS: &[&str] = &[
+        "master_id",
+        "password",
+        "ipv4_notify",
+        "ipv6_notify",
+        "keep_alive_hours",
+        "proxy_url",
+        "ipv4_url",
+        "ipv6_url",
+        "hostname",
+        "provider",
+        "method",
+        "ping_url",
+        "bind_interface",
+        "schedule",
+    ];
+    if let Some(unknown) = fields.keys().find(|k| !KNOWN_FIELDS.contains(&k.as_str())) {
+        return Err(format_msg!(
+            MessageKey::ImportUnknownFieldFmt,
+            field = unknown
+        ));
+    }
+
+    let master_id = match fields.get("master_id") {
+        Some(Value::Str(id)) => id.trim().to_string(),
+        Some(_) => {
+            return Err(format_msg!(
+                MessageKey::ImportInvalidTypeFmt,
+                field = "master_id"
+            ));
+        }
+        None => {
+            return Err(format_msg!(
+                MessageKey::ImportMissingFieldFmt,
+                field = "master_id"
+            ));
+        }
+    };
+    // 項目がない場合は、既存のアカウントのサービスか、MyDNS.JPとして扱う。
+    let provider = match fields.get("provider") {
+        Some(Value::Str(code)) => Provider::from_code(code)
+            .ok_or_else(|| format!("{}: {}", master_id, get_msg(MessageKey::ProviderInvalid)))?,
+        Some(_) => {
+            return Err(format_msg!(
+                MessageKey::ImportInvalidTypeFmt,
+                field = "provider"
+            ));
+        }
+        None => existing
+            .iter()
+            .find(|c| c.master_id == master_id)
+            .map_or(Provider::MyDns, |c| c.provider),
+    };
+    if provider == Provider::MyDns && !master_id.starts_with("mydns") {
+        return Err(format!(
+            "{}: {}",
+            master_id,
+            get_msg(MessageKey::InvalidMasterIdPrefix)
+        ));
+    }
+
+    // ファイルにない項目は、既存のアカウントの値か、新規追加時の既定値を使う。
+    let base = existing
+        .iter()
+        .find(|c| c.master_id == master_id)
+        .cloned()
+        .unwrap_or_else(|| Config {
+            master_id: master_id.clone(),
+            ipv4_notify: true,
+            ipv6_notify: true,
+            keep_alive_hours: DEFAULT_KEEP_ALIVE_HOURS,
+            ..Config::default()
+        });
+    // ログインでの通知はMyDNS.JPだけが受け付けるため、汎用のサービスでは指定できない。
+    let method = match fields.get("method") {
+        Some(Value::Str(code)) => NotifyMethod::from_code(code)
+            .ok_or_else(|| format!("{}: {}", master_id, get_msg(MessageKey::MethodInvalid)))?,
+        Some(_) => {
+            return Err(format_msg!(
+                MessageKey::ImportInvalidTypeFmt,
+                field = "method"
+            ));
+        }
+        None if provider == base.provider => base.method,
+        None => NotifyMethod::Http,
+    };
+    if provider == Provider::Generic && method != NotifyMethod::Http {
+        return Err(format!(
+            "{}: {}",
+            master_id,
+            get_msg(MessageKey::MethodRequiresMyDns)
+        ));
+    }
+    let string_field = |name: &str, current: &str| match fields.get(name) {
+        Some(Value::Str(s)) => Ok(s.clone()),
+        Some(_) => Err(format_msg!(MessageKey::ImportInvalidTypeFmt, field = name)),
+        None => Ok(current.to_string()),
+    };
+    let bool_field = |name: &str, current: bool| match fields.get(name) {
+        Some(Value::Bool(b)) => Ok(*b),
+        Some(_) => Err(format_msg!(MessageKey::ImportInvalidTypeFmt, field = name)),
+        None => Ok(current),
+    };
+    let int_field = |name: &str, current: u32| match fields.get(name) {
+        Some(Value::Int(n)) => Ok(*n),
+        Some(_) => Err(format_msg!(MessageKey::ImportInvalidTypeFmt, field = name)),
+        None => Ok(current),
+    };
+
+    let config = Config {
+        password: string_field("password", &base.password)?,
+        ipv4_notify: bool_field("ipv4_notify", base.ipv4_notify)?,
+        ipv6_notify: bool_field("ipv6_notify", base.ipv6_notify)?,
+        keep_alive_hours: int_field("keep_alive_hours", base.keep_alive_hours)?,
+        proxy_url: string_field("proxy_url", &base.proxy_url)?,
+        ipv4_url: string_field("ipv4_url", &base.ipv4_url)?,
+        ipv6_url: string_field("ipv6_url", &base.ipv6_url)?,
+        hostname: string_field("hostname", &base.hostname)?,
+        ping_url: string_field("ping_url", &base.ping_url)?,
+        bind_interface: string_field("bind_interface", &base.bind_interface)?,
+        schedule: string_field("schedule", &base.schedule)?,
+        master_id,
+        provider,
+        method,
+    };
+    if provider == Provider::Generic && config.ipv4_url.is_empty() {
+        return Err(format!(
+            "{}: {}",
+            config.master_id,
+            get_msg(MessageKey::GenericUrlRequired)
+        ));
+    }
+    if !config.schedule.is_empty() && Schedule::parse(&config.schedule).is_none() {
+        return Err(format!(
+            "{}: {}",
+            config.master_id,
+            format_msg!(MessageKey::ScheduleInvalidFmt, schedule = config.schedule)
+        ));
+    }
+    // 汎用のサービスでは、URLのトークンで認証する場合などにパスワードは不要。
+    if provider == Provider::MyDns && config.password.is_empty() {
+        return Err(format_msg!(
+            MessageKey::ImportMissingPasswordFmt,
+            id = config.master_id
+        ));
+    }
+    Ok(config)
+}
+
+/// 2つのアカウント設定の差分を、項目名と変更前後の値の表示の組として返します。
+/// パスワードは値を表示せず、変更があったことだけを示します。
+fn diff_fields(current: &Config, imported: &Config) -> Vec<(&'static str, String, String)> {
+    let before = config_fields(current, true);
+    let after = config_fields(imported, true);
+    before
+        .into_iter()
+        .zip(after)
+        .filter(|((_, old), (_, new))| old != new)
+        .map(|((name, old), (_, new))| {
+            if name == "password" {
+                (name, "****".to_string(), "****".to_string())
+            } else {
+                (name, format_value(&old), format_value(&new))
+            }
+        })
+        .collect()
+}
+
+/// JSON形式のファイルの内容を解釈します。
+fn parse_json_file(text: &str) -> Result<ImportedFile, String> {
+    let root = json::parse(text)?;
+    let Json::Object(root) = root else {
+        return Err("top level must be an object".to_string());
+    };
+
+    let mut version = None;
+    let mut accounts = Vec::new();
+    for (key, value) in root {
+        match (key.as_str(), value) {
+            ("version", Json::Number(n)) => version = Some(n),
+            ("accounts", Json::Array(items)) => {
+                for item in items {
+                    let Json::Object(members) = item else {
+                        return Err("each account must be an object".to_string());
+                    };
+                    let mut fields = AccountFields::new();
+                    for (name, value) in members {
+                        let value = match value {
+                            Json::String(s) => Value::Str(s),
+                            Json::Bool(b) => Value::Bool(b),
+                            Json::Number(n) => Value::Int(n),
+                            _ => return Err(format!("unsupported value for \"{}\"", name)),
+                        };
+                        fields.insert(name, value);
+                    }
+                    accounts.push(fields);
+                }
+            }
+            (key, _) => return Err(format!("unexpected top-level key \"{}\"", key)),
+        }
+    }
+    Ok(ImportedFile {
+        version: version.ok_or("missing \"version\"")?,
+        accounts,
+    })
+}
+
+/// TOML形式のファイルの内容を解釈します。
+///
+/// このモジュールが書き出す範囲のサブセット（トップレベルのキー、`[[accounts]]` の配列テーブル、
+/// 文字列・真偽値・整数の値、コメント）だけを扱います。
+fn parse_toml_file(text: &str) -> Result<ImportedFile, String> {
+    let mut version = None;
+    let mut accounts: Vec<AccountFields> = Vec::new();
+    for (index, raw_line) in text.lines().enumerate() {
+        let line_error = |message: &str| format!("line {}: {}", index + 1, message);
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            if strip_toml_comment(line) != "[[accounts]]" {
+                return Err(line_error("only [[accounts]] tables are supported"));
+            }
+            accounts.push(AccountFields::new());
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| line_error("expected '='"))?;
+        let key = key.trim().trim_matches('"').to_string();
+        let value = parse_toml_value(value.trim()).map_err(|e| line_error(&e))?;
+        match accounts.last_mut() {
+            Some(fields) => {
+                if fields.insert(key.clone(), value).is_some() {
+                    return Err(line_error(&format!("duplicate key \"{}\"", key)));
+                }
+            }
+            None if key == "version" => match value {
+                Value::Int(n) => version = Some(n),
+                _ => return Err(line_error("\"version\" must be an integer")),
+            },
+            None => return Err(line_error(&format!("unexpected top-level key \"{}\"", key))),
+        }
+    }
+    Ok(ImportedFile {
+        version: version.ok_or("missing \"version\"")?,
+        accounts,
+    })
+}
+
+/// TOMLの値（と、その後に続くコメント）を解釈します。
+fn parse_toml_value(text: &str) -> Result<Value, String> {
+    let mut chars = text.chars();
+    let (value, rest) = match chars.next() {
+        // 基本文字列。エスケープシーケンスを解釈する。
+        Some('"') => {
+            let mut out = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some('"') => out.push('"'),
+                        Some('\\') => out.push('\\'),
+                        Some('b') => out.push('\u{8}'),
+                        Some('f') => out.push('\u{c}'),
+                        Some('n') => out.push('\n'),
+                        Some('r') => out.push('\r'),
+                        Some('t') => out.push('\t'),
+                        Some(kind @ ('u' | 'U')) => {
+                            let len = if kind == 'u' { 4 } else { 8 };
+                            let hex: String = chars.by_ref().take(len).collect();
+                            let c = u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .filter(|_| hex.len() == len)
+                                .and_then(char::from_u32)
+                                .ok_or("invalid unicode escape")?;
+                            out.push(c);
+                        }
+                        _ => return Err("invalid escape sequence".to_string()),
+                    },
+                    Some(c) => out.push(c),
+                    None => return Err("unterminated string".to_string()),
+                }
+            }
+            (Value::Str(out), chars.as_str())
+        }
+        // リテラル文字列。エスケープを解釈しない。
+        Some('\'') => {
+            let rest = chars.as_str();
+            let end = rest.find('\'').ok_or("unterminated string")?;
+            (Value::Str(rest[..end].to_string()), &rest[end + 1..])
+        }
+        _ => {
+            let token = strip_toml_comment(text);
+            let value = match token {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                _ => Value::Int(
+                    token
+                        .replace('_', "")
+                        .parse()
+                        .map_err(|_| format!("unsupported value \"{}\"", token))?,
+                ),
+            };
+            (value, "")
+        }
+    };
+    if !strip_toml_comment(rest).is_empty() {
+        return Err("unexpected characters after value".to_string());
+    }
+    Ok(value)
+}
+
+/// 行末のコメントを取り除き、前後の空白を除いた文字列を返します。
+fn strip_toml_comment(text: &str) -> &str {
+    text.split('#').next().unwrap_or("").trim()
+}
+
+/// 文字列を二重引用符で囲み、必要な文字をエスケープします。
+///
+/// 生成する表記は、JSONの文字列とTOMLの基本文字列のどちらとしても有効です。
+pub fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::{ConfigStore, MemoryStore};
+    use std::env;
+    use std::process;
+
+    /// インポートするファイルを一時ディレクトリに書き出し、現在の保存先の設定に対する計画を求めます。
+    fn plan_from(
+        store: &dyn ConfigStore,
+        name: &str,
+        text: &str,
+    ) -> Result<Vec<ImportPlan>, AdapterError> {
+        let path = env::temp_dir().join(format!("mydns-import-{}-{}.toml", name, process::id()));
+        fs::write(&path, text).unwrap();
+        let plans = plan_import(&path, &store.load_all().unwrap());
+        fs::remove_file(&path).unwrap();
+        plans
+    }
+
+    #[test]
+    fn import_merges_into_existing_accounts() {
+        let store = MemoryStore::default();
+        store
+            .save(&Config {
+                master_id: "mydns000001".to_string(),
+                password: "secret".to_string(),
+                ipv4_notify: true,
+                keep_alive_hours: DEFAULT_KEEP_ALIVE_HOURS,
+                ..Config::default()
+            })
+            .unwrap();
+        store
+            .save(&Config {
+                master_id: "mydns000009".to_string(),
+                password: "untouched".to_string(),
+                ..Config::default()
+            })
+            .unwrap();
+
+        // パスワードを除外して書き出したファイルでも、既存のアカウントのパスワードは残る。
+        let plans = plan_from(
+            &store,
+            "merge",
+            "version = 1\n\n\
+             [[accounts]]\nmaster_id = \"mydns000001\"\nipv6_notify = true\n\n\
+             [[accounts]]\nmaster_id = \"mydns000002\"\npassword = \"new\"\n",
+        )
+        .unwrap();
+        assert!(matches!(plans[0].change, ImportChange::Changed(ref fields) if fields.len() == 1));
+        assert!(matches!(plans[1].change, ImportChange::New));
+        for plan in &plans {
+            store.save(&plan.config).unwrap();
+        }
+
+        let configs = store.load_all().unwrap();
+        let find = |id: &str| configs.iter().find(|c| c.master_id == id).unwrap();
+        assert_eq!(configs.len(), 3);
+        assert_eq!(find("mydns000001").password, "secret");
+        assert!(find("mydns000001").ipv4_notify && find("mydns000001").ipv6_notify);
+        assert_eq!(find("mydns000002").password, "new");
+        assert_eq!(
+            find("mydns000002").keep_alive_hours,
+            DEFAULT_KEEP_ALIVE_HOURS
+        );
+        // ファイルにないアカウントは変更しない。
+        assert_eq!(find("mydns000009").password, "untouched");
+
+        // 同じ内容をもう一度インポートしても、変更はない。
+        let again = plan_from(
+            &store,
+            "again",
+            "version = 1\n\n[[accounts]]\nmaster_id = \"mydns000001\"\nipv6_notify = true\n",
+        )
+        .unwrap();
+        assert!(matches!(again[0].change, ImportChange::Unchanged));
+    }
+
+    #[test]
+    fn import_rejects_whole_file_on_invalid_account() {
+        let store = MemoryStore::default();
+        // 新しいアカウントのパスワードは省略できず、ファイル全体が拒否される。
+        let result = plan_from(
+            &store,
+            "invalid",
+            "version = 1\n\n\
+             [[accounts]]\nmaster_id = \"mydns000001\"\npassword = \"ok\"\n\n\
+             [[accounts]]\nmaster_id = \"mydns000002\"\n",
+        );
+        assert!(result.is_err());
+        // 同じMasterIDが重複したファイルも拒否する。
+        let result = plan_from(
+            &store,
+            "duplicate",
+            "version = 1\n\n\
+             [[accounts]]\nmaster_id = \"mydns000001\"\npassword = \"a\"\n\n\
+             [[accounts]]\nmaster_id = \"mydns000001\"\npassword = \"b\"\n",
+        );
+        assert!(result.is_err());
+    }
+}
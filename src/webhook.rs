@@ -0,0 +1,215 @@
+//! 通知の状況が変わったことを、Webhookで外部のサービスに知らせるモジュール。
+//!
+//! `--webhook-url <URL>` で有効にすると、アカウントの通知について次の変化があったときに、
+//! そのURLへJSONをPOSTします。Discord、Slack、ntfyなどのWebhookで、DDNSの不調にすぐ気付けます。
+//! - `failure`: 成功していた（または初めての）通知が失敗した。失敗が続いている間は送らない。
+//! - `recovery`: 失敗していた通知が成功した。
+//! - `ip_changed`: 通知に成功し、サーバーが受け付けたアドレスが前回と変わった。
+//! - `expiry_warning`: 最後の通知成功から時間が経ち、MyDNS.JPのレコードの失効が近づいた（`expiry` モジュール）。
+//!
+//! 本文には機械向けの項目に加え、そのまま表示できる要約を `text`（Slack）と `content`（Discord）に入れます。
+//! ```json
+//! {"event": "failure", "account": "mydns123456", "ip": ["203.0.113.1"], "error": "...",
+//!  "time": "2026-01-01T00:00:00+09:00", "text": "...", "content": "..."}
+//! ```
+
+use crate::format_msg_log;
+use crate::i18n::MessageKey;
+use crate::logging::{log_detail, log_warn};
+use crate::registry::load_settings;
+use crate::state::{AccountState, load_state};
+use crate::transfer::quote;
+use chrono::Local;
+use reqwest::Client;
+use reqwest::header::CONTENT_TYPE;
+
+/// Webhookで知らせる、通知の状況の変化。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebhookEvent {
+    /// 通知が失敗し始めた。
+    Failure,
+    /// 失敗していた通知が成功した。
+    Recovery,
+    /// 通知したアドレスが変わった。
+    IpChanged,
+    /// レコードの失効が近づいた。最後の通知成功からの経過時間と、失効までの残り時間（時間）を持つ。
+    ExpiryWarning { hours: i64, remaining: i64 },
+}
+
+impl WebhookEvent {
+    /// 本文の `event` に入れる、変化を表す固定の文字列。
+    pub fn code(self) -> &'static str {
+        match self {
+            WebhookEvent::Failure => "failure",
+            WebhookEvent::Recovery => "recovery",
+            WebhookEvent::IpChanged => "ip_changed",
+            WebhookEvent::ExpiryWarning { .. } => "expiry_warning",
+        }
+    }
+}
+
+/// 1回の通知の前後で、アカウントの実行時状態を比べるための記録。
+///
+/// 通知の前に `begin` で状態を記録し、通知の後に `finish` で変化を調べてWebhookを送ります。
+pub struct WebhookTracker {
+    /// 送信先のURL。
+    url: String,
+    /// 通知の前の、アカウントの実行時状態。
+    before: AccountState,
+}
+
+impl WebhookTracker {
+    /// 通知の前の状態を記録します。WebhookのURLが設定されていない場合は `None` を返します。
+    pub fn begin(master_id: &str) -> Option<Self> {
+        let url = load_settings().ok()?.webhook_url;
+        if url.is_empty() {
+            return None;
+        }
+        let before = load_state()
+            .ok()
+            .and_then(|state| state.accounts.get(master_id).cloned())
+            .unwrap_or_default();
+        Some(WebhookTracker { url, before })
+    }
+
+    /// 通知の後の状態と比べて、変化があればWebhookを送ります。
+    ///
+    /// 送信の結果はログに記録するだけで、通知の成否には影響しません。
+    pub async fn finish(self, client: &Client, master_id: &str, succeeded: bool) {
+        let after = load_state()
+            .ok()
+            .and_then(|state| state.accounts.get(master_id).cloned())
+            .unwrap_or_default();
+        for event in detect_events(&self.before, &after, succeeded) {
+            send_webhook(client, &self.url, master_id, event, &after).await;
+        }
+    }
+}
+
+/// MyDNS.JPのレコードの失効が近づいていることを、Webhookで知らせます。
+///
+/// WebhookのURLが設定されていない場合は何もしません。
+pub async fn send_expiry_webhook(client: &Client, master_id: &str, hours: i64, remaining: i64) {
+    let Some(url) = load_settings()
+        .ok()
+        .map(|s| s.webhook_url)
+        .filter(|url| !url.is_empty())
+    else {
+        return;
+    };
+    let state = load_state()
+        .ok()
+        .and_then(|state| state.accounts.get(master_id).cloned())
+        .unwrap_or_default();
+    let event = WebhookEvent::ExpiryWarning { hours, remaining };
+    send_webhook(client, &url, master_id, event, &state).await;
+}
+
+/// 通知の前後の実行時状態から、知らせるべき変化を求めます。
+fn detect_events(
+    before: &AccountState,
+    after: &AccountState,
+    succeeded: bool,
+) -> Vec<WebhookEvent> {
+    let was_failing = before.last_result.as_deref().is_some_and(|r| r != "OK");
+    let mut events = Vec::new();
+    if !succeeded {
+        if !was_failing {
+            events.push(WebhookEvent::Failure);
+        }
+        return events;
+    }
+    if was_failing {
+        events.push(WebhookEvent::Recovery);
+    }
+    // 前回のアドレスがわからない場合は、変わったかどうかも判断できない。
+    if !before.last_ip.is_empty() && before.last_ip != after.last_ip {
+        events.push(WebhookEvent::IpChanged);
+    }
+    events
+}
+
+/// WebhookのURLに、変化の内容を表すJSONをPOSTします。
+async fn send_webhook(
+    client: &Client,
+    url: &str,
+    master_id: &str,
+    event: WebhookEvent,
+    state: &AccountState,
+) {
+    let ip_text = state
+        .last_ip
+        .iter()
+        .map(|ip| ip.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let error = (event == WebhookEvent::Failure)
+        .then(|| state.last_result.clone())
+        .flatten();
+    let summary = match event {
+        WebhookEvent::Failure => format_msg_log!(
+            MessageKey::WebhookFailureFmt,
+            id = master_id,
+            error = error.as_deref().unwrap_or_default()
+        ),
+        WebhookEvent::Recovery => {
+            format_msg_log!(MessageKey::WebhookRecoveryFmt, id = master_id, ip = ip_text)
+        }
+        WebhookEvent::IpChanged => {
+            format_msg_log!(
+                MessageKey::WebhookIpChangedFmt,
+                id = master_id,
+                ip = ip_text
+            )
+        }
+        WebhookEvent::ExpiryWarning { hours, remaining } => format_msg_log!(
+            MessageKey::WebhookExpiryFmt,
+            id = master_id,
+            hours = hours,
+            remaining = remaining
+        ),
+    };
+    let body = format!(
+        "{{\"event\": {}, \"account\": {}, \"ip\": [{}], \"error\": {}, \"time\": {}, \"text\": {}, \"content\": {}}}",
+        quote(event.code()),
+        quote(master_id),
+        state
+            .last_ip
+            .iter()
+            .map(|ip| quote(&ip.to_string()))
+            .collect::<Vec<_>>()
+            .join(", "),
+        error.as_deref().map_or("null".to_string(), quote),
+        quote(&Local::now().to_rfc3339()),
+        quote(&summary),
+        quote(&summary)
+    );
+    let result = client
+        .post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await
+        .and_then(|res| res.error_for_status());
+    match result {
+        Ok(_) => log_detail(&format!(
+            "[{}] {}",
+            master_id,
+            format_msg_log!(
+                MessageKey::LogWebhookSentFmt,
+                event = event.code(),
+                url = url
+            )
+        )),
+        Err(e) => log_warn(&format!(
+            "[{}] {}",
+            master_id,
+            format_msg_log!(
+                MessageKey::LogWebhookFailedFmt,
+                event = event.code(),
+                url = url,
+                error = e
+            )
+        )),
+    }
+}
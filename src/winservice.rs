@@ -4,40 +4,149 @@
 //! `windows`クレート（Win32 API）を直接呼び出してサービスのインストールやアンインストールを行います。
 
 // --- 内部モジュール ---
-use crate::i18n::{get_msg, get_msg_en};
-use crate::logging::{log_error, log_info};
-use crate::notify::perform_notification;
-use crate::registry::load_all_configs;
+use crate::adminapi::start_admin_api;
+use crate::checkip::watch_global_address;
+use crate::connectivity::{is_metered_connection, is_network_available};
+use crate::console::{Color, paint};
+use crate::dnscheck::watch_dns_records;
+use crate::elevation::is_elevated;
+use crate::error::AdapterError;
+use crate::exitcode::{ExitError, ExitReason};
+use crate::expiry::check_expiry_warnings;
+use crate::i18n::{MessageKey, explicit_lang, get_msg, get_msg_log};
+use crate::ipc::{PipeServer, Request};
+use crate::logging::{
+    LogFilter, flush_logs, log_detail, log_error, log_info, log_warn, new_round_id, read_log_tail,
+    set_console_echo, set_log_secrets, with_round_id,
+};
+use crate::mail::send_failure_alerts;
+use crate::metrics::start_metrics_server;
+use crate::netprofile::{connected_network_names, is_allowed_network};
+use crate::netwatch::AddressChangeWatcher;
+use crate::notify::{build_client, notify_all, random_duration};
+use crate::ping::send_ping;
+use crate::registry::{
+    Config, MeteredPolicy, grant_record_key_access, load_settings, machine_record_location,
+};
+use crate::schedule::{has_schedule, watch_schedules};
+use crate::state::{
+    AccountState, data_dir, grant_data_dir_access, load_state, share_data_dir, update_state,
+};
+use crate::stats::record_uptime;
+use crate::store::{active_store, load_all_configs};
+use crate::{format_msg, format_msg_log, outln};
 
 // --- 標準ライブラリ ---
 use std::ffi::OsString;
-use std::sync::mpsc;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::OnceLock;
 use std::thread;
 use std::time::Duration;
 
 // --- 外部クレート ---
+// 日時の取得に使用。
+use chrono::{DateTime, Local, TimeDelta};
+// 非同期ランタイム。複数アカウントの並行通知と、通知中の停止要求への即応に使用。
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::sync::oneshot;
+use tokio::task::{self, JoinHandle};
+use tokio::time::{self, Instant};
 // Win32 APIを直接呼び出すためのクレート。サービス管理API（SCM）の操作に使用。
-use windows::Win32::Foundation::{ERROR_SERVICE_DOES_NOT_EXIST, ERROR_SERVICE_NOT_ACTIVE};
+use windows::Win32::Foundation::{
+    BOOL, ERROR_ACCESS_DENIED, ERROR_SERVICE_ALREADY_RUNNING, ERROR_SERVICE_DOES_NOT_EXIST,
+    ERROR_SERVICE_LOGON_FAILED, ERROR_SERVICE_NOT_ACTIVE, ERROR_SERVICE_SPECIFIC_ERROR, FALSE,
+    TRUE,
+};
+use windows::Win32::System::Console::{
+    CTRL_BREAK_EVENT, CTRL_C_EVENT, CTRL_CLOSE_EVENT, SetConsoleCtrlHandler,
+};
 use windows::Win32::System::Services::{
-    CloseServiceHandle, ControlService, CreateServiceW, DeleteService, OpenSCManagerW,
-    OpenServiceW, QueryServiceStatus, SC_HANDLE, SC_MANAGER_ALL_ACCESS, SC_MANAGER_CREATE_SERVICE,
-    SERVICE_ALL_ACCESS, SERVICE_AUTO_START, SERVICE_CONTROL_STOP, SERVICE_ERROR_NORMAL,
-    SERVICE_QUERY_STATUS, SERVICE_START, SERVICE_STATUS, SERVICE_STOP, SERVICE_STOPPED,
+    ChangeServiceConfig2W, CloseServiceHandle, ControlService, CreateServiceW, DeleteService,
+    OpenSCManagerW, OpenServiceW, QueryServiceStatus, QueryServiceStatusEx, SC_HANDLE,
+    SC_MANAGER_ALL_ACCESS, SC_MANAGER_CONNECT, SC_MANAGER_CREATE_SERVICE, SC_STATUS_PROCESS_INFO,
+    SERVICE_ALL_ACCESS, SERVICE_AUTO_START, SERVICE_CONFIG_DESCRIPTION,
+    SERVICE_CONFIG_PRESHUTDOWN_INFO, SERVICE_CONTINUE_PENDING, SERVICE_CONTROL_STOP,
+    SERVICE_DESCRIPTIONW, SERVICE_ERROR_NORMAL, SERVICE_PAUSE_PENDING, SERVICE_PAUSED,
+    SERVICE_PRESHUTDOWN_INFO, SERVICE_QUERY_STATUS, SERVICE_RUNNING, SERVICE_START,
+    SERVICE_START_PENDING, SERVICE_STATUS, SERVICE_STATUS_CURRENT_STATE, SERVICE_STATUS_PROCESS,
+    SERVICE_STOP, SERVICE_STOP_PENDING, SERVICE_STOPPED, SERVICE_USER_DEFINED_CONTROL,
     SERVICE_WIN32_OWN_PROCESS, StartServiceW,
 };
-use windows::core::HRESULT;
+use windows::core::{HRESULT, PCWSTR, PWSTR};
 // Windowsサービスの実装を簡略化するためのクレート。
 use windows_service::define_windows_service;
 use windows_service::service::{
-    ServiceControl, ServiceControlAccept, ServiceExitCode, ServiceState, ServiceStatus, ServiceType,
+    PowerEventParam, ServiceControl, ServiceControlAccept, ServiceExitCode, ServiceState,
+    ServiceStatus, ServiceType,
+};
+use windows_service::service_control_handler::{
+    self, ServiceControlHandlerResult, ServiceStatusHandle,
 };
-use windows_service::service_control_handler::{self, ServiceControlHandlerResult};
 use windows_service::service_dispatcher;
 
 /// Windowsサービスとして登録される際のサービス名。
 const SERVICE_NAME: &str = "MyDNSAdapterService";
+/// 即時通知を要求する、ユーザー定義の制御コード（128〜255の範囲）。
+const SERVICE_CONTROL_KICK: u32 = 128;
 /// サービスを削除するために必要なアクセス権フラグ (`DELETE`)。
 const DELETE: u32 = 0x00010000;
+/// 共有状態（一時停止・即時通知の要求）を確認する間隔。
+const STATE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// IPアドレスの変更を検出してから通知するまでの待ち時間。
+/// アドレスの変更は短時間に続けて届くことが多いため、落ち着くのを待ってから1回だけ通知する。
+const ADDRESS_CHANGE_SETTLE: Duration = Duration::from_secs(10);
+/// アカウント設定の変更を検出してから読み込み直すまでの待ち時間。
+/// 1回の保存で複数の値が書き込まれるため、書き込みが終わるのを待ってから1回だけ読み込む。
+const CONFIG_CHANGE_SETTLE: Duration = Duration::from_secs(2);
+/// スリープや休止状態から復帰してから通知するまでの待ち時間。
+/// 復帰の直後はネットワークへの再接続が終わっていないことが多いため、少し待ってから通知する。
+const RESUME_SETTLE: Duration = Duration::from_secs(15);
+/// 従量制課金の接続で `--metered reduce` のときに、定期通知の間に空ける最小の時間。
+const METERED_REDUCED_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// シャットダウン時に、実行中の通知処理が終わるのを待つ最大時間。
+/// 途中で電源が切れて、実行時状態やログが書きかけのまま残らないようにする。
+const SHUTDOWN_FLUSH_TIMEOUT: Duration = Duration::from_secs(10);
+/// `service start` で、サービスが開始処理を終えるのを待つ最大時間。
+const START_TIMEOUT: Duration = Duration::from_secs(30);
+/// 実行中になった後、すぐに停止してしまわないかを確認する時間。
+/// 開始の報告の直後に初期化に失敗して停止した場合も、開始に失敗したものとして扱うため。
+const START_SETTLE: Duration = Duration::from_secs(3);
+/// サービスの実行中に回復できないエラーが発生して停止したときに、SCMに報告するサービス固有の終了コード。
+const SERVICE_EXIT_FAILURE: u32 = 1;
+/// 停止処理の所要時間の見込みとして、SCMに報告する時間。
+const STOP_WAIT_HINT: Duration = Duration::from_secs(5);
+/// 実行中・一時停止中のサービスが受け付ける制御。
+const ACCEPTED_CONTROLS: ServiceControlAccept = ServiceControlAccept::STOP
+    .union(ServiceControlAccept::PAUSE_CONTINUE)
+    .union(ServiceControlAccept::POWER_EVENT)
+    .union(ServiceControlAccept::PRESHUTDOWN);
+
+/// サービスのメインループに届くイベント。
+enum ServiceEvent {
+    /// SCMからの停止要求。
+    Stop,
+    /// システムのシャットダウンに先立つ、SCMからの停止要求。
+    Shutdown,
+    /// SCMからの一時停止要求。
+    Pause,
+    /// SCMからの再開要求。
+    Continue,
+    /// このマシンのIPアドレスが変更された。
+    AddressChanged,
+    /// レジストリのアカウント設定が変更された。
+    ConfigChanged,
+    /// スリープや休止状態から復帰した。
+    Resumed,
+    /// `--kick` による即時通知の要求。
+    Kick,
+    /// DNSの自己確認で、レコードが通知したアドレスと食い違っていたアカウントのMasterID。
+    DnsDiverged(Vec<String>),
+    /// 通知のスケジュールに一致する時刻になったアカウントのMasterID。
+    ScheduleDue(Vec<String>),
+    /// 名前付きパイプで受け取った要求と、その結果の返信先。
+    Pipe(Request, oneshot::Sender<Result<String, String>>),
+}
 
 /// サービスを開始するためのエントリーポイント。
 ///
@@ -64,10 +173,10 @@ define_windows_service!(ffi_service_main, service_main_logic);
 /// * `args` - サービス開始時にSCMから渡される引数。このアプリケーションでは現在使用していません。
 fn service_main_logic(args: Vec<OsString>) {
     if let Err(e) = run_service_loop_impl(args) {
-        // サービス実行中に予期せぬエラーが発生した場合、英語でログを記録する。
+        // サービス実行中に予期せぬエラーが発生した場合、ログの言語（既定は英語）で記録する。
         // サービスはシステムアカウントで実行されることが多く、ユーザーのロケールが
-        // 適用されるとは限らないため、安定して読める英語でログを出力します。
-        log_error(&get_msg_en("log_service_failed_fmt").replace("{}", &e.to_string()));
+        // 適用されるとは限らないため、言語が指定されていなければ安定して読める英語でログを出力します。
+        log_error(&format_msg_log!(MessageKey::LogServiceFailedFmt, error = e));
     }
 }
 
@@ -76,20 +185,56 @@ fn service_main_logic(args: Vec<OsString>) {
 /// この関数は、サービスが「実行中」状態にある間、継続的に実行されます。
 /// 1. 停止要求をハンドリングするための準備。
 /// 2. SCMにサービスが「実行中」であることを通知。
-/// 3. 設定を読み込み、初回通知を実行。
-/// 4. メインループに入り、定期的な通知処理と停止要求の待機を繰り返す。
+/// 3. 設定を読み込み、非同期ランタイム上でメインループ（`run_event_loop`）を実行。
+/// 4. メインループでは、定期的な通知処理と停止要求の待機を繰り返す。
 /// 5. 停止要求を受け取ったら、SCMにサービスが「停止」したことを通知して終了。
 fn run_service_loop_impl(_args: Vec<OsString>) -> windows_service::Result<()> {
-    // サービス停止要求を通知するためのチャネルを作成。
-    let (shutdown_tx, shutdown_rx) = mpsc::channel();
+    // 停止要求やIPアドレスの変更をメインループに伝えるためのチャネルを作成。
+    // SCMやIP Helper APIのスレッドから同期的に送信できるよう、上限のないチャネルを使う。
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let control_tx = event_tx.clone();
 
     // OSからの制御イベント（停止、問い合わせなど）を処理するハンドラ。
     let event_handler = move |control_event| -> ServiceControlHandlerResult {
         match control_event {
-            // 停止または問い合わせイベントを受信した場合
-            ServiceControl::Stop | ServiceControl::Interrogate => {
+            // 停止イベントを受信した場合
+            ServiceControl::Stop => {
                 // メインループに停止を通知する。送信エラーは無視する（既に停止処理中のため）。
-                shutdown_tx.send(()).ok();
+                control_tx.send(ServiceEvent::Stop).ok();
+                ServiceControlHandlerResult::NoError
+            }
+            // シャットダウンの前に届くイベントを受信した場合
+            // 通常の停止と異なり、実行中の通知処理が終わるのを待ってから停止する。
+            ServiceControl::Preshutdown => {
+                control_tx.send(ServiceEvent::Shutdown).ok();
+                ServiceControlHandlerResult::NoError
+            }
+            // 一時停止・再開イベントを受信した場合
+            // 状態の報告は、要求を処理したメインループが行う。
+            ServiceControl::Pause => {
+                control_tx.send(ServiceEvent::Pause).ok();
+                ServiceControlHandlerResult::NoError
+            }
+            ServiceControl::Continue => {
+                control_tx.send(ServiceEvent::Continue).ok();
+                ServiceControlHandlerResult::NoError
+            }
+            // 問い合わせには、最後に報告した状態がそのまま使われる。
+            ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+            // スリープや休止状態からの復帰を受信した場合
+            // 復帰後はIPアドレスが変わっていることが多いため、メインループに通知を促す。
+            // `ResumeAutomatic` は、ユーザーの操作の有無にかかわらず復帰のたびに1回だけ届く。
+            ServiceControl::PowerEvent(
+                PowerEventParam::ResumeAutomatic | PowerEventParam::ResumeCritical,
+            ) => {
+                control_tx.send(ServiceEvent::Resumed).ok();
+                ServiceControlHandlerResult::NoError
+            }
+            // その他の電源イベントは、スリープへの移行を含めて妨げない。
+            ServiceControl::PowerEvent(_) => ServiceControlHandlerResult::NoError,
+            // `--kick` から送られる即時通知の要求を受信した場合
+            ServiceControl::UserEvent(code) if code.to_raw() == SERVICE_CONTROL_KICK => {
+                control_tx.send(ServiceEvent::Kick).ok();
                 ServiceControlHandlerResult::NoError
             }
             // その他のイベントは未実装として扱う。
@@ -102,91 +247,1011 @@ fn run_service_loop_impl(_args: Vec<OsString>) -> windows_service::Result<()> {
 
     // サービスの状態を「実行中」としてOSに通知。
     // これにより、サービス管理ツールなどでサービスが実行中として表示される。
+    report_state(Some(&status_handle), ServiceState::Running)?;
+
+    // サービス開始をログに記録。
+    log_info(get_msg_log(MessageKey::LogServiceStarted));
+
+    // パニックが発生しても、SCMに停止を報告してから終了する。パニックの内容はフックで記録済み。
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_until_stopped(event_tx, event_rx, Some(status_handle))
+    }))
+    .unwrap_or_else(|_| {
+        Err(std::io::Error::other(get_msg_log(
+            MessageKey::ServicePanicked,
+        )))
+    });
+
+    // サービスの状態を「停止」としてOSに通知。
+    // エラーで停止した場合は、`service start` などが失敗を検出できるよう、終了コードを報告する。
+    let exit_code = match result {
+        Ok(()) => ServiceExitCode::Win32(0),
+        Err(_) => ServiceExitCode::ServiceSpecific(SERVICE_EXIT_FAILURE),
+    };
     status_handle.set_service_status(ServiceStatus {
         service_type: ServiceType::OWN_PROCESS,
-        current_state: ServiceState::Running,
-        // このサービスが受け入れる制御は「停止」のみ。
-        controls_accepted: ServiceControlAccept::STOP,
-        exit_code: ServiceExitCode::Win32(0),
+        current_state: ServiceState::Stopped,
+        controls_accepted: ServiceControlAccept::empty(),
+        exit_code,
         checkpoint: 0,
         wait_hint: Duration::default(),
         process_id: None,
     })?;
 
-    // サービス開始をログに記録。
-    log_info(get_msg_en("log_service_started"));
+    result.map_err(windows_service::Error::Winapi)
+}
+
+/// `--run-foreground` で、Ctrl+C などによる停止要求をメインループに伝えるためのチャネル。
+static CONSOLE_STOP_TX: OnceLock<UnboundedSender<ServiceEvent>> = OnceLock::new();
+
+/// サービスと同じ処理（定期通知、設定の再読み込み、アドレスの監視、名前付きパイプ）を、
+/// サービスとしてインストールせずにコンソールで実行します。
+///
+/// ログは標準出力にも表示し、Ctrl+C やコンソールを閉じる操作で停止します。
+/// サービスの動作を、インストールせずにその場で確認するためのものです。
+/// SCMを経由しないため、一時停止と再開、`--kick` は使えません。
+/// 即時通知などの要求は、名前付きパイプ（`service notify-now` など）で送れます。
+pub fn run_foreground() -> Result<(), AdapterError> {
+    // 同じアカウントに二重に通知しないよう、サービスが動作中なら警告する。
+    if let Ok(Some(state)) = query_service_state()
+        && (state == SERVICE_RUNNING || state == SERVICE_PAUSED)
+    {
+        outln!(
+            "{}",
+            paint(get_msg(MessageKey::ForegroundServiceRunning), Color::Yellow)
+        );
+    }
+
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let _ = CONSOLE_STOP_TX.set(event_tx.clone());
+    // Win32 APIを直接呼び出すため、unsafeブロックが必要。
+    unsafe { SetConsoleCtrlHandler(Some(console_ctrl_handler), true)? };
+
+    set_console_echo(true);
+    outln!("{}", get_msg(MessageKey::ForegroundStarted));
+    log_info(get_msg_log(MessageKey::LogForegroundStarted));
+    let result = run_until_stopped(event_tx, event_rx, None);
+    set_console_echo(false);
+    result?;
+    outln!("{}", get_msg(MessageKey::ForegroundStopped));
+    Ok(())
+}
 
+/// コンソールの制御イベント（Ctrl+C、Ctrl+Break、ウィンドウを閉じる操作など）を処理するハンドラ。
+///
+/// メインループに停止を要求し、既定の処理（プロセスの即時終了）を行わないよう `TRUE` を返します。
+unsafe extern "system" fn console_ctrl_handler(ctrl_type: u32) -> BOOL {
+    match ctrl_type {
+        CTRL_C_EVENT | CTRL_BREAK_EVENT | CTRL_CLOSE_EVENT => {
+            if let Some(tx) = CONSOLE_STOP_TX.get() {
+                tx.send(ServiceEvent::Stop).ok();
+            }
+            // ウィンドウを閉じる操作では、ハンドラから戻るとプロセスが終了させられるため、
+            // 停止処理の猶予として少し待つ。
+            if ctrl_type == CTRL_CLOSE_EVENT {
+                thread::sleep(STOP_WAIT_HINT);
+            }
+            TRUE
+        }
+        _ => FALSE,
+    }
+}
+
+/// 停止要求を受け取るまで、アドレスと設定の監視、名前付きパイプの待ち受け、メインループを実行する。
+///
+/// サービスとしての実行と `--run-foreground` で共通の処理。
+/// `status_handle` は、一時停止や停止処理中の状態をSCMに報告するためのハンドルで、
+/// コンソールで実行する場合は `None` を渡す。
+fn run_until_stopped(
+    event_tx: UnboundedSender<ServiceEvent>,
+    event_rx: UnboundedReceiver<ServiceEvent>,
+    status_handle: Option<ServiceStatusHandle>,
+) -> std::io::Result<()> {
     let configs = load_all_configs().unwrap_or_default();
     if configs.is_empty() {
-        // 設定が一つも存在しない場合は、サービスを続行できないためエラーを記録し、停止する。
-        log_error(get_msg_en("log_service_config_missing"));
-        status_handle.set_service_status(ServiceStatus {
-            service_type: ServiceType::OWN_PROCESS,
-            current_state: ServiceState::Stopped,
-            controls_accepted: ServiceControlAccept::empty(),
-            exit_code: ServiceExitCode::Win32(1), // Configuration error
-            checkpoint: 0,
-            wait_hint: Duration::default(),
-            process_id: None,
-        })?;
-        return Ok(());
+        // 設定が一つも存在しなくても停止せず、アカウントが追加されるのを待つ。
+        // これにより、インストール後に `account add` を実行するだけで、再起動なしで通知が始まる。
+        log_warn(get_msg_log(MessageKey::LogServiceConfigMissing));
     }
 
-    let client = reqwest::blocking::Client::new();
+    // IPアドレスの変更を監視し、次の定期通知を待たずに通知できるようにする。
+    // 監視を開始できなくても、定期通知だけでサービスは動作を続ける。
+    // 監視はこの変数が破棄される（サービスが停止する）まで続く。
+    let address_tx = event_tx.clone();
+    let _address_watcher = match AddressChangeWatcher::start(move || {
+        address_tx.send(ServiceEvent::AddressChanged).ok();
+    }) {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            log_warn(&format_msg_log!(
+                MessageKey::LogAddressWatchFailedFmt,
+                error = e
+            ));
+            None
+        }
+    };
 
-    // サービス開始時に、設定されているすべてのアカウントに対して一度通知を実行する。
-    for config in &configs {
-        perform_notification(&client, config);
-    }
+    // CLIからの要求を名前付きパイプで受け付ける。
+    // 待ち受けを開始できなくても、サービスは動作を続ける。
+    let pipe_tx = event_tx.clone();
+    let _pipe_server =
+        match PipeServer::start(move |request| handle_pipe_request(&pipe_tx, request)) {
+            Ok(server) => Some(server),
+            // ほかのプロセスが同じ名前のパイプを先に作成している。なりすましの可能性がある。
+            Err(e) if e.code() == HRESULT::from(ERROR_ACCESS_DENIED) => {
+                log_error(get_msg_log(MessageKey::LogPipeAlreadyExists));
+                None
+            }
+            Err(e) => {
+                log_warn(&format_msg_log!(
+                    MessageKey::LogPipeServerFailedFmt,
+                    error = e
+                ));
+                None
+            }
+        };
 
-    // サービスのメインループ。
-    loop {
-        // `recv_timeout` を使用して、定期的な処理と停止要求の待機を同時に行う。
-        // 5分間待機し、その間に停止要求が来なければタイムアウトして処理を続行する。
-        match shutdown_rx.recv_timeout(Duration::from_secs(5 * 60)) {
-            // 停止要求を受信したか、チャネルが切断された場合はループを抜ける。
-            Ok(_) | Err(mpsc::RecvTimeoutError::Disconnected) => break, // Stop
-            // タイムアウトした場合（5分経過した場合）、定期通知処理を実行する。
-            Err(mpsc::RecvTimeoutError::Timeout) => {
-                for config in &configs {
-                    perform_notification(&client, config);
-                }
+    // 設定されていれば、Prometheusが収集するメトリクスをループバックアドレスで公開する。
+    // 待ち受けを開始できなくても（ポートが使用中など）、サービスは動作を続ける。
+    let _metrics_server = match load_settings().unwrap_or_default().metrics_port {
+        0 => None,
+        port => match start_metrics_server(port) {
+            Ok(server) => {
+                log_info(&format_msg_log!(
+                    MessageKey::LogMetricsServerStartedFmt,
+                    port = port
+                ));
+                Some(server)
             }
+            Err(e) => {
+                log_warn(&format_msg_log!(
+                    MessageKey::LogMetricsServerFailedFmt,
+                    port = port,
+                    error = e
+                ));
+                None
+            }
+        },
+    };
+
+    // 設定されていれば、管理APIをループバックアドレスで公開する。
+    // 状態の問い合わせや通知の要求は、名前付きパイプと同じようにメインループに渡す。
+    let settings = load_settings().unwrap_or_default();
+    let api_tx = event_tx.clone();
+    let _admin_api = match settings.admin_api_port {
+        0 => None,
+        port => match start_admin_api(port, settings.admin_api_token, move |request| {
+            handle_pipe_request(&api_tx, request)
+        }) {
+            Ok(server) => {
+                log_info(&format_msg_log!(
+                    MessageKey::LogAdminApiStartedFmt,
+                    port = port
+                ));
+                Some(server)
+            }
+            Err(e) => {
+                log_warn(&format_msg_log!(
+                    MessageKey::LogAdminApiFailedFmt,
+                    port = port,
+                    error = e
+                ));
+                None
+            }
+        },
+    };
+
+    // グローバルアドレスの確認（`--check-ip-poll`）で変化を検出したときも、アドレスの変更として通知する。
+    let global_tx = event_tx.clone();
+    // DNSの自己確認（`--dns-check`）で食い違いを見つけたアカウントは、メインループで通知し直す。
+    let dns_tx = event_tx.clone();
+    // 通知のスケジュール（`--set-schedule`）を設定したアカウントは、その時刻にメインループで通知する。
+    let schedule_tx = event_tx.clone();
+
+    // アカウント設定の変更を監視し、`account add` や `account edit` による変更を再起動なしで反映する。
+    // 監視を開始できなくても、サービスは起動時の設定で動作を続ける。
+    let _config_watcher = match active_store().watch(Box::new(move || {
+        event_tx.send(ServiceEvent::ConfigChanged).ok();
+    })) {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            log_warn(&format_msg_log!(
+                MessageKey::LogConfigWatchFailedFmt,
+                error = e
+            ));
+            None
         }
+    };
+
+    // 管理者権限のないユーザーも一時停止や即時通知を要求できるよう、実行時データのディレクトリへの書き込みを許可する。
+    // 以前のバージョンからの更新で、サービスをインストールし直さずに開始した場合に備えて、開始のたびに確認する。
+    // LocalSystem 以外のアカウントでは許可を変更できないことがあるが、インストール時に許可済みのため詳細ログだけ残す。
+    if let Err(e) = share_data_dir() {
+        log_detail(&format_msg_log!(
+            MessageKey::DataDirShareFailedFmt,
+            path = data_dir().map_or_else(|_| String::new(), |d| d.display().to_string()),
+            error = e
+        ));
     }
 
+    // `--status` で稼働時間を表示できるよう、開始時刻を実行時状態に記録する。
+    let _ = update_state(|s| s.service_started = Some(Local::now()));
+
+    // 通知処理は非同期ランタイム上で実行する。
+    // グローバルアドレスの確認は、ランタイムが破棄される（メインループが終わる）まで続く。
+    let runtime = Runtime::new()?;
+    runtime.spawn(watch_global_address(move || {
+        global_tx.send(ServiceEvent::AddressChanged).ok();
+    }));
+    runtime.spawn(watch_dns_records(move |ids| {
+        dns_tx.send(ServiceEvent::DnsDiverged(ids)).ok();
+    }));
+    runtime.spawn(watch_schedules(move |ids| {
+        schedule_tx.send(ServiceEvent::ScheduleDue(ids)).ok();
+    }));
+    runtime.block_on(run_event_loop(event_rx, configs, status_handle));
+
     // サービス停止をログに記録。
-    log_info(get_msg_en("log_service_stopping"));
-    // サービスの状態を「停止」としてOSに通知。
+    log_info(get_msg_log(MessageKey::LogServiceStopping));
+    let _ = update_state(|s| s.service_started = None);
+    flush_logs();
+    Ok(())
+}
+
+/// 実行中または一時停止中のサービスの状態を、受け付ける制御とともにSCMに報告する。
+///
+/// `status_handle` が `None`（コンソールで実行している）場合は、何もしない。
+fn report_state(
+    status_handle: Option<&ServiceStatusHandle>,
+    state: ServiceState,
+) -> windows_service::Result<()> {
+    let Some(status_handle) = status_handle else {
+        return Ok(());
+    };
     status_handle.set_service_status(ServiceStatus {
         service_type: ServiceType::OWN_PROCESS,
-        current_state: ServiceState::Stopped,
-        controls_accepted: ServiceControlAccept::empty(),
+        current_state: state,
+        controls_accepted: ACCEPTED_CONTROLS,
         exit_code: ServiceExitCode::Win32(0),
         checkpoint: 0,
         wait_hint: Duration::default(),
         process_id: None,
-    })?;
+    })
+}
 
-    Ok(())
+/// 名前付きパイプで受け取った要求を処理する。パイプのサーバーのスレッドから呼び出される。
+///
+/// ログの取得はこのスレッドで行い、それ以外の要求はメインループに渡して結果を待つ。
+fn handle_pipe_request(
+    event_tx: &UnboundedSender<ServiceEvent>,
+    request: Request,
+) -> Result<String, String> {
+    if let Request::TailLog(count) = request {
+        return read_log_tail(count, &LogFilter::default())
+            .map(|lines| lines.join("\n"))
+            .map_err(|e| e.to_string());
+    }
+    let (reply_tx, reply_rx) = oneshot::channel();
+    // メインループが終了している場合は、送信も返信の待機も失敗する。
+    event_tx
+        .send(ServiceEvent::Pipe(request, reply_tx))
+        .map_err(|_| get_msg_log(MessageKey::LogServiceStopping).to_string())?;
+    reply_rx
+        .blocking_recv()
+        .map_err(|_| get_msg_log(MessageKey::LogServiceStopping).to_string())?
+}
+
+/// 停止処理中であることを、所要時間の見込みとともにSCMに報告する。
+///
+/// `status_handle` が `None`（コンソールで実行している）場合は、何もしない。
+fn report_stop_pending(
+    status_handle: Option<&ServiceStatusHandle>,
+    wait_hint: Duration,
+) -> windows_service::Result<()> {
+    let Some(status_handle) = status_handle else {
+        return Ok(());
+    };
+    status_handle.set_service_status(ServiceStatus {
+        service_type: ServiceType::OWN_PROCESS,
+        current_state: ServiceState::StopPending,
+        controls_accepted: ServiceControlAccept::empty(),
+        exit_code: ServiceExitCode::Win32(0),
+        checkpoint: 1,
+        wait_hint,
+        process_id: None,
+    })
+}
+
+/// サービスのメインループ。停止要求を受け取るまで、定期通知とイベントの処理を繰り返す。
+///
+/// 通知処理は別のタスクとして実行し、その間もイベントを受け付ける。
+/// これにより、応答のない接続で通知が止まっていても、停止要求には即座に応じられる。
+///
+/// SCMからの一時停止要求を受けると、実行時状態やアドレスの監視はそのままに、定期通知とアドレス変更後の通知を止める。
+/// 再開要求を受けると、止めていた間の変更を反映するため、すぐに通知を実行する。
+/// SCMによる一時停止はサービスの再起動で解除され、`--pause` による一時停止とは別に扱う。
+///
+/// シャットダウンによる停止では、実行中の通知処理を中断せず、`SHUTDOWN_FLUSH_TIMEOUT` まで完了を待つ。
+async fn run_event_loop(
+    mut event_rx: UnboundedReceiver<ServiceEvent>,
+    mut configs: Vec<Config>,
+    status_handle: Option<ServiceStatusHandle>,
+) {
+    // 実行中の通知処理。同時に実行する通知処理は1つまでとする。
+    let mut round: Option<JoinHandle<()>> = None;
+
+    // サービス開始時に、設定されているすべてのアカウントに対して一度通知を実行する。
+    // 一時停止の要求はサービスの再起動をまたいで保持されるため、その場合は通知しない。
+    // 定期通知の予定時刻は、通知処理の所要時間に関係なく開始時刻から一定間隔で刻む。
+    // 実際に通知する時刻は、予定時刻を `--jitter` の範囲でランダムにずらした時刻とする。
+    let mut next_round = Instant::now() + notify_interval();
+    let mut round_at = with_jitter(next_round);
+    // 利用統計に稼働時間を加算するための起点。
+    let mut uptime_mark = Instant::now();
+    // `service query-status` で表示する、メインループの開始時刻。
+    let started = Local::now();
+    if !configs.is_empty() && !load_state().unwrap_or_default().paused {
+        // 多数のマシンが同時に起動しても通知が集中しないよう、開始時の通知も `--jitter` の範囲で遅らせる。
+        let delay = random_duration(jitter());
+        round = Some(spawn_round_after(delay, &configs));
+    }
+    // 最後に自動の通知ラウンドを開始した時刻。従量制課金の接続で通知を減らすために使う。
+    let mut last_round_at = Instant::now();
+
+    // IPアドレスの変更を検出した後、通知を実行する予定の時刻。
+    let mut address_change_due: Option<Instant> = None;
+    // アカウント設定の変更を検出した後、設定を読み込み直す予定の時刻。
+    let mut config_change_due: Option<Instant> = None;
+    // スリープや休止状態からの復帰を検出した後、通知を実行する予定の時刻。
+    let mut resume_due: Option<Instant> = None;
+    // SCMからの一時停止要求を受けているかどうか。
+    let mut scm_paused = false;
+    // SCMからの再開要求を受けた後、まだ通知を実行していないかどうか。
+    let mut continue_pending = false;
+    // アカウントが1件もない状態から追加された後、まだ通知を実行していないかどうか。
+    let mut accounts_added_pending = false;
+    // 制御コードによる即時通知の要求を受けた後、まだ通知を実行していないかどうか。
+    let mut kick_pending = false;
+    // DNSの自己確認で食い違いが見つかり、まだ通知し直していないアカウントのMasterID。
+    let mut dns_renotify: Vec<String> = Vec::new();
+    // スケジュールの時刻になり、まだ通知していないアカウントのMasterID。
+    let mut schedule_pending: Vec<String> = Vec::new();
+    // 静穏時間帯のために見送った通知があり、時間帯が終わった後にまだ通知していないかどうか。
+    let mut quiet_missed = false;
+
+    // 停止の理由がシャットダウンであるかどうか。
+    let shutting_down = loop {
+        // タイムアウト付きでイベントを待ち、共有状態のポーリングとイベントの待機を同時に行う。
+        // 次の予定時刻（定期通知、アドレス変更後の通知、設定の読み込み）までの残り時間が
+        // ポーリング間隔より短ければ、予定時刻ちょうどに起床する。即時通知の要求があれば、すぐに起床する。
+        // 通知処理の実行中は予約した通知を開始できないため、その予定時刻では起床しない。
+        let round_running = round.as_ref().is_some_and(|r| !r.is_finished());
+        let pending_rounds = if round_running {
+            [None, None, None, None, None]
+        } else {
+            [
+                address_change_due,
+                resume_due,
+                kick_pending.then(Instant::now),
+                (!dns_renotify.is_empty()).then(Instant::now),
+                (!schedule_pending.is_empty()).then(Instant::now),
+            ]
+        };
+        let wake_at = pending_rounds
+            .into_iter()
+            .chain([config_change_due])
+            .flatten()
+            .fold(round_at, Instant::min);
+        let wait = wake_at
+            .saturating_duration_since(Instant::now())
+            .min(STATE_POLL_INTERVAL);
+        match time::timeout(wait, event_rx.recv()).await {
+            // 停止要求を受信したか、チャネルが切断された場合はループを抜ける。
+            Ok(Some(ServiceEvent::Stop)) | Ok(None) => break false,
+            Ok(Some(ServiceEvent::Shutdown)) => {
+                log_info(get_msg_log(MessageKey::LogServiceShutdown));
+                break true;
+            }
+            // IPアドレスの変更を受信した場合、少し待ってから通知するよう予約する。
+            // 既に予約済みであれば予定時刻は動かさず、変更が続いても通知が先延ばしにならないようにする。
+            Ok(Some(ServiceEvent::AddressChanged)) => {
+                address_change_due.get_or_insert_with(|| Instant::now() + ADDRESS_CHANGE_SETTLE);
+            }
+            // アカウント設定の変更を受信した場合、書き込みが落ち着くのを待ってから読み込むよう予約する。
+            // 変更が続く間は予定時刻を先送りし、保存の途中の設定を読み込まないようにする。
+            Ok(Some(ServiceEvent::ConfigChanged)) => {
+                config_change_due = Some(Instant::now() + CONFIG_CHANGE_SETTLE);
+            }
+            // 制御コードによる即時通知の要求を受信した場合、すぐに通知するよう予約する。
+            Ok(Some(ServiceEvent::Kick)) => kick_pending = true,
+            // DNSの自己確認で食い違いが見つかった場合、そのアカウントをすぐに通知し直すよう予約する。
+            Ok(Some(ServiceEvent::DnsDiverged(ids))) => {
+                for id in ids {
+                    if !dns_renotify.contains(&id) {
+                        dns_renotify.push(id);
+                    }
+                }
+            }
+            // スケジュールの時刻になったアカウントを、すぐに通知するよう予約する。
+            Ok(Some(ServiceEvent::ScheduleDue(ids))) => {
+                for id in ids {
+                    if !schedule_pending.contains(&id) {
+                        schedule_pending.push(id);
+                    }
+                }
+            }
+            // 名前付きパイプで受け取った要求を処理し、結果を返信する。
+            Ok(Some(ServiceEvent::Pipe(request, reply))) => {
+                let result = match request {
+                    Request::NotifyNow => {
+                        kick_pending = true;
+                        Ok(String::new())
+                    }
+                    Request::ReloadConfig => {
+                        let had_accounts = !configs.is_empty();
+                        config_change_due = None;
+                        reload_configs(&mut configs);
+                        if !had_accounts && !configs.is_empty() {
+                            accounts_added_pending = true;
+                        }
+                        Ok(configs.len().to_string())
+                    }
+                    Request::QueryStatus => {
+                        let state = load_state().unwrap_or_default();
+                        let until_next = round_at.saturating_duration_since(Instant::now());
+                        let next =
+                            Local::now() + TimeDelta::from_std(until_next).unwrap_or_default();
+                        Ok(format!(
+                            "version={}\nstarted={}\naccounts={}\npaused={}\nround_running={}\nnext_round={}\n",
+                            env!("CARGO_PKG_VERSION"),
+                            started.to_rfc3339(),
+                            configs.len(),
+                            scm_paused || state.paused,
+                            round.as_ref().is_some_and(|r| !r.is_finished()),
+                            next.to_rfc3339()
+                        ))
+                    }
+                    // ログの取得は、パイプのサーバーのスレッドで処理される。
+                    Request::TailLog(_) => Err(String::new()),
+                };
+                let _ = reply.send(result);
+            }
+            // スリープや休止状態からの復帰を受信した場合、ネットワークへの再接続を待ってから通知するよう予約する。
+            Ok(Some(ServiceEvent::Resumed)) => {
+                log_info(get_msg_log(MessageKey::LogPowerResumed));
+                resume_due = Some(Instant::now() + RESUME_SETTLE);
+            }
+            // SCMからの一時停止要求を受信した場合、実行中の通知処理は最後まで続けさせ、以降の通知を止める。
+            Ok(Some(ServiceEvent::Pause)) => {
+                scm_paused = true;
+                continue_pending = false;
+                let _ = report_state(status_handle.as_ref(), ServiceState::Paused);
+                log_info(get_msg_log(MessageKey::LogServicePaused));
+            }
+            // SCMからの再開要求を受信した場合、次のポーリングで通知を実行するよう予約する。
+            Ok(Some(ServiceEvent::Continue)) => {
+                scm_paused = false;
+                continue_pending = true;
+                let _ = report_state(status_handle.as_ref(), ServiceState::Running);
+                log_info(get_msg_log(MessageKey::LogServiceContinued));
+            }
+            // タイムアウトした場合、共有状態と予定時刻を確認して通知が必要かどうかを判断する。
+            Err(_) => {
+                let state = load_state().unwrap_or_default();
+                let now = Instant::now();
+                // 設定の読み込みは、実行中の通知処理とは関係なく行う。
+                // 新しい設定は、次に開始する通知処理から使われる。
+                // アカウントが1件もない間は、レジストリキーがまだなく監視できない場合に備え、
+                // 書き込み中でなければポーリングのたびに読み込み直す。
+                let had_accounts = !configs.is_empty();
+                if config_change_due.is_some_and(|due| now >= due)
+                    || (!had_accounts && config_change_due.is_none())
+                {
+                    config_change_due = None;
+                    reload_configs(&mut configs);
+                }
+                // 最初のアカウントが追加された場合は、次の定期通知を待たずに通知する。
+                if !had_accounts && !configs.is_empty() {
+                    accounts_added_pending = true;
+                }
+                let due = now >= round_at;
+                if due {
+                    // 定期通知の予定時刻ごとに、前回からの稼働時間を利用統計に加算する。
+                    record_uptime(uptime_mark.elapsed());
+                    uptime_mark = now;
+                    // 次の予定時刻は「前回の予定時刻 + 間隔」とし、処理時間による遅れを蓄積させない。
+                    // 通知が長引いたり一時停止していたりして予定時刻を過ぎた分は、まとめて実行せずに読み飛ばす。
+                    // 間隔は毎回設定から読み直し、`--interval` による変更を再起動なしで反映する。
+                    next_round = next_schedule(next_round, now, notify_interval());
+                    round_at = with_jitter(next_round);
+                    // レジストリの監視が使えない環境でも、`account add` や `account edit` による変更が
+                    // 1回の間隔のうちに反映されるよう、定期通知のたびに設定を読み込み直す。
+                    reload_configs(&mut configs);
+                }
+                // 前回の通知処理がまだ終わっていなければ、今回の通知は見送る。
+                // 即時通知の要求とアドレス変更後の通知は、処理が終わった後まで持ち越す。
+                if round.as_ref().is_some_and(|r| !r.is_finished()) {
+                    if due {
+                        log_warn(get_msg_log(MessageKey::LogRoundStillRunning));
+                    }
+                    continue;
+                }
+                // アドレス変更後の通知も、定期通知の予定時刻には影響しない。
+                let address_changed = address_change_due.is_some_and(|due| now >= due);
+                if address_changed {
+                    address_change_due = None;
+                }
+                let resumed = resume_due.is_some_and(|due| now >= due);
+                if resumed {
+                    resume_due = None;
+                }
+                // 再開後の通知は一度だけ行う。`--pause` で一時停止中であれば、通知せずに要求を取り消す。
+                let continued = std::mem::take(&mut continue_pending);
+                let accounts_added = std::mem::take(&mut accounts_added_pending);
+                let kicked = std::mem::take(&mut kick_pending);
+                let dns_diverged = std::mem::take(&mut dns_renotify);
+                let scheduled = std::mem::take(&mut schedule_pending);
+                // 静穏時間帯に見送った通知があれば、時間帯が終わったときに1回だけ通知する。
+                let quiet_ended = quiet_missed && !is_quiet_hours();
+                if quiet_ended {
+                    quiet_missed = false;
+                }
+                // 定期通知の予定時刻以外の、アドレスの変更などのきっかけによる通知かどうか。
+                let triggered =
+                    address_changed || continued || resumed || accounts_added || quiet_ended;
+                let automatic =
+                    due || triggered || !dns_diverged.is_empty() || !scheduled.is_empty();
+                // 定期通知の予定時刻には、強制通知（キープアライブ）の期限を過ぎたアカウントを求めておく。
+                // これらは定期的な強制通知として、スケジュール・静穏時間帯・従量制課金の接続による見送りより優先する。
+                let keep_alive = if due {
+                    keep_alive_targets(&configs, Local::now())
+                } else {
+                    Vec::new()
+                };
+                if kicked || state.kick_requested {
+                    // 即時通知の要求は一度だけ処理するため、フラグを下ろしてから通知する。
+                    // 制御コードを送れなかった場合の要求は、共有状態のフラグで届く。
+                    // 一時停止中であっても、明示的な要求として通知を実行する。
+                    // 即時通知は定期通知の予定時刻には影響しない。
+                    if state.kick_requested {
+                        let _ = update_state(|s| s.kick_requested = false);
+                    }
+                    log_info(get_msg_log(MessageKey::LogServiceKickReceived));
+                } else if state.paused || scm_paused || configs.is_empty() {
+                    continue;
+                } else if automatic && is_quiet_hours() {
+                    // 静穏時間帯（`--quiet-hours`）は、自動の通知をすべて見送る。ログは見送り始めたときだけ記録する。
+                    if !quiet_missed {
+                        log_info(get_msg_log(MessageKey::LogQuietHoursSkipped));
+                        quiet_missed = true;
+                    }
+                    if !keep_alive.is_empty() {
+                        round = Some(spawn_keep_alive_round(&keep_alive));
+                        last_round_at = now;
+                    }
+                    continue;
+                } else if automatic && is_metered_hold(!triggered, last_round_at.elapsed()) {
+                    // 従量制課金の接続では、`--metered` の設定に従って自動の通知を見送る。
+                    log_info(get_msg_log(MessageKey::LogMeteredRoundSkipped));
+                    if !keep_alive.is_empty() {
+                        round = Some(spawn_keep_alive_round(&keep_alive));
+                        last_round_at = now;
+                    }
+                    continue;
+                } else if !(due || triggered) {
+                    // DNSの自己確認で食い違いが見つかったアカウントと、スケジュールの時刻になったアカウントだけを通知する。
+                    // 定期通知などですべてのアカウントを通知する場合は、その中で通知される。
+                    let targets: Vec<Config> = configs
+                        .iter()
+                        .filter(|c| {
+                            dns_diverged.contains(&c.master_id) || scheduled.contains(&c.master_id)
+                        })
+                        .cloned()
+                        .collect();
+                    if !targets.is_empty() {
+                        if !dns_diverged.is_empty() {
+                            log_info(&format_msg_log!(
+                                MessageKey::LogDnsCheckRenotifyFmt,
+                                count = dns_diverged.len()
+                            ));
+                        }
+                        if !scheduled.is_empty() {
+                            log_info(&format_msg_log!(
+                                MessageKey::LogScheduleDueFmt,
+                                count = scheduled.len()
+                            ));
+                        }
+                        round = Some(spawn_round(&targets));
+                        last_round_at = now;
+                    }
+                    continue;
+                } else if accounts_added {
+                    log_info(get_msg_log(MessageKey::LogAccountsAddedNotify));
+                } else if continued {
+                    log_info(get_msg_log(MessageKey::LogServiceContinueNotify));
+                } else if resumed {
+                    log_info(get_msg_log(MessageKey::LogPowerResumeNotify));
+                } else if quiet_ended {
+                    log_info(get_msg_log(MessageKey::LogQuietHoursEndedNotify));
+                } else if address_changed {
+                    log_info(get_msg_log(MessageKey::LogAddressChanged));
+                }
+                // 定期通知では、スケジュールを設定したアカウントを除く（スケジュールの時刻に通知する）。
+                // ただし、強制通知の期限を過ぎたアカウントは、スケジュールの時刻を待たずに通知する。
+                // アドレスの変更などのきっかけによる通知と、即時通知の要求では、すべてのアカウントを通知する。
+                let targets: Vec<Config> = configs
+                    .iter()
+                    .filter(|c| {
+                        kicked
+                            || state.kick_requested
+                            || triggered
+                            || !has_schedule(c)
+                            || scheduled.contains(&c.master_id)
+                            || dns_diverged.contains(&c.master_id)
+                            || keep_alive.iter().any(|k| k.master_id == c.master_id)
+                    })
+                    .cloned()
+                    .collect();
+                if targets.is_empty() {
+                    continue;
+                }
+                round = Some(spawn_round(&targets));
+                last_round_at = now;
+            }
+        }
+    };
+
+    // 停止処理中であることを報告する。シャットダウン時は、通知処理の完了を待つ時間も見込む。
+    let wait_hint = if shutting_down {
+        SHUTDOWN_FLUSH_TIMEOUT + STOP_WAIT_HINT
+    } else {
+        STOP_WAIT_HINT
+    };
+    let _ = report_stop_pending(status_handle.as_ref(), wait_hint);
+
+    // 通知処理の途中で停止要求を受けた場合は、応答を待たずに中断する。
+    // シャットダウン時は、結果を実行時状態とログに書き終えられるよう、一定時間だけ完了を待つ。
+    // 中断されたアカウントが「通知中」のまま残らないよう、実行時状態を戻しておく。
+    if let Some(mut round) = round
+        && !round.is_finished()
+    {
+        if shutting_down
+            && time::timeout(SHUTDOWN_FLUSH_TIMEOUT, &mut round)
+                .await
+                .is_ok()
+        {
+            log_info(get_msg_log(MessageKey::LogRoundFlushed));
+        } else {
+            round.abort();
+            let _ = round.await;
+            let _ = update_state(|s| {
+                for account in s.accounts.values_mut() {
+                    account.in_flight = false;
+                }
+            });
+            log_warn(get_msg_log(MessageKey::LogRoundAborted));
+        }
+    }
+
+    // 最後の予定時刻から停止までの稼働時間を利用統計に加算する。
+    record_uptime(uptime_mark.elapsed());
+}
+
+/// レジストリからアカウント設定を読み込み直し、`configs` を置き換える。
+///
+/// 内容が変わった場合だけログに記録する。読み込みに失敗した場合は、これまでの設定を使い続ける。
+fn reload_configs(configs: &mut Vec<Config>) {
+    match load_all_configs() {
+        Ok(reloaded) if reloaded == *configs => {}
+        Ok(reloaded) => {
+            if reloaded.is_empty() {
+                log_warn(get_msg_log(MessageKey::LogConfigReloadedEmpty));
+            } else {
+                log_info(&format_msg_log!(
+                    MessageKey::LogConfigReloadedFmt,
+                    count = reloaded.len()
+                ));
+            }
+            set_log_secrets(&reloaded);
+            *configs = reloaded;
+        }
+        Err(e) => {
+            log_warn(&format_msg_log!(
+                MessageKey::LogConfigReloadFailedFmt,
+                error = e
+            ));
+        }
+    }
+}
+
+/// レジストリに保存された定期通知の間隔を取得する。
+///
+/// 設定を読み込めない場合は既定値を使う。
+fn notify_interval() -> Duration {
+    let minutes = load_settings().unwrap_or_default().notify_interval_minutes;
+    Duration::from_secs(u64::from(minutes) * 60)
+}
+
+/// 前回の予定時刻から、現在時刻より後にある次の予定時刻を求める。
+///
+/// `previous + interval` を基本とし、それでも過去になる場合は現在時刻を超えるまで間隔を足し進める。
+fn next_schedule(previous: Instant, now: Instant, interval: Duration) -> Instant {
+    let mut next = previous + interval;
+    if next <= now {
+        // 経過した間隔の数だけまとめて進める。
+        let missed = (now - next).as_nanos() / interval.as_nanos() + 1;
+        next += interval * missed as u32;
+    }
+    next
+}
+
+/// `--jitter` で設定された、通知の時刻をずらす最大の時間を返す。設定は毎回読み直す。
+///
+/// 予定時刻の前後にずらしても次の予定時刻を追い越さないよう、定期通知の間隔の半分までに制限する。
+fn jitter() -> Duration {
+    Duration::from_secs(u64::from(
+        load_settings().unwrap_or_default().jitter_seconds,
+    ))
+    .min(notify_interval() / 2)
+}
+
+/// 定期通知の予定時刻を、`--jitter` の範囲でランダムに前後にずらした時刻を返す。
+///
+/// 多数のマシンで動かしたときに、通知が同じ時刻にMyDNS.JPへ集中しないようにするため。
+/// ずらす前の予定時刻を基準に次の予定時刻を求めるため、ずらした分が積み重なることはない。
+fn with_jitter(scheduled: Instant) -> Instant {
+    let jitter = jitter();
+    (scheduled + random_duration(jitter * 2))
+        .checked_sub(jitter)
+        .unwrap_or(scheduled)
+}
+
+/// 現在の時刻が、`--quiet-hours` で設定された静穏時間帯に含まれるかどうかを判定する。設定は毎回読み直す。
+fn is_quiet_hours() -> bool {
+    load_settings()
+        .unwrap_or_default()
+        .quiet_hours
+        .is_some_and(|quiet| quiet.contains(Local::now().time()))
+}
+
+/// 従量制課金の接続で、今回の自動の通知ラウンドを見送るかどうかを判定する。
+///
+/// `--metered skip` では、すべての自動の通知を見送る。`--metered reduce` では、定期通知（`scheduled`）だけを
+/// 前回のラウンドから `METERED_REDUCED_INTERVAL` が経つまで見送る。設定は毎回読み直す。
+fn is_metered_hold(scheduled: bool, since_last_round: Duration) -> bool {
+    match load_settings().unwrap_or_default().metered_policy {
+        MeteredPolicy::Ignore => false,
+        MeteredPolicy::Reduce => {
+            scheduled && since_last_round < METERED_REDUCED_INTERVAL && is_metered_connection()
+        }
+        MeteredPolicy::Skip => is_metered_connection(),
+    }
+}
+
+/// 新しい相関IDを割り当てて、通知ラウンドを別のタスクとして開始する。
+fn spawn_round(configs: &[Config]) -> JoinHandle<()> {
+    tokio::spawn(with_round_id(
+        new_round_id(),
+        run_notification_round(configs.to_vec()),
+    ))
+}
+
+/// 静穏時間帯や従量制課金の接続で自動の通知を見送るときに、強制通知の期限を過ぎたアカウントだけを通知する。
+fn spawn_keep_alive_round(targets: &[Config]) -> JoinHandle<()> {
+    log_info(&format_msg_log!(
+        MessageKey::LogKeepAliveOverrideFmt,
+        count = targets.len()
+    ));
+    spawn_round(targets)
+}
+
+/// 指定した時間だけ待ってから、新しい相関IDを割り当てて通知ラウンドを別のタスクとして開始する。
+///
+/// 待っている間もラウンドは実行中として扱い、停止要求を受けたときは待機ごと中断する。
+fn spawn_round_after(delay: Duration, configs: &[Config]) -> JoinHandle<()> {
+    let configs = configs.to_vec();
+    tokio::spawn(async move {
+        time::sleep(delay).await;
+        with_round_id(new_round_id(), run_notification_round(configs)).await;
+    })
+}
+
+/// すべてのアカウントに対して通知を1回ずつ、並行して実行する。
+///
+/// ネットワークに接続されていない場合や、許可されたネットワークに接続していない場合は、ラウンドごと見送る。
+/// 共有状態でバックオフ期限が設定されているアカウントは、強制通知（キープアライブ）の期限を過ぎていても、
+/// 期限が過ぎるまでスキップする。
+/// HTTPクライアントは毎回作成し、`--proxy` によるプロキシの変更を再起動なしで反映する。
+/// すべてのアカウントで通知に成功した場合は、全体のping URL（`--ping-url`）にpingを送る。
+async fn run_notification_round(configs: Vec<Config>) {
+    let settings = load_settings().unwrap_or_default();
+    let client = match build_client(&settings.proxy_url) {
+        Ok(client) => client,
+        Err(e) => {
+            log_error(&format_msg_log!(MessageKey::LogProxyInvalidFmt, error = e));
+            return;
+        }
+    };
+    // 回線が切れている間は、アカウントごとに接続エラーを記録するのではなく、ラウンドごと見送る。
+    // 接続が戻ればアドレスの変更の通知か次の定期通知で通知されるため、バックオフや失敗の回数も進めない。
+    if !is_network_available(&client).await {
+        log_info(get_msg_log(MessageKey::LogRoundSkippedOffline));
+        return;
+    }
+    // 許可するネットワーク（`--allowed-networks`）が設定されていれば、そのネットワークにいるときだけ通知する。
+    // 接続中のネットワークを確かめられなかった場合は、通知できなくなることを避けるため通知を続ける。
+    if !settings.allowed_networks.is_empty() {
+        match task::spawn_blocking(connected_network_names).await {
+            Ok(Ok(names)) if !is_allowed_network(&settings.allowed_networks, &names) => {
+                log_info(&format_msg_log!(
+                    MessageKey::LogNetworkNotAllowedFmt,
+                    networks = if names.is_empty() {
+                        "-".to_string()
+                    } else {
+                        names.join(", ")
+                    }
+                ));
+                return;
+            }
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => log_warn(&format_msg_log!(
+                MessageKey::LogNetworkNamesFailedFmt,
+                error = e
+            )),
+            Err(e) => log_warn(&format_msg_log!(
+                MessageKey::LogNetworkNamesFailedFmt,
+                error = e
+            )),
+        }
+    }
+    log_info(&format_msg_log!(
+        MessageKey::LogRoundStartedFmt,
+        count = configs.len()
+    ));
+    let state = load_state().unwrap_or_default();
+    let now = Local::now();
+    let mut targets = Vec::new();
+    for config in &configs {
+        let account = state.accounts.get(&config.master_id);
+        // サーバーから指示された待ち時間（Retry-After）は、強制通知の期限を過ぎていても守る。
+        if let Some(until) = account
+            .and_then(|a| a.backoff_until)
+            .filter(|until| *until > now)
+        {
+            log_info(&format!(
+                "[{}] {}",
+                config.master_id,
+                format_msg_log!(MessageKey::LogAccountBackoffFmt, until = until.to_rfc3339())
+            ));
+            continue;
+        }
+        if is_keep_alive_due(config, account, state.service_started, now) {
+            log_info(&format!(
+                "[{}] {}",
+                config.master_id,
+                format_msg_log!(
+                    MessageKey::LogKeepAliveForcedFmt,
+                    hours = config.keep_alive_hours
+                )
+            ));
+        }
+        targets.push(config.clone());
+    }
+    let report = notify_all(&client, targets, false).await;
+    let failed = report.failed();
+    log_info(&format_msg_log!(
+        MessageKey::LogRoundFinishedFmt,
+        ok = report.accounts.len() - failed,
+        failed = failed
+    ));
+    // 通知したアカウントがない（すべてバックオフ中の）ラウンドは、成功とはみなさない。
+    if failed == 0 && !report.accounts.is_empty() && !settings.ping_url.is_empty() {
+        send_ping(&client, None, &settings.ping_url).await;
+    }
+    // 失敗が続いているアカウントがあれば、メールで知らせる。
+    send_failure_alerts(&configs).await;
+    // 通知の結果を踏まえて、レコードの失効が近づいているアカウントを警告する。
+    check_expiry_warnings(&client, &configs).await;
+}
+
+/// 強制通知（キープアライブ）の期限を過ぎたアカウントを返す。
+///
+/// サーバーから指示された待ち時間（Retry-After）の間のアカウントは、通知しても見送られるため含めない。
+fn keep_alive_targets(configs: &[Config], now: DateTime<Local>) -> Vec<Config> {
+    let state = load_state().unwrap_or_default();
+    configs
+        .iter()
+        .filter(|c| {
+            let account = state.accounts.get(&c.master_id);
+            account
+                .and_then(|a| a.backoff_until)
+                .is_none_or(|until| until <= now)
+                && is_keep_alive_due(c, account, state.service_started, now)
+        })
+        .cloned()
+        .collect()
+}
+
+/// 強制通知（キープアライブ）の期限を過ぎているかどうかを判定する。
+///
+/// 最後の通知成功から `keep_alive_hours` 時間以上経過している場合に `true` を返す。
+/// 成功した記録がないアカウントは、サービスの開始時刻（`service_started`）から数える。
+/// どちらもない場合と、`keep_alive_hours` が0の場合は `false`。
+fn is_keep_alive_due(
+    config: &Config,
+    account: Option<&AccountState>,
+    service_started: Option<DateTime<Local>>,
+    now: DateTime<Local>,
+) -> bool {
+    if config.keep_alive_hours == 0 {
+        return false;
+    }
+    account
+        .and_then(|a| a.last_success)
+        .or(service_started)
+        .is_some_and(|since| now - since >= TimeDelta::hours(i64::from(config.keep_alive_hours)))
+}
+
+/// パスワードなしでサービスを実行できる組み込みのアカウントの、別名と正式な名前。
+const BUILTIN_SERVICE_ACCOUNTS: [(&str, &str); 3] = [
+    ("LocalSystem", "LocalSystem"),
+    ("LocalService", "NT AUTHORITY\\LocalService"),
+    ("NetworkService", "NT AUTHORITY\\NetworkService"),
+];
+
+/// サービスを実行するアカウントと、そのパスワード。
+pub struct ServiceLogon {
+    /// `CreateServiceW` に渡すアカウント名（`NT AUTHORITY\LocalService` や `DOMAIN\user` など）。
+    pub account: String,
+    /// アカウントのパスワード。パスワードが不要なアカウントでは `None`。
+    pub password: Option<String>,
+}
+
+/// 組み込みのアカウントの別名または正式な名前から、正式な名前を取得します。
+///
+/// 大文字と小文字は区別しません。組み込みのアカウントでない場合は `None` を返します。
+pub fn builtin_service_account(name: &str) -> Option<&'static str> {
+    BUILTIN_SERVICE_ACCOUNTS
+        .iter()
+        .find(|(alias, full)| name.eq_ignore_ascii_case(alias) || name.eq_ignore_ascii_case(full))
+        .map(|(_, full)| *full)
 }
 
 /// サービスをWindowsにインストールし、開始する。
 ///
 /// 管理者権限が必要です。
-pub fn install_service() -> Result<(), Box<dyn std::error::Error>> {
+///
+/// # 引数
+/// * `logon` - サービスを実行するアカウント。`None` の場合は LocalSystem で実行する。
+pub fn install_service(logon: Option<&ServiceLogon>) -> Result<(), AdapterError> {
     // 管理者権限があるかチェックする。
     if !is_elevated() {
-        return Err(get_msg("admin_required_install").into());
+        return Err(ExitError::new(
+            ExitReason::AdminRequired,
+            get_msg(MessageKey::AdminRequiredInstall),
+        )
+        .into());
     }
 
     // 自身の実行可能ファイルのパスを取得し、サービス実行用の引数 `--service` を付与する。
+    // 言語が明示的に指定されていれば、サービスのログもその言語で記録するよう `--lang` も付与する。
     let exe_path = std::env::current_exe()?;
-    let bin_path_with_arg = format!("\"{}\" --service", exe_path.display());
+    let mut bin_path_with_arg = format!("\"{}\" --service", exe_path.display());
+    if let Some(lang) = explicit_lang() {
+        bin_path_with_arg.push_str(&format!(" --lang {}", lang.code()));
+    }
 
     let bin_path_hstring = windows::core::HSTRING::from(bin_path_with_arg);
     let service_name_hstring = windows::core::HSTRING::from(SERVICE_NAME);
     let display_name_hstring = windows::core::HSTRING::from("MyDNS.JP IP Notifier");
+    // アカウントとパスワードは、指定されなかった場合にNULLを渡す。
+    let account_hstring = logon.map(|l| windows::core::HSTRING::from(l.account.as_str()));
+    let password_hstring = logon
+        .and_then(|l| l.password.as_deref())
+        .map(windows::core::HSTRING::from);
+    let as_pcwstr = |s: &Option<windows::core::HSTRING>| {
+        s.as_ref().map_or(PCWSTR::null(), |s| PCWSTR(s.as_ptr()))
+    };
 
     // Win32 APIを呼び出すため、unsafeブロックを使用する。
     // 各APIの引数はドキュメントに従って正しく設定されており、ハンドルは適切にクローズされるため安全。
@@ -205,24 +1270,128 @@ pub fn install_service() -> Result<(), Box<dyn std::error::Error>> {
             None,
             None,
             None,
-            None,
-            None,
+            as_pcwstr(&account_hstring),
+            as_pcwstr(&password_hstring),
         )?;
 
-        println!(
-            "{}",
-            get_msg("service_installing_fmt").replace("{}", SERVICE_NAME)
+        // services.msc に表示されるサービスの説明を設定する。
+        // 説明がなくてもサービスは動作するため、失敗しても無視する。
+        let description_hstring =
+            windows::core::HSTRING::from(get_msg(MessageKey::ServiceDescription));
+        let description = SERVICE_DESCRIPTIONW {
+            lpDescription: PWSTR(description_hstring.as_ptr() as *mut _),
+        };
+        let _ = ChangeServiceConfig2W(
+            service_handle,
+            SERVICE_CONFIG_DESCRIPTION,
+            Some(&description as *const SERVICE_DESCRIPTIONW as *const _),
         );
-        // サービスを即時開始する。
-        StartServiceW(service_handle, None)?;
-        println!(
+        // シャットダウン時に通知処理の完了を待てるよう、SCMが停止を待つ時間を設定する。
+        // 設定できなくても既定の時間で停止するため、失敗しても無視する。
+        let preshutdown = SERVICE_PRESHUTDOWN_INFO {
+            dwPreshutdownTimeout: (SHUTDOWN_FLUSH_TIMEOUT + STOP_WAIT_HINT).as_millis() as u32,
+        };
+        let _ = ChangeServiceConfig2W(
+            service_handle,
+            SERVICE_CONFIG_PRESHUTDOWN_INFO,
+            Some(&preshutdown as *const SERVICE_PRESHUTDOWN_INFO as *const _),
+        );
+
+        // 管理者権限のないユーザーも一時停止や即時通知を要求できるよう、実行時データのディレクトリへの書き込みを許可する。
+        // 許可できなくても管理者は要求できるため、警告を表示して続ける。
+        if let Err(e) = share_data_dir() {
+            eprintln!(
+                "{}",
+                paint(
+                    &format_msg!(
+                        MessageKey::DataDirShareFailedFmt,
+                        path =
+                            data_dir().map_or_else(|_| String::new(), |d| d.display().to_string()),
+                        error = e
+                    ),
+                    Color::Yellow
+                )
+            );
+        }
+
+        // LocalSystem 以外のアカウントは既定ではHKLMの記録のキーにも実行時データのディレクトリにも
+        // 書き込めないため、サービスが通知の結果や実行時状態を記録できるよう、開始する前に書き込みを許可する。
+        // 許可できなくても通知はできるため、警告を表示して続ける。
+        if let Some(logon) = logon.filter(|l| l.account != "LocalSystem") {
+            let key = machine_record_location();
+            match grant_record_key_access(&logon.account) {
+                Ok(()) => outln!(
+                    "{}",
+                    format_msg!(
+                        MessageKey::ServiceKeyAccessGrantedFmt,
+                        account = logon.account,
+                        key = key
+                    )
+                ),
+                Err(e) => eprintln!(
+                    "{}",
+                    paint(
+                        &format_msg!(
+                            MessageKey::ServiceKeyAccessFailedFmt,
+                            account = logon.account,
+                            key = key,
+                            error = e
+                        ),
+                        Color::Yellow
+                    )
+                ),
+            }
+            let path = data_dir().map_or_else(|_| String::new(), |d| d.display().to_string());
+            match grant_data_dir_access(&logon.account) {
+                Ok(()) => outln!(
+                    "{}",
+                    format_msg!(
+                        MessageKey::ServiceDataDirAccessGrantedFmt,
+                        account = logon.account,
+                        path = path
+                    )
+                ),
+                Err(e) => eprintln!(
+                    "{}",
+                    paint(
+                        &format_msg!(
+                            MessageKey::ServiceDataDirAccessFailedFmt,
+                            account = logon.account,
+                            path = path,
+                            error = e
+                        ),
+                        Color::Yellow
+                    )
+                ),
+            }
+        }
+
+        outln!(
             "{}",
-            get_msg("service_installed_fmt").replace("{}", SERVICE_NAME)
+            format_msg!(MessageKey::ServiceInstallingFmt, name = SERVICE_NAME)
         );
+        // サービスを即時開始する。
+        let started = StartServiceW(service_handle, None);
 
         // 開いたハンドルをクローズする。エラーは無視。
         let _ = CloseServiceHandle(service_handle);
         let _ = CloseServiceHandle(scm_handle);
+
+        match started {
+            Ok(()) => {}
+            // 指定されたアカウントでログオンできなかった。サービスはインストールされたまま残る。
+            Err(e) if e.code().0 == HRESULT::from(ERROR_SERVICE_LOGON_FAILED).0 => {
+                let account = logon.map_or("LocalSystem", |l| l.account.as_str());
+                return Err(
+                    format_msg!(MessageKey::ServiceLogonFailedFmt, account = account).into(),
+                );
+            }
+            Err(e) => return Err(e.into()),
+        }
+        outln!(
+            "{}",
+            format_msg!(MessageKey::ServiceInstalledFmt, name = SERVICE_NAME)
+        );
     }
 
     Ok(())
@@ -231,10 +1400,14 @@ pub fn install_service() -> Result<(), Box<dyn std::error::Error>> {
 /// サービスを停止し、Windowsからアンインストールする。
 ///
 /// 管理者権限が必要です。
-pub fn uninstall_service() -> Result<(), Box<dyn std::error::Error>> {
+pub fn uninstall_service() -> Result<(), AdapterError> {
     // 管理者権限があるかチェックする。
     if !is_elevated() {
-        return Err(get_msg("admin_required_uninstall").into());
+        return Err(ExitError::new(
+            ExitReason::AdminRequired,
+            get_msg(MessageKey::AdminRequiredUninstall),
+        )
+        .into());
     }
 
     let service_name_hstring = windows::core::HSTRING::from(SERVICE_NAME);
@@ -252,9 +1425,9 @@ pub fn uninstall_service() -> Result<(), Box<dyn std::error::Error>> {
             Ok(handle) => handle,
             // サービスが存在しないエラーの場合は、アンインストール済みとみなし正常終了。
             Err(e) if e.code().0 == HRESULT::from(ERROR_SERVICE_DOES_NOT_EXIST).0 => {
-                println!(
+                outln!(
                     "{}",
-                    get_msg("service_not_installed_fmt").replace("{}", SERVICE_NAME)
+                    format_msg!(MessageKey::ServiceNotInstalledFmt, name = SERVICE_NAME)
                 );
                 let _ = CloseServiceHandle(scm_handle);
                 return Ok(());
@@ -263,13 +1436,13 @@ pub fn uninstall_service() -> Result<(), Box<dyn std::error::Error>> {
         };
 
         // サービスが実行中であれば停止する。
-        stop_service(service_handle)?;
+        stop_and_wait(service_handle)?;
 
         // サービスを削除する。
         DeleteService(service_handle)?;
-        println!(
+        outln!(
             "{}",
-            get_msg("service_uninstalled_fmt").replace("{}", SERVICE_NAME)
+            format_msg!(MessageKey::ServiceUninstalledFmt, name = SERVICE_NAME)
         );
 
         // 開いたハンドルをクローズする。エラーは無視。
@@ -283,9 +1456,13 @@ pub fn uninstall_service() -> Result<(), Box<dyn std::error::Error>> {
 /// サービスを再起動する。
 ///
 /// 管理者権限が必要です。
-pub fn restart_service() -> Result<(), Box<dyn std::error::Error>> {
+pub fn restart_service() -> Result<(), AdapterError> {
     if !is_elevated() {
-        return Err(get_msg("admin_required_restart").into());
+        return Err(ExitError::new(
+            ExitReason::AdminRequired,
+            get_msg(MessageKey::AdminRequiredRestart),
+        )
+        .into());
     }
 
     let service_name_hstring = windows::core::HSTRING::from(SERVICE_NAME);
@@ -303,9 +1480,9 @@ pub fn restart_service() -> Result<(), Box<dyn std::error::Error>> {
             Ok(handle) => handle,
             // サービスが存在しないエラーの場合は、メッセージを表示して正常終了。
             Err(e) if e.code().0 == HRESULT::from(ERROR_SERVICE_DOES_NOT_EXIST).0 => {
-                println!(
+                outln!(
                     "{}",
-                    get_msg("service_not_installed_fmt").replace("{}", SERVICE_NAME)
+                    format_msg!(MessageKey::ServiceNotInstalledFmt, name = SERVICE_NAME)
                 );
                 let _ = CloseServiceHandle(scm_handle);
                 return Ok(());
@@ -314,42 +1491,263 @@ pub fn restart_service() -> Result<(), Box<dyn std::error::Error>> {
         };
 
         // サービスを停止し、その後開始する。
-        stop_service(service_handle)?;
-        StartServiceW(service_handle, None)?;
-        println!("{}", get_msg("service_restarted_successfully"));
+        // 開始に失敗した場合も、開いたハンドルはクローズする。
+        let result = stop_and_wait(service_handle)
+            .map_err(Into::into)
+            .and_then(|()| start_and_wait(service_handle));
 
         // 開いたハンドルをクローズする。エラーは無視。
         let _ = CloseServiceHandle(service_handle);
         let _ = CloseServiceHandle(scm_handle);
+        result?;
     }
+    outln!("{}", get_msg(MessageKey::ServiceRestartedSuccessfully));
 
     Ok(())
 }
 
-/// 現在のプロセスが管理者権限で実行されているかどうかを判定します。
+/// インストール済みのサービスを開始し、開始処理が終わるまで待つ。
 ///
-/// SCMへのフルアクセスを試みることで、権限の有無を簡易的にチェックします。
-fn is_elevated() -> bool {
+/// 管理者権限が必要です。既に実行中の場合は、その旨を表示して正常終了します。
+pub fn start_service() -> Result<(), AdapterError> {
+    if !is_elevated() {
+        return Err(ExitError::new(
+            ExitReason::AdminRequired,
+            get_msg(MessageKey::AdminRequiredStart),
+        )
+        .into());
+    }
+    // Win32 APIを呼び出すため、unsafeブロックを使用する。
+    // 開いたハンドルは、結果にかかわらずクローズする。
     unsafe {
-        match OpenSCManagerW(None, None, SC_MANAGER_ALL_ACCESS) {
-            Ok(handle) => {
-                // SCMハンドルが正常に開けた場合、管理者権限があると判断できる。
-                // 開いたハンドルは必ずクローズする。
-                let _ = CloseServiceHandle(handle);
-                true
-            }
-            Err(_) => {
-                // ハンドルが開けなかった場合（通常はアクセス拒否エラー）、管理者権限がないと判断する。
-                false
+        let Some((scm_handle, service_handle)) =
+            open_service(SERVICE_START | SERVICE_QUERY_STATUS)?
+        else {
+            return Ok(());
+        };
+        let result = start_and_wait(service_handle);
+        let _ = CloseServiceHandle(service_handle);
+        let _ = CloseServiceHandle(scm_handle);
+        result
+    }
+}
+
+/// 実行中のサービスを停止し、停止するまで待つ。アンインストールはしない。
+///
+/// 管理者権限が必要です。既に停止している場合は、その旨を表示して正常終了します。
+pub fn stop_service() -> Result<(), AdapterError> {
+    if !is_elevated() {
+        return Err(ExitError::new(
+            ExitReason::AdminRequired,
+            get_msg(MessageKey::AdminRequiredStop),
+        )
+        .into());
+    }
+    // Win32 APIを呼び出すため、unsafeブロックを使用する。
+    // 開いたハンドルは、結果にかかわらずクローズする。
+    unsafe {
+        let Some((scm_handle, service_handle)) = open_service(SERVICE_STOP | SERVICE_QUERY_STATUS)?
+        else {
+            return Ok(());
+        };
+        let result = stop_and_wait(service_handle);
+        let _ = CloseServiceHandle(service_handle);
+        let _ = CloseServiceHandle(scm_handle);
+        Ok(result?)
+    }
+}
+
+/// 実行中のサービスに、定期通知を待たずにすぐ通知するよう要求する。
+///
+/// ユーザー定義の制御コード（`SERVICE_CONTROL_KICK`）を送るため、既定のアクセス許可では管理者権限は不要です。
+/// 制御コードを送れない場合（以前のバージョンのサービスや、サービスのアクセス許可が変更されている場合など）は、
+/// 実行時状態を介して要求し、サービスの次のポーリングで処理させます。
+pub fn kick_service() -> Result<(), AdapterError> {
+    let service_name_hstring = windows::core::HSTRING::from(SERVICE_NAME);
+    // Win32 APIを呼び出すため、unsafeブロックを使用する。
+    // 開いたハンドルは、結果にかかわらずクローズする。
+    let sent = unsafe {
+        OpenSCManagerW(None, None, SC_MANAGER_CONNECT).and_then(|scm_handle| {
+            let result = OpenServiceW(
+                scm_handle,
+                &service_name_hstring,
+                SERVICE_USER_DEFINED_CONTROL,
+            )
+            .and_then(|service_handle| {
+                let mut service_status: SERVICE_STATUS = std::mem::zeroed();
+                let result =
+                    ControlService(service_handle, SERVICE_CONTROL_KICK, &mut service_status);
+                let _ = CloseServiceHandle(service_handle);
+                result
+            });
+            let _ = CloseServiceHandle(scm_handle);
+            result
+        })
+    };
+    if sent.is_ok() {
+        return Ok(());
+    }
+    match query_service_state()? {
+        Some(state) if state == SERVICE_RUNNING || state == SERVICE_PAUSED => {
+            update_state(|s| s.kick_requested = true)?;
+            Ok(())
+        }
+        Some(_) => Err(get_msg(MessageKey::ServiceNotRunning).into()),
+        None => Err(format_msg!(MessageKey::ServiceNotInstalledFmt, name = SERVICE_NAME).into()),
+    }
+}
+
+/// SCMに接続し、指定したアクセス権でサービスを開く。
+///
+/// サービスがインストールされていない場合は、その旨を表示して `None` を返す。
+/// 返されたハンドル（SCM、サービスの順）は、呼び出し側でクローズする。
+unsafe fn open_service(access: u32) -> windows::core::Result<Option<(SC_HANDLE, SC_HANDLE)>> {
+    let service_name_hstring = windows::core::HSTRING::from(SERVICE_NAME);
+    unsafe {
+        let scm_handle = OpenSCManagerW(None, None, SC_MANAGER_CONNECT)?;
+        match OpenServiceW(scm_handle, &service_name_hstring, access) {
+            Ok(service_handle) => Ok(Some((scm_handle, service_handle))),
+            Err(e) => {
+                let _ = CloseServiceHandle(scm_handle);
+                if e.code().0 == HRESULT::from(ERROR_SERVICE_DOES_NOT_EXIST).0 {
+                    outln!(
+                        "{}",
+                        format_msg!(MessageKey::ServiceNotInstalledFmt, name = SERVICE_NAME)
+                    );
+                    Ok(None)
+                } else {
+                    Err(e)
+                }
             }
         }
     }
 }
 
+/// サービスの現在の状態を表示する。
+///
+/// 状態を問い合わせるだけのため、管理者権限は不要です。
+///
+/// # 戻り値
+/// サービスが実行中の場合に `true`。インストールされていない場合は `false`。
+pub fn service_status() -> Result<bool, AdapterError> {
+    let Some(state) = query_service_state()? else {
+        // サービスが存在しない場合は、メッセージを表示して正常終了。
+        println!(
+            "{}",
+            format_msg!(MessageKey::ServiceNotInstalledFmt, name = SERVICE_NAME)
+        );
+        return Ok(false);
+    };
+    println!(
+        "{}",
+        format_msg!(
+            MessageKey::ServiceStatusFmt,
+            name = SERVICE_NAME,
+            state = state_text(state)
+        )
+    );
+    Ok(state == SERVICE_RUNNING)
+}
+
+/// サービスの現在の状態をSCMに問い合わせます。
+///
+/// # 戻り値
+/// サービスの状態。インストールされていない場合は `None`。
+pub fn query_service_state() -> windows::core::Result<Option<SERVICE_STATUS_CURRENT_STATE>> {
+    let service_name_hstring = windows::core::HSTRING::from(SERVICE_NAME);
+
+    unsafe {
+        // Win32 APIを呼び出すため、unsafeブロックを使用する。
+        // 各APIの引数はドキュメントに従って正しく設定されており、ハンドルは適切にクローズされるため安全。
+        let scm_handle = OpenSCManagerW(None, None, SC_MANAGER_CONNECT)?;
+
+        let service_handle =
+            match OpenServiceW(scm_handle, &service_name_hstring, SERVICE_QUERY_STATUS) {
+                Ok(handle) => handle,
+                Err(e) if e.code().0 == HRESULT::from(ERROR_SERVICE_DOES_NOT_EXIST).0 => {
+                    let _ = CloseServiceHandle(scm_handle);
+                    return Ok(None);
+                }
+                Err(e) => return Err(e),
+            };
+
+        let mut service_status: SERVICE_STATUS = std::mem::zeroed();
+        let result = QueryServiceStatus(service_handle, &mut service_status);
+
+        // 開いたハンドルをクローズする。エラーは無視。
+        let _ = CloseServiceHandle(service_handle);
+        let _ = CloseServiceHandle(scm_handle);
+
+        result?;
+        Ok(Some(service_status.dwCurrentState))
+    }
+}
+
+/// 実行中のサービスのプロセスIDをSCMに問い合わせます。
+///
+/// # 戻り値
+/// サービスのプロセスID。インストールされていない場合や、実行されていない場合は `None`。
+pub fn query_service_pid() -> windows::core::Result<Option<u32>> {
+    let service_name_hstring = windows::core::HSTRING::from(SERVICE_NAME);
+
+    unsafe {
+        // Win32 APIを呼び出すため、unsafeブロックを使用する。
+        // バッファは `SERVICE_STATUS_PROCESS` の大きさで確保しており、ハンドルは適切にクローズされるため安全。
+        let scm_handle = OpenSCManagerW(None, None, SC_MANAGER_CONNECT)?;
+
+        let service_handle =
+            match OpenServiceW(scm_handle, &service_name_hstring, SERVICE_QUERY_STATUS) {
+                Ok(handle) => handle,
+                Err(e) if e.code().0 == HRESULT::from(ERROR_SERVICE_DOES_NOT_EXIST).0 => {
+                    let _ = CloseServiceHandle(scm_handle);
+                    return Ok(None);
+                }
+                Err(e) => return Err(e),
+            };
+
+        let mut service_status: SERVICE_STATUS_PROCESS = std::mem::zeroed();
+        let mut needed = 0;
+        let result = QueryServiceStatusEx(
+            service_handle,
+            SC_STATUS_PROCESS_INFO,
+            Some(std::slice::from_raw_parts_mut(
+                (&mut service_status as *mut SERVICE_STATUS_PROCESS).cast::<u8>(),
+                size_of::<SERVICE_STATUS_PROCESS>(),
+            )),
+            &mut needed,
+        );
+
+        // 開いたハンドルをクローズする。エラーは無視。
+        let _ = CloseServiceHandle(service_handle);
+        let _ = CloseServiceHandle(scm_handle);
+
+        result?;
+        // 停止しているサービスのプロセスIDは0になる。
+        Ok(Some(service_status.dwProcessId).filter(|&pid| pid != 0))
+    }
+}
+
+/// サービスの状態を、色を付けた表示用の文字列に変換します。
+///
+/// 実行中は緑、停止は赤、処理中や一時停止などそれ以外の状態は黄色で表示します。
+pub fn state_text(state: SERVICE_STATUS_CURRENT_STATE) -> String {
+    let (key, color) = match state {
+        SERVICE_RUNNING => (MessageKey::ServiceStateRunning, Color::Green),
+        SERVICE_STOPPED => (MessageKey::ServiceStateStopped, Color::Red),
+        SERVICE_START_PENDING => (MessageKey::ServiceStateStartPending, Color::Yellow),
+        SERVICE_STOP_PENDING => (MessageKey::ServiceStateStopPending, Color::Yellow),
+        SERVICE_PAUSED => (MessageKey::ServiceStatePaused, Color::Yellow),
+        SERVICE_PAUSE_PENDING => (MessageKey::ServiceStatePausePending, Color::Yellow),
+        SERVICE_CONTINUE_PENDING => (MessageKey::ServiceStateContinuePending, Color::Yellow),
+        _ => (MessageKey::ServiceStateUnknown, Color::Yellow),
+    };
+    paint(get_msg(key), color)
+}
+
 /// 指定されたサービスハンドルに対応するサービスを停止するヘルパー関数。
 ///
 /// サービスが完全に停止するまで待機します。
-unsafe fn stop_service(service_handle: SC_HANDLE) -> windows::core::Result<()> {
+unsafe fn stop_and_wait(service_handle: SC_HANDLE) -> windows::core::Result<()> {
     unsafe {
         // サービスの状態を受け取るための構造体。
         let mut service_status: SERVICE_STATUS = std::mem::zeroed();
@@ -357,9 +1755,9 @@ unsafe fn stop_service(service_handle: SC_HANDLE) -> windows::core::Result<()> {
         match ControlService(service_handle, SERVICE_CONTROL_STOP, &mut service_status) {
             Ok(()) => {
                 // 停止コマンドが受け入れられた場合
-                println!(
+                outln!(
                     "{}",
-                    get_msg("service_stopping_fmt").replace("{}", SERVICE_NAME)
+                    format_msg!(MessageKey::ServiceStoppingFmt, name = SERVICE_NAME)
                 );
                 // サービスが完全に停止するのを待つループ。
                 loop {
@@ -367,20 +1765,83 @@ unsafe fn stop_service(service_handle: SC_HANDLE) -> windows::core::Result<()> {
                     QueryServiceStatus(service_handle, &mut service_status)?;
                     // 状態が `SERVICE_STOPPED` になったらループを抜ける。
                     if service_status.dwCurrentState == SERVICE_STOPPED {
-                        println!("{}", get_msg("service_stopped"));
+                        outln!("{}", get_msg(MessageKey::ServiceStopped));
                         break;
                     }
                     // 停止するまで1秒待機する。
-                    println!("{}", get_msg("service_waiting_stop"));
+                    outln!("{}", get_msg(MessageKey::ServiceWaitingStop));
                     thread::sleep(Duration::from_secs(1));
                 }
             }
             // サービスが既に停止している場合はエラーではないので、メッセージを表示して正常終了。
             Err(e) if e.code().0 == HRESULT::from(ERROR_SERVICE_NOT_ACTIVE).0 => {
-                println!("{}", get_msg("service_not_running"));
+                outln!("{}", get_msg(MessageKey::ServiceNotRunning));
             }
             Err(e) => return Err(e),
         }
     }
     Ok(())
 }
+
+/// 指定されたサービスハンドルに対応するサービスを開始するヘルパー関数。
+///
+/// 開始処理が終わるまで（最大 `START_TIMEOUT`）待機し、実行中にならずに停止した場合はエラーを返します。
+/// 実行中になった後も `START_SETTLE` の間は状態を確認し、すぐに停止した場合はエラーを返します。
+/// エラーには、サービスが報告した終了コードを含めます。
+unsafe fn start_and_wait(service_handle: SC_HANDLE) -> Result<(), AdapterError> {
+    unsafe {
+        match StartServiceW(service_handle, None) {
+            Ok(()) => outln!(
+                "{}",
+                format_msg!(MessageKey::ServiceStartingFmt, name = SERVICE_NAME)
+            ),
+            // サービスが既に実行中の場合はエラーではないので、メッセージを表示して正常終了。
+            Err(e) if e.code().0 == HRESULT::from(ERROR_SERVICE_ALREADY_RUNNING).0 => {
+                outln!("{}", get_msg(MessageKey::ServiceAlreadyRunning));
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        }
+        // サービスの開始処理が終わり、実行中のまま安定するのを待つループ。
+        let started = std::time::Instant::now();
+        let mut running_since = None;
+        let mut service_status: SERVICE_STATUS = std::mem::zeroed();
+        loop {
+            QueryServiceStatus(service_handle, &mut service_status)?;
+            match service_status.dwCurrentState {
+                SERVICE_RUNNING => {
+                    let since = *running_since.get_or_insert_with(std::time::Instant::now);
+                    if since.elapsed() >= START_SETTLE {
+                        outln!("{}", get_msg(MessageKey::ServiceStarted));
+                        return Ok(());
+                    }
+                }
+                // 停止した場合は、サービスが報告した終了コードを表示する。
+                SERVICE_STOPPED => {
+                    let key = if running_since.is_some() {
+                        MessageKey::ServiceStoppedAfterStartFmt
+                    } else {
+                        MessageKey::ServiceStartFailedFmt
+                    };
+                    return Err(format_msg!(key, code = exit_code_text(&service_status)).into());
+                }
+                _ if started.elapsed() >= START_TIMEOUT => {
+                    return Err(get_msg(MessageKey::ServiceStartTimeout).into());
+                }
+                _ => {}
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+    }
+}
+
+/// サービスが報告した終了コードを、表示用の文字列に変換します。
+///
+/// サービス固有の終了コードが報告されている場合は、そちらを表示します。
+fn exit_code_text(status: &SERVICE_STATUS) -> String {
+    if status.dwWin32ExitCode == ERROR_SERVICE_SPECIFIC_ERROR.0 {
+        status.dwServiceSpecificExitCode.to_string()
+    } else {
+        status.dwWin32ExitCode.to_string()
+    }
+}